@@ -19,7 +19,10 @@ use crate::error::CliError;
 use crate::http::submit_batches;
 use crate::transaction::{pike_batch_builder, PIKE_NAMESPACE};
 use grid_sdk::{
-    protocol::pike::payload::{Action, CreateAgentAction, PikePayloadBuilder, UpdateAgentAction},
+    protocol::pike::payload::{
+        Action, CreateAgentAction, DeleteAgentAction, PikePayloadBuilder, RotateAgentKeyAction,
+        UpdateAgentAction,
+    },
     protos::IntoProto,
 };
 
@@ -70,3 +73,51 @@ pub fn do_update_agent(
 
     submit_batches(url, wait, &batch_list, service_id.as_deref())
 }
+
+pub fn do_delete_agent(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    delete_agent: DeleteAgentAction,
+    service_id: Option<String>,
+) -> Result<(), CliError> {
+    let payload = PikePayloadBuilder::new()
+        .with_action(Action::DeleteAgent)
+        .with_delete_agent(delete_agent)
+        .build()
+        .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+    let batch_list = pike_batch_builder(key)
+        .add_transaction(
+            &payload.into_proto()?,
+            &[PIKE_NAMESPACE.to_string()],
+            &[PIKE_NAMESPACE.to_string()],
+        )?
+        .create_batch_list();
+
+    submit_batches(url, wait, &batch_list, service_id.as_deref())
+}
+
+pub fn do_rotate_agent_key(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    rotate_agent_key: RotateAgentKeyAction,
+    service_id: Option<String>,
+) -> Result<(), CliError> {
+    let payload = PikePayloadBuilder::new()
+        .with_action(Action::RotateAgentKey)
+        .with_rotate_agent_key(rotate_agent_key)
+        .build()
+        .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+    let batch_list = pike_batch_builder(key)
+        .add_transaction(
+            &payload.into_proto()?,
+            &[PIKE_NAMESPACE.to_string()],
+            &[PIKE_NAMESPACE.to_string()],
+        )?
+        .create_batch_list();
+
+    submit_batches(url, wait, &batch_list, service_id.as_deref())
+}
@@ -16,7 +16,11 @@ use crate::error::CliError;
 
 use diesel::{connection::Connection as _, pg::PgConnection};
 
-use grid_sdk::grid_db::migrations::run_postgres_migrations;
+use grid_sdk::grid_db::{
+    migrations::{clear_postgres_database, run_postgres_migrations},
+    CommitStore as _,
+};
+use grid_sdk::store::{create_store_factory, ConnectionUri};
 
 pub fn run_migrations(database_url: &str) -> Result<(), CliError> {
     let connection = PgConnection::establish(database_url)
@@ -28,3 +32,52 @@ pub fn run_migrations(database_url: &str) -> Result<(), CliError> {
 
     Ok(())
 }
+
+pub fn resync(database_url: &str) -> Result<(), CliError> {
+    let connection = PgConnection::establish(database_url)
+        .map_err(|err| CliError::DatabaseError(err.to_string()))?;
+
+    clear_postgres_database(&connection).map_err(|err| CliError::DatabaseError(err.to_string()))?;
+
+    info!("Removed all commits; gridd will process each source from the beginning");
+
+    Ok(())
+}
+
+pub fn list_checkpoints(database_url: &str) -> Result<(), CliError> {
+    let checkpoints = create_store_factory(&parse_database_url(database_url)?)
+        .map_err(|err| CliError::DatabaseError(err.to_string()))?
+        .get_grid_commit_store()
+        .list_checkpoints()
+        .map_err(|err| CliError::DatabaseError(err.to_string()))?;
+
+    println!("{:<40} {:<12} COMMIT ID", "SOURCE", "COMMIT NUM");
+    checkpoints.iter().for_each(|checkpoint| {
+        println!(
+            "{:<40} {:<12} {}",
+            checkpoint.service_id.as_deref().unwrap_or("sawtooth"),
+            checkpoint.commit_num,
+            checkpoint.commit_id
+        )
+    });
+
+    Ok(())
+}
+
+pub fn reset_checkpoint(database_url: &str, service_id: Option<&str>) -> Result<(), CliError> {
+    create_store_factory(&parse_database_url(database_url)?)
+        .map_err(|err| CliError::DatabaseError(err.to_string()))?
+        .get_grid_commit_store()
+        .reset_checkpoint(service_id)
+        .map_err(|err| CliError::DatabaseError(err.to_string()))?;
+
+    info!("Reset checkpoint for {}", service_id.unwrap_or("sawtooth"));
+
+    Ok(())
+}
+
+fn parse_database_url(database_url: &str) -> Result<ConnectionUri, CliError> {
+    database_url
+        .parse::<ConnectionUri>()
+        .map_err(|err| CliError::UserError(err.to_string()))
+}
@@ -22,4 +22,5 @@ pub mod database;
 pub mod keygen;
 pub mod organizations;
 pub mod products;
+pub mod roles;
 pub mod schemas;
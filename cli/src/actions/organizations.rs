@@ -20,7 +20,8 @@ use crate::http::submit_batches;
 use crate::transaction::{pike_batch_builder, PIKE_NAMESPACE};
 use grid_sdk::{
     protocol::pike::payload::{
-        Action, CreateOrganizationAction, PikePayloadBuilder, UpdateOrganizationAction,
+        Action, CreateOrganizationAction, DeleteOrganizationAction, PikePayloadBuilder,
+        UpdateOrganizationAction,
     },
     protos::IntoProto,
 };
@@ -72,3 +73,27 @@ pub fn do_update_organization(
 
     submit_batches(url, wait, &batch_list, service_id.as_deref())
 }
+
+pub fn do_delete_organization(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    delete_org: DeleteOrganizationAction,
+    service_id: Option<String>,
+) -> Result<(), CliError> {
+    let payload = PikePayloadBuilder::new()
+        .with_action(Action::DeleteOrganization)
+        .with_delete_organization(delete_org)
+        .build()
+        .map_err(|err| CliError::UserError(format!("{}", err)))?;
+
+    let batch_list = pike_batch_builder(key)
+        .add_transaction(
+            &payload.into_proto()?,
+            &[PIKE_NAMESPACE.to_string()],
+            &[PIKE_NAMESPACE.to_string()],
+        )?
+        .create_batch_list();
+
+    submit_batches(url, wait, &batch_list, service_id.as_deref())
+}
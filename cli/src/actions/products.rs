@@ -19,9 +19,10 @@ use crate::transaction::{
 };
 use grid_sdk::protocol::product::payload::{
     Action, ProductCreateAction, ProductCreateActionBuilder, ProductDeleteAction,
-    ProductPayloadBuilder, ProductUpdateAction, ProductUpdateActionBuilder,
+    ProductPayloadBuilder, ProductSetStatusAction, ProductTransferAction, ProductUpdateAction,
+    ProductUpdateActionBuilder,
 };
-use grid_sdk::protocol::product::state::ProductNamespace;
+use grid_sdk::protocol::product::state::{ProductNamespace, ProductStatus};
 use grid_sdk::protocol::schema::state::{LatLongBuilder, PropertyValue, PropertyValueBuilder};
 use grid_sdk::protos::IntoProto;
 use reqwest::Client;
@@ -40,7 +41,13 @@ use std::{
 pub struct GridProduct {
     pub product_id: String,
     pub product_namespace: String,
+    #[serde(default)]
+    pub schema_name: String,
     pub owner: String,
+    #[serde(default)]
+    pub pending_owner: String,
+    #[serde(default)]
+    pub status: String,
     pub properties: Vec<GridPropertyValue>,
 }
 
@@ -70,9 +77,19 @@ pub struct LatLong {
  */
 pub fn display_product(product: &GridProduct) {
     println!(
-        "Product Id: {:?}\n Product Namespace: {:?}\n Owner: {:?}\n Properties:",
+        "Product Id: {:?}\n Product Namespace: {:?}\n Owner: {:?}",
         product.product_id, product.product_namespace, product.owner,
     );
+    if !product.schema_name.is_empty() {
+        println!(" Schema: {:?}", product.schema_name);
+    }
+    if !product.pending_owner.is_empty() {
+        println!(" Offered To: {:?}", product.pending_owner);
+    }
+    if !product.status.is_empty() {
+        println!(" Status: {:?}", product.status);
+    }
+    println!(" Properties:");
     display_product_property_definitions(&product.properties);
 }
 
@@ -171,18 +188,89 @@ pub fn do_delete_products(
     )
 }
 
+/**
+ * Offer a product to another organization, or accept or cancel a pending offer
+ *
+ * url - Url for the REST API
+ * key - Signing key of the agent
+ * wait - Time in seconds to wait for commit
+ * action - The phase of the transfer to submit
+ */
+pub fn do_transfer_product(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    action: ProductTransferAction,
+    service_id: Option<String>,
+) -> Result<(), CliError> {
+    submit_payloads(
+        url,
+        key,
+        wait,
+        vec![Action::ProductTransfer(action)],
+        service_id.as_deref(),
+    )
+}
+
+/**
+ * Move a product to another lifecycle state
+ *
+ * url - Url for the REST API
+ * key - Signing key of the agent
+ * wait - Time in seconds to wait for commit
+ * action - The product and the status to move it to
+ */
+pub fn do_set_product_status(
+    url: &str,
+    key: Option<String>,
+    wait: u64,
+    action: ProductSetStatusAction,
+    service_id: Option<String>,
+) -> Result<(), CliError> {
+    submit_payloads(
+        url,
+        key,
+        wait,
+        vec![Action::ProductSetStatus(action)],
+        service_id.as_deref(),
+    )
+}
+
 /**
  * Print all products in state
  *
  * url - Url for the REST API
+ * namespace - Only print products of this namespace
+ * schema_name - Only print CUSTOM products that follow this schema
+ * status - Only print products in this lifecycle state
  */
-pub fn do_list_products(url: &str, service_id: Option<String>) -> Result<(), CliError> {
+pub fn do_list_products(
+    url: &str,
+    namespace: Option<ProductNamespace>,
+    schema_name: Option<&str>,
+    status: Option<ProductStatus>,
+    service_id: Option<String>,
+) -> Result<(), CliError> {
     let client = Client::new();
-    let mut final_url = format!("{}/product", url);
+    let final_url = format!("{}/product", url);
+    let mut query = Vec::new();
+    if let Some(namespace) = namespace {
+        query.push(("product_namespace", format!("{:?}", namespace)));
+    }
+    if let Some(schema_name) = schema_name {
+        query.push(("schema_name", schema_name.to_string()));
+    }
+    if let Some(status) = status {
+        query.push(("status", status.to_string()));
+    }
     if let Some(service_id) = service_id {
-        final_url = format!("{}?service_id={}", final_url, service_id);
+        query.push(("service_id", service_id));
     }
-    let products = client.get(&final_url).send()?.json::<Vec<GridProduct>>()?;
+    let products = client
+        .get(&final_url)
+        .query(&query)
+        .send()?
+        .json::<Vec<GridProduct>>()?;
     products.iter().for_each(|product| display_product(product));
     Ok(())
 }
@@ -192,18 +280,30 @@ pub fn do_list_products(url: &str, service_id: Option<String>) -> Result<(), Cli
  *
  * url - Url for the REST API
  * product_id - e.g. GTIN
+ * namespace - Namespace of the product
+ * schema_name - Schema a CUSTOM product follows
  */
 pub fn do_show_products(
     url: &str,
     product_id: &str,
+    namespace: ProductNamespace,
+    schema_name: &str,
     service_id: Option<String>,
 ) -> Result<(), CliError> {
     let client = Client::new();
-    let mut final_url = format!("{}/product/{}", url, product_id);
+    let final_url = format!("{}/product/{}", url, product_id);
+    let mut query = vec![("product_namespace", format!("{:?}", namespace))];
+    if namespace == ProductNamespace::Custom {
+        query.push(("schema_name", schema_name.to_string()));
+    }
     if let Some(service_id) = service_id {
-        final_url = format!("{}?service_id={}", final_url, service_id);
+        query.push(("service_id", service_id));
     }
-    let product = client.get(&final_url).send()?.json::<GridProduct>()?;
+    let product = client
+        .get(&final_url)
+        .query(&query)
+        .send()?
+        .json::<GridProduct>()?;
     display_product(&product);
     Ok(())
 }
@@ -219,10 +319,7 @@ pub fn create_product_payloads_from_file(
     let mut payloads = Vec::new();
 
     for yml in ymls {
-        let namespace = match yml.product_namespace {
-            Namespace::GS1 => "gs1_product",
-        };
-        let schema = get_schema(url, namespace, service_id)?;
+        let schema = get_schema(url, yml.schema_name()?, service_id)?;
         payloads.push(yml.into_payload(schema.properties)?);
     }
 
@@ -240,10 +337,7 @@ pub fn update_product_payloads_from_file(
     let mut payloads = Vec::new();
 
     for yml in ymls {
-        let namespace = match yml.product_namespace {
-            Namespace::GS1 => "gs1_product",
-        };
-        let schema = get_schema(url, namespace, service_id)?;
+        let schema = get_schema(url, yml.schema_name()?, service_id)?;
         payloads.push(yml.into_payload(schema.properties)?);
     }
 
@@ -255,19 +349,27 @@ pub struct ProductCreateYaml {
     product_id: String,
     owner: String,
     product_namespace: Namespace,
+    #[serde(default)]
+    schema_name: String,
     properties: HashMap<String, serde_yaml::Value>,
 }
 
 impl ProductCreateYaml {
+    fn schema_name(&self) -> Result<&str, CliError> {
+        yaml_schema_name(&self.product_namespace, &self.schema_name)
+    }
+
     pub fn into_payload(
         self,
         definitions: Vec<GridPropertyDefinitionSlice>,
     ) -> Result<ProductCreateAction, CliError> {
-        let property_values = yaml_to_property_values(&self.properties, definitions)?;
+        let property_values = yaml_to_property_values(&self.properties, definitions.clone())?;
+        schemas::validate_property_values(&definitions, &property_values)?;
         ProductCreateActionBuilder::new()
             .with_product_id(self.product_id)
             .with_owner(self.owner)
             .with_product_namespace(self.product_namespace.into())
+            .with_schema_name(self.schema_name)
             .with_properties(property_values)
             .build()
             .map_err(|err| CliError::PayloadError(format!("{}", err)))
@@ -278,24 +380,51 @@ impl ProductCreateYaml {
 pub struct ProductUpdateYaml {
     product_id: String,
     product_namespace: Namespace,
+    #[serde(default)]
+    schema_name: String,
     properties: HashMap<String, serde_yaml::Value>,
 }
 
 impl ProductUpdateYaml {
+    fn schema_name(&self) -> Result<&str, CliError> {
+        yaml_schema_name(&self.product_namespace, &self.schema_name)
+    }
+
     pub fn into_payload(
         self,
         definitions: Vec<GridPropertyDefinitionSlice>,
     ) -> Result<ProductUpdateAction, CliError> {
-        let property_values = yaml_to_property_values(&self.properties, definitions)?;
+        let property_values = yaml_to_property_values(&self.properties, definitions.clone())?;
+        schemas::validate_property_values(&definitions, &property_values)?;
         ProductUpdateActionBuilder::new()
             .with_product_id(self.product_id)
             .with_product_namespace(self.product_namespace.into())
+            .with_schema_name(self.schema_name)
             .with_properties(property_values)
             .build()
             .map_err(|err| CliError::PayloadError(format!("{}", err)))
     }
 }
 
+/// Returns the name of the schema a product's properties are defined by: `gs1_product` for GS1
+/// products, or the schema a CUSTOM product names
+pub fn product_schema_name<'a>(namespace: &ProductNamespace, schema_name: &'a str) -> &'a str {
+    match namespace {
+        ProductNamespace::GS1 => "gs1_product",
+        ProductNamespace::Custom => schema_name,
+    }
+}
+
+fn yaml_schema_name<'a>(namespace: &Namespace, schema_name: &'a str) -> Result<&'a str, CliError> {
+    match namespace {
+        Namespace::GS1 => Ok("gs1_product"),
+        Namespace::Custom if schema_name.is_empty() => Err(CliError::PayloadError(
+            "CUSTOM namespace products require a schema_name".into(),
+        )),
+        Namespace::Custom => Ok(schema_name),
+    }
+}
+
 fn yaml_to_property_values(
     properties: &HashMap<String, serde_yaml::Value>,
     definitions: Vec<GridPropertyDefinitionSlice>,
@@ -409,6 +538,63 @@ fn yaml_to_property_values(
 
                 property_values.push(property_value);
             }
+            schemas::DataType::Timestamp => {
+                let property_value = PropertyValueBuilder::new()
+                    .with_name(def.name.clone())
+                    .with_data_type(def.data_type.into())
+                    .with_timestamp_value(serde_yaml::from_value(value.clone())?)
+                    .build()
+                    .map_err(|err| CliError::PayloadError(format!("{}", err)))?;
+                property_values.push(property_value);
+            }
+            schemas::DataType::Decimal => {
+                // Decimals may be quoted to keep trailing zeros
+                let decimal = match value {
+                    serde_yaml::Value::String(decimal) => decimal.clone(),
+                    serde_yaml::Value::Number(decimal) => decimal.to_string(),
+                    _ => {
+                        return Err(CliError::PayloadError(format!(
+                            "{:?} is not a valid decimal",
+                            value
+                        )))
+                    }
+                };
+
+                let property_value = PropertyValueBuilder::new()
+                    .with_name(def.name.clone())
+                    .with_data_type(def.data_type.into())
+                    .with_decimal_value(
+                        decimal
+                            .parse()
+                            .map_err(|err| CliError::PayloadError(format!("{}", err)))?,
+                    )
+                    .build()
+                    .map_err(|err| CliError::PayloadError(format!("{}", err)))?;
+                property_values.push(property_value);
+            }
+            schemas::DataType::List => {
+                let element_definition = def.list_element_definition().ok_or_else(|| {
+                    CliError::PayloadError(format!("List {} has no element data type", def.name))
+                })?;
+
+                let mut list_values = Vec::new();
+                for element in serde_yaml::from_value::<Vec<serde_yaml::Value>>(value.clone())? {
+                    let mut element_property = HashMap::new();
+                    element_property.insert(def.name.clone(), element);
+                    list_values.append(&mut yaml_to_property_values(
+                        &element_property,
+                        vec![element_definition.clone()],
+                    )?);
+                }
+
+                let property_value = PropertyValueBuilder::new()
+                    .with_name(def.name.clone())
+                    .with_data_type(def.data_type.into())
+                    .with_list_values(list_values)
+                    .build()
+                    .map_err(|err| CliError::PayloadError(format!("{}", err)))?;
+                property_values.push(property_value);
+            }
         }
     }
 
@@ -455,12 +641,15 @@ fn submit_payloads(
 #[derive(Deserialize, Debug)]
 pub enum Namespace {
     GS1,
+    #[serde(rename = "CUSTOM")]
+    Custom,
 }
 
 impl Into<ProductNamespace> for Namespace {
     fn into(self) -> ProductNamespace {
         match self {
             Namespace::GS1 => ProductNamespace::GS1,
+            Namespace::Custom => ProductNamespace::Custom,
         }
     }
 }
@@ -469,6 +658,7 @@ impl Into<String> for Namespace {
     fn into(self) -> String {
         match self {
             Namespace::GS1 => "GS1".to_string(),
+            Namespace::Custom => "CUSTOM".to_string(),
         }
     }
 }
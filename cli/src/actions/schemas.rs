@@ -15,16 +15,19 @@
 use crate::http::submit_batches;
 use crate::transaction::{schema_batch_builder, GRID_SCHEMA_NAMESPACE, PIKE_NAMESPACE};
 use crate::yaml_parser::{
-    parse_value_as_boolean, parse_value_as_data_type, parse_value_as_i32, parse_value_as_sequence,
-    parse_value_as_string, parse_value_as_vec_string,
+    parse_value_as_boolean, parse_value_as_data_type, parse_value_as_i32, parse_value_as_i64,
+    parse_value_as_sequence, parse_value_as_string, parse_value_as_u32, parse_value_as_vec_string,
 };
 use grid_sdk::protocol::schema::payload::{
-    Action, SchemaCreateAction, SchemaCreateBuilder, SchemaPayload, SchemaPayloadBuilder,
-    SchemaUpdateAction, SchemaUpdateBuilder,
+    Action, PropertyUpdate, PropertyUpdateBuilder, SchemaCreateAction, SchemaCreateBuilder,
+    SchemaPayload, SchemaPayloadBuilder, SchemaUpdateAction, SchemaUpdateBuilder, UpdateType,
 };
 use grid_sdk::protocol::schema::state::{
-    DataType as StateDataType, PropertyDefinition, PropertyDefinitionBuilder,
+    DataType as StateDataType, IdentifierFormat, IdentifierFormatBuilder, LatLong, LatLongBuilder,
+    PropertyConstraints, PropertyConstraintsBuilder, PropertyDefinition, PropertyDefinitionBuilder,
+    PropertyValue,
 };
+use grid_sdk::protocol::schema::validation::validate_properties;
 use grid_sdk::protos::IntoProto;
 use reqwest::Client;
 
@@ -37,10 +40,12 @@ pub struct GridSchemaSlice {
     pub name: String,
     pub description: String,
     pub owner: String,
+    #[serde(default)]
+    pub version: i32,
     pub properties: Vec<GridPropertyDefinitionSlice>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct GridPropertyDefinitionSlice {
     pub name: String,
     pub schema_name: String,
@@ -50,9 +55,71 @@ pub struct GridPropertyDefinitionSlice {
     pub number_exponent: i64,
     pub enum_options: Vec<String>,
     pub struct_properties: Vec<GridPropertyDefinitionSlice>,
+    #[serde(default)]
+    pub deprecated: bool,
+    #[serde(default)]
+    pub list_data_type: Option<DataType>,
 }
 
-#[derive(Deserialize, Debug)]
+impl GridPropertyDefinitionSlice {
+    /// Returns the definition each element of a LIST property follows, or None if the property
+    /// is not a list
+    pub fn list_element_definition(&self) -> Option<GridPropertyDefinitionSlice> {
+        self.list_data_type
+            .clone()
+            .map(|list_data_type| GridPropertyDefinitionSlice {
+                data_type: list_data_type,
+                required: true,
+                list_data_type: None,
+                ..self.clone()
+            })
+    }
+
+    /// Converts the definition returned by the REST API into the definition the contracts check
+    /// property values against
+    pub fn to_property_definition(&self) -> Result<PropertyDefinition, CliError> {
+        let mut builder = PropertyDefinitionBuilder::new()
+            .with_name(self.name.clone())
+            .with_data_type(self.data_type.clone().into())
+            .with_required(self.required)
+            .with_description(self.description.clone())
+            .with_number_exponent(self.number_exponent as i32)
+            .with_enum_options(self.enum_options.clone())
+            .with_struct_properties(
+                self.struct_properties
+                    .iter()
+                    .map(GridPropertyDefinitionSlice::to_property_definition)
+                    .collect::<Result<_, _>>()?,
+            )
+            .with_deprecated(self.deprecated);
+
+        if let Some(list_data_type) = &self.list_data_type {
+            builder = builder.with_list_data_type(list_data_type.clone().into());
+        }
+
+        builder
+            .build()
+            .map_err(|err| CliError::PayloadError(format!("{}", err)))
+    }
+}
+
+/// Checks property values against the definitions of the schema they follow before they are
+/// submitted, reporting the same errors the contracts would. Constraints are not returned by the
+/// REST API, so they are only checked on-chain.
+pub fn validate_property_values(
+    definitions: &[GridPropertyDefinitionSlice],
+    values: &[PropertyValue],
+) -> Result<(), CliError> {
+    let definitions = definitions
+        .iter()
+        .map(GridPropertyDefinitionSlice::to_property_definition)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    validate_properties(&definitions, values)
+        .map_err(|err| CliError::PayloadError(format!("{}", err)))
+}
+
+#[derive(Deserialize, Debug, Clone)]
 pub enum DataType {
     Bytes,
     Boolean,
@@ -61,6 +128,9 @@ pub enum DataType {
     Enum,
     Struct,
     LatLong,
+    Timestamp,
+    Decimal,
+    List,
 }
 
 impl Into<StateDataType> for DataType {
@@ -73,14 +143,17 @@ impl Into<StateDataType> for DataType {
             DataType::Enum => StateDataType::Enum,
             DataType::Struct => StateDataType::Struct,
             DataType::LatLong => StateDataType::LatLong,
+            DataType::Timestamp => StateDataType::Timestamp,
+            DataType::Decimal => StateDataType::Decimal,
+            DataType::List => StateDataType::List,
         }
     }
 }
 
 pub fn display_schema(schema: &GridSchemaSlice) {
     println!(
-        "Name: {:?}\n Description: {:?}\n Owner: {:?}\n Properties:",
-        schema.name, schema.description, schema.owner,
+        "Name: {:?}\n Description: {:?}\n Owner: {:?}\n Version: {:?}\n Properties:",
+        schema.name, schema.description, schema.owner, schema.version,
     );
     display_schema_property_definitions(&schema.properties);
 }
@@ -88,15 +161,18 @@ pub fn display_schema(schema: &GridSchemaSlice) {
 pub fn display_schema_property_definitions(properties: &[GridPropertyDefinitionSlice]) {
     properties.iter().for_each(|def| {
         println!(
-            "\tName: {:?}\n\t Data Type: {:?}\n\t Required: {:?}\n\t Description: {:?}
-        Number Exponent: {:?}\n\t Enum Options: {:?}\n\t Struct Properties: {:?}",
+            "\tName: {:?}\n\t Data Type: {:?}\n\t Required: {:?}\n\t Deprecated: {:?}
+        Description: {:?}\n\t Number Exponent: {:?}\n\t Enum Options: {:?}
+        Struct Properties: {:?}\n\t List Data Type: {:?}",
             def.name,
             def.data_type,
             def.required,
+            def.deprecated,
             def.description,
             def.number_exponent,
             def.enum_options,
             display_schema_property_definitions(&def.struct_properties),
+            def.list_data_type,
         );
     });
 }
@@ -208,11 +284,17 @@ fn parse_yaml(path: &str, action: Action) -> Result<Vec<SchemaPayload>, CliError
                     CliError::InvalidYamlError("Missing `name` field for schema.".to_string())
                 })?;
                 let schema_description = parse_value_as_string(schema_yaml, "description")?;
+                let identifier_format =
+                    match schema_yaml.get(&Value::String("identifier_format".to_string())) {
+                        Some(value) => Some(parse_identifier_format(value)?),
+                        None => None,
+                    };
 
                 generate_create_schema_payload(
                     &schema_name,
                     &property_definitions,
                     schema_description,
+                    identifier_format,
                 )
             })
             .collect::<Result<Vec<SchemaPayload>, _>>(),
@@ -220,18 +302,26 @@ fn parse_yaml(path: &str, action: Action) -> Result<Vec<SchemaPayload>, CliError
         Action::SchemaUpdate(_) => schemas_yaml
             .iter()
             .map(|schema_yaml| {
-                let properties =
-                    parse_value_as_sequence(schema_yaml, "properties")?.ok_or_else(|| {
-                        CliError::InvalidYamlError(
-                            "Schema definition is missing `properties` field.".to_string(),
-                        )
-                    })?;
-                let property_definitions = parse_properties(&properties)?;
+                // An update may add properties, change existing ones, or both
+                let property_definitions = match parse_value_as_sequence(schema_yaml, "properties")?
+                {
+                    Some(properties) => parse_properties(&properties)?,
+                    None => vec![],
+                };
+                let property_updates =
+                    match parse_value_as_sequence(schema_yaml, "property_updates")? {
+                        Some(updates) => parse_property_updates(&updates)?,
+                        None => vec![],
+                    };
                 let schema_name = parse_value_as_string(schema_yaml, "name")?.ok_or_else(|| {
                     CliError::InvalidYamlError("Missing `name` field for schema.".to_string())
                 })?;
 
-                generate_update_schema_payload(&schema_name, &property_definitions)
+                generate_update_schema_payload(
+                    &schema_name,
+                    &property_definitions,
+                    &property_updates,
+                )
             })
             .collect::<Result<Vec<SchemaPayload>, _>>(),
     }
@@ -241,6 +331,7 @@ fn generate_create_schema_payload(
     name: &str,
     properties: &[PropertyDefinition],
     description: Option<String>,
+    identifier_format: Option<IdentifierFormat>,
 ) -> Result<SchemaPayload, CliError> {
     let mut schema_paylod = SchemaPayloadBuilder::new();
 
@@ -253,6 +344,13 @@ fn generate_create_schema_payload(
         None => schema_create_action_builder,
     };
 
+    schema_create_action_builder = match identifier_format {
+        Some(identifier_format) => {
+            schema_create_action_builder.with_identifier_format(identifier_format)
+        }
+        None => schema_create_action_builder,
+    };
+
     let schema_create_action = schema_create_action_builder.build().map_err(|err| {
         CliError::PayloadError(format!("Failed to build schema payload: {}", err))
     })?;
@@ -266,12 +364,14 @@ fn generate_create_schema_payload(
 fn generate_update_schema_payload(
     name: &str,
     properties: &[PropertyDefinition],
+    property_updates: &[PropertyUpdate],
 ) -> Result<SchemaPayload, CliError> {
     let mut schema_paylod = SchemaPayloadBuilder::new();
 
     let schema_update_action_builder = SchemaUpdateBuilder::new()
         .with_schema_name(name.to_string())
-        .with_properties(properties.to_vec());
+        .with_properties(properties.to_vec())
+        .with_property_updates(property_updates.to_vec());
 
     let schema_update_action = schema_update_action_builder.build().map_err(|err| {
         CliError::PayloadError(format!("Failed to build schema payload: {}", err))
@@ -283,6 +383,139 @@ fn generate_update_schema_payload(
         .map_err(|err| CliError::PayloadError(format!("Failed to build schema payload: {}", err)))
 }
 
+fn parse_identifier_format(value: &Value) -> Result<IdentifierFormat, CliError> {
+    let identifier_format = value.as_mapping().ok_or_else(|| {
+        CliError::InvalidYamlError("Failed to parse schema identifier format.".to_string())
+    })?;
+
+    let mut builder = IdentifierFormatBuilder::new();
+    if let Some(pattern) = parse_value_as_string(identifier_format, "pattern")? {
+        builder = builder.with_pattern(pattern);
+    }
+    if let Some(min_length) = parse_value_as_u32(identifier_format, "min_length")? {
+        builder = builder.with_min_length(min_length);
+    }
+    if let Some(max_length) = parse_value_as_u32(identifier_format, "max_length")? {
+        builder = builder.with_max_length(max_length);
+    }
+
+    builder.build().map_err(|err| {
+        CliError::PayloadError(format!("Failed to build identifier format: {}", err))
+    })
+}
+
+fn parse_property_constraints(value: &Value) -> Result<PropertyConstraints, CliError> {
+    let constraints = value.as_mapping().ok_or_else(|| {
+        CliError::InvalidYamlError("Failed to parse property constraints.".to_string())
+    })?;
+
+    let mut builder = PropertyConstraintsBuilder::new();
+    if let Some(min_number) = parse_value_as_i64(constraints, "min_number")? {
+        builder = builder.with_min_number(min_number);
+    }
+    if let Some(max_number) = parse_value_as_i64(constraints, "max_number")? {
+        builder = builder.with_max_number(max_number);
+    }
+    if let Some(min_length) = parse_value_as_u32(constraints, "min_length")? {
+        builder = builder.with_min_length(min_length);
+    }
+    if let Some(max_length) = parse_value_as_u32(constraints, "max_length")? {
+        builder = builder.with_max_length(max_length);
+    }
+    if let Some(pattern) = parse_value_as_string(constraints, "pattern")? {
+        builder = builder.with_pattern(pattern);
+    }
+    if let Some(allowed_values) = parse_value_as_vec_string(constraints, "allowed_values")? {
+        builder = builder.with_allowed_values(allowed_values);
+    }
+    if let Some(max_bytes) = parse_value_as_u32(constraints, "max_bytes")? {
+        builder = builder.with_max_bytes(max_bytes);
+    }
+    if let Some(min_lat_long) = parse_value_as_string(constraints, "min_lat_long")? {
+        builder = builder.with_min_lat_long(parse_lat_long(&min_lat_long)?);
+    }
+    if let Some(max_lat_long) = parse_value_as_string(constraints, "max_lat_long")? {
+        builder = builder.with_max_lat_long(parse_lat_long(&max_lat_long)?);
+    }
+
+    builder.build().map_err(|err| {
+        CliError::PayloadError(format!("Failed to build property constraints: {}", err))
+    })
+}
+
+/// Parses a lat/long given as "latitude,longitude"
+fn parse_lat_long(lat_long: &str) -> Result<LatLong, CliError> {
+    let coordinates = lat_long
+        .split(',')
+        .map(|x| {
+            x.trim()
+                .parse::<i64>()
+                .map_err(|err| CliError::InvalidYamlError(format!("{}", err)))
+        })
+        .collect::<Result<Vec<i64>, CliError>>()?;
+
+    if coordinates.len() != 2 {
+        return Err(CliError::InvalidYamlError(format!(
+            "{} is not a valid latitude longitude",
+            lat_long
+        )));
+    }
+
+    LatLongBuilder::new()
+        .with_lat_long(coordinates[0], coordinates[1])
+        .build()
+        .map_err(|err| CliError::InvalidYamlError(format!("{}", err)))
+}
+
+fn parse_property_updates(updates: &[Value]) -> Result<Vec<PropertyUpdate>, CliError> {
+    updates
+        .iter()
+        .map(|value| {
+            let update = value.as_mapping().ok_or_else(|| {
+                CliError::InvalidYamlError("Failed to parse schema property update.".to_string())
+            })?;
+
+            let update_type =
+                parse_update_type(&parse_value_as_string(update, "update")?.ok_or_else(|| {
+                    CliError::InvalidYamlError(
+                        "Missing `update` field for property update.".to_string(),
+                    )
+                })?)?;
+
+            let mut builder = PropertyUpdateBuilder::new()
+                .with_property_name(parse_value_as_string(update, "name")?.ok_or_else(|| {
+                    CliError::InvalidYamlError(
+                        "Missing `name` field for property update.".to_string(),
+                    )
+                })?)
+                .with_update_type(update_type);
+            if let Some(enum_options) = parse_value_as_vec_string(update, "enum_options")? {
+                builder = builder.with_enum_options(enum_options);
+            }
+            if let Some(description) = parse_value_as_string(update, "description")? {
+                builder = builder.with_description(description);
+            }
+
+            builder.build().map_err(|err| {
+                CliError::PayloadError(format!("Failed to build property update: {}", err))
+            })
+        })
+        .collect()
+}
+
+fn parse_update_type(update_type: &str) -> Result<UpdateType, CliError> {
+    match update_type.to_lowercase().as_ref() {
+        "add_enum_options" => Ok(UpdateType::AddEnumOptions),
+        "deprecate" => Ok(UpdateType::Deprecate),
+        "set_description" => Ok(UpdateType::SetDescription),
+        "relax_required" => Ok(UpdateType::RelaxRequired),
+        _ => Err(CliError::InvalidYamlError(format!(
+            "Invalid update for property: {}",
+            update_type
+        ))),
+    }
+}
+
 fn parse_properties(properties: &[Value]) -> Result<Vec<PropertyDefinition>, CliError> {
     properties
         .iter()
@@ -322,7 +555,31 @@ fn parse_property_definition(property: &Mapping) -> Result<PropertyDefinition, C
         None => property_definition,
     };
 
-    property_definition = match data_type {
+    property_definition = match property.get(&Value::String("constraints".to_string())) {
+        Some(constraints) => {
+            property_definition.with_constraints(parse_property_constraints(constraints)?)
+        }
+        None => property_definition,
+    };
+
+    // The type-specific fields of a list describe its elements
+    let element_data_type = match data_type {
+        StateDataType::List => {
+            let list_data_type = parse_value_as_data_type(
+                &parse_value_as_string(property, "list_data_type")?.ok_or_else(|| {
+                    CliError::InvalidYamlError(
+                        "Missing `list_data_type` field for property definition with type LIST."
+                            .to_string(),
+                    )
+                })?,
+            )?;
+            property_definition = property_definition.with_list_data_type(list_data_type.clone());
+            list_data_type
+        }
+        _ => data_type,
+    };
+
+    property_definition = match element_data_type {
         StateDataType::Number => property_definition.with_number_exponent(
             parse_value_as_i32(property, "number_exponent")?.ok_or_else(|| {
                 CliError::InvalidYamlError(
@@ -363,7 +620,7 @@ mod test {
     use super::*;
     use grid_sdk::protocol::schema::payload::{Action, SchemaPayload};
     use grid_sdk::protocol::schema::state::{
-        DataType, PropertyDefinition, PropertyDefinitionBuilder,
+        DataType, PropertyDefinition, PropertyDefinitionBuilder, PropertyValueBuilder,
     };
     use std::env;
     use std::fs::{remove_file, File};
@@ -516,11 +773,284 @@ mod test {
         })
     }
 
+    ///
+    /// Verifies parse_yaml returns a valid SchemaPayload with SchemaUpdateAction set from a yaml
+    /// containing only property updates
+    ///
+    #[test]
+    fn test_valid_yaml_update_property_updates() {
+        run_test(|test_yaml_file_path| {
+            let mut file =
+                File::create(test_yaml_file_path).expect("Error creating test schema yaml file.");
+            file.write_all(
+                br##"- name: "Lightbulb"
+  property_updates:
+    - name: "bulb_type"
+      update: ADD_ENUM_OPTIONS
+      enum_options: ["halogen"]
+    - name: "size"
+      update: deprecate"##,
+            )
+            .expect("Error writting example schema.");
+
+            let payload = parse_yaml(
+                test_yaml_file_path,
+                Action::SchemaUpdate(SchemaUpdateAction::default()),
+            )
+            .expect("Error parsing yaml");
+
+            let property_updates = vec![
+                PropertyUpdateBuilder::new()
+                    .with_property_name("bulb_type".to_string())
+                    .with_update_type(UpdateType::AddEnumOptions)
+                    .with_enum_options(vec!["halogen".to_string()])
+                    .build()
+                    .unwrap(),
+                PropertyUpdateBuilder::new()
+                    .with_property_name("size".to_string())
+                    .with_update_type(UpdateType::Deprecate)
+                    .build()
+                    .unwrap(),
+            ];
+
+            assert_eq!(
+                generate_update_schema_payload("Lightbulb", &[], &property_updates).unwrap(),
+                payload[0]
+            );
+        })
+    }
+
+    ///
+    /// Verifies parse_yaml parses the constraints of a property definition
+    ///
+    #[test]
+    fn test_valid_yaml_create_property_constraints() {
+        run_test(|test_yaml_file_path| {
+            let mut file =
+                File::create(test_yaml_file_path).expect("Error creating test schema yaml file.");
+            file.write_all(
+                br##"- name: "Lightbulb"
+  description: "Example Lightbulb schema"
+  properties:
+    - name: "size"
+      data_type: NUMBER
+      number_exponent: 0
+      constraints:
+        min_number: 10
+        max_number: 200
+    - name: "model"
+      data_type: STRING
+      constraints:
+        max_length: 12
+        pattern: "LB-[0-9]+"
+    - name: "factory"
+      data_type: LAT_LONG
+      constraints:
+        min_lat_long: "0, -10000000"
+        max_lat_long: "10000000,0""##,
+            )
+            .expect("Error writting example schema.");
+
+            let payload = parse_yaml(
+                test_yaml_file_path,
+                Action::SchemaCreate(SchemaCreateAction::default()),
+            )
+            .expect("Error parsing yaml");
+
+            let properties = vec![
+                PropertyDefinitionBuilder::new()
+                    .with_name("size".to_string())
+                    .with_data_type(DataType::Number)
+                    .with_number_exponent(0)
+                    .with_constraints(
+                        PropertyConstraintsBuilder::new()
+                            .with_min_number(10)
+                            .with_max_number(200)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+                PropertyDefinitionBuilder::new()
+                    .with_name("model".to_string())
+                    .with_data_type(DataType::String)
+                    .with_constraints(
+                        PropertyConstraintsBuilder::new()
+                            .with_max_length(12)
+                            .with_pattern("LB-[0-9]+".to_string())
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+                PropertyDefinitionBuilder::new()
+                    .with_name("factory".to_string())
+                    .with_data_type(DataType::LatLong)
+                    .with_constraints(
+                        PropertyConstraintsBuilder::new()
+                            .with_min_lat_long(
+                                LatLongBuilder::new()
+                                    .with_lat_long(0, -10_000_000)
+                                    .build()
+                                    .unwrap(),
+                            )
+                            .with_max_lat_long(
+                                LatLongBuilder::new()
+                                    .with_lat_long(10_000_000, 0)
+                                    .build()
+                                    .unwrap(),
+                            )
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            ];
+
+            assert_eq!(
+                generate_create_schema_payload(
+                    "Lightbulb",
+                    &properties,
+                    Some("Example Lightbulb schema".to_string()),
+                    None,
+                )
+                .unwrap(),
+                payload[0]
+            );
+        })
+    }
+
+    ///
+    /// Verifies parse_yaml parses timestamp, decimal and list property definitions
+    ///
+    #[test]
+    fn test_valid_yaml_create_new_data_types() {
+        run_test(|test_yaml_file_path| {
+            let mut file =
+                File::create(test_yaml_file_path).expect("Error creating test schema yaml file.");
+            file.write_all(
+                br##"- name: "Snack"
+  description: "Example snack schema"
+  properties:
+    - name: "best_before"
+      data_type: TIMESTAMP
+    - name: "net_weight"
+      data_type: DECIMAL
+    - name: "allergens"
+      data_type: LIST
+      list_data_type: ENUM
+      enum_options: ["milk", "peanuts", "soy"]"##,
+            )
+            .expect("Error writting example schema.");
+
+            let payload = parse_yaml(
+                test_yaml_file_path,
+                Action::SchemaCreate(SchemaCreateAction::default()),
+            )
+            .expect("Error parsing yaml");
+
+            let properties = vec![
+                PropertyDefinitionBuilder::new()
+                    .with_name("best_before".to_string())
+                    .with_data_type(DataType::Timestamp)
+                    .build()
+                    .unwrap(),
+                PropertyDefinitionBuilder::new()
+                    .with_name("net_weight".to_string())
+                    .with_data_type(DataType::Decimal)
+                    .build()
+                    .unwrap(),
+                PropertyDefinitionBuilder::new()
+                    .with_name("allergens".to_string())
+                    .with_data_type(DataType::List)
+                    .with_list_data_type(DataType::Enum)
+                    .with_enum_options(vec![
+                        "milk".to_string(),
+                        "peanuts".to_string(),
+                        "soy".to_string(),
+                    ])
+                    .build()
+                    .unwrap(),
+            ];
+
+            assert_eq!(
+                generate_create_schema_payload(
+                    "Snack",
+                    &properties,
+                    Some("Example snack schema".to_string()),
+                    None,
+                )
+                .unwrap(),
+                payload[0]
+            );
+        })
+    }
+
+    #[test]
+    // check that property values are validated client-side against the definitions returned by
+    // the REST API
+    fn test_validate_property_values() {
+        let definitions: Vec<GridPropertyDefinitionSlice> = serde_yaml::from_str(
+            r##"- name: "dimensions"
+  schema_name: "Lightbulb"
+  data_type: Struct
+  required: true
+  description: ""
+  number_exponent: 0
+  enum_options: []
+  struct_properties:
+    - name: "height"
+      schema_name: "Lightbulb"
+      data_type: Number
+      required: true
+      description: ""
+      number_exponent: 0
+      enum_options: []
+      struct_properties: []"##,
+        )
+        .expect("Error parsing definitions");
+
+        let dimensions = |height: PropertyValue| {
+            PropertyValueBuilder::new()
+                .with_name("dimensions".to_string())
+                .with_data_type(DataType::Struct)
+                .with_struct_values(vec![height])
+                .build()
+                .unwrap()
+        };
+
+        let valid = dimensions(
+            PropertyValueBuilder::new()
+                .with_name("height".to_string())
+                .with_data_type(DataType::Number)
+                .with_number_value(10)
+                .build()
+                .unwrap(),
+        );
+        assert!(validate_property_values(&definitions, &[valid]).is_ok());
+
+        let invalid = dimensions(
+            PropertyValueBuilder::new()
+                .with_name("height".to_string())
+                .with_data_type(DataType::String)
+                .with_string_value("tall".to_string())
+                .build()
+                .unwrap(),
+        );
+        match validate_property_values(&definitions, &[invalid]) {
+            Err(CliError::PayloadError(msg)) => {
+                assert_eq!(msg, "dimensions.height: expected Number, got String")
+            }
+            res => panic!("Unexpected result {:?}", res),
+        }
+    }
+
     fn make_create_schema_payload_1() -> SchemaPayload {
         generate_create_schema_payload(
             "Lightbulb",
             &create_lightbulb_property_definitions(),
             Some("Example Lightbulb schema".to_string()),
+            None,
         )
         .unwrap()
     }
@@ -530,17 +1060,18 @@ mod test {
             "Phone",
             &create_phone_property_definitions(),
             Some("Example phone schema".to_string()),
+            None,
         )
         .unwrap()
     }
 
     fn make_update_schema_payload_1() -> SchemaPayload {
-        generate_update_schema_payload("Lightbulb", &create_lightbulb_property_definitions())
+        generate_update_schema_payload("Lightbulb", &create_lightbulb_property_definitions(), &[])
             .unwrap()
     }
 
     fn make_update_schema_payload_2() -> SchemaPayload {
-        generate_update_schema_payload("Phone", &create_phone_property_definitions()).unwrap()
+        generate_update_schema_payload("Phone", &create_phone_property_definitions(), &[]).unwrap()
     }
 
     fn create_lightbulb_property_definitions() -> Vec<PropertyDefinition> {
@@ -96,6 +96,47 @@ pub fn parse_value_as_i32(property: &Mapping, key: &str) -> Result<Option<i32>,
     }
 }
 
+/**
+ * Given a yaml object, parse it as an i64
+ *
+ * property - Yaml object we wish to parse in as an i64
+ */
+pub fn parse_value_as_i64(property: &Mapping, key: &str) -> Result<Option<i64>, CliError> {
+    match property.get(&Value::String(key.to_string())) {
+        Some(value) => match value.as_i64() {
+            Some(value) => Ok(Some(value)),
+            None => Err(CliError::InvalidYamlError(format!(
+                "Value of {} has an invalid format. Expected is a yaml integer.",
+                key
+            ))),
+        },
+        None => Ok(None),
+    }
+}
+
+/**
+ * Given a yaml object, parse it as a u32
+ *
+ * property - Yaml object we wish to parse in as a u32
+ */
+pub fn parse_value_as_u32(property: &Mapping, key: &str) -> Result<Option<u32>, CliError> {
+    match property.get(&Value::String(key.to_string())) {
+        Some(value) => match value.as_u64() {
+            Some(value) => Ok(Some(value.to_string().parse::<u32>().map_err(|_| {
+                CliError::InvalidYamlError(format!(
+                    "Failed to parse value of {} to unsigned 32 bit integer",
+                    key
+                ))
+            })?)),
+            None => Err(CliError::InvalidYamlError(format!(
+                "Value of {} has an invalid format. Expected is a positive yaml integer.",
+                key
+            ))),
+        },
+        None => Ok(None),
+    }
+}
+
 /**
  * Given a yaml object, parse it as a PropertyDefinition DataType
  *
@@ -110,6 +151,9 @@ pub fn parse_value_as_data_type(data_type: &str) -> Result<DataType, CliError> {
         "enum" => Ok(DataType::Enum),
         "struct" => Ok(DataType::Struct),
         "lat_long" => Ok(DataType::LatLong),
+        "timestamp" => Ok(DataType::Timestamp),
+        "decimal" => Ok(DataType::Decimal),
+        "list" => Ok(DataType::List),
         _ => Err(CliError::InvalidYamlError(format!(
             "Invalid data type for PropertyDefinition: {}",
             data_type
@@ -179,6 +223,15 @@ mod test {
             parse_value_as_data_type("lat_long").unwrap(),
             DataType::LatLong
         );
+        assert_eq!(
+            parse_value_as_data_type("timestamp").unwrap(),
+            DataType::Timestamp
+        );
+        assert_eq!(
+            parse_value_as_data_type("DECIMAL").unwrap(),
+            DataType::Decimal
+        );
+        assert_eq!(parse_value_as_data_type("list").unwrap(), DataType::List);
 
         // Check the method returns an error for an invalid input
         assert!(parse_value_as_data_type("not_a_valid_type").is_err());
@@ -31,6 +31,7 @@ mod integration {
     static ORG_ID: &str = "cgl";
     static ORG_NAME: &str = "Cargill";
     static ORG_ADDRESS: &str = "hq";
+    static GS1_COMPANY_PREFIX: &str = "0762111";
 
     static SCHEMA_CREATE_FILE: &str = "tests/products/test_product_schema.yaml";
 
@@ -140,7 +141,10 @@ mod integration {
             .arg(&org_id)
             .arg(&ORG_NAME)
             .arg(&ORG_ADDRESS)
-            .args(&["--metadata", &format!("gs1_company_prefixes={}", &org_id)]);
+            .args(&[
+                "--metadata",
+                &format!("gs1_company_prefixes={}", &GS1_COMPANY_PREFIX),
+            ]);
         cmd_org_create.assert().success();
 
         //run `grid agent create`
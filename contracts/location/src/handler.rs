@@ -27,20 +27,27 @@ cfg_if! {
     }
 }
 
+use std::collections::HashSet;
+
 use grid_sdk::{
+    gs1::{gln_has_company_prefix, parse_company_prefixes, validate_gln, GS1_COMPANY_PREFIXES_KEY},
     permissions::PermissionChecker,
-    protocol::location::{
-        payload::{
-            Action, LocationCreateAction, LocationDeleteAction, LocationNamespace, LocationPayload,
-            LocationUpdateAction,
+    protocol::{
+        addressing::GRID_NAMESPACE,
+        location::{
+            payload::{
+                Action, LocationCreateAction, LocationDeleteAction, LocationNamespace,
+                LocationPayload, LocationTransferAction, LocationUpdateAction, TransferPhase,
+            },
+            state::{Location, LocationBuilder, LocationNamespace as StateNamespace},
         },
-        state::{LocationBuilder, LocationNamespace as StateNamespace},
+        schema::validation::validate_properties,
     },
 };
 
 use grid_sdk::protos::FromBytes;
 
-use crate::{addressing::GRID_NAMESPACE, state::LocationState};
+use crate::state::LocationState;
 
 #[cfg(target_arch = "wasm32")]
 fn apply(
@@ -122,6 +129,9 @@ impl TransactionHandler for LocationTransactionHandler {
             Action::LocationDelete(payload) => {
                 delete_location(&payload, &mut state, signer, &perm_checker)?
             }
+            Action::LocationTransfer(payload) => {
+                transfer_location(&payload, &mut state, signer, &perm_checker)?
+            }
         }
         Ok(())
     }
@@ -134,11 +144,9 @@ fn create_location(
     perm_checker: &PermissionChecker,
 ) -> Result<(), ApplyError> {
     // 1) validate gln (gs1 only)
-    if payload.namespace() == &LocationNamespace::GS1 && !is_gln_13_valid(&payload.location_id()) {
-        return Err(ApplyError::InvalidTransaction(format!(
-            "Invalid GLN: {}",
-            payload.location_id()
-        )));
+    if payload.namespace() == &LocationNamespace::GS1 {
+        validate_gln(payload.location_id())
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
     }
 
     // 2) check if location already exists
@@ -189,15 +197,20 @@ fn create_location(
         )));
     }
 
-    // 7) check if organization has gln in gs1_company_prefix metadata
-    let mut has_gs1_prefix = false;
-    for metadata in organization.metadata() {
-        if metadata.key() == "gs1_company_prefixes" && metadata.value() == payload.location_id() {
-            has_gs1_prefix = true;
-        }
-    }
+    // 7) check if the gln starts with one of the organization's gs1_company_prefixes
+    let gs1_company_prefixes = match organization
+        .metadata()
+        .iter()
+        .find(|metadata| metadata.key() == GS1_COMPANY_PREFIXES_KEY)
+    {
+        Some(metadata) => parse_company_prefixes(metadata.value())
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?,
+        None => vec![],
+    };
 
-    if !has_gs1_prefix {
+    if !gln_has_company_prefix(payload.location_id(), &gs1_company_prefixes)
+        .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?
+    {
         return Err(ApplyError::InvalidTransaction(format!(
             "Organization {} does not have the correct gs1 prefix",
             organization.org_id()
@@ -214,34 +227,20 @@ fn create_location(
     };
 
     if payload.namespace() == &LocationNamespace::GS1 {
-        // 9) Check if properties in location are all a part of the gs1 schema
-        for property in payload.properties() {
-            if schema
-                .properties()
-                .iter()
-                .all(|p| p.name() != property.name())
-            {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "{} is not a property that is defined by the gs1 schema",
-                    property.name()
-                )));
-            }
-        }
+        // 9) check that the properties are all defined by the gs1 schema, that the required
+        // properties are present and that every value satisfies its property definition
+        validate_properties(schema.properties(), payload.properties())
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
+    }
 
-        // 10) check if location has all required fields
-        for property in schema.properties().iter().filter(|p| *p.required()) {
-            if !payload
-                .properties()
-                .iter()
-                .any(|p| p.name() == property.name() && p.data_type() == property.data_type())
-            {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Missing required field '{}' of type '{:?}'",
-                    property.name(),
-                    property.data_type()
-                )));
-            }
-        }
+    // 10) check that the parent location exists and is owned by the same organization
+    if !payload.parent_location_id().is_empty() {
+        check_parent_location(
+            state,
+            payload.location_id(),
+            payload.parent_location_id(),
+            payload.owner(),
+        )?;
     }
 
     let namespace = match payload.namespace() {
@@ -253,10 +252,18 @@ fn create_location(
         .with_namespace(namespace)
         .with_owner(payload.owner().to_string())
         .with_properties(payload.properties().to_vec())
+        .with_schema_version(*schema.version())
+        .with_parent_location_id(payload.parent_location_id().to_string())
         .build()
         .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
 
-    state.set_location(location)
+    state.set_location(location)?;
+
+    if !payload.parent_location_id().is_empty() {
+        add_child_location(state, payload.parent_location_id(), payload.location_id())?;
+    }
+
+    Ok(())
 }
 
 fn update_location(
@@ -325,34 +332,38 @@ fn update_location(
     };
 
     if payload.namespace() == &LocationNamespace::GS1 {
-        // 7) Check if properties in location are all a part of the gs1 schema
-        for property in payload.properties() {
-            if schema
-                .properties()
-                .iter()
-                .all(|p| p.name() != property.name())
-            {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "{} is not a property that is defined by the gs1 schema",
-                    property.name()
-                )));
-            }
-        }
+        // 7) check that the properties are all defined by the gs1 schema, that the required
+        // properties are present and that every value satisfies its property definition
+        validate_properties(schema.properties(), payload.properties())
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
+    }
 
-        // 8) check if location has all required fields
-        for property in schema.properties().iter().filter(|p| *p.required()) {
-            if !payload
-                .properties()
-                .iter()
-                .any(|p| p.name() == property.name() && p.data_type() == property.data_type())
-            {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Missing required field '{}' of type '{:?}'",
-                    property.name(),
-                    property.data_type()
-                )));
-            }
-        }
+    // 8) check that the location is either moved or detached, and that a new parent location
+    // exists, is owned by the same organization and is not contained in this location
+    if payload.detach_from_parent() && !payload.parent_location_id().is_empty() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Location {} cannot be both moved to {} and detached from its parent",
+            payload.location_id(),
+            payload.parent_location_id()
+        )));
+    }
+
+    let old_parent_location_id = location.parent_location_id().to_string();
+    let parent_location_id = if payload.detach_from_parent() {
+        String::new()
+    } else if payload.parent_location_id().is_empty() {
+        old_parent_location_id.clone()
+    } else {
+        payload.parent_location_id().to_string()
+    };
+    let parent_changed = parent_location_id != old_parent_location_id;
+    if parent_changed && !parent_location_id.is_empty() {
+        check_parent_location(
+            state,
+            payload.location_id(),
+            &parent_location_id,
+            location.owner(),
+        )?;
     }
 
     let namespace = match payload.namespace() {
@@ -364,10 +375,25 @@ fn update_location(
         .with_namespace(namespace)
         .with_owner(location.owner().to_string())
         .with_properties(payload.properties().to_vec())
+        .with_pending_owner(location.pending_owner().to_string())
+        .with_schema_version(*schema.version())
+        .with_parent_location_id(parent_location_id.clone())
+        .with_child_location_ids(location.child_location_ids().to_vec())
         .build()
         .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
 
-    state.set_location(location)
+    state.set_location(location)?;
+
+    if parent_changed {
+        if !old_parent_location_id.is_empty() {
+            remove_child_location(state, &old_parent_location_id, payload.location_id())?;
+        }
+        if !parent_location_id.is_empty() {
+            add_child_location(state, &parent_location_id, payload.location_id())?;
+        }
+    }
+
+    Ok(())
 }
 
 fn delete_location(
@@ -426,34 +452,250 @@ fn delete_location(
         )));
     }
 
-    state.remove_location(payload.location_id())
+    // 6) check that no other location is contained in this location
+    if !location.child_location_ids().is_empty() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Location {} cannot be deleted while it contains other locations",
+            payload.location_id()
+        )));
+    }
+
+    state.remove_location(payload.location_id())?;
+
+    if !location.parent_location_id().is_empty() {
+        remove_child_location(state, location.parent_location_id(), payload.location_id())?;
+    }
+
+    Ok(())
 }
 
-fn is_gln_13_valid(gln: &str) -> bool {
-    if gln.len() != 13 {
-        return false;
-    }
-    let mut acc = 0;
-    let (start, check_digit_str) = gln.split_at(gln.len() - 1);
-    for (i, c) in start.chars().enumerate() {
-        if let Some(v) = c.to_digit(10) {
-            if i % 2 == 0 {
-                acc += v;
-            } else {
-                acc += v * 3;
+fn transfer_location(
+    payload: &LocationTransferAction,
+    state: &mut LocationState,
+    signer: &str,
+    perm_checker: &PermissionChecker,
+) -> Result<(), ApplyError> {
+    // 1) check if location exists
+    let location = if let Some(location) = state.get_location(&payload.location_id())? {
+        location
+    } else {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "A location with GLN {} does not exist",
+            payload.location_id()
+        )));
+    };
+
+    // 2) check if agent exists
+    let agent = if let Some(agent) = state.get_agent(signer)? {
+        agent
+    } else {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Agent {} is not registered in Pike",
+            signer
+        )));
+    };
+
+    // 3) check if agent is an admin of its organization
+    if !perm_checker
+        .has_permission(agent.public_key(), "admin")
+        .map_err(|err| ApplyError::InternalError(format!("Failed to check permissions: {}", err)))?
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Agent {} does not have permission to transfer locations",
+            agent.public_key()
+        )));
+    }
+
+    // 4) check that the agent belongs to the organization that acts in this phase
+    let builder = match payload.phase() {
+        TransferPhase::Offer => {
+            if agent.org_id() != location.owner() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Agent with public key {} is not registered to {}",
+                    agent.public_key(),
+                    location.owner()
+                )));
+            }
+
+            if payload.new_owner() == location.owner() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Location {} is already owned by {}",
+                    payload.location_id(),
+                    location.owner()
+                )));
+            }
+
+            if state.get_organization(payload.new_owner())?.is_none() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Organization {} is not registered with Pike",
+                    payload.new_owner()
+                )));
             }
-        } else {
-            return false;
+
+            let new_owner = payload.new_owner().to_string();
+            location.into_builder().with_pending_owner(new_owner)
         }
-    }
+        TransferPhase::Accept => {
+            if location.pending_owner().is_empty() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "No transfer of location {} is pending",
+                    payload.location_id()
+                )));
+            }
 
-    let check_digit = if let Ok(c) = check_digit_str.parse::<u32>() {
-        c
-    } else {
-        return false;
+            if agent.org_id() != location.pending_owner() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Agent with public key {} is not registered to {}",
+                    agent.public_key(),
+                    location.pending_owner()
+                )));
+            }
+
+            // A parent and its children are always owned by the same organization
+            if !location.parent_location_id().is_empty()
+                || !location.child_location_ids().is_empty()
+            {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Location {} must be detached from its parent and child locations before it \
+                     is transferred",
+                    payload.location_id()
+                )));
+            }
+
+            let new_owner = location.pending_owner().to_string();
+            location
+                .into_builder()
+                .with_owner(new_owner)
+                .with_pending_owner(String::new())
+        }
+        TransferPhase::Cancel => {
+            if location.pending_owner().is_empty() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "No transfer of location {} is pending",
+                    payload.location_id()
+                )));
+            }
+
+            if agent.org_id() != location.owner() && agent.org_id() != location.pending_owner() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Agent with public key {} is not registered to {} or {}",
+                    agent.public_key(),
+                    location.owner(),
+                    location.pending_owner()
+                )));
+            }
+
+            location.into_builder().with_pending_owner(String::new())
+        }
+    };
+
+    let location = builder
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+
+    state.set_location(location)
+}
+
+/// Checks that `parent_location_id` names an existing location owned by `owner`, and that it is
+/// neither `location_id` itself nor contained in it, so that moving `location_id` under it does
+/// not create a cycle
+fn check_parent_location(
+    state: &LocationState,
+    location_id: &str,
+    parent_location_id: &str,
+    owner: &str,
+) -> Result<(), ApplyError> {
+    let parent = match state.get_location(parent_location_id)? {
+        Some(parent) => parent,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Parent location {} does not exist",
+                parent_location_id
+            )))
+        }
     };
 
-    (check_digit + acc) % 10 == 0
+    if parent.owner() != owner {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Parent location {} is not owned by {}",
+            parent_location_id, owner
+        )));
+    }
+
+    let mut ancestor = Some(parent);
+    let mut visited = HashSet::new();
+    while let Some(current) = ancestor {
+        if current.location_id() == location_id {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Location {} cannot be contained in {}, which it contains",
+                location_id, parent_location_id
+            )));
+        }
+        if current.parent_location_id().is_empty()
+            || !visited.insert(current.location_id().to_string())
+        {
+            break;
+        }
+        ancestor = state.get_location(current.parent_location_id())?;
+    }
+
+    Ok(())
+}
+
+fn add_child_location(
+    state: &LocationState,
+    parent_location_id: &str,
+    child_location_id: &str,
+) -> Result<(), ApplyError> {
+    let parent = get_parent_location(state, parent_location_id)?;
+    let mut child_location_ids = parent.child_location_ids().to_vec();
+    if !child_location_ids.iter().any(|id| id == child_location_id) {
+        child_location_ids.push(child_location_id.to_string());
+    }
+
+    set_child_locations(state, parent, child_location_ids)
+}
+
+fn remove_child_location(
+    state: &LocationState,
+    parent_location_id: &str,
+    child_location_id: &str,
+) -> Result<(), ApplyError> {
+    let parent = get_parent_location(state, parent_location_id)?;
+    let child_location_ids = parent
+        .child_location_ids()
+        .iter()
+        .filter(|id| *id != child_location_id)
+        .cloned()
+        .collect();
+
+    set_child_locations(state, parent, child_location_ids)
+}
+
+fn get_parent_location(
+    state: &LocationState,
+    parent_location_id: &str,
+) -> Result<Location, ApplyError> {
+    state.get_location(parent_location_id)?.ok_or_else(|| {
+        ApplyError::InternalError(format!(
+            "Parent location {} does not exist",
+            parent_location_id
+        ))
+    })
+}
+
+fn set_child_locations(
+    state: &LocationState,
+    parent: Location,
+    child_location_ids: Vec<String>,
+) -> Result<(), ApplyError> {
+    let parent = parent
+        .into_builder()
+        .with_child_location_ids(child_location_ids)
+        .build()
+        .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+
+    state.set_location(parent)
 }
 
 #[cfg(test)]
@@ -464,20 +706,24 @@ mod tests {
     use std::collections::HashMap;
 
     use grid_sdk::protocol::location::payload::{
-        LocationCreateActionBuilder, LocationDeleteActionBuilder, LocationUpdateActionBuilder,
+        LocationCreateActionBuilder, LocationDeleteActionBuilder, LocationTransferActionBuilder,
+        LocationUpdateActionBuilder,
     };
     use grid_sdk::protocol::pike::state::{
         AgentBuilder, AgentListBuilder, KeyValueEntryBuilder, OrganizationBuilder,
         OrganizationListBuilder,
     };
     use grid_sdk::protocol::schema::state::{
-        DataType, PropertyDefinitionBuilder, PropertyValueBuilder, SchemaBuilder, SchemaListBuilder,
+        DataType, PropertyConstraintsBuilder, PropertyDefinitionBuilder, PropertyValue,
+        PropertyValueBuilder, SchemaBuilder, SchemaListBuilder,
     };
     use grid_sdk::protos::IntoBytes;
 
     use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
 
-    use crate::addressing::{compute_agent_address, compute_org_address, compute_schema_address};
+    use grid_sdk::protocol::addressing::{
+        compute_agent_address, compute_organization_address, compute_schema_address,
+    };
 
     #[derive(Default, Debug)]
     struct MockTransactionContext {
@@ -491,7 +737,7 @@ mod tests {
             // create organization with prefix
             let key_value = KeyValueEntryBuilder::new()
                 .with_key("gs1_company_prefixes".to_string())
-                .with_value("9012345000004".to_string())
+                .with_value("0614141,9012345".to_string())
                 .build()
                 .unwrap();
             let prefix_org = OrganizationBuilder::new()
@@ -506,7 +752,7 @@ mod tests {
                 .build()
                 .unwrap();
             let prefix_org_bytes = prefix_org_list.into_bytes().unwrap();
-            let prefix_org_address = compute_org_address("prefix_org");
+            let prefix_org_address = compute_organization_address("prefix_org");
 
             entries.push((prefix_org_address, prefix_org_bytes));
 
@@ -522,7 +768,7 @@ mod tests {
                 .build()
                 .unwrap();
             let no_prefix_org_bytes = no_prefix_org_list.into_bytes().unwrap();
-            let no_prefix_org_address = compute_org_address("no_prefix_org");
+            let no_prefix_org_address = compute_organization_address("no_prefix_org");
 
             entries.push((no_prefix_org_address, no_prefix_org_bytes));
 
@@ -594,12 +840,38 @@ mod tests {
             mock
         }
 
+        fn add_admin(&self, public_key: &str, org_id: &str) {
+            let admin = AgentBuilder::new()
+                .with_org_id(org_id.to_string())
+                .with_public_key(public_key.to_string())
+                .with_active(true)
+                .with_roles(vec!["admin".to_string()])
+                .build()
+                .unwrap();
+            let admin_list = AgentListBuilder::new()
+                .with_agents(vec![admin])
+                .build()
+                .unwrap();
+
+            self.set_state_entries(vec![(
+                compute_agent_address(public_key),
+                admin_list.into_bytes().unwrap(),
+            )])
+            .unwrap();
+        }
+
         fn create_gs1_schema(&self) {
             let properties = vec![
                 PropertyDefinitionBuilder::new()
                     .with_name("locationName".into())
                     .with_data_type(DataType::String)
                     .with_required(true)
+                    .with_constraints(
+                        PropertyConstraintsBuilder::new()
+                            .with_max_length(20)
+                            .build()
+                            .unwrap(),
+                    )
                     .build()
                     .unwrap(),
                 PropertyDefinitionBuilder::new()
@@ -708,6 +980,92 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    fn default_properties() -> Vec<PropertyValue> {
+        vec![
+            PropertyValueBuilder::new()
+                .with_name("locationName".into())
+                .with_data_type(DataType::String)
+                .with_string_value("Taco Alley".into())
+                .build()
+                .unwrap(),
+            PropertyValueBuilder::new()
+                .with_name("description".into())
+                .with_data_type(DataType::String)
+                .with_string_value("An alley filled with tacos".into())
+                .build()
+                .unwrap(),
+        ]
+    }
+
+    fn create_child_location(
+        location_id: &str,
+        parent_location_id: &str,
+        state: &mut LocationState,
+        perm_checker: &PermissionChecker,
+    ) -> Result<(), ApplyError> {
+        let payload = LocationCreateActionBuilder::new()
+            .with_location_id(location_id.into())
+            .with_namespace(LocationNamespace::GS1)
+            .with_owner("prefix_org".into())
+            .with_properties(default_properties())
+            .with_parent_location_id(parent_location_id.into())
+            .build()
+            .unwrap();
+
+        create_location(&payload, state, "agent_with_perms", perm_checker)
+    }
+
+    fn move_location(
+        location_id: &str,
+        parent_location_id: &str,
+        state: &mut LocationState,
+        perm_checker: &PermissionChecker,
+    ) -> Result<(), ApplyError> {
+        let payload = LocationUpdateActionBuilder::new()
+            .with_location_id(location_id.into())
+            .with_namespace(LocationNamespace::GS1)
+            .with_properties(default_properties())
+            .with_parent_location_id(parent_location_id.into())
+            .build()
+            .unwrap();
+
+        update_location(&payload, state, "agent_with_perms", perm_checker)
+    }
+
+    fn detach_location(
+        location_id: &str,
+        state: &mut LocationState,
+        perm_checker: &PermissionChecker,
+    ) -> Result<(), ApplyError> {
+        let payload = LocationUpdateActionBuilder::new()
+            .with_location_id(location_id.into())
+            .with_namespace(LocationNamespace::GS1)
+            .with_properties(default_properties())
+            .with_detach_from_parent(true)
+            .build()
+            .unwrap();
+
+        update_location(&payload, state, "agent_with_perms", perm_checker)
+    }
+
+    fn transfer(
+        phase: TransferPhase,
+        new_owner: &str,
+        signer: &str,
+        state: &mut LocationState,
+        perm_checker: &PermissionChecker,
+    ) -> Result<(), ApplyError> {
+        let payload = LocationTransferActionBuilder::new()
+            .with_location_id("9012345000004".into())
+            .with_namespace(LocationNamespace::GS1)
+            .with_phase(phase)
+            .with_new_owner(new_owner.into())
+            .build()
+            .unwrap();
+
+        transfer_location(&payload, state, signer, perm_checker)
+    }
+
     #[test]
     fn test_add_location_valid() {
         let properties = vec![
@@ -802,6 +1160,42 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_add_location_with_second_gs1_prefix() {
+        let properties = vec![
+            PropertyValueBuilder::new()
+                .with_name("locationName".into())
+                .with_data_type(DataType::String)
+                .with_string_value("Taco Alley".into())
+                .build()
+                .unwrap(),
+            PropertyValueBuilder::new()
+                .with_name("description".into())
+                .with_data_type(DataType::String)
+                .with_string_value("An alley filled with tacos".into())
+                .build()
+                .unwrap(),
+        ];
+
+        let payload = LocationCreateActionBuilder::new()
+            .with_location_id("0614141000005".into())
+            .with_namespace(LocationNamespace::GS1)
+            .with_owner("prefix_org".into())
+            .with_properties(properties)
+            .build()
+            .unwrap();
+
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        let result = create_location(&payload, &mut state, "agent_with_perms", &perm_checker);
+
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn test_create_location_invalid_gln() {
         let properties = vec![
@@ -836,7 +1230,7 @@ mod tests {
         match create_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
             Ok(()) => panic!("Failed to find invalid GLN"),
             Err(ApplyError::InvalidTransaction(ref msg)) => {
-                assert_eq!("Invalid GLN: 12345", msg);
+                assert_eq!("Invalid GLN, GLNs are 13 digit numbers: 12345", msg);
             }
             Err(err) => panic!("Wrong error: {}", err),
         }
@@ -1055,12 +1449,55 @@ mod tests {
     }
 
     #[test]
-    fn test_create_location_org_does_not_have_gs1_prefix() {
-        let mock_context = MockTransactionContext::new();
-        mock_context.create_gs1_schema();
-
-        let perm_checker = PermissionChecker::new(&mock_context);
-        let mut state = LocationState::new(&mock_context);
+    fn test_create_location_org_does_not_own_gs1_prefix() {
+        let properties = vec![
+            PropertyValueBuilder::new()
+                .with_name("locationName".into())
+                .with_data_type(DataType::String)
+                .with_string_value("Taco Alley".into())
+                .build()
+                .unwrap(),
+            PropertyValueBuilder::new()
+                .with_name("description".into())
+                .with_data_type(DataType::String)
+                .with_string_value("An alley filled with tacos".into())
+                .build()
+                .unwrap(),
+        ];
+
+        let payload = LocationCreateActionBuilder::new()
+            .with_location_id("4000001000005".into())
+            .with_namespace(LocationNamespace::GS1)
+            .with_owner("prefix_org".into())
+            .with_properties(properties)
+            .build()
+            .unwrap();
+
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        match create_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
+            Ok(()) => panic!("Unexpected positive result"),
+            Err(ApplyError::InvalidTransaction(ref msg)) => {
+                assert_eq!(
+                    "Organization prefix_org does not have the correct gs1 prefix",
+                    msg
+                );
+            }
+            Err(err) => panic!("Wrong error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_create_location_org_does_not_have_gs1_prefix() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
 
         let properties = vec![
             PropertyValueBuilder::new()
@@ -1181,10 +1618,7 @@ mod tests {
         match create_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
             Ok(()) => panic!("Unexpected positive result"),
             Err(ApplyError::InvalidTransaction(ref msg)) => {
-                assert_eq!(
-                    "mvp is not a property that is defined by the gs1 schema",
-                    msg
-                );
+                assert_eq!("mvp: not defined by the schema", msg);
             }
             Err(err) => panic!("Wrong error: {}", err),
         }
@@ -1216,7 +1650,7 @@ mod tests {
         match create_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
             Ok(()) => panic!("Unexpected positive result"),
             Err(ApplyError::InvalidTransaction(ref msg)) => {
-                assert_eq!("Missing required field 'description' of type 'String'", msg);
+                assert_eq!("description: required String value is missing", msg);
             }
             Err(err) => panic!("Wrong error: {}", err),
         }
@@ -1256,7 +1690,7 @@ mod tests {
         match create_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
             Ok(()) => panic!("Unexpected positive result"),
             Err(ApplyError::InvalidTransaction(ref msg)) => {
-                assert_eq!("Missing required field 'description' of type 'String'", msg);
+                assert_eq!("description: expected String, got Number", msg);
             }
             Err(err) => panic!("Wrong error: {}", err),
         }
@@ -1475,10 +1909,7 @@ mod tests {
         match update_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
             Ok(()) => panic!("Unexpected positive result"),
             Err(ApplyError::InvalidTransaction(ref msg)) => {
-                assert_eq!(
-                    "mvp is not a property that is defined by the gs1 schema",
-                    msg
-                );
+                assert_eq!("mvp: not defined by the schema", msg);
             }
             Err(err) => panic!("Wrong error: {}", err),
         }
@@ -1511,7 +1942,7 @@ mod tests {
         match update_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
             Ok(()) => panic!("Unexpected positive result"),
             Err(ApplyError::InvalidTransaction(ref msg)) => {
-                assert_eq!("Missing required field 'description' of type 'String'", msg);
+                assert_eq!("description: required String value is missing", msg);
             }
             Err(err) => panic!("Wrong error: {}", err),
         }
@@ -1552,7 +1983,48 @@ mod tests {
         match update_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
             Ok(()) => panic!("Unexpected positive result"),
             Err(ApplyError::InvalidTransaction(ref msg)) => {
-                assert_eq!("Missing required field 'description' of type 'String'", msg);
+                assert_eq!("description: expected String, got Number", msg);
+            }
+            Err(err) => panic!("Wrong error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_update_location_property_breaks_constraints() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+
+        let properties = vec![
+            PropertyValueBuilder::new()
+                .with_name("locationName".into())
+                .with_data_type(DataType::String)
+                .with_string_value("The Taco Alley Distribution Center".into())
+                .build()
+                .unwrap(),
+            PropertyValueBuilder::new()
+                .with_name("description".into())
+                .with_data_type(DataType::String)
+                .with_string_value("Automotive".into())
+                .build()
+                .unwrap(),
+        ];
+
+        let payload = LocationUpdateActionBuilder::new()
+            .with_location_id("9012345000004".into())
+            .with_namespace(LocationNamespace::GS1)
+            .with_properties(properties)
+            .build()
+            .unwrap();
+
+        match update_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
+            Ok(()) => panic!("Unexpected positive result"),
+            Err(ApplyError::InvalidTransaction(ref msg)) => {
+                assert_eq!("locationName: longer than 20 characters", msg);
             }
             Err(err) => panic!("Wrong error: {}", err),
         }
@@ -1665,4 +2137,384 @@ mod tests {
             Err(err) => panic!("Wrong error: {}", err),
         }
     }
+
+    #[test]
+    fn test_create_location_with_parent() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+        create_child_location("9012345000011", "9012345000004", &mut state, &perm_checker).unwrap();
+
+        let parent = state.get_location("9012345000004").unwrap().unwrap();
+        assert_eq!(parent.child_location_ids(), &["9012345000011".to_string()]);
+
+        let child = state.get_location("9012345000011").unwrap().unwrap();
+        assert_eq!(child.parent_location_id(), "9012345000004");
+    }
+
+    #[test]
+    fn test_create_location_parent_does_not_exist() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        match create_child_location("9012345000011", "9012345000004", &mut state, &perm_checker) {
+            Ok(()) => panic!("Unexpected positive result"),
+            Err(ApplyError::InvalidTransaction(ref msg)) => {
+                assert_eq!("Parent location 9012345000004 does not exist", msg);
+            }
+            Err(err) => panic!("Wrong error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_update_location_moves_parent() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+        create_child_location("9012345000011", "9012345000004", &mut state, &perm_checker).unwrap();
+        create_child_location("9012345000028", "9012345000011", &mut state, &perm_checker).unwrap();
+
+        move_location("9012345000028", "9012345000004", &mut state, &perm_checker).unwrap();
+
+        let site = state.get_location("9012345000004").unwrap().unwrap();
+        assert_eq!(
+            site.child_location_ids(),
+            &["9012345000011".to_string(), "9012345000028".to_string()]
+        );
+
+        let building = state.get_location("9012345000011").unwrap().unwrap();
+        assert!(building.child_location_ids().is_empty());
+
+        let zone = state.get_location("9012345000028").unwrap().unwrap();
+        assert_eq!(zone.parent_location_id(), "9012345000004");
+    }
+
+    #[test]
+    fn test_update_location_keeps_parent() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+        create_child_location("9012345000011", "9012345000004", &mut state, &perm_checker).unwrap();
+
+        move_location("9012345000011", "", &mut state, &perm_checker).unwrap();
+
+        let site = state.get_location("9012345000004").unwrap().unwrap();
+        assert_eq!(site.child_location_ids(), &["9012345000011".to_string()]);
+
+        let building = state.get_location("9012345000011").unwrap().unwrap();
+        assert_eq!(building.parent_location_id(), "9012345000004");
+    }
+
+    #[test]
+    fn test_update_location_detaches_parent() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+        create_child_location("9012345000011", "9012345000004", &mut state, &perm_checker).unwrap();
+
+        detach_location("9012345000011", &mut state, &perm_checker).unwrap();
+
+        let site = state.get_location("9012345000004").unwrap().unwrap();
+        assert!(site.child_location_ids().is_empty());
+
+        let building = state.get_location("9012345000011").unwrap().unwrap();
+        assert!(building.parent_location_id().is_empty());
+
+        let payload = LocationUpdateActionBuilder::new()
+            .with_location_id("9012345000011".into())
+            .with_namespace(LocationNamespace::GS1)
+            .with_properties(default_properties())
+            .with_parent_location_id("9012345000004".into())
+            .with_detach_from_parent(true)
+            .build()
+            .unwrap();
+
+        match update_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
+            Ok(()) => panic!("Unexpected positive result"),
+            Err(ApplyError::InvalidTransaction(ref msg)) => {
+                assert_eq!(
+                    "Location 9012345000011 cannot be both moved to 9012345000004 and detached \
+                     from its parent",
+                    msg
+                );
+            }
+            Err(err) => panic!("Wrong error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_update_location_parent_cycle() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+        create_child_location("9012345000011", "9012345000004", &mut state, &perm_checker).unwrap();
+        create_child_location("9012345000028", "9012345000011", &mut state, &perm_checker).unwrap();
+
+        match move_location("9012345000004", "9012345000028", &mut state, &perm_checker) {
+            Ok(()) => panic!("Unexpected positive result"),
+            Err(ApplyError::InvalidTransaction(ref msg)) => {
+                assert_eq!(
+                    "Location 9012345000004 cannot be contained in 9012345000028, which it contains",
+                    msg
+                );
+            }
+            Err(err) => panic!("Wrong error: {}", err),
+        }
+
+        match move_location("9012345000004", "9012345000004", &mut state, &perm_checker) {
+            Ok(()) => panic!("Unexpected positive result"),
+            Err(ApplyError::InvalidTransaction(ref msg)) => {
+                assert_eq!(
+                    "Location 9012345000004 cannot be contained in 9012345000004, which it contains",
+                    msg
+                );
+            }
+            Err(err) => panic!("Wrong error: {}", err),
+        }
+    }
+
+    #[test]
+    fn test_delete_location_with_children() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+        create_child_location("9012345000011", "9012345000004", &mut state, &perm_checker).unwrap();
+
+        let payload = LocationDeleteActionBuilder::new()
+            .with_location_id("9012345000004".into())
+            .with_namespace(LocationNamespace::GS1)
+            .build()
+            .unwrap();
+
+        match delete_location(&payload, &mut state, "agent_with_perms", &perm_checker) {
+            Ok(()) => panic!("Unexpected positive result"),
+            Err(ApplyError::InvalidTransaction(ref msg)) => {
+                assert_eq!(
+                    "Location 9012345000004 cannot be deleted while it contains other locations",
+                    msg
+                );
+            }
+            Err(err) => panic!("Wrong error: {}", err),
+        }
+
+        let payload = LocationDeleteActionBuilder::new()
+            .with_location_id("9012345000011".into())
+            .with_namespace(LocationNamespace::GS1)
+            .build()
+            .unwrap();
+
+        delete_location(&payload, &mut state, "agent_with_perms", &perm_checker).unwrap();
+
+        let parent = state.get_location("9012345000004").unwrap().unwrap();
+        assert!(parent.child_location_ids().is_empty());
+
+        let payload = LocationDeleteActionBuilder::new()
+            .with_location_id("9012345000004".into())
+            .with_namespace(LocationNamespace::GS1)
+            .build()
+            .unwrap();
+
+        assert!(delete_location(&payload, &mut state, "agent_with_perms", &perm_checker).is_ok());
+    }
+
+    #[test]
+    fn test_transfer_location_offer_and_accept() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+        mock_context.add_admin("prefix_org_admin", "prefix_org");
+        mock_context.add_admin("no_prefix_org_admin", "no_prefix_org");
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+
+        transfer(
+            TransferPhase::Offer,
+            "no_prefix_org",
+            "prefix_org_admin",
+            &mut state,
+            &perm_checker,
+        )
+        .unwrap();
+
+        let location = state.get_location("9012345000004").unwrap().unwrap();
+        assert_eq!(location.owner(), "prefix_org");
+        assert_eq!(location.pending_owner(), "no_prefix_org");
+
+        transfer(
+            TransferPhase::Accept,
+            "",
+            "no_prefix_org_admin",
+            &mut state,
+            &perm_checker,
+        )
+        .unwrap();
+
+        let location = state.get_location("9012345000004").unwrap().unwrap();
+        assert_eq!(location.owner(), "no_prefix_org");
+        assert_eq!(location.pending_owner(), "");
+        assert_eq!(location.schema_version(), &1);
+    }
+
+    #[test]
+    fn test_transfer_location_accept_with_children() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+        mock_context.add_admin("prefix_org_admin", "prefix_org");
+        mock_context.add_admin("no_prefix_org_admin", "no_prefix_org");
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+        create_child_location("9012345000011", "9012345000004", &mut state, &perm_checker).unwrap();
+
+        transfer(
+            TransferPhase::Offer,
+            "no_prefix_org",
+            "prefix_org_admin",
+            &mut state,
+            &perm_checker,
+        )
+        .unwrap();
+
+        match transfer(
+            TransferPhase::Accept,
+            "",
+            "no_prefix_org_admin",
+            &mut state,
+            &perm_checker,
+        ) {
+            Ok(_) => panic!("Location has a child, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => assert!(err.contains(
+                "Location 9012345000004 must be detached from its parent and child locations"
+            )),
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        detach_location("9012345000011", &mut state, &perm_checker).unwrap();
+
+        transfer(
+            TransferPhase::Accept,
+            "",
+            "no_prefix_org_admin",
+            &mut state,
+            &perm_checker,
+        )
+        .unwrap();
+
+        let location = state.get_location("9012345000004").unwrap().unwrap();
+        assert_eq!(location.owner(), "no_prefix_org");
+    }
+
+    #[test]
+    fn test_transfer_location_agent_not_admin() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+
+        match transfer(
+            TransferPhase::Offer,
+            "no_prefix_org",
+            "agent_with_perms",
+            &mut state,
+            &perm_checker,
+        ) {
+            Ok(_) => panic!("Agent is not an admin, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => assert!(err
+                .contains("Agent agent_with_perms does not have permission to transfer locations")),
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    fn test_transfer_location_accept_without_offer() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+        mock_context.add_admin("no_prefix_org_admin", "no_prefix_org");
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+
+        match transfer(
+            TransferPhase::Accept,
+            "",
+            "no_prefix_org_admin",
+            &mut state,
+            &perm_checker,
+        ) {
+            Ok(_) => panic!("No transfer is pending, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("No transfer of location 9012345000004 is pending"))
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    fn test_transfer_location_cancel() {
+        let mock_context = MockTransactionContext::new();
+        mock_context.create_gs1_schema();
+        mock_context.add_admin("prefix_org_admin", "prefix_org");
+
+        let perm_checker = PermissionChecker::new(&mock_context);
+        let mut state = LocationState::new(&mock_context);
+
+        create_default_location(&mut state, &perm_checker);
+
+        transfer(
+            TransferPhase::Offer,
+            "no_prefix_org",
+            "prefix_org_admin",
+            &mut state,
+            &perm_checker,
+        )
+        .unwrap();
+        transfer(
+            TransferPhase::Cancel,
+            "",
+            "prefix_org_admin",
+            &mut state,
+            &perm_checker,
+        )
+        .unwrap();
+
+        let location = state.get_location("9012345000004").unwrap().unwrap();
+        assert_eq!(location.owner(), "prefix_org");
+        assert_eq!(location.pending_owner(), "");
+    }
 }
@@ -33,7 +33,6 @@ cfg_if! {
     }
 }
 
-mod addressing;
 pub mod handler;
 mod state;
 
@@ -12,8 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crypto::digest::Digest;
-use crypto::sha2::Sha512;
 use protobuf;
 
 cfg_if! {
@@ -31,12 +29,18 @@ cfg_if! {
     }
 }
 
-use addresser::{resource_to_byte, Resource};
+use grid_sdk::permissions::ADMIN_PERMISSION;
+use grid_sdk::protocol::addressing::{
+    compute_agent_address, compute_organization_address, compute_role_address, PIKE_NAMESPACE,
+};
 use grid_sdk::protos::pike_payload::{
-    CreateAgentAction, CreateOrganizationAction, PikePayload, PikePayload_Action as Action,
-    UpdateAgentAction, UpdateOrganizationAction,
+    CreateAgentAction, CreateOrganizationAction, CreateRoleAction, DeleteAgentAction,
+    DeleteOrganizationAction, DeleteRoleAction, PikePayload, PikePayload_Action as Action,
+    RotateAgentKeyAction, UpdateAgentAction, UpdateOrganizationAction, UpdateRoleAction,
+};
+use grid_sdk::protos::pike_state::{
+    Agent, AgentList, Organization, OrganizationList, Role, RoleList,
 };
-use grid_sdk::protos::pike_state::{Agent, AgentList, Organization, OrganizationList};
 
 pub struct PikeTransactionHandler {
     family_name: String,
@@ -44,15 +48,6 @@ pub struct PikeTransactionHandler {
     namespaces: Vec<String>,
 }
 
-const NAMESPACE: &str = "cad11d";
-
-fn compute_address(name: &str, resource: Resource) -> String {
-    let mut sha = Sha512::new();
-    sha.input(name.as_bytes());
-
-    String::from(NAMESPACE) + &resource_to_byte(resource) + &sha.result_str()[..62].to_string()
-}
-
 pub struct PikeState<'a> {
     context: &'a mut dyn TransactionContext,
 }
@@ -63,7 +58,7 @@ impl<'a> PikeState<'a> {
     }
 
     pub fn get_agent(&mut self, public_key: &str) -> Result<Option<Agent>, ApplyError> {
-        let address = compute_address(public_key, Resource::AGENT);
+        let address = compute_agent_address(public_key);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -89,7 +84,7 @@ impl<'a> PikeState<'a> {
     }
 
     pub fn set_agent(&mut self, public_key: &str, new_agent: Agent) -> Result<(), ApplyError> {
-        let address = compute_address(public_key, Resource::AGENT);
+        let address = compute_agent_address(public_key);
         let d = self.context.get_state_entry(&address)?;
         let mut agent_list = match d {
             Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
@@ -132,8 +127,48 @@ impl<'a> PikeState<'a> {
         Ok(())
     }
 
+    pub fn remove_agent(&mut self, public_key: &str) -> Result<(), ApplyError> {
+        let address = compute_agent_address(public_key);
+        let d = self.context.get_state_entry(&address)?;
+        let mut agent_list: AgentList = match d {
+            Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
+                Ok(agents) => agents,
+                Err(err) => {
+                    return Err(ApplyError::InternalError(format!(
+                        "Cannot deserialize agent list: {}",
+                        err,
+                    )))
+                }
+            },
+            None => return Ok(()),
+        };
+
+        agent_list.agents.retain(|a| a.public_key != public_key);
+
+        // If the agent was the only one at the address, remove the whole state entry
+        if agent_list.agents.is_empty() {
+            self.context
+                .delete_state_entries(&[address])
+                .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+            return Ok(());
+        }
+
+        let serialized = match protobuf::Message::write_to_bytes(&agent_list) {
+            Ok(serialized) => serialized,
+            Err(_) => {
+                return Err(ApplyError::InternalError(String::from(
+                    "Cannot serialize agent list",
+                )))
+            }
+        };
+        self.context
+            .set_state_entry(address, serialized)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        Ok(())
+    }
+
     pub fn get_organization(&mut self, id: &str) -> Result<Option<Organization>, ApplyError> {
-        let address = compute_address(id, Resource::ORG);
+        let address = compute_organization_address(id);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -163,7 +198,7 @@ impl<'a> PikeState<'a> {
         id: &str,
         new_organization: Organization,
     ) -> Result<(), ApplyError> {
-        let address = compute_address(id, Resource::ORG);
+        let address = compute_organization_address(id);
         let d = self.context.get_state_entry(&address)?;
         let mut organization_list = match d {
             Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
@@ -208,6 +243,123 @@ impl<'a> PikeState<'a> {
             .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
         Ok(())
     }
+
+    pub fn remove_organization(&mut self, id: &str) -> Result<(), ApplyError> {
+        let address = compute_organization_address(id);
+        let d = self.context.get_state_entry(&address)?;
+        let mut organization_list: OrganizationList = match d {
+            Some(packed) => match protobuf::parse_from_bytes(packed.as_slice()) {
+                Ok(orgs) => orgs,
+                Err(err) => {
+                    return Err(ApplyError::InternalError(format!(
+                        "Cannot deserialize organization list: {}",
+                        err,
+                    )))
+                }
+            },
+            None => return Ok(()),
+        };
+
+        organization_list.organizations.retain(|o| o.org_id != id);
+
+        // If the organization was the only one at the address, remove the whole state entry
+        if organization_list.organizations.is_empty() {
+            self.context
+                .delete_state_entries(&[address])
+                .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+            return Ok(());
+        }
+
+        let serialized = match protobuf::Message::write_to_bytes(&organization_list) {
+            Ok(serialized) => serialized,
+            Err(_) => {
+                return Err(ApplyError::InternalError(String::from(
+                    "Cannot serialize organization list",
+                )))
+            }
+        };
+        self.context
+            .set_state_entry(address, serialized)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        Ok(())
+    }
+
+    pub fn get_role(&mut self, org_id: &str, name: &str) -> Result<Option<Role>, ApplyError> {
+        let address = compute_role_address(org_id, name);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let roles: RoleList = match protobuf::parse_from_bytes(packed.as_slice()) {
+                    Ok(roles) => roles,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize role list: {:?}",
+                            err,
+                        )))
+                    }
+                };
+
+                for role in roles.get_roles() {
+                    if role.org_id == org_id && role.name == name {
+                        return Ok(Some(role.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn set_role(&mut self, new_role: Role) -> Result<(), ApplyError> {
+        let address = compute_role_address(&new_role.org_id, &new_role.name);
+        let mut role_list = self.get_role_list(&address)?;
+
+        // replace the old role if it exists and keep the roles sorted by name
+        role_list
+            .roles
+            .retain(|r| r.org_id != new_role.org_id || r.name != new_role.name);
+        role_list.roles.push(new_role);
+        role_list.roles.sort_by_key(|r| r.clone().name);
+
+        self.set_role_list(address, role_list)
+    }
+
+    pub fn remove_role(&mut self, org_id: &str, name: &str) -> Result<(), ApplyError> {
+        let address = compute_role_address(org_id, name);
+        let mut role_list = self.get_role_list(&address)?;
+
+        role_list
+            .roles
+            .retain(|r| r.org_id != org_id || r.name != name);
+
+        // If the role was the only one at the address, remove the whole state entry
+        if role_list.roles.is_empty() {
+            self.context
+                .delete_state_entries(&[address])
+                .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+            return Ok(());
+        }
+
+        self.set_role_list(address, role_list)
+    }
+
+    fn get_role_list(&mut self, address: &str) -> Result<RoleList, ApplyError> {
+        match self.context.get_state_entry(address)? {
+            Some(packed) => protobuf::parse_from_bytes(packed.as_slice()).map_err(|err| {
+                ApplyError::InternalError(format!("Cannot deserialize role list: {}", err))
+            }),
+            None => Ok(RoleList::new()),
+        }
+    }
+
+    fn set_role_list(&mut self, address: String, role_list: RoleList) -> Result<(), ApplyError> {
+        let serialized = protobuf::Message::write_to_bytes(&role_list)
+            .map_err(|_| ApplyError::InternalError(String::from("Cannot serialize role list")))?;
+        self.context
+            .set_state_entry(address, serialized)
+            .map_err(|err| ApplyError::InternalError(format!("{}", err)))?;
+        Ok(())
+    }
 }
 
 impl PikeTransactionHandler {
@@ -216,7 +368,7 @@ impl PikeTransactionHandler {
         PikeTransactionHandler {
             family_name: "pike".to_string(),
             family_versions: vec!["0.1".to_string()],
-            namespaces: vec![NAMESPACE.to_string()],
+            namespaces: vec![PIKE_NAMESPACE.to_string()],
         }
     }
 }
@@ -250,12 +402,22 @@ impl TransactionHandler for PikeTransactionHandler {
         match payload.action {
             Action::CREATE_AGENT => create_agent(payload.get_create_agent(), signer, &mut state),
             Action::UPDATE_AGENT => update_agent(payload.get_update_agent(), signer, &mut state),
+            Action::DELETE_AGENT => delete_agent(payload.get_delete_agent(), signer, &mut state),
+            Action::ROTATE_AGENT_KEY => {
+                rotate_agent_key(payload.get_rotate_agent_key(), signer, &mut state)
+            }
             Action::CREATE_ORGANIZATION => {
                 create_org(payload.get_create_organization(), signer, &mut state)
             }
             Action::UPDATE_ORGANIZATION => {
                 update_org(payload.get_update_organization(), signer, &mut state)
             }
+            Action::DELETE_ORGANIZATION => {
+                delete_org(payload.get_delete_organization(), signer, &mut state)
+            }
+            Action::CREATE_ROLE => create_role(payload.get_create_role(), signer, &mut state),
+            Action::UPDATE_ROLE => update_role(payload.get_update_role(), signer, &mut state),
+            Action::DELETE_ROLE => delete_role(payload.get_delete_role(), signer, &mut state),
             _ => Err(ApplyError::InvalidTransaction("Invalid action".into())),
         }
     }
@@ -309,7 +471,15 @@ fn create_agent(
 
     state
         .set_agent(payload.get_public_key(), agent)
-        .map_err(|e| ApplyError::InternalError(format!("Failed to create agent: {:?}", e)))
+        .map_err(|e| ApplyError::InternalError(format!("Failed to create agent: {:?}", e)))?;
+
+    let mut organization = get_existing_organization(payload.get_org_id(), state)?;
+    if !organization.get_agents_counted() {
+        return Ok(());
+    }
+    let agent_count = organization.get_agent_count();
+    organization.set_agent_count(agent_count + 1);
+    state.set_organization(payload.get_org_id(), organization)
 }
 
 fn update_agent(
@@ -383,6 +553,118 @@ fn update_agent(
         .map_err(|e| ApplyError::InternalError(format!("Failed to create agent: {:?}", e)))
 }
 
+fn delete_agent(
+    payload: &DeleteAgentAction,
+    signer: &str,
+    state: &mut PikeState,
+) -> Result<(), ApplyError> {
+    if payload.get_public_key().is_empty() {
+        return Err(ApplyError::InvalidTransaction("Public key required".into()));
+    }
+
+    if payload.get_org_id().is_empty() {
+        return Err(ApplyError::InvalidTransaction(
+            "Organization ID required".into(),
+        ));
+    }
+
+    // verify the signer of the transaction is authorized to delete agent
+    is_admin(signer, payload.get_org_id(), state)?;
+
+    if signer == payload.get_public_key() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Admin may not delete themselves: {}",
+            signer,
+        )));
+    }
+
+    let agent = get_existing_agent(payload.get_public_key(), state)?;
+    if agent.get_org_id() != payload.get_org_id() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Agent {} is not associated with the organization: {}",
+            payload.get_public_key(),
+            payload.get_org_id(),
+        )));
+    }
+
+    state
+        .remove_agent(payload.get_public_key())
+        .map_err(|e| ApplyError::InternalError(format!("Failed to delete agent: {:?}", e)))?;
+
+    let mut organization = get_existing_organization(payload.get_org_id(), state)?;
+    if !organization.get_agents_counted() {
+        return Ok(());
+    }
+    let agent_count = organization
+        .get_agent_count()
+        .checked_sub(1)
+        .ok_or_else(|| {
+            ApplyError::InvalidTransaction(format!(
+                "Agent count of organization {} does not match its agents",
+                payload.get_org_id(),
+            ))
+        })?;
+    organization.set_agent_count(agent_count);
+    state.set_organization(payload.get_org_id(), organization)
+}
+
+fn rotate_agent_key(
+    payload: &RotateAgentKeyAction,
+    signer: &str,
+    state: &mut PikeState,
+) -> Result<(), ApplyError> {
+    if payload.get_public_key().is_empty() || payload.get_new_public_key().is_empty() {
+        return Err(ApplyError::InvalidTransaction(
+            "Current and new public keys required".into(),
+        ));
+    }
+
+    if payload.get_org_id().is_empty() {
+        return Err(ApplyError::InvalidTransaction(
+            "Organization ID required".into(),
+        ));
+    }
+
+    // verify the signer of the transaction is authorized to approve the rotation
+    is_admin(signer, payload.get_org_id(), state)?;
+
+    let mut agent = get_existing_agent(payload.get_public_key(), state)?;
+    if agent.get_org_id() != payload.get_org_id() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Agent {} is not associated with the organization: {}",
+            payload.get_public_key(),
+            payload.get_org_id(),
+        )));
+    }
+
+    // Check the new key is not already in use by another agent
+    match state.get_agent(payload.get_new_public_key()) {
+        Ok(None) => (),
+        Ok(Some(_)) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Agent already exists: {}",
+                payload.get_new_public_key(),
+            )))
+        }
+        Err(err) => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Failed to retrieve state: {}",
+                err,
+            )))
+        }
+    };
+
+    // the roles, metadata and active flag carry over to the new key unchanged
+    agent.set_public_key(payload.get_new_public_key().to_string());
+    state
+        .set_agent(payload.get_new_public_key(), agent)
+        .map_err(|e| ApplyError::InternalError(format!("Failed to rotate agent key: {:?}", e)))?;
+
+    state
+        .remove_agent(payload.get_public_key())
+        .map_err(|e| ApplyError::InternalError(format!("Failed to rotate agent key: {:?}", e)))
+}
+
 fn create_org(
     payload: &CreateOrganizationAction,
     signer: &str,
@@ -423,6 +705,9 @@ fn create_org(
     organization.set_metadata(protobuf::RepeatedField::from_vec(
         payload.get_metadata().to_vec(),
     ));
+    // the signer becomes the organization's first agent below
+    organization.set_agent_count(1);
+    organization.set_agents_counted(true);
     state.set_organization(payload.get_id(), organization)?;
 
     state.get_agent(signer).map_err(|e| {
@@ -503,6 +788,252 @@ fn update_org(
     state.set_organization(payload.get_id(), organization)
 }
 
+fn delete_org(
+    payload: &DeleteOrganizationAction,
+    signer: &str,
+    state: &mut PikeState,
+) -> Result<(), ApplyError> {
+    if payload.get_id().is_empty() {
+        return Err(ApplyError::InvalidTransaction(
+            "Unique organization ID required".into(),
+        ));
+    }
+
+    // verify the signer of the transaction is authorized to delete organization
+    is_admin(signer, payload.get_id(), state)?;
+
+    let organization = get_existing_organization(payload.get_id(), state)?;
+
+    // Agents cannot be enumerated from state, so the agents of an organization created before
+    // they were counted can never be proven to have all been deleted
+    if !organization.get_agents_counted() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Organization {} was created before its agents were counted and cannot be deleted",
+            payload.get_id(),
+        )));
+    }
+
+    // The signing admin is removed along with the organization; every other
+    // agent must be deleted first so none are left pointing at a missing org.
+    if organization.get_agent_count() != 1 {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Organization {} still has agents other than the signer; delete them first",
+            payload.get_id(),
+        )));
+    }
+
+    // Roles are removed with the organization so that they, and any grants of them to other
+    // organizations, do not come back if the organization ID is used again
+    for name in organization.get_roles() {
+        state
+            .remove_role(payload.get_id(), name)
+            .map_err(|e| ApplyError::InternalError(format!("Failed to delete role: {:?}", e)))?;
+    }
+
+    state.remove_organization(payload.get_id()).map_err(|e| {
+        ApplyError::InternalError(format!("Failed to delete organization: {:?}", e))
+    })?;
+
+    state
+        .remove_agent(signer)
+        .map_err(|e| ApplyError::InternalError(format!("Failed to delete agent: {:?}", e)))
+}
+
+fn create_role(
+    payload: &CreateRoleAction,
+    signer: &str,
+    state: &mut PikeState,
+) -> Result<(), ApplyError> {
+    validate_role_name(payload.get_org_id(), payload.get_name())?;
+
+    // verify the signer of the transaction is authorized to create roles
+    is_admin(signer, payload.get_org_id(), state)?;
+
+    if state
+        .get_role(payload.get_org_id(), payload.get_name())?
+        .is_some()
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Role already exists: {}.{}",
+            payload.get_org_id(),
+            payload.get_name(),
+        )));
+    }
+
+    validate_permissions(payload.get_permissions())?;
+    validate_allowed_organizations(payload.get_allowed_organizations(), state)?;
+
+    let mut role = Role::new();
+    role.set_org_id(payload.get_org_id().to_string());
+    role.set_name(payload.get_name().to_string());
+    role.set_description(payload.get_description().to_string());
+    role.set_permissions(protobuf::RepeatedField::from_vec(
+        payload.get_permissions().to_vec(),
+    ));
+    role.set_allowed_organizations(protobuf::RepeatedField::from_vec(
+        payload.get_allowed_organizations().to_vec(),
+    ));
+    role.set_active(payload.get_active());
+
+    state
+        .set_role(role)
+        .map_err(|e| ApplyError::InternalError(format!("Failed to create role: {:?}", e)))?;
+
+    let mut organization = get_existing_organization(payload.get_org_id(), state)?;
+    organization
+        .mut_roles()
+        .push(payload.get_name().to_string());
+    state.set_organization(payload.get_org_id(), organization)
+}
+
+fn update_role(
+    payload: &UpdateRoleAction,
+    signer: &str,
+    state: &mut PikeState,
+) -> Result<(), ApplyError> {
+    validate_role_name(payload.get_org_id(), payload.get_name())?;
+
+    // verify the signer of the transaction is authorized to update roles
+    is_admin(signer, payload.get_org_id(), state)?;
+
+    let mut role = match state.get_role(payload.get_org_id(), payload.get_name())? {
+        Some(role) => role,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Role does not exist: {}.{}",
+                payload.get_org_id(),
+                payload.get_name(),
+            )))
+        }
+    };
+
+    validate_permissions(payload.get_permissions())?;
+    validate_allowed_organizations(payload.get_allowed_organizations(), state)?;
+
+    role.set_description(payload.get_description().to_string());
+    role.set_permissions(protobuf::RepeatedField::from_vec(
+        payload.get_permissions().to_vec(),
+    ));
+    role.set_allowed_organizations(protobuf::RepeatedField::from_vec(
+        payload.get_allowed_organizations().to_vec(),
+    ));
+    role.set_active(payload.get_active());
+
+    state
+        .set_role(role)
+        .map_err(|e| ApplyError::InternalError(format!("Failed to update role: {:?}", e)))
+}
+
+fn delete_role(
+    payload: &DeleteRoleAction,
+    signer: &str,
+    state: &mut PikeState,
+) -> Result<(), ApplyError> {
+    validate_role_name(payload.get_org_id(), payload.get_name())?;
+
+    // verify the signer of the transaction is authorized to delete roles
+    is_admin(signer, payload.get_org_id(), state)?;
+
+    if state
+        .get_role(payload.get_org_id(), payload.get_name())?
+        .is_none()
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Role does not exist: {}.{}",
+            payload.get_org_id(),
+            payload.get_name(),
+        )));
+    }
+
+    state
+        .remove_role(payload.get_org_id(), payload.get_name())
+        .map_err(|e| ApplyError::InternalError(format!("Failed to delete role: {:?}", e)))?;
+
+    let mut organization = get_existing_organization(payload.get_org_id(), state)?;
+    organization
+        .mut_roles()
+        .retain(|name| name != payload.get_name());
+    state.set_organization(payload.get_org_id(), organization)
+}
+
+fn validate_role_name(org_id: &str, name: &str) -> Result<(), ApplyError> {
+    if org_id.is_empty() {
+        return Err(ApplyError::InvalidTransaction(
+            "Organization ID required".into(),
+        ));
+    }
+
+    if name.is_empty() {
+        return Err(ApplyError::InvalidTransaction("Role name required".into()));
+    }
+
+    // agents refer to other organizations' roles as "<org_id>.<name>"
+    if name.contains('.') {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Role name may not contain '.': {}",
+            name,
+        )));
+    }
+
+    Ok(())
+}
+
+// Only an agent's own roles make it an admin, so that another organization's role cannot be used
+// to become an admin of the agent's organization
+fn validate_permissions(permissions: &[String]) -> Result<(), ApplyError> {
+    if permissions
+        .iter()
+        .any(|permission| permission == ADMIN_PERMISSION)
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Roles may not grant the '{}' permission",
+            ADMIN_PERMISSION,
+        )));
+    }
+    Ok(())
+}
+
+fn validate_allowed_organizations(
+    allowed_organizations: &[String],
+    state: &mut PikeState,
+) -> Result<(), ApplyError> {
+    for org_id in allowed_organizations {
+        get_existing_organization(org_id, state)?;
+    }
+    Ok(())
+}
+
+fn get_existing_agent(public_key: &str, state: &mut PikeState) -> Result<Agent, ApplyError> {
+    match state.get_agent(public_key) {
+        Ok(None) => Err(ApplyError::InvalidTransaction(format!(
+            "Agent does not exists: {}",
+            public_key,
+        ))),
+        Ok(Some(agent)) => Ok(agent),
+        Err(err) => Err(ApplyError::InvalidTransaction(format!(
+            "Failed to retrieve state: {}",
+            err,
+        ))),
+    }
+}
+
+fn get_existing_organization(
+    org_id: &str,
+    state: &mut PikeState,
+) -> Result<Organization, ApplyError> {
+    match state.get_organization(org_id) {
+        Ok(None) => Err(ApplyError::InvalidTransaction(format!(
+            "Organization does not exist: {}",
+            org_id,
+        ))),
+        Ok(Some(org)) => Ok(org),
+        Err(err) => Err(ApplyError::InvalidTransaction(format!(
+            "Failed to retrieve state: {}",
+            err,
+        ))),
+    }
+}
+
 pub fn is_admin(signer: &str, org_id: &str, state: &mut PikeState) -> Result<(), ApplyError> {
     let admin = match state.get_agent(signer) {
         Ok(None) => {
@@ -563,3 +1094,486 @@ fn apply(
 pub unsafe fn entrypoint(payload: WasmPtr, signer: WasmPtr, signature: WasmPtr) -> i32 {
     execute_entrypoint(payload, signer, signature, apply)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    use grid_sdk::protos::pike_state::KeyValueEntry;
+
+    use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
+
+    const ADMIN_KEY: &str = "admin_key";
+    const AGENT_KEY: &str = "agent_key";
+    const NEW_AGENT_KEY: &str = "new_agent_key";
+    const ORG_ID: &str = "test_org";
+
+    #[derive(Default, Debug)]
+    struct MockTransactionContext {
+        state: RefCell<HashMap<String, Vec<u8>>>,
+    }
+
+    impl TransactionContext for MockTransactionContext {
+        fn get_state_entries(
+            &self,
+            addresses: &[String],
+        ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+            let mut results = Vec::new();
+            for addr in addresses {
+                if let Some(data) = self.state.borrow().get(addr) {
+                    results.push((addr.to_string(), data.clone()));
+                }
+            }
+            Ok(results)
+        }
+
+        fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+            for (addr, data) in entries {
+                self.state.borrow_mut().insert(addr, data);
+            }
+            Ok(())
+        }
+
+        fn delete_state_entries(&self, addresses: &[String]) -> Result<Vec<String>, ContextError> {
+            for addr in addresses {
+                self.state.borrow_mut().remove(addr);
+            }
+            Ok(addresses.to_vec())
+        }
+
+        /// this is not needed for these tests
+        fn add_receipt_data(&self, _data: &[u8]) -> Result<(), ContextError> {
+            unimplemented!()
+        }
+
+        /// this is not needed for these tests
+        fn add_event(
+            &self,
+            _event_type: String,
+            _attributes: Vec<(String, String)>,
+            _data: &[u8],
+        ) -> Result<(), ContextError> {
+            unimplemented!()
+        }
+    }
+
+    fn make_org(org_id: &str, signer: &str, state: &mut PikeState) {
+        let mut payload = CreateOrganizationAction::new();
+        payload.set_id(org_id.to_string());
+        payload.set_name("test org".to_string());
+
+        create_org(&payload, signer, state).expect("Failed to create organization");
+    }
+
+    fn make_agent(public_key: &str, roles: &[&str], state: &mut PikeState) {
+        let mut metadata = KeyValueEntry::new();
+        metadata.set_key("email".to_string());
+        metadata.set_value("agent@example.com".to_string());
+
+        let mut payload = CreateAgentAction::new();
+        payload.set_org_id(ORG_ID.to_string());
+        payload.set_public_key(public_key.to_string());
+        payload.set_active(true);
+        payload.set_roles(protobuf::RepeatedField::from_vec(
+            roles.iter().map(|role| role.to_string()).collect(),
+        ));
+        payload.set_metadata(protobuf::RepeatedField::from_vec(vec![metadata]));
+
+        create_agent(&payload, ADMIN_KEY, state).expect("Failed to create agent");
+    }
+
+    fn delete_agent_action(public_key: &str) -> DeleteAgentAction {
+        let mut payload = DeleteAgentAction::new();
+        payload.set_org_id(ORG_ID.to_string());
+        payload.set_public_key(public_key.to_string());
+        payload
+    }
+
+    fn delete_org_action(org_id: &str) -> DeleteOrganizationAction {
+        let mut payload = DeleteOrganizationAction::new();
+        payload.set_id(org_id.to_string());
+        payload
+    }
+
+    fn assert_invalid_transaction(result: Result<(), ApplyError>, message: &str) {
+        match result {
+            Ok(()) => panic!("Should have gotten an InvalidTransaction error"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(message), "Unexpected error: {}", err)
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that deleting an agent removes it from state and from its organization's count
+    fn test_delete_agent() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+        make_agent(AGENT_KEY, &[], &mut state);
+        assert_eq!(
+            state
+                .get_organization(ORG_ID)
+                .unwrap()
+                .unwrap()
+                .get_agent_count(),
+            2
+        );
+
+        delete_agent(&delete_agent_action(AGENT_KEY), ADMIN_KEY, &mut state)
+            .expect("Failed to delete agent");
+
+        assert!(state.get_agent(AGENT_KEY).unwrap().is_none());
+        assert_eq!(
+            state
+                .get_organization(ORG_ID)
+                .unwrap()
+                .unwrap()
+                .get_agent_count(),
+            1
+        );
+    }
+
+    #[test]
+    /// Test that an admin may not delete themselves, and that only an admin may delete agents
+    fn test_delete_agent_not_allowed() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+        make_agent(AGENT_KEY, &[], &mut state);
+
+        assert_invalid_transaction(
+            delete_agent(&delete_agent_action(ADMIN_KEY), ADMIN_KEY, &mut state),
+            "Admin may not delete themselves",
+        );
+        assert_invalid_transaction(
+            delete_agent(&delete_agent_action(ADMIN_KEY), AGENT_KEY, &mut state),
+            "Signer is not an admin",
+        );
+    }
+
+    #[test]
+    /// Test that rotating an agent's key removes the old key and binds the new key to an agent
+    /// with the same organization, roles and metadata
+    fn test_rotate_agent_key() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+        make_agent(AGENT_KEY, &["can_create_product"], &mut state);
+        let agent = state.get_agent(AGENT_KEY).unwrap().unwrap();
+
+        let mut payload = RotateAgentKeyAction::new();
+        payload.set_org_id(ORG_ID.to_string());
+        payload.set_public_key(AGENT_KEY.to_string());
+        payload.set_new_public_key(NEW_AGENT_KEY.to_string());
+        rotate_agent_key(&payload, ADMIN_KEY, &mut state).expect("Failed to rotate agent key");
+
+        assert!(state.get_agent(AGENT_KEY).unwrap().is_none());
+        let rotated = state.get_agent(NEW_AGENT_KEY).unwrap().unwrap();
+        assert_eq!(rotated.get_org_id(), ORG_ID);
+        assert_eq!(rotated.get_roles(), agent.get_roles());
+        assert_eq!(rotated.get_metadata(), agent.get_metadata());
+        assert_eq!(rotated.get_active(), agent.get_active());
+    }
+
+    #[test]
+    /// Test that an agent's key may not be rotated to a key already bound to an agent
+    fn test_rotate_agent_key_taken() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+        make_agent(AGENT_KEY, &[], &mut state);
+        make_agent(NEW_AGENT_KEY, &[], &mut state);
+
+        let mut payload = RotateAgentKeyAction::new();
+        payload.set_org_id(ORG_ID.to_string());
+        payload.set_public_key(AGENT_KEY.to_string());
+        payload.set_new_public_key(NEW_AGENT_KEY.to_string());
+
+        assert_invalid_transaction(
+            rotate_agent_key(&payload, ADMIN_KEY, &mut state),
+            "Agent already exists",
+        );
+        assert!(state.get_agent(AGENT_KEY).unwrap().is_some());
+    }
+
+    #[test]
+    /// Test that an organization cannot be deleted while it has agents other than the signing
+    /// admin, and that deleting it removes the organization, the admin and its roles
+    fn test_delete_org() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+        make_agent(AGENT_KEY, &[], &mut state);
+
+        let mut role = CreateRoleAction::new();
+        role.set_org_id(ORG_ID.to_string());
+        role.set_name("shipper".to_string());
+        role.set_active(true);
+        create_role(&role, ADMIN_KEY, &mut state).expect("Failed to create role");
+
+        assert_invalid_transaction(
+            delete_org(&delete_org_action(ORG_ID), ADMIN_KEY, &mut state),
+            "still has agents other than the signer",
+        );
+
+        delete_agent(&delete_agent_action(AGENT_KEY), ADMIN_KEY, &mut state)
+            .expect("Failed to delete agent");
+        delete_org(&delete_org_action(ORG_ID), ADMIN_KEY, &mut state)
+            .expect("Failed to delete organization");
+
+        assert!(state.get_organization(ORG_ID).unwrap().is_none());
+        assert!(state.get_agent(ADMIN_KEY).unwrap().is_none());
+        assert!(state.get_role(ORG_ID, "shipper").unwrap().is_none());
+    }
+
+    #[test]
+    /// Test that an organization created before its agents were counted cannot be deleted, as
+    /// its agents cannot be proven to have all been deleted
+    fn test_delete_org_agents_not_counted() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+
+        let mut organization = state.get_organization(ORG_ID).unwrap().unwrap();
+        organization.set_agent_count(0);
+        organization.set_agents_counted(false);
+        state.set_organization(ORG_ID, organization).unwrap();
+
+        assert_invalid_transaction(
+            delete_org(&delete_org_action(ORG_ID), ADMIN_KEY, &mut state),
+            "was created before its agents were counted",
+        );
+
+        // Agents added to the organization leave it uncounted
+        make_agent(AGENT_KEY, &[], &mut state);
+        let organization = state.get_organization(ORG_ID).unwrap().unwrap();
+        assert!(!organization.get_agents_counted());
+        assert_eq!(organization.get_agent_count(), 0);
+    }
+
+    fn create_role_action(name: &str, permissions: &[&str], allowed: &[&str]) -> CreateRoleAction {
+        let mut payload = CreateRoleAction::new();
+        payload.set_org_id(ORG_ID.to_string());
+        payload.set_name(name.to_string());
+        payload.set_permissions(protobuf::RepeatedField::from_vec(
+            permissions.iter().map(|p| p.to_string()).collect(),
+        ));
+        payload.set_allowed_organizations(protobuf::RepeatedField::from_vec(
+            allowed.iter().map(|org_id| org_id.to_string()).collect(),
+        ));
+        payload.set_active(true);
+        payload
+    }
+
+    fn update_role_action(name: &str, permissions: &[&str], allowed: &[&str]) -> UpdateRoleAction {
+        let mut payload = UpdateRoleAction::new();
+        payload.set_org_id(ORG_ID.to_string());
+        payload.set_name(name.to_string());
+        payload.set_permissions(protobuf::RepeatedField::from_vec(
+            permissions.iter().map(|p| p.to_string()).collect(),
+        ));
+        payload.set_allowed_organizations(protobuf::RepeatedField::from_vec(
+            allowed.iter().map(|org_id| org_id.to_string()).collect(),
+        ));
+        payload.set_active(true);
+        payload
+    }
+
+    fn delete_role_action(name: &str) -> DeleteRoleAction {
+        let mut payload = DeleteRoleAction::new();
+        payload.set_org_id(ORG_ID.to_string());
+        payload.set_name(name.to_string());
+        payload
+    }
+
+    #[test]
+    /// Test that creating, updating and deleting a role updates the role in state and the
+    /// organization's list of roles
+    fn test_role_lifecycle() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+        make_org("other_org", "other_admin_key", &mut state);
+
+        create_role(
+            &create_role_action("shipper", &["can_create_product"], &[]),
+            ADMIN_KEY,
+            &mut state,
+        )
+        .expect("Failed to create role");
+        let role = state.get_role(ORG_ID, "shipper").unwrap().unwrap();
+        assert_eq!(role.get_permissions(), ["can_create_product"]);
+        assert_eq!(
+            state.get_organization(ORG_ID).unwrap().unwrap().get_roles(),
+            ["shipper"]
+        );
+
+        update_role(
+            &update_role_action("shipper", &["can_update_product"], &["other_org"]),
+            ADMIN_KEY,
+            &mut state,
+        )
+        .expect("Failed to update role");
+        let role = state.get_role(ORG_ID, "shipper").unwrap().unwrap();
+        assert_eq!(role.get_permissions(), ["can_update_product"]);
+        assert_eq!(role.get_allowed_organizations(), ["other_org"]);
+
+        delete_role(&delete_role_action("shipper"), ADMIN_KEY, &mut state)
+            .expect("Failed to delete role");
+        assert!(state.get_role(ORG_ID, "shipper").unwrap().is_none());
+        assert!(state
+            .get_organization(ORG_ID)
+            .unwrap()
+            .unwrap()
+            .get_roles()
+            .is_empty());
+    }
+
+    #[test]
+    /// Test that only an admin of the organization may create, update or delete its roles
+    fn test_role_requires_admin() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+        make_org("other_org", "other_admin_key", &mut state);
+        make_agent(AGENT_KEY, &["can_create_product"], &mut state);
+
+        assert_invalid_transaction(
+            create_role(
+                &create_role_action("shipper", &[], &[]),
+                AGENT_KEY,
+                &mut state,
+            ),
+            "Signer is not an admin",
+        );
+        assert_invalid_transaction(
+            create_role(
+                &create_role_action("shipper", &[], &[]),
+                "other_admin_key",
+                &mut state,
+            ),
+            "Signer is not associated with the organization",
+        );
+
+        create_role(
+            &create_role_action("shipper", &[], &[]),
+            ADMIN_KEY,
+            &mut state,
+        )
+        .expect("Failed to create role");
+        assert_invalid_transaction(
+            update_role(
+                &update_role_action("shipper", &[], &[]),
+                AGENT_KEY,
+                &mut state,
+            ),
+            "Signer is not an admin",
+        );
+        assert_invalid_transaction(
+            delete_role(&delete_role_action("shipper"), AGENT_KEY, &mut state),
+            "Signer is not an admin",
+        );
+    }
+
+    #[test]
+    /// Test that a role may not be created twice, and that a missing role may not be updated or
+    /// deleted
+    fn test_role_duplicate_and_missing() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+
+        create_role(
+            &create_role_action("shipper", &[], &[]),
+            ADMIN_KEY,
+            &mut state,
+        )
+        .expect("Failed to create role");
+        assert_invalid_transaction(
+            create_role(
+                &create_role_action("shipper", &[], &[]),
+                ADMIN_KEY,
+                &mut state,
+            ),
+            "Role already exists",
+        );
+        assert_eq!(
+            state.get_organization(ORG_ID).unwrap().unwrap().get_roles(),
+            ["shipper"]
+        );
+
+        assert_invalid_transaction(
+            update_role(
+                &update_role_action("receiver", &[], &[]),
+                ADMIN_KEY,
+                &mut state,
+            ),
+            "Role does not exist",
+        );
+        assert_invalid_transaction(
+            delete_role(&delete_role_action("receiver"), ADMIN_KEY, &mut state),
+            "Role does not exist",
+        );
+    }
+
+    #[test]
+    /// Test that role names, permissions and allowed organizations are validated
+    fn test_role_validation() {
+        let mut transaction_context = MockTransactionContext::default();
+        let mut state = PikeState::new(&mut transaction_context);
+        make_org(ORG_ID, ADMIN_KEY, &mut state);
+
+        assert_invalid_transaction(
+            create_role(
+                &create_role_action("ship.per", &[], &[]),
+                ADMIN_KEY,
+                &mut state,
+            ),
+            "Role name may not contain '.'",
+        );
+        assert_invalid_transaction(
+            create_role(
+                &create_role_action("shipper", &[], &["missing_org"]),
+                ADMIN_KEY,
+                &mut state,
+            ),
+            "Organization does not exist: missing_org",
+        );
+        assert_invalid_transaction(
+            create_role(
+                &create_role_action("shipper", &["admin"], &[]),
+                ADMIN_KEY,
+                &mut state,
+            ),
+            "Roles may not grant the 'admin' permission",
+        );
+
+        create_role(
+            &create_role_action("shipper", &[], &[]),
+            ADMIN_KEY,
+            &mut state,
+        )
+        .expect("Failed to create role");
+        assert_invalid_transaction(
+            update_role(
+                &update_role_action("shipper", &["admin"], &[]),
+                ADMIN_KEY,
+                &mut state,
+            ),
+            "Roles may not grant the 'admin' permission",
+        );
+        assert_invalid_transaction(
+            update_role(
+                &update_role_action("shipper", &[], &["missing_org"]),
+                ADMIN_KEY,
+                &mut state,
+            ),
+            "Organization does not exist: missing_org",
+        );
+    }
+}
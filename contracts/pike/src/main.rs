@@ -14,7 +14,6 @@
 
 #[macro_use]
 extern crate cfg_if;
-extern crate crypto;
 extern crate grid_sdk;
 extern crate protobuf;
 
@@ -39,7 +38,6 @@ cfg_if! {
     }
 }
 
-pub mod addresser;
 pub mod handler;
 
 //use sawtooth_sdk::processor::TransactionProcessor;
@@ -27,18 +27,28 @@ cfg_if! {
     }
 }
 
+use grid_sdk::gs1::{
+    gtin_has_company_prefix, parse_company_prefixes, validate_gtin, GS1_COMPANY_PREFIXES_KEY,
+};
 use grid_sdk::permissions::PermissionChecker;
+use grid_sdk::protocol::addressing::GRID_NAMESPACE;
 use grid_sdk::protocol::product::payload::{
-    Action, ProductCreateAction, ProductDeleteAction, ProductPayload, ProductUpdateAction,
+    Action, ProductCreateAction, ProductDeleteAction, ProductPayload, ProductSetStatusAction,
+    ProductTransferAction, ProductUpdateAction, TransferPhase,
+};
+use grid_sdk::protocol::product::state::{
+    Product, ProductBuilder, ProductNamespace, ProductStatus,
 };
-use grid_sdk::protocol::product::state::{ProductBuilder, ProductNamespace};
+use grid_sdk::protocol::schema::state::{PropertyValue, Schema};
+use grid_sdk::protocol::schema::validation;
 
 use grid_sdk::protos::FromBytes;
 
-use crate::addressing::*;
 use crate::payload::validate_payload;
 use crate::state::ProductState;
-use crate::validation::validate_gtin;
+
+/// The schema every GS1 product's properties are defined by
+const GS1_PRODUCT_SCHEMA: &str = "gs1_product";
 
 #[cfg(target_arch = "wasm32")]
 // Sabre apply must return a bool
@@ -74,7 +84,7 @@ impl ProductTransactionHandler {
         ProductTransactionHandler {
             family_name: "grid_product".to_string(),
             family_versions: vec!["1.0".to_string()],
-            namespaces: vec![get_product_prefix()],
+            namespaces: vec![GRID_NAMESPACE.to_string()],
         }
     }
 
@@ -88,6 +98,7 @@ impl ProductTransactionHandler {
         let product_id = payload.product_id();
         let owner = payload.owner();
         let product_namespace = payload.product_namespace();
+        let schema_name = payload.schema_name();
         let properties = payload.properties();
 
         // Check that the agent submitting the transactions exists in state
@@ -105,25 +116,16 @@ impl ProductTransactionHandler {
         check_permission(perm_checker, signer, "can_create_product")?;
 
         // Check if product exists in state
-        if state.get_product(product_id)?.is_some() {
+        if state
+            .get_product(product_namespace, schema_name, product_id)?
+            .is_some()
+        {
             return Err(ApplyError::InvalidTransaction(format!(
                 "Product already exists: {}",
                 product_id,
             )));
         }
 
-        // Check if the product namespace is a GS1 product
-        if product_namespace != &ProductNamespace::GS1 {
-            return Err(ApplyError::InvalidTransaction(
-                "Invalid product namespace enum for product".to_string(),
-            ));
-        }
-
-        // Check if product product_id is a valid gtin
-        if let Err(e) = validate_gtin(product_id) {
-            return Err(ApplyError::InvalidTransaction(e.to_string()));
-        }
-
         // Check that the organization ID exists in state
         let org = match state.get_organization(payload.owner())? {
             Some(org) => org,
@@ -144,25 +146,28 @@ impl ProductTransactionHandler {
             )));
         }
 
-        /* Check if the agents organization contain GS1 Company Prefix key in its metadata
-        (gs1_company_prefixes), and the prefix must match the company prefix in the product_id */
-        let metadata = org.metadata().to_vec();
-        let gs1_company_prefix_kv = match metadata
-            .iter()
-            .find(|kv| kv.key() == "gs1_company_prefixes")
-        {
-            Some(gs1_company_prefix_kv) => gs1_company_prefix_kv,
-            None => {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "The agents organization does not have the gs1_company_prefixes key in its metadata: {:?}",
-                    org.metadata()
-                )));
-            }
-        };
-        // If the 'gs1_company_prefixes' key is found
-        if gs1_company_prefix_kv.key().is_empty() {
-            // If the gtin identifer does not contain the organizations gs1 prefix
-            if !product_id.contains(gs1_company_prefix_kv.value()) {
+        if product_namespace == &ProductNamespace::GS1 {
+            /* Check if the agents organization contain GS1 Company Prefix key in its metadata
+            (gs1_company_prefixes), and one of the prefixes must match the company prefix in the
+            product_id */
+            let gs1_company_prefix_kv = match org
+                .metadata()
+                .iter()
+                .find(|kv| kv.key() == GS1_COMPANY_PREFIXES_KEY)
+            {
+                Some(gs1_company_prefix_kv) => gs1_company_prefix_kv,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "The agents organization does not have the gs1_company_prefixes key in its metadata: {:?}",
+                        org.metadata()
+                    )));
+                }
+            };
+            let gs1_company_prefixes = parse_company_prefixes(gs1_company_prefix_kv.value())
+                .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
+            if !gtin_has_company_prefix(product_id, &gs1_company_prefixes)
+                .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?
+            {
                 return Err(ApplyError::InvalidTransaction(format!(
                     "The agents organization does not own the GS1 company prefix in the GTIN product_id: {:?}",
                     org.metadata()
@@ -170,57 +175,26 @@ impl ProductTransactionHandler {
             }
         }
 
-        if payload.product_namespace() == &ProductNamespace::GS1 {
-            // Check if gs1 schema exists
-            let schema = if let Some(schema) = state.get_schema("gs1_product")? {
-                schema
-            } else {
-                return Err(ApplyError::InvalidTransaction(
-                    "gs1_product schema has not been defined".into(),
-                ));
-            };
-
-            // Check if properties in product are all a part of the gs1 schema
-            for property in payload.properties() {
-                if schema
-                    .properties()
-                    .iter()
-                    .all(|p| p.name() != property.name())
-                {
-                    return Err(ApplyError::InvalidTransaction(format!(
-                        "{} is not a property that is defined by the gs1 schema",
-                        property.name()
-                    )));
-                }
-            }
-
-            // Check if property has all required fields
-            for property in schema.properties().iter().filter(|p| *p.required()) {
-                if !payload
-                    .properties()
-                    .iter()
-                    .any(|p| p.name() == property.name() && p.data_type() == property.data_type())
-                {
-                    return Err(ApplyError::InvalidTransaction(format!(
-                        "Missing required field '{}' of type '{:?}'",
-                        property.name(),
-                        property.data_type()
-                    )));
-                }
-            }
-        }
+        // Check that the product's schema exists, that the product_id follows the format of its
+        // namespace and that the properties are all defined by the schema
+        let schema = get_product_schema(state, product_namespace, schema_name)?;
+        validate_product_id(&schema, product_namespace, product_id)?;
+        validate_properties(&schema, properties)?;
 
         let new_product = ProductBuilder::new()
             .with_product_id(product_id.to_string())
             .with_owner(owner.to_string())
             .with_product_namespace(product_namespace.clone())
+            .with_schema_name(schema_name.to_string())
             .with_properties(properties.to_vec())
+            .with_schema_version(*schema.version())
+            .with_status(payload.status().clone())
             .build()
             .map_err(|err| {
                 ApplyError::InvalidTransaction(format!("Cannot build product: {}", err))
             })?;
 
-        state.set_product(product_id, new_product)?;
+        state.set_product(new_product)?;
 
         Ok(())
     }
@@ -234,6 +208,7 @@ impl ProductTransactionHandler {
     ) -> Result<(), ApplyError> {
         let product_id = payload.product_id();
         let product_namespace = payload.product_namespace();
+        let schema_name = payload.schema_name();
         let properties = payload.properties();
 
         // Check that the agent submitting the transactions exists in state
@@ -250,15 +225,8 @@ impl ProductTransactionHandler {
         // Check signing agent's permission
         check_permission(perm_checker, signer, "can_update_product")?;
 
-        // Check if the product namespace is a GS1 product
-        if product_namespace != &ProductNamespace::GS1 {
-            return Err(ApplyError::InvalidTransaction(
-                "Invalid product namespace enum for product".to_string(),
-            ));
-        }
-
         // Check if product exists
-        let product = match state.get_product(product_id) {
+        let product = match state.get_product(product_namespace, schema_name, product_id) {
             Ok(Some(product)) => Ok(product),
             Ok(None) => Err(ApplyError::InvalidTransaction(format!(
                 "No product exists: {}",
@@ -274,63 +242,29 @@ impl ProductTransactionHandler {
             ));
         }
 
-        // Check if product product_id is a valid gtin
-        if let Err(e) = validate_gtin(product_id) {
-            return Err(ApplyError::InvalidTransaction(e.to_string()));
-        }
-
-        if payload.product_namespace() == &ProductNamespace::GS1 {
-            // Check if gs1 schema exists
-            let schema = if let Some(schema) = state.get_schema("gs1_product")? {
-                schema
-            } else {
-                return Err(ApplyError::InvalidTransaction(
-                    "gs1_product schema has not been defined".into(),
-                ));
-            };
-
-            // Check if properties in product are all a part of the gs1 schema
-            for property in payload.properties() {
-                if schema
-                    .properties()
-                    .iter()
-                    .all(|p| p.name() != property.name())
-                {
-                    return Err(ApplyError::InvalidTransaction(format!(
-                        "{} is not a property that is defined by the gs1 schema",
-                        property.name()
-                    )));
-                }
-            }
+        // Check that the product's lifecycle status allows the update
+        check_product_updatable(&product, properties)?;
 
-            // Check if property has all required fields
-            for property in schema.properties().iter().filter(|p| *p.required()) {
-                if !payload
-                    .properties()
-                    .iter()
-                    .any(|p| p.name() == property.name() && p.data_type() == property.data_type())
-                {
-                    return Err(ApplyError::InvalidTransaction(format!(
-                        "Missing required field '{}' of type '{:?}'",
-                        property.name(),
-                        property.data_type()
-                    )));
-                }
-            }
-        }
+        // Check if properties in product are all a part of the product's schema
+        let schema = get_product_schema(state, product_namespace, schema_name)?;
+        validate_properties(&schema, properties)?;
 
         // Handle updating the product
         let updated_product = ProductBuilder::new()
             .with_product_id(product_id.to_string())
             .with_owner(product.owner().to_string())
             .with_product_namespace(product_namespace.clone())
+            .with_schema_name(schema_name.to_string())
             .with_properties(properties.to_vec())
+            .with_pending_owner(product.pending_owner().to_string())
+            .with_schema_version(*schema.version())
+            .with_status(product.status().clone())
             .build()
             .map_err(|err| {
                 ApplyError::InvalidTransaction(format!("Cannot build product: {}", err))
             })?;
 
-        state.set_product(product_id, updated_product)?;
+        state.set_product(updated_product)?;
 
         Ok(())
     }
@@ -344,6 +278,7 @@ impl ProductTransactionHandler {
     ) -> Result<(), ApplyError> {
         let product_id = payload.product_id();
         let product_namespace = payload.product_namespace();
+        let schema_name = payload.schema_name();
 
         // Check that the agent submitting the transactions exists in state
         let agent = match state.get_agent(signer)? {
@@ -359,15 +294,8 @@ impl ProductTransactionHandler {
         // Check signing agent's permission
         check_permission(perm_checker, signer, "can_delete_product")?;
 
-        // Check if the product namespace is a GS1 product
-        if product_namespace != &ProductNamespace::GS1 {
-            return Err(ApplyError::InvalidTransaction(
-                "Invalid product namespace enum for product".to_string(),
-            ));
-        }
-
         // Check if product exists in state
-        let product = match state.get_product(product_id) {
+        let product = match state.get_product(product_namespace, schema_name, product_id) {
             Ok(Some(product)) => Ok(product),
             Ok(None) => Err(ApplyError::InvalidTransaction(format!(
                 "No product exists: {}",
@@ -377,8 +305,9 @@ impl ProductTransactionHandler {
         }?;
 
         // Check if product product_id is a valid gtin
-        if let Err(e) = validate_gtin(product_id) {
-            return Err(ApplyError::InvalidTransaction(e.to_string()));
+        if product_namespace == &ProductNamespace::GS1 {
+            validate_gtin(product_id)
+                .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
         }
 
         // Check that the owner of the products organization is the same as the agent trying to delete the product
@@ -388,8 +317,205 @@ impl ProductTransactionHandler {
             ));
         }
 
+        // Recalled products are kept so the recall can still be looked up
+        if product.status() == &ProductStatus::Recalled {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Recalled product {} cannot be deleted",
+                product_id
+            )));
+        }
+
         // Delete the product
-        state.remove_product(product_id)?;
+        state.remove_product(product_namespace, schema_name, product_id)?;
+        Ok(())
+    }
+
+    fn transfer_product(
+        &self,
+        payload: &ProductTransferAction,
+        state: &mut ProductState,
+        signer: &str,
+        perm_checker: &PermissionChecker,
+    ) -> Result<(), ApplyError> {
+        let product_id = payload.product_id();
+        let product_namespace = payload.product_namespace();
+        let schema_name = payload.schema_name();
+
+        // Check that the agent submitting the transactions exists in state
+        let agent = match state.get_agent(signer)? {
+            Some(agent) => agent,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "The signing Agent does not exist: {}",
+                    signer
+                )));
+            }
+        };
+
+        // Only organization admins can hand products over to another organization
+        check_permission(perm_checker, signer, "admin")?;
+
+        // Check if product exists in state
+        let product = match state.get_product(product_namespace, schema_name, product_id) {
+            Ok(Some(product)) => Ok(product),
+            Ok(None) => Err(ApplyError::InvalidTransaction(format!(
+                "No product exists: {}",
+                product_id
+            ))),
+            Err(err) => Err(err),
+        }?;
+
+        let builder = match payload.phase() {
+            TransferPhase::Offer => {
+                let new_owner = payload.new_owner();
+
+                if product.owner() != agent.org_id() {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Only an admin of {} can offer product {}",
+                        product.owner(),
+                        product_id
+                    )));
+                }
+
+                if new_owner == product.owner() {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Product {} is already owned by {}",
+                        product_id, new_owner
+                    )));
+                }
+
+                if state.get_organization(new_owner)?.is_none() {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "The organization the product is offered to does not exist: {}",
+                        new_owner
+                    )));
+                }
+
+                product
+                    .into_builder()
+                    .with_pending_owner(new_owner.to_string())
+            }
+            TransferPhase::Accept => {
+                if product.pending_owner().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "No transfer of product {} is pending",
+                        product_id
+                    )));
+                }
+
+                if product.pending_owner() != agent.org_id() {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Only an admin of {} can accept the transfer of product {}",
+                        product.pending_owner(),
+                        product_id
+                    )));
+                }
+
+                let new_owner = product.pending_owner().to_string();
+                product
+                    .into_builder()
+                    .with_owner(new_owner)
+                    .with_pending_owner(String::new())
+            }
+            TransferPhase::Cancel => {
+                if product.pending_owner().is_empty() {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "No transfer of product {} is pending",
+                        product_id
+                    )));
+                }
+
+                if product.owner() != agent.org_id() && product.pending_owner() != agent.org_id() {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Only an admin of {} or {} can cancel the transfer of product {}",
+                        product.owner(),
+                        product.pending_owner(),
+                        product_id
+                    )));
+                }
+
+                product.into_builder().with_pending_owner(String::new())
+            }
+        };
+
+        let transferred_product = builder.build().map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build product: {}", err))
+        })?;
+
+        state.set_product(transferred_product)?;
+
+        Ok(())
+    }
+
+    fn set_product_status(
+        &self,
+        payload: &ProductSetStatusAction,
+        state: &mut ProductState,
+        signer: &str,
+        perm_checker: &PermissionChecker,
+    ) -> Result<(), ApplyError> {
+        let product_id = payload.product_id();
+        let product_namespace = payload.product_namespace();
+        let schema_name = payload.schema_name();
+        let status = payload.status();
+
+        // Check that the agent submitting the transactions exists in state
+        let agent = match state.get_agent(signer)? {
+            Some(agent) => agent,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "The signing Agent does not exist: {}",
+                    signer
+                )));
+            }
+        };
+
+        // Check signing agent's permission
+        check_permission(perm_checker, signer, "can_update_product")?;
+
+        // Check if product exists in state
+        let product = match state.get_product(product_namespace, schema_name, product_id) {
+            Ok(Some(product)) => Ok(product),
+            Ok(None) => Err(ApplyError::InvalidTransaction(format!(
+                "No product exists: {}",
+                product_id
+            ))),
+            Err(err) => Err(err),
+        }?;
+
+        // Check if the agent is part of the organization that owns the product
+        if product.owner() != agent.org_id() {
+            return Err(ApplyError::InvalidTransaction(
+                "Invalid organization for the agent submitting this transaction".to_string(),
+            ));
+        }
+
+        // Check that the product may move from its current status to the new one
+        if product.status() == status {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Product {} is already {}",
+                product_id, status
+            )));
+        }
+        if !product.status().can_transition_to(status) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Product {} cannot move from {} to {}",
+                product_id,
+                product.status(),
+                status
+            )));
+        }
+
+        let updated_product = product
+            .into_builder()
+            .with_status(status.clone())
+            .build()
+            .map_err(|err| {
+                ApplyError::InvalidTransaction(format!("Cannot build product: {}", err))
+            })?;
+
+        state.set_product(updated_product)?;
+
         Ok(())
     }
 }
@@ -438,6 +564,12 @@ impl TransactionHandler for ProductTransactionHandler {
             Action::ProductDelete(delete_product_payload) => {
                 self.delete_product(delete_product_payload, &mut state, signer, &perm_checker)?
             }
+            Action::ProductTransfer(transfer_product_payload) => {
+                self.transfer_product(transfer_product_payload, &mut state, signer, &perm_checker)?
+            }
+            Action::ProductSetStatus(set_status_payload) => {
+                self.set_product_status(set_status_payload, &mut state, signer, &perm_checker)?
+            }
         }
         Ok(())
     }
@@ -458,6 +590,97 @@ fn check_permission(
     }
 }
 
+/// Gets the schema a product's properties are defined by. GS1 products follow the `gs1_product`
+/// schema; custom products follow the schema they name.
+fn get_product_schema(
+    state: &ProductState,
+    product_namespace: &ProductNamespace,
+    schema_name: &str,
+) -> Result<Schema, ApplyError> {
+    match product_namespace {
+        ProductNamespace::GS1 => {
+            if !schema_name.is_empty() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "GS1 products follow the gs1_product schema, but the {} schema was given",
+                    schema_name
+                )));
+            }
+            state.get_schema(GS1_PRODUCT_SCHEMA)?.ok_or_else(|| {
+                ApplyError::InvalidTransaction("gs1_product schema has not been defined".into())
+            })
+        }
+        ProductNamespace::Custom => state.get_schema(schema_name)?.ok_or_else(|| {
+            ApplyError::InvalidTransaction(format!("Schema does not exist: {}", schema_name))
+        }),
+    }
+}
+
+/// Checks that a GS1 product_id is a valid GTIN and that a custom product_id follows the
+/// identifier format of its schema, if the schema declares one
+fn validate_product_id(
+    schema: &Schema,
+    product_namespace: &ProductNamespace,
+    product_id: &str,
+) -> Result<(), ApplyError> {
+    match product_namespace {
+        ProductNamespace::GS1 => {
+            validate_gtin(product_id).map_err(|err| ApplyError::InvalidTransaction(err.to_string()))
+        }
+        ProductNamespace::Custom => match schema.identifier_format() {
+            Some(identifier_format) => identifier_format.validate(product_id).map_err(|err| {
+                ApplyError::InvalidTransaction(format!(
+                    "Invalid product_id for schema {}: {}",
+                    schema.name(),
+                    err
+                ))
+            }),
+            None => Ok(()),
+        },
+    }
+}
+
+/// Checks that a product's lifecycle status allows replacing its properties with `properties`.
+/// Discontinued products cannot be updated, and recalled products can only gain properties, e.g.
+/// to add recall information, while keeping every existing value unchanged.
+fn check_product_updatable(
+    product: &Product,
+    properties: &[PropertyValue],
+) -> Result<(), ApplyError> {
+    match product.status() {
+        ProductStatus::Discontinued => Err(ApplyError::InvalidTransaction(format!(
+            "Product {} is discontinued and cannot be updated",
+            product.product_id()
+        ))),
+        ProductStatus::Recalled => {
+            if product
+                .properties()
+                .iter()
+                .all(|existing| properties.contains(existing))
+            {
+                Ok(())
+            } else {
+                Err(ApplyError::InvalidTransaction(format!(
+                    "Recalled product {} can only be updated to add properties",
+                    product.product_id()
+                )))
+            }
+        }
+        ProductStatus::Draft | ProductStatus::Active => Ok(()),
+    }
+}
+
+/// Checks that every property is defined by the schema, that every property the schema
+/// requires is given and that every value satisfies its definition's constraints
+fn validate_properties(schema: &Schema, properties: &[PropertyValue]) -> Result<(), ApplyError> {
+    validation::validate_properties(schema.properties(), properties).map_err(|err| {
+        ApplyError::InvalidTransaction(format!(
+            "Properties do not follow the {} schema: {}",
+            schema.name(),
+            err
+        ))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -465,19 +688,25 @@ mod tests {
     use std::cell::RefCell;
     use std::collections::HashMap;
 
+    use grid_sdk::protocol::addressing::{
+        compute_agent_address, compute_gs1_product_address, compute_organization_address,
+        compute_schema_address,
+    };
     use grid_sdk::protocol::pike::state::{
         AgentBuilder, AgentListBuilder, KeyValueEntryBuilder, OrganizationBuilder,
         OrganizationListBuilder,
     };
     use grid_sdk::protocol::product::payload::{
         ProductCreateAction, ProductCreateActionBuilder, ProductDeleteAction,
-        ProductDeleteActionBuilder, ProductUpdateAction, ProductUpdateActionBuilder,
+        ProductDeleteActionBuilder, ProductSetStatusActionBuilder, ProductTransferAction,
+        ProductTransferActionBuilder, ProductUpdateAction, ProductUpdateActionBuilder,
     };
     use grid_sdk::protocol::product::state::{
         Product, ProductBuilder, ProductListBuilder, ProductNamespace,
     };
     use grid_sdk::protocol::schema::state::{
-        DataType, PropertyDefinitionBuilder, SchemaBuilder, SchemaListBuilder,
+        DataType, IdentifierFormatBuilder, PropertyConstraintsBuilder, PropertyDefinitionBuilder,
+        SchemaBuilder, SchemaListBuilder,
     };
     use grid_sdk::protocol::schema::state::{
         DataType as SchemaDataType, PropertyValue, PropertyValueBuilder,
@@ -490,6 +719,13 @@ mod tests {
     const PUBLIC_KEY: &str = "test_public_key";
     const PRODUCT_ID: &str = "688955434684";
     const PRODUCT_2_ID: &str = "9781981855728";
+    const GTIN_8_PRODUCT_ID: &str = "96385074";
+    const GTIN_14_PRODUCT_ID: &str = "19781981855725";
+    const GS1_COMPANY_PREFIXES: &str = "0688955,978198";
+    const CUSTOM_SCHEMA_NAME: &str = "lightbulb";
+    const CUSTOM_PRODUCT_ID: &str = "LB-0001";
+    const OTHER_ORG_ID: &str = "other_org";
+    const OTHER_PUBLIC_KEY: &str = "other_public_key";
 
     #[derive(Default, Debug)]
     /// A MockTransactionContext that can be used to test ProductState
@@ -580,13 +816,35 @@ mod tests {
             self.set_state_entry(agent_address, agent_bytes).unwrap();
         }
 
+        fn add_admin_agent(&self, public_key: &str, org_id: &str) {
+            let agent = AgentBuilder::new()
+                .with_org_id(org_id.to_string())
+                .with_public_key(public_key.to_string())
+                .with_active(true)
+                .with_roles(vec!["admin".to_string()])
+                .build()
+                .unwrap();
+
+            let agent_list = AgentListBuilder::new()
+                .with_agents(vec![agent])
+                .build()
+                .unwrap();
+            let agent_address = compute_agent_address(public_key);
+            self.set_state_entry(agent_address, agent_list.into_bytes().unwrap())
+                .unwrap();
+        }
+
         fn add_org(&self, org_id: &str) {
-            // Products can only be created when there is a gs1 prefix
-            // within the product organization's metadata
+            // Products can only be created when the product organization's metadata
+            // contains the gs1 prefix of the product's GTIN
+            self.add_org_with_gs1_prefixes(org_id, GS1_COMPANY_PREFIXES);
+        }
+
+        fn add_org_with_gs1_prefixes(&self, org_id: &str, gs1_company_prefixes: &str) {
             let builder = KeyValueEntryBuilder::new();
             let key_value = builder
                 .with_key("gs1_company_prefixes".to_string())
-                .with_value("test_value".to_string())
+                .with_value(gs1_company_prefixes.to_string())
                 .build()
                 .unwrap();
 
@@ -605,7 +863,7 @@ mod tests {
                 .build()
                 .unwrap();
             let org_bytes = org_list.into_bytes().unwrap();
-            let org_address = compute_org_address(org_id);
+            let org_address = compute_organization_address(org_id);
             self.set_state_entry(org_address, org_bytes).unwrap();
         }
 
@@ -624,7 +882,7 @@ mod tests {
                 .build()
                 .unwrap();
             let org_bytes = org_list.into_bytes().unwrap();
-            let org_address = compute_org_address(org_id);
+            let org_address = compute_organization_address(org_id);
             self.set_state_entry(org_address, org_bytes).unwrap();
         }
 
@@ -639,6 +897,22 @@ mod tests {
                 .unwrap();
         }
 
+        fn add_product_with_status(&self, prod_id: &str, status: ProductStatus) {
+            let product = make_product()
+                .into_builder()
+                .with_status(status)
+                .build()
+                .unwrap();
+            let product_list = ProductListBuilder::new()
+                .with_products(vec![product])
+                .build()
+                .unwrap();
+            let product_bytes = product_list.into_bytes().unwrap();
+            let product_address = compute_gs1_product_address(prod_id);
+            self.set_state_entry(product_address, product_bytes)
+                .unwrap();
+        }
+
         fn add_products(&self, product_ids: &[&str]) {
             let product_list = ProductListBuilder::new()
                 .with_products(make_products(product_ids))
@@ -662,6 +936,13 @@ mod tests {
                     .with_data_type(SchemaDataType::Number)
                     .with_number_exponent(1)
                     .with_required(true)
+                    .with_constraints(
+                        PropertyConstraintsBuilder::new()
+                            .with_min_number(0)
+                            .with_max_number(100)
+                            .build()
+                            .unwrap(),
+                    )
                     .build()
                     .unwrap(),
                 PropertyDefinitionBuilder::new()
@@ -670,6 +951,11 @@ mod tests {
                     .with_required(true)
                     .build()
                     .unwrap(),
+                PropertyDefinitionBuilder::new()
+                    .with_name("recall_notice".into())
+                    .with_data_type(SchemaDataType::String)
+                    .build()
+                    .unwrap(),
             ];
 
             let schema = SchemaBuilder::new()
@@ -691,20 +977,54 @@ mod tests {
             )])
             .unwrap();
         }
-    }
-
-    #[test]
-    /// Test that if ProductCreationAction is valid an OK is returned and a new Product is added to state
-    fn test_create_product_handler_valid() {
-        let transaction_context = MockTransactionContext::default();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_gs1_schema();
-        let perm_checker = PermissionChecker::new(&transaction_context);
-        let mut state = ProductState::new(&transaction_context);
 
-        let transaction_handler = ProductTransactionHandler::new();
-        let product_create_action = make_product_create_action();
+        fn add_custom_schema(&self) {
+            let properties = vec![PropertyDefinitionBuilder::new()
+                .with_name("description".into())
+                .with_data_type(SchemaDataType::String)
+                .with_required(true)
+                .build()
+                .unwrap()];
+
+            let identifier_format = IdentifierFormatBuilder::new()
+                .with_pattern("LB-[0-9]{4}".into())
+                .build()
+                .unwrap();
+
+            let schema = SchemaBuilder::new()
+                .with_name(CUSTOM_SCHEMA_NAME.into())
+                .with_description("Lightbulbs identified by a model number".into())
+                .with_owner(AGENT_ORG_ID.to_string())
+                .with_properties(properties)
+                .with_identifier_format(identifier_format)
+                .build()
+                .unwrap();
+
+            let schema_list = SchemaListBuilder::new()
+                .with_schemas(vec![schema])
+                .build()
+                .unwrap();
+
+            self.set_state_entries(vec![(
+                compute_schema_address(CUSTOM_SCHEMA_NAME),
+                schema_list.into_bytes().unwrap(),
+            )])
+            .unwrap();
+        }
+    }
+
+    #[test]
+    /// Test that if ProductCreationAction is valid an OK is returned and a new Product is added to state
+    fn test_create_product_handler_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action();
 
         assert!(transaction_handler
             .create_product(
@@ -716,7 +1036,7 @@ mod tests {
             .is_ok());
 
         let product = state
-            .get_product(PRODUCT_ID)
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
             .expect("Failed to fetch product")
             .expect("No product found");
 
@@ -756,20 +1076,676 @@ mod tests {
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_create_action = make_product_create_action();
+        let product_create_action = make_product_create_action();
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!(
+                "Agent should not have can_create_product role, InvalidTransaction should be returned"
+            ),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "The signer does not have the can_create_product permission: {}",
+                    PUBLIC_KEY
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if the agent's org does not exist.
+    fn test_create_product_org_does_not_exist() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action();
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!(
+                "Agent's organization should not exist, InvalidTransaction should be returned"
+            ),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "The Agent's organization does not exist: {}",
+                    PUBLIC_KEY
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if the agent's org does not contain the gs1 prefix.
+    fn test_create_product_org_without_gs1_prefix() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org_without_gs1_prefix(AGENT_ORG_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action();
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker
+        ) {
+            Ok(()) => panic!("Agent's organization should not have a gs1 prefix key, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("The agents organization does not have the gs1_company_prefixes key in its metadata: []"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a product can be created with any of the gs1 prefixes of the agent's org
+    fn test_create_product_with_second_gs1_prefix() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = ProductCreateActionBuilder::new()
+            .with_product_id(PRODUCT_2_ID.to_string())
+            .with_owner(AGENT_ORG_ID.to_string())
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_properties(make_properties())
+            .build()
+            .expect("Failed to build ProductCreateAction");
+
+        assert!(transaction_handler
+            .create_product(
+                &product_create_action,
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker
+            )
+            .is_ok());
+
+        assert!(state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_2_ID)
+            .expect("Failed to fetch product")
+            .is_some());
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if none of the gs1 prefixes of the agent's org
+    /// match the GTIN.
+    fn test_create_product_org_does_not_own_gs1_prefix() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org_with_gs1_prefixes(AGENT_ORG_ID, "978198,688955");
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action();
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Agent's organization should not own the gs1 prefix, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(
+                    "The agents organization does not own the GS1 company prefix in the GTIN product_id"
+                ));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if the gs1 prefixes of the agent's org are not
+    /// a comma-separated list of numbers.
+    fn test_create_product_org_with_invalid_gs1_prefix() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org_with_gs1_prefixes(AGENT_ORG_ID, "0688955,test_value");
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action();
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Agent's organization has an invalid gs1 prefix, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Invalid GS1 company prefix"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a GTIN-8 is matched as it is against the GS1-8 prefixes of the agent's org
+    fn test_create_product_gtin_8_company_prefix() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org_with_gs1_prefixes(AGENT_ORG_ID, "0688955,9638");
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action_with_id(GTIN_8_PRODUCT_ID);
+
+        assert!(transaction_handler
+            .create_product(
+                &product_create_action,
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker
+            )
+            .is_ok());
+
+        assert!(state
+            .get_product(&ProductNamespace::GS1, "", GTIN_8_PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .is_some());
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if none of the gs1 prefixes of the agent's org
+    /// match the GTIN-8.
+    fn test_create_product_gtin_8_org_does_not_own_prefix() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action_with_id(GTIN_8_PRODUCT_ID);
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Agent's organization should not own the gs1 prefix, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(
+                    "The agents organization does not own the GS1 company prefix in the GTIN product_id"
+                ));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the indicator digit of a GTIN-14 is not part of the company prefix it is
+    /// matched against
+    fn test_create_product_gtin_14_company_prefix() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action_with_id(GTIN_14_PRODUCT_ID);
+
+        assert!(transaction_handler
+            .create_product(
+                &product_create_action,
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker
+            )
+            .is_ok());
+
+        assert!(state
+            .get_product(&ProductNamespace::GS1, "", GTIN_14_PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .is_some());
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if a gs1 prefix of the agent's org only
+    /// matches the GTIN-14 when its indicator digit is included.
+    fn test_create_product_gtin_14_prefix_includes_indicator() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org_with_gs1_prefixes(AGENT_ORG_ID, "1978198");
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action_with_id(GTIN_14_PRODUCT_ID);
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Agent's organization should not own the gs1 prefix, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(
+                    "The agents organization does not own the GS1 company prefix in the GTIN product_id"
+                ));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if the a product with the same id
+    /// already exists.
+    fn test_create_product_already_exist() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_product_create_action();
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Product already exists: {}", PRODUCT_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if a property value breaks the constraints of
+    /// its definition
+    fn test_create_product_property_out_of_range() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = ProductCreateActionBuilder::new()
+            .with_product_id(PRODUCT_ID.to_string())
+            .with_owner(AGENT_ORG_ID.to_string())
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_properties(vec![
+                make_description("This is a product description"),
+                PropertyValueBuilder::new()
+                    .with_name("counter".into())
+                    .with_data_type(DataType::Number)
+                    .with_number_value(101)
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .expect("Failed to build ProductCreateAction");
+
+        match transaction_handler.create_product(
+            &product_create_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => {
+                panic!("Counter should be out of range, InvalidTransaction should be returned")
+            }
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("101 is greater than 100"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if ProductUpdateAction is valid an OK is returned and a Product is updated in state
+    fn test_update_product_handler_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_update_action = make_product_update_action();
+
+        assert!(transaction_handler
+            .update_product(
+                &product_update_action,
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker,
+            )
+            .is_ok());
+
+        let product = state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+
+        assert_eq!(product, make_updated_product());
+    }
+
+    #[test]
+    /// Test that ProductUpdateAction is invalid if the signer is not an Agent.
+    fn test_update_product_agent_does_not_exist() {
+        let transaction_context = MockTransactionContext::default();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_update_action = make_product_update_action();
+
+        match transaction_handler.update_product(
+            &product_update_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Agent should not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("The signing Agent does not exist: {}", PUBLIC_KEY)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductUpdateAction is invalid if the agent does not have can_update_product role
+    fn test_update_product_agent_without_roles() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent_without_roles(PUBLIC_KEY);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_update_action = make_product_update_action();
+
+        match transaction_handler.update_product(
+            &product_update_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!(
+                "Agent should not have can_update_product role, InvalidTransaction should be returned"
+            ),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "The signer does not have the can_update_product permission: {}",
+                    PUBLIC_KEY
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that ProductUpdateAction is invalid if there is no product to update
+    fn test_update_product_that_does_not_exist() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_update_action = make_product_update_action();
+
+        match transaction_handler.update_product(
+            &product_update_action,
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("No product exists: {}", PRODUCT_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a discontinued product cannot be updated
+    fn test_update_discontinued_product() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_product_with_status(PRODUCT_ID, ProductStatus::Discontinued);
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+
+        match transaction_handler.update_product(
+            &make_product_update_action(),
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Product is discontinued, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Product {} is discontinued and cannot be updated",
+                    PRODUCT_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a recalled product can gain properties, such as a recall notice, but its
+    /// existing properties cannot be changed
+    fn test_update_recalled_product() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_product_with_status(PRODUCT_ID, ProductStatus::Recalled);
+        transaction_context.add_gs1_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+
+        match transaction_handler.update_product(
+            &make_product_update_action(),
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Product is recalled, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Recalled product {} can only be updated to add properties",
+                    PRODUCT_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+
+        let mut properties = make_properties();
+        properties.push(
+            PropertyValueBuilder::new()
+                .with_name("recall_notice".into())
+                .with_data_type(DataType::String)
+                .with_string_value("Return to store for a full refund".into())
+                .build()
+                .unwrap(),
+        );
+        let recall_update_action = ProductUpdateActionBuilder::new()
+            .with_product_id(PRODUCT_ID.to_string())
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_properties(properties.clone())
+            .build()
+            .expect("Failed to build ProductUpdateAction");
+
+        assert!(transaction_handler
+            .update_product(&recall_update_action, &mut state, PUBLIC_KEY, &perm_checker)
+            .is_ok());
+
+        let product = state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+
+        assert_eq!(product.properties(), properties.as_slice());
+        assert_eq!(*product.status(), ProductStatus::Recalled);
+    }
+
+    #[test]
+    /// Test that if ProductDeleteAction is valid an OK is returned and a Product is deleted from state
+    fn test_delete_product_handler_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_delete_action = make_product_delete_action(PRODUCT_ID);
+
+        assert!(transaction_handler
+            .delete_product(
+                &product_delete_action,
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker
+            )
+            .is_ok());
+
+        let product = state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
+            .expect("No product found");
+
+        assert_eq!(product, None);
+    }
+
+    #[test]
+    /// Test that a recalled product cannot be deleted
+    fn test_delete_recalled_product() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_product_with_status(PRODUCT_ID, ProductStatus::Recalled);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+
+        match transaction_handler.delete_product(
+            &make_product_delete_action(PRODUCT_ID),
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => panic!("Product is recalled, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Recalled product {} cannot be deleted",
+                    PRODUCT_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if ProductDeleteAction is valid an OK is returned and a
+    /// second product is deleted from state
+    fn test_delete_second_product_handler_valid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_delete_action = make_product_delete_action(PRODUCT_2_ID);
+
+        assert!(transaction_handler
+            .delete_product(
+                &product_delete_action,
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker,
+            )
+            .is_ok());
+
+        let product = state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_2_ID)
+            .expect("No product found");
+
+        assert_eq!(product, None);
+    }
+
+    #[test]
+    /// Test that ProductDeleteAction is invalid if the agent does not have can_delete_product role
+    fn test_delete_product_agent_without_roles() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent_without_roles(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_delete_action = make_product_delete_action(PRODUCT_ID);
 
-        match transaction_handler.create_product(
-            &product_create_action,
+        match transaction_handler.delete_product(
+            &product_delete_action,
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
         ) {
             Ok(()) => panic!(
-                "Agent should not have can_create_product role, InvalidTransaction should be returned"
+                "Agent should not have can_delete_product role, InvalidTransaction should be returned"
             ),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "The signer does not have the can_create_product permission: {}",
+                    "The signer does not have the can_delete_product permission: {}",
                     PUBLIC_KEY
                 )));
             }
@@ -778,74 +1754,114 @@ mod tests {
     }
 
     #[test]
-    /// Test that ProductCreationAction is invalid if the agent's org does not exist.
-    fn test_create_product_org_does_not_exist() {
+    /// Test that ProductDeleteAction is invalid when deleting a non existant product
+    fn test_delete_product_not_exists() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_create_action = make_product_create_action();
+        let product_delete_action = make_product_delete_action("13491387613");
 
-        match transaction_handler.create_product(
-            &product_create_action,
+        match transaction_handler.delete_product(
+            &product_delete_action,
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
         ) {
-            Ok(()) => panic!(
-                "Agent's organization should not exist, InvalidTransaction should be returned"
-            ),
+            Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!(
-                    "The Agent's organization does not exist: {}",
-                    PUBLIC_KEY
-                )));
+                assert!(err.contains("No product exists: 13491387613"));
             }
             Err(err) => panic!("Should have gotten invalid error but go {}", err),
         }
     }
 
     #[test]
-    /// Test that ProductCreationAction is invalid if the agent's org does not contain the gs1 prefix.
-    fn test_create_product_org_without_gs1_prefix() {
+    /// Test that a custom product whose product_id follows its schema's identifier format is
+    /// added to state, without requiring the owner to hold a GS1 company prefix
+    fn test_create_custom_product_handler_valid() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_org_without_gs1_prefix(AGENT_ORG_ID);
+        transaction_context.add_custom_schema();
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_create_action = make_product_create_action();
+        let product_create_action = make_custom_product_create_action(CUSTOM_PRODUCT_ID);
+
+        assert!(transaction_handler
+            .create_product(
+                &product_create_action,
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker
+            )
+            .is_ok());
+
+        let product = state
+            .get_product(
+                &ProductNamespace::Custom,
+                CUSTOM_SCHEMA_NAME,
+                CUSTOM_PRODUCT_ID,
+            )
+            .expect("Failed to fetch product")
+            .expect("No product found");
+
+        assert_eq!(product.schema_name(), CUSTOM_SCHEMA_NAME);
+        assert_eq!(product.product_namespace(), &ProductNamespace::Custom);
+        assert_eq!(
+            state
+                .get_product(&ProductNamespace::GS1, "", CUSTOM_PRODUCT_ID)
+                .expect("Failed to fetch product"),
+            None
+        );
+    }
+
+    #[test]
+    /// Test that ProductCreationAction is invalid if a custom product_id does not follow the
+    /// identifier format of its schema
+    fn test_create_custom_product_invalid_identifier() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org_without_gs1_prefix(AGENT_ORG_ID);
+        transaction_context.add_custom_schema();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let mut state = ProductState::new(&transaction_context);
+
+        let transaction_handler = ProductTransactionHandler::new();
+        let product_create_action = make_custom_product_create_action("LB-12");
 
         match transaction_handler.create_product(
             &product_create_action,
             &mut state,
             PUBLIC_KEY,
-            &perm_checker
+            &perm_checker,
         ) {
-            Ok(()) => panic!("Agent's organization should not have a gs1 prefix key, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Product id should be invalid, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("The agents organization does not have the gs1_company_prefixes key in its metadata: []"));
+                assert!(err.contains("Invalid product_id for schema lightbulb"));
             }
             Err(err) => panic!("Should have gotten invalid error but go {}", err),
         }
     }
 
     #[test]
-    /// Test that ProductCreationAction is invalid if the a product with the same id
-    /// already exists.
-    fn test_create_product_already_exist() {
+    /// Test that ProductCreationAction is invalid if a custom product names a schema that does
+    /// not exist
+    fn test_create_custom_product_schema_does_not_exist() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_product(PRODUCT_ID);
+        transaction_context.add_org_without_gs1_prefix(AGENT_ORG_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_create_action = make_product_create_action();
+        let product_create_action = make_custom_product_create_action(CUSTOM_PRODUCT_ID);
 
         match transaction_handler.create_product(
             &product_create_action,
@@ -853,205 +1869,303 @@ mod tests {
             PUBLIC_KEY,
             &perm_checker,
         ) {
-            Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Schema should not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Product already exists: {}", PRODUCT_ID)));
+                assert!(err.contains(&format!("Schema does not exist: {}", CUSTOM_SCHEMA_NAME)));
             }
             Err(err) => panic!("Should have gotten invalid error but go {}", err),
         }
     }
 
     #[test]
-    /// Test that if ProductUpdateAction is valid an OK is returned and a Product is updated in state
-    fn test_update_product_handler_valid() {
+    /// Test that a custom product is updated in place, using its schema to validate the new
+    /// properties
+    fn test_update_custom_product_handler_valid() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_product(PRODUCT_ID);
-        transaction_context.add_gs1_schema();
+        transaction_context.add_org_without_gs1_prefix(AGENT_ORG_ID);
+        transaction_context.add_custom_schema();
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_update_action = make_product_update_action();
+        transaction_handler
+            .create_product(
+                &make_custom_product_create_action(CUSTOM_PRODUCT_ID),
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker,
+            )
+            .expect("Failed to create product");
+
+        let product_update_action = ProductUpdateActionBuilder::new()
+            .with_product_id(CUSTOM_PRODUCT_ID.to_string())
+            .with_product_namespace(ProductNamespace::Custom)
+            .with_schema_name(CUSTOM_SCHEMA_NAME.to_string())
+            .with_properties(vec![make_description(
+                "This is a new lightbulb description",
+            )])
+            .build()
+            .expect("Failed to build ProductUpdateAction");
 
         assert!(transaction_handler
             .update_product(
                 &product_update_action,
                 &mut state,
                 PUBLIC_KEY,
-                &perm_checker,
+                &perm_checker
             )
             .is_ok());
 
         let product = state
-            .get_product(PRODUCT_ID)
+            .get_product(
+                &ProductNamespace::Custom,
+                CUSTOM_SCHEMA_NAME,
+                CUSTOM_PRODUCT_ID,
+            )
             .expect("Failed to fetch product")
             .expect("No product found");
 
-        assert_eq!(product, make_updated_product());
+        assert_eq!(
+            product.properties(),
+            &[make_description("This is a new lightbulb description")][..]
+        );
+        assert_eq!(product.schema_version(), &1);
     }
 
     #[test]
-    /// Test that ProductUpdateAction is invalid if the signer is not an Agent.
-    fn test_update_product_agent_does_not_exist() {
+    /// Test that a product offered by an admin of its owner can be accepted by an admin of the
+    /// organization it was offered to, which then owns the product
+    fn test_transfer_product_offer_and_accept() {
         let transaction_context = MockTransactionContext::default();
+        transaction_context.add_admin_agent(PUBLIC_KEY, AGENT_ORG_ID);
+        transaction_context.add_admin_agent(OTHER_PUBLIC_KEY, OTHER_ORG_ID);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_org(OTHER_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_update_action = make_product_update_action();
 
-        match transaction_handler.update_product(
-            &product_update_action,
-            &mut state,
-            PUBLIC_KEY,
-            &perm_checker,
-        ) {
-            Ok(()) => panic!("Agent should not exist, InvalidTransaction should be returned"),
-            Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("The signing Agent does not exist: {}", PUBLIC_KEY)));
-            }
-            Err(err) => panic!("Should have gotten invalid error but go {}", err),
-        }
+        transaction_handler
+            .transfer_product(
+                &make_product_transfer_action(TransferPhase::Offer, OTHER_ORG_ID),
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker,
+            )
+            .expect("Failed to offer product");
+
+        let product = state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+        assert_eq!(product.owner(), AGENT_ORG_ID);
+        assert_eq!(product.pending_owner(), OTHER_ORG_ID);
+
+        transaction_handler
+            .transfer_product(
+                &make_product_transfer_action(TransferPhase::Accept, ""),
+                &mut state,
+                OTHER_PUBLIC_KEY,
+                &perm_checker,
+            )
+            .expect("Failed to accept product");
+
+        let product = state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+        assert_eq!(product.owner(), OTHER_ORG_ID);
+        assert_eq!(product.pending_owner(), "");
+        assert_eq!(product.properties(), &make_properties()[..]);
     }
 
     #[test]
-    /// Test that ProductUpdateAction is invalid if the agent does not have can_update_product role
-    fn test_update_product_agent_without_roles() {
+    /// Test that ProductTransferAction is invalid if the agent is not an admin of its organization
+    fn test_transfer_product_agent_without_admin_role() {
         let transaction_context = MockTransactionContext::default();
-        transaction_context.add_agent_without_roles(PUBLIC_KEY);
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_org(OTHER_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_update_action = make_product_update_action();
 
-        match transaction_handler.update_product(
-            &product_update_action,
+        match transaction_handler.transfer_product(
+            &make_product_transfer_action(TransferPhase::Offer, OTHER_ORG_ID),
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
         ) {
-            Ok(()) => panic!(
-                "Agent should not have can_update_product role, InvalidTransaction should be returned"
-            ),
+            Ok(()) => panic!("Agent is not an admin, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "The signer does not have the can_update_product permission: {}",
+                    "The signer does not have the admin permission: {}",
                     PUBLIC_KEY
                 )));
             }
-            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that ProductUpdateAction is invalid if there is no product to update
-    fn test_update_product_that_does_not_exist() {
+    /// Test that a pending transfer can only be accepted by an admin of the organization the
+    /// product was offered to
+    fn test_transfer_product_accept_by_owner() {
         let transaction_context = MockTransactionContext::default();
-        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_admin_agent(PUBLIC_KEY, AGENT_ORG_ID);
+        transaction_context.add_org(AGENT_ORG_ID);
+        transaction_context.add_org(OTHER_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_update_action = make_product_update_action();
 
-        match transaction_handler.update_product(
-            &product_update_action,
+        transaction_handler
+            .transfer_product(
+                &make_product_transfer_action(TransferPhase::Offer, OTHER_ORG_ID),
+                &mut state,
+                PUBLIC_KEY,
+                &perm_checker,
+            )
+            .expect("Failed to offer product");
+
+        match transaction_handler.transfer_product(
+            &make_product_transfer_action(TransferPhase::Accept, ""),
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
         ) {
-            Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
+            Ok(()) => {
+                panic!("Owner cannot accept its own offer, InvalidTransaction should be returned")
+            }
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("No product exists: {}", PRODUCT_ID)));
+                assert!(err.contains(&format!(
+                    "Only an admin of {} can accept the transfer of product {}",
+                    OTHER_ORG_ID, PRODUCT_ID
+                )));
             }
-            Err(err) => panic!("Should have gotten invalid error but go {}", err),
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if ProductDeleteAction is valid an OK is returned and a Product is deleted from state
-    fn test_delete_product_handler_valid() {
+    /// Test that a product cannot be offered to an organization that does not exist
+    fn test_transfer_product_offer_to_missing_org() {
         let transaction_context = MockTransactionContext::default();
-        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_admin_agent(PUBLIC_KEY, AGENT_ORG_ID);
         transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        transaction_context.add_product(PRODUCT_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_delete_action = make_product_delete_action(PRODUCT_ID);
-
-        assert!(transaction_handler
-            .delete_product(
-                &product_delete_action,
-                &mut state,
-                PUBLIC_KEY,
-                &perm_checker
-            )
-            .is_ok());
-
-        let product = state.get_product(PRODUCT_ID).expect("No product found");
 
-        assert_eq!(product, None);
+        match transaction_handler.transfer_product(
+            &make_product_transfer_action(TransferPhase::Offer, OTHER_ORG_ID),
+            &mut state,
+            PUBLIC_KEY,
+            &perm_checker,
+        ) {
+            Ok(()) => {
+                panic!("Organization should not exist, InvalidTransaction should be returned")
+            }
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "The organization the product is offered to does not exist: {}",
+                    OTHER_ORG_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
     }
 
     #[test]
-    /// Test that if ProductDeleteAction is valid an OK is returned and a
-    /// second product is deleted from state
-    fn test_delete_second_product_handler_valid() {
+    /// Test that the organization a product was offered to can decline it, leaving the owner
+    /// unchanged
+    fn test_transfer_product_cancel() {
         let transaction_context = MockTransactionContext::default();
-        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_admin_agent(PUBLIC_KEY, AGENT_ORG_ID);
+        transaction_context.add_admin_agent(OTHER_PUBLIC_KEY, OTHER_ORG_ID);
         transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        transaction_context.add_org(OTHER_ORG_ID);
+        transaction_context.add_product(PRODUCT_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_delete_action = make_product_delete_action(PRODUCT_2_ID);
 
-        assert!(transaction_handler
-            .delete_product(
-                &product_delete_action,
+        transaction_handler
+            .transfer_product(
+                &make_product_transfer_action(TransferPhase::Offer, OTHER_ORG_ID),
                 &mut state,
                 PUBLIC_KEY,
                 &perm_checker,
             )
-            .is_ok());
+            .expect("Failed to offer product");
 
-        let product = state.get_product(PRODUCT_2_ID).expect("No product found");
+        transaction_handler
+            .transfer_product(
+                &make_product_transfer_action(TransferPhase::Cancel, ""),
+                &mut state,
+                OTHER_PUBLIC_KEY,
+                &perm_checker,
+            )
+            .expect("Failed to cancel transfer");
 
-        assert_eq!(product, None);
+        let product = state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
+            .expect("Failed to fetch product")
+            .expect("No product found");
+        assert_eq!(product, make_product());
     }
 
     #[test]
-    /// Test that ProductDeleteAction is invalid if the agent does not have can_delete_product role
-    fn test_delete_product_agent_without_roles() {
+    /// Test that a draft product can be activated and then recalled, and that a recalled product
+    /// cannot be made active again
+    fn test_set_product_status() {
         let transaction_context = MockTransactionContext::default();
-        transaction_context.add_agent_without_roles(PUBLIC_KEY);
+        transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        transaction_context.add_product_with_status(PRODUCT_ID, ProductStatus::Draft);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_delete_action = make_product_delete_action(PRODUCT_ID);
 
-        match transaction_handler.delete_product(
-            &product_delete_action,
+        for status in vec![ProductStatus::Active, ProductStatus::Recalled] {
+            assert!(transaction_handler
+                .set_product_status(
+                    &make_product_set_status_action(status.clone()),
+                    &mut state,
+                    PUBLIC_KEY,
+                    &perm_checker,
+                )
+                .is_ok());
+
+            let product = state
+                .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
+                .expect("Failed to fetch product")
+                .expect("No product found");
+            assert_eq!(*product.status(), status);
+        }
+
+        match transaction_handler.set_product_status(
+            &make_product_set_status_action(ProductStatus::Active),
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
         ) {
-            Ok(()) => panic!(
-                "Agent should not have can_delete_product role, InvalidTransaction should be returned"
-            ),
+            Ok(()) => panic!("Recalled products cannot be reactivated"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "The signer does not have the can_delete_product permission: {}",
-                    PUBLIC_KEY
+                    "Product {} cannot move from RECALLED to ACTIVE",
+                    PRODUCT_ID
                 )));
             }
             Err(err) => panic!("Should have gotten invalid error but go {}", err),
@@ -1059,27 +2173,26 @@ mod tests {
     }
 
     #[test]
-    /// Test that ProductDeleteAction is invalid when deleting a non existant product
-    fn test_delete_product_not_exists() {
+    /// Test that ProductSetStatusAction is invalid if the product already has the status
+    fn test_set_product_status_unchanged() {
         let transaction_context = MockTransactionContext::default();
         transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_org(AGENT_ORG_ID);
-        transaction_context.add_products(&vec![PRODUCT_ID, PRODUCT_2_ID]);
+        transaction_context.add_product(PRODUCT_ID);
         let perm_checker = PermissionChecker::new(&transaction_context);
         let mut state = ProductState::new(&transaction_context);
 
         let transaction_handler = ProductTransactionHandler::new();
-        let product_delete_action = make_product_delete_action("13491387613");
 
-        match transaction_handler.delete_product(
-            &product_delete_action,
+        match transaction_handler.set_product_status(
+            &make_product_set_status_action(ProductStatus::Active),
             &mut state,
             PUBLIC_KEY,
             &perm_checker,
         ) {
-            Ok(()) => panic!("Product should not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Product is already active, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("No product exists: 13491387613"));
+                assert!(err.contains(&format!("Product {} is already ACTIVE", PRODUCT_ID)));
             }
             Err(err) => panic!("Should have gotten invalid error but go {}", err),
         }
@@ -1091,6 +2204,7 @@ mod tests {
             .with_owner(AGENT_ORG_ID.to_string())
             .with_product_namespace(ProductNamespace::GS1)
             .with_properties(make_properties())
+            .with_schema_version(1)
             .build()
             .expect("Failed to build new_product")
     }
@@ -1120,6 +2234,7 @@ mod tests {
             .with_owner(AGENT_ORG_ID.to_string())
             .with_product_namespace(ProductNamespace::GS1)
             .with_properties(make_updated_properties())
+            .with_schema_version(1)
             .build()
             .expect("Failed to build new_product")
     }
@@ -1165,8 +2280,12 @@ mod tests {
     }
 
     fn make_product_create_action() -> ProductCreateAction {
+        make_product_create_action_with_id(PRODUCT_ID)
+    }
+
+    fn make_product_create_action_with_id(product_id: &str) -> ProductCreateAction {
         ProductCreateActionBuilder::new()
-            .with_product_id(PRODUCT_ID.to_string())
+            .with_product_id(product_id.to_string())
             .with_owner(AGENT_ORG_ID.to_string())
             .with_product_namespace(ProductNamespace::GS1)
             .with_properties(make_properties())
@@ -1183,6 +2302,48 @@ mod tests {
             .expect("Failed to build ProductUpdateAction")
     }
 
+    fn make_custom_product_create_action(product_id: &str) -> ProductCreateAction {
+        ProductCreateActionBuilder::new()
+            .with_product_id(product_id.to_string())
+            .with_owner(AGENT_ORG_ID.to_string())
+            .with_product_namespace(ProductNamespace::Custom)
+            .with_schema_name(CUSTOM_SCHEMA_NAME.to_string())
+            .with_properties(vec![make_description("This is a lightbulb description")])
+            .build()
+            .expect("Failed to build ProductCreateAction")
+    }
+
+    fn make_description(description: &str) -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name("description".into())
+            .with_data_type(DataType::String)
+            .with_string_value(description.into())
+            .build()
+            .unwrap()
+    }
+
+    fn make_product_transfer_action(
+        phase: TransferPhase,
+        new_owner: &str,
+    ) -> ProductTransferAction {
+        ProductTransferActionBuilder::new()
+            .with_product_id(PRODUCT_ID.to_string())
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_phase(phase)
+            .with_new_owner(new_owner.to_string())
+            .build()
+            .expect("Failed to build ProductTransferAction")
+    }
+
+    fn make_product_set_status_action(status: ProductStatus) -> ProductSetStatusAction {
+        ProductSetStatusActionBuilder::new()
+            .with_product_id(PRODUCT_ID.to_string())
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_status(status)
+            .build()
+            .expect("Failed to build ProductSetStatusAction")
+    }
+
     fn make_product_delete_action(product_id: &str) -> ProductDeleteAction {
         ProductDeleteActionBuilder::new()
             .with_product_id(product_id.to_string())
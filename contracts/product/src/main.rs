@@ -32,11 +32,9 @@ cfg_if! {
     }
 }
 
-mod addressing;
 pub mod handler;
 mod payload;
 mod state;
-mod validation;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
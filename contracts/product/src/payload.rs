@@ -20,12 +20,20 @@ cfg_if! {
     }
 }
 
-use grid_sdk::protocol::product::payload::{Action, ProductCreateAction, ProductPayload};
+use grid_sdk::protocol::product::payload::{
+    Action, ProductCreateAction, ProductPayload, ProductSetStatusAction, ProductTransferAction,
+    TransferPhase,
+};
+use grid_sdk::protocol::product::state::{ProductNamespace, ProductStatus};
 
 pub fn validate_payload(payload: &ProductPayload) -> Result<(), ApplyError> {
     validate_timestamp(*payload.timestamp())?;
     match payload.action() {
         Action::ProductCreate(action_payload) => validate_product_create_action(action_payload),
+        Action::ProductTransfer(action_payload) => validate_product_transfer_action(action_payload),
+        Action::ProductSetStatus(action_payload) => {
+            validate_product_set_status_action(action_payload)
+        }
         _ => Ok(()),
     }
 }
@@ -43,6 +51,51 @@ fn validate_product_create_action(
             "Owner cannot be empty string",
         )));
     }
+    if product_create_action.product_namespace() == &ProductNamespace::Custom
+        && product_create_action.schema_name() == ""
+    {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "schema_name cannot be empty string for custom products",
+        )));
+    }
+    match product_create_action.status() {
+        ProductStatus::Draft | ProductStatus::Active => (),
+        status => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "A product cannot be created with status {}",
+                status
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_product_transfer_action(
+    product_transfer_action: &ProductTransferAction,
+) -> Result<(), ApplyError> {
+    if product_transfer_action.product_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "product_id cannot be empty string",
+        )));
+    }
+    if product_transfer_action.phase() == &TransferPhase::Offer
+        && product_transfer_action.new_owner() == ""
+    {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "new_owner cannot be empty string when offering a product",
+        )));
+    }
+    Ok(())
+}
+
+fn validate_product_set_status_action(
+    product_set_status_action: &ProductSetStatusAction,
+) -> Result<(), ApplyError> {
+    if product_set_status_action.product_id() == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "product_id cannot be empty string",
+        )));
+    }
     Ok(())
 }
 
@@ -61,9 +114,10 @@ mod tests {
 
     use grid_sdk::protos::product_payload::{
         ProductCreateAction as ProductCreateActionProto, ProductPayload as ProductPayloadProto,
-        ProductPayload_Action as ActionProto,
+        ProductPayload_Action as ActionProto, ProductTransferAction as ProductTransferActionProto,
+        ProductTransferAction_Phase as PhaseProto,
     };
-    use grid_sdk::protos::product_state::Product_ProductNamespace;
+    use grid_sdk::protos::product_state::{Product_ProductNamespace, Product_Status};
     use grid_sdk::protos::IntoNative;
 
     #[test]
@@ -133,4 +187,79 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    /// Test that an error is returned if the payload with ProductCreateAction creates a custom
+    /// product without naming its schema. This test needs to use the proto directly to be able to
+    /// build a payload the ProductCreateActionBuilder would reject.
+    fn test_validate_payload_custom_schema_name_missing() {
+        let mut payload_proto = ProductPayloadProto::new();
+
+        payload_proto.set_action(ActionProto::PRODUCT_CREATE);
+        payload_proto.set_timestamp(2);
+        let mut action = ProductCreateActionProto::new();
+        action.set_product_namespace(Product_ProductNamespace::CUSTOM);
+        action.set_product_id("LB-001".to_string());
+        action.set_owner("my_owner".to_string());
+        payload_proto.set_product_create(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload missing schema_name, should return error"),
+            Err(err) => {
+                assert!(err
+                    .to_string()
+                    .contains("schema_name cannot be empty string for custom products"));
+            }
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if the payload with ProductTransferAction offers a product
+    /// without naming the organization it is offered to. This test needs to use the proto
+    /// directly to be able to build a payload the ProductTransferActionBuilder would reject.
+    fn test_validate_payload_transfer_new_owner_missing() {
+        let mut payload_proto = ProductPayloadProto::new();
+
+        payload_proto.set_action(ActionProto::PRODUCT_TRANSFER);
+        payload_proto.set_timestamp(2);
+        let mut action = ProductTransferActionProto::new();
+        action.set_product_namespace(Product_ProductNamespace::GS1);
+        action.set_product_id("688955434684".to_string());
+        action.set_phase(PhaseProto::OFFER);
+        payload_proto.set_product_transfer(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload missing new_owner, should return error"),
+            Err(err) => {
+                assert!(err
+                    .to_string()
+                    .contains("new_owner cannot be empty string when offering a product"));
+            }
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if the payload with ProductCreateAction creates a product
+    /// that is already recalled. Products start out as drafts or active.
+    fn test_validate_payload_create_recalled() {
+        let mut payload_proto = ProductPayloadProto::new();
+
+        payload_proto.set_action(ActionProto::PRODUCT_CREATE);
+        payload_proto.set_timestamp(2);
+        let mut action = ProductCreateActionProto::new();
+        action.set_product_id("688955434684".to_string());
+        action.set_owner("my_owner".to_string());
+        action.set_product_namespace(Product_ProductNamespace::GS1);
+        action.set_status(Product_Status::RECALLED);
+        payload_proto.set_product_create(action);
+        let payload = payload_proto.into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload creates a recalled product, should return error"),
+            Err(err) => {
+                assert!(err
+                    .to_string()
+                    .contains("A product cannot be created with status RECALLED"));
+            }
+        }
+    }
 }
@@ -24,11 +24,16 @@ cfg_if! {
 
 use grid_sdk::protocol::pike::state::{Agent, AgentList};
 use grid_sdk::protocol::pike::state::{Organization, OrganizationList};
-use grid_sdk::protocol::product::state::{Product, ProductList, ProductListBuilder};
+use grid_sdk::protocol::product::state::{
+    Product, ProductList, ProductListBuilder, ProductNamespace,
+};
 use grid_sdk::protocol::schema::state::{Schema, SchemaList};
 use grid_sdk::protos::{FromBytes, IntoBytes};
 
-use crate::addressing::*;
+use grid_sdk::protocol::addressing::{
+    compute_agent_address, compute_organization_address, compute_product_address,
+    compute_schema_address,
+};
 
 pub struct ProductState<'a> {
     context: &'a dyn TransactionContext,
@@ -39,8 +44,15 @@ impl<'a> ProductState<'a> {
         ProductState { context }
     }
 
-    pub fn get_product(&self, product_id: &str) -> Result<Option<Product>, ApplyError> {
-        let address = compute_gs1_product_address(product_id); //product id = gtin
+    /// Gets a product by its identifier. Custom products are also identified by the name of their
+    /// schema, which is empty for GS1 products.
+    pub fn get_product(
+        &self,
+        namespace: &ProductNamespace,
+        schema_name: &str,
+        product_id: &str,
+    ) -> Result<Option<Product>, ApplyError> {
+        let address = compute_product_address(namespace, schema_name, product_id);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -57,15 +69,19 @@ impl<'a> ProductState<'a> {
                 Ok(products
                     .products()
                     .iter()
-                    .find(|p| p.product_id() == product_id)
+                    .find(|p| p.product_id() == product_id && p.schema_name() == schema_name)
                     .cloned())
             }
             None => Ok(None),
         }
     }
 
-    pub fn set_product(&self, product_id: &str, product: Product) -> Result<(), ApplyError> {
-        let address = compute_gs1_product_address(product_id);
+    pub fn set_product(&self, product: Product) -> Result<(), ApplyError> {
+        let address = compute_product_address(
+            product.product_namespace(),
+            product.schema_name(),
+            product.product_id(),
+        );
         let d = self.context.get_state_entry(&address)?;
         let mut products = match d {
             Some(packed) => match ProductList::from_bytes(packed.as_slice()) {
@@ -81,8 +97,8 @@ impl<'a> ProductState<'a> {
         };
 
         let mut index = None;
-        for (i, product) in products.iter().enumerate() {
-            if product.product_id() == product_id {
+        for (i, p) in products.iter().enumerate() {
+            if p.product_id() == product.product_id() && p.schema_name() == product.schema_name() {
                 index = Some(i);
                 break;
             }
@@ -115,9 +131,13 @@ impl<'a> ProductState<'a> {
         Ok(())
     }
 
-    // Currently product_id = gtin
-    pub fn remove_product(&self, product_id: &str) -> Result<(), ApplyError> {
-        let address = compute_gs1_product_address(product_id);
+    pub fn remove_product(
+        &self,
+        namespace: &ProductNamespace,
+        schema_name: &str,
+        product_id: &str,
+    ) -> Result<(), ApplyError> {
+        let address = compute_product_address(namespace, schema_name, product_id);
         let d = self.context.get_state_entry(&address)?;
         let products = match d {
             Some(packed) => match ProductList::from_bytes(packed.as_slice()) {
@@ -135,7 +155,7 @@ impl<'a> ProductState<'a> {
         // Filter out the product we are deleting
         let filtered_products = products
             .into_iter()
-            .filter(|p| p.product_id() != product_id)
+            .filter(|p| p.product_id() != product_id || p.schema_name() != schema_name)
             .collect::<Vec<_>>();
 
         // If the only product at the address was the one we are removing, we can delete the entire state entry
@@ -197,7 +217,7 @@ impl<'a> ProductState<'a> {
     }
 
     pub fn get_organization(&self, id: &str) -> Result<Option<Organization>, ApplyError> {
-        let address = compute_org_address(id);
+        let address = compute_organization_address(id);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -258,7 +278,7 @@ mod tests {
     use std::collections::HashMap;
 
     use grid_sdk::protocol::pike::state::{AgentBuilder, AgentListBuilder};
-    use grid_sdk::protocol::product::state::{ProductBuilder, ProductNamespace};
+    use grid_sdk::protocol::product::state::ProductBuilder;
     use grid_sdk::protocol::schema::state::{DataType, PropertyValue, PropertyValueBuilder};
 
     use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
@@ -353,7 +373,9 @@ mod tests {
         let mut transaction_context = MockTransactionContext::default();
         let state = ProductState::new(&mut transaction_context);
 
-        let result = state.get_product("not_a_product").unwrap();
+        let result = state
+            .get_product(&ProductNamespace::GS1, "", "not_a_product")
+            .unwrap();
         assert!(result.is_none())
     }
 
@@ -363,11 +385,40 @@ mod tests {
         let mut transaction_context = MockTransactionContext::default();
         let state = ProductState::new(&mut transaction_context);
 
-        assert!(state.set_product(PRODUCT_ID, make_product()).is_ok());
-        let result = state.get_product(PRODUCT_ID).unwrap();
+        assert!(state.set_product(make_product()).is_ok());
+        let result = state
+            .get_product(&ProductNamespace::GS1, "", PRODUCT_ID)
+            .unwrap();
         assert_eq!(result, Some(make_product()));
     }
 
+    #[test]
+    // Test that custom products are looked up by both their schema and their identifier
+    fn test_set_custom_product() {
+        let mut transaction_context = MockTransactionContext::default();
+        let state = ProductState::new(&mut transaction_context);
+
+        let product = ProductBuilder::new()
+            .with_product_id("LB-001".to_string())
+            .with_owner("some_owner".to_string())
+            .with_product_namespace(ProductNamespace::Custom)
+            .with_schema_name("lightbulb".to_string())
+            .with_properties(make_properties())
+            .build()
+            .expect("Failed to build new_product");
+
+        assert!(state.set_product(product.clone()).is_ok());
+        let result = state
+            .get_product(&ProductNamespace::Custom, "lightbulb", "LB-001")
+            .unwrap();
+        assert_eq!(result, Some(product));
+
+        let result = state
+            .get_product(&ProductNamespace::Custom, "lamp", "LB-001")
+            .unwrap();
+        assert!(result.is_none());
+    }
+
     fn make_agent(public_key: &str) -> Agent {
         AgentBuilder::new()
             .with_org_id("test_org".to_string())
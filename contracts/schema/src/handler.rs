@@ -28,18 +28,16 @@ cfg_if! {
 }
 
 use grid_sdk::permissions::PermissionChecker;
+use grid_sdk::protocol::addressing::GRID_NAMESPACE;
 use grid_sdk::protocol::schema::payload::{
-    Action, SchemaCreateAction, SchemaPayload, SchemaUpdateAction,
+    Action, PropertyUpdate, SchemaCreateAction, SchemaPayload, SchemaUpdateAction, UpdateType,
 };
-use grid_sdk::protocol::schema::state::SchemaBuilder;
+use grid_sdk::protocol::schema::state::{DataType, PropertyDefinition, SchemaBuilder};
 use grid_sdk::protos::FromBytes;
 
 use crate::payload::validate_payload;
 use crate::state::GridSchemaState;
 
-pub const GRID_NAMESPACE: &str = "621dee";
-pub const PIKE_NAMESPACE: &str = "cad11d";
-
 #[cfg(target_arch = "wasm32")]
 // Sabre apply must return a bool
 fn apply(
@@ -156,11 +154,15 @@ fn schema_create(
         )));
     }
 
-    let schema = SchemaBuilder::new()
+    let mut builder = SchemaBuilder::new()
         .with_name(schema_name.into())
         .with_description(description.into())
         .with_owner(agent.org_id().into())
-        .with_properties(properties.to_vec())
+        .with_properties(properties.to_vec());
+    if let Some(identifier_format) = payload.identifier_format() {
+        builder = builder.with_identifier_format(identifier_format.clone());
+    }
+    let schema = builder
         .build()
         .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build schema: {}", err)))?;
 
@@ -214,32 +216,111 @@ fn schema_update(
     }
 
     let mut properties = schema.properties().to_vec();
-    properties.sort_by_key(|p| p.name().to_string());
 
     for property in new_properties.iter() {
-        if properties
-            .binary_search_by_key(&property.name().to_string(), |p| p.name().to_string())
-            .is_ok()
-        {
+        if properties.iter().any(|p| p.name() == property.name()) {
             return Err(ApplyError::InvalidTransaction(format!(
                 "Schema already has PropertyDefination with name {}",
                 property.name()
             )));
         }
+
+        // Values written against earlier versions of the schema do not have the new property
+        if *property.required() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Cannot add required property {} to existing schema {}",
+                property.name(),
+                schema_name
+            )));
+        }
+    }
+
+    for update in payload.property_updates() {
+        let index = properties
+            .iter()
+            .position(|p| p.name() == update.property_name())
+            .ok_or_else(|| {
+                ApplyError::InvalidTransaction(format!(
+                    "Schema {} does not have a property named {}",
+                    schema_name,
+                    update.property_name()
+                ))
+            })?;
+        properties[index] = apply_property_update(&properties[index], update)?;
     }
     properties.append(&mut new_properties);
 
-    let schema = SchemaBuilder::new()
-        .with_name(schema.name().into())
-        .with_description(schema.description().into())
-        .with_owner(schema.owner().into())
+    // The identifier format is fixed when the schema is created, so that existing products
+    // remain valid
+    let version = schema.version() + 1;
+    let schema = schema
+        .into_builder()
         .with_properties(properties)
+        .with_version(version)
         .build()
         .map_err(|err| ApplyError::InvalidTransaction(format!("Cannot build schema: {}", err)))?;
 
     state.set_schema(schema_name, schema)
 }
 
+/// Applies an update to an existing property definition. Every update must leave values that
+/// were valid for the property before the update valid afterwards.
+fn apply_property_update(
+    property: &PropertyDefinition,
+    update: &PropertyUpdate,
+) -> Result<PropertyDefinition, ApplyError> {
+    let builder = property.clone().into_builder();
+
+    let builder = match update.update_type() {
+        UpdateType::AddEnumOptions => {
+            if property.data_type() != &DataType::Enum {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Cannot add enum options to property {} of type {:?}",
+                    property.name(),
+                    property.data_type()
+                )));
+            }
+
+            // New options are appended so that the indexes of existing enum values are kept
+            let mut enum_options = property.enum_options().to_vec();
+            for option in update.enum_options() {
+                if enum_options.contains(option) {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Property {} already has enum option {}",
+                        property.name(),
+                        option
+                    )));
+                }
+                enum_options.push(option.to_string());
+            }
+            builder.with_enum_options(enum_options)
+        }
+        UpdateType::Deprecate => {
+            if *property.deprecated() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Property {} is already deprecated",
+                    property.name()
+                )));
+            }
+            builder.with_deprecated(true).with_required(false)
+        }
+        UpdateType::SetDescription => builder.with_description(update.description().to_string()),
+        UpdateType::RelaxRequired => {
+            if !*property.required() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Property {} is not required",
+                    property.name()
+                )));
+            }
+            builder.with_required(false)
+        }
+    };
+
+    builder.build().map_err(|err| {
+        ApplyError::InvalidTransaction(format!("Cannot build property definition: {}", err))
+    })
+}
+
 fn check_permission(
     perm_checker: &PermissionChecker,
     signer: &str,
@@ -263,15 +344,18 @@ mod tests {
     use std::collections::HashMap;
 
     use grid_sdk::protocol::pike::state::{AgentBuilder, AgentListBuilder};
-    use grid_sdk::protocol::schema::payload::{SchemaCreateBuilder, SchemaUpdateBuilder};
+    use grid_sdk::protocol::schema::payload::{
+        PropertyUpdateBuilder, SchemaCreateBuilder, SchemaUpdateBuilder,
+    };
     use grid_sdk::protocol::schema::state::{
-        DataType, PropertyDefinitionBuilder, SchemaBuilder, SchemaListBuilder,
+        DataType, IdentifierFormatBuilder, PropertyDefinitionBuilder, SchemaBuilder,
+        SchemaListBuilder,
     };
     use grid_sdk::protos::IntoBytes;
     use sawtooth_sdk::processor::handler::ApplyError;
     use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
 
-    use crate::state::{compute_agent_address, compute_schema_address};
+    use grid_sdk::protocol::addressing::{compute_agent_address, compute_schema_address};
 
     #[derive(Default)]
     /// A MockTransactionContext that can be used to test GridSchemaState
@@ -414,12 +498,19 @@ mod tests {
                 .build()
                 .unwrap();
 
+            let required_definition = PropertyDefinitionBuilder::new()
+                .with_name("REQUIRED".to_string())
+                .with_data_type(DataType::String)
+                .with_required(true)
+                .build()
+                .unwrap();
+
             let builder = SchemaBuilder::new();
             let schema = builder
                 .with_name("TestSchema".to_string())
                 .with_description("Test Schema".to_string())
                 .with_owner("test_org".to_string())
-                .with_properties(vec![property_definition.clone()])
+                .with_properties(vec![property_definition.clone(), required_definition])
                 .build()
                 .unwrap();
 
@@ -600,6 +691,43 @@ mod tests {
         assert!(schema_create(&action, signer, &state, &perm_checker).is_ok());
     }
 
+    #[test]
+    // Test that the identifier format of a SchemaCreateAction is stored with the schema
+    fn test_create_schema_handler_with_identifier_format() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let property_definition = PropertyDefinitionBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::String)
+            .build()
+            .unwrap();
+
+        let identifier_format = IdentifierFormatBuilder::new()
+            .with_pattern("LB-[0-9]{4}".to_string())
+            .build()
+            .unwrap();
+
+        let action = SchemaCreateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_description("Test Schema".to_string())
+            .with_properties(vec![property_definition])
+            .with_identifier_format(identifier_format.clone())
+            .build()
+            .unwrap();
+
+        assert!(schema_create(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state
+            .get_schema("TestSchema")
+            .expect("Failed to fetch schema")
+            .expect("No schema found");
+        assert_eq!(schema.identifier_format(), Some(&identifier_format));
+    }
+
     #[test]
     // Test that if the schema does not exist in state an InvalidTransaction is returned
     fn test_update_schema_handler_schema_does_not_exists() {
@@ -832,5 +960,203 @@ mod tests {
             .unwrap();
 
         assert!(schema_update(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state.get_schema("TestSchema").unwrap().unwrap();
+        assert_eq!(schema.version(), &2);
+        assert_eq!(schema.properties()[2], property_definition);
+    }
+
+    #[test]
+    // Test that a required property cannot be added to an existing schema
+    fn test_update_schema_handler_required_property() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let property_definition = PropertyDefinitionBuilder::new()
+            .with_name("NEW".to_string())
+            .with_data_type(DataType::String)
+            .with_required(true)
+            .build()
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_properties(vec![property_definition])
+            .build()
+            .unwrap();
+
+        match schema_update(&action, signer, &state, &perm_checker) {
+            Ok(()) => panic!("Property is required, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Cannot add required property NEW"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that enum options are appended to an enum property and the version is incremented
+    fn test_update_schema_handler_add_enum_options() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let property_update = PropertyUpdateBuilder::new()
+            .with_property_name("TEST".to_string())
+            .with_update_type(UpdateType::AddEnumOptions)
+            .with_enum_options(vec!["Four".to_string()])
+            .build()
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_property_updates(vec![property_update])
+            .build()
+            .unwrap();
+
+        assert!(schema_update(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state.get_schema("TestSchema").unwrap().unwrap();
+        assert_eq!(schema.version(), &2);
+        assert_eq!(
+            schema.properties()[0].enum_options(),
+            &[
+                "One".to_string(),
+                "Two".to_string(),
+                "Three".to_string(),
+                "Four".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    // Test that existing enum options cannot be added again and that only enum properties can
+    // have options added
+    fn test_update_schema_handler_add_enum_options_invalid() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        for (property_name, expected) in &[
+            ("TEST", "Property TEST already has enum option Two"),
+            ("REQUIRED", "Cannot add enum options to property REQUIRED"),
+        ] {
+            let property_update = PropertyUpdateBuilder::new()
+                .with_property_name(property_name.to_string())
+                .with_update_type(UpdateType::AddEnumOptions)
+                .with_enum_options(vec!["Two".to_string()])
+                .build()
+                .unwrap();
+
+            let action = SchemaUpdateBuilder::new()
+                .with_schema_name("TestSchema".to_string())
+                .with_property_updates(vec![property_update])
+                .build()
+                .unwrap();
+
+            match schema_update(&action, signer, &state, &perm_checker) {
+                Ok(()) => panic!("Update is invalid, InvalidTransaction should be returned"),
+                Err(ApplyError::InvalidTransaction(err)) => assert!(err.contains(expected)),
+                Err(err) => panic!("Should have gotten invalid error but got {}", err),
+            }
+        }
+    }
+
+    #[test]
+    // Test that deprecating a property makes it optional and that it cannot be deprecated twice
+    fn test_update_schema_handler_deprecate() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let property_update = PropertyUpdateBuilder::new()
+            .with_property_name("REQUIRED".to_string())
+            .with_update_type(UpdateType::Deprecate)
+            .build()
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_property_updates(vec![property_update])
+            .build()
+            .unwrap();
+
+        assert!(schema_update(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state.get_schema("TestSchema").unwrap().unwrap();
+        assert!(schema.properties()[1].deprecated());
+        assert!(!schema.properties()[1].required());
+
+        match schema_update(&action, signer, &state, &perm_checker) {
+            Ok(()) => panic!("Property is deprecated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Property REQUIRED is already deprecated"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    // Test that descriptions can be changed and required properties made optional, but that an
+    // optional property cannot be relaxed
+    fn test_update_schema_handler_description_and_relax_required() {
+        let transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent();
+        let perm_checker = PermissionChecker::new(&transaction_context);
+        let state = GridSchemaState::new(&transaction_context);
+        let signer = "agent_public_key";
+
+        let set_description = PropertyUpdateBuilder::new()
+            .with_property_name("TEST".to_string())
+            .with_update_type(UpdateType::SetDescription)
+            .with_description("Updated".to_string())
+            .build()
+            .unwrap();
+        let relax_required = PropertyUpdateBuilder::new()
+            .with_property_name("REQUIRED".to_string())
+            .with_update_type(UpdateType::RelaxRequired)
+            .build()
+            .unwrap();
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_property_updates(vec![set_description, relax_required.clone()])
+            .build()
+            .unwrap();
+
+        assert!(schema_update(&action, signer, &state, &perm_checker).is_ok());
+
+        let schema = state.get_schema("TestSchema").unwrap().unwrap();
+        assert_eq!(schema.properties()[0].description(), "Updated");
+        assert!(!schema.properties()[1].required());
+        assert!(!schema.properties()[1].deprecated());
+
+        let action = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_property_updates(vec![relax_required])
+            .build()
+            .unwrap();
+
+        match schema_update(&action, signer, &state, &perm_checker) {
+            Ok(()) => panic!("Property is optional, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Property REQUIRED is not required"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
     }
 }
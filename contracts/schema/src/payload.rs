@@ -23,6 +23,7 @@ cfg_if! {
 use grid_sdk::protocol::schema::payload::{
     Action, SchemaCreateAction, SchemaPayload, SchemaUpdateAction,
 };
+use grid_sdk::protocol::schema::state::{IdentifierFormatBuilder, PropertyDefinition};
 
 pub fn validate_payload(payload: &SchemaPayload) -> Result<(), ApplyError> {
     match payload.action() {
@@ -43,7 +44,19 @@ fn validate_schema_create_action(create_action: &SchemaCreateAction) -> Result<(
             "Properties must not be empty",
         )));
     }
-    Ok(())
+
+    // Rebuilding the identifier format checks that its pattern compiles and that its lengths
+    // are consistent
+    if let Some(identifier_format) = create_action.identifier_format() {
+        IdentifierFormatBuilder::new()
+            .with_pattern(identifier_format.pattern().to_string())
+            .with_min_length(*identifier_format.min_length())
+            .with_max_length(*identifier_format.max_length())
+            .build()
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
+    }
+
+    validate_property_constraints(create_action.properties())
 }
 
 fn validate_schema_update_action(update_action: &SchemaUpdateAction) -> Result<(), ApplyError> {
@@ -53,11 +66,41 @@ fn validate_schema_update_action(update_action: &SchemaUpdateAction) -> Result<(
         )));
     }
 
-    if update_action.properties().is_empty() {
+    if update_action.properties().is_empty() && update_action.property_updates().is_empty() {
         return Err(ApplyError::InvalidTransaction(String::from(
-            "Properties must not be empty",
+            "Properties or property updates must not be empty",
         )));
     }
+
+    if update_action
+        .property_updates()
+        .iter()
+        .any(|update| update.property_name().is_empty())
+    {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Property name must be set for each property update",
+        )));
+    }
+
+    validate_property_constraints(update_action.properties())
+}
+
+/// Rebuilding the constraints of each property, including struct properties, checks that their
+/// patterns compile and that their bounds are consistent
+fn validate_property_constraints(properties: &[PropertyDefinition]) -> Result<(), ApplyError> {
+    for property in properties {
+        if let Some(constraints) = property.constraints() {
+            constraints.clone().into_builder().build().map_err(|err| {
+                ApplyError::InvalidTransaction(format!(
+                    "Invalid constraints for property {}: {}",
+                    property.name(),
+                    err
+                ))
+            })?;
+        }
+
+        validate_property_constraints(property.struct_properties())?;
+    }
     Ok(())
 }
 
@@ -139,6 +182,90 @@ mod tests {
         );
     }
 
+    #[test]
+    // Test that a schema create action with an identifier format that cannot be compiled is
+    // invalid. This test needs to use the proto directly because the IdentifierFormatBuilder
+    // rejects invalid patterns.
+    fn test_validate_schema_create_action_identifier_format() {
+        let mut payload_proto = protos::schema_payload::SchemaPayload::new();
+        payload_proto.set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_CREATE);
+
+        let mut property_definition = protos::schema_state::PropertyDefinition::new();
+        property_definition.set_name("TEST".into());
+        property_definition
+            .set_data_type(protos::schema_state::PropertyDefinition_DataType::STRING);
+
+        let mut identifier_format = protos::schema_state::IdentifierFormat::new();
+        identifier_format.set_pattern("LB-[0-9".into());
+
+        let mut action = protos::schema_payload::SchemaCreateAction::new();
+        action.set_schema_name("TestSchema".into());
+        action.set_properties(vec![property_definition].into());
+        action.set_identifier_format(identifier_format.clone());
+        payload_proto.set_schema_create(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_err(),
+            "Identifier pattern must compile"
+        );
+
+        identifier_format.set_pattern("LB-[0-9]{4}".into());
+        action.set_identifier_format(identifier_format);
+        payload_proto.set_schema_create(action);
+        let payload = payload_proto.into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_ok(),
+            "Payload should be valid"
+        );
+    }
+
+    #[test]
+    // Test that a schema create action with property constraints that are inconsistent is
+    // invalid, including constraints of struct properties. This test needs to use the proto
+    // directly because the PropertyConstraintsBuilder rejects invalid constraints.
+    fn test_validate_schema_create_action_property_constraints() {
+        let mut payload_proto = protos::schema_payload::SchemaPayload::new();
+        payload_proto.set_action(protos::schema_payload::SchemaPayload_Action::SCHEMA_CREATE);
+
+        let mut constraints = protos::schema_state::PropertyConstraints::new();
+        constraints.set_has_min_number(true);
+        constraints.set_min_number(10);
+        constraints.set_has_max_number(true);
+        constraints.set_max_number(5);
+
+        let mut struct_property = protos::schema_state::PropertyDefinition::new();
+        struct_property.set_name("HEIGHT".into());
+        struct_property.set_data_type(protos::schema_state::PropertyDefinition_DataType::NUMBER);
+        struct_property.set_constraints(constraints.clone());
+
+        let mut property_definition = protos::schema_state::PropertyDefinition::new();
+        property_definition.set_name("DIMENSIONS".into());
+        property_definition
+            .set_data_type(protos::schema_state::PropertyDefinition_DataType::STRUCT);
+        property_definition.set_struct_properties(vec![struct_property.clone()].into());
+
+        let mut action = protos::schema_payload::SchemaCreateAction::new();
+        action.set_schema_name("TestSchema".into());
+        action.set_properties(vec![property_definition.clone()].into());
+        payload_proto.set_schema_create(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_err(),
+            "max_number must not be less than min_number"
+        );
+
+        constraints.set_max_number(20);
+        struct_property.set_constraints(constraints);
+        property_definition.set_struct_properties(vec![struct_property].into());
+        action.set_properties(vec![property_definition].into());
+        payload_proto.set_schema_create(action);
+        let payload = payload_proto.into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_ok(),
+            "Payload should be valid"
+        );
+    }
+
     #[test]
     // Test a payload with a schema update action is properly validated. This test needs to use
     // the proto directly originally to be able to mimic the scenarios possbile from creating
@@ -175,7 +302,29 @@ mod tests {
         let payload = payload_proto.clone().into_native().unwrap();
         assert!(
             validate_payload(&payload).is_err(),
-            "Properties must not be empty"
+            "Properties or property updates must not be empty"
+        );
+
+        // create payload with a property update without a property name
+        let mut property_update = protos::schema_payload::PropertyUpdate::new();
+        property_update
+            .set_update_type(protos::schema_payload::PropertyUpdate_UpdateType::DEPRECATE);
+        action.set_property_updates(vec![property_update.clone()].into());
+        payload_proto.set_schema_update(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_err(),
+            "Property name must be set for each property update"
+        );
+
+        // create payload with only a property update
+        property_update.set_property_name("TEST".into());
+        action.set_property_updates(vec![property_update].into());
+        payload_proto.set_schema_update(action);
+        let payload = payload_proto.clone().into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_ok(),
+            "Payload should be valid"
         );
 
         // create payload with full payload
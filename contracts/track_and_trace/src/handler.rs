@@ -29,29 +29,37 @@ cfg_if! {
     }
 }
 
+use grid_sdk::protocol::addressing::{GRID_NAMESPACE, PIKE_NAMESPACE, TRACK_AND_TRACE_NAMESPACE};
 use grid_sdk::protocol::errors::BuilderError;
-use grid_sdk::protocol::schema::state::{PropertyDefinition, PropertyValue};
+use grid_sdk::protocol::schema::state::{DataType, PropertyDefinition, PropertyValue};
+use grid_sdk::protocol::schema::validation::{validate_properties, validate_property_value};
 use grid_sdk::protocol::track_and_trace::payload::{
-    Action, AnswerProposalAction, CreateProposalAction, CreateRecordAction, FinalizeRecordAction,
-    Response, RevokeReporterAction, TrackAndTracePayload, UpdatePropertiesAction,
+    Action, AggregateRecordsAction, AnswerProposalAction, ArchiveRecordAction,
+    CreateProposalAction, CreateRecordAction, DisaggregateRecordsAction, FinalizeRecordAction,
+    ReopenRecordAction, Response, RevokeReporterAction, TrackAndTracePayload,
+    UpdatePropertiesAction, UpdateRecordLocationAction,
 };
 use grid_sdk::protocol::track_and_trace::state::{
-    AssociatedAgentBuilder, PropertyBuilder, PropertyPageBuilder, ProposalBuilder,
-    ProposalListBuilder, RecordBuilder, ReportedValueBuilder, ReporterBuilder, Role, Status,
+    AggregationBuilder, AssociatedAgent, AssociatedAgentBuilder, PropertyBuilder,
+    PropertyPageBuilder, ProposalBuilder, ProposalListBuilder, Record, RecordBuilder,
+    ReportedValueBuilder, Reporter, ReporterBuilder, ReporterConstraints, Role, Status,
 };
 
 use grid_sdk::protos::FromBytes;
 
-use crate::addressing::*;
 use crate::payload::validate_payload;
 use crate::state::TrackAndTraceState;
 
 const PROPERTY_PAGE_MAX_LENGTH: usize = 256;
 
+// By default a payload may not be older than the last one applied to any record it touches
+pub const DEFAULT_MAX_TIMESTAMP_SKEW: u64 = 0;
+
 pub struct TrackAndTraceTransactionHandler {
     family_name: String,
     family_versions: Vec<String>,
     namespaces: Vec<String>,
+    max_timestamp_skew: u64,
 }
 
 impl TrackAndTraceTransactionHandler {
@@ -61,13 +69,21 @@ impl TrackAndTraceTransactionHandler {
             family_name: "grid_track_and_trace".to_string(),
             family_versions: vec!["1.0".to_string()],
             namespaces: vec![
-                get_track_and_trace_prefix(),
-                get_pike_prefix(),
-                get_grid_prefix(),
+                TRACK_AND_TRACE_NAMESPACE.to_string(),
+                PIKE_NAMESPACE.to_string(),
+                GRID_NAMESPACE.to_string(),
             ],
+            max_timestamp_skew: DEFAULT_MAX_TIMESTAMP_SKEW,
         }
     }
 
+    /// Sets how many seconds a payload's timestamp may lag behind the last timestamp recorded
+    /// on a record it touches, to tolerate clock skew between clients
+    pub fn with_max_timestamp_skew(mut self, max_timestamp_skew: u64) -> Self {
+        self.max_timestamp_skew = max_timestamp_skew;
+        self
+    }
+
     fn _create_record(
         &self,
         payload: &CreateRecordAction,
@@ -103,48 +119,36 @@ impl TrackAndTraceTransactionHandler {
             }
         };
 
+        validate_properties(schema.properties(), payload.properties())
+            .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
+
+        let product_id = payload.product_id();
+        if !product_id.is_empty()
+            && state
+                .get_product(
+                    payload.product_namespace(),
+                    payload.product_schema_name(),
+                    product_id,
+                )?
+                .is_none()
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Product does not exist: {}",
+                product_id
+            )));
+        }
+        check_location_exists(state, payload.location_id())?;
+
         let mut type_schemata: HashMap<&str, PropertyDefinition> = HashMap::new();
-        let mut required_properties: HashMap<&str, PropertyDefinition> = HashMap::new();
         let mut provided_properties: HashMap<&str, PropertyValue> = HashMap::new();
         for property in schema.properties() {
             type_schemata.insert(property.name(), property.clone());
-            if *property.required() {
-                required_properties.insert(property.name(), property.clone());
-            }
         }
 
         for property in payload.properties() {
             provided_properties.insert(property.name(), property.clone());
         }
 
-        for name in required_properties.keys() {
-            if !provided_properties.contains_key(name) {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Required property {} not provided",
-                    name
-                )));
-            }
-        }
-
-        for (provided_name, provided_properties) in provided_properties.clone() {
-            let required_type = match type_schemata.get(provided_name) {
-                Some(required_type) => required_type.data_type(),
-                None => {
-                    return Err(ApplyError::InvalidTransaction(format!(
-                        "Provided property {} is not in schema",
-                        provided_name
-                    )));
-                }
-            };
-            let provided_type = provided_properties.data_type();
-            if provided_type != required_type {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Value provided for {} is the wrong type",
-                    provided_name
-                )));
-            };
-        }
-
         let owner = AssociatedAgentBuilder::new()
             .with_agent_id(signer.to_string())
             .with_timestamp(timestamp)
@@ -154,9 +158,14 @@ impl TrackAndTraceTransactionHandler {
         let new_record = RecordBuilder::new()
             .with_record_id(record_id.to_string())
             .with_schema(schema_name.to_string())
+            .with_schema_version(*schema.version())
             .with_field_final(false)
             .with_owners(vec![owner.clone()])
             .with_custodians(vec![owner])
+            .with_product_id(product_id.to_string())
+            .with_location_id(payload.location_id().to_string())
+            .with_product_namespace(payload.product_namespace().clone())
+            .with_product_schema_name(payload.product_schema_name().to_string())
             .build()
             .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
 
@@ -254,6 +263,13 @@ impl TrackAndTraceTransactionHandler {
                 record_id
             )));
         }
+        if final_record.parent_record_id().is_some() || !final_record.child_record_ids().is_empty()
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record must be disaggregated before it is finalized: {}",
+                record_id
+            )));
+        }
 
         let updated_record = final_record
             .clone()
@@ -267,6 +283,153 @@ impl TrackAndTraceTransactionHandler {
         Ok(())
     }
 
+    fn _reopen_record(
+        &self,
+        payload: &ReopenRecordAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let record = match state.get_record(record_id)? {
+            Some(record) => record,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not exist: {}",
+                    record_id
+                )));
+            }
+        };
+        let owner = match record.owners().last() {
+            Some(x) => x.agent_id().to_string(),
+            None => {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Owner was not found",
+                )));
+            }
+        };
+        let custodian = match record.custodians().last() {
+            Some(x) => x.agent_id().to_string(),
+            None => {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Custodian was not found",
+                )));
+            }
+        };
+
+        if owner != signer && custodian != signer {
+            return Err(ApplyError::InvalidTransaction(
+                "Must be owner or custodian to reopen record".to_string(),
+            ));
+        }
+        if !*record.field_final() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record is not final: {}",
+                record_id
+            )));
+        }
+        if *record.archived() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record is archived and must be unarchived before it is reopened: {}",
+                record_id
+            )));
+        }
+        if record
+            .reopen_approvals()
+            .iter()
+            .any(|agent| agent == signer)
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Agent {} has already approved reopening record {}",
+                signer, record_id
+            )));
+        }
+
+        let mut approvals = record.reopen_approvals().to_vec();
+        approvals.push(signer.to_string());
+
+        // The record is only reopened once both the current owner and the current custodian
+        // have approved; until then the approvals are kept on the record
+        let reopened = approvals.contains(&owner) && approvals.contains(&custodian);
+
+        let updated_record = if reopened {
+            info!("Reopening record {}", record_id);
+            record
+                .into_builder()
+                .with_field_final(false)
+                .with_reopen_approvals(vec![])
+                .build()
+        } else {
+            record
+                .into_builder()
+                .with_reopen_approvals(approvals)
+                .build()
+        }
+        .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+
+        state.set_record(record_id, updated_record)?;
+
+        Ok(())
+    }
+
+    fn _archive_record(
+        &self,
+        payload: &ArchiveRecordAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let record = match state.get_record(record_id)? {
+            Some(record) => record,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not exist: {}",
+                    record_id
+                )));
+            }
+        };
+        let owner = match record.owners().last() {
+            Some(x) => x,
+            None => {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Owner was not found",
+                )));
+            }
+        };
+
+        if owner.agent_id() != signer {
+            return Err(ApplyError::InvalidTransaction(
+                "Must be owner to archive record".to_string(),
+            ));
+        }
+        if record.archived() == payload.archived() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record {} is already {}",
+                record_id,
+                if *payload.archived() {
+                    "archived"
+                } else {
+                    "unarchived"
+                }
+            )));
+        }
+        if *payload.archived() && !*record.field_final() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record must be final before it is archived: {}",
+                record_id
+            )));
+        }
+
+        let updated_record = record
+            .into_builder()
+            .with_archived(*payload.archived())
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+
+        state.set_record(record_id, updated_record)?;
+
+        Ok(())
+    }
+
     fn _update_properties(
         &self,
         payload: &UpdatePropertiesAction,
@@ -296,7 +459,6 @@ impl TrackAndTraceTransactionHandler {
 
         for update in updates {
             let name = update.name();
-            let data_type = update.data_type();
 
             let prop = match state.get_property(record_id, name)? {
                 Some(prop) => prop,
@@ -308,29 +470,70 @@ impl TrackAndTraceTransactionHandler {
                 }
             };
 
-            let mut allowed = false;
-            let mut reporter_index = 0;
-            for reporter in prop.reporters() {
-                if reporter.public_key() == signer && *reporter.authorized() {
-                    allowed = true;
-                    reporter_index = *reporter.index();
-                    break;
-                }
-            }
-            if !allowed {
+            // An agent granted the reporter role again after a grant lapsed has an entry for
+            // each grant, so the entry active at the timestamp is the one reported through
+            let mut signer_reporters = prop
+                .reporters()
+                .iter()
+                .filter(|reporter| reporter.public_key() == signer && *reporter.authorized())
+                .peekable();
+            if signer_reporters.peek().is_none() {
                 return Err(ApplyError::InvalidTransaction(format!(
                     "Reporter is not authorized: {}",
                     signer
                 )));
             }
-
-            if data_type != prop.property_definition().data_type() {
-                return Err(ApplyError::InvalidTransaction(format!(
-                    "Update has wrong type: {:?} != {:?}",
-                    data_type,
-                    prop.property_definition().data_type()
-                )));
-            }
+            let reporter = match signer_reporters.find(|reporter| reporter.is_active(timestamp)) {
+                Some(reporter) => reporter.clone(),
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Reporter {} is not authorized to report {} at {}",
+                        signer, name, timestamp
+                    )));
+                }
+            };
+            let reporter_index = *reporter.index();
+
+            validate_property_value(prop.property_definition(), update)
+                .map_err(|err| ApplyError::InvalidTransaction(err.to_string()))?;
+
+            // Reporters with a limited rate have their last report recorded on the property
+            let prop = match reporter.constraints() {
+                Some(constraints) => {
+                    check_reporter_constraints(&reporter, constraints, update, timestamp)?;
+
+                    if *constraints.min_report_interval() > 0 {
+                        let reporters = prop
+                            .reporters()
+                            .iter()
+                            .map(|prop_reporter| {
+                                if prop_reporter.index() == reporter.index() {
+                                    prop_reporter
+                                        .clone()
+                                        .into_builder()
+                                        .with_last_reported_at(timestamp)
+                                        .build()
+                                        .map_err(|err| {
+                                            map_builder_error_to_apply_error(err, "Reporter")
+                                        })
+                                } else {
+                                    Ok(prop_reporter.clone())
+                                }
+                            })
+                            .collect::<Result<Vec<_>, ApplyError>>()?;
+                        let updated_prop = prop
+                            .into_builder()
+                            .with_reporters(reporters)
+                            .build()
+                            .map_err(|err| map_builder_error_to_apply_error(err, "Property"))?;
+                        state.set_property(record_id, name, updated_prop.clone())?;
+                        updated_prop
+                    } else {
+                        prop
+                    }
+                }
+                None => prop,
+            };
 
             let page_number = prop.current_page();
             let page = match state.get_property_page(record_id, name, *page_number)? {
@@ -441,7 +644,23 @@ impl TrackAndTraceTransactionHandler {
         };
 
         let mut proposals = match state.get_proposal_list(&record_id, &receiving_agent)? {
-            Some(proposals) => proposals.proposals().to_vec(),
+            Some(proposals) => proposals
+                .proposals()
+                .iter()
+                .map(|proposal| {
+                    // An open proposal that has expired no longer blocks a new one
+                    if proposal.status() == &Status::Open && proposal.is_expired(timestamp) {
+                        proposal
+                            .clone()
+                            .into_builder()
+                            .with_status(Status::Expired)
+                            .build()
+                            .map_err(|err| map_builder_error_to_apply_error(err, "Proposal"))
+                    } else {
+                        Ok(proposal.clone())
+                    }
+                })
+                .collect::<Result<Vec<_>, ApplyError>>()?,
             None => vec![],
         };
 
@@ -509,6 +728,13 @@ impl TrackAndTraceTransactionHandler {
                     "Only the custodian can create a proposal to change custodianship",
                 )));
             }
+
+            if let Some(parent_record_id) = proposal_record.parent_record_id() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} is aggregated into {}; custody must be transferred with the parent",
+                    record_id, parent_record_id
+                )));
+            }
         }
 
         if role == &Role::Reporter {
@@ -530,6 +756,26 @@ impl TrackAndTraceTransactionHandler {
                     "No properties were specified for authorization",
                 )));
             }
+            for constraints in payload.reporter_constraints() {
+                if constraints.min_value().is_none() && constraints.max_value().is_none() {
+                    continue;
+                }
+                let prop = match state.get_property(record_id, constraints.property_name())? {
+                    Some(prop) => prop,
+                    None => {
+                        return Err(ApplyError::InvalidTransaction(format!(
+                            "Property does not exist: {}",
+                            constraints.property_name()
+                        )));
+                    }
+                };
+                if prop.property_definition().data_type() != &DataType::Number {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Value ranges can only constrain Number properties: {}",
+                        constraints.property_name()
+                    )));
+                }
+            }
         }
 
         let new_proposal = ProposalBuilder::new()
@@ -541,6 +787,10 @@ impl TrackAndTraceTransactionHandler {
             .with_properties(properties.to_vec())
             .with_status(Status::Open)
             .with_terms(terms.to_string())
+            .with_expires_at(*payload.expires_at())
+            .with_reporter_valid_from(*payload.reporter_valid_from())
+            .with_reporter_valid_until(*payload.reporter_valid_until())
+            .with_reporter_constraints(payload.reporter_constraints().to_vec())
             .build()
             .map_err(|err| map_builder_error_to_apply_error(err, "Proposal"))?;
 
@@ -626,6 +876,21 @@ impl TrackAndTraceTransactionHandler {
                 updated_proposal_builder = updated_proposal_builder.with_status(Status::Rejected);
             }
 
+            Response::Accept if current_proposal.is_expired(timestamp) => {
+                if current_proposal.receiving_agent() != signer {
+                    return Err(ApplyError::InvalidTransaction(String::from(
+                        "Only the receiving agent can accept a proposal",
+                    )));
+                };
+
+                info!(
+                    "Proposal expired at {} and can no longer be accepted",
+                    current_proposal.expires_at()
+                );
+
+                updated_proposal_builder = updated_proposal_builder.with_status(Status::Expired);
+            }
+
             Response::Accept => {
                 if current_proposal.receiving_agent() != signer {
                     return Err(ApplyError::InvalidTransaction(String::from(
@@ -729,10 +994,11 @@ impl TrackAndTraceTransactionHandler {
                                                 })
                                         } else if reporter.public_key() == receiving_agent {
                                             authorized = true;
-                                            reporter
-                                                .clone()
-                                                .into_builder()
+                                            // The owner reports without a window or constraints
+                                            ReporterBuilder::new()
+                                                .with_public_key(receiving_agent.to_string())
                                                 .with_authorized(true)
+                                                .with_index(*reporter.index())
                                                 .build()
                                                 .map_err(|err| {
                                                     map_builder_error_to_apply_error(
@@ -777,6 +1043,11 @@ impl TrackAndTraceTransactionHandler {
                             info!(
                                 "Record custodian does not match the issuing agent of the proposal"
                             );
+                            updated_proposal_builder =
+                                updated_proposal_builder.with_status(Status::Canceled);
+                        } else if proposal_record.parent_record_id().is_some() {
+                            info!("Record has been aggregated since the proposal was created");
+
                             updated_proposal_builder =
                                 updated_proposal_builder.with_status(Status::Canceled);
                         } else {
@@ -789,8 +1060,9 @@ impl TrackAndTraceTransactionHandler {
                                 })?;
 
                             let mut record_custodians = proposal_record.custodians().to_vec();
-                            record_custodians.push(new_custodian);
+                            record_custodians.push(new_custodian.clone());
                             let updated_record = proposal_record
+                                .clone()
                                 .into_builder()
                                 .with_custodians(record_custodians)
                                 .build()
@@ -798,6 +1070,13 @@ impl TrackAndTraceTransactionHandler {
 
                             state.set_record(record_id, updated_record)?;
 
+                            // Aggregated records travel with their parent
+                            self._transfer_custody_to_children(
+                                &proposal_record,
+                                &new_custodian,
+                                state,
+                            )?;
+
                             updated_proposal_builder =
                                 updated_proposal_builder.with_status(Status::Accepted);
                         }
@@ -811,7 +1090,9 @@ impl TrackAndTraceTransactionHandler {
                         } else {
                             let reporter_builder = ReporterBuilder::new()
                                 .with_public_key(receiving_agent.to_string())
-                                .with_authorized(true);
+                                .with_authorized(true)
+                                .with_valid_from(*current_proposal.reporter_valid_from())
+                                .with_valid_until(*current_proposal.reporter_valid_until());
 
                             for prop_name in current_proposal.properties() {
                                 let prop = match state.get_property(record_id, prop_name)? {
@@ -823,13 +1104,20 @@ impl TrackAndTraceTransactionHandler {
                                         )));
                                     }
                                 };
-                                let reporter = reporter_builder
+                                let mut prop_reporter_builder = reporter_builder
                                     .clone()
-                                    .with_index(prop.reporters().len() as u32)
-                                    .build()
-                                    .map_err(|err| {
-                                        map_builder_error_to_apply_error(err, "Reporter")
-                                    })?;
+                                    .with_index(prop.reporters().len() as u32);
+                                if let Some(constraints) = current_proposal
+                                    .reporter_constraints()
+                                    .iter()
+                                    .find(|constraints| constraints.property_name() == prop_name)
+                                {
+                                    prop_reporter_builder =
+                                        prop_reporter_builder.with_constraints(constraints.clone());
+                                }
+                                let reporter = prop_reporter_builder.build().map_err(|err| {
+                                    map_builder_error_to_apply_error(err, "Reporter")
+                                })?;
 
                                 let mut updated_reporter_list = prop.reporters().to_vec();
                                 updated_reporter_list.push(reporter);
@@ -928,37 +1216,43 @@ impl TrackAndTraceTransactionHandler {
                 }
             };
 
+            // A reporter may have been authorized again after an earlier revocation, so every
+            // entry for the key that is still authorized is revoked
+            let mut is_reporter = false;
             let mut revoked = false;
             let new_reporters = prop
                 .reporters()
                 .to_vec()
                 .iter()
                 .map(|reporter| {
-                    if reporter.public_key() == reporter_id {
-                        if !*reporter.authorized() {
-                            return Err(ApplyError::InvalidTransaction(
-                                "Reporter is already unauthorized.".to_string(),
-                            ));
-                        }
-                        revoked = true;
-                        reporter
-                            .clone()
-                            .into_builder()
-                            .with_authorized(false)
-                            .build()
-                            .map_err(|err| map_builder_error_to_apply_error(err, "Reporter"))
-                    } else {
-                        Ok(reporter.clone())
+                    if reporter.public_key() != reporter_id {
+                        return Ok(reporter.clone());
+                    }
+                    is_reporter = true;
+                    if !*reporter.authorized() {
+                        return Ok(reporter.clone());
                     }
+                    revoked = true;
+                    reporter
+                        .clone()
+                        .into_builder()
+                        .with_authorized(false)
+                        .build()
+                        .map_err(|err| map_builder_error_to_apply_error(err, "Reporter"))
                 })
                 .collect::<Result<Vec<_>, ApplyError>>()?;
 
-            if !revoked {
+            if !is_reporter {
                 return Err(ApplyError::InvalidTransaction(format!(
                     "{} not a reporter for property {}",
                     reporter_id, prop_name
                 )));
             }
+            if !revoked {
+                return Err(ApplyError::InvalidTransaction(
+                    "Reporter is already unauthorized.".to_string(),
+                ));
+            }
             let updated_property = prop
                 .clone()
                 .into_builder()
@@ -971,48 +1265,460 @@ impl TrackAndTraceTransactionHandler {
 
         Ok(())
     }
-}
 
-fn map_builder_error_to_apply_error(err: BuilderError, protocol_name: &str) -> ApplyError {
-    ApplyError::InvalidTransaction(format!(
-        "Failed to build {}. {}",
-        protocol_name,
-        err.to_string()
-    ))
-}
+    fn _aggregate_records(
+        &self,
+        payload: &AggregateRecordsAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let parent_record_id = payload.parent_record_id();
+        let parent_record = get_aggregatable_record(state, parent_record_id, signer)?;
 
-impl TransactionHandler for TrackAndTraceTransactionHandler {
-    fn family_name(&self) -> String {
-        self.family_name.clone()
-    }
+        // A child may not be an ancestor of the parent, or the lineage would contain a cycle
+        let mut ancestor_id = parent_record.parent_record_id().map(String::from);
+        while let Some(id) = ancestor_id {
+            if payload.child_record_ids().contains(&id) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} cannot be aggregated into its own descendant {}",
+                    id, parent_record_id
+                )));
+            }
+            ancestor_id = match state.get_record(&id)? {
+                Some(ancestor) => ancestor.parent_record_id().map(String::from),
+                None => None,
+            };
+        }
 
-    fn family_versions(&self) -> Vec<String> {
-        self.family_versions.clone()
-    }
+        let mut parent_aggregations = parent_record.aggregations().to_vec();
+        for child_record_id in payload.child_record_ids() {
+            let child_record = get_aggregatable_record(state, child_record_id, signer)?;
 
-    fn namespaces(&self) -> Vec<String> {
-        self.namespaces.clone()
-    }
+            if let Some(current_parent) = child_record.parent_record_id() {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} is already aggregated into {}",
+                    child_record_id, current_parent
+                )));
+            }
 
-    fn apply(
-        &self,
-        request: &TpProcessRequest,
-        context: &mut dyn TransactionContext,
-    ) -> Result<(), ApplyError> {
-        let payload = TrackAndTracePayload::from_bytes(request.get_payload()).map_err(|err| {
-            ApplyError::InvalidTransaction(format!("Cannot build track and trace payload: {}", err))
-        })?;
+            let aggregation = AggregationBuilder::new()
+                .with_parent_record_id(parent_record_id.to_string())
+                .with_child_record_id(child_record_id.to_string())
+                .with_aggregated_at(timestamp)
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Aggregation"))?;
 
-        validate_payload(&payload)?;
+            let mut child_aggregations = child_record.aggregations().to_vec();
+            child_aggregations.push(aggregation.clone());
+            let updated_child = child_record
+                .into_builder()
+                .with_aggregations(child_aggregations)
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+            state.set_record(child_record_id, updated_child)?;
 
-        let signer = request.get_header().get_signer_public_key();
-        let mut state = TrackAndTraceState::new(context);
+            parent_aggregations.push(aggregation);
+        }
 
-        info!(
-            "Track and Trace payload: {:?} {}",
-            payload.action(),
-            payload.timestamp(),
-        );
+        let updated_parent = parent_record
+            .into_builder()
+            .with_aggregations(parent_aggregations)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+        state.set_record(parent_record_id, updated_parent)?;
+
+        Ok(())
+    }
+
+    fn _disaggregate_records(
+        &self,
+        payload: &DisaggregateRecordsAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+        timestamp: u64,
+    ) -> Result<(), ApplyError> {
+        let parent_record_id = payload.parent_record_id();
+        let mut parent_record = get_aggregatable_record(state, parent_record_id, signer)?;
+
+        for child_record_id in payload.child_record_ids() {
+            let child_record = match state.get_record(child_record_id)? {
+                Some(record) => record,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Record does not exist: {}",
+                        child_record_id
+                    )));
+                }
+            };
+
+            if child_record.parent_record_id() != Some(parent_record_id) {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record {} is not aggregated into {}",
+                    child_record_id, parent_record_id
+                )));
+            }
+
+            let updated_child =
+                end_aggregation(child_record, parent_record_id, child_record_id, timestamp)?;
+            state.set_record(child_record_id, updated_child)?;
+
+            parent_record =
+                end_aggregation(parent_record, parent_record_id, child_record_id, timestamp)?;
+        }
+
+        state.set_record(parent_record_id, parent_record)?;
+
+        Ok(())
+    }
+
+    fn _update_record_location(
+        &self,
+        payload: &UpdateRecordLocationAction,
+        state: &mut TrackAndTraceState,
+        signer: &str,
+    ) -> Result<(), ApplyError> {
+        let record_id = payload.record_id();
+        let record = match state.get_record(record_id)? {
+            Some(record) => record,
+            None => {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Record does not exist: {}",
+                    record_id
+                )));
+            }
+        };
+
+        if *record.field_final() {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record is final: {}",
+                record_id
+            )));
+        }
+        match record.custodians().last() {
+            Some(custodian) if custodian.agent_id() == signer => (),
+            Some(_) => {
+                return Err(ApplyError::InvalidTransaction(
+                    "Must be custodian to update record location".to_string(),
+                ));
+            }
+            None => {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Custodian was not found",
+                )));
+            }
+        }
+
+        let location_id = payload.location_id();
+        check_location_exists(state, location_id)?;
+
+        let updated_record = record
+            .clone()
+            .into_builder()
+            .with_location_id(location_id.to_string())
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+        state.set_record(record_id, updated_record)?;
+
+        // Aggregated records travel with the record that contains them
+        self._move_children_to_location(&record, location_id, state)
+    }
+
+    fn _move_children_to_location(
+        &self,
+        record: &Record,
+        location_id: &str,
+        state: &mut TrackAndTraceState,
+    ) -> Result<(), ApplyError> {
+        for child_record_id in record.child_record_ids() {
+            let child_record = match state.get_record(child_record_id)? {
+                Some(record) => record,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Aggregated record does not exist: {}",
+                        child_record_id
+                    )));
+                }
+            };
+
+            let updated_child = child_record
+                .clone()
+                .into_builder()
+                .with_location_id(location_id.to_string())
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+            state.set_record(child_record_id, updated_child)?;
+
+            self._move_children_to_location(&child_record, location_id, state)?;
+        }
+
+        Ok(())
+    }
+
+    fn _transfer_custody_to_children(
+        &self,
+        record: &Record,
+        custodian: &AssociatedAgent,
+        state: &mut TrackAndTraceState,
+    ) -> Result<(), ApplyError> {
+        for child_record_id in record.child_record_ids() {
+            let child_record = match state.get_record(child_record_id)? {
+                Some(record) => record,
+                None => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Aggregated record does not exist: {}",
+                        child_record_id
+                    )));
+                }
+            };
+
+            let mut record_custodians = child_record.custodians().to_vec();
+            record_custodians.push(custodian.clone());
+            let updated_child = child_record
+                .clone()
+                .into_builder()
+                .with_custodians(record_custodians)
+                .build()
+                .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+            state.set_record(child_record_id, updated_child)?;
+
+            self._transfer_custody_to_children(&child_record, custodian, state)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks that a location referenced by a record exists. An empty location id means the record's
+/// location is unknown and is always accepted.
+fn check_location_exists(state: &TrackAndTraceState, location_id: &str) -> Result<(), ApplyError> {
+    if !location_id.is_empty() && state.get_location(location_id)?.is_none() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Location does not exist: {}",
+            location_id
+        )));
+    }
+    Ok(())
+}
+
+/// Fetches a record taking part in an aggregation, checking that it is not final and that the
+/// signer is its current custodian
+fn get_aggregatable_record(
+    state: &TrackAndTraceState,
+    record_id: &str,
+    signer: &str,
+) -> Result<Record, ApplyError> {
+    let record = match state.get_record(record_id)? {
+        Some(record) => record,
+        None => {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record does not exist: {}",
+                record_id
+            )));
+        }
+    };
+
+    if *record.field_final() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Record is final: {}",
+            record_id
+        )));
+    }
+
+    match record.custodians().last() {
+        Some(custodian) if custodian.agent_id() == signer => Ok(record),
+        Some(_) => Err(ApplyError::InvalidTransaction(format!(
+            "Must be custodian of record {} to aggregate or disaggregate it",
+            record_id
+        ))),
+        None => Err(ApplyError::InvalidTransaction(String::from(
+            "Custodian was not found",
+        ))),
+    }
+}
+
+/// Marks the current aggregation of `child_record_id` into `parent_record_id` on `record` as
+/// ended at `timestamp`
+fn end_aggregation(
+    record: Record,
+    parent_record_id: &str,
+    child_record_id: &str,
+    timestamp: u64,
+) -> Result<Record, ApplyError> {
+    let aggregations = record
+        .aggregations()
+        .iter()
+        .map(|aggregation| {
+            if aggregation.is_current()
+                && aggregation.parent_record_id() == parent_record_id
+                && aggregation.child_record_id() == child_record_id
+            {
+                aggregation
+                    .clone()
+                    .into_builder()
+                    .with_disaggregated_at(timestamp)
+                    .build()
+                    .map_err(|err| map_builder_error_to_apply_error(err, "Aggregation"))
+            } else {
+                Ok(aggregation.clone())
+            }
+        })
+        .collect::<Result<Vec<_>, ApplyError>>()?;
+
+    record
+        .into_builder()
+        .with_aggregations(aggregations)
+        .build()
+        .map_err(|err| map_builder_error_to_apply_error(err, "Record"))
+}
+
+/// Checks a reported value against the rate and value range the reporter was granted
+fn check_reporter_constraints(
+    reporter: &Reporter,
+    constraints: &ReporterConstraints,
+    value: &PropertyValue,
+    timestamp: u64,
+) -> Result<(), ApplyError> {
+    let min_report_interval = *constraints.min_report_interval();
+    if min_report_interval > 0
+        && *reporter.last_reported_at() != 0
+        && timestamp
+            < reporter
+                .last_reported_at()
+                .saturating_add(min_report_interval)
+    {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Reporter {} may only report {} every {} seconds; last reported at {}",
+            reporter.public_key(),
+            constraints.property_name(),
+            min_report_interval,
+            reporter.last_reported_at()
+        )));
+    }
+
+    if value.data_type() == &DataType::Number && !constraints.allows_value(*value.number_value()) {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Value {} of {} is outside the range reporter {} is authorized for",
+            value.number_value(),
+            constraints.property_name(),
+            reporter.public_key()
+        )));
+    }
+    Ok(())
+}
+
+/// The records a payload reads or writes
+fn affected_record_ids(action: &Action) -> Vec<&str> {
+    match action {
+        Action::CreateRecord(action_payload) => vec![action_payload.record_id()],
+        Action::FinalizeRecord(action_payload) => vec![action_payload.record_id()],
+        Action::UpdateProperties(action_payload) => vec![action_payload.record_id()],
+        Action::CreateProposal(action_payload) => vec![action_payload.record_id()],
+        Action::AnswerProposal(action_payload) => vec![action_payload.record_id()],
+        Action::RevokeReporter(action_payload) => vec![action_payload.record_id()],
+        Action::ReopenRecord(action_payload) => vec![action_payload.record_id()],
+        Action::ArchiveRecord(action_payload) => vec![action_payload.record_id()],
+        Action::UpdateRecordLocation(action_payload) => vec![action_payload.record_id()],
+        Action::AggregateRecords(action_payload) => {
+            let mut record_ids = vec![action_payload.parent_record_id()];
+            record_ids.extend(action_payload.child_record_ids().iter().map(String::as_str));
+            record_ids
+        }
+        Action::DisaggregateRecords(action_payload) => {
+            let mut record_ids = vec![action_payload.parent_record_id()];
+            record_ids.extend(action_payload.child_record_ids().iter().map(String::as_str));
+            record_ids
+        }
+    }
+}
+
+/// Checks that `timestamp` is not older than the last timestamp recorded on `record`, less the
+/// allowed skew
+fn check_record_timestamp(
+    record: &Record,
+    timestamp: u64,
+    max_timestamp_skew: u64,
+) -> Result<(), ApplyError> {
+    if timestamp.saturating_add(max_timestamp_skew) < *record.last_timestamp() {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Timestamp {} is older than the last update to record {} at {}",
+            timestamp,
+            record.record_id(),
+            record.last_timestamp()
+        )));
+    }
+    Ok(())
+}
+
+/// Records `timestamp` as the last timestamp of the record, if it is newer
+fn advance_record_timestamp(
+    state: &mut TrackAndTraceState,
+    record_id: &str,
+    timestamp: u64,
+) -> Result<(), ApplyError> {
+    let record = match state.get_record(record_id)? {
+        Some(record) => record,
+        None => return Ok(()),
+    };
+
+    if *record.last_timestamp() < timestamp {
+        let updated_record = record
+            .into_builder()
+            .with_last_timestamp(timestamp)
+            .build()
+            .map_err(|err| map_builder_error_to_apply_error(err, "Record"))?;
+        state.set_record(record_id, updated_record)?;
+    }
+    Ok(())
+}
+
+fn map_builder_error_to_apply_error(err: BuilderError, protocol_name: &str) -> ApplyError {
+    ApplyError::InvalidTransaction(format!(
+        "Failed to build {}. {}",
+        protocol_name,
+        err.to_string()
+    ))
+}
+
+impl TransactionHandler for TrackAndTraceTransactionHandler {
+    fn family_name(&self) -> String {
+        self.family_name.clone()
+    }
+
+    fn family_versions(&self) -> Vec<String> {
+        self.family_versions.clone()
+    }
+
+    fn namespaces(&self) -> Vec<String> {
+        self.namespaces.clone()
+    }
+
+    fn apply(
+        &self,
+        request: &TpProcessRequest,
+        context: &mut dyn TransactionContext,
+    ) -> Result<(), ApplyError> {
+        let payload = TrackAndTracePayload::from_bytes(request.get_payload()).map_err(|err| {
+            ApplyError::InvalidTransaction(format!("Cannot build track and trace payload: {}", err))
+        })?;
+
+        validate_payload(&payload)?;
+
+        let signer = request.get_header().get_signer_public_key();
+        let mut state = TrackAndTraceState::new(context);
+
+        info!(
+            "Track and Trace payload: {:?} {}",
+            payload.action(),
+            payload.timestamp(),
+        );
+
+        let record_ids = affected_record_ids(payload.action());
+        for record_id in &record_ids {
+            if let Some(record) = state.get_record(record_id)? {
+                check_record_timestamp(&record, *payload.timestamp(), self.max_timestamp_skew)?;
+            }
+        }
 
         match payload.action() {
             Action::CreateRecord(action_payload) => {
@@ -1033,6 +1739,28 @@ impl TransactionHandler for TrackAndTraceTransactionHandler {
             Action::RevokeReporter(action_payload) => {
                 self._revoke_reporter(action_payload, &mut state, signer)?
             }
+            Action::AggregateRecords(action_payload) => {
+                self._aggregate_records(action_payload, &mut state, signer, *payload.timestamp())?
+            }
+            Action::DisaggregateRecords(action_payload) => self._disaggregate_records(
+                action_payload,
+                &mut state,
+                signer,
+                *payload.timestamp(),
+            )?,
+            Action::ReopenRecord(action_payload) => {
+                self._reopen_record(action_payload, &mut state, signer)?
+            }
+            Action::ArchiveRecord(action_payload) => {
+                self._archive_record(action_payload, &mut state, signer)?
+            }
+            Action::UpdateRecordLocation(action_payload) => {
+                self._update_record_location(action_payload, &mut state, signer)?
+            }
+        }
+
+        for record_id in &record_ids {
+            advance_record_timestamp(&mut state, record_id, *payload.timestamp())?;
         }
         Ok(())
     }
@@ -1067,18 +1795,31 @@ mod tests {
     use std::cell::RefCell;
     use std::collections::HashMap;
 
+    use grid_sdk::protocol::addressing::{
+        compute_agent_address, compute_custom_product_address, compute_gs1_location_address,
+        compute_gs1_product_address, compute_property_address, compute_proposal_address,
+        compute_record_address, compute_schema_address,
+    };
+    use grid_sdk::protocol::location::state::{
+        LocationBuilder, LocationListBuilder, LocationNamespace,
+    };
     use grid_sdk::protocol::pike::state::{AgentBuilder, AgentListBuilder};
+    use grid_sdk::protocol::product::state::{
+        ProductBuilder, ProductListBuilder, ProductNamespace,
+    };
     use grid_sdk::protocol::schema::state::{
-        DataType, PropertyDefinitionBuilder, PropertyValueBuilder, SchemaBuilder, SchemaListBuilder,
+        DataType, PropertyConstraintsBuilder, PropertyDefinitionBuilder, PropertyValueBuilder,
+        SchemaBuilder, SchemaListBuilder,
     };
     use grid_sdk::protocol::track_and_trace::payload::{
-        AnswerProposalActionBuilder, CreateProposalActionBuilder, CreateRecordActionBuilder,
-        FinalizeRecordActionBuilder, RevokeReporterActionBuilder, UpdatePropertiesAction,
-        UpdatePropertiesActionBuilder,
+        AggregateRecordsActionBuilder, AnswerProposalActionBuilder, ArchiveRecordActionBuilder,
+        CreateProposalActionBuilder, CreateRecordActionBuilder, DisaggregateRecordsActionBuilder,
+        FinalizeRecordActionBuilder, ReopenRecordActionBuilder, RevokeReporterActionBuilder,
+        UpdatePropertiesAction, UpdatePropertiesActionBuilder, UpdateRecordLocationActionBuilder,
     };
     use grid_sdk::protocol::track_and_trace::state::{
-        Property, PropertyListBuilder, PropertyPage, PropertyPageListBuilder, Proposal, Record,
-        RecordListBuilder, Role, Status,
+        Aggregation, Property, PropertyListBuilder, PropertyPage, PropertyPageListBuilder,
+        Proposal, Record, RecordListBuilder, ReporterConstraintsBuilder, Role, Status,
     };
     use grid_sdk::protos::IntoBytes;
     use sawtooth_sdk::processor::handler::{ContextError, TransactionContext};
@@ -1089,6 +1830,9 @@ mod tests {
     const OPTIONAL_PROPERTY_NAME: &str = "test_optional";
     const REQUIRED_PROPERTY_NAME: &str = "test_required";
     const SCHEMA_NAME: &str = "test_schema";
+    const TEMPERATURE_PROPERTY_NAME: &str = "temperature";
+    const GTIN: &str = "00012345600012";
+    const GLN: &str = "9012345000004";
 
     #[derive(Default, Debug)]
     /// A MockTransactionContext that can be used to test TrackAndTraceState
@@ -1154,7 +1898,7 @@ mod tests {
             let builder = AgentListBuilder::new();
             let agent_list = builder.with_agents(vec![agent.clone()]).build().unwrap();
             let agent_bytes = agent_list.into_bytes().unwrap();
-            let agent_address = make_agent_address(public_key);
+            let agent_address = compute_agent_address(public_key);
             self.set_state_entry(agent_address, agent_bytes).unwrap();
         }
 
@@ -1174,7 +1918,7 @@ mod tests {
             let builder = SchemaListBuilder::new();
             let schema_list = builder.with_schemas(vec![schema]).build().unwrap();
             let schema_bytes = schema_list.into_bytes().unwrap();
-            let schema_address = make_schema_address(SCHEMA_NAME);
+            let schema_address = compute_schema_address(SCHEMA_NAME);
             self.set_state_entry(schema_address, schema_bytes).unwrap();
         }
 
@@ -1184,10 +1928,75 @@ mod tests {
                 .build()
                 .unwrap();
             let record_bytes = record_list.into_bytes().unwrap();
-            let record_address = make_record_address(RECORD_ID);
+            let record_address = compute_record_address(RECORD_ID);
+            self.set_state_entry(record_address, record_bytes).unwrap();
+        }
+
+        fn add_record_with_id(&self, record_id: &str) {
+            let record_list = RecordListBuilder::new()
+                .with_records(vec![make_record_with_id(record_id)])
+                .build()
+                .unwrap();
+            let record_bytes = record_list.into_bytes().unwrap();
+            let record_address = compute_record_address(record_id);
             self.set_state_entry(record_address, record_bytes).unwrap();
         }
 
+        fn add_product(&self, gtin: &str) {
+            let product = ProductBuilder::new()
+                .with_product_id(gtin.to_string())
+                .with_product_namespace(ProductNamespace::GS1)
+                .with_owner("test_org".to_string())
+                .with_properties(vec![])
+                .build()
+                .unwrap();
+            let product_list = ProductListBuilder::new()
+                .with_products(vec![product])
+                .build()
+                .unwrap();
+            let product_bytes = product_list.into_bytes().unwrap();
+            let product_address = compute_gs1_product_address(gtin);
+            self.set_state_entry(product_address, product_bytes)
+                .unwrap();
+        }
+
+        fn add_custom_product(&self, schema_name: &str, product_id: &str) {
+            let product = ProductBuilder::new()
+                .with_product_id(product_id.to_string())
+                .with_product_namespace(ProductNamespace::Custom)
+                .with_schema_name(schema_name.to_string())
+                .with_owner("test_org".to_string())
+                .with_properties(vec![])
+                .build()
+                .unwrap();
+            let product_list = ProductListBuilder::new()
+                .with_products(vec![product])
+                .build()
+                .unwrap();
+            let product_bytes = product_list.into_bytes().unwrap();
+            let product_address = compute_custom_product_address(schema_name, product_id);
+            self.set_state_entry(product_address, product_bytes)
+                .unwrap();
+        }
+
+        fn add_location(&self, gln: &str) {
+            let location = LocationBuilder::new()
+                .with_location_id(gln.to_string())
+                .with_namespace(LocationNamespace::GS1)
+                .with_owner("test_org".to_string())
+                .with_properties(vec![])
+                .build()
+                .unwrap();
+            let location_list = LocationListBuilder::new()
+                .with_locations(vec![location])
+                .build()
+                .unwrap();
+            let location_bytes = location_list.into_bytes().unwrap();
+            let location_address = compute_gs1_location_address(gln);
+            self.set_state_entry(location_address, location_bytes)
+                .unwrap();
+        }
+
         fn add_property(&self, property_name: &str, property_definition: PropertyDefinition) {
             let property_list = PropertyListBuilder::new()
                 .with_properties(vec![make_property(property_name, property_definition)])
@@ -1195,7 +2004,7 @@ mod tests {
                 .unwrap();
 
             let property_list_bytes = property_list.into_bytes().unwrap();
-            let property_list_address = make_property_address(RECORD_ID, property_name, 0);
+            let property_list_address = compute_property_address(RECORD_ID, property_name, 0);
             self.set_state_entry(property_list_address, property_list_bytes)
                 .unwrap();
         }
@@ -1209,7 +2018,7 @@ mod tests {
             let property_page_list_bytes = property_page_list
                 .into_bytes()
                 .expect("Failed to write page list to bytes");
-            let address = make_property_address(RECORD_ID, property_name, 1);
+            let address = compute_property_address(RECORD_ID, property_name, 1);
             self.set_state_entry(address, property_page_list_bytes)
                 .expect("Failed to set state");
         }
@@ -1235,7 +2044,7 @@ mod tests {
                 .build()
                 .unwrap();
             let record_bytes = record_list.into_bytes().unwrap();
-            let record_address = make_record_address(RECORD_ID);
+            let record_address = compute_record_address(RECORD_ID);
             self.set_state_entry(record_address, record_bytes).unwrap();
         }
 
@@ -1256,7 +2065,7 @@ mod tests {
                 .build()
                 .unwrap();
             let proposal_list_bytes = proposal_list.into_bytes().unwrap();
-            let proposal_list_address = make_proposal_address(RECORD_ID, receiving_agent_key);
+            let proposal_list_address = compute_proposal_address(RECORD_ID, receiving_agent_key);
             self.set_state_entry(proposal_list_address, proposal_list_bytes)
                 .unwrap();
         }
@@ -1280,7 +2089,29 @@ mod tests {
                 .unwrap();
 
             let property_list_bytes = property_list.into_bytes().unwrap();
-            let property_list_address = make_property_address(RECORD_ID, property_name, 0);
+            let property_list_address = compute_property_address(RECORD_ID, property_name, 0);
+            self.set_state_entry(property_list_address, property_list_bytes)
+                .unwrap();
+        }
+
+        fn add_property_with_granted_reporter(
+            &self,
+            property_name: &str,
+            reporter: Reporter,
+            property_definition: PropertyDefinition,
+        ) {
+            let property = make_property(property_name, property_definition)
+                .into_builder()
+                .with_reporters(vec![reporter])
+                .build()
+                .unwrap();
+            let property_list = PropertyListBuilder::new()
+                .with_properties(vec![property])
+                .build()
+                .unwrap();
+
+            let property_list_bytes = property_list.into_bytes().unwrap();
+            let property_list_address = compute_property_address(RECORD_ID, property_name, 0);
             self.set_state_entry(property_list_address, property_list_bytes)
                 .unwrap();
         }
@@ -1354,20 +2185,157 @@ mod tests {
     }
 
     #[test]
-    /// Test that if the CreateRecordAction is invalid if the signer is not an Agent.
-    fn test_create_record_agent_does_not_exist() {
+    /// Test that a record created with a product and a location references both
+    fn test_create_record_with_product_and_location() {
         let mut transaction_context = MockTransactionContext::default();
         transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_product(GTIN);
+        transaction_context.add_location(GLN);
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let create_record_action = create_record_action_with_properties(vec![
-            optional_property_value(),
-            required_property_value(),
-        ]);
+        let create_record_action = create_record_action_with_references(GTIN, GLN);
 
-        match transaction_handler._create_record(
-            &create_record_action,
+        assert!(transaction_handler
+            ._create_record(&create_record_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+
+        assert_eq!(record.product_id(), GTIN);
+        assert_eq!(record.location_id(), GLN);
+    }
+
+    #[test]
+    /// Test that the CreateRecordAction is invalid if the referenced product does not exist
+    fn test_create_record_product_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_location(GLN);
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = create_record_action_with_references(GTIN, GLN);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Product does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Product does not exist: {}", GTIN)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a record references a custom product by its ID within the product's schema
+    fn test_create_record_with_custom_product() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_custom_product("lumber", "LB-0001");
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = CreateRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_properties(vec![required_property_value()])
+            .with_product_id("LB-0001".to_string())
+            .with_product_namespace(ProductNamespace::Custom)
+            .with_product_schema_name("planks".to_string())
+            .build()
+            .expect("Failed to build CreateRecordAction");
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => {
+                panic!("Product is not in that schema, InvalidTransaction should be returned")
+            }
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Product does not exist: LB-0001"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        let create_record_action = CreateRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_properties(vec![required_property_value()])
+            .with_product_id("LB-0001".to_string())
+            .with_product_namespace(ProductNamespace::Custom)
+            .with_product_schema_name("lumber".to_string())
+            .build()
+            .expect("Failed to build CreateRecordAction");
+
+        assert!(transaction_handler
+            ._create_record(&create_record_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+
+        assert_eq!(record.product_id(), "LB-0001");
+        assert_eq!(record.product_namespace(), &ProductNamespace::Custom);
+        assert_eq!(record.product_schema_name(), "lumber");
+    }
+
+    #[test]
+    /// Test that the CreateRecordAction is invalid if the referenced location does not exist
+    fn test_create_record_location_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_product(GTIN);
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = create_record_action_with_references(GTIN, GLN);
+
+        match transaction_handler._create_record(
+            &create_record_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Location does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Location does not exist: {}", GLN)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the CreateRecordAction is invalid if the signer is not an Agent.
+    fn test_create_record_agent_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let create_record_action = create_record_action_with_properties(vec![
+            optional_property_value(),
+            required_property_value(),
+        ]);
+
+        match transaction_handler._create_record(
+            &create_record_action,
             &mut state,
             PUBLIC_KEY,
             TIMESTAMP,
@@ -1465,7 +2433,7 @@ mod tests {
             Ok(()) => panic!("Required property is missing, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "Required property {} not provided",
+                    "{}: required String value is missing",
                     REQUIRED_PROPERTY_NAME
                 )));
             }
@@ -1509,7 +2477,7 @@ mod tests {
                 InvalidTransaction should be returned"
             ),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains("Provided property invalid_property is not in schema"));
+                assert!(err.contains("invalid_property: not defined by the schema"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
@@ -1550,7 +2518,7 @@ mod tests {
             ),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "Value provided for {} is the wrong type",
+                    "{}: expected String, got Number",
                     REQUIRED_PROPERTY_NAME
                 )));
             }
@@ -1659,113 +2627,188 @@ mod tests {
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction is valid an OK is returned and new value is added
-    /// to the record's PropertyPage
-    fn test_update_properties_valid() {
+    /// Test that a final record held by separate owner and custodian agents is only reopened
+    /// once both have approved, and that the approvals are cleared afterwards
+    fn test_reopen_record_requires_owner_and_custodian_approval() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_record();
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+        transaction_context.add_finalized_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
+        let custodian = AssociatedAgentBuilder::new()
+            .with_agent_id("custodian_public_key".to_string())
+            .with_timestamp(TIMESTAMP)
+            .build()
+            .expect("Failed to build AssociatedAgent");
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found")
+            .into_builder()
+            .with_custodians(vec![custodian])
+            .build()
+            .expect("Failed to build record");
+        state
+            .set_record(RECORD_ID, record)
+            .expect("Failed to set record");
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
         assert!(transaction_handler
-            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, TIMESTAMP,)
+            ._reopen_record(&create_reopen_record(), &mut state, PUBLIC_KEY)
             .is_ok());
 
-        let page = state
-            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
-            .expect("Failed to get property page from state")
-            .expect("Property page is none, it should be some");
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert!(*record.field_final());
+        assert_eq!(record.reopen_approvals(), &[PUBLIC_KEY.to_string()]);
 
-        assert_eq!(page.reported_values().len(), 2);
-        assert_eq!(page.reported_values()[1].value(), &updated_property_value());
+        match transaction_handler._reopen_record(&create_reopen_record(), &mut state, PUBLIC_KEY) {
+            Ok(()) => panic!("Owner already approved, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("has already approved reopening record"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        assert!(transaction_handler
+            ._reopen_record(&create_reopen_record(), &mut state, "custodian_public_key")
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert!(!*record.field_final());
+        assert!(record.reopen_approvals().is_empty());
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the record does not exist
-    fn test_update_properties_record_does_not_exist() {
+    /// Test that a record whose owner is also its custodian is reopened by a single approval
+    fn test_reopen_record_owner_and_custodian_same_agent() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_finalized_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
+        assert!(transaction_handler
+            ._reopen_record(&create_reopen_record(), &mut state, PUBLIC_KEY)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert!(!*record.field_final());
+    }
+
+    #[test]
+    /// Test that the ReopenRecordAction fails if the signer is neither owner nor custodian
+    fn test_reopen_record_signer_not_owner_nor_custodian() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_finalized_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._reopen_record(
+            &create_reopen_record(),
             &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
+            "agent_public_key_not_owner",
         ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!(
+                "Signer is not record owner nor custodian, InvalidTransaction should be returned"
+            ),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+                assert!(err.contains("Must be owner or custodian to reopen record"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the record is set to final
-    fn test_update_properties_record_is_final() {
+    /// Test that the ReopenRecordAction fails if the record is not final
+    fn test_reopen_record_not_final() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._reopen_record(&create_reopen_record(), &mut state, PUBLIC_KEY) {
+            Ok(()) => panic!("Record is not final, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record is not final: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a final record can be archived and unarchived by its owner, and that an
+    /// archived record cannot be reopened
+    fn test_archive_record_valid() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_finalized_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
-            &mut state,
-            PUBLIC_KEY,
-            TIMESTAMP,
-        ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+        assert!(transaction_handler
+            ._archive_record(&create_archive_record(true), &mut state, PUBLIC_KEY)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert!(*record.archived());
+
+        match transaction_handler._reopen_record(&create_reopen_record(), &mut state, PUBLIC_KEY) {
+            Ok(()) => panic!("Record is archived, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+                assert!(err.contains("must be unarchived before it is reopened"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
+
+        assert!(transaction_handler
+            ._archive_record(&create_archive_record(false), &mut state, PUBLIC_KEY)
+            .is_ok());
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("Record not found");
+        assert!(!*record.archived());
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is not part of
-    /// the record
-    fn test_update_properties_record_missing_property() {
+    /// Test that the ArchiveRecordAction fails if the record is not final
+    fn test_archive_record_not_final() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
         transaction_context.add_record();
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
+        match transaction_handler._archive_record(
+            &create_archive_record(true),
             &mut state,
             PUBLIC_KEY,
-            TIMESTAMP,
         ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Record is not final, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "Record does not have provided property: {}",
-                    REQUIRED_PROPERTY_NAME
+                    "Record must be final before it is archived: {}",
+                    RECORD_ID
                 )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
@@ -1773,39 +2816,32 @@ mod tests {
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the signer is not an authorized reporter
-    fn test_update_properties_signer_not_authorized() {
+    /// Test that the ArchiveRecordAction fails if the signer is not the record owner
+    fn test_archive_record_signer_not_owner() {
         let mut transaction_context = MockTransactionContext::default();
-        transaction_context.add_schema();
-        transaction_context.add_agent(PUBLIC_KEY);
-        transaction_context.add_record();
-        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
-        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+        transaction_context.add_finalized_record();
 
-        let signer = "not_authorized";
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
         let transaction_handler = TrackAndTraceTransactionHandler::new();
-        let update_property_action = update_property_action(vec![updated_property_value()]);
 
-        match transaction_handler._update_properties(
-            &update_property_action,
+        match transaction_handler._archive_record(
+            &create_archive_record(true),
             &mut state,
-            signer.clone(),
-            TIMESTAMP,
+            "agent_public_key_not_owner",
         ) {
-            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Ok(()) => panic!("Signer is not record owner, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
-                assert!(err.contains(&format!("Reporter is not authorized: {}", signer)));
+                assert!(err.contains("Must be owner to archive record"));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
     #[test]
-    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is set to
-    /// a data type that does not match the property definition.
-    fn test_update_properties_property_value_wrong_type() {
+    /// Test that if the UpdatedPropertiesAction is valid an OK is returned and new value is added
+    /// to the record's PropertyPage
+    fn test_update_properties_valid() {
         let mut transaction_context = MockTransactionContext::default();
         transaction_context.add_schema();
         transaction_context.add_agent(PUBLIC_KEY);
@@ -1815,7 +2851,151 @@ mod tests {
 
         let mut state = TrackAndTraceState::new(&mut transaction_context);
 
-        let property_value_wrong = PropertyValueBuilder::new()
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        assert!(transaction_handler
+            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, TIMESTAMP,)
+            .is_ok());
+
+        let page = state
+            .get_property_page(RECORD_ID, REQUIRED_PROPERTY_NAME, 1)
+            .expect("Failed to get property page from state")
+            .expect("Property page is none, it should be some");
+
+        assert_eq!(page.reported_values().len(), 2);
+        assert_eq!(page.reported_values()[1].value(), &updated_property_value());
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the record does not exist
+    fn test_update_properties_record_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_finalized_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the record is set to final
+    fn test_update_properties_record_is_final() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_finalized_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record is final: {}", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is not part of
+    /// the record
+    fn test_update_properties_record_missing_property() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record does not have provided property: {}",
+                    REQUIRED_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the signer is not an authorized reporter
+    fn test_update_properties_signer_not_authorized() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let signer = "not_authorized";
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![updated_property_value()]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            signer.clone(),
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Reporter is not authorized: {}", signer)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that if the UpdatedPropertiesAction fails if the property to be updated is set to
+    /// a data type that does not match the property definition.
+    fn test_update_properties_property_value_wrong_type() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let property_value_wrong = PropertyValueBuilder::new()
             .with_name(REQUIRED_PROPERTY_NAME.to_string())
             .with_data_type(DataType::Number)
             .with_number_value(123)
@@ -1834,15 +3014,63 @@ mod tests {
             Ok(()) => panic!("Record is does not exist, InvalidTransaction should be returned"),
             Err(ApplyError::InvalidTransaction(err)) => {
                 assert!(err.contains(&format!(
-                    "Update has wrong type: {:?} != {:?}",
-                    DataType::Number,
-                    DataType::String
+                    "{}: expected {:?}, got {:?}",
+                    REQUIRED_PROPERTY_NAME,
+                    DataType::String,
+                    DataType::Number
                 )));
             }
             Err(err) => panic!("Should have gotten invalid error but got {}", err),
         }
     }
 
+    #[test]
+    /// Test that UpdatedPropertiesAction is invalid if a value breaks the constraints of the
+    /// property definition
+    fn test_update_properties_property_value_breaks_constraints() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_schema();
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_record();
+        let property_definition = required_property_definition()
+            .into_builder()
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_pattern("[a-z_]+".to_string())
+                    .build()
+                    .expect("Failed to build property constraints"),
+            )
+            .build()
+            .expect("Failed to build property definition");
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, property_definition);
+        transaction_context.add_property_page(REQUIRED_PROPERTY_NAME, required_property_value());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let property_value = PropertyValueBuilder::new()
+            .with_name(REQUIRED_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("Updated Required Field".to_string())
+            .build()
+            .expect("Failed to build property value");
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![property_value]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Value breaks constraints, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("does not match the pattern [a-z_]+"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
     #[test]
     /// Test that if the UpdatedPropertiesAction start new PropertyPage when needed.
     fn test_update_properties_new_page() {
@@ -3111,6 +4339,73 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Test that a reporter that was revoked and then authorized again can be revoked again
+    fn test_revoke_reporter_authorized_again() {
+        let mut transaction_context = MockTransactionContext::default();
+        let reporter_key = "reporter_key";
+        transaction_context.add_record();
+        transaction_context.add_property_with_reporter(
+            REQUIRED_PROPERTY_NAME,
+            reporter_key,
+            false,
+            required_property_definition(),
+        );
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch required property")
+            .expect("Required property not found");
+        let mut reporters = property.reporters().to_vec();
+        reporters.push(
+            ReporterBuilder::new()
+                .with_public_key(reporter_key.to_string())
+                .with_authorized(true)
+                .with_index(1)
+                .build()
+                .expect("Failed to build Reporter"),
+        );
+        let property = property
+            .into_builder()
+            .with_reporters(reporters)
+            .build()
+            .expect("Failed to build property");
+        state
+            .set_property(RECORD_ID, REQUIRED_PROPERTY_NAME, property)
+            .expect("Failed to set property");
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload =
+            revoke_reporter_action(reporter_key, vec![REQUIRED_PROPERTY_NAME.to_string()]);
+
+        assert!(transaction_handler
+            ._revoke_reporter(&payload, &mut state, PUBLIC_KEY)
+            .is_ok());
+
+        let property = state
+            .get_property(RECORD_ID, REQUIRED_PROPERTY_NAME)
+            .expect("Failed to fetch required property")
+            .expect("Required property not found");
+        assert_eq!(property.reporters().len(), 2);
+        assert!(property
+            .reporters()
+            .iter()
+            .all(|reporter| !*reporter.authorized()));
+
+        match transaction_handler._revoke_reporter(&payload, &mut state, PUBLIC_KEY) {
+            Ok(()) => {
+                panic!("Reporter already unauthorized, InvalidTransaction should be returned")
+            }
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Reporter is already unauthorized."));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
     #[test]
     /// Test that when the RevokeReporterAction fails if the key is not a valid reporter for the
     /// property
@@ -3141,38 +4436,919 @@ mod tests {
         }
     }
 
-    fn optional_property_value() -> PropertyValue {
-        PropertyValueBuilder::new()
-            .with_name(OPTIONAL_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::Enum)
-            .with_enum_value(1)
-            .build()
-            .expect("Failed to build property value")
-    }
+    #[test]
+    /// Test that updating a record's location moves the records aggregated into it as well
+    fn test_update_record_location_moves_children() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_record_with_id("case_1");
+        transaction_context.add_location(GLN);
 
-    fn required_property_value() -> PropertyValue {
-        PropertyValueBuilder::new()
-            .with_name(REQUIRED_PROPERTY_NAME.to_string())
-            .with_data_type(DataType::String)
-            .with_string_value("required_field".to_string())
-            .build()
-            .expect("Failed to build property value")
-    }
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
 
-    fn create_record_action_with_properties(properties: Vec<PropertyValue>) -> CreateRecordAction {
-        CreateRecordActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .with_schema(SCHEMA_NAME.to_string())
-            .with_properties(properties)
-            .build()
-            .expect("Failed to build CreateRecordAction")
-    }
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
 
-    fn create_finalize_record() -> FinalizeRecordAction {
-        FinalizeRecordActionBuilder::new()
-            .with_record_id(RECORD_ID.to_string())
-            .build()
-            .expect("Failed to build FinalizeRecordAction")
+        let payload = aggregate_records_action(RECORD_ID, vec!["case_1"]);
+        transaction_handler
+            ._aggregate_records(&payload, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to aggregate records");
+
+        assert!(transaction_handler
+            ._update_record_location(&update_record_location_action(GLN), &mut state, PUBLIC_KEY)
+            .is_ok());
+
+        for record_id in &[RECORD_ID, "case_1"] {
+            let record = state
+                .get_record(record_id)
+                .expect("Failed to fetch record")
+                .expect("No record found");
+            assert_eq!(record.location_id(), GLN);
+        }
+
+        // An empty location marks the record as being in transit
+        assert!(transaction_handler
+            ._update_record_location(&update_record_location_action(""), &mut state, PUBLIC_KEY)
+            .is_ok());
+
+        let record = state
+            .get_record("case_1")
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(record.location_id(), "");
+    }
+
+    #[test]
+    /// Test that the UpdateRecordLocationAction fails if the signer is not the custodian
+    fn test_update_record_location_signer_not_custodian() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_location(GLN);
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._update_record_location(
+            &update_record_location_action(GLN),
+            &mut state,
+            "not_the_custodian",
+        ) {
+            Ok(()) => panic!("Signer is not custodian, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Must be custodian to update record location"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the UpdateRecordLocationAction fails if the location does not exist
+    fn test_update_record_location_does_not_exist() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._update_record_location(
+            &update_record_location_action(GLN),
+            &mut state,
+            PUBLIC_KEY,
+        ) {
+            Ok(()) => panic!("Location does not exist, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Location does not exist: {}", GLN)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that when the AggregateRecordsAction is valid an OK is returned and both the parent
+    /// and the children record the aggregation
+    fn test_aggregate_records_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_record_with_id("case_1");
+        transaction_context.add_record_with_id("case_2");
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload = aggregate_records_action(RECORD_ID, vec!["case_1", "case_2"]);
+
+        assert!(transaction_handler
+            ._aggregate_records(&payload, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .is_ok());
+
+        let parent = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(parent.child_record_ids(), vec!["case_1", "case_2"]);
+        assert_eq!(parent.parent_record_id(), None);
+
+        for child_record_id in &["case_1", "case_2"] {
+            let child = state
+                .get_record(child_record_id)
+                .expect("Failed to fetch record")
+                .expect("No record found");
+            assert_eq!(child.parent_record_id(), Some(RECORD_ID));
+            assert_eq!(
+                child.aggregations(),
+                &[make_aggregation(RECORD_ID, child_record_id, 0)]
+            );
+        }
+    }
+
+    #[test]
+    /// Test that the AggregateRecordsAction fails if the signer is not the custodian of every
+    /// record involved
+    fn test_aggregate_records_signer_not_custodian() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_record_with_id("case_1");
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let payload = aggregate_records_action(RECORD_ID, vec!["case_1"]);
+
+        match transaction_handler._aggregate_records(
+            &payload,
+            &mut state,
+            "not_custodian",
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Signer is not custodian, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Must be custodian of record {} to aggregate or disaggregate it",
+                    RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the AggregateRecordsAction fails if a child is already aggregated into another
+    /// record
+    fn test_aggregate_records_child_already_aggregated() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_record_with_id("case_1");
+        transaction_context.add_record_with_id("pallet_2");
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        transaction_handler
+            ._aggregate_records(
+                &aggregate_records_action(RECORD_ID, vec!["case_1"]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to aggregate records");
+
+        match transaction_handler._aggregate_records(
+            &aggregate_records_action("pallet_2", vec!["case_1"]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Child is already aggregated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record case_1 is already aggregated into {}",
+                    RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that the AggregateRecordsAction fails if a record would be aggregated into one of its
+    /// own descendants
+    fn test_aggregate_records_cycle() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_record_with_id("case_1");
+        transaction_context.add_record_with_id("can_1");
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        transaction_handler
+            ._aggregate_records(
+                &aggregate_records_action(RECORD_ID, vec!["case_1"]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to aggregate records");
+        transaction_handler
+            ._aggregate_records(
+                &aggregate_records_action("case_1", vec!["can_1"]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to aggregate records");
+
+        match transaction_handler._aggregate_records(
+            &aggregate_records_action("can_1", vec![RECORD_ID]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Aggregation creates a cycle, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record {} cannot be aggregated into its own descendant can_1",
+                    RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that when the DisaggregateRecordsAction is valid an OK is returned, the children no
+    /// longer have a parent and the ended aggregation is kept for lineage
+    fn test_disaggregate_records_valid() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_record_with_id("case_1");
+        transaction_context.add_record_with_id("case_2");
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        transaction_handler
+            ._aggregate_records(
+                &aggregate_records_action(RECORD_ID, vec!["case_1", "case_2"]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to aggregate records");
+
+        assert!(transaction_handler
+            ._disaggregate_records(
+                &disaggregate_records_action(RECORD_ID, vec!["case_1"]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP + 1,
+            )
+            .is_ok());
+
+        let parent = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(parent.child_record_ids(), vec!["case_2"]);
+        assert_eq!(
+            parent.aggregations(),
+            &[
+                make_aggregation(RECORD_ID, "case_1", TIMESTAMP + 1),
+                make_aggregation(RECORD_ID, "case_2", 0),
+            ]
+        );
+
+        let child = state
+            .get_record("case_1")
+            .expect("Failed to fetch record")
+            .expect("No record found");
+        assert_eq!(child.parent_record_id(), None);
+        assert_eq!(
+            child.aggregations(),
+            &[make_aggregation(RECORD_ID, "case_1", TIMESTAMP + 1)]
+        );
+    }
+
+    #[test]
+    /// Test that the DisaggregateRecordsAction fails if the child is not aggregated into the
+    /// parent
+    fn test_disaggregate_records_not_aggregated() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_record_with_id("case_1");
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._disaggregate_records(
+            &disaggregate_records_action(RECORD_ID, vec!["case_1"]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Child is not aggregated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Record case_1 is not aggregated into {}",
+                    RECORD_ID
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that accepting a custodian proposal for a parent record transfers custody of every
+    /// record aggregated into it, recursively
+    fn test_answer_proposal_accept_custodian_cascades_to_children() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+        transaction_context.add_record_with_id("case_1");
+        transaction_context.add_record_with_id("can_1");
+        transaction_context.add_schema();
+
+        transaction_context.add_proposal(
+            PUBLIC_KEY,
+            receiving_agent_key,
+            Role::Custodian,
+            Status::Open,
+        );
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        transaction_handler
+            ._aggregate_records(
+                &aggregate_records_action(RECORD_ID, vec!["case_1"]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to aggregate records");
+        transaction_handler
+            ._aggregate_records(
+                &aggregate_records_action("case_1", vec!["can_1"]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to aggregate records");
+
+        let payload =
+            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
+
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP)
+            .is_ok());
+
+        for record_id in &[RECORD_ID, "case_1", "can_1"] {
+            let record = state
+                .get_record(record_id)
+                .expect("Failed to fetch record")
+                .expect("No record found");
+            let custodian = record.custodians().last().expect("No custodian found");
+            assert_eq!(custodian.agent_id(), receiving_agent_key);
+        }
+    }
+
+    #[test]
+    /// Test that a custodian proposal cannot be created for a record that is aggregated into
+    /// another record
+    fn test_create_proposal_custodian_record_aggregated() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record_with_id("pallet");
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        transaction_handler
+            ._aggregate_records(
+                &aggregate_records_action("pallet", vec![RECORD_ID]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to aggregate records");
+
+        match transaction_handler._create_proposal(
+            &create_proposal_action(Role::Custodian, receiving_agent_key),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Record is aggregated, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!("Record {} is aggregated into pallet", RECORD_ID)));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that accepting a proposal after its expiry marks it Expired and leaves the record
+    /// untouched
+    fn test_answer_proposal_accept_expired() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        transaction_handler
+            ._create_proposal(
+                &expiring_create_proposal_action(
+                    Role::Custodian,
+                    receiving_agent_key,
+                    TIMESTAMP + 1,
+                ),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to create proposal");
+
+        let payload =
+            answer_proposal_action(Role::Custodian, receiving_agent_key, Response::Accept);
+
+        assert!(transaction_handler
+            ._answer_proposal(&payload, &mut state, receiving_agent_key, TIMESTAMP + 2)
+            .is_ok());
+
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 1);
+        assert_eq!(proposal_list.proposals()[0].status(), &Status::Expired);
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+
+        assert_eq!(record.custodians().len(), 1);
+        assert_eq!(record.custodians()[0].agent_id(), PUBLIC_KEY);
+    }
+
+    #[test]
+    /// Test that an open proposal that has expired does not prevent a new proposal from being
+    /// created, and is marked Expired
+    fn test_create_proposal_replaces_expired_proposal() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        transaction_handler
+            ._create_proposal(
+                &expiring_create_proposal_action(Role::Owner, receiving_agent_key, TIMESTAMP + 1),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .expect("Failed to create proposal");
+
+        assert!(transaction_handler
+            ._create_proposal(
+                &create_proposal_action(Role::Owner, receiving_agent_key),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP + 2,
+            )
+            .is_ok());
+
+        let proposal_list = state
+            .get_proposal_list(RECORD_ID, receiving_agent_key)
+            .expect("Failed to get ProposalList from state")
+            .expect("ProposalList not found");
+
+        assert_eq!(proposal_list.proposals().len(), 2);
+        assert_eq!(proposal_list.proposals()[0].status(), &Status::Expired);
+        assert_eq!(proposal_list.proposals()[1].status(), &Status::Open);
+        assert_eq!(*proposal_list.proposals()[1].expires_at(), 0);
+    }
+
+    #[test]
+    /// Test that a payload older than the last update to a record is rejected unless it is
+    /// within the allowed skew
+    fn test_check_record_timestamp() {
+        let record = make_record()
+            .into_builder()
+            .with_last_timestamp(10)
+            .build()
+            .expect("Failed to build record");
+
+        match check_record_timestamp(&record, 5, 0) {
+            Ok(()) => panic!("Timestamp is stale, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("is older than the last update to record"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        assert!(check_record_timestamp(&record, 5, 5).is_ok());
+        assert!(check_record_timestamp(&record, 10, 0).is_ok());
+        assert!(check_record_timestamp(&record, 11, 0).is_ok());
+    }
+
+    #[test]
+    /// Test that a record's last timestamp only ever moves forward
+    fn test_advance_record_timestamp() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        advance_record_timestamp(&mut state, RECORD_ID, 10).expect("Failed to advance timestamp");
+        advance_record_timestamp(&mut state, RECORD_ID, 5).expect("Failed to advance timestamp");
+
+        let record = state
+            .get_record(RECORD_ID)
+            .expect("Failed to fetch record")
+            .expect("No record found");
+
+        assert_eq!(*record.last_timestamp(), 10);
+    }
+
+    #[test]
+    /// Test that a reporter cannot report once its grant has lapsed
+    fn test_update_properties_reporter_grant_expired() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        transaction_context.add_property_with_granted_reporter(
+            TEMPERATURE_PROPERTY_NAME,
+            make_granted_reporter(PUBLIC_KEY, TIMESTAMP, None),
+            temperature_property_definition(),
+        );
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![temperature_value(20)]);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP + 1,
+        ) {
+            Ok(()) => panic!("Reporter grant has expired, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains(&format!(
+                    "Reporter {} is not authorized to report {}",
+                    PUBLIC_KEY, TEMPERATURE_PROPERTY_NAME
+                )));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    #[test]
+    /// Test that a reporter whose grant lapsed reports through the grant it was given again,
+    /// rather than the lapsed one
+    fn test_update_properties_reporter_granted_again() {
+        let mut transaction_context = MockTransactionContext::default();
+        let reporter_key = "reporter_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(reporter_key);
+        transaction_context.add_record();
+        transaction_context.add_schema();
+        transaction_context.add_property_with_granted_reporter(
+            TEMPERATURE_PROPERTY_NAME,
+            make_granted_reporter(reporter_key, TIMESTAMP, None),
+            temperature_property_definition(),
+        );
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let proposal = make_proposal(PUBLIC_KEY, reporter_key, Role::Reporter, Status::Open)
+            .into_builder()
+            .with_properties(vec![TEMPERATURE_PROPERTY_NAME.to_string()])
+            .build()
+            .expect("Failed to build proposal");
+        let proposal_list = ProposalListBuilder::new()
+            .with_proposals(vec![proposal])
+            .build()
+            .expect("Failed to build ProposalList");
+        state
+            .set_proposal_list(RECORD_ID, reporter_key, proposal_list)
+            .expect("Failed to set ProposalList");
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        transaction_handler
+            ._answer_proposal(
+                &answer_proposal_action(Role::Reporter, reporter_key, Response::Accept),
+                &mut state,
+                reporter_key,
+                TIMESTAMP + 1,
+            )
+            .expect("Failed to grant the reporter role again");
+
+        let property = state
+            .get_property(RECORD_ID, TEMPERATURE_PROPERTY_NAME)
+            .expect("Failed to fetch property")
+            .expect("Property not found");
+        assert_eq!(property.reporters().len(), 2);
+
+        assert!(transaction_handler
+            ._update_properties(
+                &update_property_action(vec![temperature_value(20)]),
+                &mut state,
+                reporter_key,
+                TIMESTAMP + 2,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    /// Test that a reporter cannot report values outside the range it was granted
+    fn test_update_properties_reporter_value_out_of_range() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        let constraints = ReporterConstraintsBuilder::new()
+            .with_property_name(TEMPERATURE_PROPERTY_NAME.to_string())
+            .with_min_value(-40)
+            .with_max_value(60)
+            .build()
+            .expect("Failed to build ReporterConstraints");
+        transaction_context.add_property_with_granted_reporter(
+            TEMPERATURE_PROPERTY_NAME,
+            make_granted_reporter(PUBLIC_KEY, 0, Some(constraints)),
+            temperature_property_definition(),
+        );
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        match transaction_handler._update_properties(
+            &update_property_action(vec![temperature_value(61)]),
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Value is out of range, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("is outside the range reporter"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        assert!(transaction_handler
+            ._update_properties(
+                &update_property_action(vec![temperature_value(-40)]),
+                &mut state,
+                PUBLIC_KEY,
+                TIMESTAMP,
+            )
+            .is_ok());
+    }
+
+    #[test]
+    /// Test that a rate limited reporter must wait out its interval between reports, and that
+    /// its last report is recorded on the property
+    fn test_update_properties_reporter_rate_limited() {
+        let mut transaction_context = MockTransactionContext::default();
+        transaction_context.add_record();
+        let constraints = ReporterConstraintsBuilder::new()
+            .with_property_name(TEMPERATURE_PROPERTY_NAME.to_string())
+            .with_min_report_interval(60)
+            .build()
+            .expect("Failed to build ReporterConstraints");
+        transaction_context.add_property_with_granted_reporter(
+            TEMPERATURE_PROPERTY_NAME,
+            make_granted_reporter(PUBLIC_KEY, 0, Some(constraints)),
+            temperature_property_definition(),
+        );
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+        let update_property_action = update_property_action(vec![temperature_value(20)]);
+
+        transaction_handler
+            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, 100)
+            .expect("Failed to update properties");
+
+        let property = state
+            .get_property(RECORD_ID, TEMPERATURE_PROPERTY_NAME)
+            .expect("Failed to fetch property")
+            .expect("No property found");
+        assert_eq!(*property.reporters()[0].last_reported_at(), 100);
+
+        match transaction_handler._update_properties(
+            &update_property_action,
+            &mut state,
+            PUBLIC_KEY,
+            120,
+        ) {
+            Ok(()) => panic!("Reporter is rate limited, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("every 60 seconds"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+
+        assert!(transaction_handler
+            ._update_properties(&update_property_action, &mut state, PUBLIC_KEY, 160)
+            .is_ok());
+    }
+
+    #[test]
+    /// Test that accepting a reporter proposal grants the reporter the proposal's validity
+    /// window and the constraints for each property
+    fn test_answer_proposal_accept_reporter_with_grant() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+        transaction_context
+            .add_property(TEMPERATURE_PROPERTY_NAME, temperature_property_definition());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let constraints = ReporterConstraintsBuilder::new()
+            .with_property_name(TEMPERATURE_PROPERTY_NAME.to_string())
+            .with_min_report_interval(60)
+            .with_min_value(-40)
+            .with_max_value(60)
+            .build()
+            .expect("Failed to build ReporterConstraints");
+
+        let create_proposal_action = CreateProposalActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_properties(vec![TEMPERATURE_PROPERTY_NAME.to_string()])
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(Role::Reporter)
+            .with_terms("".to_string())
+            .with_reporter_valid_from(10)
+            .with_reporter_valid_until(20)
+            .with_reporter_constraints(vec![constraints.clone()])
+            .build()
+            .expect("Failed to build CreateProposalAction");
+
+        transaction_handler
+            ._create_proposal(&create_proposal_action, &mut state, PUBLIC_KEY, TIMESTAMP)
+            .expect("Failed to create proposal");
+
+        transaction_handler
+            ._answer_proposal(
+                &answer_proposal_action(Role::Reporter, receiving_agent_key, Response::Accept),
+                &mut state,
+                receiving_agent_key,
+                TIMESTAMP,
+            )
+            .expect("Failed to accept proposal");
+
+        let property = state
+            .get_property(RECORD_ID, TEMPERATURE_PROPERTY_NAME)
+            .expect("Failed to fetch property")
+            .expect("No property found");
+
+        let reporter = property
+            .reporters()
+            .iter()
+            .find(|reporter| reporter.public_key() == receiving_agent_key)
+            .expect("Reporter was not added");
+
+        assert_eq!(*reporter.valid_from(), 10);
+        assert_eq!(*reporter.valid_until(), 20);
+        assert_eq!(reporter.constraints(), Some(&constraints));
+    }
+
+    #[test]
+    /// Test that a reporter proposal cannot constrain the value range of a non-Number property
+    fn test_create_proposal_reporter_value_range_not_number() {
+        let mut transaction_context = MockTransactionContext::default();
+        let receiving_agent_key = "receiving_agent_key";
+        transaction_context.add_agent(PUBLIC_KEY);
+        transaction_context.add_agent(receiving_agent_key);
+        transaction_context.add_record();
+        transaction_context.add_property(REQUIRED_PROPERTY_NAME, required_property_definition());
+
+        let mut state = TrackAndTraceState::new(&mut transaction_context);
+
+        let transaction_handler = TrackAndTraceTransactionHandler::new();
+
+        let create_proposal_action = CreateProposalActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_properties(vec![REQUIRED_PROPERTY_NAME.to_string()])
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(Role::Reporter)
+            .with_terms("".to_string())
+            .with_reporter_constraints(vec![ReporterConstraintsBuilder::new()
+                .with_property_name(REQUIRED_PROPERTY_NAME.to_string())
+                .with_max_value(60)
+                .build()
+                .expect("Failed to build ReporterConstraints")])
+            .build()
+            .expect("Failed to build CreateProposalAction");
+
+        match transaction_handler._create_proposal(
+            &create_proposal_action,
+            &mut state,
+            PUBLIC_KEY,
+            TIMESTAMP,
+        ) {
+            Ok(()) => panic!("Property is not a Number, InvalidTransaction should be returned"),
+            Err(ApplyError::InvalidTransaction(err)) => {
+                assert!(err.contains("Value ranges can only constrain Number properties"));
+            }
+            Err(err) => panic!("Should have gotten invalid error but got {}", err),
+        }
+    }
+
+    fn optional_property_value() -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(OPTIONAL_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::Enum)
+            .with_enum_value(1)
+            .build()
+            .expect("Failed to build property value")
+    }
+
+    fn required_property_value() -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(REQUIRED_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::String)
+            .with_string_value("required_field".to_string())
+            .build()
+            .expect("Failed to build property value")
+    }
+
+    fn create_record_action_with_properties(properties: Vec<PropertyValue>) -> CreateRecordAction {
+        CreateRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_properties(properties)
+            .build()
+            .expect("Failed to build CreateRecordAction")
+    }
+
+    fn create_record_action_with_references(
+        product_id: &str,
+        location_id: &str,
+    ) -> CreateRecordAction {
+        CreateRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_schema(SCHEMA_NAME.to_string())
+            .with_properties(vec![required_property_value()])
+            .with_product_id(product_id.to_string())
+            .with_location_id(location_id.to_string())
+            .build()
+            .expect("Failed to build CreateRecordAction")
+    }
+
+    fn create_finalize_record() -> FinalizeRecordAction {
+        FinalizeRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .build()
+            .expect("Failed to build FinalizeRecordAction")
+    }
+
+    fn create_reopen_record() -> ReopenRecordAction {
+        ReopenRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .build()
+            .expect("Failed to build ReopenRecordAction")
+    }
+
+    fn create_archive_record(archived: bool) -> ArchiveRecordAction {
+        ArchiveRecordActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_archived(archived)
+            .build()
+            .expect("Failed to build ArchiveRecordAction")
     }
 
     fn updated_property_value() -> PropertyValue {
@@ -3206,6 +5382,22 @@ mod tests {
             .expect("Failed to build CreateProposalAction")
     }
 
+    fn expiring_create_proposal_action(
+        role: Role,
+        receiving_agent_key: &str,
+        expires_at: u64,
+    ) -> CreateProposalAction {
+        CreateProposalActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_properties(vec![])
+            .with_receiving_agent(receiving_agent_key.to_string())
+            .with_role(role)
+            .with_terms("".to_string())
+            .with_expires_at(expires_at)
+            .build()
+            .expect("Failed to build CreateProposalAction")
+    }
+
     fn create_proposal_no_props_action(
         role: Role,
         receiving_agent_key: &str,
@@ -3243,6 +5435,36 @@ mod tests {
             .expect("Failed to build RevokeReporterAction")
     }
 
+    fn update_record_location_action(location_id: &str) -> UpdateRecordLocationAction {
+        UpdateRecordLocationActionBuilder::new()
+            .with_record_id(RECORD_ID.to_string())
+            .with_location_id(location_id.to_string())
+            .build()
+            .expect("Failed to build UpdateRecordLocationAction")
+    }
+
+    fn aggregate_records_action(
+        parent_record_id: &str,
+        children: Vec<&str>,
+    ) -> AggregateRecordsAction {
+        AggregateRecordsActionBuilder::new()
+            .with_parent_record_id(parent_record_id.to_string())
+            .with_child_record_ids(children.into_iter().map(String::from).collect())
+            .build()
+            .expect("Failed to build AggregateRecordsAction")
+    }
+
+    fn disaggregate_records_action(
+        parent_record_id: &str,
+        children: Vec<&str>,
+    ) -> DisaggregateRecordsAction {
+        DisaggregateRecordsActionBuilder::new()
+            .with_parent_record_id(parent_record_id.to_string())
+            .with_child_record_ids(children.into_iter().map(String::from).collect())
+            .build()
+            .expect("Failed to build DisaggregateRecordsAction")
+    }
+
     fn optional_property_definition() -> PropertyDefinition {
         PropertyDefinitionBuilder::new()
             .with_name(OPTIONAL_PROPERTY_NAME.to_string())
@@ -3267,6 +5489,44 @@ mod tests {
             .expect("Failed to build property definition")
     }
 
+    fn temperature_property_definition() -> PropertyDefinition {
+        PropertyDefinitionBuilder::new()
+            .with_name(TEMPERATURE_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::Number)
+            .with_description("Temperature".to_string())
+            .with_number_exponent(0)
+            .build()
+            .expect("Failed to build property definition")
+    }
+
+    fn temperature_value(value: i64) -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(TEMPERATURE_PROPERTY_NAME.to_string())
+            .with_data_type(DataType::Number)
+            .with_number_value(value)
+            .build()
+            .expect("Failed to build property value")
+    }
+
+    fn make_granted_reporter(
+        reporter_key: &str,
+        valid_until: u64,
+        constraints: Option<ReporterConstraints>,
+    ) -> Reporter {
+        let reporter_builder = ReporterBuilder::new()
+            .with_public_key(reporter_key.to_string())
+            .with_authorized(true)
+            .with_index(0)
+            .with_valid_until(valid_until);
+
+        let reporter_builder = match constraints {
+            Some(constraints) => reporter_builder.with_constraints(constraints),
+            None => reporter_builder,
+        };
+
+        reporter_builder.build().expect("Failed to build Reporter")
+    }
+
     fn make_record() -> Record {
         let associated_agent = AssociatedAgentBuilder::new()
             .with_agent_id(PUBLIC_KEY.to_string())
@@ -3280,10 +5540,33 @@ mod tests {
             .with_owners(vec![associated_agent.clone()])
             .with_custodians(vec![associated_agent.clone()])
             .with_field_final(false)
+            .with_schema_version(1)
+            .build()
+            .expect("Failed to build new_record")
+    }
+
+    fn make_record_with_id(record_id: &str) -> Record {
+        make_record()
+            .into_builder()
+            .with_record_id(record_id.to_string())
             .build()
             .expect("Failed to build new_record")
     }
 
+    fn make_aggregation(
+        parent_record_id: &str,
+        child_record_id: &str,
+        disaggregated_at: u64,
+    ) -> Aggregation {
+        AggregationBuilder::new()
+            .with_parent_record_id(parent_record_id.to_string())
+            .with_child_record_id(child_record_id.to_string())
+            .with_aggregated_at(TIMESTAMP)
+            .with_disaggregated_at(disaggregated_at)
+            .build()
+            .expect("Failed to build Aggregation")
+    }
+
     fn make_property(property_name: &str, property_definition: PropertyDefinition) -> Property {
         let reporter = ReporterBuilder::new()
             .with_public_key(PUBLIC_KEY.to_string())
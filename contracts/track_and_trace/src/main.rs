@@ -26,14 +26,13 @@ cfg_if! {
         use log4rs::config::{Appender, Config, Root};
         use log4rs::encode::pattern::PatternEncoder;
         use sawtooth_sdk::processor::TransactionProcessor;
-        use handler::TrackAndTraceTransactionHandler;
+        use handler::{TrackAndTraceTransactionHandler, DEFAULT_MAX_TIMESTAMP_SKEW};
     } else {
         #[macro_use]
         extern crate sabre_sdk;
     }
 }
 
-mod addressing;
 pub mod handler;
 mod payload;
 mod state;
@@ -46,7 +45,9 @@ fn main() {
         (@arg connect: -C --connect +takes_value
          "connection endpoint for validator")
         (@arg verbose: -v --verbose +multiple
-         "increase output verbosity"))
+         "increase output verbosity")
+        (@arg max_timestamp_skew: --("max-timestamp-skew") +takes_value
+         "seconds a payload timestamp may lag behind the last update to a record; must match on every node"))
     .get_matches();
 
     let endpoint = matches
@@ -80,7 +81,19 @@ fn main() {
         Err(_) => process::exit(1),
     }
 
-    let handler = TrackAndTraceTransactionHandler::new();
+    let max_timestamp_skew = match matches.value_of("max_timestamp_skew") {
+        Some(value) => match value.parse::<u64>() {
+            Ok(max_timestamp_skew) => max_timestamp_skew,
+            Err(_) => {
+                error!("Invalid max timestamp skew: {}", value);
+                process::exit(1)
+            }
+        },
+        None => DEFAULT_MAX_TIMESTAMP_SKEW,
+    };
+
+    let handler =
+        TrackAndTraceTransactionHandler::new().with_max_timestamp_skew(max_timestamp_skew);
     let mut processor = TransactionProcessor::new(endpoint);
 
     info!("Console logging level: {}", console_log_level);
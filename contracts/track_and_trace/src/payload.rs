@@ -20,14 +20,30 @@ cfg_if! {
     }
 }
 
+use std::collections::HashSet;
+
+use grid_sdk::gs1::validate_gtin;
+use grid_sdk::protocol::product::state::ProductNamespace;
 use grid_sdk::protocol::track_and_trace::payload::{
-    Action, CreateRecordAction, TrackAndTracePayload,
+    Action, CreateProposalAction, CreateRecordAction, TrackAndTracePayload,
 };
+use grid_sdk::protocol::track_and_trace::state::Role;
 
 pub fn validate_payload(payload: &TrackAndTracePayload) -> Result<(), ApplyError> {
     validate_timestamp(*payload.timestamp())?;
     match payload.action() {
         Action::CreateRecord(action_payload) => validate_record_create_action(action_payload),
+        Action::CreateProposal(action_payload) => {
+            validate_proposal_create_action(action_payload, *payload.timestamp())
+        }
+        Action::AggregateRecords(action_payload) => validate_aggregation_action(
+            action_payload.parent_record_id(),
+            action_payload.child_record_ids(),
+        ),
+        Action::DisaggregateRecords(action_payload) => validate_aggregation_action(
+            action_payload.parent_record_id(),
+            action_payload.child_record_ids(),
+        ),
         _ => Ok(()),
     }
 }
@@ -46,6 +62,145 @@ fn validate_record_create_action(
             "Schema name cannot be empty string",
         )));
     }
+
+    // A GS1 product is referenced by its GTIN. A custom product is only unique within its schema,
+    // so it is referenced by its ID together with the name of that schema.
+    let product_id = create_record_action.product_id();
+    let product_schema_name = create_record_action.product_schema_name();
+    if product_id.is_empty() {
+        if !product_schema_name.is_empty() {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Product schema name cannot be set without a product id",
+            )));
+        }
+        return Ok(());
+    }
+
+    match create_record_action.product_namespace() {
+        ProductNamespace::GS1 => {
+            if !product_schema_name.is_empty() {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "GS1 products are referenced by GTIN alone, without a product schema name",
+                )));
+            }
+            validate_gtin(product_id).map_err(|err| {
+                ApplyError::InvalidTransaction(format!(
+                    "GS1 products must be referenced by GTIN: {}",
+                    err
+                ))
+            })?;
+        }
+        ProductNamespace::Custom => {
+            if product_schema_name.is_empty() {
+                return Err(ApplyError::InvalidTransaction(String::from(
+                    "Custom products must be referenced with the name of their schema",
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_proposal_create_action(
+    create_proposal_action: &CreateProposalAction,
+    timestamp: u64,
+) -> Result<(), ApplyError> {
+    let expires_at = *create_proposal_action.expires_at();
+    if expires_at != 0 && expires_at <= timestamp {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Proposal expiry {} must be after the payload timestamp {}",
+            expires_at, timestamp
+        )));
+    }
+
+    let valid_from = *create_proposal_action.reporter_valid_from();
+    let valid_until = *create_proposal_action.reporter_valid_until();
+    let constraints = create_proposal_action.reporter_constraints();
+
+    if create_proposal_action.role() != &Role::Reporter {
+        if valid_from != 0 || valid_until != 0 || !constraints.is_empty() {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Reporter validity windows and constraints can only be set on reporter proposals",
+            )));
+        }
+        return Ok(());
+    }
+
+    if valid_from != 0 && valid_until != 0 && valid_from > valid_until {
+        return Err(ApplyError::InvalidTransaction(format!(
+            "Reporter validity window starts at {} after it ends at {}",
+            valid_from, valid_until
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    for constraint in constraints {
+        let property_name = constraint.property_name();
+        if !create_proposal_action
+            .properties()
+            .iter()
+            .any(|name| name == property_name)
+        {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Reporter constraints given for a property that is not part of the proposal: {}",
+                property_name
+            )));
+        }
+        if !seen.insert(property_name) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Reporter constraints are duplicated for property: {}",
+                property_name
+            )));
+        }
+        if let (Some(min_value), Some(max_value)) = (constraint.min_value(), constraint.max_value())
+        {
+            if min_value > max_value {
+                return Err(ApplyError::InvalidTransaction(format!(
+                    "Reporter constraints for {} have a minimum value {} above the maximum {}",
+                    property_name, min_value, max_value
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_aggregation_action(
+    parent_record_id: &str,
+    child_record_ids: &[String],
+) -> Result<(), ApplyError> {
+    if parent_record_id == "" {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "Parent record id cannot be empty string",
+        )));
+    }
+
+    if child_record_ids.is_empty() {
+        return Err(ApplyError::InvalidTransaction(String::from(
+            "At least one child record id must be provided",
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    for child_record_id in child_record_ids {
+        if child_record_id == "" {
+            return Err(ApplyError::InvalidTransaction(String::from(
+                "Child record id cannot be empty string",
+            )));
+        }
+        if child_record_id == parent_record_id {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Record cannot be aggregated into itself: {}",
+                child_record_id
+            )));
+        }
+        if !seen.insert(child_record_id) {
+            return Err(ApplyError::InvalidTransaction(format!(
+                "Child record id is duplicated: {}",
+                child_record_id
+            )));
+        }
+    }
     Ok(())
 }
 
@@ -62,11 +217,17 @@ fn validate_timestamp(timestamp: u64) -> Result<(), ApplyError> {
 mod tests {
     use super::*;
 
+    use grid_sdk::protos::product_state::Product_ProductNamespace as ProductNamespaceProto;
     use grid_sdk::protos::track_and_trace_payload::{
+        AggregateRecordsAction as AggregateRecordsActionProto,
+        CreateProposalAction as CreateProposalActionProto,
         CreateRecordAction as CreateRecordActionProto,
         TrackAndTracePayload as TrackAndTracePayloadProto,
         TrackAndTracePayload_Action as ActionProto,
     };
+    use grid_sdk::protos::track_and_trace_state::{
+        Proposal_Role as RoleProto, ReporterConstraints as ReporterConstraintsProto,
+    };
     use grid_sdk::protos::IntoNative;
 
     #[test]
@@ -169,4 +330,202 @@ mod tests {
             "Payload should be valid"
         );
     }
+
+    #[test]
+    /// Test that an error is returned if the payload with CreateRecordAction references a GS1
+    /// product by an ID that is not a GTIN, or with a product schema name
+    fn test_validate_payload_product_id_not_gtin() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::CREATE_RECORD);
+        payload_proto.set_timestamp(2);
+        let mut action = CreateRecordActionProto::new();
+        action.set_record_id("my_record".to_string());
+        action.set_schema("my_schema".to_string());
+        action.set_product_id("LB-0001".to_string());
+        payload_proto.set_create_record(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Product ID is not a GTIN, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("GS1 products must be referenced by GTIN")),
+        }
+
+        let mut action = CreateRecordActionProto::new();
+        action.set_record_id("my_record".to_string());
+        action.set_schema("my_schema".to_string());
+        action.set_product_id("041205707820".to_string());
+        action.set_product_namespace(ProductNamespaceProto::GS1);
+        action.set_product_schema_name("gs1_product".to_string());
+        payload_proto.set_create_record(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("GS1 product has a product schema name, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("GS1 products are referenced by GTIN alone")),
+        }
+
+        action.clear_product_schema_name();
+        payload_proto.set_create_record(action);
+        let payload = payload_proto.into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_ok(),
+            "Payload should be valid"
+        );
+    }
+
+    #[test]
+    /// Test that a CreateRecordAction references a custom product by any product ID, but only
+    /// together with the name of the product's schema
+    fn test_validate_payload_custom_product() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::CREATE_RECORD);
+        payload_proto.set_timestamp(2);
+        let mut action = CreateRecordActionProto::new();
+        action.set_record_id("my_record".to_string());
+        action.set_schema("my_schema".to_string());
+        action.set_product_id("LB-0001".to_string());
+        action.set_product_namespace(ProductNamespaceProto::CUSTOM);
+        payload_proto.set_create_record(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Custom product has no product schema name, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("Custom products must be referenced with the name of their schema")),
+        }
+
+        action.set_product_schema_name("lumber".to_string());
+        payload_proto.set_create_record(action.clone());
+        let payload = payload_proto.clone().into_native().unwrap();
+        assert!(
+            validate_payload(&payload).is_ok(),
+            "Payload should be valid"
+        );
+
+        action.clear_product_id();
+        payload_proto.set_create_record(action);
+        let payload = payload_proto.into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Product schema name is set without a product, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("Product schema name cannot be set without a product id")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if an AggregateRecordsAction lists the parent as one of
+    /// its children
+    fn test_validate_payload_aggregate_into_itself() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::AGGREGATE_RECORDS);
+        payload_proto.set_timestamp(2);
+        let mut action = AggregateRecordsActionProto::new();
+        action.set_parent_record_id("pallet".to_string());
+        action.set_child_record_ids(vec!["case".to_string(), "pallet".to_string()].into());
+        payload_proto.set_aggregate_records(action);
+        let payload = payload_proto.into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload aggregates a record into itself, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("Record cannot be aggregated into itself")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if an AggregateRecordsAction has no children
+    fn test_validate_payload_aggregate_no_children() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::AGGREGATE_RECORDS);
+        payload_proto.set_timestamp(2);
+        let mut action = AggregateRecordsActionProto::new();
+        action.set_parent_record_id("pallet".to_string());
+        payload_proto.set_aggregate_records(action);
+        let payload = payload_proto.into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload has no children, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("At least one child record id must be provided")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if a CreateProposalAction expires before it is created
+    fn test_validate_payload_proposal_already_expired() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::CREATE_PROPOSAL);
+        payload_proto.set_timestamp(2);
+        let mut action = CreateProposalActionProto::new();
+        action.set_record_id("pallet".to_string());
+        action.set_receiving_agent("receiving_agent".to_string());
+        action.set_expires_at(2);
+        payload_proto.set_create_proposal(action);
+        let payload = payload_proto.into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Payload proposal is already expired, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("must be after the payload timestamp")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if a non-reporter CreateProposalAction sets reporter
+    /// constraints
+    fn test_validate_payload_owner_proposal_with_reporter_constraints() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::CREATE_PROPOSAL);
+        payload_proto.set_timestamp(2);
+        let mut action = CreateProposalActionProto::new();
+        action.set_record_id("pallet".to_string());
+        action.set_receiving_agent("receiving_agent".to_string());
+        action.set_role(RoleProto::OWNER);
+        action.set_reporter_valid_until(10);
+        payload_proto.set_create_proposal(action);
+        let payload = payload_proto.into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Owner proposal sets a reporter window, should return error"),
+            Err(err) => assert!(err
+                .to_string()
+                .contains("can only be set on reporter proposals")),
+        }
+    }
+
+    #[test]
+    /// Test that an error is returned if a reporter CreateProposalAction constrains values to an
+    /// empty range
+    fn test_validate_payload_reporter_constraints_empty_range() {
+        let mut payload_proto = TrackAndTracePayloadProto::new();
+
+        payload_proto.set_action(ActionProto::CREATE_PROPOSAL);
+        payload_proto.set_timestamp(2);
+        let mut constraints = ReporterConstraintsProto::new();
+        constraints.set_property_name("temperature".to_string());
+        constraints.set_has_min_value(true);
+        constraints.set_min_value(60);
+        constraints.set_has_max_value(true);
+        constraints.set_max_value(-40);
+        let mut action = CreateProposalActionProto::new();
+        action.set_record_id("pallet".to_string());
+        action.set_receiving_agent("receiving_agent".to_string());
+        action.set_role(RoleProto::REPORTER);
+        action.set_properties(vec!["temperature".to_string()].into());
+        action.set_reporter_constraints(vec![constraints].into());
+        payload_proto.set_create_proposal(action);
+        let payload = payload_proto.into_native().unwrap();
+        match validate_payload(&payload) {
+            Ok(_) => panic!("Reporter constraints have an empty range, should return error"),
+            Err(err) => assert!(err.to_string().contains("above the maximum")),
+        }
+    }
 }
@@ -22,7 +22,14 @@ cfg_if! {
     }
 }
 
+use grid_sdk::protocol::addressing::{
+    compute_agent_address, compute_gs1_location_address, compute_product_address,
+    compute_property_address, compute_proposal_address, compute_record_address,
+    compute_schema_address,
+};
+use grid_sdk::protocol::location::state::{Location, LocationList};
 use grid_sdk::protocol::pike::state::{Agent, AgentList};
+use grid_sdk::protocol::product::state::{Product, ProductList, ProductNamespace};
 use grid_sdk::protocol::schema::state::{Schema, SchemaList};
 use grid_sdk::protocol::track_and_trace::state::{
     Property, PropertyList, PropertyListBuilder, PropertyPage, PropertyPageList,
@@ -31,8 +38,6 @@ use grid_sdk::protocol::track_and_trace::state::{
 
 use grid_sdk::protos::{FromBytes, IntoBytes};
 
-use crate::addressing::*;
-
 pub struct TrackAndTraceState<'a> {
     context: &'a mut dyn TransactionContext,
 }
@@ -43,7 +48,7 @@ impl<'a> TrackAndTraceState<'a> {
     }
 
     pub fn get_record(&self, record_id: &str) -> Result<Option<Record>, ApplyError> {
-        let address = make_record_address(record_id);
+        let address = compute_record_address(record_id);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -70,7 +75,7 @@ impl<'a> TrackAndTraceState<'a> {
     }
 
     pub fn set_record(&self, record_id: &str, record: Record) -> Result<(), ApplyError> {
-        let address = make_record_address(record_id);
+        let address = compute_record_address(record_id);
         let d = self.context.get_state_entry(&address)?;
         let mut records = match d {
             Some(packed) => match RecordList::from_bytes(packed.as_slice()) {
@@ -121,7 +126,7 @@ impl<'a> TrackAndTraceState<'a> {
     }
 
     pub fn get_schema(&self, schema_name: &str) -> Result<Option<Schema>, ApplyError> {
-        let address = make_schema_address(schema_name);
+        let address = compute_schema_address(schema_name);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -149,7 +154,7 @@ impl<'a> TrackAndTraceState<'a> {
 
     /// Gets a Pike Agent. Handles retrieving the correct agent from an AgentList.
     pub fn get_agent(&self, public_key: &str) -> Result<Option<Agent>, ApplyError> {
-        let address = make_agent_address(public_key);
+        let address = compute_agent_address(public_key);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -175,12 +180,77 @@ impl<'a> TrackAndTraceState<'a> {
         }
     }
 
+    /// Gets a product by its namespace and ID. The schema name is only used by custom products.
+    pub fn get_product(
+        &self,
+        namespace: &ProductNamespace,
+        schema_name: &str,
+        product_id: &str,
+    ) -> Result<Option<Product>, ApplyError> {
+        let address = compute_product_address(namespace, schema_name, product_id);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let products = match ProductList::from_bytes(packed.as_slice()) {
+                    Ok(products) => products,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize product list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the product with the correct id and, for custom products, schema
+                for product in products.products() {
+                    if product.product_namespace() == namespace
+                        && product.product_id() == product_id
+                        && (namespace == &ProductNamespace::GS1
+                            || product.schema_name() == schema_name)
+                    {
+                        return Ok(Some(product.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Gets a GS1 location by its GLN.
+    pub fn get_location(&self, gln: &str) -> Result<Option<Location>, ApplyError> {
+        let address = compute_gs1_location_address(gln);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let locations = match LocationList::from_bytes(packed.as_slice()) {
+                    Ok(locations) => locations,
+                    Err(err) => {
+                        return Err(ApplyError::InternalError(format!(
+                            "Cannot deserialize location list: {:?}",
+                            err,
+                        )));
+                    }
+                };
+
+                // find the location with the correct gln
+                for location in locations.locations() {
+                    if location.location_id() == gln {
+                        return Ok(Some(location.clone()));
+                    }
+                }
+                Ok(None)
+            }
+            None => Ok(None),
+        }
+    }
+
     pub fn get_property(
         &self,
         record_id: &str,
         property_name: &str,
     ) -> Result<Option<Property>, ApplyError> {
-        let address = make_property_address(record_id, property_name, 0);
+        let address = compute_property_address(record_id, property_name, 0);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -212,7 +282,7 @@ impl<'a> TrackAndTraceState<'a> {
         property_name: &str,
         property: Property,
     ) -> Result<(), ApplyError> {
-        let address = make_property_address(record_id, property_name, 0);
+        let address = compute_property_address(record_id, property_name, 0);
         let d = self.context.get_state_entry(&address)?;
         let mut properties = match d {
             Some(packed) => match PropertyList::from_bytes(packed.as_slice()) {
@@ -271,7 +341,7 @@ impl<'a> TrackAndTraceState<'a> {
         property_name: &str,
         page: u32,
     ) -> Result<Option<PropertyPage>, ApplyError> {
-        let address = make_property_address(record_id, property_name, page);
+        let address = compute_property_address(record_id, property_name, page);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => {
@@ -304,7 +374,7 @@ impl<'a> TrackAndTraceState<'a> {
         page_num: u32,
         property_page: PropertyPage,
     ) -> Result<(), ApplyError> {
-        let address = make_property_address(record_id, property_name, page_num);
+        let address = compute_property_address(record_id, property_name, page_num);
         let d = self.context.get_state_entry(&address)?;
         let mut pages = match d {
             Some(packed) => match PropertyPageList::from_bytes(packed.as_slice()) {
@@ -365,7 +435,7 @@ impl<'a> TrackAndTraceState<'a> {
         record_id: &str,
         agent_id: &str,
     ) -> Result<Option<ProposalList>, ApplyError> {
-        let address = make_proposal_address(record_id, agent_id);
+        let address = compute_proposal_address(record_id, agent_id);
         let d = self.context.get_state_entry(&address)?;
         match d {
             Some(packed) => match ProposalList::from_bytes(packed.as_slice()) {
@@ -385,7 +455,7 @@ impl<'a> TrackAndTraceState<'a> {
         agent_id: &str,
         proposals: ProposalList,
     ) -> Result<(), ApplyError> {
-        let address = make_proposal_address(record_id, agent_id);
+        let address = compute_proposal_address(record_id, agent_id);
         let serialized = match proposals.into_bytes() {
             Ok(serialized) => serialized,
             Err(err) => {
@@ -482,7 +552,7 @@ mod tests {
                 .build()
                 .unwrap();
             let agent_bytes = agent_list.into_bytes().unwrap();
-            let agent_address = make_agent_address(public_key);
+            let agent_address = compute_agent_address(public_key);
             self.set_state_entry(agent_address, agent_bytes).unwrap();
         }
     }
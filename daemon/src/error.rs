@@ -88,6 +88,7 @@ impl From<flexi_logger::FlexiLoggerError> for DaemonError {
 #[derive(Debug, PartialEq)]
 pub enum ConfigurationError {
     MissingValue(String),
+    InvalidValue(String),
 }
 
 impl Error for ConfigurationError {}
@@ -98,6 +99,7 @@ impl fmt::Display for ConfigurationError {
             ConfigurationError::MissingValue(config_field_name) => {
                 write!(f, "Missing configuration for {}", config_field_name)
             }
+            ConfigurationError::InvalidValue(msg) => write!(f, "Invalid configuration: {}", msg),
         }
     }
 }
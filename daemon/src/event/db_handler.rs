@@ -19,15 +19,16 @@ use diesel::Connection;
 use grid_sdk::{
     grid_db::{
         agents::store::Agent,
-        commits::store::CommitEvent as DbCommitEvent,
+        commits::store::{Checkpoint, CommitEvent as DbCommitEvent},
         locations::store::{LatLongValue as LocationLatLongValue, Location, LocationAttribute},
-        organizations::store::Organization,
+        organizations::store::{Organization, Role},
         products::store::{
             LatLongValue as ProductLatLongValue, Product, PropertyValue as ProductPropertyValue,
         },
         schemas::store::{PropertyDefinition as StorePropertyDefinition, Schema},
         track_and_trace::store::{
-            AssociatedAgent, LatLongValue as TntLatLongValue, Property, Proposal, Record,
+            AssociatedAgent, LatLongValue as TntLatLongValue, PageReportedValue, Property,
+            PropertyPageSnapshot, Proposal, Record, RecordAggregation,
             ReportedValue as StoreReportedValue, Reporter,
         },
         AgentStore, CommitStore, DieselAgentStore, DieselCommitStore, DieselLocationStore,
@@ -35,15 +36,19 @@ use grid_sdk::{
         LocationStore, OrganizationStore, ProductStore, SchemaStore, TrackAndTraceStore,
     },
     protocol::{
+        addressing::{
+            classify_address, compute_agent_address, compute_organization_address,
+            compute_role_address, parse_property_page, AddressKind,
+        },
         location::state::LocationList,
-        pike::state::{AgentList, OrganizationList},
+        pike::state::{AgentList, OrganizationList, RoleList},
         product::state::ProductList,
         schema::state::{DataType, PropertyDefinition, PropertyValue, SchemaList},
         track_and_trace::state::{
-            PropertyList, PropertyPageList, ProposalList, RecordList, ReportedValue,
+            PropertyList, PropertyPage, PropertyPageList, ProposalList, RecordList, ReportedValue,
         },
     },
-    protos::FromBytes,
+    protos::{FromBytes, IntoBytes},
 };
 use std::collections::HashMap;
 use std::i64;
@@ -51,15 +56,14 @@ use std::i64;
 use crate::database::ConnectionPool;
 
 use super::{
-    CommitEvent, EventError, EventHandler, StateChange, GRID_LOCATION, GRID_PRODUCT, GRID_SCHEMA,
-    IGNORED_NAMESPACES, PIKE_AGENT, PIKE_ORG, TRACK_AND_TRACE_PROPERTY, TRACK_AND_TRACE_PROPOSAL,
-    TRACK_AND_TRACE_RECORD,
+    CommitEvent, EventError, EventHandler, EventScope, Provenance, StateChange, IGNORED_NAMESPACES,
 };
 
 pub const MAX_COMMIT_NUM: i64 = i64::MAX;
 
 pub struct DatabaseEventHandler<C: diesel::Connection + 'static> {
     connection_pool: ConnectionPool<C>,
+    scope: EventScope,
     agent_store: DieselAgentStore<C>,
     commit_store: DieselCommitStore<C>,
     organization_store: DieselOrganizationStore<C>,
@@ -70,7 +74,10 @@ pub struct DatabaseEventHandler<C: diesel::Connection + 'static> {
 }
 
 impl DatabaseEventHandler<diesel::pg::PgConnection> {
-    pub fn from_pg_pool(connection_pool: ConnectionPool<diesel::pg::PgConnection>) -> Self {
+    pub fn from_pg_pool(
+        connection_pool: ConnectionPool<diesel::pg::PgConnection>,
+        scope: EventScope,
+    ) -> Self {
         let agent_store = DieselAgentStore::new(connection_pool.pool.clone());
         let commit_store = DieselCommitStore::new(connection_pool.pool.clone());
         let organization_store = DieselOrganizationStore::new(connection_pool.pool.clone());
@@ -82,6 +89,7 @@ impl DatabaseEventHandler<diesel::pg::PgConnection> {
         Self {
             agent_store,
             connection_pool,
+            scope,
             commit_store,
             organization_store,
             location_store,
@@ -112,14 +120,26 @@ impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
                 "Commit could not be constructed from event data".to_string(),
             ));
         };
+        let state_changes = event
+            .state_changes
+            .iter()
+            .filter(|state_change| self.scope.includes_state_change(state_change))
+            .cloned()
+            .collect::<Vec<_>>();
         let db_ops = create_db_operations_from_state_changes(
-            &event.state_changes,
+            &state_changes,
             commit.commit_num,
             commit.service_id.as_ref(),
         )?;
 
         trace!("The following operations will be performed: {:#?}", db_ops);
 
+        let checkpoint = Checkpoint {
+            service_id: commit.service_id.clone(),
+            commit_id: commit.commit_id.clone(),
+            commit_num: commit.commit_num,
+        };
+
         conn.build_transaction().run::<_, EventError, _>(|| {
             match self
                 .commit_store
@@ -160,6 +180,54 @@ impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
                         debug!("Inserting {} organizations", orgs.len());
                         self.organization_store.add_organizations(orgs)?;
                     }
+                    DbInsertOperation::RemoveAgent(ref address, ref service_id, commit_num) => {
+                        for agent in self.agent_store.list_agents(service_id.as_deref())? {
+                            if compute_agent_address(&agent.public_key) == *address {
+                                self.agent_store.delete_agent(
+                                    &agent.public_key,
+                                    service_id.as_deref(),
+                                    commit_num,
+                                )?;
+                            }
+                        }
+                    }
+                    DbInsertOperation::RemoveOrganization(
+                        ref address,
+                        ref service_id,
+                        commit_num,
+                    ) => {
+                        for org in self
+                            .organization_store
+                            .list_organizations(service_id.as_deref())?
+                        {
+                            if compute_organization_address(&org.org_id) == *address {
+                                self.organization_store.delete_organization(
+                                    &org.org_id,
+                                    service_id.as_deref(),
+                                    commit_num,
+                                )?;
+                            }
+                        }
+                    }
+                    DbInsertOperation::Roles(roles) => {
+                        debug!("Inserting {} roles", roles.len());
+                        self.organization_store.add_roles(roles)?;
+                    }
+                    DbInsertOperation::RemoveRole(ref address, ref service_id, commit_num) => {
+                        for role in self
+                            .organization_store
+                            .list_roles(None, service_id.as_deref())?
+                        {
+                            if compute_role_address(&role.org_id, &role.name) == *address {
+                                self.organization_store.delete_role(
+                                    &role.org_id,
+                                    &role.name,
+                                    service_id.as_deref(),
+                                    commit_num,
+                                )?;
+                            }
+                        }
+                    }
 
                     DbInsertOperation::GridSchemas(schemas) => {
                         debug!("Inserting {} schemas", schemas.len());
@@ -173,7 +241,9 @@ impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
                         debug!("Inserting {} reporters", reporters.len());
                         self.tnt_store.add_reporters(reporters)?;
                     }
-                    DbInsertOperation::ReportedValues(reported_values) => {
+                    DbInsertOperation::ReportedValues(reported_values, pages) => {
+                        debug!("Archiving {} property pages", pages.len());
+                        self.tnt_store.archive_property_pages(pages)?;
                         debug!("Inserting {} reported values", reported_values.len());
                         self.tnt_store.add_reported_values(reported_values)?;
                     }
@@ -181,11 +251,13 @@ impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
                         debug!("Inserting {} proposals", proposals.len());
                         self.tnt_store.add_proposals(proposals)?;
                     }
-                    DbInsertOperation::Records(records, associated_agents) => {
+                    DbInsertOperation::Records(records, associated_agents, aggregations) => {
                         debug!("Inserting {} records", records.len());
                         self.tnt_store.add_records(records)?;
                         debug!("Inserting {} associated agents", associated_agents.len());
                         self.tnt_store.add_associated_agents(associated_agents)?;
+                        debug!("Inserting {} record aggregations", aggregations.len());
+                        self.tnt_store.add_record_aggregations(aggregations)?;
                     }
                     DbInsertOperation::Locations(locations) => {
                         debug!("Inserting {} locations", locations.len());
@@ -210,18 +282,24 @@ impl EventHandler for DatabaseEventHandler<diesel::pg::PgConnection> {
                 };
             }
 
+            self.commit_store.update_checkpoint(checkpoint)?;
+
             Ok(())
         })
     }
 
     fn cloned_box(&self) -> Box<dyn EventHandler> {
-        Box::new(Self::from_pg_pool(self.connection_pool.clone()))
+        Box::new(Self::from_pg_pool(
+            self.connection_pool.clone(),
+            self.scope.clone(),
+        ))
     }
 }
 
 impl DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
     pub fn from_sqlite_pool(
         connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection>,
+        scope: EventScope,
     ) -> Self {
         let agent_store = DieselAgentStore::new(connection_pool.pool.clone());
         let commit_store = DieselCommitStore::new(connection_pool.pool.clone());
@@ -234,6 +312,7 @@ impl DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
         Self {
             agent_store,
             connection_pool,
+            scope,
             commit_store,
             organization_store,
             location_store,
@@ -264,14 +343,26 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
                 "Commit could not be constructed from event data".to_string(),
             ));
         };
+        let state_changes = event
+            .state_changes
+            .iter()
+            .filter(|state_change| self.scope.includes_state_change(state_change))
+            .cloned()
+            .collect::<Vec<_>>();
         let db_ops = create_db_operations_from_state_changes(
-            &event.state_changes,
+            &state_changes,
             commit.commit_num,
             commit.service_id.as_ref(),
         )?;
 
         trace!("The following operations will be performed: {:#?}", db_ops);
 
+        let checkpoint = Checkpoint {
+            service_id: commit.service_id.clone(),
+            commit_id: commit.commit_id.clone(),
+            commit_num: commit.commit_num,
+        };
+
         conn.transaction::<_, EventError, _>(|| {
             match self
                 .commit_store
@@ -312,6 +403,54 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
                         debug!("Inserting {} organizations", orgs.len());
                         self.organization_store.add_organizations(orgs)?;
                     }
+                    DbInsertOperation::RemoveAgent(ref address, ref service_id, commit_num) => {
+                        for agent in self.agent_store.list_agents(service_id.as_deref())? {
+                            if compute_agent_address(&agent.public_key) == *address {
+                                self.agent_store.delete_agent(
+                                    &agent.public_key,
+                                    service_id.as_deref(),
+                                    commit_num,
+                                )?;
+                            }
+                        }
+                    }
+                    DbInsertOperation::RemoveOrganization(
+                        ref address,
+                        ref service_id,
+                        commit_num,
+                    ) => {
+                        for org in self
+                            .organization_store
+                            .list_organizations(service_id.as_deref())?
+                        {
+                            if compute_organization_address(&org.org_id) == *address {
+                                self.organization_store.delete_organization(
+                                    &org.org_id,
+                                    service_id.as_deref(),
+                                    commit_num,
+                                )?;
+                            }
+                        }
+                    }
+                    DbInsertOperation::Roles(roles) => {
+                        debug!("Inserting {} roles", roles.len());
+                        self.organization_store.add_roles(roles)?;
+                    }
+                    DbInsertOperation::RemoveRole(ref address, ref service_id, commit_num) => {
+                        for role in self
+                            .organization_store
+                            .list_roles(None, service_id.as_deref())?
+                        {
+                            if compute_role_address(&role.org_id, &role.name) == *address {
+                                self.organization_store.delete_role(
+                                    &role.org_id,
+                                    &role.name,
+                                    service_id.as_deref(),
+                                    commit_num,
+                                )?;
+                            }
+                        }
+                    }
 
                     DbInsertOperation::GridSchemas(schemas) => {
                         debug!("Inserting {} schemas", schemas.len());
@@ -325,7 +464,9 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
                         debug!("Inserting {} reporters", reporters.len());
                         self.tnt_store.add_reporters(reporters)?;
                     }
-                    DbInsertOperation::ReportedValues(reported_values) => {
+                    DbInsertOperation::ReportedValues(reported_values, pages) => {
+                        debug!("Archiving {} property pages", pages.len());
+                        self.tnt_store.archive_property_pages(pages)?;
                         debug!("Inserting {} reported values", reported_values.len());
                         self.tnt_store.add_reported_values(reported_values)?;
                     }
@@ -333,11 +474,13 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
                         debug!("Inserting {} proposals", proposals.len());
                         self.tnt_store.add_proposals(proposals)?;
                     }
-                    DbInsertOperation::Records(records, associated_agents) => {
+                    DbInsertOperation::Records(records, associated_agents, aggregations) => {
                         debug!("Inserting {} records", records.len());
                         self.tnt_store.add_records(records)?;
                         debug!("Inserting {} associated agents", associated_agents.len());
                         self.tnt_store.add_associated_agents(associated_agents)?;
+                        debug!("Inserting {} record aggregations", aggregations.len());
+                        self.tnt_store.add_record_aggregations(aggregations)?;
                     }
                     DbInsertOperation::Locations(locations) => {
                         debug!("Inserting {} locations", locations.len());
@@ -362,12 +505,17 @@ impl EventHandler for DatabaseEventHandler<diesel::sqlite::SqliteConnection> {
                 };
             }
 
+            self.commit_store.update_checkpoint(checkpoint)?;
+
             Ok(())
         })
     }
 
     fn cloned_box(&self) -> Box<dyn EventHandler> {
-        Box::new(Self::from_sqlite_pool(self.connection_pool.clone()))
+        Box::new(Self::from_sqlite_pool(
+            self.connection_pool.clone(),
+            self.scope.clone(),
+        ))
     }
 }
 
@@ -384,357 +532,501 @@ fn create_db_operations_from_state_changes(
         .collect::<Result<Vec<DbInsertOperation>, EventError>>()
 }
 
-fn state_change_to_db_operation(
+pub(super) fn state_change_to_db_operation(
     state_change: &StateChange,
     commit_num: i64,
     service_id: Option<&String>,
 ) -> Result<Option<DbInsertOperation>, EventError> {
     match state_change {
-        StateChange::Set { key, value } => match &key[0..8] {
-            PIKE_AGENT => {
-                let agents = AgentList::from_bytes(&value)
-                    .map_err(|err| EventError(format!("Failed to parse agent list {}", err)))?
-                    .agents()
-                    .iter()
-                    .map(|agent| Agent {
-                        public_key: agent.public_key().to_string(),
-                        org_id: agent.org_id().to_string(),
-                        active: *agent.active(),
-                        roles: agent.roles().to_vec(),
-                        metadata: json!(agent.metadata().iter().fold(
-                            HashMap::new(),
-                            |mut acc, md| {
-                                acc.insert(md.key().to_string(), md.value().to_string());
-                                acc
-                            }
-                        ))
-                        .to_string()
-                        .into_bytes(),
-                        start_commit_num: commit_num,
-                        end_commit_num: MAX_COMMIT_NUM,
-                        service_id: service_id.cloned(),
-                    })
-                    .collect::<Vec<Agent>>();
-
-                Ok(Some(DbInsertOperation::Agents(agents)))
-            }
-            PIKE_ORG => {
-                let orgs = OrganizationList::from_bytes(&value)
-                    .map_err(|err| {
-                        EventError(format!("Failed to parse organization list {}", err))
-                    })?
-                    .organizations()
-                    .iter()
-                    .map(|org| Organization {
-                        org_id: org.org_id().to_string(),
-                        name: org.name().to_string(),
-                        address: org.address().to_string(),
-                        metadata: json!(org.metadata().iter().fold(
-                            HashMap::new(),
-                            |mut acc, md| {
-                                acc.insert(md.key().to_string(), md.value().to_string());
-                                acc
-                            }
-                        ))
-                        .to_string()
-                        .into_bytes(),
-                        start_commit_num: commit_num,
-                        end_commit_num: MAX_COMMIT_NUM,
-                        service_id: service_id.cloned(),
-                    })
-                    .collect::<Vec<Organization>>();
-
-                Ok(Some(DbInsertOperation::Organizations(orgs)))
-            }
-            GRID_SCHEMA => {
-                let schemas = SchemaList::from_bytes(&value)
-                    .map_err(|err| EventError(format!("Failed to parse schema list {}", err)))?
-                    .schemas()
-                    .iter()
-                    .map(|state_schema| Schema {
-                        name: state_schema.name().to_string(),
-                        description: state_schema.description().to_string(),
-                        owner: state_schema.owner().to_string(),
-                        start_commit_num: commit_num,
-                        end_commit_num: MAX_COMMIT_NUM,
-                        service_id: service_id.cloned(),
-                        properties: make_property_definitions(
-                            commit_num,
-                            service_id,
-                            state_schema.name(),
-                            state_schema.properties(),
-                        ),
-                    })
-                    .collect();
-
-                Ok(Some(DbInsertOperation::GridSchemas(schemas)))
-            }
-            TRACK_AND_TRACE_PROPERTY if &key[66..] == "0000" => {
-                let properties = PropertyList::from_bytes(&value)
-                    .map_err(|err| EventError(format!("Failed to parse property list {}", err)))?
-                    .properties()
-                    .iter()
-                    .map(|prop| {
-                        let property = Property {
-                            id: None,
-                            name: prop.name().to_string(),
-                            record_id: prop.record_id().to_string(),
-                            property_definition: prop.property_definition().name().to_string(),
-                            current_page: *prop.current_page() as i32,
-                            wrapped: *prop.wrapped(),
+        StateChange::Set {
+            key,
+            value,
+            provenance,
+        } => {
+            let transaction_id = provenance.as_ref().map(|p| p.transaction_id.clone());
+            let signer = provenance.as_ref().map(|p| p.signer.clone());
+
+            match classify_address(&key) {
+                Some(AddressKind::Agent) => {
+                    let agents = AgentList::from_bytes(&value)
+                        .map_err(|err| EventError(format!("Failed to parse agent list {}", err)))?
+                        .agents()
+                        .iter()
+                        .map(|agent| Agent {
+                            public_key: agent.public_key().to_string(),
+                            org_id: agent.org_id().to_string(),
+                            active: *agent.active(),
+                            roles: agent.roles().to_vec(),
+                            metadata: json!(agent.metadata().iter().fold(
+                                HashMap::new(),
+                                |mut acc, md| {
+                                    acc.insert(md.key().to_string(), md.value().to_string());
+                                    acc
+                                }
+                            ))
+                            .to_string()
+                            .into_bytes(),
                             start_commit_num: commit_num,
                             end_commit_num: MAX_COMMIT_NUM,
                             service_id: service_id.cloned(),
-                        };
+                            transaction_id: transaction_id.clone(),
+                            signer: signer.clone(),
+                        })
+                        .collect::<Vec<Agent>>();
 
-                        let reporters = prop
-                            .reporters()
-                            .iter()
-                            .map(|reporter| Reporter {
+                    Ok(Some(DbInsertOperation::Agents(agents)))
+                }
+                Some(AddressKind::Organization) => {
+                    let orgs = OrganizationList::from_bytes(&value)
+                        .map_err(|err| {
+                            EventError(format!("Failed to parse organization list {}", err))
+                        })?
+                        .organizations()
+                        .iter()
+                        .map(|org| Organization {
+                            org_id: org.org_id().to_string(),
+                            name: org.name().to_string(),
+                            address: org.address().to_string(),
+                            metadata: json!(org.metadata().iter().fold(
+                                HashMap::new(),
+                                |mut acc, md| {
+                                    acc.insert(md.key().to_string(), md.value().to_string());
+                                    acc
+                                }
+                            ))
+                            .to_string()
+                            .into_bytes(),
+                            start_commit_num: commit_num,
+                            end_commit_num: MAX_COMMIT_NUM,
+                            service_id: service_id.cloned(),
+                            transaction_id: transaction_id.clone(),
+                            signer: signer.clone(),
+                        })
+                        .collect::<Vec<Organization>>();
+
+                    Ok(Some(DbInsertOperation::Organizations(orgs)))
+                }
+                Some(AddressKind::Role) => {
+                    let roles = RoleList::from_bytes(&value)
+                        .map_err(|err| EventError(format!("Failed to parse role list {}", err)))?
+                        .roles()
+                        .iter()
+                        .map(|role| Role {
+                            org_id: role.org_id().to_string(),
+                            name: role.name().to_string(),
+                            description: role.description().to_string(),
+                            active: role.active(),
+                            permissions: role.permissions().to_vec(),
+                            allowed_organizations: role.allowed_organizations().to_vec(),
+                            start_commit_num: commit_num,
+                            end_commit_num: MAX_COMMIT_NUM,
+                            service_id: service_id.cloned(),
+                            transaction_id: transaction_id.clone(),
+                            signer: signer.clone(),
+                        })
+                        .collect::<Vec<Role>>();
+
+                    Ok(Some(DbInsertOperation::Roles(roles)))
+                }
+                Some(AddressKind::Schema) => {
+                    let schemas = SchemaList::from_bytes(&value)
+                        .map_err(|err| EventError(format!("Failed to parse schema list {}", err)))?
+                        .schemas()
+                        .iter()
+                        .map(|state_schema| Schema {
+                            name: state_schema.name().to_string(),
+                            description: state_schema.description().to_string(),
+                            owner: state_schema.owner().to_string(),
+                            start_commit_num: commit_num,
+                            end_commit_num: MAX_COMMIT_NUM,
+                            service_id: service_id.cloned(),
+                            transaction_id: transaction_id.clone(),
+                            signer: signer.clone(),
+                            properties: make_property_definitions(
+                                commit_num,
+                                service_id,
+                                state_schema.name(),
+                                state_schema.properties(),
+                            ),
+                            version: *state_schema.version() as i32,
+                        })
+                        .collect();
+
+                    Ok(Some(DbInsertOperation::GridSchemas(schemas)))
+                }
+                Some(AddressKind::Property) => {
+                    let properties = PropertyList::from_bytes(&value)
+                        .map_err(|err| {
+                            EventError(format!("Failed to parse property list {}", err))
+                        })?
+                        .properties()
+                        .iter()
+                        .map(|prop| {
+                            let property = Property {
                                 id: None,
-                                property_name: prop.name().to_string(),
+                                name: prop.name().to_string(),
                                 record_id: prop.record_id().to_string(),
-                                public_key: reporter.public_key().to_string(),
-                                authorized: *reporter.authorized(),
-                                reporter_index: *reporter.index() as i32,
+                                property_definition: prop.property_definition().name().to_string(),
+                                current_page: *prop.current_page() as i32,
+                                wrapped: *prop.wrapped(),
                                 start_commit_num: commit_num,
                                 end_commit_num: MAX_COMMIT_NUM,
                                 service_id: service_id.cloned(),
-                            })
-                            .collect::<Vec<Reporter>>();
+                                transaction_id: transaction_id.clone(),
+                                signer: signer.clone(),
+                            };
+
+                            let reporters = prop
+                                .reporters()
+                                .iter()
+                                .map(|reporter| Reporter {
+                                    id: None,
+                                    property_name: prop.name().to_string(),
+                                    record_id: prop.record_id().to_string(),
+                                    public_key: reporter.public_key().to_string(),
+                                    authorized: *reporter.authorized(),
+                                    reporter_index: *reporter.index() as i32,
+                                    start_commit_num: commit_num,
+                                    end_commit_num: MAX_COMMIT_NUM,
+                                    service_id: service_id.cloned(),
+                                })
+                                .collect::<Vec<Reporter>>();
 
-                        (property, reporters)
-                    })
-                    .collect::<Vec<(Property, Vec<Reporter>)>>();
+                            (property, reporters)
+                        })
+                        .collect::<Vec<(Property, Vec<Reporter>)>>();
 
-                let reporters = properties
-                    .clone()
-                    .into_iter()
-                    .flat_map(|(_, r)| r.into_iter())
-                    .collect();
+                    let reporters = properties
+                        .clone()
+                        .into_iter()
+                        .flat_map(|(_, r)| r.into_iter())
+                        .collect();
 
-                let properties = properties.into_iter().map(|(s, _)| s).collect();
+                    let properties = properties.into_iter().map(|(s, _)| s).collect();
 
-                Ok(Some(DbInsertOperation::Properties(properties, reporters)))
-            }
-            TRACK_AND_TRACE_PROPERTY => {
-                let property_pages = PropertyPageList::from_bytes(&value)
-                    .map_err(|err| {
-                        EventError(format!("Failed to parse property page list {}", err))
-                    })?
-                    .property_pages()
-                    .to_vec();
-
-                let mut reported_values: Vec<StoreReportedValue> = vec![];
-                for page in property_pages {
-                    page.reported_values().to_vec().iter().try_fold(
-                        &mut reported_values,
-                        |acc, value| match make_reported_values(
-                            commit_num,
-                            page.record_id(),
-                            value.value().name(),
-                            value,
-                        ) {
-                            Ok(mut vals) => {
-                                acc.append(&mut vals);
-                                Ok(acc)
-                            }
-                            Err(err) => Err(err),
-                        },
-                    )?;
+                    Ok(Some(DbInsertOperation::Properties(properties, reporters)))
                 }
+                Some(AddressKind::PropertyPage) => {
+                    let property_pages = PropertyPageList::from_bytes(&value)
+                        .map_err(|err| {
+                            EventError(format!("Failed to parse property page list {}", err))
+                        })?
+                        .property_pages()
+                        .to_vec();
+
+                    let page_num = parse_property_page(&key).ok_or_else(|| {
+                        EventError(format!("Failed to parse property page number of {}", key))
+                    })?;
 
-                Ok(Some(DbInsertOperation::ReportedValues(reported_values)))
-            }
-            TRACK_AND_TRACE_PROPOSAL => {
-                let proposals = ProposalList::from_bytes(&value)
-                    .map_err(|err| EventError(format!("Failed to parse proposal list {}", err)))?
-                    .proposals()
-                    .iter()
-                    .map(|proposal| Proposal {
-                        id: None,
-                        record_id: proposal.record_id().to_string(),
-                        timestamp: *proposal.timestamp() as i64,
-                        issuing_agent: proposal.issuing_agent().to_string(),
-                        receiving_agent: proposal.receiving_agent().to_string(),
-                        role: format!("{:?}", proposal.role()),
-                        properties: proposal.properties().to_vec(),
-                        status: format!("{:?}", proposal.status()),
-                        terms: proposal.terms().to_string(),
-                        start_commit_num: commit_num,
-                        end_commit_num: MAX_COMMIT_NUM,
-                        service_id: service_id.cloned(),
-                    })
-                    .collect::<Vec<Proposal>>();
-
-                Ok(Some(DbInsertOperation::Proposals(proposals)))
-            }
-            TRACK_AND_TRACE_RECORD => {
-                let record_list = RecordList::from_bytes(&value)
-                    .map_err(|err| EventError(format!("Failed to parse record list {}", err)))?
-                    .records()
-                    .to_vec();
-
-                let records = record_list
-                    .iter()
-                    .map(|record| Record {
-                        id: None,
-                        record_id: record.record_id().to_string(),
-                        final_: *record.field_final(),
-                        schema: record.schema().to_string(),
-                        owners: record
-                            .owners()
-                            .iter()
-                            .map(|x| x.agent_id().to_string())
-                            .collect(),
-                        custodians: record
-                            .custodians()
-                            .iter()
-                            .map(|x| x.agent_id().to_string())
-                            .collect(),
-                        start_commit_num: commit_num,
-                        end_commit_num: MAX_COMMIT_NUM,
-                        service_id: service_id.cloned(),
-                    })
-                    .collect::<Vec<Record>>();
-
-                let mut associated_agents = record_list
-                    .iter()
-                    .flat_map(|record| {
-                        record.owners().iter().map(move |agent| AssociatedAgent {
+                    let mut pages = vec![];
+                    let mut reported_values: Vec<StoreReportedValue> = vec![];
+                    for page in property_pages {
+                        pages.push(make_property_page_snapshot(
+                            commit_num, service_id, page_num, &page,
+                        )?);
+
+                        page.reported_values().to_vec().iter().try_fold(
+                            &mut reported_values,
+                            |acc, value| match make_reported_values(
+                                commit_num,
+                                provenance.as_ref(),
+                                page.record_id(),
+                                value.value().name(),
+                                value,
+                            ) {
+                                Ok(mut vals) => {
+                                    acc.append(&mut vals);
+                                    Ok(acc)
+                                }
+                                Err(err) => Err(err),
+                            },
+                        )?;
+                    }
+
+                    Ok(Some(DbInsertOperation::ReportedValues(
+                        reported_values,
+                        pages,
+                    )))
+                }
+                Some(AddressKind::Proposal) => {
+                    let proposals = ProposalList::from_bytes(&value)
+                        .map_err(|err| {
+                            EventError(format!("Failed to parse proposal list {}", err))
+                        })?
+                        .proposals()
+                        .iter()
+                        .map(|proposal| Proposal {
+                            id: None,
+                            record_id: proposal.record_id().to_string(),
+                            timestamp: *proposal.timestamp() as i64,
+                            issuing_agent: proposal.issuing_agent().to_string(),
+                            receiving_agent: proposal.receiving_agent().to_string(),
+                            role: format!("{:?}", proposal.role()),
+                            properties: proposal.properties().to_vec(),
+                            status: format!("{:?}", proposal.status()),
+                            terms: proposal.terms().to_string(),
+                            expires_at: match *proposal.expires_at() {
+                                0 => None,
+                                expires_at => Some(expires_at as i64),
+                            },
+                            start_commit_num: commit_num,
+                            end_commit_num: MAX_COMMIT_NUM,
+                            service_id: service_id.cloned(),
+                            transaction_id: transaction_id.clone(),
+                            signer: signer.clone(),
+                        })
+                        .collect::<Vec<Proposal>>();
+
+                    Ok(Some(DbInsertOperation::Proposals(proposals)))
+                }
+                Some(AddressKind::Record) => {
+                    let record_list = RecordList::from_bytes(&value)
+                        .map_err(|err| EventError(format!("Failed to parse record list {}", err)))?
+                        .records()
+                        .to_vec();
+
+                    let records = record_list
+                        .iter()
+                        .map(|record| Record {
                             id: None,
-                            agent_id: agent.agent_id().to_string(),
                             record_id: record.record_id().to_string(),
-                            role: "OWNER".to_string(),
-                            timestamp: *agent.timestamp() as i64,
+                            final_: *record.field_final(),
+                            schema: record.schema().to_string(),
+                            owners: record
+                                .owners()
+                                .iter()
+                                .map(|x| x.agent_id().to_string())
+                                .collect(),
+                            custodians: record
+                                .custodians()
+                                .iter()
+                                .map(|x| x.agent_id().to_string())
+                                .collect(),
                             start_commit_num: commit_num,
                             end_commit_num: MAX_COMMIT_NUM,
                             service_id: service_id.cloned(),
+                            transaction_id: transaction_id.clone(),
+                            signer: signer.clone(),
+                            schema_version: *record.schema_version() as i32,
+                            archived: *record.archived(),
+                            product_id: Some(record.product_id())
+                                .filter(|id| !id.is_empty())
+                                .map(String::from),
+                            location_id: Some(record.location_id())
+                                .filter(|id| !id.is_empty())
+                                .map(String::from),
+                            product_namespace: Some(record.product_id())
+                                .filter(|id| !id.is_empty())
+                                .map(|_| format!("{:?}", record.product_namespace())),
+                            product_schema_name: Some(record.product_schema_name())
+                                .filter(|name| !name.is_empty())
+                                .map(String::from),
+                        })
+                        .collect::<Vec<Record>>();
+
+                    let mut associated_agents = record_list
+                        .iter()
+                        .flat_map(|record| {
+                            record.owners().iter().map(move |agent| AssociatedAgent {
+                                id: None,
+                                agent_id: agent.agent_id().to_string(),
+                                record_id: record.record_id().to_string(),
+                                role: "OWNER".to_string(),
+                                timestamp: *agent.timestamp() as i64,
+                                start_commit_num: commit_num,
+                                end_commit_num: MAX_COMMIT_NUM,
+                                service_id: service_id.cloned(),
+                            })
                         })
-                    })
-                    .collect::<Vec<AssociatedAgent>>();
+                        .collect::<Vec<AssociatedAgent>>();
+
+                    associated_agents.append(
+                        &mut record_list
+                            .iter()
+                            .flat_map(|record| {
+                                record
+                                    .custodians()
+                                    .iter()
+                                    .map(move |agent| AssociatedAgent {
+                                        id: None,
+                                        agent_id: agent.agent_id().to_string(),
+                                        role: "CUSTODIAN".to_string(),
+                                        record_id: record.record_id().to_string(),
+                                        timestamp: *agent.timestamp() as i64,
+                                        start_commit_num: commit_num,
+                                        end_commit_num: MAX_COMMIT_NUM,
+                                        service_id: service_id.cloned(),
+                                    })
+                            })
+                            .collect::<Vec<AssociatedAgent>>(),
+                    );
 
-                associated_agents.append(
-                    &mut record_list
+                    // Both records in an aggregation carry it in state; the child's copy is
+                    // stored so each aggregation is only recorded once
+                    let aggregations = record_list
                         .iter()
                         .flat_map(|record| {
                             record
-                                .custodians()
+                                .aggregations()
                                 .iter()
-                                .map(move |agent| AssociatedAgent {
+                                .filter(move |aggregation| {
+                                    aggregation.child_record_id() == record.record_id()
+                                })
+                                .map(|aggregation| RecordAggregation {
                                     id: None,
-                                    agent_id: agent.agent_id().to_string(),
-                                    role: "CUSTODIAN".to_string(),
-                                    record_id: record.record_id().to_string(),
-                                    timestamp: *agent.timestamp() as i64,
+                                    parent_record_id: aggregation.parent_record_id().to_string(),
+                                    child_record_id: aggregation.child_record_id().to_string(),
+                                    aggregated_at: *aggregation.aggregated_at() as i64,
+                                    disaggregated_at: if aggregation.is_current() {
+                                        None
+                                    } else {
+                                        Some(*aggregation.disaggregated_at() as i64)
+                                    },
                                     start_commit_num: commit_num,
                                     end_commit_num: MAX_COMMIT_NUM,
                                     service_id: service_id.cloned(),
                                 })
                         })
-                        .collect::<Vec<AssociatedAgent>>(),
-                );
+                        .collect::<Vec<RecordAggregation>>();
 
-                Ok(Some(DbInsertOperation::Records(records, associated_agents)))
-            }
-            GRID_LOCATION => {
-                let locations = LocationList::from_bytes(&value)
-                    .map_err(|err| EventError(format!("Failed to parse location list {}", err)))?
-                    .locations()
-                    .iter()
-                    .map(|location| Location {
-                        location_id: location.location_id().to_string(),
-                        location_address: key.to_string(),
-                        location_namespace: format!("{:?}", location.namespace()),
-                        owner: location.owner().to_string(),
-                        attributes: make_location_attributes(
-                            commit_num,
-                            service_id,
-                            location.location_id(),
-                            &key,
-                            location.properties(),
-                        ),
-                        start_commit_num: commit_num,
-                        end_commit_num: MAX_COMMIT_NUM,
-                        service_id: service_id.cloned(),
-                    })
-                    .collect();
-
-                Ok(Some(DbInsertOperation::Locations(locations)))
-            }
-            GRID_PRODUCT => {
-                let products = ProductList::from_bytes(&value)
-                    .map_err(|err| EventError(format!("Failed to parse product list {}", err)))?
-                    .products()
-                    .iter()
-                    .map(|product| Product {
-                        product_id: product.product_id().to_string(),
-                        product_address: key.to_string(),
-                        product_namespace: format!("{:?}", product.product_namespace()),
-                        owner: product.owner().to_string(),
-                        start_commit_num: commit_num,
-                        end_commit_num: MAX_COMMIT_NUM,
-                        service_id: service_id.cloned(),
-                        properties: make_product_property_values(
-                            commit_num,
-                            service_id,
-                            product.product_id(),
-                            &key,
-                            product.properties(),
-                        ),
-                    })
-                    .collect();
-
-                Ok(Some(DbInsertOperation::Products(products)))
-            }
-            _ => {
-                let ignore_state_change = IGNORED_NAMESPACES
-                    .iter()
-                    .any(|namespace| key.starts_with(namespace));
-                if !ignore_state_change {
-                    debug!("received state change for unknown address: {}", key);
+                    Ok(Some(DbInsertOperation::Records(
+                        records,
+                        associated_agents,
+                        aggregations,
+                    )))
+                }
+                Some(AddressKind::Location) => {
+                    let locations = LocationList::from_bytes(&value)
+                        .map_err(|err| {
+                            EventError(format!("Failed to parse location list {}", err))
+                        })?
+                        .locations()
+                        .iter()
+                        .map(|location| Location {
+                            location_id: location.location_id().to_string(),
+                            location_address: key.to_string(),
+                            location_namespace: format!("{:?}", location.namespace()),
+                            owner: location.owner().to_string(),
+                            attributes: make_location_attributes(
+                                commit_num,
+                                service_id,
+                                location.location_id(),
+                                &key,
+                                location.properties(),
+                            ),
+                            start_commit_num: commit_num,
+                            end_commit_num: MAX_COMMIT_NUM,
+                            service_id: service_id.cloned(),
+                            transaction_id: transaction_id.clone(),
+                            signer: signer.clone(),
+                            pending_owner: location.pending_owner().to_string(),
+                            schema_version: *location.schema_version() as i32,
+                            parent_location_id: Some(location.parent_location_id())
+                                .filter(|id| !id.is_empty())
+                                .map(String::from),
+                        })
+                        .collect();
+
+                    Ok(Some(DbInsertOperation::Locations(locations)))
+                }
+                Some(AddressKind::Product) => {
+                    let products = ProductList::from_bytes(&value)
+                        .map_err(|err| EventError(format!("Failed to parse product list {}", err)))?
+                        .products()
+                        .iter()
+                        .map(|product| Product {
+                            product_id: product.product_id().to_string(),
+                            product_address: key.to_string(),
+                            product_namespace: format!("{:?}", product.product_namespace()),
+                            schema_name: product.schema_name().to_string(),
+                            owner: product.owner().to_string(),
+                            start_commit_num: commit_num,
+                            end_commit_num: MAX_COMMIT_NUM,
+                            service_id: service_id.cloned(),
+                            transaction_id: transaction_id.clone(),
+                            signer: signer.clone(),
+                            properties: make_product_property_values(
+                                commit_num,
+                                service_id,
+                                product.product_id(),
+                                &key,
+                                product.properties(),
+                            ),
+                            pending_owner: product.pending_owner().to_string(),
+                            schema_version: *product.schema_version() as i32,
+                            status: product.status().to_string(),
+                        })
+                        .collect();
+
+                    Ok(Some(DbInsertOperation::Products(products)))
+                }
+                _ => {
+                    let ignore_state_change = IGNORED_NAMESPACES
+                        .iter()
+                        .any(|namespace| key.starts_with(namespace));
+                    if !ignore_state_change {
+                        debug!("received state change for unknown address: {}", key);
+                    }
+                    Ok(None)
                 }
-                Ok(None)
-            }
-        },
-        StateChange::Delete { key } => {
-            if &key[0..8] == GRID_PRODUCT {
-                Ok(Some(DbInsertOperation::RemoveProduct(
-                    key.to_string(),
-                    commit_num,
-                )))
-            } else if &key[0..8] == GRID_LOCATION {
-                Ok(Some(DbInsertOperation::RemoveLocation(
-                    key.to_string(),
-                    commit_num,
-                )))
-            } else {
-                Err(EventError(format!(
-                    "could not handle state change; unexpected delete of key {}",
-                    key
-                )))
             }
         }
+        StateChange::Delete { key, .. } => match classify_address(&key) {
+            // Pike addresses are hashes of the public key, organization ID or role name, so
+            // the rows to end are found by matching the address of each current entry
+            Some(AddressKind::Agent) => Ok(Some(DbInsertOperation::RemoveAgent(
+                key.to_string(),
+                service_id.cloned(),
+                commit_num,
+            ))),
+            Some(AddressKind::Organization) => Ok(Some(DbInsertOperation::RemoveOrganization(
+                key.to_string(),
+                service_id.cloned(),
+                commit_num,
+            ))),
+            Some(AddressKind::Role) => Ok(Some(DbInsertOperation::RemoveRole(
+                key.to_string(),
+                service_id.cloned(),
+                commit_num,
+            ))),
+            Some(AddressKind::Product) => Ok(Some(DbInsertOperation::RemoveProduct(
+                key.to_string(),
+                commit_num,
+            ))),
+            Some(AddressKind::Location) => Ok(Some(DbInsertOperation::RemoveLocation(
+                key.to_string(),
+                commit_num,
+            ))),
+            _ => Err(EventError(format!(
+                "could not handle state change; unexpected delete of key {}",
+                key
+            ))),
+        },
     }
 }
 
 #[derive(Debug)]
-enum DbInsertOperation {
+pub(super) enum DbInsertOperation {
     Agents(Vec<Agent>),
     Organizations(Vec<Organization>),
+    Roles(Vec<Role>),
     GridSchemas(Vec<Schema>),
     Locations(Vec<Location>),
     Properties(Vec<Property>, Vec<Reporter>),
-    ReportedValues(Vec<StoreReportedValue>),
+    ReportedValues(Vec<StoreReportedValue>, Vec<PropertyPageSnapshot>),
     Proposals(Vec<Proposal>),
-    Records(Vec<Record>, Vec<AssociatedAgent>),
+    Records(Vec<Record>, Vec<AssociatedAgent>, Vec<RecordAggregation>),
     Products(Vec<Product>),
+    RemoveAgent(String, Option<String>, i64),
+    RemoveOrganization(String, Option<String>, i64),
+    RemoveRole(String, Option<String>, i64),
     RemoveLocation(String, i64),
     RemoveProduct(String, i64),
 }
 
 fn make_reported_values(
     start_commit_num: i64,
+    provenance: Option<&Provenance>,
     record_id: &str,
     property_name: &str,
     reported_value: &ReportedValue,
@@ -749,6 +1041,8 @@ fn make_reported_values(
         start_commit_num,
         end_commit_num: MAX_COMMIT_NUM,
         data_type: format!("{:?}", reported_value.value().data_type()),
+        transaction_id: provenance.map(|p| p.transaction_id.clone()),
+        signer: provenance.map(|p| p.signer.clone()),
         ..StoreReportedValue::default()
     };
 
@@ -780,6 +1074,7 @@ fn make_reported_values(
 
                 child_values.append(&mut make_reported_values(
                     start_commit_num,
+                    provenance,
                     record_id,
                     &property_name,
                     &value,
@@ -795,6 +1090,37 @@ fn make_reported_values(
             );
             new_value.lat_long_value = Some(lat_long_value);
         }
+        DataType::Timestamp => {
+            new_value.timestamp_value = Some(*reported_value.value().timestamp_value() as i64)
+        }
+        DataType::Decimal => {
+            new_value.decimal_value = Some(reported_value.value().decimal_value().to_string())
+        }
+        DataType::List => {
+            let mut child_values = Vec::new();
+
+            for (index, value) in reported_value.value().list_values().iter().enumerate() {
+                let property_name = format!("{}_{}", reported_value.value().name(), index);
+                let value = reported_value
+                    .clone()
+                    .into_builder()
+                    .with_value(value.clone())
+                    .build()
+                    .map_err(|err| {
+                        EventError(format!("Failed to build ReportedValue: {:?}", err))
+                    })?;
+
+                child_values.append(&mut make_reported_values(
+                    start_commit_num,
+                    provenance,
+                    record_id,
+                    &property_name,
+                    &value,
+                )?);
+            }
+
+            new_value.list_values = Some(child_values);
+        }
     };
 
     new_values.push(new_value);
@@ -802,6 +1128,43 @@ fn make_reported_values(
     Ok(new_values)
 }
 
+fn make_property_page_snapshot(
+    commit_num: i64,
+    service_id: Option<&String>,
+    page_num: u32,
+    page: &PropertyPage,
+) -> Result<PropertyPageSnapshot, EventError> {
+    let reported_values =
+        page.reported_values()
+            .iter()
+            .map(|reported_value| {
+                let value = reported_value.value().clone().into_bytes().map_err(|err| {
+                    EventError(format!("Failed to serialize reported value {}", err))
+                })?;
+
+                Ok(PageReportedValue {
+                    reporter_index: *reported_value.reporter_index() as i32,
+                    timestamp: *reported_value.timestamp() as i64,
+                    data_type: format!("{:?}", reported_value.value().data_type()),
+                    value,
+                    number_value: match reported_value.value().data_type() {
+                        DataType::Number => Some(*reported_value.value().number_value()),
+                        _ => None,
+                    },
+                })
+            })
+            .collect::<Result<Vec<PageReportedValue>, EventError>>()?;
+
+    Ok(PropertyPageSnapshot {
+        record_id: page.record_id().to_string(),
+        property_name: page.name().to_string(),
+        page_num: page_num as i32,
+        reported_values,
+        commit_num,
+        service_id: service_id.cloned(),
+    })
+}
+
 fn make_property_definitions(
     start_commit_num: i64,
     service_id: Option<&String>,
@@ -828,6 +1191,10 @@ fn make_property_definitions(
             start_commit_num,
             end_commit_num: MAX_COMMIT_NUM,
             service_id: service_id.cloned(),
+            deprecated: *def.deprecated(),
+            list_data_type: def
+                .list_data_type()
+                .map(|data_type| format!("{:?}", data_type)),
         });
     }
 
@@ -865,6 +1232,22 @@ fn make_product_property_values(
                 latitude: *val.lat_long_value().latitude(),
                 longitude: *val.lat_long_value().longitude(),
             }),
+            timestamp_value: Some(*val.timestamp_value() as i64),
+            decimal_value: Some(val.decimal_value().to_string()),
+            list_values: make_product_property_values(
+                start_commit_num,
+                service_id,
+                product_id,
+                product_address,
+                val.list_values(),
+            )
+            .into_iter()
+            .enumerate()
+            .map(|(index, mut element)| {
+                element.property_name = index.to_string();
+                element
+            })
+            .collect(),
             start_commit_num,
             end_commit_num: MAX_COMMIT_NUM,
             service_id: service_id.cloned(),
@@ -905,6 +1288,24 @@ fn make_location_attributes(
                 *attr.lat_long_value().latitude(),
                 *attr.lat_long_value().longitude(),
             )),
+            timestamp_value: Some(*attr.timestamp_value() as i64),
+            decimal_value: Some(attr.decimal_value().to_string()),
+            list_values: Some(
+                make_location_attributes(
+                    start_commit_num,
+                    service_id,
+                    location_id,
+                    location_address,
+                    attr.list_values(),
+                )
+                .into_iter()
+                .enumerate()
+                .map(|(index, mut element)| {
+                    element.property_name = index.to_string();
+                    element
+                })
+                .collect(),
+            ),
             start_commit_num,
             end_commit_num: MAX_COMMIT_NUM,
             service_id: service_id.cloned(),
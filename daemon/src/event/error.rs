@@ -99,6 +99,8 @@ impl From<diesel::result::Error> for EventError {
 pub enum EventIoError {
     ConnectionError(String),
     InvalidMessage(String),
+    /// The connection has no more events to deliver
+    EndOfStream(String),
 }
 
 impl Error for EventIoError {}
@@ -110,6 +112,7 @@ impl fmt::Display for EventIoError {
                 write!(f, "event connection encountered an error: {}", err)
             }
             Self::InvalidMessage(err) => write!(f, "connection received invalid message: {}", err),
+            Self::EndOfStream(msg) => write!(f, "event stream has ended: {}", msg),
         }
     }
 }
@@ -17,6 +17,10 @@
 
 pub mod db_handler;
 mod error;
+#[cfg(any(feature = "kafka-publisher", test))]
+pub mod publisher;
+pub mod recording;
+mod scope;
 
 use std::cell::RefCell;
 use std::thread;
@@ -24,22 +28,12 @@ use std::thread;
 use grid_sdk::grid_db::commits::store::{
     CommitEvent as DbCommitEvent, StateChange as DbStateChange,
 };
+use grid_sdk::protocol::addressing::{GRID_NAMESPACE, PIKE_NAMESPACE, TRACK_AND_TRACE_NAMESPACE};
 
-pub use self::error::{EventError, EventIoError, EventProcessorError};
-
-const PIKE_NAMESPACE: &str = "cad11d";
-const PIKE_AGENT: &str = "cad11d00";
-const PIKE_ORG: &str = "cad11d01";
-
-const GRID_NAMESPACE: &str = "621dee";
-const GRID_SCHEMA: &str = "621dee01";
-const GRID_PRODUCT: &str = "621dee02";
-const GRID_LOCATION: &str = "621dee04";
+pub use grid_sdk::grid_db::commits::store::Provenance;
 
-const TRACK_AND_TRACE_NAMESPACE: &str = "a43b46";
-const TRACK_AND_TRACE_PROPERTY: &str = "a43b46ea";
-const TRACK_AND_TRACE_PROPOSAL: &str = "a43b46aa";
-const TRACK_AND_TRACE_RECORD: &str = "a43b46ec";
+pub use self::error::{EventError, EventIoError, EventProcessorError};
+pub use self::scope::{EntityFamily, EventScope, ALL_ENTITY_FAMILIES};
 
 const ALL_GRID_NAMESPACES: &[&str] = &[PIKE_NAMESPACE, GRID_NAMESPACE, TRACK_AND_TRACE_NAMESPACE];
 
@@ -109,19 +103,47 @@ impl From<&CommitEvent> for DbCommitEvent {
 }
 
 /// A change that has been applied to state, represented in terms of a key/value pair
+///
+/// The provenance identifies the transaction that made the change, if the event stream provides
+/// it.
 #[derive(Clone, Eq, PartialEq)]
 pub enum StateChange {
-    Set { key: String, value: Vec<u8> },
-    Delete { key: String },
+    Set {
+        key: String,
+        value: Vec<u8>,
+        provenance: Option<Provenance>,
+    },
+    Delete {
+        key: String,
+        provenance: Option<Provenance>,
+    },
 }
 
 impl StateChange {
-    pub fn key_has_prefix(&self, prefix: &str) -> bool {
-        let key = match self {
+    pub fn key(&self) -> &str {
+        match self {
             Self::Set { key, .. } => key,
             Self::Delete { key, .. } => key,
-        };
-        key.get(0..prefix.len())
+        }
+    }
+
+    pub fn provenance(&self) -> Option<&Provenance> {
+        match self {
+            Self::Set { provenance, .. } => provenance.as_ref(),
+            Self::Delete { provenance, .. } => provenance.as_ref(),
+        }
+    }
+
+    pub fn set_provenance(&mut self, new_provenance: Option<Provenance>) {
+        match self {
+            Self::Set { provenance, .. } => *provenance = new_provenance,
+            Self::Delete { provenance, .. } => *provenance = new_provenance,
+        }
+    }
+
+    pub fn key_has_prefix(&self, prefix: &str) -> bool {
+        self.key()
+            .get(0..prefix.len())
             .map(|key_prefix| key_prefix == prefix)
             .unwrap_or(false)
     }
@@ -136,8 +158,16 @@ impl StateChange {
 impl From<DbStateChange> for StateChange {
     fn from(event: DbStateChange) -> StateChange {
         match event {
-            DbStateChange::Set { key: k, value: v } => StateChange::Set { key: k, value: v },
-            DbStateChange::Delete { key: k } => StateChange::Delete { key: k },
+            DbStateChange::Set {
+                key,
+                value,
+                provenance,
+            } => StateChange::Set {
+                key,
+                value,
+                provenance,
+            },
+            DbStateChange::Delete { key, provenance } => StateChange::Delete { key, provenance },
         }
     }
 }
@@ -145,8 +175,16 @@ impl From<DbStateChange> for StateChange {
 impl From<StateChange> for DbStateChange {
     fn from(event: StateChange) -> DbStateChange {
         match event {
-            StateChange::Set { key: k, value: v } => DbStateChange::Set { key: k, value: v },
-            StateChange::Delete { key: k } => DbStateChange::Delete { key: k },
+            StateChange::Set {
+                key,
+                value,
+                provenance,
+            } => DbStateChange::Set {
+                key,
+                value,
+                provenance,
+            },
+            StateChange::Delete { key, provenance } => DbStateChange::Delete { key, provenance },
         }
     }
 }
@@ -233,10 +271,11 @@ impl<Conn: EventConnection + 'static> EventProcessor<Conn> {
     pub fn start(
         mut connection: Conn,
         last_known_commit_id: Option<&str>,
+        scope: &EventScope,
         event_handlers: Vec<Box<dyn EventHandler>>,
     ) -> Result<Self, EventProcessorError> {
         let unsubscriber = connection
-            .subscribe(ALL_GRID_NAMESPACES, last_known_commit_id)
+            .subscribe(&scope.namespaces(), last_known_commit_id)
             .map_err(|err| EventProcessorError(format!("Unable to unsubscribe: {}", err)))?;
 
         let join_handle = thread::Builder::new()
@@ -248,6 +287,10 @@ impl<Conn: EventConnection + 'static> EventProcessor<Conn> {
                         Err(EventIoError::InvalidMessage(msg)) => {
                             warn!("{}; ignoring...", msg);
                         }
+                        Err(EventIoError::EndOfStream(msg)) => {
+                            info!("{}", msg);
+                            break;
+                        }
                         Err(err) => {
                             error!("Failed to receive events; aborting: {}", err);
                             break;
@@ -0,0 +1,65 @@
+/*
+ * Copyright 2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use kafka::producer::{Producer, Record, RequiredAcks};
+
+use super::{MessageBroker, MessageBrokerError};
+
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A `MessageBroker` backed by a Kafka cluster.
+///
+/// Messages are only considered published once every in-sync replica has acknowledged them.
+/// Clones share the same producer.
+#[derive(Clone)]
+pub struct KafkaBroker {
+    producer: Arc<Mutex<Producer>>,
+}
+
+impl KafkaBroker {
+    /// Connects to the Kafka cluster through the given `host:port` bootstrap brokers
+    pub fn connect(hosts: Vec<String>) -> Result<Self, MessageBrokerError> {
+        let producer = Producer::from_hosts(hosts)
+            .with_ack_timeout(ACK_TIMEOUT)
+            .with_required_acks(RequiredAcks::All)
+            .create()
+            .map_err(|err| {
+                MessageBrokerError(format!("Unable to connect to Kafka brokers: {}", err))
+            })?;
+
+        Ok(Self {
+            producer: Arc::new(Mutex::new(producer)),
+        })
+    }
+}
+
+impl MessageBroker for KafkaBroker {
+    fn publish(&self, topic: &str, key: &[u8], payload: &[u8]) -> Result<(), MessageBrokerError> {
+        self.producer
+            .lock()
+            .map_err(|_| MessageBrokerError("Kafka producer lock was poisoned".into()))?
+            .send(&Record::from_key_value(topic, key, payload))
+            .map_err(|err| MessageBrokerError(format!("Unable to send to Kafka: {}", err)))
+    }
+
+    fn cloned_box(&self) -> Box<dyn MessageBroker> {
+        Box::new(self.clone())
+    }
+}
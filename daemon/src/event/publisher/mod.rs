@@ -0,0 +1,620 @@
+/*
+ * Copyright 2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+//! Publishing of decoded Grid entity changes to a message broker.
+//!
+//! Each commit event is published as a single versioned JSON envelope. Messages are keyed by the
+//! event's `service_id`, so a broker that partitions by key keeps the commits of each source in
+//! order.
+
+#[cfg(feature = "kafka-publisher")]
+pub mod kafka;
+
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use grid_sdk::grid_db::{
+    AgentStore, DieselAgentStore, DieselLocationStore, DieselOrganizationStore, DieselProductStore,
+    LocationStore, OrganizationStore, ProductStore,
+};
+use grid_sdk::protocol::addressing::{
+    compute_agent_address, compute_organization_address, compute_role_address, AddressKind,
+};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::database::ConnectionPool;
+
+use super::db_handler::{state_change_to_db_operation, DbInsertOperation};
+use super::{CommitEvent, EventError, EventHandler, EventScope, StateChange};
+
+/// The version of the envelope format, incremented whenever it changes incompatibly
+pub const ENVELOPE_VERSION: u32 = 1;
+
+const INITIAL_RETRY_DELAY: Duration = Duration::from_millis(100);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/// Fields of the decoded entities that are either specific to Grid's database or already present
+/// on the envelope
+const OMITTED_FIELDS: &[&str] = &[
+    "id",
+    "start_commit_num",
+    "end_commit_num",
+    "service_id",
+    "transaction_id",
+    "signer",
+];
+
+#[derive(Debug)]
+pub struct MessageBrokerError(pub String);
+
+impl Error for MessageBrokerError {}
+
+impl fmt::Display for MessageBrokerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Message Broker Error: {}", self.0)
+    }
+}
+
+/// A message broker to which the decoded entity stream is published
+pub trait MessageBroker: Send + Sync {
+    /// Publishes a message to the given topic, returning once the broker has acknowledged it
+    fn publish(&self, topic: &str, key: &[u8], payload: &[u8]) -> Result<(), MessageBrokerError>;
+
+    fn cloned_box(&self) -> Box<dyn MessageBroker>;
+}
+
+/// Looks up the identifiers of the entities stored at a deleted address. A delete only carries
+/// the address, which for Pike entities is a hash of the identifiers.
+pub trait RemovedEntityLookup: Send + Sync {
+    /// Returns the identifiers of the current entities of the given kind stored at the address
+    fn removed_entities(
+        &self,
+        kind: AddressKind,
+        address: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<JsonValue>, EventError>;
+
+    fn cloned_box(&self) -> Box<dyn RemovedEntityLookup>;
+}
+
+/// A `RemovedEntityLookup` backed by Grid's database. The publisher precedes the database
+/// handler, so the entities removed by a commit are still current when it is published.
+#[derive(Clone)]
+pub struct StoreLookup {
+    agent_store: Arc<dyn AgentStore>,
+    organization_store: Arc<dyn OrganizationStore>,
+    location_store: Arc<dyn LocationStore>,
+    product_store: Arc<dyn ProductStore>,
+}
+
+impl StoreLookup {
+    pub fn new<C: diesel::Connection + 'static>(connection_pool: &ConnectionPool<C>) -> Self
+    where
+        DieselAgentStore<C>: AgentStore,
+        DieselOrganizationStore<C>: OrganizationStore,
+        DieselLocationStore<C>: LocationStore,
+        DieselProductStore<C>: ProductStore,
+    {
+        Self {
+            agent_store: Arc::new(DieselAgentStore::new(connection_pool.pool.clone())),
+            organization_store: Arc::new(DieselOrganizationStore::new(
+                connection_pool.pool.clone(),
+            )),
+            location_store: Arc::new(DieselLocationStore::new(connection_pool.pool.clone())),
+            product_store: Arc::new(DieselProductStore::new(connection_pool.pool.clone())),
+        }
+    }
+}
+
+impl RemovedEntityLookup for StoreLookup {
+    fn removed_entities(
+        &self,
+        kind: AddressKind,
+        address: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<JsonValue>, EventError> {
+        let ids = match kind {
+            AddressKind::Agent => self
+                .agent_store
+                .list_agents(service_id)?
+                .into_iter()
+                .filter(|agent| compute_agent_address(&agent.public_key) == address)
+                .map(|agent| json!({ "public_key": agent.public_key, "org_id": agent.org_id }))
+                .collect(),
+            AddressKind::Organization => self
+                .organization_store
+                .list_organizations(service_id)?
+                .into_iter()
+                .filter(|org| compute_organization_address(&org.org_id) == address)
+                .map(|org| json!({ "org_id": org.org_id }))
+                .collect(),
+            AddressKind::Role => self
+                .organization_store
+                .list_roles(None, service_id)?
+                .into_iter()
+                .filter(|role| compute_role_address(&role.org_id, &role.name) == address)
+                .map(|role| json!({ "org_id": role.org_id, "name": role.name }))
+                .collect(),
+            AddressKind::Location => self
+                .location_store
+                .list_locations(service_id)?
+                .into_iter()
+                .filter(|location| location.location_address == address)
+                .map(|location| json!({ "location_id": location.location_id }))
+                .collect(),
+            AddressKind::Product => self
+                .product_store
+                .fetch_product(address, service_id)?
+                .into_iter()
+                .map(|product| {
+                    json!({
+                        "product_id": product.product_id,
+                        "product_namespace": product.product_namespace,
+                        "schema_name": product.schema_name,
+                    })
+                })
+                .collect(),
+            _ => vec![],
+        };
+
+        Ok(ids)
+    }
+
+    fn cloned_box(&self) -> Box<dyn RemovedEntityLookup> {
+        Box::new(self.clone())
+    }
+}
+
+/// The decoded entity changes of a single commit
+#[derive(Debug, Serialize)]
+pub struct EventEnvelope {
+    pub version: u32,
+    pub commit_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    pub changes: Vec<EntityChange>,
+}
+
+/// The entities of a single kind decoded from one state change
+#[derive(Debug, Serialize)]
+pub struct EntityChange {
+    pub entity: &'static str,
+    pub operation: ChangeOperation,
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+    /// The entities stored at the address, or the identifiers of those removed if the address was
+    /// deleted
+    pub data: Vec<JsonValue>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOperation {
+    Set,
+    Delete,
+}
+
+impl EventEnvelope {
+    /// Decodes the state changes of the event that fall within the given scope
+    pub fn from_event(
+        event: &CommitEvent,
+        scope: &EventScope,
+        lookup: &dyn RemovedEntityLookup,
+    ) -> Result<Self, EventError> {
+        let changes = event
+            .state_changes
+            .iter()
+            .filter(|state_change| scope.includes_state_change(state_change))
+            .map(|state_change| entity_changes(state_change, event.service_id.as_ref(), lookup))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok(Self {
+            version: ENVELOPE_VERSION,
+            commit_id: event.id.clone(),
+            service_id: event.service_id.clone(),
+            height: event.height,
+            changes,
+        })
+    }
+}
+
+/// An event handler that publishes the decoded entity changes of each commit to a message broker.
+///
+/// Publishing is retried until the broker acknowledges the envelope, so the handlers after this
+/// one, including the database handler that advances the checkpoint, only see a commit once it
+/// has been published. If `gridd` stops before then, the commit is received again on restart.
+/// Delivery is therefore at-least-once, and consumers should deduplicate on `commit_id`.
+pub struct PublishingEventHandler {
+    broker: Box<dyn MessageBroker>,
+    topic: String,
+    scope: EventScope,
+    lookup: Box<dyn RemovedEntityLookup>,
+}
+
+impl PublishingEventHandler {
+    pub fn new(
+        broker: Box<dyn MessageBroker>,
+        topic: &str,
+        scope: EventScope,
+        lookup: Box<dyn RemovedEntityLookup>,
+    ) -> Self {
+        Self {
+            broker,
+            topic: topic.to_string(),
+            scope,
+            lookup,
+        }
+    }
+}
+
+impl EventHandler for PublishingEventHandler {
+    fn handle_event(&self, event: &CommitEvent) -> Result<(), EventError> {
+        let envelope = EventEnvelope::from_event(event, &self.scope, &*self.lookup)?;
+        if envelope.changes.is_empty() {
+            debug!("Commit {} has no Grid changes to publish", event.id);
+            return Ok(());
+        }
+
+        let payload = serde_json::to_vec(&envelope)
+            .map_err(|err| EventError(format!("Unable to serialize event envelope: {}", err)))?;
+        let key = event.service_id.as_deref().unwrap_or_default().as_bytes();
+
+        let mut retry_delay = INITIAL_RETRY_DELAY;
+        loop {
+            match self.broker.publish(&self.topic, key, &payload) {
+                Ok(()) => {
+                    debug!("Published commit {} to {}", event.id, self.topic);
+                    return Ok(());
+                }
+                Err(err) => {
+                    warn!(
+                        "Unable to publish commit {}; retrying in {:?}: {}",
+                        event.id, retry_delay, err
+                    );
+                    thread::sleep(retry_delay);
+                    retry_delay = cmp::min(retry_delay * 2, MAX_RETRY_DELAY);
+                }
+            }
+        }
+    }
+
+    fn cloned_box(&self) -> Box<dyn EventHandler> {
+        Box::new(Self {
+            broker: self.broker.cloned_box(),
+            topic: self.topic.clone(),
+            scope: self.scope.clone(),
+            lookup: self.lookup.cloned_box(),
+        })
+    }
+}
+
+fn entity_changes(
+    state_change: &StateChange,
+    service_id: Option<&String>,
+    lookup: &dyn RemovedEntityLookup,
+) -> Result<Vec<EntityChange>, EventError> {
+    // The commit number only determines database bookkeeping fields, which are omitted
+    let op = match state_change_to_db_operation(state_change, 0, service_id)? {
+        Some(op) => op,
+        None => return Ok(vec![]),
+    };
+    let removed = |kind, address: &str| {
+        lookup.removed_entities(kind, address, service_id.map(String::as_str))
+    };
+
+    let entities = match op {
+        DbInsertOperation::Agents(agents) => vec![("agent", to_data(&agents)?)],
+        DbInsertOperation::Organizations(orgs) => vec![("organization", to_data(&orgs)?)],
+        DbInsertOperation::Roles(roles) => vec![("role", to_data(&roles)?)],
+        DbInsertOperation::GridSchemas(schemas) => vec![("schema", to_data(&schemas)?)],
+        DbInsertOperation::Locations(locations) => vec![("location", to_data(&locations)?)],
+        DbInsertOperation::Properties(properties, reporters) => vec![
+            ("property", to_data(&properties)?),
+            ("reporter", to_data(&reporters)?),
+        ],
+        DbInsertOperation::ReportedValues(values, _) => {
+            vec![("reported_value", to_data(&values)?)]
+        }
+        DbInsertOperation::Proposals(proposals) => vec![("proposal", to_data(&proposals)?)],
+        DbInsertOperation::Records(records, associated_agents, aggregations) => vec![
+            ("record", to_data(&records)?),
+            ("associated_agent", to_data(&associated_agents)?),
+            ("record_aggregation", to_data(&aggregations)?),
+        ],
+        DbInsertOperation::Products(products) => vec![("product", to_data(&products)?)],
+        DbInsertOperation::RemoveAgent(ref address, ..) => {
+            vec![("agent", removed(AddressKind::Agent, address)?)]
+        }
+        DbInsertOperation::RemoveOrganization(ref address, ..) => {
+            vec![("organization", removed(AddressKind::Organization, address)?)]
+        }
+        DbInsertOperation::RemoveRole(ref address, ..) => {
+            vec![("role", removed(AddressKind::Role, address)?)]
+        }
+        DbInsertOperation::RemoveLocation(ref address, ..) => {
+            vec![("location", removed(AddressKind::Location, address)?)]
+        }
+        DbInsertOperation::RemoveProduct(ref address, ..) => {
+            vec![("product", removed(AddressKind::Product, address)?)]
+        }
+    };
+
+    let operation = match state_change {
+        StateChange::Set { .. } => ChangeOperation::Set,
+        StateChange::Delete { .. } => ChangeOperation::Delete,
+    };
+    let provenance = state_change.provenance();
+
+    Ok(entities
+        .into_iter()
+        .map(|(entity, data)| EntityChange {
+            entity,
+            operation,
+            address: state_change.key().to_string(),
+            transaction_id: provenance.map(|p| p.transaction_id.clone()),
+            signer: provenance.map(|p| p.signer.clone()),
+            data,
+        })
+        .collect())
+}
+
+fn to_data<T: Serialize>(entities: &[T]) -> Result<Vec<JsonValue>, EventError> {
+    entities
+        .iter()
+        .map(|entity| {
+            let mut value = serde_json::to_value(entity)
+                .map_err(|err| EventError(format!("Unable to serialize entity: {}", err)))?;
+            omit_fields(&mut value);
+            Ok(value)
+        })
+        .collect()
+}
+
+fn omit_fields(value: &mut JsonValue) {
+    match value {
+        JsonValue::Object(fields) => {
+            for field in OMITTED_FIELDS {
+                fields.remove(*field);
+            }
+            fields.values_mut().for_each(omit_fields);
+        }
+        JsonValue::Array(values) => values.iter_mut().for_each(omit_fields),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use grid_sdk::protocol::pike::state::{AgentBuilder, AgentListBuilder};
+    use grid_sdk::protos::IntoBytes;
+
+    use grid_sdk::protocol::addressing::PIKE_AGENT_PREFIX;
+
+    use super::super::{EntityFamily, Provenance};
+
+    /// An in-process broker that keeps the messages published to it, and fails a configurable
+    /// number of publish attempts first.
+    #[derive(Clone, Default)]
+    struct InMemoryBroker {
+        messages: Arc<Mutex<Vec<(String, Vec<u8>, Vec<u8>)>>>,
+        failures: Arc<Mutex<usize>>,
+    }
+
+    impl MessageBroker for InMemoryBroker {
+        fn publish(
+            &self,
+            topic: &str,
+            key: &[u8],
+            payload: &[u8],
+        ) -> Result<(), MessageBrokerError> {
+            let mut failures = self.failures.lock().expect("failures lock poisoned");
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(MessageBrokerError("broker unavailable".into()));
+            }
+
+            self.messages.lock().expect("messages lock poisoned").push((
+                topic.to_string(),
+                key.to_vec(),
+                payload.to_vec(),
+            ));
+            Ok(())
+        }
+
+        fn cloned_box(&self) -> Box<dyn MessageBroker> {
+            Box::new(self.clone())
+        }
+    }
+
+    /// A lookup that finds the same agent at every deleted agent address
+    #[derive(Clone)]
+    struct FixedLookup;
+
+    impl RemovedEntityLookup for FixedLookup {
+        fn removed_entities(
+            &self,
+            kind: AddressKind,
+            _address: &str,
+            _service_id: Option<&str>,
+        ) -> Result<Vec<JsonValue>, EventError> {
+            match kind {
+                AddressKind::Agent => Ok(vec![json!({ "public_key": "agent_public_key" })]),
+                _ => Ok(vec![]),
+            }
+        }
+
+        fn cloned_box(&self) -> Box<dyn RemovedEntityLookup> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn agent_event(service_id: Option<&str>) -> CommitEvent {
+        let agent = AgentBuilder::new()
+            .with_org_id("org_01".into())
+            .with_public_key("agent_public_key".into())
+            .with_active(true)
+            .with_roles(vec!["admin".into()])
+            .with_metadata(vec![])
+            .build()
+            .expect("Unable to build agent");
+        let agent_list = AgentListBuilder::new()
+            .with_agents(vec![agent])
+            .build()
+            .expect("Unable to build agent list");
+
+        CommitEvent {
+            service_id: service_id.map(ToOwned::to_owned),
+            id: "commit-1".into(),
+            height: Some(1),
+            state_changes: vec![StateChange::Set {
+                key: format!("{}{}", PIKE_AGENT_PREFIX, "0".repeat(62)),
+                value: agent_list
+                    .into_bytes()
+                    .expect("Unable to serialize agent list"),
+                provenance: Some(Provenance {
+                    transaction_id: "txn-1".into(),
+                    signer: "signer-1".into(),
+                }),
+            }],
+        }
+    }
+
+    /// Verify that a commit is published as a single envelope, keyed by its service ID, that
+    /// carries the decoded agent without any database bookkeeping fields.
+    #[test]
+    fn publishes_decoded_entities() {
+        let broker = InMemoryBroker::default();
+        let handler = PublishingEventHandler::new(
+            Box::new(broker.clone()),
+            "grid-events",
+            EventScope::default(),
+            Box::new(FixedLookup),
+        );
+
+        handler
+            .handle_event(&agent_event(Some("circuit::service")))
+            .expect("Unable to publish event");
+
+        let messages = broker.messages.lock().unwrap();
+        assert_eq!(messages.len(), 1);
+        let (topic, key, payload) = &messages[0];
+        assert_eq!(topic, "grid-events");
+        assert_eq!(key, b"circuit::service");
+
+        let envelope: JsonValue = serde_json::from_slice(payload).expect("Invalid envelope");
+        assert_eq!(envelope["version"], ENVELOPE_VERSION);
+        assert_eq!(envelope["commit_id"], "commit-1");
+        assert_eq!(envelope["service_id"], "circuit::service");
+
+        let change = &envelope["changes"][0];
+        assert_eq!(change["entity"], "agent");
+        assert_eq!(change["operation"], "set");
+        assert_eq!(change["transaction_id"], "txn-1");
+        assert_eq!(change["signer"], "signer-1");
+        assert_eq!(change["data"][0]["public_key"], "agent_public_key");
+        assert!(change["data"][0].get("start_commit_num").is_none());
+        assert!(change["data"][0].get("service_id").is_none());
+    }
+
+    /// Verify that the delete of an agent's address is published with the identifiers of the
+    /// removed agent, since the state change only carries the address.
+    #[test]
+    fn publishes_identifiers_of_removed_entities() {
+        let broker = InMemoryBroker::default();
+        let handler = PublishingEventHandler::new(
+            Box::new(broker.clone()),
+            "grid-events",
+            EventScope::default(),
+            Box::new(FixedLookup),
+        );
+
+        let event = CommitEvent {
+            service_id: None,
+            id: "commit-2".into(),
+            height: Some(2),
+            state_changes: vec![StateChange::Delete {
+                key: format!("{}{}", PIKE_AGENT_PREFIX, "0".repeat(62)),
+                provenance: None,
+            }],
+        };
+        handler
+            .handle_event(&event)
+            .expect("Unable to publish event");
+
+        let messages = broker.messages.lock().unwrap();
+        let envelope: JsonValue = serde_json::from_slice(&messages[0].2).expect("Invalid envelope");
+        let change = &envelope["changes"][0];
+        assert_eq!(change["entity"], "agent");
+        assert_eq!(change["operation"], "delete");
+        assert_eq!(change["data"][0]["public_key"], "agent_public_key");
+    }
+
+    /// Verify that a failed publish is retried until the broker accepts the envelope, so the
+    /// handler only returns once the commit has been delivered.
+    #[test]
+    fn retries_until_published() {
+        let broker = InMemoryBroker::default();
+        *broker.failures.lock().unwrap() = 2;
+        let handler = PublishingEventHandler::new(
+            Box::new(broker.clone()),
+            "grid-events",
+            EventScope::default(),
+            Box::new(FixedLookup),
+        );
+
+        handler
+            .handle_event(&agent_event(None))
+            .expect("Unable to publish event");
+
+        assert_eq!(*broker.failures.lock().unwrap(), 0);
+        assert_eq!(broker.messages.lock().unwrap().len(), 1);
+    }
+
+    /// Verify that commits from entity families outside of the scope are not published.
+    #[test]
+    fn skips_events_outside_of_scope() {
+        let broker = InMemoryBroker::default();
+        let handler = PublishingEventHandler::new(
+            Box::new(broker.clone()),
+            "grid-events",
+            EventScope::new(vec![EntityFamily::Product], None),
+            Box::new(FixedLookup),
+        );
+        handler
+            .handle_event(&agent_event(None))
+            .expect("Unable to handle event");
+
+        assert!(broker.messages.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,596 @@
+/*
+ * Copyright 2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+//! Recording and replay of commit event streams.
+//!
+//! Commit events are stored one per line as JSON objects, in the order in which they were
+//! received. State change values are base64 encoded.
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Lines, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use grid_sdk::grid_db::commits::store::Checkpoint;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    CommitEvent, EventConnection, EventConnectionUnsubscriber, EventError, EventHandler,
+    EventIoError, EventScope, Provenance, StateChange,
+};
+
+#[derive(Serialize, Deserialize)]
+struct RecordedCommitEvent {
+    service_id: Option<String>,
+    id: String,
+    height: Option<u64>,
+    state_changes: Vec<RecordedStateChange>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum RecordedStateChange {
+    Set {
+        key: String,
+        value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        provenance: Option<RecordedProvenance>,
+    },
+    Delete {
+        key: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        provenance: Option<RecordedProvenance>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct RecordedProvenance {
+    transaction_id: String,
+    signer: String,
+}
+
+impl From<&Provenance> for RecordedProvenance {
+    fn from(provenance: &Provenance) -> Self {
+        Self {
+            transaction_id: provenance.transaction_id.clone(),
+            signer: provenance.signer.clone(),
+        }
+    }
+}
+
+impl From<RecordedProvenance> for Provenance {
+    fn from(provenance: RecordedProvenance) -> Self {
+        Self {
+            transaction_id: provenance.transaction_id,
+            signer: provenance.signer,
+        }
+    }
+}
+
+impl From<&CommitEvent> for RecordedCommitEvent {
+    fn from(event: &CommitEvent) -> Self {
+        Self {
+            service_id: event.service_id.clone(),
+            id: event.id.clone(),
+            height: event.height,
+            state_changes: event
+                .state_changes
+                .iter()
+                .map(|state_change| match state_change {
+                    StateChange::Set {
+                        key,
+                        value,
+                        provenance,
+                    } => RecordedStateChange::Set {
+                        key: key.clone(),
+                        value: base64::encode(value),
+                        provenance: provenance.as_ref().map(RecordedProvenance::from),
+                    },
+                    StateChange::Delete { key, provenance } => RecordedStateChange::Delete {
+                        key: key.clone(),
+                        provenance: provenance.as_ref().map(RecordedProvenance::from),
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl RecordedCommitEvent {
+    fn into_commit_event(self) -> Result<CommitEvent, EventIoError> {
+        let state_changes = self
+            .state_changes
+            .into_iter()
+            .map(|state_change| match state_change {
+                RecordedStateChange::Set {
+                    key,
+                    value,
+                    provenance,
+                } => base64::decode(&value)
+                    .map(|value| StateChange::Set {
+                        key,
+                        value,
+                        provenance: provenance.map(Provenance::from),
+                    })
+                    .map_err(|err| {
+                        EventIoError::InvalidMessage(format!(
+                            "recorded state change value is not valid base64: {}",
+                            err
+                        ))
+                    }),
+                RecordedStateChange::Delete { key, provenance } => Ok(StateChange::Delete {
+                    key,
+                    provenance: provenance.map(Provenance::from),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(CommitEvent {
+            service_id: self.service_id,
+            id: self.id,
+            height: self.height,
+            state_changes,
+        })
+    }
+}
+
+/// An event handler that appends every commit event it receives to a recording file.
+///
+/// Clones of the handler share the underlying file, so a single recording may be used for
+/// several event processors.
+pub struct RecordingEventHandler {
+    file: Arc<Mutex<File>>,
+}
+
+impl RecordingEventHandler {
+    /// Opens the given file for recording, appending to it if it already exists.
+    pub fn open(path: &str) -> Result<Self, EventIoError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|err| {
+                EventIoError::ConnectionError(format!(
+                    "unable to open recording file {}: {}",
+                    path, err
+                ))
+            })?;
+
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+}
+
+impl EventHandler for RecordingEventHandler {
+    fn handle_event(&self, event: &CommitEvent) -> Result<(), EventError> {
+        let mut line = serde_json::to_string(&RecordedCommitEvent::from(event))
+            .map_err(|err| EventError(format!("Unable to serialize commit event: {}", err)))?;
+        line.push('\n');
+
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| EventError("Recording file lock was poisoned".into()))?;
+        file.write_all(line.as_bytes())
+            .and_then(|_| file.flush())
+            .map_err(|err| EventError(format!("Unable to record commit event: {}", err)))
+    }
+
+    fn cloned_box(&self) -> Box<dyn EventHandler> {
+        Box::new(Self {
+            file: self.file.clone(),
+        })
+    }
+}
+
+/// An event connection that replays commit events from a recording file.
+pub struct ReplayEventConnection {
+    name: String,
+    path: String,
+    lines: RefCell<Lines<BufReader<File>>>,
+    namespaces: Vec<String>,
+    scope: EventScope,
+    checkpoints: HashMap<Option<String>, String>,
+    skip_until: RefCell<HashMap<Option<String>, String>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl ReplayEventConnection {
+    /// Opens the given recording file for replay.
+    pub fn open(path: &str) -> Result<Self, EventIoError> {
+        let file = File::open(path).map_err(|err| {
+            EventIoError::ConnectionError(format!("unable to open replay file {}: {}", path, err))
+        })?;
+
+        Ok(Self {
+            name: format!("replay:{}", path),
+            path: path.to_string(),
+            lines: RefCell::new(BufReader::new(file).lines()),
+            namespaces: vec![],
+            scope: EventScope::default(),
+            checkpoints: HashMap::new(),
+            skip_until: RefCell::new(HashMap::new()),
+            shutdown: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// Only replays the commits of the sources within the given scope; the commits of other
+    /// sources are skipped without being passed on.
+    pub fn with_scope(mut self, scope: EventScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Resumes each source from its checkpoint: the recorded commits of a source are skipped up
+    /// to and including the commit of its checkpoint.
+    pub fn with_checkpoints(mut self, checkpoints: Vec<Checkpoint>) -> Self {
+        self.checkpoints = checkpoints
+            .into_iter()
+            .map(|checkpoint| (checkpoint.service_id, checkpoint.commit_id))
+            .collect();
+        self
+    }
+
+    /// Returns the checkpoints of the sources in scope that have commits in the recording, failing
+    /// if the commit of one of them was not recorded, as the commits to resume from are then
+    /// unknown.
+    fn find_checkpoints(
+        &self,
+        checkpoints: HashMap<Option<String>, String>,
+    ) -> Result<HashMap<Option<String>, String>, EventIoError> {
+        let file = File::open(&self.path).map_err(|err| {
+            EventIoError::ConnectionError(format!(
+                "unable to open replay file {}: {}",
+                self.path, err
+            ))
+        })?;
+
+        let mut recorded_sources = HashSet::new();
+        let mut found_sources = HashSet::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|err| EventIoError::ConnectionError(err.to_string()))?;
+            // Lines that cannot be parsed are reported when they are replayed
+            let event = match serde_json::from_str::<RecordedCommitEvent>(&line) {
+                Ok(event) => event,
+                Err(_) => continue,
+            };
+            if !self.scope.includes_service(event.service_id.as_deref()) {
+                continue;
+            }
+            if checkpoints.get(&event.service_id) == Some(&event.id) {
+                found_sources.insert(event.service_id.clone());
+            }
+            recorded_sources.insert(event.service_id);
+        }
+
+        checkpoints
+            .into_iter()
+            .filter(|(source, _)| recorded_sources.contains(source))
+            .map(|(source, commit_id)| {
+                if found_sources.contains(&source) {
+                    Ok((source, commit_id))
+                } else {
+                    Err(EventIoError::ConnectionError(format!(
+                        "checkpoint {} of {} is not in the recording {}",
+                        commit_id,
+                        source.as_deref().unwrap_or("sawtooth"),
+                        self.path
+                    )))
+                }
+            })
+            .collect()
+    }
+
+    fn next_event(&self) -> Result<CommitEvent, EventIoError> {
+        loop {
+            if self.shutdown.load(Ordering::SeqCst) {
+                return Err(EventIoError::EndOfStream(format!(
+                    "replay from {} was stopped",
+                    self.name
+                )));
+            }
+
+            let line = match self.lines.borrow_mut().next() {
+                Some(Ok(line)) => line,
+                Some(Err(err)) => return Err(EventIoError::ConnectionError(err.to_string())),
+                None => {
+                    return Err(EventIoError::EndOfStream(format!(
+                        "{} has no more events",
+                        self.name
+                    )))
+                }
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            return serde_json::from_str::<RecordedCommitEvent>(&line)
+                .map_err(|err| {
+                    EventIoError::InvalidMessage(format!("unable to parse recorded event: {}", err))
+                })?
+                .into_commit_event();
+        }
+    }
+}
+
+impl EventConnection for ReplayEventConnection {
+    type Unsubscriber = ReplayEventUnsubscriber;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn subscribe(
+        &mut self,
+        namespaces: &[&str],
+        last_commit_id: Option<&str>,
+    ) -> Result<Self::Unsubscriber, EventIoError> {
+        self.namespaces = namespaces.iter().map(|ns| ns.to_string()).collect();
+
+        // As for a Sawtooth connection, the last commit ID given is that of the Sawtooth source
+        let mut checkpoints = self.checkpoints.clone();
+        if let Some(last_commit_id) = last_commit_id {
+            checkpoints.insert(None, last_commit_id.to_string());
+        }
+        *self.skip_until.borrow_mut() = self.find_checkpoints(checkpoints)?;
+
+        Ok(ReplayEventUnsubscriber {
+            shutdown: self.shutdown.clone(),
+        })
+    }
+
+    fn recv(&self) -> Result<CommitEvent, EventIoError> {
+        loop {
+            let mut event = self.next_event()?;
+
+            if !self.scope.includes_service(event.service_id.as_deref()) {
+                continue;
+            }
+
+            // Skip each source's commits up to and including the last one already processed
+            let mut skip_until = self.skip_until.borrow_mut();
+            if let Some(last_commit_id) = skip_until.get(&event.service_id) {
+                if &event.id == last_commit_id {
+                    skip_until.remove(&event.service_id);
+                }
+                continue;
+            }
+
+            let namespaces = &self.namespaces;
+            event.state_changes.retain(|state_change| {
+                namespaces
+                    .iter()
+                    .any(|namespace| state_change.key_has_prefix(namespace))
+            });
+
+            break Ok(event);
+        }
+    }
+
+    fn close(self) -> Result<(), EventIoError> {
+        Ok(())
+    }
+}
+
+/// EventConnectionUnsubscriber for replayed event streams.
+pub struct ReplayEventUnsubscriber {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl EventConnectionUnsubscriber for ReplayEventUnsubscriber {
+    fn unsubscribe(self) -> Result<(), EventIoError> {
+        self.shutdown.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::fs;
+
+    use super::super::{ALL_ENTITY_FAMILIES, ALL_GRID_NAMESPACES};
+
+    /// Verify that events written by the `RecordingEventHandler` are replayed unchanged and in
+    /// order by the `ReplayEventConnection`, and that the replay ends cleanly.
+    #[test]
+    fn record_and_replay_events() {
+        let path = env::temp_dir().join(format!("grid-replay-test-{}", std::process::id()));
+        let path = path.to_str().expect("temp path is not valid utf-8");
+        let _ = fs::remove_file(path);
+
+        let events = vec![
+            CommitEvent {
+                service_id: None,
+                id: "block-1".into(),
+                height: Some(1),
+                state_changes: vec![
+                    StateChange::Set {
+                        key: "cad11d00abcd".into(),
+                        value: vec![0x01, 0x02],
+                        provenance: Some(Provenance {
+                            transaction_id: "txn-1".into(),
+                            signer: "signer-1".into(),
+                        }),
+                    },
+                    StateChange::Delete {
+                        key: "621dee02abcd".into(),
+                        provenance: None,
+                    },
+                ],
+            },
+            CommitEvent {
+                service_id: Some("circuit::service".into()),
+                id: "commit-2".into(),
+                height: None,
+                state_changes: vec![StateChange::Set {
+                    key: "a43b46ec1234".into(),
+                    value: vec![],
+                    provenance: None,
+                }],
+            },
+        ];
+
+        let handler = RecordingEventHandler::open(path).expect("Unable to open recording");
+        for event in &events {
+            handler.handle_event(event).expect("Unable to record event");
+        }
+
+        let mut connection = ReplayEventConnection::open(path).expect("Unable to open replay");
+        connection
+            .subscribe(ALL_GRID_NAMESPACES, None)
+            .expect("Unable to subscribe");
+
+        for expected in &events {
+            let replayed = connection.recv().expect("Unable to replay event");
+            assert_eq!(replayed.service_id, expected.service_id);
+            assert_eq!(replayed.id, expected.id);
+            assert_eq!(replayed.height, expected.height);
+            assert!(replayed.state_changes == expected.state_changes);
+        }
+
+        match connection.recv() {
+            Err(EventIoError::EndOfStream(_)) => (),
+            _ => panic!("Expected replay to end after the recorded events"),
+        }
+
+        let mut connection = ReplayEventConnection::open(path).expect("Unable to open replay");
+        connection
+            .subscribe(ALL_GRID_NAMESPACES, Some("block-1"))
+            .expect("Unable to subscribe");
+        let replayed = connection.recv().expect("Unable to replay event");
+        assert_eq!(replayed.id, "commit-2");
+
+        fs::remove_file(path).expect("Unable to remove recording");
+    }
+
+    /// Verify that the `ReplayEventConnection` resumes each source after its own checkpoint,
+    /// ignores checkpoints of sources that were not recorded, and fails to subscribe if the
+    /// commit of a checkpoint is not in the recording.
+    #[test]
+    fn replay_resumes_each_source_from_its_checkpoint() {
+        let path = env::temp_dir().join(format!(
+            "grid-replay-checkpoint-test-{}",
+            std::process::id()
+        ));
+        let path = path.to_str().expect("temp path is not valid utf-8");
+        let _ = fs::remove_file(path);
+
+        let commit = |service_id: &str, id: &str| CommitEvent {
+            service_id: Some(service_id.into()),
+            id: id.into(),
+            height: None,
+            state_changes: vec![],
+        };
+        let checkpoint = |service_id: &str, commit_id: &str| Checkpoint {
+            service_id: Some(service_id.into()),
+            commit_id: commit_id.into(),
+            commit_num: 0,
+        };
+
+        let handler = RecordingEventHandler::open(path).expect("Unable to open recording");
+        for event in &[
+            commit("circuit::a", "a-1"),
+            commit("circuit::b", "b-1"),
+            commit("circuit::a", "a-2"),
+            commit("circuit::b", "b-2"),
+        ] {
+            handler.handle_event(event).expect("Unable to record event");
+        }
+
+        let mut connection = ReplayEventConnection::open(path)
+            .expect("Unable to open replay")
+            .with_checkpoints(vec![
+                checkpoint("circuit::a", "a-1"),
+                checkpoint("circuit::c", "c-1"),
+            ]);
+        connection
+            .subscribe(ALL_GRID_NAMESPACES, None)
+            .expect("Unable to subscribe");
+        let replayed = (0..3)
+            .map(|_| connection.recv().expect("Unable to replay event").id)
+            .collect::<Vec<_>>();
+        assert_eq!(replayed, vec!["b-1", "a-2", "b-2"]);
+
+        let mut connection = ReplayEventConnection::open(path)
+            .expect("Unable to open replay")
+            .with_checkpoints(vec![checkpoint("circuit::b", "b-3")]);
+        match connection.subscribe(ALL_GRID_NAMESPACES, None) {
+            Err(EventIoError::ConnectionError(_)) => (),
+            _ => panic!("Expected subscribing from an unrecorded checkpoint to fail"),
+        }
+
+        fs::remove_file(path).expect("Unable to remove recording");
+    }
+
+    /// Verify that the `ReplayEventConnection` only replays the commits of sources within its
+    /// scope, and does not require the checkpoints of the other sources to be recorded.
+    #[test]
+    fn replay_skips_sources_outside_of_scope() {
+        let path = env::temp_dir().join(format!("grid-replay-scope-test-{}", std::process::id()));
+        let path = path.to_str().expect("temp path is not valid utf-8");
+        let _ = fs::remove_file(path);
+
+        let commit = |service_id: &str, id: &str| CommitEvent {
+            service_id: Some(service_id.into()),
+            id: id.into(),
+            height: None,
+            state_changes: vec![],
+        };
+
+        let handler = RecordingEventHandler::open(path).expect("Unable to open recording");
+        for event in &[
+            commit("circuit::a", "a-1"),
+            commit("other::b", "b-1"),
+            commit("circuit::a", "a-2"),
+        ] {
+            handler.handle_event(event).expect("Unable to record event");
+        }
+
+        let mut connection = ReplayEventConnection::open(path)
+            .expect("Unable to open replay")
+            .with_scope(EventScope::new(
+                ALL_ENTITY_FAMILIES.to_vec(),
+                Some(vec!["circuit".into()]),
+            ))
+            .with_checkpoints(vec![Checkpoint {
+                service_id: Some("other::b".into()),
+                commit_id: "b-2".into(),
+                commit_num: 0,
+            }]);
+        connection
+            .subscribe(ALL_GRID_NAMESPACES, None)
+            .expect("Unable to subscribe");
+        let replayed = (0..2)
+            .map(|_| connection.recv().expect("Unable to replay event").id)
+            .collect::<Vec<_>>();
+        assert_eq!(replayed, vec!["a-1", "a-2"]);
+
+        match connection.recv() {
+            Err(EventIoError::EndOfStream(_)) => (),
+            _ => panic!("Expected replay to end after the commits in scope"),
+        }
+
+        fs::remove_file(path).expect("Unable to remove recording");
+    }
+}
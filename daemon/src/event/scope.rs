@@ -0,0 +1,200 @@
+/*
+ * Copyright 2020 Cargill Incorporated
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ * -----------------------------------------------------------------------------
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use grid_sdk::protocol::addressing::{
+    GRID_LOCATION_PREFIX, GRID_PRODUCT_PREFIX, GRID_SCHEMA_PREFIX, PIKE_AGENT_PREFIX,
+    PIKE_ORGANIZATION_PREFIX, TRACK_AND_TRACE_NAMESPACE,
+};
+
+use super::StateChange;
+
+/// A family of Grid entities that share an address prefix
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EntityFamily {
+    Agent,
+    Organization,
+    Schema,
+    Product,
+    Location,
+    TrackAndTrace,
+}
+
+pub const ALL_ENTITY_FAMILIES: &[EntityFamily] = &[
+    EntityFamily::Agent,
+    EntityFamily::Organization,
+    EntityFamily::Schema,
+    EntityFamily::Product,
+    EntityFamily::Location,
+    EntityFamily::TrackAndTrace,
+];
+
+impl EntityFamily {
+    /// Returns the address prefix of every entity in this family
+    pub fn prefix(self) -> &'static str {
+        match self {
+            EntityFamily::Agent => PIKE_AGENT_PREFIX,
+            EntityFamily::Organization => PIKE_ORGANIZATION_PREFIX,
+            EntityFamily::Schema => GRID_SCHEMA_PREFIX,
+            EntityFamily::Product => GRID_PRODUCT_PREFIX,
+            EntityFamily::Location => GRID_LOCATION_PREFIX,
+            EntityFamily::TrackAndTrace => TRACK_AND_TRACE_NAMESPACE,
+        }
+    }
+}
+
+impl fmt::Display for EntityFamily {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            EntityFamily::Agent => "agent",
+            EntityFamily::Organization => "organization",
+            EntityFamily::Schema => "schema",
+            EntityFamily::Product => "product",
+            EntityFamily::Location => "location",
+            EntityFamily::TrackAndTrace => "track_and_trace",
+        })
+    }
+}
+
+impl FromStr for EntityFamily {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "agent" => Ok(EntityFamily::Agent),
+            "organization" => Ok(EntityFamily::Organization),
+            "schema" => Ok(EntityFamily::Schema),
+            "product" => Ok(EntityFamily::Product),
+            "location" => Ok(EntityFamily::Location),
+            "track_and_trace" => Ok(EntityFamily::TrackAndTrace),
+            _ => Err(format!("unknown entity family: {}", s)),
+        }
+    }
+}
+
+/// Describes which entity families and which sources an event processor ingests.
+///
+/// Sources are given either as a circuit ID, which includes every service on that circuit, or as
+/// a fully-qualified `circuit_id::service_id`. Sources only apply to Splinter; Sawtooth events are
+/// always in scope.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventScope {
+    families: Vec<EntityFamily>,
+    services: Option<Vec<String>>,
+}
+
+impl Default for EventScope {
+    fn default() -> Self {
+        Self {
+            families: ALL_ENTITY_FAMILIES.to_vec(),
+            services: None,
+        }
+    }
+}
+
+impl EventScope {
+    pub fn new(families: Vec<EntityFamily>, services: Option<Vec<String>>) -> Self {
+        Self { families, services }
+    }
+
+    pub fn families(&self) -> &[EntityFamily] {
+        &self.families
+    }
+
+    pub fn services(&self) -> Option<&[String]> {
+        self.services.as_deref()
+    }
+
+    /// Returns the address prefixes to subscribe to
+    pub fn namespaces(&self) -> Vec<&'static str> {
+        self.families.iter().map(|family| family.prefix()).collect()
+    }
+
+    pub fn includes_state_change(&self, state_change: &StateChange) -> bool {
+        self.families
+            .iter()
+            .any(|family| state_change.key_has_prefix(family.prefix()))
+    }
+
+    pub fn includes_service(&self, service_id: Option<&str>) -> bool {
+        match (service_id, &self.services) {
+            (Some(service_id), Some(services)) => services.iter().any(|source| {
+                service_id == source || service_id.starts_with(&format!("{}::", source))
+            }),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the default scope includes every Grid state change and every service.
+    #[test]
+    fn default_scope_includes_everything() {
+        let scope = EventScope::default();
+
+        for family in ALL_ENTITY_FAMILIES {
+            assert!(scope.includes_state_change(&StateChange::Delete {
+                key: format!("{}0000", family.prefix()),
+                provenance: None,
+            }));
+        }
+        assert!(scope.includes_service(None));
+        assert!(scope.includes_service(Some("circuit::service")));
+    }
+
+    /// Verify that a restricted scope only includes the configured families and sources, and that
+    /// circuit IDs match every service on the circuit.
+    #[test]
+    fn restricted_scope() {
+        let scope = EventScope::new(
+            vec![EntityFamily::Product, EntityFamily::Location],
+            Some(vec!["abcde-01234".into(), "fghij-56789::gsAA".into()]),
+        );
+
+        assert_eq!(
+            scope.namespaces(),
+            vec![GRID_PRODUCT_PREFIX, GRID_LOCATION_PREFIX]
+        );
+        assert!(scope.includes_state_change(&StateChange::Delete {
+            key: format!("{}0000", GRID_PRODUCT_PREFIX),
+            provenance: None,
+        }));
+        assert!(!scope.includes_state_change(&StateChange::Delete {
+            key: format!("{}0000", PIKE_AGENT_PREFIX),
+            provenance: None,
+        }));
+
+        assert!(scope.includes_service(None));
+        assert!(scope.includes_service(Some("abcde-01234::gsBB")));
+        assert!(scope.includes_service(Some("fghij-56789::gsAA")));
+        assert!(!scope.includes_service(Some("fghij-56789::gsBB")));
+        assert!(!scope.includes_service(Some("abcde-012345::gsAA")));
+    }
+
+    /// Verify that entity families are parsed case-insensitively and unknown names are rejected.
+    #[test]
+    fn parse_entity_family() {
+        assert_eq!("Product".parse(), Ok(EntityFamily::Product));
+        assert_eq!("track_and_trace".parse(), Ok(EntityFamily::TrackAndTrace));
+        assert!("widget".parse::<EntityFamily>().is_err());
+    }
+}
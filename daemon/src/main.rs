@@ -48,9 +48,15 @@ use flexi_logger::{LogSpecBuilder, Logger};
 use crate::config::{GridConfig, GridConfigBuilder};
 use crate::database::{ConnectionPool, DatabaseError};
 use crate::error::DaemonError;
-#[cfg(feature = "splinter-support")]
-use crate::event::EventHandler;
-use crate::event::{db_handler::DatabaseEventHandler, EventProcessor};
+#[cfg(feature = "kafka-publisher")]
+use crate::event::publisher::{
+    kafka::KafkaBroker, PublishingEventHandler, RemovedEntityLookup, StoreLookup,
+};
+use crate::event::{
+    db_handler::DatabaseEventHandler,
+    recording::{RecordingEventHandler, ReplayEventConnection},
+    EventHandler, EventProcessor,
+};
 #[cfg(feature = "sawtooth-support")]
 use crate::sawtooth::{batch_submitter::SawtoothBatchSubmitter, connection::SawtoothConnection};
 #[cfg(feature = "splinter-support")]
@@ -76,7 +82,18 @@ fn run() -> Result<(), DaemonError> {
         (@arg database_url: --("database-url") +takes_value
          "specifies the database URL to connect to.")
         (@arg bind: -b --bind +takes_value "connection endpoint for rest API")
-        (@arg admin_key_dir: --("admin-key-dir") +takes_value "directory containing the Scabbard admin key files"))
+        (@arg admin_key_dir: --("admin-key-dir") +takes_value "directory containing the Scabbard admin key files")
+        (@arg entities: --entities +takes_value +use_delimiter
+         "comma-separated list of entity families to ingest: agent, organization, schema, product, location, track_and_trace")
+        (@arg services: --services +takes_value +use_delimiter
+         "comma-separated list of circuits or circuit::service IDs to ingest (Splinter only)")
+        (@arg record: --record +takes_value "record all received commit events to the given file")
+        (@arg publish_brokers: --("publish-brokers") +takes_value +use_delimiter
+         "comma-separated list of Kafka brokers to publish decoded Grid events to")
+        (@arg publish_topic: --("publish-topic") +takes_value
+         "topic to publish decoded Grid events to (default: grid-events)")
+        (@arg replay: --replay +takes_value conflicts_with[connect]
+         "replay the commit events recorded in the given file into the database, then exit"))
     .get_matches();
 
     let log_level = match matches.occurrences_of("verbose") {
@@ -96,7 +113,9 @@ fn run() -> Result<(), DaemonError> {
         .with_cli_args(&matches)
         .build()?;
 
-    if config.endpoint().is_sawtooth() {
+    if config.replay_file().is_some() {
+        run_replay(config)?;
+    } else if config.endpoint().is_sawtooth() {
         run_sawtooth(config)?;
     } else if config.endpoint().is_splinter() {
         run_splinter(config)?;
@@ -110,6 +129,138 @@ fn run() -> Result<(), DaemonError> {
     Ok(())
 }
 
+fn open_recording(config: &GridConfig) -> Result<Option<RecordingEventHandler>, DaemonError> {
+    config
+        .record_file()
+        .map(RecordingEventHandler::open)
+        .transpose()
+        .map_err(|err| DaemonError::StartUpError(Box::new(err)))
+}
+
+#[cfg(feature = "kafka-publisher")]
+fn open_publisher(
+    config: &GridConfig,
+) -> Result<Option<Box<dyn EventHandler + Sync>>, DaemonError> {
+    let brokers = match config.publish_brokers() {
+        Some(brokers) => brokers,
+        None => return Ok(None),
+    };
+    let broker = KafkaBroker::connect(brokers.to_vec())
+        .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
+
+    Ok(Some(Box::new(PublishingEventHandler::new(
+        Box::new(broker),
+        config.publish_topic(),
+        config.event_scope().clone(),
+        open_removed_entity_lookup(config)?,
+    ))))
+}
+
+#[cfg(feature = "kafka-publisher")]
+fn open_removed_entity_lookup(
+    config: &GridConfig,
+) -> Result<Box<dyn RemovedEntityLookup>, DaemonError> {
+    let connection_uri = config
+        .database_url()
+        .parse()
+        .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
+
+    match connection_uri {
+        ConnectionUri::Postgres(_) => {
+            let connection_pool: ConnectionPool<diesel::pg::PgConnection> =
+                ConnectionPool::new(config.database_url())?;
+            Ok(Box::new(StoreLookup::new(&connection_pool)))
+        }
+        ConnectionUri::Sqlite(_) | ConnectionUri::Memory => {
+            let connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection> =
+                ConnectionPool::new(config.database_url())?;
+            Ok(Box::new(StoreLookup::new(&connection_pool)))
+        }
+    }
+}
+
+#[cfg(not(feature = "kafka-publisher"))]
+fn open_publisher(
+    config: &GridConfig,
+) -> Result<Option<Box<dyn EventHandler + Sync>>, DaemonError> {
+    match config.publish_brokers() {
+        Some(_) => Err(DaemonError::ConfigurationError(Box::new(
+            crate::error::ConfigurationError::InvalidValue(
+                "Brokers to publish to were provided but Kafka support is not enabled for this \
+                 binary."
+                    .into(),
+            ),
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn run_replay(config: GridConfig) -> Result<(), DaemonError> {
+    let connection_uri = config
+        .database_url()
+        .parse()
+        .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
+
+    // Commits the database already holds are not applied again
+    let checkpoints = create_store_factory(&connection_uri)
+        .map_err(|err| DaemonError::StartUpError(Box::new(err)))?
+        .get_grid_commit_store()
+        .list_checkpoints()
+        .map_err(|err| DatabaseError::ConnectionError {
+            context: "Could not list checkpoints".to_string(),
+            source: Box::new(err),
+        })?;
+
+    let replay_file = config.replay_file().unwrap_or_default();
+    let replay_connection = ReplayEventConnection::open(replay_file)
+        .map_err(|err| DaemonError::StartUpError(Box::new(err)))?
+        .with_scope(config.event_scope().clone())
+        .with_checkpoints(checkpoints);
+
+    let evt_processor = match connection_uri {
+        ConnectionUri::Postgres(_) => {
+            let connection_pool: ConnectionPool<diesel::pg::PgConnection> =
+                ConnectionPool::new(config.database_url())?;
+            EventProcessor::start(
+                replay_connection,
+                None,
+                config.event_scope(),
+                event_handlers![DatabaseEventHandler::from_pg_pool(
+                    connection_pool,
+                    config.event_scope().clone(),
+                )],
+            )
+        }
+        ConnectionUri::Sqlite(_) | ConnectionUri::Memory => {
+            let connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection> =
+                ConnectionPool::new(config.database_url())?;
+            EventProcessor::start(
+                replay_connection,
+                None,
+                config.event_scope(),
+                event_handlers![DatabaseEventHandler::from_sqlite_pool(
+                    connection_pool,
+                    config.event_scope().clone(),
+                )],
+            )
+        }
+    }
+    .map_err(|err| DaemonError::EventProcessorError(Box::new(err)))?;
+
+    let (_, event_processor_join_handle) = evt_processor.take_shutdown_controls();
+
+    event_processor_join_handle
+        .join()
+        .map_err(|_| {
+            DaemonError::ShutdownError("Unable to cleanly join the event processor".into())
+        })
+        .and_then(|res| res.map_err(DaemonError::from))?;
+
+    info!("Finished replaying events from {}", replay_file);
+
+    Ok(())
+}
+
 #[cfg(feature = "sawtooth-support")]
 fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
     let connection_uri = config
@@ -124,24 +275,39 @@ fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
     let batch_submitter = Box::new(SawtoothBatchSubmitter::new(
         sawtooth_connection.get_sender(),
     ));
+    let recording_handler = open_recording(&config)?;
+    let mut event_handlers: Vec<Box<dyn EventHandler>> = recording_handler
+        .iter()
+        .map(|handler| handler.cloned_box())
+        .collect();
+    // The publisher must precede the database handler, which advances the checkpoint
+    if let Some(publishing_handler) = open_publisher(&config)? {
+        event_handlers.push(publishing_handler);
+    }
+
     let (db_executor, evt_processor) = {
         let commit_store = store_factory.get_grid_commit_store();
-        let current_commit =
-            commit_store
-                .get_current_commit_id()
-                .map_err(|err| DatabaseError::ConnectionError {
-                    context: "Could not get current commit ID".to_string(),
-                    source: Box::new(err),
-                })?;
+        let current_commit = commit_store
+            .get_checkpoint(None)
+            .map_err(|err| DatabaseError::ConnectionError {
+                context: "Could not get checkpoint".to_string(),
+                source: Box::new(err),
+            })?
+            .map(|checkpoint| checkpoint.commit_id);
 
         match connection_uri {
             ConnectionUri::Postgres(_) => {
                 let connection_pool: ConnectionPool<diesel::pg::PgConnection> =
                     ConnectionPool::new(config.database_url())?;
+                event_handlers.push(Box::new(DatabaseEventHandler::from_pg_pool(
+                    connection_pool.clone(),
+                    config.event_scope().clone(),
+                )));
                 let evt_processor = EventProcessor::start(
                     sawtooth_connection,
                     current_commit.as_deref(),
-                    event_handlers![DatabaseEventHandler::from_pg_pool(connection_pool.clone())],
+                    config.event_scope(),
+                    event_handlers,
                 )
                 .map_err(|err| DaemonError::EventProcessorError(Box::new(err)))?;
 
@@ -153,12 +319,15 @@ fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
             ConnectionUri::Sqlite(_) | ConnectionUri::Memory => {
                 let connection_pool: ConnectionPool<diesel::sqlite::SqliteConnection> =
                     ConnectionPool::new(config.database_url())?;
+                event_handlers.push(Box::new(DatabaseEventHandler::from_sqlite_pool(
+                    connection_pool.clone(),
+                    config.event_scope().clone(),
+                )));
                 let evt_processor = EventProcessor::start(
                     sawtooth_connection,
                     current_commit.as_deref(),
-                    event_handlers![DatabaseEventHandler::from_sqlite_pool(
-                        connection_pool.clone()
-                    )],
+                    config.event_scope(),
+                    event_handlers,
                 )
                 .map_err(|err| DaemonError::EventProcessorError(Box::new(err)))?;
 
@@ -175,6 +344,7 @@ fn run_sawtooth(config: GridConfig) -> Result<(), DaemonError> {
         db_executor,
         batch_submitter,
         config.endpoint().clone(),
+        config.event_scope().clone(),
     )?;
 
     let (event_processor_shutdown_handle, event_processor_join_handle) =
@@ -232,18 +402,35 @@ fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
     let scabbard_event_connection_factory =
         ScabbardEventConnectionFactory::new(&config.endpoint().url(), reactor.igniter());
 
+    let mut event_handlers: Vec<Box<dyn EventHandler + Sync + 'static>> = vec![];
+    if let Some(recording_handler) = open_recording(&config)? {
+        event_handlers.push(Box::new(recording_handler));
+    }
+    // The publisher must precede the database handler, which advances the checkpoint
+    if let Some(publishing_handler) = open_publisher(&config)? {
+        event_handlers.push(publishing_handler);
+    }
+
+    let connection_uri = config
+        .database_url()
+        .parse()
+        .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
+
+    let commit_store = create_store_factory(&connection_uri)
+        .map_err(|err| DaemonError::StartUpError(Box::new(err)))?
+        .get_grid_commit_store();
+
     let (db_executor, db_handler): (rest_api::DbExecutor, Box<dyn EventHandler + Sync + 'static>) = {
-        let connection_uri = config
-            .database_url()
-            .parse()
-            .map_err(|err| DaemonError::StartUpError(Box::new(err)))?;
         match connection_uri {
             ConnectionUri::Postgres(_) => {
                 let connection_pool: ConnectionPool<diesel::pg::PgConnection> =
                     ConnectionPool::new(config.database_url())?;
                 (
                     rest_api::DbExecutor::from_pg_pool(connection_pool.clone()),
-                    Box::new(DatabaseEventHandler::from_pg_pool(connection_pool)),
+                    Box::new(DatabaseEventHandler::from_pg_pool(
+                        connection_pool,
+                        config.event_scope().clone(),
+                    )),
                 )
             }
             ConnectionUri::Sqlite(_) | ConnectionUri::Memory => {
@@ -251,16 +438,23 @@ fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
                     ConnectionPool::new(config.database_url())?;
                 (
                     rest_api::DbExecutor::from_sqlite_pool(connection_pool.clone()),
-                    Box::new(DatabaseEventHandler::from_sqlite_pool(connection_pool)),
+                    Box::new(DatabaseEventHandler::from_sqlite_pool(
+                        connection_pool,
+                        config.event_scope().clone(),
+                    )),
                 )
             }
         }
     };
 
+    event_handlers.push(db_handler);
+
     app_auth_handler::run(
         config.endpoint().url(),
         scabbard_event_connection_factory,
-        db_handler,
+        config.event_scope().clone(),
+        commit_store,
+        event_handlers,
         reactor.igniter(),
         scabbard_admin_key,
     )?;
@@ -272,6 +466,7 @@ fn run_splinter(config: GridConfig) -> Result<(), DaemonError> {
         db_executor,
         batch_submitter,
         config.endpoint().clone(),
+        config.event_scope().clone(),
     )?;
 
     let reactor_shutdown_signaler = reactor.shutdown_signaler();
@@ -19,11 +19,15 @@ use std::sync::mpsc;
 use std::thread;
 
 use crate::config::Endpoint;
+use crate::event::EventScope;
 pub use crate::rest_api::error::RestApiServerError;
 use crate::rest_api::routes::{
-    fetch_agent, fetch_grid_schema, fetch_location, fetch_organization, fetch_product,
-    fetch_record, fetch_record_property, get_batch_statuses, list_agents, list_grid_schemas,
-    list_locations, list_organizations, list_products, list_records, submit_batches,
+    aggregate_record_property, fetch_agent, fetch_grid_schema, fetch_location, fetch_organization,
+    fetch_product, fetch_record, fetch_record_lineage, fetch_record_property,
+    fetch_record_property_route, fetch_role, get_batch_statuses, get_status, list_agents,
+    list_child_locations, list_grid_schemas, list_location_ancestors, list_locations,
+    list_nearby_locations, list_organizations, list_product_status_history, list_products,
+    list_records, list_records_within, list_roles, submit_batches, StatusSlice,
 };
 
 use crate::submitter::BatchSubmitter;
@@ -125,6 +129,7 @@ pub fn run(
     db_executor: DbExecutor,
     batch_submitter: Box<dyn BatchSubmitter + 'static>,
     endpoint: Endpoint,
+    scope: EventScope,
 ) -> Result<
     (
         RestApiShutdownHandle,
@@ -134,6 +139,7 @@ pub fn run(
 > {
     let bind_url = bind_url.to_owned();
     let (tx, rx) = mpsc::channel();
+    let status = StatusSlice::from(&scope);
 
     let join_handle = thread::Builder::new()
         .name("GridRestApi".into())
@@ -145,6 +151,8 @@ pub fn run(
                 App::new()
                     .data(state.clone())
                     .app_data(endpoint.clone())
+                    .data(status.clone())
+                    .service(web::resource("/status").route(web::get().to(get_status)))
                     .service(web::resource("/batches").route(web::post().to(submit_batches)))
                     .service(
                         web::resource("/batch_statuses")
@@ -161,7 +169,18 @@ pub fn run(
                     .service(
                         web::scope("/location")
                             .service(web::resource("").route(web::get().to(list_locations)))
-                            .service(web::resource("/{id}").route(web::get().to(fetch_location))),
+                            .service(
+                                web::scope("/{id}")
+                                    .service(web::resource("").route(web::get().to(fetch_location)))
+                                    .service(
+                                        web::resource("/children")
+                                            .route(web::get().to(list_child_locations)),
+                                    )
+                                    .service(
+                                        web::resource("/ancestors")
+                                            .route(web::get().to(list_location_ancestors)),
+                                    ),
+                            ),
                     )
                     .service(
                         web::scope("/organization")
@@ -170,10 +189,24 @@ pub fn run(
                                 web::resource("/{id}").route(web::get().to(fetch_organization)),
                             ),
                     )
+                    .service(
+                        web::scope("/role")
+                            .service(web::resource("").route(web::get().to(list_roles)))
+                            .service(
+                                web::resource("/{org_id}/{name}").route(web::get().to(fetch_role)),
+                            ),
+                    )
                     .service(
                         web::scope("/product")
                             .service(web::resource("").route(web::get().to(list_products)))
-                            .service(web::resource("/{id}").route(web::get().to(fetch_product))),
+                            .service(
+                                web::scope("/{id}")
+                                    .service(web::resource("").route(web::get().to(fetch_product)))
+                                    .service(
+                                        web::resource("/status-history")
+                                            .route(web::get().to(list_product_status_history)),
+                                    ),
+                            ),
                     )
                     .service(
                         web::scope("/schema")
@@ -188,12 +221,34 @@ pub fn run(
                             .service(
                                 web::scope("/{record_id}")
                                     .service(web::resource("").route(web::get().to(fetch_record)))
+                                    .service(
+                                        web::resource("/lineage")
+                                            .route(web::get().to(fetch_record_lineage)),
+                                    )
                                     .service(
                                         web::resource("/property/{property_name}")
                                             .route(web::get().to(fetch_record_property)),
+                                    )
+                                    .service(
+                                        web::resource("/property/{property_name}/aggregate")
+                                            .route(web::get().to(aggregate_record_property)),
+                                    )
+                                    .service(
+                                        web::resource("/property/{property_name}/route")
+                                            .route(web::get().to(fetch_record_property_route)),
                                     ),
                             ),
                     )
+                    .service(
+                        web::scope("/geo")
+                            .service(
+                                web::resource("/locations")
+                                    .route(web::get().to(list_nearby_locations)),
+                            )
+                            .service(
+                                web::resource("/records").route(web::get().to(list_records_within)),
+                            ),
+                    )
             })
             .bind(bind_url)?
             .disable_signals()
@@ -34,6 +34,12 @@ pub struct AgentSlice {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 impl TryFrom<Agent> for AgentSlice {
@@ -57,6 +63,8 @@ impl TryFrom<Agent> for AgentSlice {
             roles: agent.roles.clone(),
             metadata,
             service_id: agent.service_id,
+            transaction_id: agent.transaction_id,
+            signer: agent.signer,
         })
     }
 }
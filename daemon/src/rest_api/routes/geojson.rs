@@ -0,0 +1,65 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! GeoJSON (RFC 7946) documents returned by the spatial queries. Positions are longitude and
+//! latitude pairs in degrees, converted from the micro-degrees Grid stores.
+
+use grid_sdk::grid_db::geo::to_degrees;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum Geometry {
+    Point { coordinates: [f64; 2] },
+    LineString { coordinates: Vec<[f64; 2]> },
+}
+
+impl Geometry {
+    /// Creates a point from a latitude and longitude in micro-degrees
+    pub fn point(latitude: i64, longitude: i64) -> Self {
+        Geometry::Point {
+            coordinates: position(latitude, longitude),
+        }
+    }
+
+    /// Creates a line through latitudes and longitudes in micro-degrees
+    pub fn line_string<I>(lat_longs: I) -> Self
+    where
+        I: IntoIterator<Item = (i64, i64)>,
+    {
+        Geometry::LineString {
+            coordinates: lat_longs
+                .into_iter()
+                .map(|(latitude, longitude)| position(latitude, longitude))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub struct Feature<P> {
+    pub geometry: Geometry,
+    pub properties: P,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub struct FeatureCollection<P> {
+    pub features: Vec<Feature<P>>,
+}
+
+fn position(latitude: i64, longitude: i64) -> [f64; 2] {
+    [to_degrees(longitude), to_degrees(latitude)]
+}
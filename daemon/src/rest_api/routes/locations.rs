@@ -16,9 +16,13 @@ use crate::rest_api::{
     error::RestApiResponseError, routes::DbExecutor, AcceptServiceIdParam, AppState, QueryServiceId,
 };
 
+use crate::rest_api::routes::{Feature, FeatureCollection, Geometry};
 use actix::{Handler, Message, SyncContext};
 use actix_web::{web, HttpResponse};
-use grid_sdk::grid_db::locations::store::{LatLongValue, Location, LocationAttribute};
+use grid_sdk::grid_db::geo::is_valid_lat_long;
+use grid_sdk::grid_db::locations::store::{
+    LatLongValue, Location, LocationAttribute, NearbyLocation,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,10 +30,23 @@ pub struct LocationSlice {
     pub location_id: String,
     pub location_namespace: String,
     pub owner: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pending_owner: String,
+    pub schema_version: i32,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_location_id: Option<String>,
     pub properties: Vec<LocationPropertyValueSlice>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 impl From<Location> for LocationSlice {
@@ -38,12 +55,17 @@ impl From<Location> for LocationSlice {
             location_id: location.location_id,
             location_namespace: location.location_namespace,
             owner: location.owner,
+            pending_owner: location.pending_owner,
+            schema_version: location.schema_version,
+            parent_location_id: location.parent_location_id,
             properties: location
                 .attributes
                 .into_iter()
                 .map(LocationPropertyValueSlice::from)
                 .collect(),
             service_id: location.service_id,
+            transaction_id: location.transaction_id,
+            signer: location.signer,
         }
     }
 }
@@ -62,6 +84,12 @@ pub struct LocationPropertyValueSlice {
     pub enum_value: Option<i32>,
     pub struct_values: Option<Vec<LocationPropertyValueSlice>>,
     pub lat_long_value: Option<LatLongSlice>,
+    #[serde(default)]
+    pub timestamp_value: Option<i64>,
+    #[serde(default)]
+    pub decimal_value: Option<String>,
+    #[serde(default)]
+    pub list_values: Option<Vec<LocationPropertyValueSlice>>,
 }
 
 impl From<LocationAttribute> for LocationPropertyValueSlice {
@@ -82,6 +110,14 @@ impl From<LocationAttribute> for LocationPropertyValueSlice {
                     .collect()
             }),
             lat_long_value: attribute.lat_long_value.map(LatLongSlice::from),
+            timestamp_value: attribute.timestamp_value,
+            decimal_value: attribute.decimal_value,
+            list_values: attribute.list_values.map(|attrs| {
+                attrs
+                    .into_iter()
+                    .map(LocationPropertyValueSlice::from)
+                    .collect()
+            }),
         }
     }
 }
@@ -177,3 +213,205 @@ pub async fn fetch_location(
         .await?
         .map(|location| HttpResponse::Ok().json(location))
 }
+
+struct ListChildLocations {
+    location_id: String,
+    service_id: Option<String>,
+}
+
+impl Message for ListChildLocations {
+    type Result = Result<Vec<LocationSlice>, RestApiResponseError>;
+}
+
+impl Handler<ListChildLocations> for DbExecutor {
+    type Result = Result<Vec<LocationSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListChildLocations, _: &mut SyncContext<Self>) -> Self::Result {
+        let service_id = msg.service_id.as_deref();
+        if self
+            .location_store
+            .fetch_location(&msg.location_id, service_id)?
+            .is_none()
+        {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find location with id: {}",
+                msg.location_id
+            )));
+        }
+
+        Ok(self
+            .location_store
+            .list_child_locations(&msg.location_id, service_id)?
+            .into_iter()
+            .map(LocationSlice::from)
+            .collect())
+    }
+}
+
+pub async fn list_child_locations(
+    state: web::Data<AppState>,
+    location_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(ListChildLocations {
+            location_id: location_id.into_inner(),
+            service_id: query.into_inner().service_id,
+        })
+        .await?
+        .map(|locations| HttpResponse::Ok().json(locations))
+}
+
+struct ListLocationAncestors {
+    location_id: String,
+    service_id: Option<String>,
+}
+
+impl Message for ListLocationAncestors {
+    type Result = Result<Vec<LocationSlice>, RestApiResponseError>;
+}
+
+impl Handler<ListLocationAncestors> for DbExecutor {
+    type Result = Result<Vec<LocationSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListLocationAncestors, _: &mut SyncContext<Self>) -> Self::Result {
+        let service_id = msg.service_id.as_deref();
+        if self
+            .location_store
+            .fetch_location(&msg.location_id, service_id)?
+            .is_none()
+        {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find location with id: {}",
+                msg.location_id
+            )));
+        }
+
+        Ok(self
+            .location_store
+            .list_location_ancestors(&msg.location_id, service_id)?
+            .into_iter()
+            .map(LocationSlice::from)
+            .collect())
+    }
+}
+
+/// Returns the path from a location up to its top-level location, starting with the parent
+pub async fn list_location_ancestors(
+    state: web::Data<AppState>,
+    location_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(ListLocationAncestors {
+            location_id: location_id.into_inner(),
+            service_id: query.into_inner().service_id,
+        })
+        .await?
+        .map(|locations| HttpResponse::Ok().json(locations))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct NearbyLocationProperties {
+    pub location_id: String,
+    pub location_namespace: String,
+    pub owner: String,
+    /// The name of the LatLong property nearest to the queried point
+    pub property_name: String,
+    /// The distance from the queried point, in meters
+    pub distance: f64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+impl From<NearbyLocation> for Feature<NearbyLocationProperties> {
+    fn from(nearby: NearbyLocation) -> Self {
+        Self {
+            geometry: Geometry::point(nearby.lat_long_value.0, nearby.lat_long_value.1),
+            properties: NearbyLocationProperties {
+                location_id: nearby.location.location_id,
+                location_namespace: nearby.location.location_namespace,
+                owner: nearby.location.owner,
+                property_name: nearby.property_name,
+                distance: nearby.distance,
+                service_id: nearby.location.service_id,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearbyLocationsQuery {
+    pub latitude: i64,
+    pub longitude: i64,
+    pub radius: f64,
+    pub service_id: Option<String>,
+}
+
+struct ListNearbyLocations {
+    latitude: i64,
+    longitude: i64,
+    radius: f64,
+    service_id: Option<String>,
+}
+
+impl Message for ListNearbyLocations {
+    type Result = Result<FeatureCollection<NearbyLocationProperties>, RestApiResponseError>;
+}
+
+impl Handler<ListNearbyLocations> for DbExecutor {
+    type Result = Result<FeatureCollection<NearbyLocationProperties>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListNearbyLocations, _: &mut SyncContext<Self>) -> Self::Result {
+        if !is_valid_lat_long(msg.latitude, msg.longitude) {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "Invalid position: {}, {}",
+                msg.latitude, msg.longitude
+            )));
+        }
+
+        if !msg.radius.is_finite() || msg.radius <= 0.0 {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "Invalid radius: {}",
+                msg.radius
+            )));
+        }
+
+        Ok(FeatureCollection {
+            features: self
+                .location_store
+                .list_locations_within(
+                    msg.latitude,
+                    msg.longitude,
+                    msg.radius,
+                    msg.service_id.as_deref(),
+                )?
+                .into_iter()
+                .map(Feature::from)
+                .collect(),
+        })
+    }
+}
+
+pub async fn list_nearby_locations(
+    state: web::Data<AppState>,
+    query: web::Query<NearbyLocationsQuery>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
+    state
+        .database_connection
+        .send(ListNearbyLocations {
+            latitude: query.latitude,
+            longitude: query.longitude,
+            radius: query.radius,
+            service_id: query.service_id,
+        })
+        .await?
+        .map(|locations| HttpResponse::Ok().json(locations))
+}
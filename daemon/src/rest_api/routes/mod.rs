@@ -22,19 +22,25 @@ use grid_sdk::grid_db::{
 
 mod agents;
 mod batches;
+mod geojson;
 mod locations;
 mod organizations;
 mod products;
 mod records;
+mod roles;
 mod schemas;
+mod status;
 
 pub use agents::*;
 pub use batches::*;
+pub use geojson::*;
 pub use locations::*;
 pub use organizations::*;
 pub use products::*;
 pub use records::*;
+pub use roles::*;
 pub use schemas::*;
+pub use status::*;
 
 use crate::database::ConnectionPool;
 
@@ -133,10 +139,18 @@ mod test {
         products::store::{diesel::DieselProductStore, Product, PropertyValue},
         schemas::store::{diesel::DieselSchemaStore, PropertyDefinition, Schema},
         track_and_trace::store::{
-            diesel::DieselTrackAndTraceStore, AssociatedAgent, LatLongValue, Property, Proposal,
-            Record, ReportedValue, Reporter,
+            diesel::DieselTrackAndTraceStore, AssociatedAgent, LatLongValue, PageReportedValue,
+            Property, PropertyPageSnapshot, Proposal, Record, RecordAggregation, ReportedValue,
+            Reporter,
         },
     };
+    use grid_sdk::protocol::addressing::{
+        compute_custom_product_address, compute_gs1_product_address,
+    };
+    use grid_sdk::protocol::schema::state::{
+        DataType as StateDataType, LatLongBuilder, PropertyValueBuilder,
+    };
+    use grid_sdk::protos::IntoBytes;
     use sawtooth_sdk::messages::validator::{Message, Message_MessageType};
 
     use sawtooth_sdk::messaging::stream::{MessageFuture, MessageSender, SendError};
@@ -371,15 +385,40 @@ mod test {
                         .service(web::resource("").route(web::get().to(list_organizations)))
                         .service(web::resource("/{id}").route(web::get().to(fetch_organization))),
                 )
+                .service(
+                    web::scope("/role")
+                        .service(web::resource("").route(web::get().to(list_roles)))
+                        .service(
+                            web::resource("/{org_id}/{name}").route(web::get().to(fetch_role)),
+                        ),
+                )
                 .service(
                     web::scope("/product")
                         .service(web::resource("").route(web::get().to(list_products)))
-                        .service(web::resource("/{id}").route(web::get().to(fetch_product))),
+                        .service(
+                            web::scope("/{id}")
+                                .service(web::resource("").route(web::get().to(fetch_product)))
+                                .service(
+                                    web::resource("/status-history")
+                                        .route(web::get().to(list_product_status_history)),
+                                ),
+                        ),
                 )
                 .service(
                     web::scope("/location")
                         .service(web::resource("").route(web::get().to(list_locations)))
-                        .service(web::resource("/{id}").route(web::get().to(fetch_location))),
+                        .service(
+                            web::scope("/{id}")
+                                .service(web::resource("").route(web::get().to(fetch_location)))
+                                .service(
+                                    web::resource("/children")
+                                        .route(web::get().to(list_child_locations)),
+                                )
+                                .service(
+                                    web::resource("/ancestors")
+                                        .route(web::get().to(list_location_ancestors)),
+                                ),
+                        ),
                 )
                 .service(
                     web::scope("/schema")
@@ -392,12 +431,33 @@ mod test {
                         .service(
                             web::scope("/{record_id}")
                                 .service(web::resource("").route(web::get().to(fetch_record)))
+                                .service(
+                                    web::resource("/lineage")
+                                        .route(web::get().to(fetch_record_lineage)),
+                                )
                                 .service(
                                     web::resource("/property/{property_name}")
                                         .route(web::get().to(fetch_record_property)),
+                                )
+                                .service(
+                                    web::resource("/property/{property_name}/aggregate")
+                                        .route(web::get().to(aggregate_record_property)),
+                                )
+                                .service(
+                                    web::resource("/property/{property_name}/route")
+                                        .route(web::get().to(fetch_record_property_route)),
                                 ),
                         ),
                 )
+                .service(
+                    web::scope("/geo")
+                        .service(
+                            web::resource("/locations").route(web::get().to(list_nearby_locations)),
+                        )
+                        .service(
+                            web::resource("/records").route(web::get().to(list_records_within)),
+                        ),
+                )
         })
     }
 
@@ -1348,7 +1408,10 @@ mod test {
 
         let test_product = body.first().unwrap();
         assert_eq!(test_product.product_id, "041205707820".to_string());
-        assert_eq!(test_product.product_address, "test_address".to_string());
+        assert_eq!(
+            test_product.product_address,
+            compute_gs1_product_address("041205707820")
+        );
         assert_eq!(test_product.product_namespace, "Grid Product".to_string());
         assert_eq!(test_product.owner, "phillips001".to_string());
         assert_eq!(test_product.properties.len(), 2);
@@ -1439,13 +1502,205 @@ mod test {
 
         let test_product = body.first().unwrap();
         assert_eq!(test_product.product_id, "041205707820".to_string());
-        assert_eq!(test_product.product_address, "test_address".to_string());
+        assert_eq!(
+            test_product.product_address,
+            compute_gs1_product_address("041205707820")
+        );
         assert_eq!(test_product.product_namespace, "Grid Product".to_string());
         assert_eq!(test_product.owner, "phillips001".to_string());
         assert_eq!(test_product.properties.len(), 2);
         assert_eq!(test_product.service_id, Some(TEST_SERVICE_ID.to_string()));
     }
 
+    /// Verifies a GET /product?product_namespace=...&schema_name=... responds with an OK
+    ///     response with a list_products request.
+    ///
+    ///     The TestServer will receive a request with namespace and schema filters,
+    ///         then will respond with an Ok status and the Products that match them.
+    #[actix_rt::test]
+    async fn test_list_products_with_filters() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        // Clears the product table in the test database
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        let mut products = get_product(None);
+        products.push(Product {
+            product_id: "LB-0001".to_string(),
+            product_address: compute_custom_product_address("lightbulb", "LB-0001"),
+            product_namespace: "Custom".to_string(),
+            schema_name: "lightbulb".to_string(),
+            owner: "phillips001".to_string(),
+            start_commit_num: 0,
+            end_commit_num: i64::MAX,
+            properties: vec![],
+            service_id: None,
+            transaction_id: None,
+            signer: None,
+            pending_owner: "".to_string(),
+            schema_version: 1,
+            status: "ACTIVE".to_string(),
+        });
+        populate_product_table(test_pool, products);
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/product?product_namespace=Custom&schema_name=lightbulb"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<ProductSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.len(), 1);
+        assert_eq!(body[0].product_id, "LB-0001".to_string());
+        assert_eq!(body[0].schema_name, "lightbulb".to_string());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product?schema_name=lamp"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<ProductSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(body.is_empty());
+    }
+
+    /// Verifies a GET /product?status=... responds with only the products in that lifecycle state
+    ///     and GET /product/{id}/status-history lists each status the product has been in.
+    ///
+    ///     The product is added as ACTIVE, updated without a status change, then discontinued.
+    #[actix_rt::test]
+    async fn test_product_status_filter_and_history() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        // Clears the product table in the test database
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        let mut products = get_product(None);
+        let mut updated = get_product(None);
+        updated[0].start_commit_num = 1;
+        updated[0].properties.iter_mut().for_each(|property| {
+            property.start_commit_num = 1;
+        });
+        let mut discontinued = get_product(None);
+        discontinued[0].start_commit_num = 2;
+        discontinued[0].status = "DISCONTINUED".to_string();
+        discontinued[0].properties.iter_mut().for_each(|property| {
+            property.start_commit_num = 2;
+        });
+        products.append(&mut updated);
+        products.append(&mut discontinued);
+        populate_product_table(test_pool, products);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product?status=DISCONTINUED"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<ProductSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.len(), 1);
+        assert_eq!(body[0].product_id, "041205707820".to_string());
+        assert_eq!(body[0].status, "DISCONTINUED".to_string());
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/product?status=ACTIVE"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<ProductSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(body.is_empty());
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/product/041205707820/status-history"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<ProductStatusChangeSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(
+            body.iter()
+                .map(|change| (change.status.as_str(), change.start_commit_num))
+                .collect::<Vec<_>>(),
+            vec![("ACTIVE", 0), ("DISCONTINUED", 2)]
+        );
+
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/product/not_in_database/status-history"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    /// Verifies a GET /product/{id}/status-history?product_namespace=Custom&schema_name=...
+    ///     lists only the status changes of the product that follows that schema when products
+    ///     of two schemas share an id.
+    #[actix_rt::test]
+    async fn test_custom_product_status_history_by_schema() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        let products = [("lightbulb", "ACTIVE"), ("lamp", "DISCONTINUED")]
+            .iter()
+            .map(|(schema_name, status)| Product {
+                product_id: "LB-0001".to_string(),
+                product_address: compute_custom_product_address(schema_name, "LB-0001"),
+                product_namespace: "Custom".to_string(),
+                schema_name: schema_name.to_string(),
+                owner: "phillips001".to_string(),
+                start_commit_num: 0,
+                end_commit_num: i64::MAX,
+                properties: vec![],
+                service_id: None,
+                transaction_id: None,
+                signer: None,
+                pending_owner: "".to_string(),
+                schema_version: 1,
+                status: status.to_string(),
+            })
+            .collect();
+        populate_product_table(test_pool, products);
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(
+                    "/product/LB-0001/status-history?product_namespace=Custom&schema_name=lamp",
+                ),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<ProductStatusChangeSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(
+            body.iter()
+                .map(|change| change.status.as_str())
+                .collect::<Vec<_>>(),
+            vec!["DISCONTINUED"]
+        );
+    }
+
     /// Verifies a GET /location?service_id=test_service responds with an OK response with a
     ///     list_locations request.
     ///
@@ -1519,7 +1774,10 @@ mod test {
         let test_product: ProductSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
         assert_eq!(test_product.product_id, "041205707820".to_string());
-        assert_eq!(test_product.product_address, "test_address".to_string());
+        assert_eq!(
+            test_product.product_address,
+            compute_gs1_product_address("041205707820")
+        );
         assert_eq!(test_product.product_namespace, "Grid Product".to_string());
         assert_eq!(test_product.owner, "phillips001".to_string());
         assert_eq!(test_product.properties.len(), 2);
@@ -1585,7 +1843,10 @@ mod test {
         let test_product: ProductSlice =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
         assert_eq!(test_product.product_id, "041205707820".to_string());
-        assert_eq!(test_product.product_address, "test_address".to_string());
+        assert_eq!(
+            test_product.product_address,
+            compute_gs1_product_address("041205707820")
+        );
         assert_eq!(test_product.product_namespace, "Grid Product".to_string());
         assert_eq!(test_product.owner, "phillips001".to_string());
         assert_eq!(test_product.properties.len(), 2);
@@ -1647,6 +1908,74 @@ mod test {
         assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
     }
 
+    ///
+    /// Verifies a GET /product/{id}?product_namespace=Custom&schema_name=... responds with the
+    ///     product that follows that schema when products of two schemas share an id, and
+    ///     that a Custom lookup without a schema_name is rejected.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_custom_product_by_schema() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        let products = ["lightbulb", "lamp"]
+            .iter()
+            .map(|schema_name| Product {
+                product_id: "LB-0001".to_string(),
+                product_address: compute_custom_product_address(schema_name, "LB-0001"),
+                product_namespace: "Custom".to_string(),
+                schema_name: schema_name.to_string(),
+                owner: "phillips001".to_string(),
+                start_commit_num: 0,
+                end_commit_num: i64::MAX,
+                properties: vec![],
+                service_id: None,
+                transaction_id: None,
+                signer: None,
+                pending_owner: "".to_string(),
+                schema_version: 1,
+                status: "ACTIVE".to_string(),
+            })
+            .collect();
+        populate_product_table(test_pool, products);
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/product/LB-0001?product_namespace=Custom&schema_name=lamp"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_product: ProductSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_product.schema_name, "lamp".to_string());
+        assert_eq!(
+            test_product.product_address,
+            compute_custom_product_address("lamp", "LB-0001")
+        );
+
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/product/LB-0001?product_namespace=Custom"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+
+        let response = srv
+            .request(http::Method::GET, srv.url("/product/LB-0001"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
     ///
     /// Verifies a GET /location/{id} responds with a Not Found error
     ///     when there is no Product with the specified id
@@ -1715,6 +2044,73 @@ mod test {
         assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
     }
 
+    ///
+    /// Verifies GET /location/{id}/children lists the locations directly contained in a location
+    ///     and GET /location/{id}/ancestors lists the path up to its top-level location.
+    ///
+    #[actix_rt::test]
+    async fn test_location_hierarchy() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+        populate_location_table(
+            test_pool,
+            vec![
+                get_location_in("9012345000004", None),
+                get_location_in("9012345000011", Some("9012345000004")),
+                get_location_in("9012345000028", Some("9012345000011")),
+            ],
+        );
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/location/9012345000004/children"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let children: Vec<LocationSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].location_id, "9012345000011".to_string());
+        assert_eq!(
+            children[0].parent_location_id,
+            Some("9012345000004".to_string())
+        );
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/location/9012345000028/ancestors"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let ancestors: Vec<LocationSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(
+            ancestors
+                .iter()
+                .map(|location| location.location_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["9012345000011", "9012345000004"]
+        );
+
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/location/9012345000035/children"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
     ///
     /// Verifies a GET /record responds with an Ok response
     ///     with a list containing one record
@@ -2100,49 +2496,161 @@ mod test {
     }
 
     ///
-    /// Verifies a GET /record/{record_id} responds with an OK response
-    ///     and the Record with the specified record ID.
+    /// Verifies an archived record is left out of GET /record unless include_archived=true is
+    ///     given, but can still be fetched with GET /record/{record_id}.
     ///
     #[actix_rt::test]
-    async fn test_fetch_record_ok() {
+    async fn test_list_records_archived() {
         run_migrations(&DATABASE_URL);
         let test_pool = get_connection_pool();
         let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
         clear_postgres_database(&test_pool.get().unwrap()).unwrap();
 
-        populate_agent_table(test_pool.clone(), get_agents_with_roles(None));
-        populate_associated_agent_table(test_pool.clone(), get_associated_agents(None));
-        populate_proposal_table(test_pool.clone(), get_proposal(None));
-        populate_record_table(test_pool.clone(), get_record("TestRecord", None));
-        populate_grid_schema_table(test_pool.clone(), get_grid_schema_for_record(None));
-        populate_tnt_property_table(
-            test_pool,
-            get_property_for_record(None),
-            get_reported_value_for_property_record(None),
-            get_reporter_for_property_record(None),
+        let mut records = get_record("TestRecord", None);
+        records.extend(
+            get_record("ArchivedRecord", None)
+                .into_iter()
+                .map(|record| Record {
+                    final_: true,
+                    archived: true,
+                    ..record
+                }),
         );
+        populate_record_table(test_pool, records);
+
         let mut response = srv
-            .request(
-                http::Method::GET,
-                srv.url(&format!("/record/{}", "TestRecord".to_string())),
-            )
+            .request(http::Method::GET, srv.url("/record"))
             .send()
             .await
             .unwrap();
         assert!(response.status().is_success());
-        let test_record: RecordSlice =
+        let body: Vec<RecordSlice> =
             serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
-        assert_eq!(test_record.record_id, "TestRecord".to_string());
-        assert_eq!(test_record.schema, "TestGridSchema".to_string());
-        assert_eq!(test_record.owner, KEY1.to_string());
-        assert_eq!(test_record.custodian, KEY2.to_string());
+        assert_eq!(body.len(), 1);
+        assert_eq!(body[0].record_id, "TestRecord".to_string());
 
-        assert_eq!(test_record.properties.len(), 2);
-        assert_eq!(test_record.properties[0].name, "TestProperty1");
-        assert_eq!(test_record.properties[0].record_id, "TestRecord");
-        assert_eq!(test_record.properties[0].data_type, "String");
-        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
-        assert_eq!(test_record.properties[0].updates.len(), 1);
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/record?include_archived=true"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<RecordSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.len(), 2);
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/record/ArchivedRecord"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_record: RecordSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_record.record_id, "ArchivedRecord".to_string());
+        assert!(test_record.archived);
+    }
+
+    ///
+    /// Verifies GET /record can be filtered by the product a record tracks and the location it is
+    ///     currently at
+    ///
+    #[actix_rt::test]
+    async fn test_list_records_by_product_and_location() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        let mut records = get_record("TestRecord", None);
+        records.extend(
+            get_record("ProductRecord", None)
+                .into_iter()
+                .map(|record| Record {
+                    product_id: Some("00012345600012".to_string()),
+                    location_id: Some("9012345000004".to_string()),
+                    product_namespace: Some("GS1".to_string()),
+                    ..record
+                }),
+        );
+        populate_record_table(test_pool, records);
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record?product_id=00012345600012"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<RecordSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(body.len(), 1);
+        assert_eq!(body[0].record_id, "ProductRecord".to_string());
+        assert_eq!(body[0].location_id, Some("9012345000004".to_string()));
+        assert_eq!(body[0].product_namespace, Some("GS1".to_string()));
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record?location_id=9012345000011"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let body: Vec<RecordSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert!(body.is_empty());
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id} responds with an OK response
+    ///     and the Record with the specified record ID.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_record_ok() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        populate_agent_table(test_pool.clone(), get_agents_with_roles(None));
+        populate_associated_agent_table(test_pool.clone(), get_associated_agents(None));
+        populate_proposal_table(test_pool.clone(), get_proposal(None));
+        populate_record_table(test_pool.clone(), get_record("TestRecord", None));
+        populate_grid_schema_table(test_pool.clone(), get_grid_schema_for_record(None));
+        populate_tnt_property_table(
+            test_pool,
+            get_property_for_record(None),
+            get_reported_value_for_property_record(None),
+            get_reporter_for_property_record(None),
+        );
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/record/{}", "TestRecord".to_string())),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_record: RecordSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(test_record.record_id, "TestRecord".to_string());
+        assert_eq!(test_record.schema, "TestGridSchema".to_string());
+        assert_eq!(test_record.owner, KEY1.to_string());
+        assert_eq!(test_record.custodian, KEY2.to_string());
+
+        assert_eq!(test_record.properties.len(), 2);
+        assert_eq!(test_record.properties[0].name, "TestProperty1");
+        assert_eq!(test_record.properties[0].record_id, "TestRecord");
+        assert_eq!(test_record.properties[0].data_type, "String");
+        assert_eq!(test_record.properties[0].reporters, vec![KEY1.to_string()]);
+        assert_eq!(test_record.properties[0].updates.len(), 1);
         assert_eq!(
             test_record.properties[0].updates[0].value,
             Value::String("value_1".to_string())
@@ -2230,6 +2738,52 @@ mod test {
         assert_eq!(test_record.proposals[0].terms, "Proposal Terms".to_string());
     }
 
+    ///
+    /// Verifies a GET /record/{record_id} reports an open proposal whose
+    ///     expiry has passed as expired.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_record_expired_proposal() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        let proposals = get_proposal(None)
+            .into_iter()
+            .map(|proposal| Proposal {
+                expires_at: Some(2),
+                ..proposal
+            })
+            .collect();
+
+        populate_agent_table(test_pool.clone(), get_agents_with_roles(None));
+        populate_associated_agent_table(test_pool.clone(), get_associated_agents(None));
+        populate_proposal_table(test_pool.clone(), proposals);
+        populate_record_table(test_pool.clone(), get_record("TestRecord", None));
+        populate_grid_schema_table(test_pool.clone(), get_grid_schema_for_record(None));
+        populate_tnt_property_table(
+            test_pool,
+            get_property_for_record(None),
+            get_reported_value_for_property_record(None),
+            get_reporter_for_property_record(None),
+        );
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!("/record/{}", "TestRecord".to_string())),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let test_record: RecordSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(test_record.proposals[0].status, "Expired");
+        assert_eq!(test_record.proposals[0].expires_at, Some(2));
+    }
+
     ///
     /// Verifies a GET /record/{record_id}?service_id=test_service responds with an OK response
     ///     and the Record with the specified record ID.
@@ -2579,6 +3133,87 @@ mod test {
         assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
     }
 
+    ///
+    /// Verifies a GET /record/{record_id}/lineage responds with an OK response, the record's
+    ///     current parent and children, and the aggregations among its ancestors and
+    ///     descendants.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_record_lineage_ok() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        for record_id in &["Pallet", "Case1", "Case2", "Can1"] {
+            populate_record_table(test_pool.clone(), get_record(record_id, None));
+        }
+        populate_record_aggregation_table(
+            test_pool,
+            vec![
+                get_record_aggregation("Pallet", "Case1", None),
+                get_record_aggregation("Pallet", "Case2", Some(3)),
+                get_record_aggregation("Case1", "Can1", None),
+            ],
+        );
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/record/Case1/lineage"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let lineage: RecordLineageSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(lineage.record_id, "Case1");
+        assert_eq!(lineage.parent, Some("Pallet".to_string()));
+        assert_eq!(lineage.children, vec!["Can1".to_string()]);
+        // Case2 shares a parent with Case1 but is not in its lineage
+        assert_eq!(lineage.aggregations.len(), 2);
+        assert_eq!(lineage.aggregations[0].parent_record_id, "Pallet");
+        assert_eq!(lineage.aggregations[0].child_record_id, "Case1");
+        assert_eq!(lineage.aggregations[1].parent_record_id, "Case1");
+        assert_eq!(lineage.aggregations[1].child_record_id, "Can1");
+
+        let mut response = srv
+            .request(http::Method::GET, srv.url("/record/Pallet/lineage"))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        let lineage: RecordLineageSlice =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+        assert_eq!(lineage.parent, None);
+        assert_eq!(lineage.children, vec!["Case1".to_string()]);
+        assert_eq!(lineage.aggregations.len(), 3);
+        assert!(lineage
+            .aggregations
+            .iter()
+            .any(|aggregation| aggregation.child_record_id == "Case2"
+                && aggregation.disaggregated_at == Some(3)));
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id}/lineage responds with a Not Found error when there is
+    ///     no Record with the specified record_id.
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_record_lineage_not_found() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record/not_in_database/lineage"),
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), http::StatusCode::NOT_FOUND);
+    }
+
     ///
     /// Verifies a GET /record/{record_id}/property/{property_name} responds with an OK response
     ///     and the infomation on the Property requested
@@ -2872,6 +3507,318 @@ mod test {
         }
     }
 
+    ///
+    /// Verifies a GET /record/{record_id}/property/{property_name}/aggregate responds with an OK
+    ///     response and the archived Number values bucketed by timestamp, scaled by the property's
+    ///     number exponent
+    ///
+    #[actix_rt::test]
+    async fn test_aggregate_record_property_ok() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        populate_grid_schema_table(test_pool.clone(), get_grid_schema_for_number_record());
+        populate_record_table(test_pool.clone(), get_record("record_01", None));
+        populate_tnt_property_table(
+            test_pool.clone(),
+            get_property(None),
+            vec![],
+            get_reporter(None),
+        );
+        populate_property_page_archive(test_pool.clone(), get_number_property_pages());
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record/record_01/property/TestProperty/aggregate?bucket=10"),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+
+        let buckets: Vec<ReportedValueBucketSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(
+            buckets,
+            vec![
+                ReportedValueBucketSlice {
+                    start: 0,
+                    count: 2,
+                    min: 1.0,
+                    max: 2.0,
+                    avg: 1.5,
+                },
+                ReportedValueBucketSlice {
+                    start: 10,
+                    count: 1,
+                    min: 3.5,
+                    max: 3.5,
+                    avg: 3.5,
+                },
+            ]
+        );
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record/record_01/property/TestProperty/aggregate?bucket=100&latest=2"),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+
+        let buckets: Vec<ReportedValueBucketSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(
+            buckets,
+            vec![ReportedValueBucketSlice {
+                start: 0,
+                count: 2,
+                min: 2.0,
+                max: 3.5,
+                avg: 2.75,
+            }]
+        );
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id}/property/{property_name}/aggregate responds with a Bad
+    ///     Request error when the property is not a Number property
+    ///
+    #[actix_rt::test]
+    async fn test_aggregate_record_property_not_number() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        populate_grid_schema_table(test_pool.clone(), get_grid_schema_for_struct_record(None));
+        populate_record_table(test_pool.clone(), get_record("record_01", None));
+        populate_tnt_property_table(
+            test_pool.clone(),
+            get_property(None),
+            get_reported_value(None),
+            get_reporter(None),
+        );
+
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record/record_01/property/TestProperty/aggregate?bucket=10"),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+    }
+
+    ///
+    /// Verifies a GET /geo/locations responds with an OK response and a GeoJSON feature for each
+    ///     location within the radius of the point, nearest first
+    ///
+    #[actix_rt::test]
+    async fn test_list_nearby_locations() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        populate_location_table(
+            test_pool,
+            vec![
+                get_location_at("0653114000000", LONDON),
+                get_location_at("0653114000017", PARIS),
+            ],
+        );
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(&format!(
+                    "/geo/locations?latitude={}&longitude={}&radius=50000",
+                    LONDON.0, LONDON.1
+                )),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+
+        let locations: FeatureCollection<NearbyLocationProperties> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(locations.features.len(), 1);
+        assert_eq!(
+            locations.features[0].properties.location_id,
+            "0653114000000".to_string()
+        );
+        assert_eq!(locations.features[0].properties.distance, 0.0);
+        assert_eq!(
+            locations.features[0].geometry,
+            Geometry::Point {
+                coordinates: [-0.1278, 51.5074]
+            }
+        );
+
+        let response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/geo/locations?latitude=91000000&longitude=0&radius=50000"),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), http::StatusCode::BAD_REQUEST);
+    }
+
+    ///
+    /// Verifies a GET /geo/records responds with an OK response and a GeoJSON feature for each
+    ///     record whose most recent position is within the bounding box
+    ///
+    #[actix_rt::test]
+    async fn test_list_records_within() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        let store = DieselTrackAndTraceStore::new(test_pool.pool.clone());
+        store
+            .add_reported_values(vec![
+                get_position_reported_value(1, PARIS),
+                get_position_reported_value(2, LONDON),
+            ])
+            .unwrap();
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(
+                    "/geo/records?min_latitude=51000000&min_longitude=-1000000\
+                     &max_latitude=52000000&max_longitude=1000000",
+                ),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+
+        let records: FeatureCollection<RecordPositionProperties> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(records.features.len(), 1);
+        assert_eq!(
+            records.features[0].properties,
+            RecordPositionProperties {
+                record_id: "record_01".to_string(),
+                property_name: "location".to_string(),
+                timestamp: 2,
+                service_id: None,
+            }
+        );
+
+        // The record was in Paris before its most recent report
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url(
+                    "/geo/records?min_latitude=48000000&min_longitude=2000000\
+                     &max_latitude=49000000&max_longitude=3000000",
+                ),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+
+        let records: FeatureCollection<RecordPositionProperties> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert!(records.features.is_empty());
+    }
+
+    ///
+    /// Verifies a GET /record/{record_id}/property/{property_name}/route responds with an OK
+    ///     response and a GeoJSON line through the archived positions, oldest first
+    ///
+    #[actix_rt::test]
+    async fn test_fetch_record_property_route() {
+        run_migrations(&DATABASE_URL);
+        let test_pool = get_connection_pool();
+        let srv = create_test_server(Backend::Sawtooth, ResponseType::ClientBatchStatusResponseOK);
+
+        clear_postgres_database(&test_pool.get().unwrap()).unwrap();
+
+        populate_grid_schema_table(test_pool.clone(), get_grid_schema_for_position_record());
+        populate_record_table(test_pool.clone(), get_record("record_01", None));
+
+        let store = DieselTrackAndTraceStore::new(test_pool.pool.clone());
+        store
+            .add_properties(vec![Property {
+                name: "location".to_string(),
+                ..get_property(None)[0].clone()
+            }])
+            .unwrap();
+        store
+            .archive_property_pages(vec![PropertyPageSnapshot {
+                record_id: "record_01".to_string(),
+                property_name: "location".to_string(),
+                page_num: 1,
+                reported_values: vec![(2, LONDON), (1, PARIS)]
+                    .into_iter()
+                    .map(|(timestamp, position)| PageReportedValue {
+                        reporter_index: 0,
+                        timestamp,
+                        data_type: "LatLong".to_string(),
+                        value: get_position_property_value(position),
+                        number_value: None,
+                    })
+                    .collect(),
+                commit_num: 1,
+                service_id: None,
+            }])
+            .unwrap();
+
+        let mut response = srv
+            .request(
+                http::Method::GET,
+                srv.url("/record/record_01/property/location/route"),
+            )
+            .send()
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+
+        let route: Feature<RoutePropertiesSlice> =
+            serde_json::from_slice(&*response.body().await.unwrap()).unwrap();
+
+        assert_eq!(
+            route.geometry,
+            Geometry::LineString {
+                coordinates: vec![[2.3522, 48.8566], [-0.1278, 51.5074]]
+            }
+        );
+        assert_eq!(route.properties.timestamps, vec![1, 2]);
+    }
+
     ///
     /// Verifies a GET /record/{record_id}/property/{property_name} responds with a Not Found
     /// error when there is no property with the specified property_name.
@@ -2997,6 +3944,8 @@ mod test {
             start_commit_num: 0,
             end_commit_num: i64::MAX,
             service_id,
+            transaction_id: None,
+            signer: None,
         }]
     }
 
@@ -3011,6 +3960,8 @@ mod test {
                 start_commit_num: 0,
                 end_commit_num: i64::MAX,
                 service_id: service_id.clone(),
+                transaction_id: None,
+                signer: None,
             },
             Agent {
                 public_key: KEY2.to_string(),
@@ -3021,6 +3972,8 @@ mod test {
                 start_commit_num: 0,
                 end_commit_num: i64::MAX,
                 service_id,
+                transaction_id: None,
+                signer: None,
             },
         ]
     }
@@ -3041,6 +3994,8 @@ mod test {
             start_commit_num: 1,
             end_commit_num: i64::MAX,
             service_id,
+            transaction_id: None,
+            signer: None,
         }]
     }
 
@@ -3054,6 +4009,8 @@ mod test {
                 start_commit_num: 2,
                 end_commit_num: 4,
                 service_id: None,
+                transaction_id: None,
+                signer: None,
             },
             Organization {
                 org_id: KEY3.to_string(),
@@ -3063,6 +4020,8 @@ mod test {
                 start_commit_num: 4,
                 end_commit_num: i64::MAX,
                 service_id: None,
+                transaction_id: None,
+                signer: None,
             },
         ]
     }
@@ -3083,20 +4042,29 @@ mod test {
             properties: get_property_definition(service_id.clone()),
             description: "Example test grid schema".to_string(),
             owner: "phillips001".to_string(),
+            version: 1,
             service_id,
+            transaction_id: None,
+            signer: None,
         }]
     }
 
     fn get_product(service_id: Option<String>) -> Vec<Product> {
         vec![Product {
             product_id: "041205707820".to_string(),
-            product_address: "test_address".to_string(),
+            product_address: compute_gs1_product_address("041205707820"),
             product_namespace: "Grid Product".to_string(),
+            schema_name: "".to_string(),
             owner: "phillips001".to_string(),
             start_commit_num: 0,
             end_commit_num: i64::MAX,
             properties: get_product_property_value(service_id.clone()),
             service_id,
+            transaction_id: None,
+            signer: None,
+            pending_owner: "".to_string(),
+            schema_version: 1,
+            status: "ACTIVE".to_string(),
         }]
     }
 
@@ -3119,9 +4087,23 @@ mod test {
             start_commit_num: 0,
             end_commit_num: i64::MAX,
             service_id,
+            transaction_id: None,
+            signer: None,
+            pending_owner: "".to_string(),
+            schema_version: 1,
+            parent_location_id: None,
         }]
     }
 
+    fn get_location_in(location_id: &str, parent_location_id: Option<&str>) -> Location {
+        Location {
+            location_id: location_id.to_string(),
+            attributes: vec![],
+            parent_location_id: parent_location_id.map(String::from),
+            ..get_location(None).remove(0)
+        }
+    }
+
     fn get_location_attributes(service_id: Option<String>) -> Vec<LocationAttribute> {
         vec![
             LocationAttribute {
@@ -3139,6 +4121,9 @@ mod test {
                 enum_value: None,
                 struct_values: None,
                 lat_long_value: None,
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: None,
                 service_id: service_id.clone(),
             },
             LocationAttribute {
@@ -3156,6 +4141,9 @@ mod test {
                 enum_value: None,
                 struct_values: None,
                 lat_long_value: None,
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: None,
                 service_id,
             },
         ]
@@ -3245,6 +4233,9 @@ mod test {
             status: "OPEN".to_string(),
             terms: "Proposal Terms".to_string(),
             service_id,
+            transaction_id: None,
+            signer: None,
+            expires_at: None,
         }]
     }
 
@@ -3263,6 +4254,9 @@ mod test {
                 status: "OPEN".to_string(),
                 terms: "Proposal Terms".to_string(),
                 service_id: None,
+                transaction_id: None,
+                signer: None,
+                expires_at: None,
             },
             Proposal {
                 id: None,
@@ -3277,6 +4271,9 @@ mod test {
                 status: "CANCELED".to_string(),
                 terms: "Proposal Terms".to_string(),
                 service_id: None,
+                transaction_id: None,
+                signer: None,
+                expires_at: None,
             },
         ]
     }
@@ -3292,9 +4289,34 @@ mod test {
             owners: vec![KEY1.to_string()],
             custodians: vec![KEY2.to_string()],
             service_id,
+            transaction_id: None,
+            signer: None,
+            schema_version: 1,
+            archived: false,
+            product_id: None,
+            location_id: None,
+            product_namespace: None,
+            product_schema_name: None,
         }]
     }
 
+    fn get_record_aggregation(
+        parent_record_id: &str,
+        child_record_id: &str,
+        disaggregated_at: Option<i64>,
+    ) -> RecordAggregation {
+        RecordAggregation {
+            id: None,
+            parent_record_id: parent_record_id.to_string(),
+            child_record_id: child_record_id.to_string(),
+            aggregated_at: 1,
+            disaggregated_at,
+            start_commit_num: 0,
+            end_commit_num: i64::MAX,
+            service_id: None,
+        }
+    }
+
     fn get_updated_record() -> Vec<Record> {
         vec![
             Record {
@@ -3307,6 +4329,14 @@ mod test {
                 owners: vec![KEY1.to_string()],
                 custodians: vec![KEY2.to_string()],
                 service_id: None,
+                transaction_id: None,
+                signer: None,
+                schema_version: 1,
+                archived: false,
+                product_id: None,
+                location_id: None,
+                product_namespace: None,
+                product_schema_name: None,
             },
             Record {
                 id: None,
@@ -3318,6 +4348,14 @@ mod test {
                 owners: vec![KEY2.to_string(), KEY1.to_string()],
                 custodians: vec![KEY1.to_string(), KEY2.to_string()],
                 service_id: None,
+                transaction_id: None,
+                signer: None,
+                schema_version: 1,
+                archived: false,
+                product_id: None,
+                location_id: None,
+                product_namespace: None,
+                product_schema_name: None,
             },
         ]
     }
@@ -3334,6 +4372,14 @@ mod test {
                 owners: vec![KEY1.to_string()],
                 custodians: vec![KEY2.to_string()],
                 service_id: None,
+                transaction_id: None,
+                signer: None,
+                schema_version: 1,
+                archived: false,
+                product_id: None,
+                location_id: None,
+                product_namespace: None,
+                product_schema_name: None,
             },
             Record {
                 id: None,
@@ -3345,6 +4391,14 @@ mod test {
                 owners: vec![KEY2.to_string(), KEY1.to_string()],
                 custodians: vec![KEY1.to_string(), KEY2.to_string()],
                 service_id: None,
+                transaction_id: None,
+                signer: None,
+                schema_version: 1,
+                archived: false,
+                product_id: None,
+                location_id: None,
+                product_namespace: None,
+                product_schema_name: None,
             },
             Record {
                 id: None,
@@ -3356,6 +4410,14 @@ mod test {
                 owners: vec![KEY1.to_string()],
                 custodians: vec![KEY2.to_string()],
                 service_id: None,
+                transaction_id: None,
+                signer: None,
+                schema_version: 1,
+                archived: false,
+                product_id: None,
+                location_id: None,
+                product_namespace: None,
+                product_schema_name: None,
             },
         ]
     }
@@ -3392,6 +4454,8 @@ mod test {
                 current_page: 1,
                 wrapped: false,
                 service_id: service_id.clone(),
+                transaction_id: None,
+                signer: None,
             },
             Property {
                 id: None,
@@ -3403,6 +4467,8 @@ mod test {
                 current_page: 1,
                 wrapped: false,
                 service_id,
+                transaction_id: None,
+                signer: None,
             },
         ]
     }
@@ -3452,7 +4518,12 @@ mod test {
                 enum_value: None,
                 struct_values: None,
                 lat_long_value: None,
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: None,
                 service_id: service_id.clone(),
+                transaction_id: None,
+                signer: None,
             },
             ReportedValue {
                 id: None,
@@ -3470,7 +4541,12 @@ mod test {
                 enum_value: None,
                 struct_values: None,
                 lat_long_value: None,
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: None,
                 service_id,
+                transaction_id: None,
+                signer: None,
             },
         ]
     }
@@ -3485,6 +4561,8 @@ mod test {
             current_page: 1,
             wrapped: false,
             service_id,
+            transaction_id: None,
+            signer: None,
         }]
     }
 
@@ -3540,6 +4618,8 @@ mod test {
                 number_exponent: 0,
                 enum_options: vec![],
                 struct_properties: vec![],
+                deprecated: false,
+                list_data_type: None,
                 service_id: service_id.clone(),
             },
             PropertyDefinition {
@@ -3553,6 +4633,8 @@ mod test {
                 number_exponent: 0,
                 enum_options: vec![],
                 struct_properties: vec![],
+                deprecated: false,
+                list_data_type: None,
                 service_id: service_id,
             },
         ]
@@ -3566,7 +4648,10 @@ mod test {
             properties: get_grid_property_definition_struct_for_record(service_id.clone()),
             description: "Example test grid schema".to_string(),
             owner: "phillips001".to_string(),
+            version: 1,
             service_id,
+            transaction_id: None,
+            signer: None,
         }]
     }
 
@@ -3584,10 +4669,163 @@ mod test {
             number_exponent: 0,
             enum_options: vec![],
             struct_properties: vec![],
+            deprecated: false,
+            list_data_type: None,
             service_id,
         }]
     }
 
+    fn get_grid_schema_for_number_record() -> Vec<Schema> {
+        vec![Schema {
+            start_commit_num: 0,
+            end_commit_num: i64::MAX,
+            name: "TestGridSchema".to_string(),
+            properties: vec![PropertyDefinition {
+                start_commit_num: 0,
+                end_commit_num: i64::MAX,
+                name: "TestProperty".to_string(),
+                schema_name: "TestGridSchema".to_string(),
+                data_type: "Number".to_string(),
+                required: false,
+                description: "Definition Description".to_string(),
+                number_exponent: -2,
+                enum_options: vec![],
+                struct_properties: vec![],
+                deprecated: false,
+                list_data_type: None,
+                service_id: None,
+            }],
+            description: "Example test grid schema".to_string(),
+            owner: "phillips001".to_string(),
+            version: 1,
+            service_id: None,
+            transaction_id: None,
+            signer: None,
+        }]
+    }
+
+    fn get_number_property_pages() -> Vec<PropertyPageSnapshot> {
+        vec![PropertyPageSnapshot {
+            record_id: "record_01".to_string(),
+            property_name: "TestProperty".to_string(),
+            page_num: 1,
+            reported_values: vec![(1, 100), (2, 200), (11, 350)]
+                .into_iter()
+                .map(|(timestamp, number_value)| PageReportedValue {
+                    reporter_index: 0,
+                    timestamp,
+                    data_type: "Number".to_string(),
+                    value: vec![],
+                    number_value: Some(number_value),
+                })
+                .collect(),
+            commit_num: 1,
+            service_id: None,
+        }]
+    }
+
+    const LONDON: (i64, i64) = (51_507_400, -127_800);
+    const PARIS: (i64, i64) = (48_856_600, 2_352_200);
+
+    fn get_location_at(location_id: &str, position: (i64, i64)) -> Location {
+        Location {
+            location_id: location_id.to_string(),
+            location_namespace: "Grid Location".to_string(),
+            owner: "phillips001".to_string(),
+            attributes: vec![LocationAttribute {
+                location_id: location_id.to_string(),
+                location_address: "test_address".to_string(),
+                property_name: "lat_long".to_string(),
+                data_type: "LatLong".to_string(),
+                bytes_value: None,
+                boolean_value: None,
+                number_value: None,
+                string_value: None,
+                enum_value: None,
+                struct_values: None,
+                lat_long_value: Some(grid_sdk::grid_db::locations::store::LatLongValue(
+                    position.0, position.1,
+                )),
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: None,
+                start_commit_num: 0,
+                end_commit_num: i64::MAX,
+                service_id: None,
+            }],
+            start_commit_num: 0,
+            end_commit_num: i64::MAX,
+            service_id: None,
+            transaction_id: None,
+            signer: None,
+            pending_owner: "".to_string(),
+            schema_version: 1,
+            parent_location_id: None,
+        }
+    }
+
+    fn get_position_reported_value(timestamp: i64, position: (i64, i64)) -> ReportedValue {
+        ReportedValue {
+            id: None,
+            start_commit_num: timestamp,
+            end_commit_num: i64::MAX,
+            property_name: "location".to_string(),
+            record_id: "record_01".to_string(),
+            reporter_index: 0,
+            timestamp,
+            data_type: "LatLong".to_string(),
+            bytes_value: None,
+            boolean_value: None,
+            number_value: None,
+            string_value: None,
+            enum_value: None,
+            struct_values: None,
+            lat_long_value: Some(LatLongValue(position.0, position.1)),
+            timestamp_value: None,
+            decimal_value: None,
+            list_values: None,
+            service_id: None,
+            transaction_id: None,
+            signer: None,
+        }
+    }
+
+    fn get_position_property_value(position: (i64, i64)) -> Vec<u8> {
+        PropertyValueBuilder::new()
+            .with_name("location".to_string())
+            .with_data_type(StateDataType::LatLong)
+            .with_lat_long_value(
+                LatLongBuilder::new()
+                    .with_lat_long(position.0, position.1)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()
+            .into_bytes()
+            .unwrap()
+    }
+
+    fn get_grid_schema_for_position_record() -> Vec<Schema> {
+        vec![Schema {
+            properties: vec![PropertyDefinition {
+                name: "location".to_string(),
+                data_type: "LatLong".to_string(),
+                number_exponent: 0,
+                ..get_grid_schema_for_number_record()[0].properties[0].clone()
+            }],
+            ..get_grid_schema_for_number_record()[0].clone()
+        }]
+    }
+
+    fn populate_property_page_archive(
+        pool: ConnectionPool<diesel::pg::PgConnection>,
+        pages: Vec<PropertyPageSnapshot>,
+    ) {
+        let store = DieselTrackAndTraceStore::new(pool.pool);
+        store.archive_property_pages(pages).unwrap();
+    }
+
     fn get_grid_schema_for_record(service_id: Option<String>) -> Vec<Schema> {
         vec![Schema {
             start_commit_num: 0,
@@ -3596,7 +4834,10 @@ mod test {
             properties: get_grid_property_definition_for_record(service_id.clone()),
             description: "Example test grid schema".to_string(),
             owner: "phillips001".to_string(),
+            version: 1,
             service_id,
+            transaction_id: None,
+            signer: None,
         }]
     }
 
@@ -3615,6 +4856,8 @@ mod test {
                 number_exponent: 0,
                 enum_options: vec![],
                 struct_properties: vec![],
+                deprecated: false,
+                list_data_type: None,
                 service_id: service_id.clone(),
             },
             PropertyDefinition {
@@ -3628,6 +4871,8 @@ mod test {
                 number_exponent: 0,
                 enum_options: vec![],
                 struct_properties: vec![],
+                deprecated: false,
+                list_data_type: None,
                 service_id,
             },
         ]
@@ -3639,7 +4884,7 @@ mod test {
                 start_commit_num: 0,
                 end_commit_num: i64::MAX,
                 product_id: "041205707820".to_string(),
-                product_address: "test_address".to_string(),
+                product_address: compute_gs1_product_address("041205707820"),
                 property_name: "Test Grid Product".to_string(),
                 data_type: "Lightbulb".to_string(),
                 bytes_value: None,
@@ -3649,13 +4894,16 @@ mod test {
                 enum_value: None,
                 struct_values: vec![],
                 lat_long_value: None,
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: vec![],
                 service_id: service_id.clone(),
             },
             PropertyValue {
                 start_commit_num: 0,
                 end_commit_num: i64::MAX,
                 product_id: "041205707820".to_string(),
-                product_address: "test_address".to_string(),
+                product_address: compute_gs1_product_address("041205707820"),
                 property_name: "Test Grid Product".to_string(),
                 data_type: "Lightbulb".to_string(),
                 bytes_value: None,
@@ -3665,6 +4913,9 @@ mod test {
                 enum_value: None,
                 struct_values: vec![],
                 lat_long_value: None,
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: vec![],
                 service_id,
             },
         ]
@@ -3691,6 +4942,14 @@ mod test {
         store.add_records(records).unwrap();
     }
 
+    fn populate_record_aggregation_table(
+        pool: ConnectionPool<diesel::pg::PgConnection>,
+        aggregations: Vec<RecordAggregation>,
+    ) {
+        let store = DieselTrackAndTraceStore::new(pool.pool);
+        store.add_record_aggregations(aggregations).unwrap();
+    }
+
     fn run_migrations(database_url: &str) {
         let connection = PgConnection::establish(database_url)
             .expect("Failed to stablish connection with database");
@@ -3746,7 +5005,12 @@ mod test {
                                 enum_value: None,
                                 struct_values: None,
                                 lat_long_value: None,
+                                timestamp_value: None,
+                                decimal_value: None,
+                                list_values: None,
                                 service_id: service_id.clone(),
+                                transaction_id: None,
+                                signer: None,
                             },
                             ReportedValue {
                                 id: None,
@@ -3765,11 +5029,21 @@ mod test {
                                 enum_value: None,
                                 struct_values: None,
                                 lat_long_value: None,
+                                timestamp_value: None,
+                                decimal_value: None,
+                                list_values: None,
                                 service_id: service_id.clone(),
+                                transaction_id: None,
+                                signer: None,
                             },
                         ]),
                         lat_long_value: None,
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                     ReportedValue {
                         id: None,
@@ -3787,7 +5061,12 @@ mod test {
                         enum_value: None,
                         struct_values: None,
                         lat_long_value: Some(LatLongValue(1, 1)),
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                     ReportedValue {
                         id: None,
@@ -3805,7 +5084,12 @@ mod test {
                         enum_value: None,
                         struct_values: None,
                         lat_long_value: None,
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                     ReportedValue {
                         id: None,
@@ -3823,7 +5107,12 @@ mod test {
                         enum_value: Some(1),
                         struct_values: None,
                         lat_long_value: None,
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                     ReportedValue {
                         id: None,
@@ -3841,11 +5130,21 @@ mod test {
                         enum_value: None,
                         struct_values: None,
                         lat_long_value: None,
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                 ]),
                 lat_long_value: None,
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: None,
                 service_id: service_id.clone(),
+                transaction_id: None,
+                signer: None,
             },
             ReportedValue {
                 id: None,
@@ -3894,7 +5193,12 @@ mod test {
                                 enum_value: None,
                                 struct_values: None,
                                 lat_long_value: None,
+                                timestamp_value: None,
+                                decimal_value: None,
+                                list_values: None,
                                 service_id: service_id.clone(),
+                                transaction_id: None,
+                                signer: None,
                             },
                             ReportedValue {
                                 id: None,
@@ -3913,11 +5217,21 @@ mod test {
                                 enum_value: None,
                                 struct_values: None,
                                 lat_long_value: None,
+                                timestamp_value: None,
+                                decimal_value: None,
+                                list_values: None,
                                 service_id: service_id.clone(),
+                                transaction_id: None,
+                                signer: None,
                             },
                         ]),
                         lat_long_value: None,
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                     ReportedValue {
                         id: None,
@@ -3935,7 +5249,12 @@ mod test {
                         enum_value: None,
                         struct_values: None,
                         lat_long_value: Some(LatLongValue(2, 2)),
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                     ReportedValue {
                         id: None,
@@ -3953,7 +5272,12 @@ mod test {
                         enum_value: None,
                         struct_values: None,
                         lat_long_value: None,
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                     ReportedValue {
                         id: None,
@@ -3971,7 +5295,12 @@ mod test {
                         enum_value: Some(2),
                         struct_values: None,
                         lat_long_value: None,
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                     ReportedValue {
                         id: None,
@@ -3989,11 +5318,21 @@ mod test {
                         enum_value: None,
                         struct_values: None,
                         lat_long_value: None,
+                        timestamp_value: None,
+                        decimal_value: None,
+                        list_values: None,
                         service_id: service_id.clone(),
+                        transaction_id: None,
+                        signer: None,
                     },
                 ]),
                 lat_long_value: None,
+                timestamp_value: None,
+                decimal_value: None,
+                list_values: None,
                 service_id,
+                transaction_id: None,
+                signer: None,
             },
         ]
     }
@@ -33,6 +33,12 @@ pub struct OrganizationSlice {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 impl TryFrom<Organization> for OrganizationSlice {
@@ -55,6 +61,8 @@ impl TryFrom<Organization> for OrganizationSlice {
             address: organization.address.clone(),
             metadata,
             service_id: organization.service_id,
+            transaction_id: organization.transaction_id,
+            signer: organization.signer,
         })
     }
 }
@@ -16,12 +16,15 @@
  */
 
 use crate::rest_api::{
-    error::RestApiResponseError, routes::DbExecutor, AcceptServiceIdParam, AppState, QueryServiceId,
+    error::RestApiResponseError, routes::DbExecutor, AcceptServiceIdParam, AppState,
 };
 
 use actix::{Handler, Message, SyncContext};
 use actix_web::{web, HttpResponse};
-use grid_sdk::grid_db::products::store::{LatLongValue, Product, PropertyValue};
+use grid_sdk::grid_db::products::store::{
+    LatLongValue, Product, ProductStatusChange, PropertyValue,
+};
+use grid_sdk::protocol::addressing::{compute_custom_product_address, compute_gs1_product_address};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -29,11 +32,25 @@ pub struct ProductSlice {
     pub product_id: String,
     pub product_address: String,
     pub product_namespace: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub schema_name: String,
     pub owner: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub pending_owner: String,
+    pub schema_version: i32,
+    pub status: String,
     pub properties: Vec<ProductPropertyValueSlice>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 impl From<Product> for ProductSlice {
@@ -42,13 +59,19 @@ impl From<Product> for ProductSlice {
             product_id: product.product_id.clone(),
             product_address: product.product_address.clone(),
             product_namespace: product.product_namespace.clone(),
+            schema_name: product.schema_name.clone(),
             owner: product.owner.clone(),
+            pending_owner: product.pending_owner.clone(),
+            schema_version: product.schema_version,
+            status: product.status.clone(),
             properties: product
                 .properties
                 .into_iter()
                 .map(ProductPropertyValueSlice::from)
                 .collect(),
             service_id: product.service_id,
+            transaction_id: product.transaction_id,
+            signer: product.signer,
         }
     }
 }
@@ -67,6 +90,12 @@ pub struct ProductPropertyValueSlice {
     pub enum_value: Option<i32>,
     pub struct_values: Vec<ProductPropertyValueSlice>,
     pub lat_long_value: Option<LatLongSlice>,
+    #[serde(default)]
+    pub timestamp_value: Option<i64>,
+    #[serde(default)]
+    pub decimal_value: Option<String>,
+    #[serde(default)]
+    pub list_values: Vec<ProductPropertyValueSlice>,
 }
 
 impl From<PropertyValue> for ProductPropertyValueSlice {
@@ -86,6 +115,13 @@ impl From<PropertyValue> for ProductPropertyValueSlice {
                 .map(ProductPropertyValueSlice::from)
                 .collect(),
             lat_long_value: property_value.lat_long_value.map(LatLongSlice::from),
+            timestamp_value: property_value.timestamp_value,
+            decimal_value: property_value.decimal_value.clone(),
+            list_values: property_value
+                .list_values
+                .into_iter()
+                .map(ProductPropertyValueSlice::from)
+                .collect(),
         }
     }
 }
@@ -105,7 +141,18 @@ impl From<LatLongValue> for LatLongSlice {
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ProductListQuery {
+    pub product_namespace: Option<String>,
+    pub schema_name: Option<String>,
+    pub status: Option<String>,
+    pub service_id: Option<String>,
+}
+
 struct ListProducts {
+    product_namespace: Option<String>,
+    schema_name: Option<String>,
+    status: Option<String>,
     service_id: Option<String>,
 }
 
@@ -119,7 +166,12 @@ impl Handler<ListProducts> for DbExecutor {
     fn handle(&mut self, msg: ListProducts, _: &mut SyncContext<Self>) -> Self::Result {
         Ok(self
             .product_store
-            .list_products(msg.service_id.as_deref())?
+            .list_products(
+                msg.product_namespace.as_deref(),
+                msg.schema_name.as_deref(),
+                msg.status.as_deref(),
+                msg.service_id.as_deref(),
+            )?
             .into_iter()
             .map(ProductSlice::from)
             .collect())
@@ -128,20 +180,50 @@ impl Handler<ListProducts> for DbExecutor {
 
 pub async fn list_products(
     state: web::Data<AppState>,
-    query: web::Query<QueryServiceId>,
+    query: web::Query<ProductListQuery>,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
     state
         .database_connection
         .send(ListProducts {
-            service_id: query.into_inner().service_id,
+            product_namespace: query.product_namespace,
+            schema_name: query.schema_name,
+            status: query.status,
+            service_id: query.service_id,
         })
         .await?
         .map(|products| HttpResponse::Ok().json(products))
 }
 
+/// Identifies a single product. Products are GS1 products unless the custom schema they follow
+/// is given, since products of different schemas may share an ID.
+#[derive(Debug, Deserialize)]
+pub struct ProductQuery {
+    pub product_namespace: Option<String>,
+    pub schema_name: Option<String>,
+    pub service_id: Option<String>,
+}
+
+fn product_address(product_id: &str, query: &ProductQuery) -> Result<String, RestApiResponseError> {
+    match query.product_namespace.as_deref().unwrap_or("GS1") {
+        "GS1" => Ok(compute_gs1_product_address(product_id)),
+        "Custom" => match query.schema_name.as_deref() {
+            Some(schema_name) => Ok(compute_custom_product_address(schema_name, product_id)),
+            None => Err(RestApiResponseError::BadRequest(
+                "Custom products require a schema_name".to_string(),
+            )),
+        },
+        unknown => Err(RestApiResponseError::BadRequest(format!(
+            "Unrecognized product namespace: {}",
+            unknown
+        ))),
+    }
+}
+
 struct FetchProduct {
     product_id: String,
+    product_address: String,
     service_id: Option<String>,
 }
 
@@ -155,7 +237,7 @@ impl Handler<FetchProduct> for DbExecutor {
     fn handle(&mut self, msg: FetchProduct, _: &mut SyncContext<Self>) -> Self::Result {
         match self
             .product_store
-            .fetch_product(&msg.product_id, msg.service_id.as_deref())?
+            .fetch_product(&msg.product_address, msg.service_id.as_deref())?
         {
             Some(product) => Ok(ProductSlice::from(product)),
             None => Err(RestApiResponseError::NotFoundError(format!(
@@ -169,15 +251,98 @@ impl Handler<FetchProduct> for DbExecutor {
 pub async fn fetch_product(
     state: web::Data<AppState>,
     product_id: web::Path<String>,
-    query: web::Query<QueryServiceId>,
+    query: web::Query<ProductQuery>,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
+    let product_id = product_id.into_inner();
+    let query = query.into_inner();
+    let product_address = product_address(&product_id, &query)?;
     state
         .database_connection
         .send(FetchProduct {
-            product_id: product_id.into_inner(),
-            service_id: query.into_inner().service_id,
+            product_id,
+            product_address,
+            service_id: query.service_id,
         })
         .await?
         .map(|product| HttpResponse::Ok().json(product))
 }
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductStatusChangeSlice {
+    pub status: String,
+    pub start_commit_num: i64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+}
+
+impl From<ProductStatusChange> for ProductStatusChangeSlice {
+    fn from(change: ProductStatusChange) -> Self {
+        Self {
+            status: change.status,
+            start_commit_num: change.start_commit_num,
+            transaction_id: change.transaction_id,
+            signer: change.signer,
+        }
+    }
+}
+
+struct ListProductStatusChanges {
+    product_id: String,
+    product_address: String,
+    service_id: Option<String>,
+}
+
+impl Message for ListProductStatusChanges {
+    type Result = Result<Vec<ProductStatusChangeSlice>, RestApiResponseError>;
+}
+
+impl Handler<ListProductStatusChanges> for DbExecutor {
+    type Result = Result<Vec<ProductStatusChangeSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListProductStatusChanges, _: &mut SyncContext<Self>) -> Self::Result {
+        let service_id = msg.service_id.as_deref();
+        if self
+            .product_store
+            .fetch_product(&msg.product_address, service_id)?
+            .is_none()
+        {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find product with id: {}",
+                msg.product_id
+            )));
+        }
+
+        Ok(self
+            .product_store
+            .list_product_status_changes(&msg.product_address, service_id)?
+            .into_iter()
+            .map(ProductStatusChangeSlice::from)
+            .collect())
+    }
+}
+
+/// Returns the lifecycle states a product has been in, oldest first
+pub async fn list_product_status_history(
+    state: web::Data<AppState>,
+    product_id: web::Path<String>,
+    query: web::Query<ProductQuery>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let product_id = product_id.into_inner();
+    let query = query.into_inner();
+    let product_address = product_address(&product_id, &query)?;
+    state
+        .database_connection
+        .send(ListProductStatusChanges {
+            product_id,
+            product_address,
+            service_id: query.service_id,
+        })
+        .await?
+        .map(|changes| HttpResponse::Ok().json(changes))
+}
@@ -13,17 +13,24 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::rest_api::{
-    error::RestApiResponseError, routes::DbExecutor, AcceptServiceIdParam, AppState, QueryServiceId,
+    error::RestApiResponseError,
+    routes::{DbExecutor, Feature, FeatureCollection, Geometry},
+    AcceptServiceIdParam, AppState, QueryServiceId,
 };
 
 use actix::{Handler, Message, SyncContext};
 use actix_web::{web, HttpResponse};
+use grid_sdk::grid_db::geo::{is_valid_lat_long, BoundingBox};
 use grid_sdk::grid_db::track_and_trace::store::{
-    AssociatedAgent, LatLongValue, Property, Proposal, Record,
+    ArchivedReportedValue, AssociatedAgent, LatLongValue, Property, Proposal, Record,
+    RecordAggregation, RecordPosition, ReportedValueBucket, ReportedValueRange,
     ReportedValueReporterToAgentMetadata, TrackAndTraceStore,
 };
+use grid_sdk::protocol::schema::state::{DataType, PropertyValue};
+use grid_sdk::protos::FromBytes;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -56,38 +63,91 @@ pub struct ProposalSlice {
     pub timestamp: u64,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 impl From<Proposal> for ProposalSlice {
     fn from(proposal: Proposal) -> Self {
+        let status = if has_lapsed(&proposal) {
+            "Expired".to_string()
+        } else {
+            proposal.status.clone()
+        };
         Self {
             receiving_agent: proposal.receiving_agent.clone(),
             issuing_agent: proposal.issuing_agent.clone(),
             role: proposal.role.clone(),
             properties: proposal.properties.clone(),
-            status: proposal.status.clone(),
+            status,
             terms: proposal.terms.clone(),
             timestamp: proposal.timestamp as u64,
+            expires_at: proposal.expires_at.map(|expires_at| expires_at as u64),
             service_id: proposal.service_id,
+            transaction_id: proposal.transaction_id,
+            signer: proposal.signer,
         }
     }
 }
 
+/// An open proposal is only marked expired on chain when someone tries to accept it, so one whose
+/// expiry has already passed is reported as expired here.
+fn has_lapsed(proposal: &Proposal) -> bool {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default();
+
+    proposal.status.eq_ignore_ascii_case("open")
+        && proposal
+            .expires_at
+            .map(|expires_at| expires_at < now)
+            .unwrap_or(false)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RecordSlice {
     pub record_id: String,
     pub schema: String,
+    pub schema_version: i32,
     pub owner: String,
     pub custodian: String,
     pub properties: Vec<PropertySlice>,
     pub r#final: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_namespace: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub product_schema_name: Option<String>,
     pub proposals: Vec<ProposalSlice>,
     pub owner_updates: Vec<AssociatedAgentSlice>,
     pub custodian_updates: Vec<AssociatedAgentSlice>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 impl RecordSlice {
@@ -115,6 +175,7 @@ impl RecordSlice {
         Self {
             record_id: record.record_id.clone(),
             schema: record.schema.clone(),
+            schema_version: record.schema_version,
             owner: match owner_updates.last() {
                 Some(owner) => owner.agent_id.clone(),
                 None => "".to_string(),
@@ -125,15 +186,34 @@ impl RecordSlice {
             },
             properties: properties.to_vec(),
             r#final: record.final_,
+            archived: record.archived,
+            product_id: record.product_id,
+            location_id: record.location_id,
+            product_namespace: record.product_namespace,
+            product_schema_name: record.product_schema_name,
             proposals: proposals.into_iter().map(ProposalSlice::from).collect(),
             owner_updates,
             custodian_updates,
             service_id: record.service_id,
+            transaction_id: record.transaction_id,
+            signer: record.signer,
         }
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RecordListQuery {
+    #[serde(default)]
+    pub include_archived: bool,
+    pub product_id: Option<String>,
+    pub location_id: Option<String>,
+    pub service_id: Option<String>,
+}
+
 struct ListRecords {
+    include_archived: bool,
+    product_id: Option<String>,
+    location_id: Option<String>,
     service_id: Option<String>,
 }
 
@@ -145,7 +225,12 @@ impl Handler<ListRecords> for DbExecutor {
     type Result = Result<Vec<RecordSlice>, RestApiResponseError>;
 
     fn handle(&mut self, msg: ListRecords, _: &mut SyncContext<Self>) -> Self::Result {
-        let records = self.tnt_store.list_records(msg.service_id.as_deref())?;
+        let records = self.tnt_store.list_records(
+            msg.include_archived,
+            msg.product_id.as_deref(),
+            msg.location_id.as_deref(),
+            msg.service_id.as_deref(),
+        )?;
 
         let record_ids: Vec<String> = records
             .iter()
@@ -201,13 +286,17 @@ impl Handler<ListRecords> for DbExecutor {
 
 pub async fn list_records(
     state: web::Data<AppState>,
-    query: web::Query<QueryServiceId>,
+    query: web::Query<RecordListQuery>,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
     state
         .database_connection
         .send(ListRecords {
-            service_id: query.into_inner().service_id,
+            include_archived: query.include_archived,
+            product_id: query.product_id,
+            location_id: query.location_id,
+            service_id: query.service_id,
         })
         .await?
         .map(|records| HttpResponse::Ok().json(records))
@@ -286,6 +375,117 @@ pub async fn fetch_record(
         .map(|record| HttpResponse::Ok().json(record))
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordAggregationSlice {
+    pub parent_record_id: String,
+    pub child_record_id: String,
+    pub aggregated_at: u64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disaggregated_at: Option<u64>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+impl From<RecordAggregation> for RecordAggregationSlice {
+    fn from(aggregation: RecordAggregation) -> Self {
+        Self {
+            parent_record_id: aggregation.parent_record_id,
+            child_record_id: aggregation.child_record_id,
+            aggregated_at: aggregation.aggregated_at as u64,
+            disaggregated_at: aggregation.disaggregated_at.map(|at| at as u64),
+            service_id: aggregation.service_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecordLineageSlice {
+    pub record_id: String,
+    /// The record this record is currently aggregated into
+    pub parent: Option<String>,
+    /// The records currently aggregated into this record
+    pub children: Vec<String>,
+    /// Every aggregation, current or past, among the record's ancestors and descendants
+    pub aggregations: Vec<RecordAggregationSlice>,
+}
+
+impl RecordLineageSlice {
+    pub fn from_models(record_id: String, aggregations: Vec<RecordAggregation>) -> Self {
+        let current = aggregations
+            .iter()
+            .filter(|aggregation| aggregation.disaggregated_at.is_none());
+
+        let parent = current
+            .clone()
+            .find(|aggregation| aggregation.child_record_id == record_id)
+            .map(|aggregation| aggregation.parent_record_id.clone());
+        let children = current
+            .filter(|aggregation| aggregation.parent_record_id == record_id)
+            .map(|aggregation| aggregation.child_record_id.clone())
+            .collect();
+
+        Self {
+            record_id,
+            parent,
+            children,
+            aggregations: aggregations
+                .into_iter()
+                .map(RecordAggregationSlice::from)
+                .collect(),
+        }
+    }
+}
+
+struct FetchRecordLineage {
+    record_id: String,
+    service_id: Option<String>,
+}
+
+impl Message for FetchRecordLineage {
+    type Result = Result<RecordLineageSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchRecordLineage> for DbExecutor {
+    type Result = Result<RecordLineageSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchRecordLineage, _: &mut SyncContext<Self>) -> Self::Result {
+        if self
+            .tnt_store
+            .fetch_record(&msg.record_id, msg.service_id.as_deref())?
+            .is_none()
+        {
+            return Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find record with id: {}",
+                msg.record_id
+            )));
+        }
+
+        let aggregations = self
+            .tnt_store
+            .list_record_lineage(&msg.record_id, msg.service_id.as_deref())?;
+
+        Ok(RecordLineageSlice::from_models(msg.record_id, aggregations))
+    }
+}
+
+pub async fn fetch_record_lineage(
+    state: web::Data<AppState>,
+    record_id: web::Path<String>,
+    query: web::Query<QueryServiceId>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchRecordLineage {
+            record_id: record_id.into_inner(),
+            service_id: query.into_inner().service_id,
+        })
+        .await?
+        .map(|lineage| HttpResponse::Ok().json(lineage))
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PropertySlice {
     pub name: String,
@@ -294,9 +494,20 @@ pub struct PropertySlice {
     pub reporters: Vec<String>,
     pub updates: Vec<PropertyValueSlice>,
     pub value: Option<PropertyValueSlice>,
+    /// Every archived value of the property, including those on pages that have since been
+    /// reused; only included when a single property is fetched
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<ArchivedValueSlice>>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 impl PropertySlice {
     pub fn from_model(
@@ -313,7 +524,10 @@ impl PropertySlice {
             reporters: reporters.to_vec(),
             updates: updates.to_vec(),
             value,
+            history: None,
             service_id: property.service_id.clone(),
+            transaction_id: property.transaction_id.clone(),
+            signer: property.signer.clone(),
         }
     }
 }
@@ -326,6 +540,48 @@ pub struct PropertyValueSlice {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchivedValueSlice {
+    pub timestamp: u64,
+    pub value: Value,
+    pub reporter_index: u32,
+    /// The public key of the reporter, if the reporter is still known
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reporter: Option<String>,
+    pub page: u32,
+    pub generation: u64,
+}
+
+impl ArchivedValueSlice {
+    pub fn from_model(
+        archived_value: &ArchivedReportedValue,
+        reporter: Option<String>,
+    ) -> Result<ArchivedValueSlice, RestApiResponseError> {
+        let value = PropertyValue::from_bytes(&archived_value.value).map_err(|err| {
+            RestApiResponseError::DatabaseError(format!(
+                "Unable to parse archived reported value: {}",
+                err
+            ))
+        })?;
+
+        Ok(ArchivedValueSlice {
+            timestamp: archived_value.timestamp as u64,
+            value: parse_property_value(&value),
+            reporter_index: archived_value.reporter_index as u32,
+            reporter,
+            page: archived_value.page_num as u32,
+            generation: archived_value.generation as u64,
+        })
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -338,6 +594,7 @@ pub enum Value {
     Number(i64),
     Enum(i32),
     Bytes(String),
+    List(Vec<Value>),
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -402,6 +659,8 @@ impl PropertyValueSlice {
                 service_id: reported_value_with_reporter.service_id.clone(),
             },
             service_id: reported_value_with_reporter.service_id.clone(),
+            transaction_id: reported_value_with_reporter.transaction_id.clone(),
+            signer: reported_value_with_reporter.signer.clone(),
         })
     }
 }
@@ -478,6 +737,28 @@ fn parse_value(
 
             Ok(Value::Struct(value))
         }
+        "Timestamp" => {
+            let timestamp_value = val.timestamp_value.ok_or_else(|| {
+                RestApiResponseError::DatabaseError(
+                    "ReportedValue is of Timestamp data_type, but is missing timestamp value"
+                        .to_string(),
+                )
+            })?;
+
+            Ok(Value::Number(timestamp_value))
+        }
+        "Decimal" => {
+            // Decimals are returned as strings so no precision is lost
+            let decimal_value = val.decimal_value.clone().ok_or_else(|| {
+                RestApiResponseError::DatabaseError(
+                    "ReportedValue is of Decimal data_type, but is missing decimal value"
+                        .to_string(),
+                )
+            })?;
+
+            Ok(Value::String(decimal_value))
+        }
+        "List" => Ok(Value::List(parse_list_values(&val.list_values)?)),
         _ => Err(RestApiResponseError::DatabaseError(format!(
             "Invalid data type in ReportedValue: {}",
             val.data_type
@@ -485,9 +766,20 @@ fn parse_value(
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct RecordPropertyQuery {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub offset: Option<u64>,
+    pub limit: Option<u64>,
+    pub latest: Option<u64>,
+    pub service_id: Option<String>,
+}
+
 struct FetchRecordProperty {
     record_id: String,
     property_name: String,
+    range: ReportedValueRange,
     service_id: Option<String>,
 }
 
@@ -498,15 +790,23 @@ impl Message for FetchRecordProperty {
 pub async fn fetch_record_property(
     state: web::Data<AppState>,
     params: web::Path<(String, String)>,
-    query: web::Query<QueryServiceId>,
+    query: web::Query<RecordPropertyQuery>,
     _: AcceptServiceIdParam,
 ) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
     state
         .database_connection
         .send(FetchRecordProperty {
             record_id: params.0.clone(),
             property_name: params.1.clone(),
-            service_id: query.into_inner().service_id,
+            range: ReportedValueRange {
+                from: query.from.map(|from| from as i64),
+                to: query.to.map(|to| to as i64),
+                offset: query.offset.map(|offset| offset as i64),
+                limit: query.limit.map(|limit| limit as i64),
+                latest: query.latest.map(|latest| latest as i64),
+            },
+            service_id: query.service_id,
         })
         .await?
         .map(|record| HttpResponse::Ok().json(record))
@@ -530,12 +830,370 @@ impl Handler<FetchRecordProperty> for DbExecutor {
                 ))
             })?;
 
-        parse_property_slice(
+        let mut property_slice = parse_property_slice(
             &self.tnt_store,
             &property,
             &data_type,
             msg.service_id.as_deref(),
-        )
+        )?;
+
+        let reporters = self.tnt_store.list_reporters(
+            &msg.record_id,
+            &msg.property_name,
+            msg.service_id.as_deref(),
+        )?;
+
+        let history = self
+            .tnt_store
+            .list_archived_reported_values(
+                &msg.record_id,
+                &msg.property_name,
+                &msg.range,
+                msg.service_id.as_deref(),
+            )?
+            .iter()
+            .map(|archived_value| {
+                let reporter = reporters
+                    .iter()
+                    .find(|reporter| reporter.reporter_index == archived_value.reporter_index)
+                    .map(|reporter| reporter.public_key.clone());
+                ArchivedValueSlice::from_model(archived_value, reporter)
+            })
+            .collect::<Result<Vec<ArchivedValueSlice>, _>>()?;
+
+        property_slice.history = Some(history);
+
+        Ok(property_slice)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReportedValueBucketSlice {
+    pub start: u64,
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+impl From<ReportedValueBucket> for ReportedValueBucketSlice {
+    fn from(bucket: ReportedValueBucket) -> Self {
+        Self {
+            start: bucket.start as u64,
+            count: bucket.count as u64,
+            min: bucket.min,
+            max: bucket.max,
+            avg: bucket.avg,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordPropertyAggregateQuery {
+    pub bucket: u64,
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub latest: Option<u64>,
+    pub service_id: Option<String>,
+}
+
+struct AggregateRecordProperty {
+    record_id: String,
+    property_name: String,
+    bucket: u64,
+    range: ReportedValueRange,
+    service_id: Option<String>,
+}
+
+impl Message for AggregateRecordProperty {
+    type Result = Result<Vec<ReportedValueBucketSlice>, RestApiResponseError>;
+}
+
+pub async fn aggregate_record_property(
+    state: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+    query: web::Query<RecordPropertyAggregateQuery>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
+    state
+        .database_connection
+        .send(AggregateRecordProperty {
+            record_id: params.0.clone(),
+            property_name: params.1.clone(),
+            bucket: query.bucket,
+            range: ReportedValueRange {
+                from: query.from.map(|from| from as i64),
+                to: query.to.map(|to| to as i64),
+                latest: query.latest.map(|latest| latest as i64),
+                ..ReportedValueRange::default()
+            },
+            service_id: query.service_id,
+        })
+        .await?
+        .map(|buckets| HttpResponse::Ok().json(buckets))
+}
+
+impl Handler<AggregateRecordProperty> for DbExecutor {
+    type Result = Result<Vec<ReportedValueBucketSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: AggregateRecordProperty, _: &mut SyncContext<Self>) -> Self::Result {
+        if msg.bucket == 0 || msg.bucket > i64::MAX as u64 {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "Invalid bucket size {}",
+                msg.bucket
+            )));
+        }
+
+        let (_, data_type) = self
+            .tnt_store
+            .fetch_property_with_data_type(
+                &msg.record_id,
+                &msg.property_name,
+                msg.service_id.as_deref(),
+            )?
+            .ok_or_else(|| {
+                RestApiResponseError::NotFoundError(format!(
+                    "Could not find property {} for record {}",
+                    msg.property_name, msg.record_id
+                ))
+            })?;
+
+        if data_type.as_deref() != Some("Number") {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "Property {} of record {} is not a Number property",
+                msg.property_name, msg.record_id
+            )));
+        }
+
+        Ok(self
+            .tnt_store
+            .aggregate_reported_values(
+                &msg.record_id,
+                &msg.property_name,
+                msg.bucket as i64,
+                &msg.range,
+                msg.service_id.as_deref(),
+            )?
+            .into_iter()
+            .map(ReportedValueBucketSlice::from)
+            .collect())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RecordPositionProperties {
+    pub record_id: String,
+    pub property_name: String,
+    pub timestamp: u64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+}
+
+impl From<RecordPosition> for Feature<RecordPositionProperties> {
+    fn from(position: RecordPosition) -> Self {
+        Self {
+            geometry: Geometry::point(position.lat_long_value.0, position.lat_long_value.1),
+            properties: RecordPositionProperties {
+                record_id: position.record_id,
+                property_name: position.property_name,
+                timestamp: position.timestamp as u64,
+                service_id: position.service_id,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordsWithinQuery {
+    pub min_latitude: i64,
+    pub min_longitude: i64,
+    pub max_latitude: i64,
+    pub max_longitude: i64,
+    /// The LatLong property holding each record's position
+    #[serde(default = "default_position_property")]
+    pub property: String,
+    pub service_id: Option<String>,
+}
+
+fn default_position_property() -> String {
+    "location".to_string()
+}
+
+struct ListRecordsWithin {
+    property_name: String,
+    bounding_box: BoundingBox,
+    service_id: Option<String>,
+}
+
+impl Message for ListRecordsWithin {
+    type Result = Result<FeatureCollection<RecordPositionProperties>, RestApiResponseError>;
+}
+
+pub async fn list_records_within(
+    state: web::Data<AppState>,
+    query: web::Query<RecordsWithinQuery>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
+    state
+        .database_connection
+        .send(ListRecordsWithin {
+            property_name: query.property,
+            bounding_box: BoundingBox {
+                min_latitude: query.min_latitude,
+                min_longitude: query.min_longitude,
+                max_latitude: query.max_latitude,
+                max_longitude: query.max_longitude,
+            },
+            service_id: query.service_id,
+        })
+        .await?
+        .map(|records| HttpResponse::Ok().json(records))
+}
+
+impl Handler<ListRecordsWithin> for DbExecutor {
+    type Result = Result<FeatureCollection<RecordPositionProperties>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListRecordsWithin, _: &mut SyncContext<Self>) -> Self::Result {
+        let bounding_box = &msg.bounding_box;
+        if !is_valid_lat_long(bounding_box.min_latitude, bounding_box.min_longitude)
+            || !is_valid_lat_long(bounding_box.max_latitude, bounding_box.max_longitude)
+            || bounding_box.min_latitude > bounding_box.max_latitude
+        {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "Invalid bounding box: {:?}",
+                bounding_box
+            )));
+        }
+
+        Ok(FeatureCollection {
+            features: self
+                .tnt_store
+                .list_records_within(&msg.property_name, bounding_box, msg.service_id.as_deref())?
+                .into_iter()
+                .map(Feature::from)
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RoutePropertiesSlice {
+    pub record_id: String,
+    pub property_name: String,
+    /// The time each position was reported, in the order of the line's coordinates
+    pub timestamps: Vec<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecordPropertyRouteQuery {
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub latest: Option<u64>,
+    pub service_id: Option<String>,
+}
+
+struct FetchRecordPropertyRoute {
+    record_id: String,
+    property_name: String,
+    range: ReportedValueRange,
+    service_id: Option<String>,
+}
+
+impl Message for FetchRecordPropertyRoute {
+    type Result = Result<Feature<RoutePropertiesSlice>, RestApiResponseError>;
+}
+
+pub async fn fetch_record_property_route(
+    state: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+    query: web::Query<RecordPropertyRouteQuery>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
+    state
+        .database_connection
+        .send(FetchRecordPropertyRoute {
+            record_id: params.0.clone(),
+            property_name: params.1.clone(),
+            range: ReportedValueRange {
+                from: query.from.map(|from| from as i64),
+                to: query.to.map(|to| to as i64),
+                latest: query.latest.map(|latest| latest as i64),
+                ..ReportedValueRange::default()
+            },
+            service_id: query.service_id,
+        })
+        .await?
+        .map(|route| HttpResponse::Ok().json(route))
+}
+
+impl Handler<FetchRecordPropertyRoute> for DbExecutor {
+    type Result = Result<Feature<RoutePropertiesSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchRecordPropertyRoute, _: &mut SyncContext<Self>) -> Self::Result {
+        let (_, data_type) = self
+            .tnt_store
+            .fetch_property_with_data_type(
+                &msg.record_id,
+                &msg.property_name,
+                msg.service_id.as_deref(),
+            )?
+            .ok_or_else(|| {
+                RestApiResponseError::NotFoundError(format!(
+                    "Could not find property {} for record {}",
+                    msg.property_name, msg.record_id
+                ))
+            })?;
+
+        if data_type.as_deref() != Some("LatLong") {
+            return Err(RestApiResponseError::BadRequest(format!(
+                "Property {} of record {} is not a LatLong property",
+                msg.property_name, msg.record_id
+            )));
+        }
+
+        let positions = self
+            .tnt_store
+            .list_archived_reported_values(
+                &msg.record_id,
+                &msg.property_name,
+                &msg.range,
+                msg.service_id.as_deref(),
+            )?
+            .iter()
+            .map(|archived_value| {
+                let value = PropertyValue::from_bytes(&archived_value.value).map_err(|err| {
+                    RestApiResponseError::DatabaseError(format!(
+                        "Unable to parse archived reported value: {}",
+                        err
+                    ))
+                })?;
+                Ok((
+                    archived_value.timestamp as u64,
+                    *value.lat_long_value().latitude(),
+                    *value.lat_long_value().longitude(),
+                ))
+            })
+            .collect::<Result<Vec<(u64, i64, i64)>, RestApiResponseError>>()?;
+
+        Ok(Feature {
+            geometry: Geometry::line_string(
+                positions
+                    .iter()
+                    .map(|(_, latitude, longitude)| (*latitude, *longitude)),
+            ),
+            properties: RoutePropertiesSlice {
+                record_id: msg.record_id,
+                property_name: msg.property_name,
+                timestamps: positions
+                    .iter()
+                    .map(|(timestamp, _, _)| *timestamp)
+                    .collect(),
+            },
+        })
     }
 }
 
@@ -611,6 +1269,40 @@ fn parse_reported_values(
     PropertyValueSlice::from_model(&reported_value, struct_values)
 }
 
+fn parse_property_value(value: &PropertyValue) -> Value {
+    match value.data_type() {
+        DataType::Bytes => Value::Bytes(base64::encode(value.bytes_value())),
+        DataType::Boolean => Value::Bool(*value.boolean_value()),
+        DataType::Number => Value::Number(*value.number_value()),
+        DataType::String => Value::String(value.string_value().to_string()),
+        DataType::Enum => Value::Enum(*value.enum_value() as i32),
+        DataType::Struct => Value::Struct(
+            value
+                .struct_values()
+                .iter()
+                .map(|struct_value| StructPropertyValue {
+                    name: struct_value.name().to_string(),
+                    data_type: format!("{:?}", struct_value.data_type()),
+                    value: parse_property_value(struct_value),
+                })
+                .collect(),
+        ),
+        DataType::LatLong => Value::LatLong(LatLong {
+            latitude: *value.lat_long_value().latitude(),
+            longitude: *value.lat_long_value().longitude(),
+        }),
+        DataType::Timestamp => Value::Number(*value.timestamp_value() as i64),
+        DataType::Decimal => Value::String(value.decimal_value().to_string()),
+        DataType::List => Value::List(
+            value
+                .list_values()
+                .iter()
+                .map(parse_property_value)
+                .collect(),
+        ),
+    }
+}
+
 fn parse_struct_values(
     record_id: &str,
     struct_values: &[ReportedValueReporterToAgentMetadata],
@@ -637,3 +1329,24 @@ fn parse_struct_values(
     }
     Ok(inner_values)
 }
+
+fn parse_list_values(
+    list_values: &[ReportedValueReporterToAgentMetadata],
+) -> Result<Vec<Value>, RestApiResponseError> {
+    list_values
+        .iter()
+        .map(|element| {
+            let struct_values = if element.data_type == "Struct" {
+                Some(parse_struct_values(
+                    &element.record_id,
+                    &element.struct_values,
+                    element.service_id.as_deref(),
+                )?)
+            } else {
+                None
+            };
+
+            parse_value(element, struct_values)
+        })
+        .collect()
+}
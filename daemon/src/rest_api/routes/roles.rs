@@ -0,0 +1,146 @@
+// Copyright 2019 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::rest_api::{
+    error::RestApiResponseError, routes::DbExecutor, AcceptServiceIdParam, AppState, QueryServiceId,
+};
+
+use actix::{Handler, Message, SyncContext};
+use actix_web::{web, HttpResponse};
+use grid_sdk::grid_db::organizations::store::Role;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoleSlice {
+    pub org_id: String,
+    pub name: String,
+    pub description: String,
+    pub active: bool,
+    pub permissions: Vec<String>,
+    pub allowed_organizations: Vec<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+}
+
+impl From<Role> for RoleSlice {
+    fn from(role: Role) -> Self {
+        Self {
+            org_id: role.org_id,
+            name: role.name,
+            description: role.description,
+            active: role.active,
+            permissions: role.permissions,
+            allowed_organizations: role.allowed_organizations,
+            service_id: role.service_id,
+            transaction_id: role.transaction_id,
+            signer: role.signer,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RoleListQuery {
+    pub org_id: Option<String>,
+    pub service_id: Option<String>,
+}
+
+struct ListRoles {
+    org_id: Option<String>,
+    service_id: Option<String>,
+}
+
+impl Message for ListRoles {
+    type Result = Result<Vec<RoleSlice>, RestApiResponseError>;
+}
+
+impl Handler<ListRoles> for DbExecutor {
+    type Result = Result<Vec<RoleSlice>, RestApiResponseError>;
+
+    fn handle(&mut self, msg: ListRoles, _: &mut SyncContext<Self>) -> Self::Result {
+        Ok(self
+            .organization_store
+            .list_roles(msg.org_id.as_deref(), msg.service_id.as_deref())?
+            .into_iter()
+            .map(RoleSlice::from)
+            .collect())
+    }
+}
+
+pub async fn list_roles(
+    state: web::Data<AppState>,
+    query: web::Query<RoleListQuery>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    let query = query.into_inner();
+    state
+        .database_connection
+        .send(ListRoles {
+            org_id: query.org_id,
+            service_id: query.service_id,
+        })
+        .await?
+        .map(|roles| HttpResponse::Ok().json(roles))
+}
+
+struct FetchRole {
+    org_id: String,
+    name: String,
+    service_id: Option<String>,
+}
+
+impl Message for FetchRole {
+    type Result = Result<RoleSlice, RestApiResponseError>;
+}
+
+impl Handler<FetchRole> for DbExecutor {
+    type Result = Result<RoleSlice, RestApiResponseError>;
+
+    fn handle(&mut self, msg: FetchRole, _: &mut SyncContext<Self>) -> Self::Result {
+        match self.organization_store.fetch_role(
+            &msg.org_id,
+            &msg.name,
+            msg.service_id.as_deref(),
+        )? {
+            Some(role) => Ok(RoleSlice::from(role)),
+            None => Err(RestApiResponseError::NotFoundError(format!(
+                "Could not find role {} for organization {}",
+                msg.name, msg.org_id
+            ))),
+        }
+    }
+}
+
+pub async fn fetch_role(
+    state: web::Data<AppState>,
+    params: web::Path<(String, String)>,
+    query: web::Query<QueryServiceId>,
+    _: AcceptServiceIdParam,
+) -> Result<HttpResponse, RestApiResponseError> {
+    state
+        .database_connection
+        .send(FetchRole {
+            org_id: params.0.clone(),
+            name: params.1.clone(),
+            service_id: query.into_inner().service_id,
+        })
+        .await?
+        .map(|role| HttpResponse::Ok().json(role))
+}
@@ -26,10 +26,17 @@ pub struct GridSchemaSlice {
     pub name: String,
     pub description: String,
     pub owner: String,
+    pub version: i32,
     pub properties: Vec<GridPropertyDefinitionSlice>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
 }
 
 impl From<Schema> for GridSchemaSlice {
@@ -38,12 +45,15 @@ impl From<Schema> for GridSchemaSlice {
             name: schema.name.clone(),
             description: schema.description.clone(),
             owner: schema.owner.clone(),
+            version: schema.version,
             properties: schema
                 .properties
                 .into_iter()
                 .map(GridPropertyDefinitionSlice::from)
                 .collect(),
             service_id: schema.service_id,
+            transaction_id: schema.transaction_id,
+            signer: schema.signer,
         }
     }
 }
@@ -59,6 +69,11 @@ pub struct GridPropertyDefinitionSlice {
     pub enum_options: Vec<String>,
     pub struct_properties: Vec<GridPropertyDefinitionSlice>,
     #[serde(default)]
+    pub deprecated: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_data_type: Option<String>,
+    #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
 }
@@ -78,6 +93,8 @@ impl From<PropertyDefinition> for GridPropertyDefinitionSlice {
                 .into_iter()
                 .map(GridPropertyDefinitionSlice::from)
                 .collect(),
+            deprecated: definition.deprecated,
+            list_data_type: definition.list_data_type,
             service_id: definition.service_id,
         }
     }
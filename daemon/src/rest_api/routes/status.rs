@@ -0,0 +1,52 @@
+// Copyright 2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use actix_web::{web, HttpResponse};
+use serde::{Deserialize, Serialize};
+
+use crate::event::EventScope;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatusSlice {
+    pub version: String,
+    pub scope: ScopeSlice,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScopeSlice {
+    pub entities: Vec<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub services: Option<Vec<String>>,
+}
+
+impl From<&EventScope> for StatusSlice {
+    fn from(scope: &EventScope) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            scope: ScopeSlice {
+                entities: scope
+                    .families()
+                    .iter()
+                    .map(|family| family.to_string())
+                    .collect(),
+                services: scope.services().map(|services| services.to_vec()),
+            },
+        }
+    }
+}
+
+pub async fn get_status(status: web::Data<StatusSlice>) -> HttpResponse {
+    HttpResponse::Ok().json(status.get_ref())
+}
@@ -15,6 +15,7 @@
  * -----------------------------------------------------------------------------
  */
 
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
@@ -22,15 +23,22 @@ use protobuf::Message as _;
 
 use sawtooth_sdk::{
     messages::{
+        client_block::{
+            ClientBlockGetByIdRequest, ClientBlockGetResponse, ClientBlockGetResponse_Status,
+        },
         client_event::{
             ClientEventsSubscribeRequest, ClientEventsSubscribeResponse,
             ClientEventsSubscribeResponse_Status,
         },
+        client_receipt::{
+            ClientReceiptGetRequest, ClientReceiptGetResponse, ClientReceiptGetResponse_Status,
+        },
         events::{
             Event as SawtoothEvent, EventFilter, EventFilter_FilterType,
             EventList as SawtoothEventList, EventSubscription,
         },
         network::PingResponse as SawtoothPingResponse,
+        transaction::TransactionHeader,
         transaction_receipt::{
             StateChange as SawtoothStateChange, StateChangeList,
             StateChange_Type as SawtoothStateChange_Type,
@@ -44,7 +52,8 @@ use sawtooth_sdk::{
 };
 
 use crate::event::{
-    CommitEvent, EventConnection, EventConnectionUnsubscriber, EventIoError, StateChange,
+    CommitEvent, EventConnection, EventConnectionUnsubscriber, EventIoError, Provenance,
+    StateChange,
 };
 
 use super::connection::SawtoothConnection;
@@ -103,7 +112,7 @@ impl EventConnection for SawtoothConnection {
         loop {
             match self.get_receiver().recv() {
                 Ok(Ok(msg)) if msg.get_message_type() == Message_MessageType::CLIENT_EVENTS => {
-                    break extract_event(msg)
+                    break extract_event(msg).map(|event| add_provenance(&self.get_sender(), event))
                 }
                 Ok(Ok(msg)) if msg.get_message_type() == Message_MessageType::PING_REQUEST => {
                     self.get_sender().send(
@@ -342,6 +351,129 @@ fn get_sawtooth_state_changes_from_sawtooth_event(
         })
 }
 
+/// Attaches the ID and signer of the transaction that made each state change in the block.
+///
+/// State delta events only carry the net changes of a block, so the block and its transaction
+/// receipts are fetched from the validator. If they are unavailable, the state changes are left
+/// without provenance.
+fn add_provenance(sender: &ZmqMessageSender, mut event: CommitEvent) -> CommitEvent {
+    match fetch_provenance(sender, &event.id) {
+        Ok(provenance) => {
+            for state_change in event.state_changes.iter_mut() {
+                let state_change_provenance = provenance.get(state_change.key()).cloned();
+                state_change.set_provenance(state_change_provenance);
+            }
+        }
+        Err(err) => warn!(
+            "Unable to determine the provenance of changes in block {}: {}",
+            event.id, err
+        ),
+    }
+
+    event
+}
+
+/// Returns the provenance of every address written in the given block.
+fn fetch_provenance(
+    sender: &ZmqMessageSender,
+    block_id: &str,
+) -> Result<HashMap<String, Provenance>, EventIoError> {
+    let mut block_request = ClientBlockGetByIdRequest::new();
+    block_request.set_block_id(block_id.into());
+    let block_response: ClientBlockGetResponse = send_request(
+        sender,
+        Message_MessageType::CLIENT_BLOCK_GET_BY_ID_REQUEST,
+        Message_MessageType::CLIENT_BLOCK_GET_RESPONSE,
+        &block_request,
+    )?;
+    if block_response.get_status() != ClientBlockGetResponse_Status::OK {
+        return Err(EventIoError::InvalidMessage(format!(
+            "unable to fetch block: {:?}",
+            block_response.get_status()
+        )));
+    }
+
+    let transactions = block_response
+        .get_block()
+        .get_batches()
+        .iter()
+        .flat_map(|batch| batch.get_transactions().iter())
+        .map(|transaction| {
+            protobuf::parse_from_bytes::<TransactionHeader>(transaction.get_header())
+                .map(|header| Provenance {
+                    transaction_id: transaction.get_header_signature().to_string(),
+                    signer: header.get_signer_public_key().to_string(),
+                })
+                .map_err(|err| {
+                    EventIoError::InvalidMessage(format!(
+                        "unable to parse transaction header: {}",
+                        err
+                    ))
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if transactions.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut receipt_request = ClientReceiptGetRequest::new();
+    receipt_request.set_transaction_ids(
+        transactions
+            .iter()
+            .map(|provenance| provenance.transaction_id.clone())
+            .collect::<Vec<_>>()
+            .into(),
+    );
+    let receipt_response: ClientReceiptGetResponse = send_request(
+        sender,
+        Message_MessageType::CLIENT_RECEIPT_GET_REQUEST,
+        Message_MessageType::CLIENT_RECEIPT_GET_RESPONSE,
+        &receipt_request,
+    )?;
+    if receipt_response.get_status() != ClientReceiptGetResponse_Status::OK {
+        return Err(EventIoError::InvalidMessage(format!(
+            "unable to fetch transaction receipts: {:?}",
+            receipt_response.get_status()
+        )));
+    }
+
+    let receipts = receipt_response
+        .get_receipts()
+        .iter()
+        .map(|receipt| (receipt.get_transaction_id(), receipt))
+        .collect::<HashMap<_, _>>();
+
+    // Transactions are visited in block order, so each address ends up with its last writer
+    let mut provenance = HashMap::new();
+    for transaction in transactions {
+        if let Some(receipt) = receipts.get(transaction.transaction_id.as_str()) {
+            for state_change in receipt.get_state_changes() {
+                provenance.insert(state_change.get_address().to_string(), transaction.clone());
+            }
+        }
+    }
+
+    Ok(provenance)
+}
+
+fn send_request<Req: protobuf::Message, Resp: protobuf::Message>(
+    sender: &ZmqMessageSender,
+    request_type: Message_MessageType,
+    response_type: Message_MessageType,
+    request: &Req,
+) -> Result<Resp, EventIoError> {
+    let mut future = sender.send(
+        request_type,
+        &correlation_id(),
+        &request.write_to_bytes().map_err(|err| {
+            EventIoError::ConnectionError(format!("Failed to serialize request: {}", err))
+        })?,
+    )?;
+
+    content_of_type(response_type, future.get()?)
+}
+
 fn sawtooth_state_changes_into_native_state_changes(
     sawtooth_state_changes: Vec<SawtoothStateChange>,
 ) -> Result<Vec<StateChange>, EventIoError> {
@@ -362,8 +494,12 @@ impl TryInto<StateChange> for SawtoothStateChange {
             SawtoothStateChange_Type::SET => Ok(StateChange::Set {
                 key: self.address,
                 value: self.value,
+                provenance: None,
+            }),
+            SawtoothStateChange_Type::DELETE => Ok(StateChange::Delete {
+                key: self.address,
+                provenance: None,
             }),
-            SawtoothStateChange_Type::DELETE => Ok(StateChange::Delete { key: self.address }),
         }
     }
 }
@@ -431,9 +567,11 @@ mod tests {
                 SawtoothStateChange_Type::SET => StateChange::Set {
                     key: sawtooth_state_change.get_address().into(),
                     value: sawtooth_state_change.get_value().into(),
+                    provenance: None,
                 },
                 SawtoothStateChange_Type::DELETE => StateChange::Delete {
                     key: sawtooth_state_change.get_address().into(),
+                    provenance: None,
                 },
                 _ => panic!("Sawtooth state change type unset"),
             };
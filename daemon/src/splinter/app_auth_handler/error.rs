@@ -37,6 +37,7 @@ pub enum AppAuthHandlerError {
     ScabbardEventConnectionError(ScabbardEventConnectionError),
     EventIoError(EventIoError),
     EventProcessorError(String),
+    DatabaseError(String),
     SabreError(String),
     SigningError(String),
     BatchSubmitError(String),
@@ -53,6 +54,7 @@ impl Error for AppAuthHandlerError {
             AppAuthHandlerError::ScabbardEventConnectionError(err) => Some(err),
             AppAuthHandlerError::EventIoError(err) => Some(err),
             AppAuthHandlerError::EventProcessorError(_) => None,
+            AppAuthHandlerError::DatabaseError(_) => None,
             AppAuthHandlerError::SabreError(_) => None,
             AppAuthHandlerError::SigningError(_) => None,
             AppAuthHandlerError::BatchSubmitError(_) => None,
@@ -77,6 +79,9 @@ impl fmt::Display for AppAuthHandlerError {
             AppAuthHandlerError::EventProcessorError(msg) => {
                 write!(f, "Event processor error: {}", msg)
             }
+            AppAuthHandlerError::DatabaseError(msg) => {
+                write!(f, "A database error occurred: {}", msg)
+            }
             AppAuthHandlerError::SabreError(msg) => write!(
                 f,
                 "An error occurred while building a Sabre payload: {}",
@@ -21,12 +21,13 @@ mod sabre;
 
 use std::collections::HashMap;
 
+use grid_sdk::grid_db::CommitStore;
 use splinter::{
     admin::messages::AdminServiceEvent,
     events::{Igniter, ParseBytes, ParseError, WebSocketClient, WebSocketError, WsResponse},
 };
 
-use crate::event::{EventHandler, EventProcessor};
+use crate::event::{EventHandler, EventProcessor, EventScope};
 use crate::splinter::{
     app_auth_handler::{error::AppAuthHandlerError, node::get_node_id, sabre::setup_grid},
     event::ScabbardEventConnectionFactory,
@@ -58,7 +59,9 @@ impl ParseBytes<AdminEvent> for AdminEvent {
 pub fn run(
     splinterd_url: String,
     event_connection_factory: ScabbardEventConnectionFactory,
-    handler: Box<dyn EventHandler + Sync>,
+    scope: EventScope,
+    commit_store: Box<dyn CommitStore>,
+    handlers: Vec<Box<dyn EventHandler + Sync>>,
     igniter: Igniter,
     scabbard_admin_key: String,
 ) -> Result<(), AppAuthHandlerError> {
@@ -70,7 +73,12 @@ pub fn run(
         if let Err(err) = process_admin_event(
             event,
             &event_connection_factory,
-            handler.cloned_box(),
+            &scope,
+            &*commit_store,
+            handlers
+                .iter()
+                .map(|handler| handler.cloned_box())
+                .collect(),
             &node_id,
             &scabbard_admin_key,
             &splinterd_url,
@@ -107,7 +115,9 @@ pub fn run(
 fn process_admin_event(
     event: AdminEvent,
     event_connection_factory: &ScabbardEventConnectionFactory,
-    handler: Box<dyn EventHandler>,
+    scope: &EventScope,
+    commit_store: &dyn CommitStore,
+    handlers: Vec<Box<dyn EventHandler>>,
     node_id: &str,
     scabbard_admin_key: &str,
     splinterd_url: &str,
@@ -151,11 +161,24 @@ fn process_admin_event(
                     })
                 })?;
 
-            let event_connection = event_connection_factory
-                .create_connection(&msg_proposal.circuit_id, &service.service_id)?;
-
-            EventProcessor::start(event_connection, None, vec![handler])
-                .map_err(|err| AppAuthHandlerError::EventProcessorError(err.0))?;
+            let source = format!("{}::{}", msg_proposal.circuit_id, service.service_id);
+            if scope.includes_service(Some(&source)) {
+                let event_connection = event_connection_factory
+                    .create_connection(&msg_proposal.circuit_id, &service.service_id)?;
+
+                let last_commit_id = commit_store
+                    .get_checkpoint(Some(&source))
+                    .map_err(|err| AppAuthHandlerError::DatabaseError(err.to_string()))?
+                    .map(|checkpoint| checkpoint.commit_id);
+
+                EventProcessor::start(event_connection, last_commit_id.as_deref(), scope, handlers)
+                    .map_err(|err| AppAuthHandlerError::EventProcessorError(err.0))?;
+            } else {
+                info!(
+                    "Not ingesting events from {}: outside of the configured scope",
+                    source
+                );
+            }
 
             setup_grid(
                 scabbard_admin_key,
@@ -18,6 +18,9 @@
 use std::convert::TryInto;
 use std::time::Duration;
 
+use grid_sdk::protocol::addressing::{
+    GRID_LOCATION_PREFIX, GRID_PRODUCT_PREFIX, GRID_SCHEMA_PREFIX, PIKE_NAMESPACE,
+};
 use sabre_sdk::protocol::payload::{
     CreateContractActionBuilder, CreateContractRegistryActionBuilder,
     CreateNamespaceRegistryActionBuilder, CreateNamespaceRegistryPermissionActionBuilder,
@@ -38,22 +41,18 @@ use crate::splinter::app_auth_handler::error::AppAuthHandlerError;
 const SCABBARD_SUBMISSION_WAIT_SECS: u64 = 10;
 
 // Pike constants
-const PIKE_PREFIX: &str = "cad11d";
 const PIKE_CONTRACT_NAME: &str = "grid-pike";
 const PIKE_CONTRACT_VERSION_REQ: &str = "0.1.0-dev";
 
 // Product constants
-const PRODUCT_PREFIX: &str = "621dee02";
 const PRODUCT_CONTRACT_NAME: &str = "grid-product";
 const PRODUCT_CONTRACT_VERSION_REQ: &str = "0.1.0-dev";
 
 // Schema constants
-const SCHEMA_PREFIX: &str = "621dee01";
 const SCHEMA_CONTRACT_NAME: &str = "grid-schema";
 const SCHEMA_CONTRACT_VERSION_REQ: &str = "0.1.0-dev";
 
 // Location constants
-const LOCATION_PREFIX: &str = "621dee04";
 const LOCATION_CONTRACT_NAME: &str = "grid-location";
 const LOCATION_CONTRACT_VERSION_REQ: &str = "0.1.0-dev";
 
@@ -84,10 +83,10 @@ pub fn setup_grid(
     )?;
     let pike_contract_registry_txn =
         make_contract_registry_txn(&signer, &pike_contract.metadata.name)?;
-    let pike_contract_txn = make_upload_contract_txn(&signer, &pike_contract, PIKE_PREFIX)?;
-    let pike_namespace_registry_txn = make_namespace_registry_txn(&signer, PIKE_PREFIX)?;
+    let pike_contract_txn = make_upload_contract_txn(&signer, &pike_contract, PIKE_NAMESPACE)?;
+    let pike_namespace_registry_txn = make_namespace_registry_txn(&signer, PIKE_NAMESPACE)?;
     let pike_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &pike_contract, PIKE_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &pike_contract, PIKE_NAMESPACE)?;
 
     // Make Product transactions
     let product_contract = SmartContractArchive::from_scar_file(
@@ -98,14 +97,14 @@ pub fn setup_grid(
     let product_contract_registry_txn =
         make_contract_registry_txn(&signer, &product_contract.metadata.name)?;
     let product_contract_txn =
-        make_upload_contract_txn(&signer, &product_contract, PRODUCT_PREFIX)?;
-    let product_namespace_registry_txn = make_namespace_registry_txn(&signer, PRODUCT_PREFIX)?;
+        make_upload_contract_txn(&signer, &product_contract, GRID_PRODUCT_PREFIX)?;
+    let product_namespace_registry_txn = make_namespace_registry_txn(&signer, GRID_PRODUCT_PREFIX)?;
     let product_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &product_contract, PRODUCT_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &product_contract, GRID_PRODUCT_PREFIX)?;
     let product_pike_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &product_contract, PIKE_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &product_contract, PIKE_NAMESPACE)?;
     let product_schema_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &product_contract, SCHEMA_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &product_contract, GRID_SCHEMA_PREFIX)?;
 
     // Make Location transactions
     let location_contract = SmartContractArchive::from_scar_file(
@@ -116,14 +115,15 @@ pub fn setup_grid(
     let location_contract_registry_txn =
         make_contract_registry_txn(&signer, &location_contract.metadata.name)?;
     let location_contract_txn =
-        make_upload_contract_txn(&signer, &location_contract, LOCATION_PREFIX)?;
-    let location_namespace_registry_txn = make_namespace_registry_txn(&signer, LOCATION_PREFIX)?;
+        make_upload_contract_txn(&signer, &location_contract, GRID_LOCATION_PREFIX)?;
+    let location_namespace_registry_txn =
+        make_namespace_registry_txn(&signer, GRID_LOCATION_PREFIX)?;
     let location_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &location_contract, LOCATION_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &location_contract, GRID_LOCATION_PREFIX)?;
     let location_pike_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &location_contract, PIKE_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &location_contract, PIKE_NAMESPACE)?;
     let location_schema_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &location_contract, SCHEMA_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &location_contract, GRID_SCHEMA_PREFIX)?;
 
     // Make schema transactions
     let schema_contract = SmartContractArchive::from_scar_file(
@@ -133,12 +133,13 @@ pub fn setup_grid(
     )?;
     let schema_contract_registry_txn =
         make_contract_registry_txn(&signer, &schema_contract.metadata.name)?;
-    let schema_contract_txn = make_upload_contract_txn(&signer, &schema_contract, SCHEMA_PREFIX)?;
-    let schema_namespace_registry_txn = make_namespace_registry_txn(&signer, SCHEMA_PREFIX)?;
+    let schema_contract_txn =
+        make_upload_contract_txn(&signer, &schema_contract, GRID_SCHEMA_PREFIX)?;
+    let schema_namespace_registry_txn = make_namespace_registry_txn(&signer, GRID_SCHEMA_PREFIX)?;
     let schema_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &schema_contract, SCHEMA_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &schema_contract, GRID_SCHEMA_PREFIX)?;
     let schema_pike_namespace_permissions_txn =
-        make_namespace_permissions_txn(&signer, &schema_contract, PIKE_PREFIX)?;
+        make_namespace_permissions_txn(&signer, &schema_contract, PIKE_NAMESPACE)?;
 
     let txns = vec![
         pike_contract_registry_txn,
@@ -199,7 +200,7 @@ fn make_upload_contract_txn(
     contract: &SmartContractArchive,
     contract_prefix: &str,
 ) -> Result<Transaction, AppAuthHandlerError> {
-    let action_addresses = vec![PIKE_PREFIX.into(), contract_prefix.into()];
+    let action_addresses = vec![PIKE_NAMESPACE.into(), contract_prefix.into()];
     Ok(CreateContractActionBuilder::new()
         .with_name(contract.metadata.name.clone())
         .with_version(contract.metadata.version.clone())
@@ -87,6 +87,7 @@ pub struct ScabbardEventConnection {
     connection_url: String,
     igniter: Igniter,
     connection_state: RefCell<ConnectionState>,
+    namespaces: Vec<String>,
 }
 
 impl ScabbardEventConnection {
@@ -96,6 +97,7 @@ impl ScabbardEventConnection {
             connection_url,
             igniter,
             connection_state: RefCell::new(ConnectionState::Disconnected),
+            namespaces: vec![],
         }
     }
 }
@@ -109,9 +111,13 @@ impl EventConnection for ScabbardEventConnection {
 
     fn subscribe(
         &mut self,
-        _namespaces: &[&str],
+        namespaces: &[&str],
         last_commit_id: Option<&str>,
     ) -> Result<Self::Unsubscriber, EventIoError> {
+        // Scabbard does not support filtering events by address, so the namespaces are applied
+        // as the events are received
+        self.namespaces = namespaces.iter().map(|ns| ns.to_string()).collect();
+
         let (sender, receiver) = sync_channel(128);
 
         let source = self.name.clone();
@@ -176,11 +182,23 @@ impl EventConnection for ScabbardEventConnection {
                     state_changes: scabbard_evt
                         .state_changes
                         .into_iter()
+                        // Scabbard's state change events do not identify the transaction or
+                        // signer that made each change
                         .map(|state_change| match state_change {
-                            ScabbardStateChange::Set { key, value } => {
-                                StateChange::Set { key, value }
-                            }
-                            ScabbardStateChange::Delete { key } => StateChange::Delete { key },
+                            ScabbardStateChange::Set { key, value } => StateChange::Set {
+                                key,
+                                value,
+                                provenance: None,
+                            },
+                            ScabbardStateChange::Delete { key } => StateChange::Delete {
+                                key,
+                                provenance: None,
+                            },
+                        })
+                        .filter(|state_change| {
+                            self.namespaces
+                                .iter()
+                                .any(|namespace| state_change.key_has_prefix(namespace))
                         })
                         .collect(),
                 }),
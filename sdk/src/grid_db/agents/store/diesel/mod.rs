@@ -23,6 +23,7 @@ use super::{Agent, AgentStore, AgentStoreError, Role};
 use crate::database::DatabaseError;
 use crate::grid_db::commits::MAX_COMMIT_NUM;
 use operations::add_agent::AgentStoreAddAgentOperation as _;
+use operations::delete_agent::AgentStoreDeleteAgentOperation as _;
 use operations::fetch_agent::AgentStoreFetchAgentOperation as _;
 use operations::list_agents::AgentStoreListAgentsOperation as _;
 use operations::update_agent::AgentStoreUpdateAgentOperation as _;
@@ -92,6 +93,21 @@ impl AgentStore for DieselAgentStore<diesel::pg::PgConnection> {
         })?)
         .update_agent(agent.clone().into(), make_role_models(&agent))
     }
+
+    fn delete_agent(
+        &self,
+        pub_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        AgentStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .delete_agent(pub_key, service_id, current_commit_num)
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -139,6 +155,21 @@ impl AgentStore for DieselAgentStore<diesel::sqlite::SqliteConnection> {
         })?)
         .update_agent(agent.clone().into(), make_role_models(&agent))
     }
+
+    fn delete_agent(
+        &self,
+        pub_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        AgentStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .delete_agent(pub_key, service_id, current_commit_num)
+    }
 }
 
 impl From<RoleModel> for Role {
@@ -167,6 +198,8 @@ impl From<(AgentModel, Vec<RoleModel>)> for Agent {
             start_commit_num: agent_model.start_commit_num,
             end_commit_num: agent_model.end_commit_num,
             service_id: agent_model.service_id,
+            transaction_id: agent_model.transaction_id,
+            signer: agent_model.signer,
         }
     }
 }
@@ -181,6 +214,8 @@ impl Into<NewAgentModel> for Agent {
             start_commit_num: self.start_commit_num,
             end_commit_num: MAX_COMMIT_NUM,
             service_id: self.service_id,
+            transaction_id: self.transaction_id,
+            signer: self.signer,
         }
     }
 }
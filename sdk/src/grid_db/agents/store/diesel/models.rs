@@ -27,6 +27,8 @@ pub struct NewAgentModel {
     pub end_commit_num: i64,
 
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -44,6 +46,8 @@ pub struct AgentModel {
     pub end_commit_num: i64,
 
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -0,0 +1,201 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::AgentStoreOperations;
+use crate::grid_db::agents::store::diesel::{
+    schema::{agent, role},
+    AgentStoreError,
+};
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+
+use diesel::{dsl::update, prelude::*};
+
+pub(in crate::grid_db::agents) trait AgentStoreDeleteAgentOperation {
+    fn delete_agent(
+        &self,
+        public_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AgentStoreDeleteAgentOperation for AgentStoreOperations<'a, diesel::pg::PgConnection> {
+    fn delete_agent(
+        &self,
+        public_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, AgentStoreError, _>(|| {
+                pg::end_agent(&*self.conn, public_key, service_id, current_commit_num)?;
+                pg::end_roles(&*self.conn, public_key, service_id, current_commit_num)?;
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AgentStoreDeleteAgentOperation
+    for AgentStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn delete_agent(
+        &self,
+        public_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        self.conn
+            .immediate_transaction::<_, AgentStoreError, _>(|| {
+                sqlite::end_agent(&*self.conn, public_key, service_id, current_commit_num)?;
+                sqlite::end_roles(&*self.conn, public_key, service_id, current_commit_num)?;
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(feature = "postgres")]
+mod pg {
+    use super::*;
+
+    pub fn end_agent(
+        conn: &PgConnection,
+        public_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        let mut query = update(agent::table)
+            .filter(
+                agent::public_key
+                    .eq(public_key)
+                    .and(agent::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .into_boxed();
+
+        if let Some(service_id) = service_id {
+            query = query.filter(agent::service_id.eq(service_id));
+        } else {
+            query = query.filter(agent::service_id.is_null());
+        }
+
+        query
+            .set(agent::end_commit_num.eq(current_commit_num))
+            .execute(conn)
+            .map(|_| ())
+            .map_err(|err| AgentStoreError::OperationError {
+                context: "Failed to delete agent".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+
+    pub fn end_roles(
+        conn: &PgConnection,
+        public_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        let mut query = update(role::table)
+            .filter(
+                role::public_key
+                    .eq(public_key)
+                    .and(role::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .into_boxed();
+
+        if let Some(service_id) = service_id {
+            query = query.filter(role::service_id.eq(service_id));
+        } else {
+            query = query.filter(role::service_id.is_null());
+        }
+
+        query
+            .set(role::end_commit_num.eq(current_commit_num))
+            .execute(conn)
+            .map(|_| ())
+            .map_err(|err| AgentStoreError::OperationError {
+                context: "Failed to delete agent roles".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::*;
+
+    pub fn end_agent(
+        conn: &SqliteConnection,
+        public_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        let mut query = update(agent::table)
+            .filter(
+                agent::public_key
+                    .eq(public_key)
+                    .and(agent::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .into_boxed();
+
+        if let Some(service_id) = service_id {
+            query = query.filter(agent::service_id.eq(service_id));
+        } else {
+            query = query.filter(agent::service_id.is_null());
+        }
+
+        query
+            .set(agent::end_commit_num.eq(current_commit_num))
+            .execute(conn)
+            .map(|_| ())
+            .map_err(|err| AgentStoreError::OperationError {
+                context: "Failed to delete agent".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+
+    pub fn end_roles(
+        conn: &SqliteConnection,
+        public_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        let mut query = update(role::table)
+            .filter(
+                role::public_key
+                    .eq(public_key)
+                    .and(role::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .into_boxed();
+
+        if let Some(service_id) = service_id {
+            query = query.filter(role::service_id.eq(service_id));
+        } else {
+            query = query.filter(role::service_id.is_null());
+        }
+
+        query
+            .set(role::end_commit_num.eq(current_commit_num))
+            .execute(conn)
+            .map(|_| ())
+            .map_err(|err| AgentStoreError::OperationError {
+                context: "Failed to delete agent roles".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
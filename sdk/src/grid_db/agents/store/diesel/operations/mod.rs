@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub(super) mod add_agent;
+pub(super) mod delete_agent;
 pub(super) mod fetch_agent;
 pub(super) mod list_agents;
 pub(super) mod update_agent;
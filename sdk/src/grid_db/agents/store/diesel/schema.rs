@@ -22,6 +22,8 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
     }
 }
 
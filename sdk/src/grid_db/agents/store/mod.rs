@@ -31,6 +31,8 @@ pub struct Agent {
     pub end_commit_num: i64,
 
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 /// Represents a Grid Agent Role
@@ -77,6 +79,20 @@ pub trait AgentStore: Send + Sync {
     ///
     ///  * `agent` - The updated agent to add
     fn update_agent(&self, agent: Agent) -> Result<(), AgentStoreError>;
+
+    /// Ends the current version of an agent and its roles
+    ///
+    /// # Arguments
+    ///
+    ///  * `pub_key` - The public key of the agent to delete
+    ///  * `service_id` - The service id of the agent to delete
+    ///  * `current_commit_num` - The commit number the agent was deleted in
+    fn delete_agent(
+        &self,
+        pub_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError>;
 }
 
 impl<AS> AgentStore for Box<AS>
@@ -102,4 +118,13 @@ where
     fn update_agent(&self, agent: Agent) -> Result<(), AgentStoreError> {
         (**self).update_agent(agent)
     }
+
+    fn delete_agent(
+        &self,
+        pub_key: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), AgentStoreError> {
+        (**self).delete_agent(pub_key, service_id, current_commit_num)
+    }
 }
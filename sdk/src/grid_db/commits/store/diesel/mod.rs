@@ -18,15 +18,21 @@ pub(in crate::grid_db) mod schema;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
-use super::{Commit, CommitEvent, CommitEventError, CommitStore, CommitStoreError};
+use super::{Checkpoint, Commit, CommitEvent, CommitEventError, CommitStore, CommitStoreError};
 use crate::database::DatabaseError;
-use crate::grid_db::commits::store::diesel::models::{CommitModel, NewCommitModel};
+use crate::grid_db::commits::store::diesel::models::{
+    CheckpointModel, CommitModel, NewCheckpointModel, NewCommitModel,
+};
 use operations::add_commit::CommitStoreAddCommitOperation as _;
 use operations::create_db_commit_from_commit_event::CommitStoreCreateDbCommitFromCommitEventOperation as _;
+use operations::get_checkpoint::CommitStoreGetCheckpointOperation as _;
 use operations::get_commit_by_commit_num::CommitStoreGetCommitByCommitNumOperation as _;
 use operations::get_current_commit_id::CommitStoreGetCurrentCommitIdOperation as _;
 use operations::get_next_commit_num::CommitStoreGetNextCommitNumOperation as _;
+use operations::list_checkpoints::CommitStoreListCheckpointsOperation as _;
+use operations::reset_checkpoint::CommitStoreResetCheckpointOperation as _;
 use operations::resolve_fork::CommitStoreResolveForkOperation as _;
+use operations::update_checkpoint::CommitStoreUpdateCheckpointOperation as _;
 use operations::CommitStoreOperations;
 
 /// Manages creating commits in the database
@@ -115,6 +121,49 @@ impl CommitStore for DieselCommitStore<diesel::pg::PgConnection> {
         })?)
         .create_db_commit_from_commit_event(event)
     }
+
+    fn get_checkpoint(
+        &self,
+        service_id: Option<&str>,
+    ) -> Result<Option<Checkpoint>, CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .get_checkpoint(service_id)
+    }
+
+    fn list_checkpoints(&self) -> Result<Vec<Checkpoint>, CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_checkpoints()
+    }
+
+    fn update_checkpoint(&self, checkpoint: Checkpoint) -> Result<(), CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .update_checkpoint(checkpoint.into())
+    }
+
+    fn reset_checkpoint(&self, service_id: Option<&str>) -> Result<(), CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .reset_checkpoint(service_id)
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -184,6 +233,49 @@ impl CommitStore for DieselCommitStore<diesel::sqlite::SqliteConnection> {
         })?)
         .create_db_commit_from_commit_event(event)
     }
+
+    fn get_checkpoint(
+        &self,
+        service_id: Option<&str>,
+    ) -> Result<Option<Checkpoint>, CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .get_checkpoint(service_id)
+    }
+
+    fn list_checkpoints(&self) -> Result<Vec<Checkpoint>, CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_checkpoints()
+    }
+
+    fn update_checkpoint(&self, checkpoint: Checkpoint) -> Result<(), CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .update_checkpoint(checkpoint.into())
+    }
+
+    fn reset_checkpoint(&self, service_id: Option<&str>) -> Result<(), CommitStoreError> {
+        CommitStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .reset_checkpoint(service_id)
+    }
 }
 
 impl From<CommitModel> for Commit {
@@ -216,6 +308,26 @@ impl Into<NewCommitModel> for Commit {
     }
 }
 
+impl From<CheckpointModel> for Checkpoint {
+    fn from(checkpoint: CheckpointModel) -> Self {
+        Self {
+            service_id: checkpoint.service_id,
+            commit_id: checkpoint.commit_id,
+            commit_num: checkpoint.commit_num,
+        }
+    }
+}
+
+impl Into<NewCheckpointModel> for Checkpoint {
+    fn into(self) -> NewCheckpointModel {
+        NewCheckpointModel {
+            service_id: self.service_id,
+            commit_id: self.commit_id,
+            commit_num: self.commit_num,
+        }
+    }
+}
+
 pub trait CloneBoxCommitStore: CommitStore {
     fn clone_box(&self) -> Box<dyn CloneBoxCommitStore>;
 }
@@ -30,3 +30,20 @@ pub struct CommitModel {
     pub commit_num: i64,
     pub service_id: Option<String>,
 }
+
+#[derive(Insertable, PartialEq, Queryable)]
+#[table_name = "commit_checkpoint"]
+pub struct NewCheckpointModel {
+    pub service_id: Option<String>,
+    pub commit_id: String,
+    pub commit_num: i64,
+}
+
+#[derive(Queryable, PartialEq, Identifiable, Debug)]
+#[table_name = "commit_checkpoint"]
+pub struct CheckpointModel {
+    pub id: i64,
+    pub service_id: Option<String>,
+    pub commit_id: String,
+    pub commit_num: i64,
+}
@@ -0,0 +1,83 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CommitStoreOperations;
+use crate::grid_db::commits::store::diesel::{
+    models::CheckpointModel, schema::commit_checkpoint, Checkpoint, CommitStoreError,
+};
+
+use diesel::{prelude::*, result::Error::NotFound};
+
+pub(in crate::grid_db::commits) trait CommitStoreGetCheckpointOperation {
+    fn get_checkpoint(
+        &self,
+        service_id: Option<&str>,
+    ) -> Result<Option<Checkpoint>, CommitStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CommitStoreGetCheckpointOperation for CommitStoreOperations<'a, diesel::pg::PgConnection> {
+    fn get_checkpoint(
+        &self,
+        service_id: Option<&str>,
+    ) -> Result<Option<Checkpoint>, CommitStoreError> {
+        let mut query = commit_checkpoint::table
+            .into_boxed()
+            .select(commit_checkpoint::all_columns);
+
+        if let Some(service_id) = service_id {
+            query = query.filter(commit_checkpoint::service_id.eq(service_id));
+        } else {
+            query = query.filter(commit_checkpoint::service_id.is_null());
+        }
+
+        query
+            .first::<CheckpointModel>(self.conn)
+            .map(|checkpoint| Some(Checkpoint::from(checkpoint)))
+            .or_else(|err| if err == NotFound { Ok(None) } else { Err(err) })
+            .map_err(|err| CommitStoreError::OperationError {
+                context: "Failed to fetch checkpoint".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CommitStoreGetCheckpointOperation
+    for CommitStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn get_checkpoint(
+        &self,
+        service_id: Option<&str>,
+    ) -> Result<Option<Checkpoint>, CommitStoreError> {
+        let mut query = commit_checkpoint::table
+            .into_boxed()
+            .select(commit_checkpoint::all_columns);
+
+        if let Some(service_id) = service_id {
+            query = query.filter(commit_checkpoint::service_id.eq(service_id));
+        } else {
+            query = query.filter(commit_checkpoint::service_id.is_null());
+        }
+
+        query
+            .first::<CheckpointModel>(self.conn)
+            .map(|checkpoint| Some(Checkpoint::from(checkpoint)))
+            .or_else(|err| if err == NotFound { Ok(None) } else { Err(err) })
+            .map_err(|err| CommitStoreError::OperationError {
+                context: "Failed to fetch checkpoint".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CommitStoreOperations;
+use crate::grid_db::commits::store::diesel::{
+    models::CheckpointModel, schema::commit_checkpoint, Checkpoint, CommitStoreError,
+};
+
+use diesel::prelude::*;
+
+pub(in crate::grid_db::commits) trait CommitStoreListCheckpointsOperation {
+    fn list_checkpoints(&self) -> Result<Vec<Checkpoint>, CommitStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CommitStoreListCheckpointsOperation
+    for CommitStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_checkpoints(&self) -> Result<Vec<Checkpoint>, CommitStoreError> {
+        commit_checkpoint::table
+            .select(commit_checkpoint::all_columns)
+            .order_by(commit_checkpoint::service_id.asc())
+            .load::<CheckpointModel>(self.conn)
+            .map(|checkpoints| checkpoints.into_iter().map(Checkpoint::from).collect())
+            .map_err(|err| CommitStoreError::OperationError {
+                context: "Failed to list checkpoints".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CommitStoreListCheckpointsOperation
+    for CommitStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_checkpoints(&self) -> Result<Vec<Checkpoint>, CommitStoreError> {
+        commit_checkpoint::table
+            .select(commit_checkpoint::all_columns)
+            .order_by(commit_checkpoint::service_id.asc())
+            .load::<CheckpointModel>(self.conn)
+            .map(|checkpoints| checkpoints.into_iter().map(Checkpoint::from).collect())
+            .map_err(|err| CommitStoreError::OperationError {
+                context: "Failed to list checkpoints".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
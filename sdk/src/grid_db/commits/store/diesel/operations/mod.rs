@@ -14,10 +14,14 @@
 
 pub(super) mod add_commit;
 pub(super) mod create_db_commit_from_commit_event;
+pub(super) mod get_checkpoint;
 pub(super) mod get_commit_by_commit_num;
 pub(super) mod get_current_commit_id;
 pub(super) mod get_next_commit_num;
+pub(super) mod list_checkpoints;
+pub(super) mod reset_checkpoint;
 pub(super) mod resolve_fork;
+pub(super) mod update_checkpoint;
 
 pub(super) struct CommitStoreOperations<'a, C> {
     conn: &'a C,
@@ -0,0 +1,70 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CommitStoreOperations;
+use crate::grid_db::commits::store::diesel::{schema::commit_checkpoint, CommitStoreError};
+
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::grid_db::commits) trait CommitStoreResetCheckpointOperation {
+    fn reset_checkpoint(&self, service_id: Option<&str>) -> Result<(), CommitStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CommitStoreResetCheckpointOperation
+    for CommitStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn reset_checkpoint(&self, service_id: Option<&str>) -> Result<(), CommitStoreError> {
+        let result = if let Some(service_id) = service_id {
+            delete(commit_checkpoint::table)
+                .filter(commit_checkpoint::service_id.eq(service_id))
+                .execute(self.conn)
+        } else {
+            delete(commit_checkpoint::table)
+                .filter(commit_checkpoint::service_id.is_null())
+                .execute(self.conn)
+        };
+
+        result
+            .map(|_| ())
+            .map_err(|err| CommitStoreError::OperationError {
+                context: "Failed to reset checkpoint".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CommitStoreResetCheckpointOperation
+    for CommitStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn reset_checkpoint(&self, service_id: Option<&str>) -> Result<(), CommitStoreError> {
+        let result = if let Some(service_id) = service_id {
+            delete(commit_checkpoint::table)
+                .filter(commit_checkpoint::service_id.eq(service_id))
+                .execute(self.conn)
+        } else {
+            delete(commit_checkpoint::table)
+                .filter(commit_checkpoint::service_id.is_null())
+                .execute(self.conn)
+        };
+
+        result
+            .map(|_| ())
+            .map_err(|err| CommitStoreError::OperationError {
+                context: "Failed to reset checkpoint".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
@@ -15,6 +15,7 @@
 use super::CommitStoreOperations;
 use crate::grid_db::commits::store::diesel::{schema::chain_record, schema::commit};
 use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::track_and_trace::store::diesel::schema::reported_value_archive;
 
 use crate::grid_db::commits::store::CommitStoreError;
 
@@ -49,6 +50,16 @@ impl<'a> CommitStoreResolveForkOperation for CommitStoreOperations<'a, diesel::p
                 source: Some(Box::new(err)),
             })?;
 
+        // The archive is not versioned, so values archived on the fork are removed outright
+        delete(reported_value_archive::table)
+            .filter(reported_value_archive::commit_num.ge(commit_num))
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(|err| CommitStoreError::OperationError {
+                context: "Failed to resolve fork".to_string(),
+                source: Some(Box::new(err)),
+            })?;
+
         delete(commit::table)
             .filter(commit::commit_num.ge(commit_num))
             .execute(self.conn)
@@ -86,6 +97,16 @@ impl<'a> CommitStoreResolveForkOperation
                 source: Some(Box::new(err)),
             })?;
 
+        // The archive is not versioned, so values archived on the fork are removed outright
+        delete(reported_value_archive::table)
+            .filter(reported_value_archive::commit_num.ge(commit_num))
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(|err| CommitStoreError::OperationError {
+                context: "Failed to resolve fork".to_string(),
+                source: Some(Box::new(err)),
+            })?;
+
         delete(commit::table)
             .filter(commit::commit_num.ge(commit_num))
             .execute(self.conn)
@@ -0,0 +1,69 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::reset_checkpoint::CommitStoreResetCheckpointOperation as _;
+use super::CommitStoreOperations;
+use crate::grid_db::commits::store::diesel::{
+    models::NewCheckpointModel, schema::commit_checkpoint, CommitStoreError,
+};
+
+use diesel::{dsl::insert_into, prelude::*};
+
+pub(in crate::grid_db::commits) trait CommitStoreUpdateCheckpointOperation {
+    fn update_checkpoint(&self, checkpoint: NewCheckpointModel) -> Result<(), CommitStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CommitStoreUpdateCheckpointOperation
+    for CommitStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn update_checkpoint(&self, checkpoint: NewCheckpointModel) -> Result<(), CommitStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, CommitStoreError, _>(|| {
+                self.reset_checkpoint(checkpoint.service_id.as_deref())?;
+
+                insert_into(commit_checkpoint::table)
+                    .values(&checkpoint)
+                    .execute(self.conn)
+                    .map(|_| ())
+                    .map_err(|err| CommitStoreError::OperationError {
+                        context: "Failed to update checkpoint".to_string(),
+                        source: Some(Box::new(err)),
+                    })
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CommitStoreUpdateCheckpointOperation
+    for CommitStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn update_checkpoint(&self, checkpoint: NewCheckpointModel) -> Result<(), CommitStoreError> {
+        self.conn
+            .immediate_transaction::<_, CommitStoreError, _>(|| {
+                self.reset_checkpoint(checkpoint.service_id.as_deref())?;
+
+                insert_into(commit_checkpoint::table)
+                    .values(&checkpoint)
+                    .execute(self.conn)
+                    .map(|_| ())
+                    .map_err(|err| CommitStoreError::OperationError {
+                        context: "Failed to update checkpoint".to_string(),
+                        source: Some(Box::new(err)),
+                    })
+            })
+    }
+}
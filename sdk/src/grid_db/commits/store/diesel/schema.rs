@@ -29,3 +29,12 @@ table! {
         service_id -> Nullable<Text>,
     }
 }
+
+table! {
+    commit_checkpoint (id) {
+        id -> Int8,
+        service_id -> Nullable<Text>,
+        commit_id -> Text,
+        commit_num -> Int8,
+    }
+}
@@ -19,7 +19,7 @@ use std::sync::{Arc, Mutex};
 use super::CommitStore;
 use crate::grid_db::commits::store::{
     error::{CommitEventError, CommitStoreError},
-    ChainRecord, Commit, CommitEvent,
+    ChainRecord, Checkpoint, Commit, CommitEvent,
 };
 use crate::grid_db::commits::MAX_COMMIT_NUM;
 
@@ -29,6 +29,7 @@ use crate::grid_db::commits::MAX_COMMIT_NUM;
 pub struct MemoryCommitStore {
     inner_commit: Arc<Mutex<HashMap<String, Commit>>>,
     inner_cr: Arc<Mutex<HashMap<String, ChainRecord>>>,
+    inner_checkpoint: Arc<Mutex<HashMap<Option<String>, Checkpoint>>>,
 }
 
 impl MemoryCommitStore {
@@ -36,6 +37,7 @@ impl MemoryCommitStore {
         MemoryCommitStore {
             inner_commit: Arc::new(Mutex::new(HashMap::new())),
             inner_cr: Arc::new(Mutex::new(HashMap::new())),
+            inner_checkpoint: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -172,4 +174,59 @@ impl CommitStore for MemoryCommitStore {
             service_id,
         }))
     }
+
+    fn get_checkpoint(
+        &self,
+        service_id: Option<&str>,
+    ) -> Result<Option<Checkpoint>, CommitStoreError> {
+        let inner_checkpoint =
+            self.inner_checkpoint
+                .lock()
+                .map_err(|_| CommitStoreError::StorageError {
+                    context: "Cannot access checkpoints: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+
+        Ok(inner_checkpoint.get(&service_id.map(String::from)).cloned())
+    }
+
+    fn list_checkpoints(&self) -> Result<Vec<Checkpoint>, CommitStoreError> {
+        let inner_checkpoint =
+            self.inner_checkpoint
+                .lock()
+                .map_err(|_| CommitStoreError::StorageError {
+                    context: "Cannot access checkpoints: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+
+        let mut checkpoints = inner_checkpoint.values().cloned().collect::<Vec<_>>();
+        checkpoints.sort_by(|a, b| a.service_id.cmp(&b.service_id));
+        Ok(checkpoints)
+    }
+
+    fn update_checkpoint(&self, checkpoint: Checkpoint) -> Result<(), CommitStoreError> {
+        let mut inner_checkpoint =
+            self.inner_checkpoint
+                .lock()
+                .map_err(|_| CommitStoreError::StorageError {
+                    context: "Cannot access checkpoints: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+
+        inner_checkpoint.insert(checkpoint.service_id.clone(), checkpoint);
+        Ok(())
+    }
+
+    fn reset_checkpoint(&self, service_id: Option<&str>) -> Result<(), CommitStoreError> {
+        let mut inner_checkpoint =
+            self.inner_checkpoint
+                .lock()
+                .map_err(|_| CommitStoreError::StorageError {
+                    context: "Cannot access checkpoints: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+
+        inner_checkpoint.remove(&service_id.map(String::from));
+        Ok(())
+    }
 }
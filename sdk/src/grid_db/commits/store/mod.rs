@@ -34,11 +34,38 @@ pub struct ChainRecord {
     pub service_id: Option<String>,
 }
 
+/// The last commit that has been fully processed from a source
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct Checkpoint {
+    /// The source of the commits; `None` for Sawtooth
+    pub service_id: Option<String>,
+    pub commit_id: String,
+    pub commit_num: i64,
+}
+
+/// Identifies the transaction that caused a change to state
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Provenance {
+    /// The ID of the transaction, which is its header signature
+    pub transaction_id: String,
+    /// The public key of the transaction's signer
+    pub signer: String,
+}
+
 /// A change that has been applied to state, represented in terms of a key/value pair
+///
+/// The provenance is only present if the source of the change provides it.
 #[derive(Clone, Eq, PartialEq)]
 pub enum StateChange {
-    Set { key: String, value: Vec<u8> },
-    Delete { key: String },
+    Set {
+        key: String,
+        value: Vec<u8>,
+        provenance: Option<Provenance>,
+    },
+    Delete {
+        key: String,
+        provenance: Option<Provenance>,
+    },
 }
 
 // /// A notification that some source has committed a set of changes to state
@@ -99,6 +126,34 @@ pub trait CommitStore: Send + Sync {
         &self,
         event: &CommitEvent,
     ) -> Result<Option<Commit>, CommitEventError>;
+
+    /// Gets the checkpoint of a source from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `service_id` - The source of the commits, or `None` for Sawtooth
+    fn get_checkpoint(
+        &self,
+        service_id: Option<&str>,
+    ) -> Result<Option<Checkpoint>, CommitStoreError>;
+
+    /// Lists the checkpoints of all sources from the underlying storage
+    fn list_checkpoints(&self) -> Result<Vec<Checkpoint>, CommitStoreError>;
+
+    /// Records the last commit that was fully processed from a source, replacing any previous
+    /// checkpoint for that source
+    ///
+    /// # Arguments
+    ///
+    ///  * `checkpoint` - The new checkpoint
+    fn update_checkpoint(&self, checkpoint: Checkpoint) -> Result<(), CommitStoreError>;
+
+    /// Removes the checkpoint of a source, so that it will be processed from the beginning
+    ///
+    /// # Arguments
+    ///
+    ///  * `service_id` - The source of the commits, or `None` for Sawtooth
+    fn reset_checkpoint(&self, service_id: Option<&str>) -> Result<(), CommitStoreError>;
 }
 
 impl<CS> CommitStore for Box<CS>
@@ -134,4 +189,23 @@ where
     ) -> Result<Option<Commit>, CommitEventError> {
         (**self).create_db_commit_from_commit_event(event)
     }
+
+    fn get_checkpoint(
+        &self,
+        service_id: Option<&str>,
+    ) -> Result<Option<Checkpoint>, CommitStoreError> {
+        (**self).get_checkpoint(service_id)
+    }
+
+    fn list_checkpoints(&self) -> Result<Vec<Checkpoint>, CommitStoreError> {
+        (**self).list_checkpoints()
+    }
+
+    fn update_checkpoint(&self, checkpoint: Checkpoint) -> Result<(), CommitStoreError> {
+        (**self).update_checkpoint(checkpoint)
+    }
+
+    fn reset_checkpoint(&self, service_id: Option<&str>) -> Result<(), CommitStoreError> {
+        (**self).reset_checkpoint(service_id)
+    }
 }
@@ -0,0 +1,194 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Spatial helpers for LatLong values, which Grid stores as pairs of micro-degrees.
+//!
+//! Spatial queries narrow their candidates with a bounding box on the stored latitude and
+//! longitude columns and then filter by great-circle distance, so they work on any database
+//! without an extension such as PostGIS.
+
+use std::f64::consts::PI;
+
+/// The number of micro-degrees in a degree
+pub const MICRO_DEGREES_PER_DEGREE: f64 = 1_000_000.0;
+
+/// The mean radius of the Earth, in meters
+const EARTH_RADIUS: f64 = 6_371_008.8;
+
+const MAX_LATITUDE: i64 = 90_000_000;
+const MAX_LONGITUDE: i64 = 180_000_000;
+
+/// A box of latitudes and longitudes, in micro-degrees. A box whose minimum longitude is greater
+/// than its maximum longitude crosses the antimeridian.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub min_latitude: i64,
+    pub min_longitude: i64,
+    pub max_latitude: i64,
+    pub max_longitude: i64,
+}
+
+impl BoundingBox {
+    /// Returns the smallest box that contains every point within `radius` meters of the given
+    /// point
+    pub fn around(latitude: i64, longitude: i64, radius: f64) -> Self {
+        let angular_radius = radius / EARTH_RADIUS;
+        let lat = to_radians(latitude);
+        let min_lat = lat - angular_radius;
+        let max_lat = lat + angular_radius;
+
+        // If a pole is within the radius, every longitude is too
+        if min_lat <= -PI / 2.0 || max_lat >= PI / 2.0 {
+            return BoundingBox {
+                min_latitude: from_radians(min_lat).max(-MAX_LATITUDE),
+                min_longitude: -MAX_LONGITUDE,
+                max_latitude: from_radians(max_lat).min(MAX_LATITUDE),
+                max_longitude: MAX_LONGITUDE,
+            };
+        }
+
+        let long = to_radians(longitude);
+        let delta_long = (angular_radius.sin() / lat.cos()).asin();
+        let mut min_long = long - delta_long;
+        let mut max_long = long + delta_long;
+
+        if min_long < -PI {
+            min_long += 2.0 * PI;
+        }
+        if max_long > PI {
+            max_long -= 2.0 * PI;
+        }
+
+        BoundingBox {
+            min_latitude: from_radians(min_lat),
+            min_longitude: from_radians(min_long),
+            max_latitude: from_radians(max_lat),
+            max_longitude: from_radians(max_long),
+        }
+    }
+
+    /// Returns whether the box crosses the antimeridian
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.min_longitude > self.max_longitude
+    }
+
+    /// Returns whether the point lies within the box, including its edges
+    pub fn contains(&self, latitude: i64, longitude: i64) -> bool {
+        let within_longitude = if self.crosses_antimeridian() {
+            longitude >= self.min_longitude || longitude <= self.max_longitude
+        } else {
+            longitude >= self.min_longitude && longitude <= self.max_longitude
+        };
+
+        latitude >= self.min_latitude && latitude <= self.max_latitude && within_longitude
+    }
+}
+
+/// Returns the great-circle distance between two points, in meters
+pub fn distance(from: (i64, i64), to: (i64, i64)) -> f64 {
+    let (from_lat, from_long) = (to_radians(from.0), to_radians(from.1));
+    let (to_lat, to_long) = (to_radians(to.0), to_radians(to.1));
+
+    let a = ((to_lat - from_lat) / 2.0).sin().powi(2)
+        + from_lat.cos() * to_lat.cos() * ((to_long - from_long) / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+}
+
+/// Returns whether a latitude and longitude, in micro-degrees, are within their valid ranges
+pub fn is_valid_lat_long(latitude: i64, longitude: i64) -> bool {
+    latitude.abs() <= MAX_LATITUDE && longitude.abs() <= MAX_LONGITUDE
+}
+
+/// Converts micro-degrees to degrees
+pub fn to_degrees(micro_degrees: i64) -> f64 {
+    micro_degrees as f64 / MICRO_DEGREES_PER_DEGREE
+}
+
+fn to_radians(micro_degrees: i64) -> f64 {
+    to_degrees(micro_degrees).to_radians()
+}
+
+fn from_radians(radians: f64) -> i64 {
+    (radians.to_degrees() * MICRO_DEGREES_PER_DEGREE).round() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LONDON: (i64, i64) = (51_507_400, -127_800);
+    const PARIS: (i64, i64) = (48_856_600, 2_352_200);
+
+    #[test]
+    // This tests that the distance between London and Paris is about 344 km
+    fn distance_london_paris() {
+        let distance = distance(LONDON, PARIS);
+        assert!((distance - 343_500.0).abs() < 1_000.0, "{}", distance);
+        assert_eq!(distance, super::distance(PARIS, LONDON));
+    }
+
+    #[test]
+    // This tests that the distance from a point to itself is zero
+    fn distance_same_point() {
+        assert_eq!(distance(LONDON, LONDON), 0.0);
+    }
+
+    #[test]
+    // This tests that the box around a point contains the points within the radius and not
+    // points beyond it
+    fn bounding_box_around() {
+        let bounding_box = BoundingBox::around(LONDON.0, LONDON.1, 50_000.0);
+
+        assert!(bounding_box.contains(LONDON.0, LONDON.1));
+        assert!(!bounding_box.contains(PARIS.0, PARIS.1));
+        assert!(!bounding_box.crosses_antimeridian());
+
+        // Oxford is about 83 km from London
+        assert!(!bounding_box.contains(51_752_000, -1_257_800));
+        // A point about 56 km away can still be within the corners of the box
+        assert!(bounding_box.contains(51_907_400, 372_200));
+        assert!(distance(LONDON, (51_907_400, 372_200)) > 50_000.0);
+    }
+
+    #[test]
+    // This tests that a box around a point near the antimeridian wraps around to the other side
+    fn bounding_box_across_antimeridian() {
+        let bounding_box = BoundingBox::around(0, 179_900_000, 50_000.0);
+
+        assert!(bounding_box.crosses_antimeridian());
+        assert!(bounding_box.contains(0, 179_950_000));
+        assert!(bounding_box.contains(0, -179_900_000));
+        assert!(!bounding_box.contains(0, 0));
+    }
+
+    #[test]
+    // This tests that a box around a point near a pole includes every longitude
+    fn bounding_box_around_pole() {
+        let bounding_box = BoundingBox::around(89_900_000, 0, 50_000.0);
+
+        assert_eq!(bounding_box.max_latitude, MAX_LATITUDE);
+        assert!(bounding_box.contains(89_950_000, 179_000_000));
+        assert!(bounding_box.contains(89_950_000, -90_000_000));
+    }
+
+    #[test]
+    // This tests that latitudes and longitudes outside their ranges are rejected
+    fn valid_lat_long() {
+        assert!(is_valid_lat_long(LONDON.0, LONDON.1));
+        assert!(is_valid_lat_long(-90_000_000, 180_000_000));
+        assert!(!is_valid_lat_long(90_000_001, 0));
+        assert!(!is_valid_lat_long(0, -180_000_001));
+    }
+}
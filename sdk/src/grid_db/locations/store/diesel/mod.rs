@@ -21,13 +21,18 @@ use diesel::r2d2::{ConnectionManager, Pool};
 use super::diesel::models::{
     LocationAttributeModel, LocationModel, NewLocationAttributeModel, NewLocationModel,
 };
-use super::{LatLongValue, Location, LocationAttribute, LocationStore, LocationStoreError};
+use super::{
+    LatLongValue, Location, LocationAttribute, LocationStore, LocationStoreError, NearbyLocation,
+};
 use crate::database::DatabaseError;
 use crate::grid_db::commits::MAX_COMMIT_NUM;
 use operations::add_location::LocationStoreAddLocationOperation as _;
 use operations::delete_location::LocationStoreDeleteLocationOperation as _;
 use operations::fetch_location::LocationStoreFetchLocationOperation as _;
+use operations::list_child_locations::LocationStoreListChildLocationsOperation as _;
+use operations::list_location_ancestors::LocationStoreListLocationAncestorsOperation as _;
 use operations::list_locations::LocationStoreListLocationsOperation as _;
+use operations::list_locations_within::LocationStoreListLocationsWithinOperation as _;
 use operations::update_location::LocationStoreUpdateLocationOperation as _;
 use operations::LocationStoreOperations;
 
@@ -91,6 +96,50 @@ impl LocationStore for DieselLocationStore<diesel::pg::PgConnection> {
         .list_locations(service_id)
     }
 
+    fn list_child_locations(
+        &self,
+        parent_location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_child_locations(parent_location_id, service_id)
+    }
+
+    fn list_location_ancestors(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_location_ancestors(location_id, service_id)
+    }
+
+    fn list_locations_within(
+        &self,
+        latitude: i64,
+        longitude: i64,
+        radius: f64,
+        service_id: Option<&str>,
+    ) -> Result<Vec<NearbyLocation>, LocationStoreError> {
+        LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_locations_within(latitude, longitude, radius, service_id)
+    }
+
     fn update_location(&self, location: Location) -> Result<(), LocationStoreError> {
         let attributes = make_location_attribute_models(&location.attributes, None);
         let current_commit_num = location.start_commit_num;
@@ -159,6 +208,50 @@ impl LocationStore for DieselLocationStore<diesel::sqlite::SqliteConnection> {
         .list_locations(service_id)
     }
 
+    fn list_child_locations(
+        &self,
+        parent_location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_child_locations(parent_location_id, service_id)
+    }
+
+    fn list_location_ancestors(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_location_ancestors(location_id, service_id)
+    }
+
+    fn list_locations_within(
+        &self,
+        latitude: i64,
+        longitude: i64,
+        radius: f64,
+        service_id: Option<&str>,
+    ) -> Result<Vec<NearbyLocation>, LocationStoreError> {
+        LocationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_locations_within(latitude, longitude, radius, service_id)
+    }
+
     fn update_location(&self, location: Location) -> Result<(), LocationStoreError> {
         let attributes = make_location_attribute_models(&location.attributes, None);
         let current_commit_num = location.start_commit_num;
@@ -197,6 +290,11 @@ impl Into<NewLocationModel> for Location {
             start_commit_num: self.start_commit_num,
             end_commit_num: MAX_COMMIT_NUM,
             service_id: self.service_id,
+            transaction_id: self.transaction_id,
+            signer: self.signer,
+            pending_owner: self.pending_owner,
+            schema_version: self.schema_version,
+            parent_location_id: self.parent_location_id,
         }
     }
 }
@@ -224,6 +322,8 @@ pub fn make_location_attribute_models(
             start_commit_num: attr.start_commit_num,
             end_commit_num: MAX_COMMIT_NUM,
             service_id: attr.service_id.clone(),
+            timestamp_value: attr.timestamp_value,
+            decimal_value: attr.decimal_value.clone(),
         });
 
         if attr.struct_values.is_some() {
@@ -235,6 +335,16 @@ pub fn make_location_attribute_models(
                 ));
             }
         }
+
+        // List elements are stored as children named by their index
+        if let Some(vals) = &attr.list_values {
+            if !vals.is_empty() {
+                attrs.append(&mut make_location_attribute_models(
+                    &vals,
+                    Some(attr.property_name.to_string()),
+                ));
+            }
+        }
     }
 
     attrs
@@ -260,6 +370,9 @@ impl From<LocationAttributeModel> for LocationAttribute {
             enum_value: model.enum_value,
             struct_values: None,
             lat_long_value: create_lat_long_value(model.latitude_value, model.longitude_value),
+            timestamp_value: model.timestamp_value,
+            decimal_value: model.decimal_value,
+            list_values: None,
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
@@ -269,6 +382,12 @@ impl From<LocationAttributeModel> for LocationAttribute {
 
 impl From<(LocationAttributeModel, Vec<LocationAttribute>)> for LocationAttribute {
     fn from((model, children): (LocationAttributeModel, Vec<LocationAttribute>)) -> Self {
+        let (struct_values, list_values) = if model.data_type == "List" {
+            (None, Some(children))
+        } else {
+            (Some(children), None)
+        };
+
         Self {
             location_id: model.location_id,
             location_address: model.location_address,
@@ -279,8 +398,11 @@ impl From<(LocationAttributeModel, Vec<LocationAttribute>)> for LocationAttribut
             number_value: model.number_value,
             string_value: model.string_value,
             enum_value: model.enum_value,
-            struct_values: Some(children),
+            struct_values,
             lat_long_value: create_lat_long_value(model.latitude_value, model.longitude_value),
+            timestamp_value: model.timestamp_value,
+            decimal_value: model.decimal_value,
+            list_values,
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
@@ -299,6 +421,11 @@ impl From<(LocationModel, Vec<LocationAttribute>)> for Location {
             start_commit_num: location.start_commit_num,
             end_commit_num: location.end_commit_num,
             service_id: location.service_id,
+            transaction_id: location.transaction_id,
+            signer: location.signer,
+            pending_owner: location.pending_owner,
+            schema_version: location.schema_version,
+            parent_location_id: location.parent_location_id,
         }
     }
 }
@@ -314,6 +441,11 @@ impl From<LocationModel> for Location {
             start_commit_num: location.start_commit_num,
             end_commit_num: location.end_commit_num,
             service_id: location.service_id,
+            transaction_id: location.transaction_id,
+            signer: location.signer,
+            pending_owner: location.pending_owner,
+            schema_version: location.schema_version,
+            parent_location_id: location.parent_location_id,
         }
     }
 }
@@ -329,6 +461,11 @@ impl From<NewLocationModel> for Location {
             start_commit_num: location.start_commit_num,
             end_commit_num: location.end_commit_num,
             service_id: location.service_id,
+            transaction_id: location.transaction_id,
+            signer: location.signer,
+            pending_owner: location.pending_owner,
+            schema_version: location.schema_version,
+            parent_location_id: location.parent_location_id,
         }
     }
 }
@@ -27,6 +27,11 @@ pub struct NewLocationModel {
     pub end_commit_num: i64,
 
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub pending_owner: String,
+    pub schema_version: i32,
+    pub parent_location_id: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -43,6 +48,11 @@ pub struct LocationModel {
     pub end_commit_num: i64,
 
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub pending_owner: String,
+    pub schema_version: i32,
+    pub parent_location_id: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -66,6 +76,8 @@ pub struct NewLocationAttributeModel {
     pub end_commit_num: i64,
 
     pub service_id: Option<String>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -90,4 +102,6 @@ pub struct LocationAttributeModel {
     pub end_commit_num: i64,
 
     pub service_id: Option<String>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
 }
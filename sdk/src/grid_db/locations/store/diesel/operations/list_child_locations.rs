@@ -0,0 +1,116 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::fetch_location::LocationStoreFetchLocationOperation;
+use super::LocationStoreOperations;
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::locations::store::diesel::{schema::location, LocationStoreError};
+use crate::grid_db::locations::store::Location;
+use diesel::prelude::*;
+
+pub(in crate::grid_db::locations::store::diesel) trait LocationStoreListChildLocationsOperation {
+    fn list_child_locations(
+        &self,
+        parent_location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> LocationStoreListChildLocationsOperation
+    for LocationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_child_locations(
+        &self,
+        parent_location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        let mut query = location::table
+            .into_boxed()
+            .select(location::location_id)
+            .filter(
+                location::parent_location_id
+                    .eq(parent_location_id)
+                    .and(location::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .order(location::location_id);
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location::service_id.eq(service_id));
+        } else {
+            query = query.filter(location::service_id.is_null());
+        }
+
+        let location_ids =
+            query
+                .load::<String>(self.conn)
+                .map_err(|err| LocationStoreError::OperationError {
+                    context: "Failed to fetch child locations".to_string(),
+                    source: Some(Box::new(err)),
+                })?;
+
+        let mut locations = Vec::new();
+        for location_id in location_ids {
+            if let Some(location) = self.fetch_location(&location_id, service_id)? {
+                locations.push(location);
+            }
+        }
+
+        Ok(locations)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> LocationStoreListChildLocationsOperation
+    for LocationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_child_locations(
+        &self,
+        parent_location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        let mut query = location::table
+            .into_boxed()
+            .select(location::location_id)
+            .filter(
+                location::parent_location_id
+                    .eq(parent_location_id)
+                    .and(location::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .order(location::location_id);
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location::service_id.eq(service_id));
+        } else {
+            query = query.filter(location::service_id.is_null());
+        }
+
+        let location_ids =
+            query
+                .load::<String>(self.conn)
+                .map_err(|err| LocationStoreError::OperationError {
+                    context: "Failed to fetch child locations".to_string(),
+                    source: Some(Box::new(err)),
+                })?;
+
+        let mut locations = Vec::new();
+        for location_id in location_ids {
+            if let Some(location) = self.fetch_location(&location_id, service_id)? {
+                locations.push(location);
+            }
+        }
+
+        Ok(locations)
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::fetch_location::LocationStoreFetchLocationOperation;
+use super::LocationStoreOperations;
+use crate::grid_db::locations::store::diesel::LocationStoreError;
+use crate::grid_db::locations::store::Location;
+
+pub(in crate::grid_db::locations::store::diesel) trait LocationStoreListLocationAncestorsOperation {
+    fn list_location_ancestors(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> LocationStoreListLocationAncestorsOperation
+    for LocationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_location_ancestors(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        let mut ancestors: Vec<Location> = Vec::new();
+        let mut parent_location_id = self
+            .fetch_location(location_id, service_id)?
+            .and_then(|location| location.parent_location_id);
+
+        while let Some(id) = parent_location_id {
+            // The contract rejects cycles; this only guards against looping on bad data
+            if id == location_id || ancestors.iter().any(|ancestor| ancestor.location_id == id) {
+                break;
+            }
+            match self.fetch_location(&id, service_id)? {
+                Some(parent) => {
+                    parent_location_id = parent.parent_location_id.clone();
+                    ancestors.push(parent);
+                }
+                None => break,
+            }
+        }
+
+        Ok(ancestors)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> LocationStoreListLocationAncestorsOperation
+    for LocationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_location_ancestors(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        let mut ancestors: Vec<Location> = Vec::new();
+        let mut parent_location_id = self
+            .fetch_location(location_id, service_id)?
+            .and_then(|location| location.parent_location_id);
+
+        while let Some(id) = parent_location_id {
+            // The contract rejects cycles; this only guards against looping on bad data
+            if id == location_id || ancestors.iter().any(|ancestor| ancestor.location_id == id) {
+                break;
+            }
+            match self.fetch_location(&id, service_id)? {
+                Some(parent) => {
+                    parent_location_id = parent.parent_location_id.clone();
+                    ancestors.push(parent);
+                }
+                None => break,
+            }
+        }
+
+        Ok(ancestors)
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use super::fetch_location::LocationStoreFetchLocationOperation as _;
+use super::LocationStoreOperations;
+use crate::grid_db::locations::store::diesel::{schema::location_attribute, LocationStoreError};
+
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::geo::{distance, BoundingBox};
+use crate::grid_db::locations::store::{LatLongValue, NearbyLocation};
+use diesel::prelude::*;
+
+pub(in crate::grid_db::locations::store::diesel) trait LocationStoreListLocationsWithinOperation {
+    fn list_locations_within(
+        &self,
+        latitude: i64,
+        longitude: i64,
+        radius: f64,
+        service_id: Option<&str>,
+    ) -> Result<Vec<NearbyLocation>, LocationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> LocationStoreListLocationsWithinOperation
+    for LocationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_locations_within(
+        &self,
+        latitude: i64,
+        longitude: i64,
+        radius: f64,
+        service_id: Option<&str>,
+    ) -> Result<Vec<NearbyLocation>, LocationStoreError> {
+        let bounding_box = BoundingBox::around(latitude, longitude, radius);
+
+        let mut query = location_attribute::table
+            .into_boxed()
+            .select((
+                location_attribute::location_id,
+                location_attribute::property_name,
+                location_attribute::latitude_value,
+                location_attribute::longitude_value,
+            ))
+            .filter(
+                location_attribute::data_type
+                    .eq("LatLong")
+                    .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM))
+                    .and(location_attribute::latitude_value.ge(bounding_box.min_latitude))
+                    .and(location_attribute::latitude_value.le(bounding_box.max_latitude)),
+            );
+
+        if bounding_box.crosses_antimeridian() {
+            query = query.filter(
+                location_attribute::longitude_value
+                    .ge(bounding_box.min_longitude)
+                    .or(location_attribute::longitude_value.le(bounding_box.max_longitude)),
+            );
+        } else {
+            query = query.filter(
+                location_attribute::longitude_value
+                    .ge(bounding_box.min_longitude)
+                    .and(location_attribute::longitude_value.le(bounding_box.max_longitude)),
+            );
+        }
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location_attribute::service_id.eq(service_id));
+        } else {
+            query = query.filter(location_attribute::service_id.is_null());
+        }
+
+        let candidates = query
+            .load::<(String, String, Option<i64>, Option<i64>)>(self.conn)
+            .map_err(|err| LocationStoreError::QueryError {
+                context: "Failed to fetch location coordinates".to_string(),
+                source: Box::new(err),
+            })?;
+
+        let mut locations = Vec::new();
+
+        for (location_id, property_name, lat_long, distance) in
+            nearest_per_location(candidates, (latitude, longitude), radius)
+        {
+            if let Some(location) = self.fetch_location(&location_id, service_id)? {
+                locations.push(NearbyLocation {
+                    location,
+                    property_name,
+                    lat_long_value: LatLongValue::from(lat_long),
+                    distance,
+                });
+            }
+        }
+
+        Ok(locations)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> LocationStoreListLocationsWithinOperation
+    for LocationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_locations_within(
+        &self,
+        latitude: i64,
+        longitude: i64,
+        radius: f64,
+        service_id: Option<&str>,
+    ) -> Result<Vec<NearbyLocation>, LocationStoreError> {
+        let bounding_box = BoundingBox::around(latitude, longitude, radius);
+
+        let mut query = location_attribute::table
+            .into_boxed()
+            .select((
+                location_attribute::location_id,
+                location_attribute::property_name,
+                location_attribute::latitude_value,
+                location_attribute::longitude_value,
+            ))
+            .filter(
+                location_attribute::data_type
+                    .eq("LatLong")
+                    .and(location_attribute::end_commit_num.eq(MAX_COMMIT_NUM))
+                    .and(location_attribute::latitude_value.ge(bounding_box.min_latitude))
+                    .and(location_attribute::latitude_value.le(bounding_box.max_latitude)),
+            );
+
+        if bounding_box.crosses_antimeridian() {
+            query = query.filter(
+                location_attribute::longitude_value
+                    .ge(bounding_box.min_longitude)
+                    .or(location_attribute::longitude_value.le(bounding_box.max_longitude)),
+            );
+        } else {
+            query = query.filter(
+                location_attribute::longitude_value
+                    .ge(bounding_box.min_longitude)
+                    .and(location_attribute::longitude_value.le(bounding_box.max_longitude)),
+            );
+        }
+
+        if let Some(service_id) = service_id {
+            query = query.filter(location_attribute::service_id.eq(service_id));
+        } else {
+            query = query.filter(location_attribute::service_id.is_null());
+        }
+
+        let candidates = query
+            .load::<(String, String, Option<i64>, Option<i64>)>(self.conn)
+            .map_err(|err| LocationStoreError::QueryError {
+                context: "Failed to fetch location coordinates".to_string(),
+                source: Box::new(err),
+            })?;
+
+        let mut locations = Vec::new();
+
+        for (location_id, property_name, lat_long, distance) in
+            nearest_per_location(candidates, (latitude, longitude), radius)
+        {
+            if let Some(location) = self.fetch_location(&location_id, service_id)? {
+                locations.push(NearbyLocation {
+                    location,
+                    property_name,
+                    lat_long_value: LatLongValue::from(lat_long),
+                    distance,
+                });
+            }
+        }
+
+        Ok(locations)
+    }
+}
+
+/// Keeps the LatLong attribute of each location that is nearest to the point, if it is within
+/// the radius, and orders the locations from nearest to farthest
+fn nearest_per_location(
+    candidates: Vec<(String, String, Option<i64>, Option<i64>)>,
+    point: (i64, i64),
+    radius: f64,
+) -> Vec<(String, String, (i64, i64), f64)> {
+    let mut nearest: HashMap<String, (String, (i64, i64), f64)> = HashMap::new();
+
+    for (location_id, property_name, latitude, longitude) in candidates {
+        let lat_long = match (latitude, longitude) {
+            (Some(latitude), Some(longitude)) => (latitude, longitude),
+            _ => continue,
+        };
+
+        let distance = distance(point, lat_long);
+        if distance > radius {
+            continue;
+        }
+
+        match nearest.get(&location_id) {
+            Some((_, _, nearest_distance)) if *nearest_distance <= distance => (),
+            _ => {
+                nearest.insert(location_id, (property_name, lat_long, distance));
+            }
+        }
+    }
+
+    let mut locations = nearest
+        .into_iter()
+        .map(|(location_id, (property_name, lat_long, distance))| {
+            (location_id, property_name, lat_long, distance)
+        })
+        .collect::<Vec<_>>();
+
+    locations.sort_by(|a, b| a.3.partial_cmp(&b.3).unwrap_or(std::cmp::Ordering::Equal));
+
+    locations
+}
@@ -15,7 +15,10 @@
 pub(super) mod add_location;
 pub(super) mod delete_location;
 pub(super) mod fetch_location;
+pub(super) mod list_child_locations;
+pub(super) mod list_location_ancestors;
 pub(super) mod list_locations;
+pub(super) mod list_locations_within;
 pub(super) mod update_location;
 
 pub(super) struct LocationStoreOperations<'a, C> {
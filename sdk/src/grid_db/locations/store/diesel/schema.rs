@@ -22,6 +22,11 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
+        pending_owner -> Text,
+        schema_version -> Int4,
+        parent_location_id -> Nullable<Varchar>,
     }
 }
 
@@ -44,5 +49,7 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        timestamp_value -> Nullable<Int8>,
+        decimal_value -> Nullable<Text>,
     }
 }
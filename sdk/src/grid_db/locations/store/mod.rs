@@ -30,6 +30,14 @@ pub struct Location {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    /// The organization a transfer of the location has been offered to; empty if none is pending
+    pub pending_owner: String,
+    /// The version of the schema the location was last validated against
+    pub schema_version: i32,
+    /// The location this location is contained in; None for a top-level location
+    pub parent_location_id: Option<String>,
 }
 
 /// Represents a Grid Location Attribute
@@ -46,6 +54,11 @@ pub struct LocationAttribute {
     pub enum_value: Option<i32>,
     pub struct_values: Option<Vec<LocationAttribute>>,
     pub lat_long_value: Option<LatLongValue>,
+    /// Seconds since the Unix epoch
+    pub timestamp_value: Option<i64>,
+    /// The exact decimal string, e.g. "12.050"
+    pub decimal_value: Option<String>,
+    pub list_values: Option<Vec<LocationAttribute>>,
     // The indicators of the start and stop for the slowly-changing dimensions.
     pub start_commit_num: i64,
     pub end_commit_num: i64,
@@ -58,6 +71,17 @@ pub struct LatLong;
 #[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct LatLongValue(pub i64, pub i64);
 
+/// A location with a LatLong attribute within the radius of a spatial query
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct NearbyLocation {
+    pub location: Location,
+    /// The name of the location's LatLong attribute nearest to the queried point
+    pub property_name: String,
+    pub lat_long_value: LatLongValue,
+    /// The great-circle distance from the queried point, in meters
+    pub distance: f64,
+}
+
 pub trait LocationStore: Send + Sync {
     /// Adds a location to the underlying storage
     ///
@@ -86,6 +110,47 @@ pub trait LocationStore: Send + Sync {
     fn list_locations(&self, service_id: Option<&str>)
         -> Result<Vec<Location>, LocationStoreError>;
 
+    /// Gets the locations directly contained in a location
+    ///
+    /// # Arguments
+    ///
+    ///  * `parent_location_id` - The ID of the containing location
+    ///  * `service_id` - optional - The service ID to get the locations for
+    fn list_child_locations(
+        &self,
+        parent_location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError>;
+
+    /// Gets the locations a location is contained in, starting with its parent and ending with
+    /// the top-level location
+    ///
+    /// # Arguments
+    ///
+    ///  * `location_id` - The ID of the location
+    ///  * `service_id` - optional - The service ID to get the locations for
+    fn list_location_ancestors(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError>;
+
+    /// Gets the locations with a LatLong attribute within a radius of a point, nearest first
+    ///
+    /// # Arguments
+    ///
+    ///  * `latitude` - The latitude of the point, in micro-degrees
+    ///  * `longitude` - The longitude of the point, in micro-degrees
+    ///  * `radius` - The radius around the point, in meters
+    ///  * `service_id` - optional - The service ID to get the locations for
+    fn list_locations_within(
+        &self,
+        latitude: i64,
+        longitude: i64,
+        radius: f64,
+        service_id: Option<&str>,
+    ) -> Result<Vec<NearbyLocation>, LocationStoreError>;
+
     /// Gets locations from the underlying storage
     ///
     /// # Arguments
@@ -129,6 +194,32 @@ where
         (**self).list_locations(service_id)
     }
 
+    fn list_child_locations(
+        &self,
+        parent_location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        (**self).list_child_locations(parent_location_id, service_id)
+    }
+
+    fn list_location_ancestors(
+        &self,
+        location_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Location>, LocationStoreError> {
+        (**self).list_location_ancestors(location_id, service_id)
+    }
+
+    fn list_locations_within(
+        &self,
+        latitude: i64,
+        longitude: i64,
+        radius: f64,
+        service_id: Option<&str>,
+    ) -> Result<Vec<NearbyLocation>, LocationStoreError> {
+        (**self).list_locations_within(latitude, longitude, radius, service_id)
+    }
+
     fn update_location(&self, location: Location) -> Result<(), LocationStoreError> {
         (**self).update_location(location)
     }
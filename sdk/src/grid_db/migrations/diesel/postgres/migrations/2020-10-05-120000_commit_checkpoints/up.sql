@@ -0,0 +1,37 @@
+-- Copyright 2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE commit_checkpoint (
+    id BIGSERIAL PRIMARY KEY,
+    service_id TEXT,
+    commit_id VARCHAR(128) NOT NULL,
+    commit_num BIGINT NOT NULL
+);
+
+CREATE UNIQUE INDEX commit_checkpoint_service_id_idx
+    ON commit_checkpoint (COALESCE(service_id, ''));
+
+-- Seed each source's checkpoint with its latest known commit
+INSERT INTO commit_checkpoint (service_id, commit_id, commit_num)
+SELECT c.service_id, c.commit_id, c.commit_num
+FROM commit c
+WHERE c.commit_id IS NOT NULL
+    AND c.id = (
+        SELECT latest.id
+        FROM commit latest
+        WHERE latest.service_id IS NOT DISTINCT FROM c.service_id
+        ORDER BY latest.commit_num DESC, latest.id DESC
+        LIMIT 1
+    );
@@ -0,0 +1,108 @@
+-- Copyright 2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+ALTER TABLE agent ADD COLUMN transaction_id TEXT;
+ALTER TABLE agent ADD COLUMN signer TEXT;
+
+ALTER TABLE organization ADD COLUMN transaction_id TEXT;
+ALTER TABLE organization ADD COLUMN signer TEXT;
+
+ALTER TABLE location ADD COLUMN transaction_id TEXT;
+ALTER TABLE location ADD COLUMN signer TEXT;
+
+ALTER TABLE product ADD COLUMN transaction_id TEXT;
+ALTER TABLE product ADD COLUMN signer TEXT;
+
+ALTER TABLE grid_schema ADD COLUMN transaction_id TEXT;
+ALTER TABLE grid_schema ADD COLUMN signer TEXT;
+
+ALTER TABLE property ADD COLUMN transaction_id TEXT;
+ALTER TABLE property ADD COLUMN signer TEXT;
+
+ALTER TABLE proposal ADD COLUMN transaction_id TEXT;
+ALTER TABLE proposal ADD COLUMN signer TEXT;
+
+ALTER TABLE record ADD COLUMN transaction_id TEXT;
+ALTER TABLE record ADD COLUMN signer TEXT;
+
+ALTER TABLE reported_value ADD COLUMN transaction_id TEXT;
+ALTER TABLE reported_value ADD COLUMN signer TEXT;
+
+DROP VIEW reported_value_reporter_to_agent_metadata;
+
+CREATE VIEW reported_value_reporter_to_agent_metadata
+AS
+  SELECT id,
+         property_name,
+         record_id,
+         reporter_index,
+         timestamp,
+         data_type,
+         bytes_value,
+         boolean_value,
+         number_value,
+         string_value,
+         enum_value,
+         parent_name,
+         latitude_value,
+         longitude_value,
+         public_key,
+         authorized,
+         metadata,
+         reported_value_end_commit_num,
+         reporter_end_commit_num,
+         service_id,
+         transaction_id,
+         signer
+  FROM   (SELECT Row_number()
+                   OVER (
+                     partition BY id
+                     ORDER BY reporter_end_commit_num) AS RowNum,
+                 *
+          FROM   (SELECT reported_value.id,
+                         reported_value.property_name,
+                         reported_value.record_id,
+                         reported_value.reporter_index,
+                         reported_value.timestamp,
+                         reported_value.data_type,
+                         reported_value.bytes_value,
+                         reported_value.boolean_value,
+                         reported_value.number_value,
+                         reported_value.string_value,
+                         reported_value.enum_value,
+                         reported_value.parent_name,
+                         reported_value.latitude_value,
+                         reported_value.longitude_value,
+                         reported_value.end_commit_num AS
+                         "reported_value_end_commit_num",
+                         reporter_to_agent_metadata.reporter_end_commit_num,
+                         reporter_to_agent_metadata.public_key,
+                         reporter_to_agent_metadata.authorized,
+                         reporter_to_agent_metadata.metadata,
+                         reported_value.service_id,
+                         reported_value.transaction_id,
+                         reported_value.signer
+                  FROM   reported_value
+                         LEFT JOIN reporter_to_agent_metadata
+                                ON reported_value.record_id =
+                                   reporter_to_agent_metadata.record_id
+                                   AND reported_value.property_name =
+                                       reporter_to_agent_metadata.property_name
+                                   AND reported_value.reporter_index =
+                                       reporter_to_agent_metadata.reporter_index
+                                   AND reported_value.end_commit_num <=
+  reporter_to_agent_metadata.reporter_end_commit_num) AS
+  join_tables) X
+  WHERE  rownum = 1;
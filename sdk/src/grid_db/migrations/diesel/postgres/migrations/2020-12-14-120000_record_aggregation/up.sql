@@ -0,0 +1,33 @@
+-- Copyright 2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- One row per time a record was aggregated into another, e.g. a case onto a pallet. The row is
+-- kept after the child is split off so the lineage of both records can be walked.
+CREATE TABLE record_aggregation (
+    id BIGSERIAL PRIMARY KEY,
+    parent_record_id TEXT NOT NULL,
+    child_record_id TEXT NOT NULL,
+    aggregated_at BIGINT NOT NULL,
+    disaggregated_at BIGINT,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+
+CREATE INDEX record_aggregation_parent_idx
+    ON record_aggregation (parent_record_id, end_commit_num);
+
+CREATE INDEX record_aggregation_child_idx
+    ON record_aggregation (child_record_id, end_commit_num);
@@ -0,0 +1,22 @@
+-- Copyright 2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Records are found by the position in their latest reported value of a LatLong property, which
+-- is narrowed to a bounding box and checked against newer values of the same record
+CREATE INDEX reported_value_lat_long_idx
+    ON reported_value (property_name, latitude_value, longitude_value);
+
+CREATE INDEX reported_value_record_property_idx
+    ON reported_value (record_id, property_name, end_commit_num);
@@ -0,0 +1,47 @@
+-- Copyright 2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE pike_role (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    org_id VARCHAR(256) NOT NULL,
+    name VARCHAR(256) NOT NULL,
+    description TEXT NOT NULL,
+    active BOOLEAN NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT,
+    transaction_id TEXT,
+    signer TEXT
+);
+
+CREATE TABLE pike_role_permission (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    org_id VARCHAR(256) NOT NULL,
+    role_name VARCHAR(256) NOT NULL,
+    permission TEXT NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+
+CREATE TABLE pike_role_allowed_org (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    org_id VARCHAR(256) NOT NULL,
+    role_name VARCHAR(256) NOT NULL,
+    allowed_org_id VARCHAR(256) NOT NULL,
+    start_commit_num BIGINT NOT NULL,
+    end_commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
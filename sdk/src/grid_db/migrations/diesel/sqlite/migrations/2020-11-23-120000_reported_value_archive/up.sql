@@ -0,0 +1,33 @@
+-- Copyright 2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Append-only copy of every reported value seen on a property page. Rows are never ended, so
+-- values remain after the page is reused once the property's pages wrap.
+CREATE TABLE reported_value_archive (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    record_id TEXT NOT NULL,
+    property_name TEXT NOT NULL,
+    page_num INTEGER NOT NULL,
+    generation BIGINT NOT NULL,
+    reporter_index INTEGER NOT NULL,
+    timestamp BIGINT NOT NULL,
+    data_type TEXT NOT NULL,
+    value BYTEA NOT NULL,
+    commit_num BIGINT NOT NULL,
+    service_id TEXT
+);
+
+CREATE INDEX reported_value_archive_property_idx
+    ON reported_value_archive (record_id, property_name, timestamp);
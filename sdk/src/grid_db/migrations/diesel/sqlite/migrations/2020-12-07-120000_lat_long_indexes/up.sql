@@ -0,0 +1,21 @@
+-- Copyright 2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Spatial queries narrow their candidates to a bounding box before filtering by distance
+CREATE INDEX location_attribute_lat_long_idx
+    ON location_attribute (latitude_value, longitude_value);
+
+CREATE INDEX reported_value_property_name_idx
+    ON reported_value (property_name, data_type, end_commit_num);
@@ -0,0 +1,21 @@
+-- Copyright 2020 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- The namespace of the product a record tracks, and the schema of a custom product, whose ID is
+-- only unique within that schema. Products referenced before then are GS1 products.
+ALTER TABLE record ADD COLUMN product_namespace TEXT;
+ALTER TABLE record ADD COLUMN product_schema_name TEXT;
+
+UPDATE record SET product_namespace = 'GS1' WHERE product_id IS NOT NULL;
@@ -14,16 +14,23 @@
 
 use crate::grid_db::{
     agents::store::diesel::schema::{agent::dsl::*, role::dsl::role},
-    commits::store::diesel::schema::{chain_record::dsl::*, commit::dsl::*},
+    commits::store::diesel::schema::{
+        chain_record::dsl::*, commit::dsl::*, commit_checkpoint::dsl::commit_checkpoint,
+    },
     locations::store::diesel::schema::{location::dsl::*, location_attribute::dsl::*},
-    organizations::store::diesel::schema::organization::dsl::*,
+    organizations::store::diesel::schema::{
+        organization::dsl::*, pike_role::dsl::pike_role,
+        pike_role_allowed_org::dsl::pike_role_allowed_org,
+        pike_role_permission::dsl::pike_role_permission,
+    },
     products::store::diesel::schema::{product::dsl::*, product_property_value::dsl::*},
     schemas::store::diesel::schema::{
         grid_property_definition::dsl::grid_property_definition, grid_schema::dsl::*,
     },
     track_and_trace::store::diesel::schema::{
         associated_agent::dsl::*, property::dsl::*, proposal::dsl::*, record::dsl::*,
-        reported_value::dsl::*, reporter::dsl::*,
+        record_aggregation::dsl::record_aggregation, reported_value::dsl::*,
+        reported_value_archive::dsl::reported_value_archive, reporter::dsl::*,
     },
 };
 
@@ -60,9 +67,13 @@ pub fn clear_database(conn: &SqliteConnection) -> Result<(), DatabaseError> {
         diesel::delete(role).execute(conn)?;
         diesel::delete(chain_record).execute(conn)?;
         diesel::delete(commit).execute(conn)?;
+        diesel::delete(commit_checkpoint).execute(conn)?;
         diesel::delete(location).execute(conn)?;
         diesel::delete(location_attribute).execute(conn)?;
         diesel::delete(organization).execute(conn)?;
+        diesel::delete(pike_role).execute(conn)?;
+        diesel::delete(pike_role_permission).execute(conn)?;
+        diesel::delete(pike_role_allowed_org).execute(conn)?;
         diesel::delete(product).execute(conn)?;
         diesel::delete(product_property_value).execute(conn)?;
         diesel::delete(grid_schema).execute(conn)?;
@@ -71,7 +82,9 @@ pub fn clear_database(conn: &SqliteConnection) -> Result<(), DatabaseError> {
         diesel::delete(property).execute(conn)?;
         diesel::delete(proposal).execute(conn)?;
         diesel::delete(record).execute(conn)?;
+        diesel::delete(record_aggregation).execute(conn)?;
         diesel::delete(reported_value).execute(conn)?;
+        diesel::delete(reported_value_archive).execute(conn)?;
         diesel::delete(reporter).execute(conn)?;
 
         Ok(())
@@ -18,6 +18,7 @@
 
 pub mod agents;
 pub mod commits;
+pub mod geo;
 pub mod locations;
 pub mod organizations;
 pub mod products;
@@ -19,12 +19,21 @@ pub(in crate::grid_db) mod schema;
 use diesel::r2d2::{ConnectionManager, Pool};
 use std::iter::FromIterator;
 
-use super::diesel::models::{NewOrganizationModel, OrganizationModel};
-use super::{Organization, OrganizationStore, OrganizationStoreError};
+use super::diesel::models::{
+    NewOrganizationModel, NewRoleAllowedOrgModel, NewRoleModel, NewRolePermissionModel,
+    OrganizationModel, RoleAllowedOrgModel, RoleModel, RolePermissionModel,
+};
+use super::{Organization, OrganizationStore, OrganizationStoreError, Role};
 use crate::database::DatabaseError;
+use crate::grid_db::commits::MAX_COMMIT_NUM;
 use operations::add_organizations::OrganizationStoreAddOrganizationsOperation as _;
+use operations::add_roles::OrganizationStoreAddRolesOperation as _;
+use operations::delete_organization::OrganizationStoreDeleteOrganizationOperation as _;
+use operations::delete_role::OrganizationStoreDeleteRoleOperation as _;
 use operations::fetch_organization::OrganizationStoreFetchOrganizationOperation as _;
+use operations::fetch_role::OrganizationStoreFetchRoleOperation as _;
 use operations::list_organizations::OrganizationStoreListOrganizationsOperation as _;
+use operations::list_roles::OrganizationStoreListRolesOperation as _;
 use operations::OrganizationStoreOperations;
 
 /// Manages creating organizations in the database
@@ -84,6 +93,76 @@ impl OrganizationStore for DieselOrganizationStore<diesel::pg::PgConnection> {
         })?)
         .fetch_organization(org_id, service_id)
     }
+
+    fn delete_organization(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .delete_organization(org_id, service_id, current_commit_num)
+    }
+
+    fn add_roles(&self, roles: Vec<Role>) -> Result<(), OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .add_roles(roles.iter().map(make_role_models).collect())
+    }
+
+    fn list_roles(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Role>, OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_roles(org_id, service_id)
+    }
+
+    fn fetch_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<Role>, OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .fetch_role(org_id, name, service_id)
+    }
+
+    fn delete_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .delete_role(org_id, name, service_id, current_commit_num)
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -124,6 +203,76 @@ impl OrganizationStore for DieselOrganizationStore<diesel::sqlite::SqliteConnect
         })?)
         .fetch_organization(org_id, service_id)
     }
+
+    fn delete_organization(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .delete_organization(org_id, service_id, current_commit_num)
+    }
+
+    fn add_roles(&self, roles: Vec<Role>) -> Result<(), OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .add_roles(roles.iter().map(make_role_models).collect())
+    }
+
+    fn list_roles(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Role>, OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_roles(org_id, service_id)
+    }
+
+    fn fetch_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<Role>, OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .fetch_role(org_id, name, service_id)
+    }
+
+    fn delete_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        OrganizationStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .delete_role(org_id, name, service_id, current_commit_num)
+    }
 }
 
 impl From<OrganizationModel> for Organization {
@@ -136,6 +285,8 @@ impl From<OrganizationModel> for Organization {
             start_commit_num: org.start_commit_num,
             end_commit_num: org.end_commit_num,
             service_id: org.service_id,
+            transaction_id: org.transaction_id,
+            signer: org.signer,
         }
     }
 }
@@ -150,6 +301,8 @@ impl From<NewOrganizationModel> for Organization {
             start_commit_num: org.start_commit_num,
             end_commit_num: org.end_commit_num,
             service_id: org.service_id,
+            transaction_id: org.transaction_id,
+            signer: org.signer,
         }
     }
 }
@@ -164,10 +317,98 @@ impl Into<NewOrganizationModel> for Organization {
             start_commit_num: self.start_commit_num,
             end_commit_num: self.end_commit_num,
             service_id: self.service_id,
+            transaction_id: self.transaction_id,
+            signer: self.signer,
         }
     }
 }
 
+impl
+    From<(
+        RoleModel,
+        Vec<RolePermissionModel>,
+        Vec<RoleAllowedOrgModel>,
+    )> for Role
+{
+    fn from(
+        (role, permissions, allowed_orgs): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleAllowedOrgModel>,
+        ),
+    ) -> Self {
+        Self {
+            org_id: role.org_id,
+            name: role.name,
+            description: role.description,
+            active: role.active,
+            permissions: permissions
+                .into_iter()
+                .map(|permission| permission.permission)
+                .collect(),
+            allowed_organizations: allowed_orgs
+                .into_iter()
+                .map(|allowed_org| allowed_org.allowed_org_id)
+                .collect(),
+            start_commit_num: role.start_commit_num,
+            end_commit_num: role.end_commit_num,
+            service_id: role.service_id,
+            transaction_id: role.transaction_id,
+            signer: role.signer,
+        }
+    }
+}
+
+pub fn make_role_models(
+    role: &Role,
+) -> (
+    NewRoleModel,
+    Vec<NewRolePermissionModel>,
+    Vec<NewRoleAllowedOrgModel>,
+) {
+    let permissions = role
+        .permissions
+        .iter()
+        .map(|permission| NewRolePermissionModel {
+            org_id: role.org_id.to_string(),
+            role_name: role.name.to_string(),
+            permission: permission.to_string(),
+            start_commit_num: role.start_commit_num,
+            end_commit_num: MAX_COMMIT_NUM,
+            service_id: role.service_id.clone(),
+        })
+        .collect();
+
+    let allowed_orgs = role
+        .allowed_organizations
+        .iter()
+        .map(|allowed_org_id| NewRoleAllowedOrgModel {
+            org_id: role.org_id.to_string(),
+            role_name: role.name.to_string(),
+            allowed_org_id: allowed_org_id.to_string(),
+            start_commit_num: role.start_commit_num,
+            end_commit_num: MAX_COMMIT_NUM,
+            service_id: role.service_id.clone(),
+        })
+        .collect();
+
+    (
+        NewRoleModel {
+            org_id: role.org_id.to_string(),
+            name: role.name.to_string(),
+            description: role.description.to_string(),
+            active: role.active,
+            start_commit_num: role.start_commit_num,
+            end_commit_num: MAX_COMMIT_NUM,
+            service_id: role.service_id.clone(),
+            transaction_id: role.transaction_id.clone(),
+            signer: role.signer.clone(),
+        },
+        permissions,
+        allowed_orgs,
+    )
+}
+
 impl From<DatabaseError> for OrganizationStoreError {
     fn from(err: DatabaseError) -> OrganizationStoreError {
         OrganizationStoreError::ConnectionError(Box::new(err))
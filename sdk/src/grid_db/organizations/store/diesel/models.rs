@@ -27,6 +27,8 @@ pub struct NewOrganizationModel {
     pub end_commit_num: i64,
 
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Queryable, PartialEq, Identifiable, Debug)]
@@ -43,5 +45,101 @@ pub struct OrganizationModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
 
+    pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "pike_role"]
+pub struct NewRoleModel {
+    pub org_id: String,
+    pub name: String,
+    pub description: String,
+    pub active: bool,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+}
+
+#[derive(Queryable, PartialEq, Identifiable, Debug)]
+#[table_name = "pike_role"]
+pub struct RoleModel {
+    ///  This is the record id for the slowly-changing-dimensions table.
+    pub id: i64,
+    pub org_id: String,
+    pub name: String,
+    pub description: String,
+    pub active: bool,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "pike_role_permission"]
+pub struct NewRolePermissionModel {
+    pub org_id: String,
+    pub role_name: String,
+    pub permission: String,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
+
+#[derive(Queryable, PartialEq, Identifiable, Debug)]
+#[table_name = "pike_role_permission"]
+pub struct RolePermissionModel {
+    pub id: i64,
+    pub org_id: String,
+    pub role_name: String,
+    pub permission: String,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "pike_role_allowed_org"]
+pub struct NewRoleAllowedOrgModel {
+    pub org_id: String,
+    pub role_name: String,
+    pub allowed_org_id: String,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
+    pub service_id: Option<String>,
+}
+
+#[derive(Queryable, PartialEq, Identifiable, Debug)]
+#[table_name = "pike_role_allowed_org"]
+pub struct RoleAllowedOrgModel {
+    pub id: i64,
+    pub org_id: String,
+    pub role_name: String,
+    pub allowed_org_id: String,
+
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+
     pub service_id: Option<String>,
 }
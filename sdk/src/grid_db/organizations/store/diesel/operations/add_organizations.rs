@@ -19,7 +19,11 @@ use crate::grid_db::commits::MAX_COMMIT_NUM;
 use crate::grid_db::organizations::store::diesel::models::{
     NewOrganizationModel, OrganizationModel,
 };
-use diesel::{dsl::insert_into, prelude::*, result::Error::NotFound};
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+    result::Error::NotFound,
+};
 
 pub(in crate::grid_db::organizations::store::diesel) trait OrganizationStoreAddOrganizationsOperation
 {
@@ -53,10 +57,20 @@ impl<'a> OrganizationStoreAddOrganizationsOperation
                     source: Box::new(err),
                 })?;
             if duplicate_org.is_some() {
-                return Err(OrganizationStoreError::DuplicateError {
-                    context: "Organization already exists".to_string(),
-                    source: None,
-                });
+                update(organization::table)
+                    .filter(
+                        organization::org_id
+                            .eq(&org.org_id)
+                            .and(organization::service_id.eq(&org.service_id))
+                            .and(organization::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    )
+                    .set(organization::end_commit_num.eq(org.start_commit_num))
+                    .execute(self.conn)
+                    .map(|_| ())
+                    .map_err(|err| OrganizationStoreError::OperationError {
+                        context: "Failed to update organization".to_string(),
+                        source: Some(Box::new(err)),
+                    })?;
             }
 
             insert_into(organization::table)
@@ -97,10 +111,20 @@ impl<'a> OrganizationStoreAddOrganizationsOperation
                     source: Box::new(err),
                 })?;
             if duplicate_org.is_some() {
-                return Err(OrganizationStoreError::DuplicateError {
-                    context: "Organization already exists".to_string(),
-                    source: None,
-                });
+                update(organization::table)
+                    .filter(
+                        organization::org_id
+                            .eq(&org.org_id)
+                            .and(organization::service_id.eq(&org.service_id))
+                            .and(organization::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    )
+                    .set(organization::end_commit_num.eq(org.start_commit_num))
+                    .execute(self.conn)
+                    .map(|_| ())
+                    .map_err(|err| OrganizationStoreError::OperationError {
+                        context: "Failed to update organization".to_string(),
+                        source: Some(Box::new(err)),
+                    })?;
             }
 
             insert_into(organization::table)
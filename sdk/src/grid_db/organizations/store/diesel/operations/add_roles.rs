@@ -0,0 +1,144 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::delete_role::OrganizationStoreDeleteRoleOperation;
+use super::OrganizationStoreOperations;
+use crate::grid_db::organizations::store::diesel::{
+    schema::{pike_role, pike_role_allowed_org, pike_role_permission},
+    OrganizationStoreError,
+};
+
+use crate::grid_db::organizations::store::diesel::models::{
+    NewRoleAllowedOrgModel, NewRoleModel, NewRolePermissionModel,
+};
+use diesel::{dsl::insert_into, prelude::*};
+
+pub(in crate::grid_db::organizations::store::diesel) trait OrganizationStoreAddRolesOperation {
+    fn add_roles(
+        &self,
+        roles: Vec<(
+            NewRoleModel,
+            Vec<NewRolePermissionModel>,
+            Vec<NewRoleAllowedOrgModel>,
+        )>,
+    ) -> Result<(), OrganizationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> OrganizationStoreAddRolesOperation
+    for OrganizationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_roles(
+        &self,
+        roles: Vec<(
+            NewRoleModel,
+            Vec<NewRolePermissionModel>,
+            Vec<NewRoleAllowedOrgModel>,
+        )>,
+    ) -> Result<(), OrganizationStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, OrganizationStoreError, _>(|| {
+                for (role, permissions, allowed_orgs) in roles {
+                    // End the current version of the role, if any, before adding the new one
+                    self.delete_role(
+                        &role.org_id,
+                        &role.name,
+                        role.service_id.as_deref(),
+                        role.start_commit_num,
+                    )?;
+
+                    insert_into(pike_role::table)
+                        .values(&role)
+                        .execute(self.conn)
+                        .map_err(|err| OrganizationStoreError::OperationError {
+                            context: "Failed to add role".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+
+                    insert_into(pike_role_permission::table)
+                        .values(&permissions)
+                        .execute(self.conn)
+                        .map_err(|err| OrganizationStoreError::OperationError {
+                            context: "Failed to add role permissions".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+
+                    insert_into(pike_role_allowed_org::table)
+                        .values(&allowed_orgs)
+                        .execute(self.conn)
+                        .map_err(|err| OrganizationStoreError::OperationError {
+                            context: "Failed to add role allowed organizations".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+                }
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> OrganizationStoreAddRolesOperation
+    for OrganizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_roles(
+        &self,
+        roles: Vec<(
+            NewRoleModel,
+            Vec<NewRolePermissionModel>,
+            Vec<NewRoleAllowedOrgModel>,
+        )>,
+    ) -> Result<(), OrganizationStoreError> {
+        self.conn
+            .immediate_transaction::<_, OrganizationStoreError, _>(|| {
+                for (role, permissions, allowed_orgs) in roles {
+                    // End the current version of the role, if any, before adding the new one
+                    self.delete_role(
+                        &role.org_id,
+                        &role.name,
+                        role.service_id.as_deref(),
+                        role.start_commit_num,
+                    )?;
+
+                    insert_into(pike_role::table)
+                        .values(&role)
+                        .execute(self.conn)
+                        .map_err(|err| OrganizationStoreError::OperationError {
+                            context: "Failed to add role".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+
+                    insert_into(pike_role_permission::table)
+                        .values(&permissions)
+                        .execute(self.conn)
+                        .map_err(|err| OrganizationStoreError::OperationError {
+                            context: "Failed to add role permissions".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+
+                    insert_into(pike_role_allowed_org::table)
+                        .values(&allowed_orgs)
+                        .execute(self.conn)
+                        .map_err(|err| OrganizationStoreError::OperationError {
+                            context: "Failed to add role allowed organizations".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+                }
+
+                Ok(())
+            })
+    }
+}
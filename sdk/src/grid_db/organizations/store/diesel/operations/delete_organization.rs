@@ -0,0 +1,99 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::OrganizationStoreOperations;
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::organizations::store::diesel::{schema::organization, OrganizationStoreError};
+
+use diesel::{dsl::update, prelude::*};
+
+pub(in crate::grid_db::organizations::store::diesel) trait OrganizationStoreDeleteOrganizationOperation
+{
+    fn delete_organization(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> OrganizationStoreDeleteOrganizationOperation
+    for OrganizationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn delete_organization(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        let mut query = update(organization::table)
+            .filter(
+                organization::org_id
+                    .eq(org_id)
+                    .and(organization::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .into_boxed();
+
+        if let Some(service_id) = service_id {
+            query = query.filter(organization::service_id.eq(service_id));
+        } else {
+            query = query.filter(organization::service_id.is_null());
+        }
+
+        query
+            .set(organization::end_commit_num.eq(current_commit_num))
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(|err| OrganizationStoreError::OperationError {
+                context: "Failed to delete organization".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> OrganizationStoreDeleteOrganizationOperation
+    for OrganizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn delete_organization(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        let mut query = update(organization::table)
+            .filter(
+                organization::org_id
+                    .eq(org_id)
+                    .and(organization::end_commit_num.eq(MAX_COMMIT_NUM)),
+            )
+            .into_boxed();
+
+        if let Some(service_id) = service_id {
+            query = query.filter(organization::service_id.eq(service_id));
+        } else {
+            query = query.filter(organization::service_id.is_null());
+        }
+
+        query
+            .set(organization::end_commit_num.eq(current_commit_num))
+            .execute(self.conn)
+            .map(|_| ())
+            .map_err(|err| OrganizationStoreError::OperationError {
+                context: "Failed to delete organization".to_string(),
+                source: Some(Box::new(err)),
+            })
+    }
+}
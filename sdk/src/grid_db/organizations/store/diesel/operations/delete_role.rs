@@ -0,0 +1,206 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::OrganizationStoreOperations;
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::organizations::store::diesel::{
+    schema::{pike_role, pike_role_allowed_org, pike_role_permission},
+    OrganizationStoreError,
+};
+
+use diesel::{dsl::update, prelude::*};
+
+pub(in crate::grid_db::organizations::store::diesel) trait OrganizationStoreDeleteRoleOperation {
+    fn delete_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> OrganizationStoreDeleteRoleOperation
+    for OrganizationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn delete_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, OrganizationStoreError, _>(|| {
+                let mut query = update(pike_role::table)
+                    .filter(
+                        pike_role::org_id
+                            .eq(org_id)
+                            .and(pike_role::name.eq(name))
+                            .and(pike_role::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    )
+                    .into_boxed();
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(pike_role::service_id.eq(service_id));
+                } else {
+                    query = query.filter(pike_role::service_id.is_null());
+                }
+
+                query
+                    .set(pike_role::end_commit_num.eq(current_commit_num))
+                    .execute(self.conn)
+                    .map_err(|err| OrganizationStoreError::OperationError {
+                        context: "Failed to delete role".to_string(),
+                        source: Some(Box::new(err)),
+                    })?;
+
+                let mut query = update(pike_role_permission::table)
+                    .filter(
+                        pike_role_permission::org_id
+                            .eq(org_id)
+                            .and(pike_role_permission::role_name.eq(name))
+                            .and(pike_role_permission::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    )
+                    .into_boxed();
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(pike_role_permission::service_id.eq(service_id));
+                } else {
+                    query = query.filter(pike_role_permission::service_id.is_null());
+                }
+
+                query
+                    .set(pike_role_permission::end_commit_num.eq(current_commit_num))
+                    .execute(self.conn)
+                    .map_err(|err| OrganizationStoreError::OperationError {
+                        context: "Failed to delete role permissions".to_string(),
+                        source: Some(Box::new(err)),
+                    })?;
+
+                let mut query = update(pike_role_allowed_org::table)
+                    .filter(
+                        pike_role_allowed_org::org_id
+                            .eq(org_id)
+                            .and(pike_role_allowed_org::role_name.eq(name))
+                            .and(pike_role_allowed_org::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    )
+                    .into_boxed();
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(pike_role_allowed_org::service_id.eq(service_id));
+                } else {
+                    query = query.filter(pike_role_allowed_org::service_id.is_null());
+                }
+
+                query
+                    .set(pike_role_allowed_org::end_commit_num.eq(current_commit_num))
+                    .execute(self.conn)
+                    .map(|_| ())
+                    .map_err(|err| OrganizationStoreError::OperationError {
+                        context: "Failed to delete role allowed organizations".to_string(),
+                        source: Some(Box::new(err)),
+                    })
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> OrganizationStoreDeleteRoleOperation
+    for OrganizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn delete_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        self.conn
+            .immediate_transaction::<_, OrganizationStoreError, _>(|| {
+                let mut query = update(pike_role::table)
+                    .filter(
+                        pike_role::org_id
+                            .eq(org_id)
+                            .and(pike_role::name.eq(name))
+                            .and(pike_role::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    )
+                    .into_boxed();
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(pike_role::service_id.eq(service_id));
+                } else {
+                    query = query.filter(pike_role::service_id.is_null());
+                }
+
+                query
+                    .set(pike_role::end_commit_num.eq(current_commit_num))
+                    .execute(self.conn)
+                    .map_err(|err| OrganizationStoreError::OperationError {
+                        context: "Failed to delete role".to_string(),
+                        source: Some(Box::new(err)),
+                    })?;
+
+                let mut query = update(pike_role_permission::table)
+                    .filter(
+                        pike_role_permission::org_id
+                            .eq(org_id)
+                            .and(pike_role_permission::role_name.eq(name))
+                            .and(pike_role_permission::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    )
+                    .into_boxed();
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(pike_role_permission::service_id.eq(service_id));
+                } else {
+                    query = query.filter(pike_role_permission::service_id.is_null());
+                }
+
+                query
+                    .set(pike_role_permission::end_commit_num.eq(current_commit_num))
+                    .execute(self.conn)
+                    .map_err(|err| OrganizationStoreError::OperationError {
+                        context: "Failed to delete role permissions".to_string(),
+                        source: Some(Box::new(err)),
+                    })?;
+
+                let mut query = update(pike_role_allowed_org::table)
+                    .filter(
+                        pike_role_allowed_org::org_id
+                            .eq(org_id)
+                            .and(pike_role_allowed_org::role_name.eq(name))
+                            .and(pike_role_allowed_org::end_commit_num.eq(MAX_COMMIT_NUM)),
+                    )
+                    .into_boxed();
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(pike_role_allowed_org::service_id.eq(service_id));
+                } else {
+                    query = query.filter(pike_role_allowed_org::service_id.is_null());
+                }
+
+                query
+                    .set(pike_role_allowed_org::end_commit_num.eq(current_commit_num))
+                    .execute(self.conn)
+                    .map(|_| ())
+                    .map_err(|err| OrganizationStoreError::OperationError {
+                        context: "Failed to delete role allowed organizations".to_string(),
+                        source: Some(Box::new(err)),
+                    })
+            })
+    }
+}
@@ -0,0 +1,219 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::OrganizationStoreOperations;
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::organizations::store::diesel::models::{
+    RoleAllowedOrgModel, RoleModel, RolePermissionModel,
+};
+use crate::grid_db::organizations::store::diesel::{
+    schema::{pike_role, pike_role_allowed_org, pike_role_permission},
+    OrganizationStoreError,
+};
+use crate::grid_db::organizations::store::Role;
+use diesel::prelude::*;
+
+pub(in crate::grid_db::organizations::store::diesel) trait OrganizationStoreListRolesOperation {
+    fn list_roles(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Role>, OrganizationStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> OrganizationStoreListRolesOperation
+    for OrganizationStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_roles(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Role>, OrganizationStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, OrganizationStoreError, _>(|| {
+                let mut query = pike_role::table
+                    .into_boxed()
+                    .select(pike_role::all_columns)
+                    .filter(pike_role::end_commit_num.eq(MAX_COMMIT_NUM));
+
+                if let Some(org_id) = org_id {
+                    query = query.filter(pike_role::org_id.eq(org_id));
+                }
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(pike_role::service_id.eq(service_id));
+                } else {
+                    query = query.filter(pike_role::service_id.is_null());
+                }
+
+                let role_models = query.load::<RoleModel>(self.conn).map_err(|err| {
+                    OrganizationStoreError::OperationError {
+                        context: "Failed to fetch roles".to_string(),
+                        source: Some(Box::new(err)),
+                    }
+                })?;
+
+                let mut roles = Vec::new();
+
+                for role in role_models {
+                    let mut query = pike_role_permission::table
+                        .into_boxed()
+                        .select(pike_role_permission::all_columns)
+                        .filter(
+                            pike_role_permission::org_id
+                                .eq(&role.org_id)
+                                .and(pike_role_permission::role_name.eq(&role.name))
+                                .and(pike_role_permission::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        );
+
+                    if let Some(service_id) = service_id {
+                        query = query.filter(pike_role_permission::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(pike_role_permission::service_id.is_null());
+                    }
+
+                    let permissions =
+                        query
+                            .load::<RolePermissionModel>(self.conn)
+                            .map_err(|err| OrganizationStoreError::OperationError {
+                                context: "Failed to fetch role permissions".to_string(),
+                                source: Some(Box::new(err)),
+                            })?;
+
+                    let mut query = pike_role_allowed_org::table
+                        .into_boxed()
+                        .select(pike_role_allowed_org::all_columns)
+                        .filter(
+                            pike_role_allowed_org::org_id
+                                .eq(&role.org_id)
+                                .and(pike_role_allowed_org::role_name.eq(&role.name))
+                                .and(pike_role_allowed_org::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        );
+
+                    if let Some(service_id) = service_id {
+                        query = query.filter(pike_role_allowed_org::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(pike_role_allowed_org::service_id.is_null());
+                    }
+
+                    let allowed_orgs =
+                        query
+                            .load::<RoleAllowedOrgModel>(self.conn)
+                            .map_err(|err| OrganizationStoreError::OperationError {
+                                context: "Failed to fetch role allowed organizations".to_string(),
+                                source: Some(Box::new(err)),
+                            })?;
+
+                    roles.push(Role::from((role, permissions, allowed_orgs)));
+                }
+
+                Ok(roles)
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> OrganizationStoreListRolesOperation
+    for OrganizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_roles(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Role>, OrganizationStoreError> {
+        self.conn
+            .immediate_transaction::<_, OrganizationStoreError, _>(|| {
+                let mut query = pike_role::table
+                    .into_boxed()
+                    .select(pike_role::all_columns)
+                    .filter(pike_role::end_commit_num.eq(MAX_COMMIT_NUM));
+
+                if let Some(org_id) = org_id {
+                    query = query.filter(pike_role::org_id.eq(org_id));
+                }
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(pike_role::service_id.eq(service_id));
+                } else {
+                    query = query.filter(pike_role::service_id.is_null());
+                }
+
+                let role_models = query.load::<RoleModel>(self.conn).map_err(|err| {
+                    OrganizationStoreError::OperationError {
+                        context: "Failed to fetch roles".to_string(),
+                        source: Some(Box::new(err)),
+                    }
+                })?;
+
+                let mut roles = Vec::new();
+
+                for role in role_models {
+                    let mut query = pike_role_permission::table
+                        .into_boxed()
+                        .select(pike_role_permission::all_columns)
+                        .filter(
+                            pike_role_permission::org_id
+                                .eq(&role.org_id)
+                                .and(pike_role_permission::role_name.eq(&role.name))
+                                .and(pike_role_permission::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        );
+
+                    if let Some(service_id) = service_id {
+                        query = query.filter(pike_role_permission::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(pike_role_permission::service_id.is_null());
+                    }
+
+                    let permissions =
+                        query
+                            .load::<RolePermissionModel>(self.conn)
+                            .map_err(|err| OrganizationStoreError::OperationError {
+                                context: "Failed to fetch role permissions".to_string(),
+                                source: Some(Box::new(err)),
+                            })?;
+
+                    let mut query = pike_role_allowed_org::table
+                        .into_boxed()
+                        .select(pike_role_allowed_org::all_columns)
+                        .filter(
+                            pike_role_allowed_org::org_id
+                                .eq(&role.org_id)
+                                .and(pike_role_allowed_org::role_name.eq(&role.name))
+                                .and(pike_role_allowed_org::end_commit_num.eq(MAX_COMMIT_NUM)),
+                        );
+
+                    if let Some(service_id) = service_id {
+                        query = query.filter(pike_role_allowed_org::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(pike_role_allowed_org::service_id.is_null());
+                    }
+
+                    let allowed_orgs =
+                        query
+                            .load::<RoleAllowedOrgModel>(self.conn)
+                            .map_err(|err| OrganizationStoreError::OperationError {
+                                context: "Failed to fetch role allowed organizations".to_string(),
+                                source: Some(Box::new(err)),
+                            })?;
+
+                    roles.push(Role::from((role, permissions, allowed_orgs)));
+                }
+
+                Ok(roles)
+            })
+    }
+}
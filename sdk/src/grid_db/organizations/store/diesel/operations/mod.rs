@@ -13,8 +13,13 @@
 // limitations under the License.
 
 pub(super) mod add_organizations;
+pub(super) mod add_roles;
+pub(super) mod delete_organization;
+pub(super) mod delete_role;
 pub(super) mod fetch_organization;
+pub(super) mod fetch_role;
 pub(super) mod list_organizations;
+pub(super) mod list_roles;
 
 pub(super) struct OrganizationStoreOperations<'a, C> {
     conn: &'a C,
@@ -22,5 +22,46 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
+    }
+}
+
+table! {
+    pike_role (id) {
+        id -> Int8,
+        org_id -> Varchar,
+        name -> Varchar,
+        description -> Text,
+        active -> Bool,
+        start_commit_num -> Int8,
+        end_commit_num -> Int8,
+        service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
+    }
+}
+
+table! {
+    pike_role_permission (id) {
+        id -> Int8,
+        org_id -> Varchar,
+        role_name -> Varchar,
+        permission -> Text,
+        start_commit_num -> Int8,
+        end_commit_num -> Int8,
+        service_id -> Nullable<Text>,
+    }
+}
+
+table! {
+    pike_role_allowed_org (id) {
+        id -> Int8,
+        org_id -> Varchar,
+        role_name -> Varchar,
+        allowed_org_id -> Varchar,
+        start_commit_num -> Int8,
+        end_commit_num -> Int8,
+        service_id -> Nullable<Text>,
     }
 }
@@ -18,19 +18,21 @@ use std::sync::{Arc, Mutex};
 
 use super::OrganizationStore;
 use crate::grid_db::commits::MAX_COMMIT_NUM;
-use crate::grid_db::organizations::store::{error::OrganizationStoreError, Organization};
+use crate::grid_db::organizations::store::{error::OrganizationStoreError, Organization, Role};
 
 /// Implementation of OrganizationStore that stores Organizations in memory. Useful for when
 /// persistence isn't necessary.
 #[derive(Clone, Default)]
 pub struct MemoryOrganizationStore {
     inner_organization: Arc<Mutex<HashMap<String, Organization>>>,
+    inner_role: Arc<Mutex<HashMap<(String, String), Role>>>,
 }
 
 impl MemoryOrganizationStore {
     pub fn new() -> Self {
         MemoryOrganizationStore {
             inner_organization: Arc::new(Mutex::new(HashMap::new())),
+            inner_role: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -75,6 +77,8 @@ impl OrganizationStore for MemoryOrganizationStore {
                 start_commit_num: o.start_commit_num,
                 end_commit_num: o.end_commit_num,
                 service_id: o.service_id.clone(),
+                transaction_id: o.transaction_id.clone(),
+                signer: o.signer.clone(),
             });
         Ok(Vec::from_iter(filtered_orgs))
     }
@@ -106,4 +110,113 @@ impl OrganizationStore for MemoryOrganizationStore {
             org_id,
         )))
     }
+
+    fn delete_organization(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        let mut inner_organization =
+            self.inner_organization
+                .lock()
+                .map_err(|_| OrganizationStoreError::StorageError {
+                    context: "Cannot access organizations: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+
+        for (_, o) in inner_organization.iter_mut() {
+            if o.service_id == service_id.map(String::from)
+                && o.org_id == org_id
+                && o.end_commit_num == MAX_COMMIT_NUM
+            {
+                o.end_commit_num = current_commit_num;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn add_roles(&self, roles: Vec<Role>) -> Result<(), OrganizationStoreError> {
+        let mut inner_role =
+            self.inner_role
+                .lock()
+                .map_err(|_| OrganizationStoreError::StorageError {
+                    context: "Cannot access roles: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        for role in roles {
+            inner_role.insert((role.org_id.clone(), role.name.clone()), role);
+        }
+        Ok(())
+    }
+
+    fn list_roles(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Role>, OrganizationStoreError> {
+        let inner_role =
+            self.inner_role
+                .lock()
+                .map_err(|_| OrganizationStoreError::StorageError {
+                    context: "Cannot access roles: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+        let filtered_roles = inner_role
+            .values()
+            .filter(|r| {
+                r.service_id.eq(&service_id.map(String::from))
+                    && r.end_commit_num.eq(&MAX_COMMIT_NUM)
+                    && org_id.map(|org_id| r.org_id == org_id).unwrap_or(true)
+            })
+            .cloned();
+        Ok(Vec::from_iter(filtered_roles))
+    }
+
+    fn fetch_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<Role>, OrganizationStoreError> {
+        let inner_role =
+            self.inner_role
+                .lock()
+                .map_err(|_| OrganizationStoreError::StorageError {
+                    context: "Cannot access roles: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+
+        Ok(inner_role
+            .get(&(org_id.to_string(), name.to_string()))
+            .filter(|r| {
+                r.service_id == service_id.map(String::from) && r.end_commit_num == MAX_COMMIT_NUM
+            })
+            .cloned())
+    }
+
+    fn delete_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        let mut inner_role =
+            self.inner_role
+                .lock()
+                .map_err(|_| OrganizationStoreError::StorageError {
+                    context: "Cannot access roles: mutex lock poisoned".to_string(),
+                    source: None,
+                })?;
+
+        if let Some(r) = inner_role.get_mut(&(org_id.to_string(), name.to_string())) {
+            if r.service_id == service_id.map(String::from) && r.end_commit_num == MAX_COMMIT_NUM {
+                r.end_commit_num = current_commit_num;
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -35,6 +35,25 @@ pub struct Organization {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+}
+
+/// Represents a Pike role owned by an organization
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct Role {
+    pub org_id: String,
+    pub name: String,
+    pub description: String,
+    pub active: bool,
+    pub permissions: Vec<String>,
+    pub allowed_organizations: Vec<String>,
+    // The indicators of the start and stop for the slowly-changing dimensions.
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+    pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 pub trait OrganizationStore: Send + Sync {
@@ -66,6 +85,69 @@ pub trait OrganizationStore: Send + Sync {
         org_id: &str,
         service_id: Option<&str>,
     ) -> Result<Option<Organization>, OrganizationStoreError>;
+
+    /// Ends the current version of an organization
+    ///
+    /// # Arguments
+    ///
+    ///  * `org_id` - The organization id to delete
+    ///  * `service_id` - The service id of the organization to delete
+    ///  * `current_commit_num` - The commit number the organization was deleted in
+    fn delete_organization(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError>;
+
+    /// Adds roles to the underlying storage, replacing any current version
+    ///
+    /// # Arguments
+    ///
+    ///  * `roles` - The roles to be added
+    fn add_roles(&self, roles: Vec<Role>) -> Result<(), OrganizationStoreError>;
+
+    ///  Lists roles from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `org_id` - Optional organization id to restrict the roles to
+    ///  * `service_id` - The service id to list roles for
+    fn list_roles(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Role>, OrganizationStoreError>;
+
+    /// Fetches a role from the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `org_id` - The id of the organization that owns the role
+    ///  * `name` - The name of the role to fetch
+    ///  * `service_id` - The service id of the role to fetch
+    fn fetch_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<Role>, OrganizationStoreError>;
+
+    /// Ends the current version of a role
+    ///
+    /// # Arguments
+    ///
+    ///  * `org_id` - The id of the organization that owns the role
+    ///  * `name` - The name of the role to delete
+    ///  * `service_id` - The service id of the role to delete
+    ///  * `current_commit_num` - The commit number the role was deleted in
+    fn delete_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError>;
 }
 
 impl<OS> OrganizationStore for Box<OS>
@@ -90,4 +172,44 @@ where
     ) -> Result<Option<Organization>, OrganizationStoreError> {
         (**self).fetch_organization(org_id, service_id)
     }
+
+    fn delete_organization(
+        &self,
+        org_id: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        (**self).delete_organization(org_id, service_id, current_commit_num)
+    }
+
+    fn add_roles(&self, roles: Vec<Role>) -> Result<(), OrganizationStoreError> {
+        (**self).add_roles(roles)
+    }
+
+    fn list_roles(
+        &self,
+        org_id: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Role>, OrganizationStoreError> {
+        (**self).list_roles(org_id, service_id)
+    }
+
+    fn fetch_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+    ) -> Result<Option<Role>, OrganizationStoreError> {
+        (**self).fetch_role(org_id, name, service_id)
+    }
+
+    fn delete_role(
+        &self,
+        org_id: &str,
+        name: &str,
+        service_id: Option<&str>,
+        current_commit_num: i64,
+    ) -> Result<(), OrganizationStoreError> {
+        (**self).delete_role(org_id, name, service_id, current_commit_num)
+    }
 }
@@ -22,13 +22,17 @@ use crate::grid_db::products::MAX_COMMIT_NUM;
 use models::{NewProduct, NewProductPropertyValue, Product as ModelProduct, ProductPropertyValue};
 use operations::{
     add_product::AddProductOperation, delete_product::DeleteProductOperation,
-    fetch_product::FetchProductOperation, list_products::ListProductsOperation,
-    update_product::UpdateProductOperation, ProductStoreOperations,
+    fetch_product::FetchProductOperation,
+    list_product_status_changes::ListProductStatusChangesOperation,
+    list_products::ListProductsOperation, update_product::UpdateProductOperation,
+    ProductStoreOperations,
 };
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
-use super::{LatLongValue, Product, ProductStore, ProductStoreError, PropertyValue};
+use super::{
+    LatLongValue, Product, ProductStatusChange, ProductStore, ProductStoreError, PropertyValue,
+};
 
 #[derive(Clone)]
 pub struct DieselProductStore<C: diesel::Connection + 'static> {
@@ -56,7 +60,7 @@ impl ProductStore for DieselProductStore<diesel::pg::PgConnection> {
 
     fn fetch_product(
         &self,
-        product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError> {
         ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -65,17 +69,23 @@ impl ProductStore for DieselProductStore<diesel::pg::PgConnection> {
                 source: Box::new(err),
             }
         })?)
-        .fetch_product(product_id, service_id)
+        .fetch_product(product_address, service_id)
     }
 
-    fn list_products(&self, service_id: Option<&str>) -> Result<Vec<Product>, ProductStoreError> {
+    fn list_products(
+        &self,
+        product_namespace: Option<&str>,
+        schema_name: Option<&str>,
+        status: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
         ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             DatabaseError::ConnectionError {
                 context: "Could not get connection pool".to_string(),
                 source: Box::new(err),
             }
         })?)
-        .list_products(service_id)
+        .list_products(product_namespace, schema_name, status, service_id)
     }
 
     fn update_product(
@@ -93,6 +103,20 @@ impl ProductStore for DieselProductStore<diesel::pg::PgConnection> {
         .update_product(product_id, service_id, current_commit_num)
     }
 
+    fn list_product_status_changes(
+        &self,
+        product_address: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ProductStatusChange>, ProductStoreError> {
+        ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_product_status_changes(product_address, service_id)
+    }
+
     fn delete_product(
         &self,
         address: &str,
@@ -122,7 +146,7 @@ impl ProductStore for DieselProductStore<diesel::sqlite::SqliteConnection> {
 
     fn fetch_product(
         &self,
-        product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError> {
         ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -131,17 +155,23 @@ impl ProductStore for DieselProductStore<diesel::sqlite::SqliteConnection> {
                 source: Box::new(err),
             }
         })?)
-        .fetch_product(product_id, service_id)
+        .fetch_product(product_address, service_id)
     }
 
-    fn list_products(&self, service_id: Option<&str>) -> Result<Vec<Product>, ProductStoreError> {
+    fn list_products(
+        &self,
+        product_namespace: Option<&str>,
+        schema_name: Option<&str>,
+        status: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
         ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
             DatabaseError::ConnectionError {
                 context: "Could not get connection pool".to_string(),
                 source: Box::new(err),
             }
         })?)
-        .list_products(service_id)
+        .list_products(product_namespace, schema_name, status, service_id)
     }
 
     fn update_product(
@@ -159,6 +189,20 @@ impl ProductStore for DieselProductStore<diesel::sqlite::SqliteConnection> {
         .update_product(product_id, service_id, current_commit_num)
     }
 
+    fn list_product_status_changes(
+        &self,
+        product_address: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ProductStatusChange>, ProductStoreError> {
+        ProductStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_product_status_changes(product_address, service_id)
+    }
+
     fn delete_product(
         &self,
         address: &str,
@@ -180,10 +224,16 @@ impl Into<(NewProduct, Vec<NewProductPropertyValue>)> for Product {
             product_id: self.product_id.clone(),
             product_address: self.product_address.clone(),
             product_namespace: self.product_namespace.clone(),
+            schema_name: self.schema_name.clone(),
             owner: self.owner.clone(),
             start_commit_num: self.start_commit_num,
             end_commit_num: self.end_commit_num,
             service_id: self.service_id.clone(),
+            transaction_id: self.transaction_id.clone(),
+            signer: self.signer.clone(),
+            pending_owner: self.pending_owner.clone(),
+            schema_version: self.schema_version,
+            status: self.status.clone(),
         };
 
         (product, make_property_values(None, &self.properties))
@@ -196,11 +246,17 @@ impl From<(ModelProduct, Vec<PropertyValue>)> for Product {
             product_id: model.product_id,
             product_address: model.product_address,
             product_namespace: model.product_namespace,
+            schema_name: model.schema_name,
             owner: model.owner,
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
+            transaction_id: model.transaction_id,
+            signer: model.signer,
             properties,
+            pending_owner: model.pending_owner,
+            schema_version: model.schema_version,
+            status: model.status,
         }
     }
 }
@@ -228,6 +284,8 @@ fn make_property_values(
             start_commit_num: property.start_commit_num,
             end_commit_num: MAX_COMMIT_NUM,
             service_id: property.service_id.clone(),
+            timestamp_value: property.timestamp_value,
+            decimal_value: property.decimal_value.clone(),
         });
 
         if !property.struct_values.is_empty() {
@@ -239,6 +297,17 @@ fn make_property_values(
                 &property.struct_values,
             ));
         }
+
+        // List elements are stored as children named by their index
+        if !property.list_values.is_empty() {
+            model_properties.append(&mut make_property_values(
+                Some(format!(
+                    "{}:{}",
+                    property.product_id, property.property_name
+                )),
+                &property.list_values,
+            ));
+        }
     }
 
     model_properties
@@ -257,6 +326,7 @@ impl From<ProductPropertyValue> for PropertyValue {
             string_value: model.string_value,
             enum_value: model.enum_value,
             struct_values: vec![],
+            list_values: vec![],
             lat_long_value: if model.latitude_value.is_some() && model.longitude_value.is_some() {
                 Some(LatLongValue {
                     latitude: model.latitude_value.unwrap(),
@@ -265,6 +335,8 @@ impl From<ProductPropertyValue> for PropertyValue {
             } else {
                 None
             },
+            timestamp_value: model.timestamp_value,
+            decimal_value: model.decimal_value,
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
@@ -274,6 +346,12 @@ impl From<ProductPropertyValue> for PropertyValue {
 
 impl From<(ProductPropertyValue, Vec<PropertyValue>)> for PropertyValue {
     fn from((model, children): (ProductPropertyValue, Vec<PropertyValue>)) -> Self {
+        let (struct_values, list_values) = if model.data_type == "List" {
+            (vec![], children)
+        } else {
+            (children, vec![])
+        };
+
         Self {
             product_id: model.product_id,
             product_address: model.product_address,
@@ -284,7 +362,8 @@ impl From<(ProductPropertyValue, Vec<PropertyValue>)> for PropertyValue {
             number_value: model.number_value,
             string_value: model.string_value,
             enum_value: model.enum_value,
-            struct_values: children,
+            struct_values,
+            list_values,
             lat_long_value: if model.latitude_value.is_some() && model.longitude_value.is_some() {
                 Some(LatLongValue {
                     latitude: model.latitude_value.unwrap(),
@@ -293,6 +372,8 @@ impl From<(ProductPropertyValue, Vec<PropertyValue>)> for PropertyValue {
             } else {
                 None
             },
+            timestamp_value: model.timestamp_value,
+            decimal_value: model.decimal_value,
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
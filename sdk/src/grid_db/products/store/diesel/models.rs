@@ -24,6 +24,12 @@ pub struct NewProduct {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub schema_name: String,
+    pub pending_owner: String,
+    pub schema_version: i32,
+    pub status: String,
 }
 
 #[derive(Queryable, Identifiable, Debug)]
@@ -37,6 +43,12 @@ pub struct Product {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub schema_name: String,
+    pub pending_owner: String,
+    pub schema_version: i32,
+    pub status: String,
 }
 
 #[derive(AsChangeset, Clone, Insertable, Debug)]
@@ -57,6 +69,8 @@ pub struct NewProductPropertyValue {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
 }
 
 #[derive(Queryable, Identifiable, Debug)]
@@ -78,4 +92,6 @@ pub struct ProductPropertyValue {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
 }
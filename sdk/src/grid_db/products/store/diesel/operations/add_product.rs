@@ -71,6 +71,7 @@ mod pg {
         update_prod_end_commit_num(
             conn,
             &product.product_id,
+            &product.product_address,
             product.service_id.as_deref(),
             product.start_commit_num,
         )?;
@@ -89,6 +90,7 @@ mod pg {
             update_prod_property_values(
                 conn,
                 &value.product_id,
+                &value.product_address,
                 value.service_id.as_deref(),
                 value.start_commit_num,
             )?;
@@ -102,6 +104,7 @@ mod pg {
     fn update_prod_end_commit_num(
         conn: &PgConnection,
         product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
         current_commit_num: i64,
     ) -> QueryResult<()> {
@@ -112,6 +115,7 @@ mod pg {
                 .filter(
                     product::product_id
                         .eq(product_id)
+                        .and(product::product_address.eq(product_address))
                         .and(product::end_commit_num.eq(MAX_COMMIT_NUM))
                         .and(product::service_id.eq(service_id)),
                 )
@@ -123,6 +127,7 @@ mod pg {
                 .filter(
                     product::product_id
                         .eq(product_id)
+                        .and(product::product_address.eq(product_address))
                         .and(product::end_commit_num.eq(MAX_COMMIT_NUM)),
                 )
                 .set(product::end_commit_num.eq(current_commit_num))
@@ -134,6 +139,7 @@ mod pg {
     fn update_prod_property_values(
         conn: &PgConnection,
         product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
         current_commit_num: i64,
     ) -> QueryResult<()> {
@@ -144,6 +150,7 @@ mod pg {
                 .filter(
                     product_property_value::product_id
                         .eq(product_id)
+                        .and(product_property_value::product_address.eq(product_address))
                         .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM))
                         .and(product_property_value::service_id.eq(service_id)),
                 )
@@ -155,6 +162,7 @@ mod pg {
                 .filter(
                     product_property_value::product_id
                         .eq(product_id)
+                        .and(product_property_value::product_address.eq(product_address))
                         .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
                 )
                 .set(product_property_value::end_commit_num.eq(current_commit_num))
@@ -172,6 +180,7 @@ mod sqlite {
         update_prod_end_commit_num(
             conn,
             &product.product_id,
+            &product.product_address,
             product.service_id.as_deref(),
             product.start_commit_num,
         )?;
@@ -190,6 +199,7 @@ mod sqlite {
             update_prod_property_values(
                 conn,
                 &value.product_id,
+                &value.product_address,
                 value.service_id.as_deref(),
                 value.start_commit_num,
             )?;
@@ -204,6 +214,7 @@ mod sqlite {
     fn update_prod_end_commit_num(
         conn: &SqliteConnection,
         product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
         current_commit_num: i64,
     ) -> QueryResult<()> {
@@ -214,6 +225,7 @@ mod sqlite {
                 .filter(
                     product::product_id
                         .eq(product_id)
+                        .and(product::product_address.eq(product_address))
                         .and(product::end_commit_num.eq(MAX_COMMIT_NUM))
                         .and(product::service_id.eq(service_id)),
                 )
@@ -225,6 +237,7 @@ mod sqlite {
                 .filter(
                     product::product_id
                         .eq(product_id)
+                        .and(product::product_address.eq(product_address))
                         .and(product::end_commit_num.eq(MAX_COMMIT_NUM)),
                 )
                 .set(product::end_commit_num.eq(current_commit_num))
@@ -236,6 +249,7 @@ mod sqlite {
     fn update_prod_property_values(
         conn: &SqliteConnection,
         product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
         current_commit_num: i64,
     ) -> QueryResult<()> {
@@ -246,6 +260,7 @@ mod sqlite {
                 .filter(
                     product_property_value::product_id
                         .eq(product_id)
+                        .and(product_property_value::product_address.eq(product_address))
                         .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM))
                         .and(product_property_value::service_id.eq(service_id)),
                 )
@@ -257,6 +272,7 @@ mod sqlite {
                 .filter(
                     product_property_value::product_id
                         .eq(product_id)
+                        .and(product_property_value::product_address.eq(product_address))
                         .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
                 )
                 .set(product_property_value::end_commit_num.eq(current_commit_num))
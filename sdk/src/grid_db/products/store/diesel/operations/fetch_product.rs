@@ -30,7 +30,7 @@ use diesel::{prelude::*, result::Error::NotFound};
 pub(in crate::grid_db::products) trait FetchProductOperation {
     fn fetch_product(
         &self,
-        product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError>;
 }
@@ -39,17 +39,18 @@ pub(in crate::grid_db::products) trait FetchProductOperation {
 impl<'a> FetchProductOperation for ProductStoreOperations<'a, diesel::pg::PgConnection> {
     fn fetch_product(
         &self,
-        product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError> {
-        let product = if let Some(product) = pg::fetch_product(&*self.conn, product_id, service_id)?
-        {
-            product
-        } else {
-            return Ok(None);
-        };
+        let product =
+            if let Some(product) = pg::fetch_product(&*self.conn, product_address, service_id)? {
+                product
+            } else {
+                return Ok(None);
+            };
 
-        let root_values = pg::get_root_values(&*self.conn, product_id)?;
+        let root_values =
+            pg::get_root_values(&*self.conn, &product.product_id, &product.product_address)?;
 
         let values = pg::get_property_values(&*self.conn, root_values)?;
 
@@ -61,17 +62,19 @@ impl<'a> FetchProductOperation for ProductStoreOperations<'a, diesel::pg::PgConn
 impl<'a> FetchProductOperation for ProductStoreOperations<'a, diesel::sqlite::SqliteConnection> {
     fn fetch_product(
         &self,
-        product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError> {
-        let product =
-            if let Some(product) = sqlite::fetch_product(&*self.conn, product_id, service_id)? {
-                product
-            } else {
-                return Ok(None);
-            };
+        let product = if let Some(product) =
+            sqlite::fetch_product(&*self.conn, product_address, service_id)?
+        {
+            product
+        } else {
+            return Ok(None);
+        };
 
-        let root_values = sqlite::get_root_values(&*self.conn, product_id)?;
+        let root_values =
+            sqlite::get_root_values(&*self.conn, &product.product_id, &product.product_address)?;
 
         let values = sqlite::get_property_values(&*self.conn, root_values)?;
 
@@ -85,15 +88,15 @@ mod pg {
 
     pub fn fetch_product(
         conn: &PgConnection,
-        product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Option<ModelProduct>> {
         let mut query = product::table
             .into_boxed()
             .select(product::all_columns)
             .filter(
-                product::product_id
-                    .eq(product_id)
+                product::product_address
+                    .eq(product_address)
                     .and(product::end_commit_num.eq(MAX_COMMIT_NUM)),
             );
 
@@ -112,12 +115,14 @@ mod pg {
     pub fn get_root_values(
         conn: &PgConnection,
         product_id: &str,
+        product_address: &str,
     ) -> QueryResult<Vec<ProductPropertyValue>> {
         product_property_value::table
             .select(product_property_value::all_columns)
             .filter(
                 product_property_value::product_id
                     .eq(product_id)
+                    .and(product_property_value::product_address.eq(product_address))
                     .and(product_property_value::parent_property.is_null())
                     .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
             )
@@ -133,7 +138,13 @@ mod pg {
         for root_value in root_values {
             let children = product_property_value::table
                 .select(product_property_value::all_columns)
-                .filter(product_property_value::parent_property.eq(&root_value.parent_property))
+                .filter(product_property_value::parent_property.eq(format!(
+                    "{}:{}",
+                    root_value.product_id, root_value.property_name
+                )))
+                .filter(product_property_value::product_address.eq(&root_value.product_address))
+                .filter(product_property_value::end_commit_num.eq(root_value.end_commit_num))
+                .order(product_property_value::id)
                 .load(conn)?;
 
             if children.is_empty() {
@@ -156,15 +167,15 @@ mod sqlite {
 
     pub fn fetch_product(
         conn: &SqliteConnection,
-        product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
     ) -> QueryResult<Option<ModelProduct>> {
         let mut query = product::table
             .into_boxed()
             .select(product::all_columns)
             .filter(
-                product::product_id
-                    .eq(product_id)
+                product::product_address
+                    .eq(product_address)
                     .and(product::end_commit_num.eq(MAX_COMMIT_NUM)),
             );
 
@@ -183,12 +194,14 @@ mod sqlite {
     pub fn get_root_values(
         conn: &SqliteConnection,
         product_id: &str,
+        product_address: &str,
     ) -> QueryResult<Vec<ProductPropertyValue>> {
         product_property_value::table
             .select(product_property_value::all_columns)
             .filter(
                 product_property_value::product_id
                     .eq(product_id)
+                    .and(product_property_value::product_address.eq(product_address))
                     .and(product_property_value::parent_property.is_null())
                     .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
             )
@@ -204,7 +217,13 @@ mod sqlite {
         for root_value in root_values {
             let children = product_property_value::table
                 .select(product_property_value::all_columns)
-                .filter(product_property_value::parent_property.eq(&root_value.parent_property))
+                .filter(product_property_value::parent_property.eq(format!(
+                    "{}:{}",
+                    root_value.product_id, root_value.property_name
+                )))
+                .filter(product_property_value::product_address.eq(&root_value.product_address))
+                .filter(product_property_value::end_commit_num.eq(root_value.end_commit_num))
+                .order(product_property_value::id)
                 .load(conn)?;
 
             if children.is_empty() {
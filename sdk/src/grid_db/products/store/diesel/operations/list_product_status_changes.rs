@@ -0,0 +1,110 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::ProductStoreOperations;
+
+use crate::grid_db::products::store::{
+    diesel::{models::Product as ModelProduct, schema::product},
+    error::ProductStoreError,
+    ProductStatusChange,
+};
+use diesel::prelude::*;
+
+pub(in crate::grid_db::products) trait ListProductStatusChangesOperation {
+    fn list_product_status_changes(
+        &self,
+        product_address: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ProductStatusChange>, ProductStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ListProductStatusChangesOperation
+    for ProductStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_product_status_changes(
+        &self,
+        product_address: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ProductStatusChange>, ProductStoreError> {
+        let mut query = product::table
+            .into_boxed()
+            .select(product::all_columns)
+            .filter(product::product_address.eq(product_address))
+            .order(product::start_commit_num.asc());
+
+        if let Some(service_id) = service_id {
+            query = query.filter(product::service_id.eq(service_id));
+        } else {
+            query = query.filter(product::service_id.is_null());
+        }
+
+        let versions = query.load::<ModelProduct>(&*self.conn)?;
+
+        Ok(make_status_changes(versions))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ListProductStatusChangesOperation
+    for ProductStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_product_status_changes(
+        &self,
+        product_address: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ProductStatusChange>, ProductStoreError> {
+        let mut query = product::table
+            .into_boxed()
+            .select(product::all_columns)
+            .filter(product::product_address.eq(product_address))
+            .order(product::start_commit_num.asc());
+
+        if let Some(service_id) = service_id {
+            query = query.filter(product::service_id.eq(service_id));
+        } else {
+            query = query.filter(product::service_id.is_null());
+        }
+
+        let versions = query.load::<ModelProduct>(&*self.conn)?;
+
+        Ok(make_status_changes(versions))
+    }
+}
+
+/// Every version of a product is kept, so its status history is the versions at which the status
+/// differs from the version before, oldest first
+fn make_status_changes(versions: Vec<ModelProduct>) -> Vec<ProductStatusChange> {
+    let mut changes: Vec<ProductStatusChange> = Vec::new();
+
+    for version in versions {
+        if changes
+            .last()
+            .map(|change| change.status == version.status)
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        changes.push(ProductStatusChange {
+            product_id: version.product_id,
+            status: version.status,
+            start_commit_num: version.start_commit_num,
+            transaction_id: version.transaction_id,
+            signer: version.signer,
+        });
+    }
+
+    changes
+}
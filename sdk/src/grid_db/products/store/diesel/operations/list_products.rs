@@ -28,18 +28,37 @@ use crate::grid_db::products::{
 use diesel::prelude::*;
 
 pub(in crate::grid_db::products) trait ListProductsOperation {
-    fn list_products(&self, service_id: Option<&str>) -> Result<Vec<Product>, ProductStoreError>;
+    fn list_products(
+        &self,
+        product_namespace: Option<&str>,
+        schema_name: Option<&str>,
+        status: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError>;
 }
 
 #[cfg(feature = "postgres")]
 impl<'a> ListProductsOperation for ProductStoreOperations<'a, diesel::pg::PgConnection> {
-    fn list_products(&self, service_id: Option<&str>) -> Result<Vec<Product>, ProductStoreError> {
-        let db_products = pg::list_products(&*self.conn, service_id)?;
+    fn list_products(
+        &self,
+        product_namespace: Option<&str>,
+        schema_name: Option<&str>,
+        status: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
+        let db_products = pg::list_products(
+            &*self.conn,
+            product_namespace,
+            schema_name,
+            status,
+            service_id,
+        )?;
 
         let mut products = Vec::new();
 
         for product in db_products {
-            let root_values = pg::get_root_values(&*self.conn, &product.product_id)?;
+            let root_values =
+                pg::get_root_values(&*self.conn, &product.product_id, &product.product_address)?;
 
             let values = pg::get_property_values(&*self.conn, root_values)?;
 
@@ -52,13 +71,29 @@ impl<'a> ListProductsOperation for ProductStoreOperations<'a, diesel::pg::PgConn
 
 #[cfg(feature = "sqlite")]
 impl<'a> ListProductsOperation for ProductStoreOperations<'a, diesel::sqlite::SqliteConnection> {
-    fn list_products(&self, service_id: Option<&str>) -> Result<Vec<Product>, ProductStoreError> {
-        let db_products = sqlite::list_products(&*self.conn, service_id)?;
+    fn list_products(
+        &self,
+        product_namespace: Option<&str>,
+        schema_name: Option<&str>,
+        status: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError> {
+        let db_products = sqlite::list_products(
+            &*self.conn,
+            product_namespace,
+            schema_name,
+            status,
+            service_id,
+        )?;
 
         let mut products = Vec::new();
 
         for product in db_products {
-            let root_values = sqlite::get_root_values(&*self.conn, &product.product_id)?;
+            let root_values = sqlite::get_root_values(
+                &*self.conn,
+                &product.product_id,
+                &product.product_address,
+            )?;
 
             let values = sqlite::get_property_values(&*self.conn, root_values)?;
 
@@ -75,6 +110,9 @@ mod pg {
 
     pub fn list_products(
         conn: &PgConnection,
+        product_namespace: Option<&str>,
+        schema_name: Option<&str>,
+        status: Option<&str>,
         service_id: Option<&str>,
     ) -> QueryResult<Vec<ModelProduct>> {
         let mut query = product::table
@@ -82,6 +120,16 @@ mod pg {
             .select(product::all_columns)
             .filter(product::end_commit_num.eq(MAX_COMMIT_NUM));
 
+        if let Some(product_namespace) = product_namespace {
+            query = query.filter(product::product_namespace.eq(product_namespace));
+        }
+        if let Some(schema_name) = schema_name {
+            query = query.filter(product::schema_name.eq(schema_name));
+        }
+        if let Some(status) = status {
+            query = query.filter(product::status.eq(status));
+        }
+
         if let Some(service_id) = service_id {
             query = query.filter(product::service_id.eq(service_id));
         } else {
@@ -93,12 +141,14 @@ mod pg {
     pub fn get_root_values(
         conn: &PgConnection,
         product_id: &str,
+        product_address: &str,
     ) -> QueryResult<Vec<ProductPropertyValue>> {
         product_property_value::table
             .select(product_property_value::all_columns)
             .filter(
                 product_property_value::product_id
                     .eq(product_id)
+                    .and(product_property_value::product_address.eq(product_address))
                     .and(product_property_value::parent_property.is_null())
                     .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
             )
@@ -114,7 +164,13 @@ mod pg {
         for root_value in root_values {
             let children = product_property_value::table
                 .select(product_property_value::all_columns)
-                .filter(product_property_value::parent_property.eq(&root_value.parent_property))
+                .filter(product_property_value::parent_property.eq(format!(
+                    "{}:{}",
+                    root_value.product_id, root_value.property_name
+                )))
+                .filter(product_property_value::product_address.eq(&root_value.product_address))
+                .filter(product_property_value::end_commit_num.eq(root_value.end_commit_num))
+                .order(product_property_value::id)
                 .load(conn)?;
 
             if children.is_empty() {
@@ -137,6 +193,9 @@ mod sqlite {
 
     pub fn list_products(
         conn: &SqliteConnection,
+        product_namespace: Option<&str>,
+        schema_name: Option<&str>,
+        status: Option<&str>,
         service_id: Option<&str>,
     ) -> QueryResult<Vec<ModelProduct>> {
         let mut query = product::table
@@ -144,6 +203,16 @@ mod sqlite {
             .select(product::all_columns)
             .filter(product::end_commit_num.eq(MAX_COMMIT_NUM));
 
+        if let Some(product_namespace) = product_namespace {
+            query = query.filter(product::product_namespace.eq(product_namespace));
+        }
+        if let Some(schema_name) = schema_name {
+            query = query.filter(product::schema_name.eq(schema_name));
+        }
+        if let Some(status) = status {
+            query = query.filter(product::status.eq(status));
+        }
+
         if let Some(service_id) = service_id {
             query = query.filter(product::service_id.eq(service_id));
         } else {
@@ -155,12 +224,14 @@ mod sqlite {
     pub fn get_root_values(
         conn: &SqliteConnection,
         product_id: &str,
+        product_address: &str,
     ) -> QueryResult<Vec<ProductPropertyValue>> {
         product_property_value::table
             .select(product_property_value::all_columns)
             .filter(
                 product_property_value::product_id
                     .eq(product_id)
+                    .and(product_property_value::product_address.eq(product_address))
                     .and(product_property_value::parent_property.is_null())
                     .and(product_property_value::end_commit_num.eq(MAX_COMMIT_NUM)),
             )
@@ -176,7 +247,13 @@ mod sqlite {
         for root_value in root_values {
             let children = product_property_value::table
                 .select(product_property_value::all_columns)
-                .filter(product_property_value::parent_property.eq(&root_value.parent_property))
+                .filter(product_property_value::parent_property.eq(format!(
+                    "{}:{}",
+                    root_value.product_id, root_value.property_name
+                )))
+                .filter(product_property_value::product_address.eq(&root_value.product_address))
+                .filter(product_property_value::end_commit_num.eq(root_value.end_commit_num))
+                .order(product_property_value::id)
                 .load(conn)?;
 
             if children.is_empty() {
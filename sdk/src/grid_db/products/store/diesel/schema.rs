@@ -30,6 +30,8 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        timestamp_value -> Nullable<Int8>,
+        decimal_value -> Nullable<Text>,
     }
 }
 
@@ -43,5 +45,11 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
+        schema_name -> Text,
+        pending_owner -> Text,
+        schema_version -> Int4,
+        status -> Text,
     }
 }
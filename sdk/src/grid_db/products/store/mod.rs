@@ -23,11 +23,32 @@ pub struct Product {
     pub product_id: String,
     pub product_address: String,
     pub product_namespace: String,
+    /// The schema a custom product follows; empty for GS1 products
+    pub schema_name: String,
     pub owner: String,
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
     pub properties: Vec<PropertyValue>,
+    /// The organization a transfer of the product has been offered to; empty if none is pending
+    pub pending_owner: String,
+    /// The version of the schema the product was last validated against
+    pub schema_version: i32,
+    /// Where the product is in its lifecycle: DRAFT, ACTIVE, DISCONTINUED or RECALLED
+    pub status: String,
+}
+
+/// A version of a product whose status differs from the version before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductStatusChange {
+    pub product_id: String,
+    pub status: String,
+    /// The commit the status took effect in
+    pub start_commit_num: i64,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +64,11 @@ pub struct PropertyValue {
     pub enum_value: Option<i32>,
     pub struct_values: Vec<PropertyValue>,
     pub lat_long_value: Option<LatLongValue>,
+    /// Seconds since the Unix epoch
+    pub timestamp_value: Option<i64>,
+    /// The exact decimal string, e.g. "12.050"
+    pub decimal_value: Option<String>,
+    pub list_values: Vec<PropertyValue>,
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
@@ -57,13 +83,23 @@ pub struct LatLongValue {
 pub trait ProductStore: Send + Sync {
     fn add_product(&self, product: Product) -> Result<(), ProductStoreError>;
 
+    /// Fetches the current version of the product stored at an address. Products that follow
+    /// different custom schemas may share an ID, so the address is what identifies a product.
     fn fetch_product(
         &self,
-        product_id: &str,
+        product_address: &str,
         service_id: Option<&str>,
     ) -> Result<Option<Product>, ProductStoreError>;
 
-    fn list_products(&self, service_id: Option<&str>) -> Result<Vec<Product>, ProductStoreError>;
+    /// Lists the current products, optionally only those of a namespace, of a custom schema or
+    /// with a lifecycle status
+    fn list_products(
+        &self,
+        product_namespace: Option<&str>,
+        schema_name: Option<&str>,
+        status: Option<&str>,
+        service_id: Option<&str>,
+    ) -> Result<Vec<Product>, ProductStoreError>;
 
     fn update_product(
         &self,
@@ -77,4 +113,11 @@ pub trait ProductStore: Send + Sync {
         address: &str,
         current_commit_num: i64,
     ) -> Result<(), ProductStoreError>;
+
+    /// Lists the changes in lifecycle status of the product stored at an address, oldest first
+    fn list_product_status_changes(
+        &self,
+        product_address: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ProductStatusChange>, ProductStoreError>;
 }
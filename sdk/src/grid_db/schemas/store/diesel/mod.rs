@@ -209,8 +209,11 @@ impl Into<(NewGridSchema, Vec<NewGridPropertyDefinition>)> for Schema {
             description: self.description.clone(),
             owner: self.owner.clone(),
             service_id: self.service_id.clone(),
+            transaction_id: self.transaction_id.clone(),
+            signer: self.signer.clone(),
             start_commit_num: self.start_commit_num,
             end_commit_num: self.end_commit_num,
+            version: self.version,
         };
 
         let properties = make_property_definitions(&self.properties, None);
@@ -227,8 +230,11 @@ impl From<(GridSchema, Vec<PropertyDefinition>)> for Schema {
             owner: model.owner,
             properties,
             service_id: model.service_id,
+            transaction_id: model.transaction_id,
+            signer: model.signer,
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
+            version: model.version,
         }
     }
 }
@@ -252,6 +258,8 @@ fn make_property_definitions(
             start_commit_num: def.start_commit_num,
             end_commit_num: def.end_commit_num,
             service_id: def.service_id.clone(),
+            deprecated: def.deprecated,
+            list_data_type: def.list_data_type.clone(),
         });
 
         if !def.struct_properties.is_empty() {
@@ -279,6 +287,8 @@ impl From<GridPropertyDefinition> for PropertyDefinition {
             enum_options: model.enum_options.split(',').map(String::from).collect(),
             struct_properties: vec![],
             service_id: model.service_id,
+            deprecated: model.deprecated,
+            list_data_type: model.list_data_type,
         }
     }
 }
@@ -297,6 +307,8 @@ impl From<(GridPropertyDefinition, Vec<PropertyDefinition>)> for PropertyDefinit
             enum_options: model.enum_options.split(',').map(String::from).collect(),
             struct_properties: children,
             service_id: model.service_id,
+            deprecated: model.deprecated,
+            list_data_type: model.list_data_type,
         }
     }
 }
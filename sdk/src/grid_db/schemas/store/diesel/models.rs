@@ -26,6 +26,9 @@ pub struct NewGridSchema {
     pub description: String,
     pub owner: String,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub version: i32,
 }
 
 #[derive(Queryable, Debug)]
@@ -37,6 +40,9 @@ pub struct GridSchema {
     pub description: String,
     pub owner: String,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub version: i32,
 }
 
 #[derive(Clone, Insertable, Debug)]
@@ -54,6 +60,8 @@ pub struct NewGridPropertyDefinition {
     pub enum_options: String,
     pub parent_name: Option<String>,
     pub service_id: Option<String>,
+    pub deprecated: bool,
+    pub list_data_type: Option<String>,
 }
 
 #[derive(Queryable, Debug)]
@@ -71,4 +79,6 @@ pub struct GridPropertyDefinition {
     pub enum_options: String,
     pub parent_name: Option<String>,
     pub service_id: Option<String>,
+    pub deprecated: bool,
+    pub list_data_type: Option<String>,
 }
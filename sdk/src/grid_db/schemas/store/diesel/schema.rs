@@ -24,6 +24,9 @@ table! {
         description -> Text,
         owner -> Text,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
+        version -> Int4,
     }
 }
 
@@ -41,5 +44,7 @@ table! {
         enum_options -> Text,
         parent_name -> Nullable<Text>,
         service_id -> Nullable<Text>,
+        deprecated -> Bool,
+        list_data_type -> Nullable<Text>,
     }
 }
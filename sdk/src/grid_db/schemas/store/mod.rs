@@ -27,8 +27,11 @@ pub struct Schema {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
     pub start_commit_num: i64,
     pub end_commit_num: i64,
+    pub version: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -46,6 +49,11 @@ pub struct PropertyDefinition {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_id: Option<String>,
+    pub deprecated: bool,
+    /// The type of each element of a List property
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_data_type: Option<String>,
 }
 
 pub trait SchemaStore: Send + Sync {
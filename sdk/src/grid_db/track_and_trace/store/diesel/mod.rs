@@ -20,27 +20,37 @@ use diesel::r2d2::{ConnectionManager, Pool};
 
 use super::diesel::models::{
     AssociatedAgentModel, NewAssociatedAgentModel, NewPropertyModel, NewProposalModel,
-    NewRecordModel, NewReportedValueModel, NewReporterModel, PropertyModel, ProposalModel,
-    RecordModel, ReportedValueReporterToAgentMetadataModel, ReporterModel,
+    NewRecordAggregationModel, NewRecordModel, NewReportedValueModel, NewReporterModel,
+    PropertyModel, ProposalModel, RecordAggregationModel, RecordModel, ReportedValueArchiveModel,
+    ReportedValueReporterToAgentMetadataModel, ReporterModel,
 };
 use super::{
-    AssociatedAgent, LatLongValue, Property, Proposal, Record, ReportedValue,
-    ReportedValueReporterToAgentMetadata, Reporter, TrackAndTraceStore, TrackAndTraceStoreError,
+    ArchivedReportedValue, AssociatedAgent, LatLongValue, Property, PropertyPageSnapshot, Proposal,
+    Record, RecordAggregation, RecordPosition, ReportedValue, ReportedValueBucket,
+    ReportedValueRange, ReportedValueReporterToAgentMetadata, Reporter, TrackAndTraceStore,
+    TrackAndTraceStoreError,
 };
 use crate::database::DatabaseError;
+use crate::grid_db::geo::BoundingBox;
 use operations::add_associated_agents::TrackAndTraceStoreAddAssociatedAgentsOperation as _;
 use operations::add_properties::TrackAndTraceStoreAddPropertiesOperation as _;
 use operations::add_proposals::TrackAndTraceStoreAddProposalsOperation as _;
+use operations::add_record_aggregations::TrackAndTraceStoreAddRecordAggregationsOperation as _;
 use operations::add_records::TrackAndTraceStoreAddRecordsOperation as _;
 use operations::add_reported_values::TrackAndTraceStoreAddReportedValuesOperation as _;
 use operations::add_reporters::TrackAndTraceStoreAddReportersOperation as _;
+use operations::aggregate_reported_values::TrackAndTraceStoreAggregateReportedValuesOperation as _;
+use operations::archive_property_pages::TrackAndTraceStoreArchivePropertyPagesOperation as _;
 use operations::fetch_property_with_data_type::TrackAndTraceStoreFetchPropertyWithDataTypeOperation as _;
 use operations::fetch_record::TrackAndTraceStoreFetchRecordOperation as _;
 use operations::fetch_reported_value_reporter_to_agent_metadata::TrackAndTraceStoreFetchReportedValueReporterToAgentMetadataOperation as _;
+use operations::list_archived_reported_values::TrackAndTraceStoreListArchivedReportedValuesOperation as _;
 use operations::list_associated_agents::TrackAndTraceStoreListAssociatedAgentsOperation as _;
 use operations::list_properties_with_data_type::TrackAndTraceStoreListPropertiesWithDataTypeOperation as _;
 use operations::list_proposals::TrackAndTraceStoreListProposalsOperation as _;
+use operations::list_record_lineage::TrackAndTraceStoreListRecordLineageOperation as _;
 use operations::list_records::TrackAndTraceStoreListRecordsOperation as _;
+use operations::list_records_within::TrackAndTraceStoreListRecordsWithinOperation as _;
 use operations::list_reported_value_reporter_to_agent_metadata::TrackAndTraceStoreListReportedValueReporterToAgentMetadataOperation as _;
 use operations::list_reporters::TrackAndTraceStoreListReportersOperation as _;
 use operations::TrackAndTraceStoreOperations;
@@ -227,6 +237,9 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
 
     fn list_records(
         &self,
+        include_archived: bool,
+        product_id: Option<&str>,
+        location_id: Option<&str>,
         service_id: Option<&str>,
     ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -235,7 +248,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
                 source: Box::new(err),
             }
         })?)
-        .list_records(service_id)
+        .list_records(include_archived, product_id, location_id, service_id)
     }
 
     fn list_reported_value_reporter_to_agent_metadata(
@@ -271,6 +284,100 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::pg::PgConnection> {
         })?)
         .list_reporters(record_id, property_name, service_id)
     }
+
+    fn archive_property_pages(
+        &self,
+        pages: Vec<PropertyPageSnapshot>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .archive_property_pages(pages)
+    }
+
+    fn list_archived_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ArchivedReportedValue>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_archived_reported_values(record_id, property_name, range, service_id)
+    }
+
+    fn aggregate_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        bucket_size: i64,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ReportedValueBucket>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .aggregate_reported_values(
+            record_id,
+            property_name,
+            bucket_size,
+            range,
+            service_id,
+        )
+    }
+
+    fn list_records_within(
+        &self,
+        property_name: &str,
+        bounding_box: &BoundingBox,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordPosition>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_records_within(property_name, bounding_box, service_id)
+    }
+
+    fn add_record_aggregations(
+        &self,
+        aggregations: Vec<RecordAggregation>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .add_record_aggregations(aggregations.into_iter().map(|a| a.into()).collect())
+    }
+
+    fn list_record_lineage(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordAggregation>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_record_lineage(record_id, service_id)
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -435,6 +542,9 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
 
     fn list_records(
         &self,
+        include_archived: bool,
+        product_id: Option<&str>,
+        location_id: Option<&str>,
         service_id: Option<&str>,
     ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
         TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
@@ -443,7 +553,7 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
                 source: Box::new(err),
             }
         })?)
-        .list_records(service_id)
+        .list_records(include_archived, product_id, location_id, service_id)
     }
 
     fn list_reported_value_reporter_to_agent_metadata(
@@ -479,6 +589,100 @@ impl TrackAndTraceStore for DieselTrackAndTraceStore<diesel::sqlite::SqliteConne
         })?)
         .list_reporters(record_id, property_name, service_id)
     }
+
+    fn archive_property_pages(
+        &self,
+        pages: Vec<PropertyPageSnapshot>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .archive_property_pages(pages)
+    }
+
+    fn list_archived_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ArchivedReportedValue>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_archived_reported_values(record_id, property_name, range, service_id)
+    }
+
+    fn aggregate_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        bucket_size: i64,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ReportedValueBucket>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .aggregate_reported_values(
+            record_id,
+            property_name,
+            bucket_size,
+            range,
+            service_id,
+        )
+    }
+
+    fn list_records_within(
+        &self,
+        property_name: &str,
+        bounding_box: &BoundingBox,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordPosition>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_records_within(property_name, bounding_box, service_id)
+    }
+
+    fn add_record_aggregations(
+        &self,
+        aggregations: Vec<RecordAggregation>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .add_record_aggregations(aggregations.into_iter().map(|a| a.into()).collect())
+    }
+
+    fn list_record_lineage(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordAggregation>, TrackAndTraceStoreError> {
+        TrackAndTraceStoreOperations::new(&*self.connection_pool.get().map_err(|err| {
+            DatabaseError::ConnectionError {
+                context: "Could not get connection pool".to_string(),
+                source: Box::new(err),
+            }
+        })?)
+        .list_record_lineage(record_id, service_id)
+    }
 }
 
 impl From<(i64, i64)> for LatLongValue {
@@ -512,6 +716,8 @@ impl Into<NewPropertyModel> for Property {
             start_commit_num: self.start_commit_num,
             end_commit_num: self.end_commit_num,
             service_id: self.service_id,
+            transaction_id: self.transaction_id,
+            signer: self.signer,
         }
     }
 }
@@ -530,6 +736,9 @@ impl Into<NewProposalModel> for Proposal {
             start_commit_num: self.start_commit_num,
             end_commit_num: self.end_commit_num,
             service_id: self.service_id,
+            transaction_id: self.transaction_id,
+            signer: self.signer,
+            expires_at: self.expires_at,
         }
     }
 }
@@ -545,6 +754,14 @@ impl Into<NewRecordModel> for Record {
             start_commit_num: self.start_commit_num,
             end_commit_num: self.end_commit_num,
             service_id: self.service_id,
+            transaction_id: self.transaction_id,
+            signer: self.signer,
+            schema_version: self.schema_version,
+            archived: self.archived,
+            product_id: self.product_id,
+            location_id: self.location_id,
+            product_namespace: self.product_namespace,
+            product_schema_name: self.product_schema_name,
         }
     }
 }
@@ -585,9 +802,13 @@ fn make_reported_value_models(
             parent_name: parent_name.clone(),
             latitude_value: val.lat_long_value.clone().map(|lat_long| lat_long.0),
             longitude_value: val.lat_long_value.clone().map(|lat_long| lat_long.1),
+            timestamp_value: val.timestamp_value,
+            decimal_value: val.decimal_value.clone(),
             start_commit_num: val.start_commit_num,
             end_commit_num: val.end_commit_num,
             service_id: val.service_id.clone(),
+            transaction_id: val.transaction_id.clone(),
+            signer: val.signer.clone(),
         });
 
         if val.struct_values.is_some() {
@@ -599,11 +820,50 @@ fn make_reported_value_models(
                 ));
             }
         }
+
+        // List elements are stored as children named by their index
+        if let Some(vs) = &val.list_values {
+            if !vs.is_empty() {
+                vals.append(&mut make_reported_value_models(
+                    vs,
+                    Some(val.property_name.clone()),
+                ));
+            }
+        }
     }
 
     vals
 }
 
+impl Into<NewRecordAggregationModel> for RecordAggregation {
+    fn into(self) -> NewRecordAggregationModel {
+        NewRecordAggregationModel {
+            parent_record_id: self.parent_record_id,
+            child_record_id: self.child_record_id,
+            aggregated_at: self.aggregated_at,
+            disaggregated_at: self.disaggregated_at,
+            start_commit_num: self.start_commit_num,
+            end_commit_num: self.end_commit_num,
+            service_id: self.service_id,
+        }
+    }
+}
+
+impl From<RecordAggregationModel> for RecordAggregation {
+    fn from(model: RecordAggregationModel) -> Self {
+        Self {
+            id: Some(model.id),
+            parent_record_id: model.parent_record_id,
+            child_record_id: model.child_record_id,
+            aggregated_at: model.aggregated_at,
+            disaggregated_at: model.disaggregated_at,
+            start_commit_num: model.start_commit_num,
+            end_commit_num: model.end_commit_num,
+            service_id: model.service_id,
+        }
+    }
+}
+
 impl From<AssociatedAgentModel> for AssociatedAgent {
     fn from(model: AssociatedAgentModel) -> Self {
         Self {
@@ -631,6 +891,8 @@ impl From<PropertyModel> for Property {
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
+            transaction_id: model.transaction_id,
+            signer: model.signer,
         }
     }
 }
@@ -650,6 +912,9 @@ impl From<ProposalModel> for Proposal {
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
+            transaction_id: model.transaction_id,
+            signer: model.signer,
+            expires_at: model.expires_at,
         }
     }
 }
@@ -666,6 +931,14 @@ impl From<RecordModel> for Record {
             start_commit_num: model.start_commit_num,
             end_commit_num: model.end_commit_num,
             service_id: model.service_id,
+            transaction_id: model.transaction_id,
+            signer: model.signer,
+            schema_version: model.schema_version,
+            archived: model.archived,
+            product_id: model.product_id,
+            location_id: model.location_id,
+            product_namespace: model.product_namespace,
+            product_schema_name: model.product_schema_name,
         }
     }
 }
@@ -686,6 +959,24 @@ impl From<ReporterModel> for Reporter {
     }
 }
 
+impl From<ReportedValueArchiveModel> for ArchivedReportedValue {
+    fn from(model: ReportedValueArchiveModel) -> Self {
+        Self {
+            record_id: model.record_id,
+            property_name: model.property_name,
+            page_num: model.page_num,
+            generation: model.generation,
+            reporter_index: model.reporter_index,
+            timestamp: model.timestamp,
+            data_type: model.data_type,
+            value: model.value,
+            number_value: model.number_value,
+            commit_num: model.commit_num,
+            service_id: model.service_id,
+        }
+    }
+}
+
 impl From<ReportedValueReporterToAgentMetadataModel> for ReportedValueReporterToAgentMetadata {
     fn from(model: ReportedValueReporterToAgentMetadataModel) -> Self {
         Self {
@@ -702,12 +993,17 @@ impl From<ReportedValueReporterToAgentMetadataModel> for ReportedValueReporterTo
             enum_value: model.enum_value,
             struct_values: Vec::new(),
             lat_long_value: create_lat_long_value(model.latitude_value, model.longitude_value),
+            timestamp_value: model.timestamp_value,
+            decimal_value: model.decimal_value,
+            list_values: Vec::new(),
             public_key: model.public_key,
             authorized: model.authorized,
             metadata: model.metadata,
             reported_value_end_commit_num: model.reported_value_end_commit_num,
             reporter_end_commit_num: model.reporter_end_commit_num,
             service_id: model.service_id,
+            transaction_id: model.transaction_id,
+            signer: model.signer,
         }
     }
 }
@@ -724,6 +1020,12 @@ impl
             Vec<ReportedValueReporterToAgentMetadata>,
         ),
     ) -> Self {
+        let (struct_values, list_values) = if model.data_type == "List" {
+            (Vec::new(), values)
+        } else {
+            (values, Vec::new())
+        };
+
         Self {
             id: Some(model.id),
             property_name: model.property_name,
@@ -736,14 +1038,19 @@ impl
             number_value: model.number_value,
             string_value: model.string_value,
             enum_value: model.enum_value,
-            struct_values: values,
+            struct_values,
             lat_long_value: create_lat_long_value(model.latitude_value, model.longitude_value),
+            timestamp_value: model.timestamp_value,
+            decimal_value: model.decimal_value,
+            list_values,
             public_key: model.public_key,
             authorized: model.authorized,
             metadata: model.metadata,
             reported_value_end_commit_num: model.reported_value_end_commit_num,
             reporter_end_commit_num: model.reporter_end_commit_num,
             service_id: model.service_id,
+            transaction_id: model.transaction_id,
+            signer: model.signer,
         }
     }
 }
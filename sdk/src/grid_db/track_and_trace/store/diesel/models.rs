@@ -54,6 +54,8 @@ pub struct NewGridPropertyDefinitionModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub deprecated: bool,
+    pub list_data_type: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -72,6 +74,8 @@ pub struct GridPropertyDefinitionModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub deprecated: bool,
+    pub list_data_type: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -85,6 +89,8 @@ pub struct NewPropertyModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -99,6 +105,8 @@ pub struct PropertyModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -117,6 +125,9 @@ pub struct NewProposalModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub expires_at: Option<i64>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -135,6 +146,9 @@ pub struct ProposalModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub expires_at: Option<i64>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -150,6 +164,14 @@ pub struct NewRecordModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub schema_version: i32,
+    pub archived: bool,
+    pub product_id: Option<String>,
+    pub location_id: Option<String>,
+    pub product_namespace: Option<String>,
+    pub product_schema_name: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -166,6 +188,14 @@ pub struct RecordModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub schema_version: i32,
+    pub archived: bool,
+    pub product_id: Option<String>,
+    pub location_id: Option<String>,
+    pub product_namespace: Option<String>,
+    pub product_schema_name: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -187,6 +217,10 @@ pub struct NewReportedValueModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -209,6 +243,10 @@ pub struct ReportedValueModel {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -227,12 +265,16 @@ pub struct NewReportedValueReporterToAgentMetadataModel {
     pub parent_name: Option<String>,
     pub latitude_value: Option<i64>,
     pub longitude_value: Option<i64>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
     pub public_key: Option<String>,
     pub authorized: Option<bool>,
     pub metadata: Option<Vec<u8>>,
     pub reported_value_end_commit_num: i64,
     pub reporter_end_commit_num: Option<i64>,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -252,12 +294,16 @@ pub struct ReportedValueReporterToAgentMetadataModel {
     pub parent_name: Option<String>,
     pub latitude_value: Option<i64>,
     pub longitude_value: Option<i64>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
     pub public_key: Option<String>,
     pub authorized: Option<bool>,
     pub metadata: Option<Vec<u8>>,
     pub reported_value_end_commit_num: i64,
     pub reporter_end_commit_num: Option<i64>,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Insertable, PartialEq, Queryable, Debug)]
@@ -286,3 +332,61 @@ pub struct ReporterModel {
     pub end_commit_num: i64,
     pub service_id: Option<String>,
 }
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "reported_value_archive"]
+pub struct NewReportedValueArchiveModel {
+    pub record_id: String,
+    pub property_name: String,
+    pub page_num: i32,
+    pub generation: i64,
+    pub reporter_index: i32,
+    pub timestamp: i64,
+    pub data_type: String,
+    pub value: Vec<u8>,
+    pub commit_num: i64,
+    pub service_id: Option<String>,
+    pub number_value: Option<i64>,
+}
+
+#[derive(Queryable, PartialEq, Identifiable, Debug)]
+#[table_name = "reported_value_archive"]
+pub struct ReportedValueArchiveModel {
+    pub id: i64,
+    pub record_id: String,
+    pub property_name: String,
+    pub page_num: i32,
+    pub generation: i64,
+    pub reporter_index: i32,
+    pub timestamp: i64,
+    pub data_type: String,
+    pub value: Vec<u8>,
+    pub commit_num: i64,
+    pub service_id: Option<String>,
+    pub number_value: Option<i64>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "record_aggregation"]
+pub struct NewRecordAggregationModel {
+    pub parent_record_id: String,
+    pub child_record_id: String,
+    pub aggregated_at: i64,
+    pub disaggregated_at: Option<i64>,
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+    pub service_id: Option<String>,
+}
+
+#[derive(Insertable, PartialEq, Queryable, Debug)]
+#[table_name = "record_aggregation"]
+pub struct RecordAggregationModel {
+    pub id: i64,
+    pub parent_record_id: String,
+    pub child_record_id: String,
+    pub aggregated_at: i64,
+    pub disaggregated_at: Option<i64>,
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+    pub service_id: Option<String>,
+}
@@ -0,0 +1,134 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::grid_db::track_and_trace::store::diesel::{
+    schema::record_aggregation, TrackAndTraceStoreError,
+};
+
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::track_and_trace::store::diesel::models::NewRecordAggregationModel;
+
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+};
+
+pub(in crate::grid_db::track_and_trace::store::diesel) trait TrackAndTraceStoreAddRecordAggregationsOperation
+{
+    fn add_record_aggregations(
+        &self,
+        aggregations: Vec<NewRecordAggregationModel>,
+    ) -> Result<(), TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreAddRecordAggregationsOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_record_aggregations(
+        &self,
+        aggregations: Vec<NewRecordAggregationModel>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, TrackAndTraceStoreError, _>(|| {
+                for aggregation in aggregations {
+                    update(record_aggregation::table)
+                        .filter(
+                            record_aggregation::parent_record_id
+                                .eq(&aggregation.parent_record_id)
+                                .and(
+                                    record_aggregation::child_record_id
+                                        .eq(&aggregation.child_record_id),
+                                )
+                                .and(
+                                    record_aggregation::aggregated_at
+                                        .eq(&aggregation.aggregated_at),
+                                )
+                                .and(record_aggregation::end_commit_num.eq(MAX_COMMIT_NUM))
+                                .and(record_aggregation::service_id.eq(&aggregation.service_id)),
+                        )
+                        .set(record_aggregation::end_commit_num.eq(&aggregation.start_commit_num))
+                        .execute(self.conn)
+                        .map(|_| ())
+                        .map_err(|err| TrackAndTraceStoreError::OperationError {
+                            context: "Failed to update record aggregation".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+
+                    insert_into(record_aggregation::table)
+                        .values(&aggregation)
+                        .execute(self.conn)
+                        .map(|_| ())
+                        .map_err(|err| TrackAndTraceStoreError::OperationError {
+                            context: "Failed to add record aggregation".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+                }
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreAddRecordAggregationsOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_record_aggregations(
+        &self,
+        aggregations: Vec<NewRecordAggregationModel>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        self.conn
+            .immediate_transaction::<_, TrackAndTraceStoreError, _>(|| {
+                for aggregation in aggregations {
+                    update(record_aggregation::table)
+                        .filter(
+                            record_aggregation::parent_record_id
+                                .eq(&aggregation.parent_record_id)
+                                .and(
+                                    record_aggregation::child_record_id
+                                        .eq(&aggregation.child_record_id),
+                                )
+                                .and(
+                                    record_aggregation::aggregated_at
+                                        .eq(&aggregation.aggregated_at),
+                                )
+                                .and(record_aggregation::end_commit_num.eq(MAX_COMMIT_NUM))
+                                .and(record_aggregation::service_id.eq(&aggregation.service_id)),
+                        )
+                        .set(record_aggregation::end_commit_num.eq(&aggregation.start_commit_num))
+                        .execute(self.conn)
+                        .map(|_| ())
+                        .map_err(|err| TrackAndTraceStoreError::OperationError {
+                            context: "Failed to update record aggregation".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+
+                    insert_into(record_aggregation::table)
+                        .values(&aggregation)
+                        .execute(self.conn)
+                        .map(|_| ())
+                        .map_err(|err| TrackAndTraceStoreError::OperationError {
+                            context: "Failed to add record aggregation".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+                }
+
+                Ok(())
+            })
+    }
+}
@@ -0,0 +1,336 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::grid_db::track_and_trace::store::diesel::{
+    schema::{grid_property_definition, property, record},
+    TrackAndTraceStoreError,
+};
+
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::track_and_trace::store::{ReportedValueBucket, ReportedValueRange};
+
+use diesel::{
+    prelude::*,
+    result::Error::NotFound,
+    sql_query,
+    sql_types::{BigInt, Double, Nullable, Text},
+};
+
+// Groups the archived Number values of a property into buckets of reported timestamps. The
+// inner query selects the values in range, only the most recent if a limit is given, and aligns
+// their timestamps to the start of their bucket, rounding down for negative timestamps as well.
+// Parameters: bucket size, record ID, property name, service ID, from, to, latest.
+#[cfg(feature = "postgres")]
+const PG_AGGREGATE_QUERY: &str = r#"
+SELECT bucket_start,
+    COUNT(*) AS value_count,
+    MIN(number_value) AS min_value,
+    MAX(number_value) AS max_value,
+    CAST(AVG(number_value) AS DOUBLE PRECISION) AS avg_value
+FROM (
+    SELECT "timestamp" - (("timestamp" % $1) + $1) % $1 AS bucket_start, number_value
+    FROM reported_value_archive
+    WHERE record_id = $2
+        AND property_name = $3
+        AND number_value IS NOT NULL
+        AND service_id IS NOT DISTINCT FROM $4
+        AND ($5 IS NULL OR "timestamp" >= $5)
+        AND ($6 IS NULL OR "timestamp" <= $6)
+    ORDER BY "timestamp" DESC, reporter_index DESC, id DESC
+    LIMIT $7
+) AS archived_value
+GROUP BY bucket_start
+ORDER BY bucket_start
+"#;
+
+#[cfg(feature = "sqlite")]
+const SQLITE_AGGREGATE_QUERY: &str = r#"
+SELECT bucket_start,
+    COUNT(*) AS value_count,
+    MIN(number_value) AS min_value,
+    MAX(number_value) AS max_value,
+    CAST(AVG(number_value) AS REAL) AS avg_value
+FROM (
+    SELECT "timestamp" - (("timestamp" % ?1) + ?1) % ?1 AS bucket_start, number_value
+    FROM reported_value_archive
+    WHERE record_id = ?2
+        AND property_name = ?3
+        AND number_value IS NOT NULL
+        AND service_id IS ?4
+        AND (?5 IS NULL OR "timestamp" >= ?5)
+        AND (?6 IS NULL OR "timestamp" <= ?6)
+    ORDER BY "timestamp" DESC, reporter_index DESC, id DESC
+    LIMIT ?7
+) AS archived_value
+GROUP BY bucket_start
+ORDER BY bucket_start
+"#;
+
+pub(in crate::grid_db::track_and_trace::store::diesel) trait TrackAndTraceStoreAggregateReportedValuesOperation
+{
+    fn aggregate_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        bucket_size: i64,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ReportedValueBucket>, TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreAggregateReportedValuesOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn aggregate_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        bucket_size: i64,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ReportedValueBucket>, TrackAndTraceStoreError> {
+        if bucket_size <= 0 {
+            return Err(TrackAndTraceStoreError::OperationError {
+                context: format!("Invalid bucket size {}", bucket_size),
+                source: None,
+            });
+        }
+
+        let mut exponent_query = property::table
+            .into_boxed()
+            .left_join(
+                record::table.on(property::record_id
+                    .eq(record::record_id)
+                    .and(property::end_commit_num.eq(record::end_commit_num))),
+            )
+            .left_join(
+                grid_property_definition::table.on(record::schema
+                    .eq(grid_property_definition::schema_name)
+                    .and(property::name.eq(grid_property_definition::name))
+                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM))),
+            )
+            .filter(
+                property::name
+                    .eq(property_name)
+                    .and(property::record_id.eq(record_id))
+                    .and(property::end_commit_num.eq(MAX_COMMIT_NUM)),
+            );
+
+        if let Some(service_id) = service_id {
+            exponent_query = exponent_query.filter(property::service_id.eq(service_id));
+        } else {
+            exponent_query = exponent_query.filter(property::service_id.is_null());
+        }
+
+        let number_exponent = exponent_query
+            .select(grid_property_definition::number_exponent.nullable())
+            .first::<Option<i64>>(self.conn)
+            .map(Some)
+            .or_else(|err| if err == NotFound { Ok(None) } else { Err(err) })
+            .map_err(|err| TrackAndTraceStoreError::QueryError {
+                context: "Failed to fetch property number exponent".to_string(),
+                source: Box::new(err),
+            })?
+            .flatten()
+            .unwrap_or(0);
+
+        let totals = sql_query(PG_AGGREGATE_QUERY)
+            .bind::<BigInt, _>(bucket_size)
+            .bind::<Text, _>(record_id)
+            .bind::<Text, _>(property_name)
+            .bind::<Nullable<Text>, _>(service_id)
+            .bind::<Nullable<BigInt>, _>(range.from)
+            .bind::<Nullable<BigInt>, _>(range.to)
+            .bind::<Nullable<BigInt>, _>(range.latest)
+            .load::<BucketTotals>(self.conn)
+            .map_err(|err| TrackAndTraceStoreError::QueryError {
+                context: "Failed to aggregate archived number values".to_string(),
+                source: Box::new(err),
+            })?;
+
+        Ok(make_buckets(totals, number_exponent))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreAggregateReportedValuesOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn aggregate_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        bucket_size: i64,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ReportedValueBucket>, TrackAndTraceStoreError> {
+        if bucket_size <= 0 {
+            return Err(TrackAndTraceStoreError::OperationError {
+                context: format!("Invalid bucket size {}", bucket_size),
+                source: None,
+            });
+        }
+
+        let mut exponent_query = property::table
+            .into_boxed()
+            .left_join(
+                record::table.on(property::record_id
+                    .eq(record::record_id)
+                    .and(property::end_commit_num.eq(record::end_commit_num))),
+            )
+            .left_join(
+                grid_property_definition::table.on(record::schema
+                    .eq(grid_property_definition::schema_name)
+                    .and(property::name.eq(grid_property_definition::name))
+                    .and(grid_property_definition::end_commit_num.eq(MAX_COMMIT_NUM))),
+            )
+            .filter(
+                property::name
+                    .eq(property_name)
+                    .and(property::record_id.eq(record_id))
+                    .and(property::end_commit_num.eq(MAX_COMMIT_NUM)),
+            );
+
+        if let Some(service_id) = service_id {
+            exponent_query = exponent_query.filter(property::service_id.eq(service_id));
+        } else {
+            exponent_query = exponent_query.filter(property::service_id.is_null());
+        }
+
+        let number_exponent = exponent_query
+            .select(grid_property_definition::number_exponent.nullable())
+            .first::<Option<i64>>(self.conn)
+            .map(Some)
+            .or_else(|err| if err == NotFound { Ok(None) } else { Err(err) })
+            .map_err(|err| TrackAndTraceStoreError::QueryError {
+                context: "Failed to fetch property number exponent".to_string(),
+                source: Box::new(err),
+            })?
+            .flatten()
+            .unwrap_or(0);
+
+        // SQLite treats a negative limit as no limit
+        let totals = sql_query(SQLITE_AGGREGATE_QUERY)
+            .bind::<BigInt, _>(bucket_size)
+            .bind::<Text, _>(record_id)
+            .bind::<Text, _>(property_name)
+            .bind::<Nullable<Text>, _>(service_id)
+            .bind::<Nullable<BigInt>, _>(range.from)
+            .bind::<Nullable<BigInt>, _>(range.to)
+            .bind::<BigInt, _>(range.latest.unwrap_or(-1))
+            .load::<BucketTotals>(self.conn)
+            .map_err(|err| TrackAndTraceStoreError::QueryError {
+                context: "Failed to aggregate archived number values".to_string(),
+                source: Box::new(err),
+            })?;
+
+        Ok(make_buckets(totals, number_exponent))
+    }
+}
+
+/// The totals of one bucket, in the unscaled units of the stored values
+#[derive(Debug, QueryableByName)]
+struct BucketTotals {
+    #[sql_type = "BigInt"]
+    bucket_start: i64,
+    #[sql_type = "BigInt"]
+    value_count: i64,
+    #[sql_type = "BigInt"]
+    min_value: i64,
+    #[sql_type = "BigInt"]
+    max_value: i64,
+    #[sql_type = "Double"]
+    avg_value: f64,
+}
+
+fn make_buckets(totals: Vec<BucketTotals>, number_exponent: i64) -> Vec<ReportedValueBucket> {
+    totals
+        .into_iter()
+        .map(|totals| ReportedValueBucket {
+            start: totals.bucket_start,
+            count: totals.value_count,
+            min: scale(totals.min_value as f64, number_exponent),
+            max: scale(totals.max_value as f64, number_exponent),
+            avg: scale(totals.avg_value, number_exponent),
+        })
+        .collect()
+}
+
+// Dividing for negative exponents keeps values such as 1.23 exact where multiplying by 0.01
+// would not.
+fn scale(value: f64, number_exponent: i64) -> f64 {
+    if number_exponent < 0 {
+        value / 10f64.powi(-number_exponent as i32)
+    } else {
+        value * 10f64.powi(number_exponent as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // Check that the totals of each bucket are scaled by the property's number exponent
+    fn check_make_buckets_scales_totals() {
+        let totals = vec![
+            BucketTotals {
+                bucket_start: 0,
+                value_count: 2,
+                min_value: 100,
+                max_value: 250,
+                avg_value: 175.0,
+            },
+            BucketTotals {
+                bucket_start: 60,
+                value_count: 1,
+                min_value: -5,
+                max_value: -5,
+                avg_value: -5.0,
+            },
+        ];
+
+        assert_eq!(
+            make_buckets(totals, -2),
+            vec![
+                ReportedValueBucket {
+                    start: 0,
+                    count: 2,
+                    min: 1.0,
+                    max: 2.5,
+                    avg: 1.75,
+                },
+                ReportedValueBucket {
+                    start: 60,
+                    count: 1,
+                    min: -0.05,
+                    max: -0.05,
+                    avg: -0.05,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    // Check that values are scaled up for positive exponents, down for negative ones, and left
+    // as they are for an exponent of zero
+    fn check_scale() {
+        assert_eq!(scale(123.0, 0), 123.0);
+        assert_eq!(scale(123.0, 2), 12300.0);
+        assert_eq!(scale(123.0, -2), 1.23);
+        assert_eq!(scale(-123.0, -2), -1.23);
+    }
+}
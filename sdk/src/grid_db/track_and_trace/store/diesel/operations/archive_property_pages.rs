@@ -0,0 +1,255 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::grid_db::track_and_trace::store::diesel::{
+    schema::reported_value_archive, TrackAndTraceStoreError,
+};
+
+use crate::grid_db::track_and_trace::store::diesel::models::{
+    NewReportedValueArchiveModel, ReportedValueArchiveModel,
+};
+use crate::grid_db::track_and_trace::store::{PageReportedValue, PropertyPageSnapshot};
+
+use diesel::{dsl::insert_into, dsl::max, prelude::*};
+
+pub(in crate::grid_db::track_and_trace::store::diesel) trait TrackAndTraceStoreArchivePropertyPagesOperation
+{
+    fn archive_property_pages(
+        &self,
+        pages: Vec<PropertyPageSnapshot>,
+    ) -> Result<(), TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreArchivePropertyPagesOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn archive_property_pages(
+        &self,
+        pages: Vec<PropertyPageSnapshot>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        self.conn
+            .build_transaction()
+            .read_write()
+            .run::<_, TrackAndTraceStoreError, _>(|| {
+                for page in pages {
+                    let mut query = reported_value_archive::table
+                        .select(max(reported_value_archive::generation))
+                        .filter(
+                            reported_value_archive::record_id
+                                .eq(&page.record_id)
+                                .and(reported_value_archive::property_name.eq(&page.property_name))
+                                .and(reported_value_archive::page_num.eq(page.page_num)),
+                        )
+                        .into_boxed();
+
+                    if let Some(service_id) = &page.service_id {
+                        query = query.filter(reported_value_archive::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(reported_value_archive::service_id.is_null());
+                    }
+
+                    let generation = query.first::<Option<i64>>(self.conn).map_err(|err| {
+                        TrackAndTraceStoreError::QueryError {
+                            context: "Failed to fetch current property page generation".to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+
+                    let archived = match generation {
+                        Some(generation) => {
+                            let mut query = reported_value_archive::table
+                                .into_boxed()
+                                .select(reported_value_archive::all_columns)
+                                .filter(
+                                    reported_value_archive::record_id
+                                        .eq(&page.record_id)
+                                        .and(
+                                            reported_value_archive::property_name
+                                                .eq(&page.property_name),
+                                        )
+                                        .and(reported_value_archive::page_num.eq(page.page_num))
+                                        .and(reported_value_archive::generation.eq(generation)),
+                                );
+
+                            if let Some(service_id) = &page.service_id {
+                                query =
+                                    query.filter(reported_value_archive::service_id.eq(service_id));
+                            } else {
+                                query = query.filter(reported_value_archive::service_id.is_null());
+                            }
+
+                            let values = query
+                                .load::<ReportedValueArchiveModel>(self.conn)
+                                .map_err(|err| TrackAndTraceStoreError::QueryError {
+                                    context: "Failed to fetch archived property page".to_string(),
+                                    source: Box::new(err),
+                                })?;
+
+                            Some((generation, values))
+                        }
+                        None => None,
+                    };
+
+                    insert_into(reported_value_archive::table)
+                        .values(&make_archive_models(page, archived))
+                        .execute(self.conn)
+                        .map_err(|err| TrackAndTraceStoreError::OperationError {
+                            context: "Failed to archive property page".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+                }
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreArchivePropertyPagesOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn archive_property_pages(
+        &self,
+        pages: Vec<PropertyPageSnapshot>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        self.conn
+            .immediate_transaction::<_, TrackAndTraceStoreError, _>(|| {
+                for page in pages {
+                    let mut query = reported_value_archive::table
+                        .select(max(reported_value_archive::generation))
+                        .filter(
+                            reported_value_archive::record_id
+                                .eq(&page.record_id)
+                                .and(reported_value_archive::property_name.eq(&page.property_name))
+                                .and(reported_value_archive::page_num.eq(page.page_num)),
+                        )
+                        .into_boxed();
+
+                    if let Some(service_id) = &page.service_id {
+                        query = query.filter(reported_value_archive::service_id.eq(service_id));
+                    } else {
+                        query = query.filter(reported_value_archive::service_id.is_null());
+                    }
+
+                    let generation = query.first::<Option<i64>>(self.conn).map_err(|err| {
+                        TrackAndTraceStoreError::QueryError {
+                            context: "Failed to fetch current property page generation".to_string(),
+                            source: Box::new(err),
+                        }
+                    })?;
+
+                    let archived = match generation {
+                        Some(generation) => {
+                            let mut query = reported_value_archive::table
+                                .into_boxed()
+                                .select(reported_value_archive::all_columns)
+                                .filter(
+                                    reported_value_archive::record_id
+                                        .eq(&page.record_id)
+                                        .and(
+                                            reported_value_archive::property_name
+                                                .eq(&page.property_name),
+                                        )
+                                        .and(reported_value_archive::page_num.eq(page.page_num))
+                                        .and(reported_value_archive::generation.eq(generation)),
+                                );
+
+                            if let Some(service_id) = &page.service_id {
+                                query =
+                                    query.filter(reported_value_archive::service_id.eq(service_id));
+                            } else {
+                                query = query.filter(reported_value_archive::service_id.is_null());
+                            }
+
+                            let values = query
+                                .load::<ReportedValueArchiveModel>(self.conn)
+                                .map_err(|err| TrackAndTraceStoreError::QueryError {
+                                    context: "Failed to fetch archived property page".to_string(),
+                                    source: Box::new(err),
+                                })?;
+
+                            Some((generation, values))
+                        }
+                        None => None,
+                    };
+
+                    insert_into(reported_value_archive::table)
+                        .values(&make_archive_models(page, archived))
+                        .execute(self.conn)
+                        .map_err(|err| TrackAndTraceStoreError::OperationError {
+                            context: "Failed to archive property page".to_string(),
+                            source: Some(Box::new(err)),
+                        })?;
+                }
+
+                Ok(())
+            })
+    }
+}
+
+/// Returns the rows to append for a snapshot of a page, given the page's current generation and
+/// the values archived for it. If any archived value is missing from the snapshot, the page was
+/// reused after the property's pages wrapped, so every value starts a new generation.
+fn make_archive_models(
+    page: PropertyPageSnapshot,
+    archived: Option<(i64, Vec<ReportedValueArchiveModel>)>,
+) -> Vec<NewReportedValueArchiveModel> {
+    let (generation, archived) = match archived {
+        Some((generation, archived)) => {
+            let reused = archived.iter().any(|archived_value| {
+                !page
+                    .reported_values
+                    .iter()
+                    .any(|value| is_archived(value, archived_value))
+            });
+
+            if reused {
+                (generation + 1, vec![])
+            } else {
+                (generation, archived)
+            }
+        }
+        None => (0, vec![]),
+    };
+
+    page.reported_values
+        .iter()
+        .filter(|value| {
+            !archived
+                .iter()
+                .any(|archived_value| is_archived(value, archived_value))
+        })
+        .map(|value| NewReportedValueArchiveModel {
+            record_id: page.record_id.clone(),
+            property_name: page.property_name.clone(),
+            page_num: page.page_num,
+            generation,
+            reporter_index: value.reporter_index,
+            timestamp: value.timestamp,
+            data_type: value.data_type.clone(),
+            value: value.value.clone(),
+            commit_num: page.commit_num,
+            service_id: page.service_id.clone(),
+            number_value: value.number_value,
+        })
+        .collect()
+}
+
+fn is_archived(value: &PageReportedValue, archived_value: &ReportedValueArchiveModel) -> bool {
+    value.reporter_index == archived_value.reporter_index
+        && value.timestamp == archived_value.timestamp
+        && value.value == archived_value.value
+}
@@ -0,0 +1,186 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::grid_db::track_and_trace::store::diesel::{
+    schema::reported_value_archive, TrackAndTraceStoreError,
+};
+
+use crate::grid_db::track_and_trace::store::diesel::models::ReportedValueArchiveModel;
+use crate::grid_db::track_and_trace::store::{ArchivedReportedValue, ReportedValueRange};
+
+use diesel::prelude::*;
+
+pub(in crate::grid_db::track_and_trace::store::diesel) trait TrackAndTraceStoreListArchivedReportedValuesOperation
+{
+    fn list_archived_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ArchivedReportedValue>, TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreListArchivedReportedValuesOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_archived_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ArchivedReportedValue>, TrackAndTraceStoreError> {
+        let mut query = reported_value_archive::table
+            .into_boxed()
+            .select(reported_value_archive::all_columns)
+            .filter(
+                reported_value_archive::record_id
+                    .eq(record_id)
+                    .and(reported_value_archive::property_name.eq(property_name)),
+            );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(reported_value_archive::service_id.eq(service_id));
+        } else {
+            query = query.filter(reported_value_archive::service_id.is_null());
+        }
+
+        if let Some(from) = range.from {
+            query = query.filter(reported_value_archive::timestamp.ge(from));
+        }
+
+        if let Some(to) = range.to {
+            query = query.filter(reported_value_archive::timestamp.le(to));
+        }
+
+        if let Some(latest) = range.latest {
+            query = query
+                .order((
+                    reported_value_archive::timestamp.desc(),
+                    reported_value_archive::reporter_index.desc(),
+                    reported_value_archive::id.desc(),
+                ))
+                .limit(latest);
+        } else {
+            query = query.order((
+                reported_value_archive::timestamp.asc(),
+                reported_value_archive::reporter_index.asc(),
+                reported_value_archive::id.asc(),
+            ));
+
+            if let Some(offset) = range.offset {
+                query = query.offset(offset);
+            }
+
+            if let Some(limit) = range.limit {
+                query = query.limit(limit);
+            }
+        }
+
+        let mut values = query
+            .load::<ReportedValueArchiveModel>(self.conn)
+            .map_err(|err| TrackAndTraceStoreError::QueryError {
+                context: "Failed to fetch archived reported values".to_string(),
+                source: Box::new(err),
+            })?
+            .into_iter()
+            .map(ArchivedReportedValue::from)
+            .collect::<Vec<ArchivedReportedValue>>();
+
+        if range.latest.is_some() {
+            values.reverse();
+        }
+
+        Ok(values)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreListArchivedReportedValuesOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_archived_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ArchivedReportedValue>, TrackAndTraceStoreError> {
+        let mut query = reported_value_archive::table
+            .into_boxed()
+            .select(reported_value_archive::all_columns)
+            .filter(
+                reported_value_archive::record_id
+                    .eq(record_id)
+                    .and(reported_value_archive::property_name.eq(property_name)),
+            );
+
+        if let Some(service_id) = service_id {
+            query = query.filter(reported_value_archive::service_id.eq(service_id));
+        } else {
+            query = query.filter(reported_value_archive::service_id.is_null());
+        }
+
+        if let Some(from) = range.from {
+            query = query.filter(reported_value_archive::timestamp.ge(from));
+        }
+
+        if let Some(to) = range.to {
+            query = query.filter(reported_value_archive::timestamp.le(to));
+        }
+
+        if let Some(latest) = range.latest {
+            query = query
+                .order((
+                    reported_value_archive::timestamp.desc(),
+                    reported_value_archive::reporter_index.desc(),
+                    reported_value_archive::id.desc(),
+                ))
+                .limit(latest);
+        } else {
+            query = query.order((
+                reported_value_archive::timestamp.asc(),
+                reported_value_archive::reporter_index.asc(),
+                reported_value_archive::id.asc(),
+            ));
+
+            if let Some(offset) = range.offset {
+                query = query.offset(offset);
+            }
+
+            if let Some(limit) = range.limit {
+                query = query.limit(limit);
+            }
+        }
+
+        let mut values = query
+            .load::<ReportedValueArchiveModel>(self.conn)
+            .map_err(|err| TrackAndTraceStoreError::QueryError {
+                context: "Failed to fetch archived reported values".to_string(),
+                source: Box::new(err),
+            })?
+            .into_iter()
+            .map(ArchivedReportedValue::from)
+            .collect::<Vec<ArchivedReportedValue>>();
+
+        if range.latest.is_some() {
+            values.reverse();
+        }
+
+        Ok(values)
+    }
+}
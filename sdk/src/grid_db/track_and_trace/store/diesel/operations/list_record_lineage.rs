@@ -0,0 +1,157 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::grid_db::track_and_trace::store::diesel::{
+    schema::record_aggregation, TrackAndTraceStoreError,
+};
+
+use std::collections::HashSet;
+
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::track_and_trace::store::diesel::models::RecordAggregationModel;
+use crate::grid_db::track_and_trace::store::RecordAggregation;
+
+use diesel::prelude::*;
+
+pub(in crate::grid_db::track_and_trace::store::diesel) trait TrackAndTraceStoreListRecordLineageOperation
+{
+    fn list_record_lineage(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordAggregation>, TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreListRecordLineageOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_record_lineage(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordAggregation>, TrackAndTraceStoreError> {
+        let mut seen = HashSet::new();
+        let mut lineage = Vec::new();
+
+        // Walk up through the record's parents, then down through its children
+        for &towards_parents in &[true, false] {
+            let mut frontier = vec![record_id.to_string()];
+
+            while !frontier.is_empty() {
+                let mut query = record_aggregation::table
+                    .into_boxed()
+                    .select(record_aggregation::all_columns)
+                    .filter(record_aggregation::end_commit_num.eq(MAX_COMMIT_NUM));
+
+                if towards_parents {
+                    query = query.filter(record_aggregation::child_record_id.eq_any(&frontier));
+                } else {
+                    query = query.filter(record_aggregation::parent_record_id.eq_any(&frontier));
+                }
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(record_aggregation::service_id.eq(service_id));
+                } else {
+                    query = query.filter(record_aggregation::service_id.is_null());
+                }
+
+                let models = query
+                    .order(record_aggregation::aggregated_at.asc())
+                    .load::<RecordAggregationModel>(self.conn)
+                    .map_err(|err| TrackAndTraceStoreError::OperationError {
+                        context: "Failed to fetch record aggregations".to_string(),
+                        source: Some(Box::new(err)),
+                    })?;
+
+                // Past aggregations may form a cycle, so each row is only followed once
+                frontier = Vec::new();
+                for model in models {
+                    if seen.insert(model.id) {
+                        frontier.push(if towards_parents {
+                            model.parent_record_id.clone()
+                        } else {
+                            model.child_record_id.clone()
+                        });
+                        lineage.push(RecordAggregation::from(model));
+                    }
+                }
+            }
+        }
+
+        Ok(lineage)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreListRecordLineageOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_record_lineage(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordAggregation>, TrackAndTraceStoreError> {
+        let mut seen = HashSet::new();
+        let mut lineage = Vec::new();
+
+        // Walk up through the record's parents, then down through its children
+        for &towards_parents in &[true, false] {
+            let mut frontier = vec![record_id.to_string()];
+
+            while !frontier.is_empty() {
+                let mut query = record_aggregation::table
+                    .into_boxed()
+                    .select(record_aggregation::all_columns)
+                    .filter(record_aggregation::end_commit_num.eq(MAX_COMMIT_NUM));
+
+                if towards_parents {
+                    query = query.filter(record_aggregation::child_record_id.eq_any(&frontier));
+                } else {
+                    query = query.filter(record_aggregation::parent_record_id.eq_any(&frontier));
+                }
+
+                if let Some(service_id) = service_id {
+                    query = query.filter(record_aggregation::service_id.eq(service_id));
+                } else {
+                    query = query.filter(record_aggregation::service_id.is_null());
+                }
+
+                let models = query
+                    .order(record_aggregation::aggregated_at.asc())
+                    .load::<RecordAggregationModel>(self.conn)
+                    .map_err(|err| TrackAndTraceStoreError::OperationError {
+                        context: "Failed to fetch record aggregations".to_string(),
+                        source: Some(Box::new(err)),
+                    })?;
+
+                // Past aggregations may form a cycle, so each row is only followed once
+                frontier = Vec::new();
+                for model in models {
+                    if seen.insert(model.id) {
+                        frontier.push(if towards_parents {
+                            model.parent_record_id.clone()
+                        } else {
+                            model.child_record_id.clone()
+                        });
+                        lineage.push(RecordAggregation::from(model));
+                    }
+                }
+            }
+        }
+
+        Ok(lineage)
+    }
+}
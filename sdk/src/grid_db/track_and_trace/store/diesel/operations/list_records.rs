@@ -25,6 +25,9 @@ pub(in crate::grid_db::track_and_trace::store::diesel) trait TrackAndTraceStoreL
 {
     fn list_records(
         &self,
+        include_archived: bool,
+        product_id: Option<&str>,
+        location_id: Option<&str>,
         service_id: Option<&str>,
     ) -> Result<Vec<Record>, TrackAndTraceStoreError>;
 }
@@ -35,6 +38,9 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
 {
     fn list_records(
         &self,
+        include_archived: bool,
+        product_id: Option<&str>,
+        location_id: Option<&str>,
         service_id: Option<&str>,
     ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
         let mut query = record::table
@@ -48,6 +54,18 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
             query = query.filter(record::service_id.is_null());
         }
 
+        if !include_archived {
+            query = query.filter(record::archived.eq(false));
+        }
+
+        if let Some(product_id) = product_id {
+            query = query.filter(record::product_id.eq(product_id));
+        }
+
+        if let Some(location_id) = location_id {
+            query = query.filter(record::location_id.eq(location_id));
+        }
+
         let models: Vec<RecordModel> = query
             .load::<RecordModel>(self.conn)
             .map(Some)
@@ -73,6 +91,9 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
 {
     fn list_records(
         &self,
+        include_archived: bool,
+        product_id: Option<&str>,
+        location_id: Option<&str>,
         service_id: Option<&str>,
     ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
         let mut query = record::table
@@ -86,6 +107,18 @@ impl<'a> TrackAndTraceStoreListRecordsOperation
             query = query.filter(record::service_id.is_null());
         }
 
+        if !include_archived {
+            query = query.filter(record::archived.eq(false));
+        }
+
+        if let Some(product_id) = product_id {
+            query = query.filter(record::product_id.eq(product_id));
+        }
+
+        if let Some(location_id) = location_id {
+            query = query.filter(record::location_id.eq(location_id));
+        }
+
         let models: Vec<RecordModel> = query
             .load::<RecordModel>(self.conn)
             .map(Some)
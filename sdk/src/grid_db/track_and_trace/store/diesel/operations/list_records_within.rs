@@ -0,0 +1,189 @@
+// Copyright 2018-2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::TrackAndTraceStoreOperations;
+use crate::grid_db::track_and_trace::store::diesel::TrackAndTraceStoreError;
+
+use crate::grid_db::commits::MAX_COMMIT_NUM;
+use crate::grid_db::geo::BoundingBox;
+use crate::grid_db::track_and_trace::store::{LatLongValue, RecordPosition};
+
+use diesel::{
+    prelude::*,
+    sql_query,
+    sql_types::{BigInt, Bool, Nullable, Text},
+};
+
+// Selects the current LatLong values of a property that lie within a bounding box and are the
+// most recently reported value of their record, newest by timestamp and then by insertion. The
+// box's longitudes wrap around when it crosses the antimeridian.
+// Parameters: property name, current commit num, service ID, min latitude, max latitude,
+// min longitude, max longitude, crosses antimeridian.
+#[cfg(feature = "postgres")]
+const PG_RECORDS_WITHIN_QUERY: &str = r#"
+SELECT latest.record_id, latest."timestamp", latest.latitude_value, latest.longitude_value
+FROM reported_value AS latest
+WHERE latest.property_name = $1
+    AND latest.data_type = 'LatLong'
+    AND latest.parent_name IS NULL
+    AND latest.end_commit_num = $2
+    AND latest.service_id IS NOT DISTINCT FROM $3
+    AND latest.latitude_value >= $4
+    AND latest.latitude_value <= $5
+    AND (
+        (NOT $8 AND latest.longitude_value >= $6 AND latest.longitude_value <= $7)
+        OR ($8 AND (latest.longitude_value >= $6 OR latest.longitude_value <= $7))
+    )
+    AND NOT EXISTS (
+        SELECT 1
+        FROM reported_value AS newer
+        WHERE newer.record_id = latest.record_id
+            AND newer.property_name = latest.property_name
+            AND newer.data_type = 'LatLong'
+            AND newer.parent_name IS NULL
+            AND newer.end_commit_num = latest.end_commit_num
+            AND newer.service_id IS NOT DISTINCT FROM latest.service_id
+            AND (newer."timestamp" > latest."timestamp"
+                OR (newer."timestamp" = latest."timestamp" AND newer.id > latest.id))
+    )
+ORDER BY latest.record_id
+"#;
+
+#[cfg(feature = "sqlite")]
+const SQLITE_RECORDS_WITHIN_QUERY: &str = r#"
+SELECT latest.record_id, latest."timestamp", latest.latitude_value, latest.longitude_value
+FROM reported_value AS latest
+WHERE latest.property_name = ?1
+    AND latest.data_type = 'LatLong'
+    AND latest.parent_name IS NULL
+    AND latest.end_commit_num = ?2
+    AND latest.service_id IS ?3
+    AND latest.latitude_value >= ?4
+    AND latest.latitude_value <= ?5
+    AND (
+        (NOT ?8 AND latest.longitude_value >= ?6 AND latest.longitude_value <= ?7)
+        OR (?8 AND (latest.longitude_value >= ?6 OR latest.longitude_value <= ?7))
+    )
+    AND NOT EXISTS (
+        SELECT 1
+        FROM reported_value AS newer
+        WHERE newer.record_id = latest.record_id
+            AND newer.property_name = latest.property_name
+            AND newer.data_type = 'LatLong'
+            AND newer.parent_name IS NULL
+            AND newer.end_commit_num = latest.end_commit_num
+            AND newer.service_id IS latest.service_id
+            AND (newer."timestamp" > latest."timestamp"
+                OR (newer."timestamp" = latest."timestamp" AND newer.id > latest.id))
+    )
+ORDER BY latest.record_id
+"#;
+
+pub(in crate::grid_db::track_and_trace::store::diesel) trait TrackAndTraceStoreListRecordsWithinOperation
+{
+    fn list_records_within(
+        &self,
+        property_name: &str,
+        bounding_box: &BoundingBox,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordPosition>, TrackAndTraceStoreError>;
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> TrackAndTraceStoreListRecordsWithinOperation
+    for TrackAndTraceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn list_records_within(
+        &self,
+        property_name: &str,
+        bounding_box: &BoundingBox,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordPosition>, TrackAndTraceStoreError> {
+        let positions = sql_query(PG_RECORDS_WITHIN_QUERY)
+            .bind::<Text, _>(property_name)
+            .bind::<BigInt, _>(MAX_COMMIT_NUM)
+            .bind::<Nullable<Text>, _>(service_id)
+            .bind::<BigInt, _>(bounding_box.min_latitude)
+            .bind::<BigInt, _>(bounding_box.max_latitude)
+            .bind::<BigInt, _>(bounding_box.min_longitude)
+            .bind::<BigInt, _>(bounding_box.max_longitude)
+            .bind::<Bool, _>(bounding_box.crosses_antimeridian())
+            .load::<LatestPosition>(self.conn)
+            .map_err(|err| TrackAndTraceStoreError::QueryError {
+                context: "Failed to fetch reported positions".to_string(),
+                source: Box::new(err),
+            })?;
+
+        Ok(make_record_positions(positions, property_name, service_id))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> TrackAndTraceStoreListRecordsWithinOperation
+    for TrackAndTraceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn list_records_within(
+        &self,
+        property_name: &str,
+        bounding_box: &BoundingBox,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordPosition>, TrackAndTraceStoreError> {
+        let positions = sql_query(SQLITE_RECORDS_WITHIN_QUERY)
+            .bind::<Text, _>(property_name)
+            .bind::<BigInt, _>(MAX_COMMIT_NUM)
+            .bind::<Nullable<Text>, _>(service_id)
+            .bind::<BigInt, _>(bounding_box.min_latitude)
+            .bind::<BigInt, _>(bounding_box.max_latitude)
+            .bind::<BigInt, _>(bounding_box.min_longitude)
+            .bind::<BigInt, _>(bounding_box.max_longitude)
+            .bind::<Bool, _>(bounding_box.crosses_antimeridian())
+            .load::<LatestPosition>(self.conn)
+            .map_err(|err| TrackAndTraceStoreError::QueryError {
+                context: "Failed to fetch reported positions".to_string(),
+                source: Box::new(err),
+            })?;
+
+        Ok(make_record_positions(positions, property_name, service_id))
+    }
+}
+
+/// The most recently reported position of a record
+#[derive(Debug, QueryableByName)]
+struct LatestPosition {
+    #[sql_type = "Text"]
+    record_id: String,
+    #[sql_type = "BigInt"]
+    timestamp: i64,
+    #[sql_type = "BigInt"]
+    latitude_value: i64,
+    #[sql_type = "BigInt"]
+    longitude_value: i64,
+}
+
+fn make_record_positions(
+    positions: Vec<LatestPosition>,
+    property_name: &str,
+    service_id: Option<&str>,
+) -> Vec<RecordPosition> {
+    positions
+        .into_iter()
+        .map(|position| RecordPosition {
+            record_id: position.record_id,
+            property_name: property_name.to_string(),
+            lat_long_value: LatLongValue(position.latitude_value, position.longitude_value),
+            timestamp: position.timestamp,
+            service_id: service_id.map(String::from),
+        })
+        .collect()
+}
@@ -15,16 +15,22 @@
 pub(super) mod add_associated_agents;
 pub(super) mod add_properties;
 pub(super) mod add_proposals;
+pub(super) mod add_record_aggregations;
 pub(super) mod add_records;
 pub(super) mod add_reported_values;
 pub(super) mod add_reporters;
+pub(super) mod aggregate_reported_values;
+pub(super) mod archive_property_pages;
 pub(super) mod fetch_property_with_data_type;
 pub(super) mod fetch_record;
 pub(super) mod fetch_reported_value_reporter_to_agent_metadata;
+pub(super) mod list_archived_reported_values;
 pub(super) mod list_associated_agents;
 pub(super) mod list_properties_with_data_type;
 pub(super) mod list_proposals;
+pub(super) mod list_record_lineage;
 pub(super) mod list_records;
+pub(super) mod list_records_within;
 pub(super) mod list_reported_value_reporter_to_agent_metadata;
 pub(super) mod list_reporters;
 
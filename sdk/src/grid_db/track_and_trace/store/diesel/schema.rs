@@ -39,6 +39,8 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        deprecated -> Bool,
+        list_data_type -> Nullable<Text>,
     }
 }
 
@@ -53,6 +55,8 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
     }
 }
 
@@ -70,6 +74,9 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
+        expires_at -> Nullable<Int8>,
     }
 }
 
@@ -85,6 +92,27 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
+        schema_version -> Int4,
+        archived -> Bool,
+        product_id -> Nullable<Text>,
+        location_id -> Nullable<Text>,
+        product_namespace -> Nullable<Text>,
+        product_schema_name -> Nullable<Text>,
+    }
+}
+
+table! {
+    record_aggregation (id) {
+        id -> Int8,
+        parent_record_id -> Text,
+        child_record_id -> Text,
+        aggregated_at -> Int8,
+        disaggregated_at -> Nullable<Int8>,
+        start_commit_num -> Int8,
+        end_commit_num -> Int8,
+        service_id -> Nullable<Text>,
     }
 }
 
@@ -107,6 +135,10 @@ table! {
         start_commit_num -> Int8,
         end_commit_num -> Int8,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
+        timestamp_value -> Nullable<Int8>,
+        decimal_value -> Nullable<Text>,
     }
 }
 
@@ -127,12 +159,16 @@ table! {
         parent_name ->  Nullable<Text>,
         latitude_value -> Nullable<Int8>,
         longitude_value -> Nullable<Int8>,
+        timestamp_value -> Nullable<Int8>,
+        decimal_value -> Nullable<Text>,
         public_key ->  Nullable<Text>,
         authorized ->  Nullable<Bool>,
         metadata ->  Nullable<Binary>,
         reported_value_end_commit_num -> Int8,
         reporter_end_commit_num ->  Nullable<Int8>,
         service_id -> Nullable<Text>,
+        transaction_id -> Nullable<Text>,
+        signer -> Nullable<Text>,
     }
 }
 
@@ -150,12 +186,30 @@ table! {
     }
 }
 
+table! {
+    reported_value_archive (id) {
+        id -> Int8,
+        record_id -> Text,
+        property_name -> Text,
+        page_num -> Int4,
+        generation -> Int8,
+        reporter_index -> Int4,
+        timestamp -> Int8,
+        data_type -> Text,
+        value -> Binary,
+        commit_num -> Int8,
+        service_id -> Nullable<Text>,
+        number_value -> Nullable<Int8>,
+    }
+}
+
 allow_tables_to_appear_in_same_query!(
     associated_agent,
     grid_property_definition,
     property,
     proposal,
     record,
+    record_aggregation,
     reported_value,
     reporter,
 );
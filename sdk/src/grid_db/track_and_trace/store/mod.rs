@@ -18,6 +18,8 @@ mod error;
 
 pub use error::TrackAndTraceStoreError;
 
+use crate::grid_db::geo::BoundingBox;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssociatedAgent {
     pub id: Option<i64>,
@@ -41,6 +43,8 @@ pub struct Property {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +61,9 @@ pub struct Proposal {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    pub expires_at: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,6 +77,34 @@ pub struct Record {
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
+    /// The version of the schema the record was created with
+    pub schema_version: i32,
+    /// Whether the record is hidden from default listings
+    pub archived: bool,
+    /// The ID of the product the record tracks
+    pub product_id: Option<String>,
+    /// The GLN of the location the record is currently at
+    pub location_id: Option<String>,
+    /// The namespace of the product the record tracks
+    pub product_namespace: Option<String>,
+    /// The schema of the custom product the record tracks
+    pub product_schema_name: Option<String>,
+}
+
+/// A record aggregated into another record, e.g. a case onto a pallet
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordAggregation {
+    pub id: Option<i64>,
+    pub parent_record_id: String,
+    pub child_record_id: String,
+    pub aggregated_at: i64,
+    /// Unset while the child is still aggregated into the parent
+    pub disaggregated_at: Option<i64>,
+    pub start_commit_num: i64,
+    pub end_commit_num: i64,
+    pub service_id: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -87,9 +122,16 @@ pub struct ReportedValue {
     pub enum_value: Option<i32>,
     pub struct_values: Option<Vec<ReportedValue>>,
     pub lat_long_value: Option<LatLongValue>,
+    /// Seconds since the Unix epoch
+    pub timestamp_value: Option<i64>,
+    /// The exact decimal string, e.g. "12.050"
+    pub decimal_value: Option<String>,
+    pub list_values: Option<Vec<ReportedValue>>,
     pub start_commit_num: i64,
     pub end_commit_num: i64,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,12 +162,17 @@ pub struct ReportedValueReporterToAgentMetadata {
     pub enum_value: Option<i32>,
     pub struct_values: Vec<ReportedValueReporterToAgentMetadata>,
     pub lat_long_value: Option<LatLongValue>,
+    pub timestamp_value: Option<i64>,
+    pub decimal_value: Option<String>,
+    pub list_values: Vec<ReportedValueReporterToAgentMetadata>,
     pub public_key: Option<String>,
     pub authorized: Option<bool>,
     pub metadata: Option<Vec<u8>>,
     pub reported_value_end_commit_num: i64,
     pub reporter_end_commit_num: Option<i64>,
     pub service_id: Option<String>,
+    pub transaction_id: Option<String>,
+    pub signer: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize)]
@@ -134,6 +181,88 @@ pub struct LatLong;
 #[derive(Default, Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct LatLongValue(pub i64, pub i64);
 
+/// The reported values on one page of a property, as they appear in state at a commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropertyPageSnapshot {
+    pub record_id: String,
+    pub property_name: String,
+    pub page_num: i32,
+    pub reported_values: Vec<PageReportedValue>,
+    pub commit_num: i64,
+    pub service_id: Option<String>,
+}
+
+/// A reported value as it appears on a property page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageReportedValue {
+    pub reporter_index: i32,
+    pub timestamp: i64,
+    pub data_type: String,
+    /// The serialized `PropertyValue`
+    pub value: Vec<u8>,
+    /// The unscaled value, if the value is a Number
+    pub number_value: Option<i64>,
+}
+
+/// A reported value kept in the append-only archive of property pages. Pages are reused once a
+/// property's ring of pages wraps; the generation counts how many times the page had been
+/// reused when the value was reported.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedReportedValue {
+    pub record_id: String,
+    pub property_name: String,
+    pub page_num: i32,
+    pub generation: i64,
+    pub reporter_index: i32,
+    pub timestamp: i64,
+    pub data_type: String,
+    /// The serialized `PropertyValue`
+    pub value: Vec<u8>,
+    /// The unscaled value, if the value is a Number
+    pub number_value: Option<i64>,
+    /// The commit the value was archived in
+    pub commit_num: i64,
+    pub service_id: Option<String>,
+}
+
+/// Restricts a listing of reported values to a range of report timestamps and a page of results
+#[derive(Debug, Clone, Default)]
+pub struct ReportedValueRange {
+    /// Only include values reported at or after this timestamp
+    pub from: Option<i64>,
+    /// Only include values reported at or before this timestamp
+    pub to: Option<i64>,
+    /// The number of values to skip, oldest first
+    pub offset: Option<i64>,
+    /// The maximum number of values to return
+    pub limit: Option<i64>,
+    /// Only include the most recent values, up to this many. Takes precedence over `offset` and
+    /// `limit`.
+    pub latest: Option<i64>,
+}
+
+/// The most recent LatLong value reported for a property of a record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordPosition {
+    pub record_id: String,
+    pub property_name: String,
+    pub lat_long_value: LatLongValue,
+    pub timestamp: i64,
+    pub service_id: Option<String>,
+}
+
+/// Summary of the Number values reported for a property within one time bucket. The values are
+/// scaled by the property's `number_exponent`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReportedValueBucket {
+    /// The first timestamp in the bucket; buckets are aligned to multiples of the bucket size
+    pub start: i64,
+    pub count: i64,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
 pub trait TrackAndTraceStore: Send + Sync {
     /// Adds an associated agent to the underlying storage
     ///
@@ -265,9 +394,15 @@ pub trait TrackAndTraceStore: Send + Sync {
     ///
     /// # Arguments
     ///
+    ///  * `include_archived` - Whether archived records are included
+    ///  * `product_id` - Only include records tracking this product, if set
+    ///  * `location_id` - Only include records currently at this location, if set
     ///  * `service_id` - The service ID to fetch for
     fn list_records(
         &self,
+        include_archived: bool,
+        product_id: Option<&str>,
+        location_id: Option<&str>,
         service_id: Option<&str>,
     ) -> Result<Vec<Record>, TrackAndTraceStoreError>;
 
@@ -298,6 +433,91 @@ pub trait TrackAndTraceStore: Send + Sync {
         property_name: &str,
         service_id: Option<&str>,
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError>;
+
+    /// Adds the values of property pages to the append-only archive. Values that are already
+    /// archived for a page are skipped; if archived values are missing from the snapshot, the
+    /// page has been reused and its values are archived under a new generation.
+    ///
+    /// # Arguments
+    ///
+    ///  * `pages` - The snapshots of the property pages to archive
+    fn archive_property_pages(
+        &self,
+        pages: Vec<PropertyPageSnapshot>,
+    ) -> Result<(), TrackAndTraceStoreError>;
+
+    /// Lists the archived reported values of a property, oldest first
+    ///
+    /// # Arguments
+    ///
+    ///  * `record_id` - The record ID to fetch for
+    ///  * `property_name` - The property name to fetch
+    ///  * `range` - The range of timestamps and results to return
+    ///  * `service_id` - The service ID to fetch for
+    fn list_archived_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ArchivedReportedValue>, TrackAndTraceStoreError>;
+
+    /// Aggregates the archived Number values of a property into buckets of reported timestamps,
+    /// oldest first. Buckets without values are omitted.
+    ///
+    /// # Arguments
+    ///
+    ///  * `record_id` - The record ID to fetch for
+    ///  * `property_name` - The property name to fetch
+    ///  * `bucket_size` - The width of each bucket, in the units of the reported timestamps
+    ///  * `range` - The range of timestamps to aggregate; `offset` and `limit` are ignored
+    ///  * `service_id` - The service ID to fetch for
+    fn aggregate_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        bucket_size: i64,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ReportedValueBucket>, TrackAndTraceStoreError>;
+
+    /// Lists the records whose most recently reported value of a LatLong property lies within a
+    /// bounding box
+    ///
+    /// # Arguments
+    ///
+    ///  * `property_name` - The name of the LatLong property holding each record's position
+    ///  * `bounding_box` - The box the positions must lie within
+    ///  * `service_id` - The service ID to fetch for
+    fn list_records_within(
+        &self,
+        property_name: &str,
+        bounding_box: &BoundingBox,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordPosition>, TrackAndTraceStoreError>;
+
+    /// Adds record aggregations to the underlying storage
+    ///
+    /// # Arguments
+    ///
+    ///  * `aggregations` - The aggregations to be added
+    fn add_record_aggregations(
+        &self,
+        aggregations: Vec<RecordAggregation>,
+    ) -> Result<(), TrackAndTraceStoreError>;
+
+    /// Lists every aggregation, current or past, that connects a record to its ancestors and
+    /// descendants
+    ///
+    /// # Arguments
+    ///
+    ///  * `record_id` - The record whose lineage is walked
+    ///  * `service_id` - The service ID to fetch for
+    fn list_record_lineage(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordAggregation>, TrackAndTraceStoreError>;
 }
 
 impl<TS> TrackAndTraceStore for Box<TS>
@@ -392,9 +612,12 @@ where
 
     fn list_records(
         &self,
+        include_archived: bool,
+        product_id: Option<&str>,
+        location_id: Option<&str>,
         service_id: Option<&str>,
     ) -> Result<Vec<Record>, TrackAndTraceStoreError> {
-        (**self).list_records(service_id)
+        (**self).list_records(include_archived, product_id, location_id, service_id)
     }
 
     fn list_reported_value_reporter_to_agent_metadata(
@@ -418,4 +641,56 @@ where
     ) -> Result<Vec<Reporter>, TrackAndTraceStoreError> {
         (**self).list_reporters(record_id, property_name, service_id)
     }
+
+    fn archive_property_pages(
+        &self,
+        pages: Vec<PropertyPageSnapshot>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        (**self).archive_property_pages(pages)
+    }
+
+    fn list_archived_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ArchivedReportedValue>, TrackAndTraceStoreError> {
+        (**self).list_archived_reported_values(record_id, property_name, range, service_id)
+    }
+
+    fn aggregate_reported_values(
+        &self,
+        record_id: &str,
+        property_name: &str,
+        bucket_size: i64,
+        range: &ReportedValueRange,
+        service_id: Option<&str>,
+    ) -> Result<Vec<ReportedValueBucket>, TrackAndTraceStoreError> {
+        (**self).aggregate_reported_values(record_id, property_name, bucket_size, range, service_id)
+    }
+
+    fn list_records_within(
+        &self,
+        property_name: &str,
+        bounding_box: &BoundingBox,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordPosition>, TrackAndTraceStoreError> {
+        (**self).list_records_within(property_name, bounding_box, service_id)
+    }
+
+    fn add_record_aggregations(
+        &self,
+        aggregations: Vec<RecordAggregation>,
+    ) -> Result<(), TrackAndTraceStoreError> {
+        (**self).add_record_aggregations(aggregations)
+    }
+
+    fn list_record_lineage(
+        &self,
+        record_id: &str,
+        service_id: Option<&str>,
+    ) -> Result<Vec<RecordAggregation>, TrackAndTraceStoreError> {
+        (**self).list_record_lineage(record_id, service_id)
+    }
 }
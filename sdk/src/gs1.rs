@@ -0,0 +1,355 @@
+// Copyright 2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation of GS1 identifiers and of the GS1 company prefixes an organization owns.
+//!
+//! Organizations record the prefixes they have licensed from GS1 as a comma-separated list under
+//! the `gs1_company_prefixes` key of their metadata. A GTIN or GLN belongs to an organization if
+//! it starts with one of those prefixes once normalized. GTIN-12s are prefixed with a zero and
+//! GTIN-14s lose their indicator digit, so that a company prefix matches every GTIN it was used
+//! to allocate. GTIN-8s are allocated from separate GS1-8 prefixes and are matched as they are.
+//!
+//! See <https://www.gs1.org/standards/id-keys/company-prefix> and
+//! <https://www.gs1.org/services/how-calculate-check-digit-manually>.
+
+use std::error::Error;
+use std::fmt;
+
+/// The organization metadata key that lists the GS1 company prefixes the organization owns
+pub const GS1_COMPANY_PREFIXES_KEY: &str = "gs1_company_prefixes";
+
+const GTIN_14_LENGTH: usize = 14;
+const GLN_LENGTH: usize = 13;
+const MAX_COMPANY_PREFIX_LENGTH: usize = 12;
+
+#[derive(Debug, PartialEq)]
+pub enum Gs1Error {
+    /// Returned for a GTIN with an invalid format, length or check digit.
+    InvalidGtin(String),
+    /// Returned for a GLN with an invalid format, length or check digit.
+    InvalidGln(String),
+    /// Returned for a company prefix that is empty, too long or not numeric.
+    InvalidCompanyPrefix(String),
+}
+
+impl fmt::Display for Gs1Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Gs1Error::InvalidGtin(ref msg) => write!(f, "{}", msg),
+            Gs1Error::InvalidGln(ref msg) => write!(f, "{}", msg),
+            Gs1Error::InvalidCompanyPrefix(ref msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for Gs1Error {}
+
+/// Validates a GTIN-8, GTIN-12, GTIN-13 or GTIN-14 and returns it as a GTIN-14
+pub fn normalize_gtin(gtin: &str) -> Result<String, Gs1Error> {
+    if !is_numeric(gtin) {
+        return Err(Gs1Error::InvalidGtin(format!(
+            "Invalid format, GTIN identifiers only contain numbers: {}",
+            gtin
+        )));
+    }
+
+    match gtin.len() {
+        8 | 12 | 13 | 14 => (),
+        _ => {
+            return Err(Gs1Error::InvalidGtin(format!(
+                "Invalid length for GTIN identifier: {}",
+                gtin
+            )))
+        }
+    }
+
+    if !has_valid_check_digit(gtin) {
+        return Err(Gs1Error::InvalidGtin(format!(
+            "Invalid gtin, check digit validation failed: {}",
+            gtin
+        )));
+    }
+
+    Ok(format!("{:0>width$}", gtin, width = GTIN_14_LENGTH))
+}
+
+/// Validates a GTIN-8, GTIN-12, GTIN-13 or GTIN-14
+pub fn validate_gtin(gtin: &str) -> Result<(), Gs1Error> {
+    normalize_gtin(gtin).map(|_| ())
+}
+
+/// Validates a 13 digit GLN
+pub fn validate_gln(gln: &str) -> Result<(), Gs1Error> {
+    if !is_numeric(gln) || gln.len() != GLN_LENGTH {
+        return Err(Gs1Error::InvalidGln(format!(
+            "Invalid GLN, GLNs are 13 digit numbers: {}",
+            gln
+        )));
+    }
+
+    if !has_valid_check_digit(gln) {
+        return Err(Gs1Error::InvalidGln(format!(
+            "Invalid GLN, check digit validation failed: {}",
+            gln
+        )));
+    }
+
+    Ok(())
+}
+
+/// Parses the comma-separated list of company prefixes stored in an organization's metadata
+pub fn parse_company_prefixes(value: &str) -> Result<Vec<&str>, Gs1Error> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|prefix| !prefix.is_empty())
+        .map(|prefix| {
+            if is_numeric(prefix) && prefix.len() <= MAX_COMPANY_PREFIX_LENGTH {
+                Ok(prefix)
+            } else {
+                Err(Gs1Error::InvalidCompanyPrefix(format!(
+                    "Invalid GS1 company prefix, prefixes are numbers of at most {} digits: {}",
+                    MAX_COMPANY_PREFIX_LENGTH, prefix
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Returns whether the GTIN was allocated from one of the company prefixes
+pub fn gtin_has_company_prefix(gtin: &str, prefixes: &[&str]) -> Result<bool, Gs1Error> {
+    let gtin_14 = normalize_gtin(gtin)?;
+    let allocated = if gtin.len() == 8 {
+        gtin
+    } else {
+        // Drop the indicator digit, leaving the GTIN-13 form the prefix was allocated in
+        &gtin_14[1..]
+    };
+
+    Ok(has_prefix(allocated, prefixes))
+}
+
+/// Returns whether the GLN was allocated from one of the company prefixes
+pub fn gln_has_company_prefix(gln: &str, prefixes: &[&str]) -> Result<bool, Gs1Error> {
+    validate_gln(gln)?;
+
+    Ok(has_prefix(gln, prefixes))
+}
+
+fn has_prefix(identifier: &str, prefixes: &[&str]) -> bool {
+    prefixes
+        .iter()
+        .any(|prefix| prefix.len() < identifier.len() && identifier.starts_with(prefix))
+}
+
+/// Checks the last digit against the others, which are weighted 3 and 1 alternately starting
+/// from the rightmost one.
+fn has_valid_check_digit(identifier: &str) -> bool {
+    let mut digits = identifier.chars().rev().filter_map(|c| c.to_digit(10));
+    let check_digit = match digits.next() {
+        Some(digit) => digit,
+        None => return false,
+    };
+
+    let sum: u32 = digits
+        .enumerate()
+        .map(|(i, digit)| if i % 2 == 0 { digit * 3 } else { digit })
+        .sum();
+
+    (10 - sum % 10) % 10 == check_digit
+}
+
+fn is_numeric(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    // This tests that a valid gtin-8 is accepted and padded to a gtin-14
+    fn valid_gtin_8() {
+        assert_eq!(normalize_gtin("40170725"), Ok("00000040170725".to_string()));
+    }
+
+    #[test]
+    // This tests that the check-digit validation of the gtin-8: "40170726" fails
+    fn invalid_gtin_8() {
+        assert_eq!(
+            validate_gtin("40170726").unwrap_err().to_string(),
+            "Invalid gtin, check digit validation failed: 40170726"
+        );
+    }
+
+    #[test]
+    // This tests that a valid gtin-12 is accepted and padded to a gtin-14
+    fn valid_gtin_12() {
+        assert_eq!(
+            normalize_gtin("688955434684"),
+            Ok("00688955434684".to_string())
+        );
+    }
+
+    #[test]
+    // This tests that the check-digit validation of the gtin-12: "688955434584" fails
+    fn invalid_gtin_12() {
+        assert_eq!(
+            validate_gtin("688955434584").unwrap_err().to_string(),
+            "Invalid gtin, check digit validation failed: 688955434584"
+        );
+    }
+
+    #[test]
+    // This tests that a valid gtin-13 is accepted and padded to a gtin-14
+    fn valid_gtin_13() {
+        assert_eq!(
+            normalize_gtin("9781981855728"),
+            Ok("09781981855728".to_string())
+        );
+    }
+
+    #[test]
+    // This tests that the check-digit validation of the gtin-13: "9781981855738" fails
+    fn invalid_gtin_13() {
+        assert_eq!(
+            validate_gtin("9781981855738").unwrap_err().to_string(),
+            "Invalid gtin, check digit validation failed: 9781981855738"
+        );
+    }
+
+    #[test]
+    // This tests that a valid gtin-14 is accepted unchanged
+    fn valid_gtin_14() {
+        assert_eq!(
+            normalize_gtin("10012345678902"),
+            Ok("10012345678902".to_string())
+        );
+    }
+
+    #[test]
+    // This tests that the check-digit validation of the gtin-14: "10012345678912" fails
+    fn invalid_gtin_14() {
+        assert_eq!(
+            validate_gtin("10012345678912").unwrap_err().to_string(),
+            "Invalid gtin, check digit validation failed: 10012345678912"
+        );
+    }
+
+    #[test]
+    // This tests gtins entered of an invalid length
+    fn invalid_gtin_length() {
+        assert_eq!(
+            validate_gtin("123").unwrap_err().to_string(),
+            "Invalid length for GTIN identifier: 123"
+        );
+        assert_eq!(
+            validate_gtin("10012345678923423423423412")
+                .unwrap_err()
+                .to_string(),
+            "Invalid length for GTIN identifier: 10012345678923423423423412"
+        );
+    }
+
+    #[test]
+    // This tests gtins entered of an invalid format
+    fn invalid_gtin_format() {
+        assert_eq!(
+            validate_gtin("1012938473jer").unwrap_err().to_string(),
+            "Invalid format, GTIN identifiers only contain numbers: 1012938473jer"
+        );
+        assert!(validate_gtin("1e100000").is_err());
+    }
+
+    #[test]
+    // This tests that GLNs are validated by length and check digit
+    fn validate_glns() {
+        assert!(validate_gln("9012345000004").is_ok());
+        assert_eq!(
+            validate_gln("9012345000005").unwrap_err().to_string(),
+            "Invalid GLN, check digit validation failed: 9012345000005"
+        );
+        assert_eq!(
+            validate_gln("12345").unwrap_err().to_string(),
+            "Invalid GLN, GLNs are 13 digit numbers: 12345"
+        );
+    }
+
+    #[test]
+    // This tests that the prefix list is split on commas, ignoring whitespace and empty entries
+    fn parse_prefix_lists() {
+        assert_eq!(
+            parse_company_prefixes("0688955, 978198,,"),
+            Ok(vec!["0688955", "978198"])
+        );
+        assert_eq!(parse_company_prefixes(""), Ok(vec![]));
+        assert_eq!(
+            parse_company_prefixes("0688955,test_value"),
+            Err(Gs1Error::InvalidCompanyPrefix(
+                "Invalid GS1 company prefix, prefixes are numbers of at most 12 digits: test_value"
+                    .to_string()
+            ))
+        );
+    }
+
+    #[test]
+    // This tests that the company prefix of each gtin length is matched against its
+    // normalized form
+    fn gtin_company_prefixes() {
+        let prefixes = vec!["0688955", "978198", "0012345", "4017"];
+
+        assert_eq!(gtin_has_company_prefix("40170725", &prefixes), Ok(true));
+        assert_eq!(gtin_has_company_prefix("688955434684", &prefixes), Ok(true));
+        assert_eq!(
+            gtin_has_company_prefix("9781981855728", &prefixes),
+            Ok(true)
+        );
+        assert_eq!(
+            gtin_has_company_prefix("10012345678902", &prefixes),
+            Ok(true)
+        );
+
+        assert_eq!(gtin_has_company_prefix("40170725", &["0688955"]), Ok(false));
+        assert_eq!(
+            gtin_has_company_prefix("688955434684", &["688955"]),
+            Ok(false)
+        );
+        assert_eq!(
+            gtin_has_company_prefix("9781981855728", &["0688955"]),
+            Ok(false)
+        );
+        assert_eq!(
+            gtin_has_company_prefix("10012345678902", &["1001234"]),
+            Ok(false)
+        );
+        assert!(gtin_has_company_prefix("9781981855738", &prefixes).is_err());
+    }
+
+    #[test]
+    // This tests that glns are matched against the company prefixes
+    fn gln_company_prefixes() {
+        assert_eq!(
+            gln_has_company_prefix("9012345000004", &["9012345"]),
+            Ok(true)
+        );
+        assert_eq!(
+            gln_has_company_prefix("9012345000004", &["0688955"]),
+            Ok(false)
+        );
+        assert_eq!(
+            gln_has_company_prefix("9012345000004", &["9012345000004"]),
+            Ok(false)
+        );
+    }
+}
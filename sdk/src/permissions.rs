@@ -12,8 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crypto::digest::Digest;
-use crypto::sha2::Sha512;
 use std::error::Error;
 use std::fmt;
 
@@ -27,18 +25,10 @@ cfg_if! {
     }
 }
 
-use crate::protocol::pike::state::{Agent, AgentList};
+use crate::protocol::addressing::{compute_agent_address, compute_role_address};
+use crate::protocol::pike::state::{Agent, AgentList, Role, RoleList};
 use crate::protos::{FromBytes, ProtoConversionError};
 
-const PIKE_NAMESPACE: &str = "cad11d";
-const PIKE_AGENT_RESOURCE: &str = "00";
-
-fn compute_agent_address(public_key: &str) -> String {
-    let mut sha = Sha512::new();
-    sha.input(public_key.as_bytes());
-    String::from(PIKE_NAMESPACE) + PIKE_AGENT_RESOURCE + &sha.result_str()[..62].to_string()
-}
-
 #[derive(Debug)]
 pub enum PermissionCheckerError {
     /// Returned for an error originating at the TransactionContext.
@@ -83,6 +73,9 @@ impl From<ProtoConversionError> for PermissionCheckerError {
     }
 }
 
+/// The permission held by an organization's admins, which Pike Roles may not grant
+pub const ADMIN_PERMISSION: &str = "admin";
+
 /// Helper struct for Pike functionality.
 pub struct PermissionChecker<'a> {
     /// A PermissionChecker is tied to a version of state, so it has a
@@ -101,7 +94,13 @@ impl<'a> PermissionChecker<'a> {
         PermissionChecker { context }
     }
 
-    /// Checks whether an agent with a given public key has a certain role.
+    /// Checks whether an agent with a given public key has a certain permission over the
+    /// resources of its own organization.
+    ///
+    /// The permission is held if one of the agent's roles is the permission string itself, or
+    /// names a Pike Role of the agent's organization that grants it. Roles of other
+    /// organizations only grant permissions over those organizations' resources; see
+    /// `has_permission_in_organization`.
     ///
     /// # Arguments
     ///
@@ -113,14 +112,102 @@ impl<'a> PermissionChecker<'a> {
         public_key: &str,
         permission: &str,
     ) -> Result<bool, PermissionCheckerError> {
-        let agent = self.get_agent(public_key)?;
-        match agent {
-            Some(agent) => Ok(agent.roles().iter().any(|r| r == permission)),
-            None => Err(PermissionCheckerError::InvalidPublicKey(format!(
+        let agent = self.get_existing_agent(public_key)?;
+        self.agent_has_permission(&agent, permission, agent.org_id())
+    }
+
+    /// Checks whether an agent with a given public key has a certain permission over the
+    /// resources of the organization `org_id`.
+    ///
+    /// For the agent's own organization this is the same as `has_permission`. For another
+    /// organization, the permission is only held through a role of that organization the agent
+    /// is assigned as `<org_id>.<name>`, and which lists the agent's organization as allowed.
+    ///
+    /// # Arguments
+    ///
+    /// * `public_key` - Public key of a Pike agent.
+    /// * `permission` - Permission string to be checked.
+    /// * `org_id` - ID of the organization whose resources the permission applies to.
+    ///
+    pub fn has_permission_in_organization(
+        &self,
+        public_key: &str,
+        permission: &str,
+        org_id: &str,
+    ) -> Result<bool, PermissionCheckerError> {
+        let agent = self.get_existing_agent(public_key)?;
+        self.agent_has_permission(&agent, permission, org_id)
+    }
+
+    fn agent_has_permission(
+        &self,
+        agent: &Agent,
+        permission: &str,
+        org_id: &str,
+    ) -> Result<bool, PermissionCheckerError> {
+        for role_ref in agent.roles() {
+            if role_ref == permission && agent.org_id() == org_id {
+                return Ok(true);
+            }
+
+            // Roles cannot make an agent an admin; only the agent's own roles can
+            if permission == ADMIN_PERMISSION {
+                continue;
+            }
+
+            if let Some(role) = self.resolve_role(agent.org_id(), role_ref)? {
+                if role.org_id() == org_id && role.permissions().iter().any(|p| p == permission) {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Returns the active Pike Role a role reference of an agent in `agent_org_id` refers to,
+    /// if the agent's organization is allowed to use it.
+    fn resolve_role(
+        &self,
+        agent_org_id: &str,
+        role_ref: &str,
+    ) -> Result<Option<Role>, PermissionCheckerError> {
+        // Role names cannot contain '.', but organization IDs may, so the reference is split at
+        // its last '.'
+        let mut parts = role_ref.rsplitn(2, '.');
+        let (name, org_id) = match (parts.next(), parts.next()) {
+            (Some(name), Some(org_id)) => (name, org_id),
+            _ => (role_ref, agent_org_id),
+        };
+
+        let role = match self.get_role(org_id, name)? {
+            Some(role) => role,
+            None => return Ok(None),
+        };
+
+        if !role.active() {
+            return Ok(None);
+        }
+
+        if org_id != agent_org_id
+            && !role
+                .allowed_organizations()
+                .iter()
+                .any(|allowed| allowed == agent_org_id)
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(role))
+    }
+
+    fn get_existing_agent(&self, public_key: &str) -> Result<Agent, PermissionCheckerError> {
+        self.get_agent(public_key)?.ok_or_else(|| {
+            PermissionCheckerError::InvalidPublicKey(format!(
                 "The signer is not an Agent: {}",
                 public_key
-            ))),
-        }
+            ))
+        })
     }
 
     fn get_agent(&self, public_key: &str) -> Result<Option<Agent>, PermissionCheckerError> {
@@ -139,6 +226,22 @@ impl<'a> PermissionChecker<'a> {
             None => Ok(None),
         }
     }
+
+    fn get_role(&self, org_id: &str, name: &str) -> Result<Option<Role>, PermissionCheckerError> {
+        let address = compute_role_address(org_id, name);
+        let d = self.context.get_state_entry(&address)?;
+        match d {
+            Some(packed) => {
+                let role_list = RoleList::from_bytes(packed.as_slice())?;
+                Ok(role_list
+                    .roles()
+                    .iter()
+                    .find(|role| role.org_id() == org_id && role.name() == name)
+                    .cloned())
+            }
+            None => Ok(None),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -148,7 +251,9 @@ mod tests {
     use std::cell::RefCell;
     use std::collections::HashMap;
 
-    use crate::protocol::pike::state::{AgentBuilder, AgentListBuilder};
+    use crate::protocol::pike::state::{
+        AgentBuilder, AgentListBuilder, RoleBuilder, RoleListBuilder,
+    };
     use crate::protos::IntoBytes;
 
     const ROLE_A: &str = "Role A";
@@ -156,6 +261,8 @@ mod tests {
 
     const PUBLIC_KEY: &str = "test_public_key";
     const ORG_ID: &str = "test_org";
+    const OTHER_ORG_ID: &str = "other_org";
+    const PERMISSION: &str = "can_update_location";
 
     #[derive(Default)]
     /// A MockTransactionContext that can be used to test PermissionChecker
@@ -325,4 +432,163 @@ mod tests {
         let result = pc.has_permission(PUBLIC_KEY, ROLE_B).unwrap();
         assert!(result);
     }
+
+    fn set_agent(context: &MockTransactionContext, org_id: &str, roles: Vec<String>) {
+        let agent = AgentBuilder::new()
+            .with_org_id(org_id.to_string())
+            .with_public_key(PUBLIC_KEY.to_string())
+            .with_active(true)
+            .with_roles(roles)
+            .build()
+            .unwrap();
+        let agent_list = AgentListBuilder::new()
+            .with_agents(vec![agent])
+            .build()
+            .unwrap();
+        context
+            .set_state_entry(
+                compute_agent_address(PUBLIC_KEY),
+                agent_list.into_bytes().unwrap(),
+            )
+            .unwrap();
+    }
+
+    fn set_role(
+        context: &MockTransactionContext,
+        org_id: &str,
+        allowed: Vec<String>,
+        active: bool,
+    ) {
+        let role = RoleBuilder::new()
+            .with_org_id(org_id.to_string())
+            .with_name(ROLE_A.to_string())
+            .with_permissions(vec![PERMISSION.to_string()])
+            .with_allowed_organizations(allowed)
+            .with_active(active)
+            .build()
+            .unwrap();
+        let role_list = RoleListBuilder::new()
+            .with_roles(vec![role])
+            .build()
+            .unwrap();
+        context
+            .set_state_entry(
+                compute_role_address(org_id, ROLE_A),
+                role_list.into_bytes().unwrap(),
+            )
+            .unwrap();
+    }
+
+    #[test]
+    // Test that a role of the agent's own organization grants its permissions, unless the role
+    // is inactive
+    fn test_has_permission_through_role() {
+        let context = MockTransactionContext::default();
+        let pc = PermissionChecker::new(&context);
+
+        set_agent(&context, ORG_ID, vec![ROLE_A.to_string()]);
+        set_role(&context, ORG_ID, vec![], true);
+
+        assert!(pc.has_permission(PUBLIC_KEY, PERMISSION).unwrap());
+        assert!(!pc.has_permission(PUBLIC_KEY, "can_create_product").unwrap());
+
+        set_role(&context, ORG_ID, vec![], false);
+        assert!(!pc.has_permission(PUBLIC_KEY, PERMISSION).unwrap());
+    }
+
+    #[test]
+    // Test that another organization's role only grants its permissions if the agent's
+    // organization is allowed to use it, and only over that organization's resources
+    fn test_has_permission_through_cross_organization_role() {
+        let context = MockTransactionContext::default();
+        let pc = PermissionChecker::new(&context);
+
+        set_agent(
+            &context,
+            ORG_ID,
+            vec![format!("{}.{}", OTHER_ORG_ID, ROLE_A)],
+        );
+
+        set_role(&context, OTHER_ORG_ID, vec![], true);
+        assert!(!pc
+            .has_permission_in_organization(PUBLIC_KEY, PERMISSION, OTHER_ORG_ID)
+            .unwrap());
+
+        set_role(&context, OTHER_ORG_ID, vec![ORG_ID.to_string()], true);
+        assert!(pc
+            .has_permission_in_organization(PUBLIC_KEY, PERMISSION, OTHER_ORG_ID)
+            .unwrap());
+        assert!(!pc.has_permission(PUBLIC_KEY, PERMISSION).unwrap());
+        assert!(!pc
+            .has_permission_in_organization(PUBLIC_KEY, PERMISSION, ORG_ID)
+            .unwrap());
+    }
+
+    #[test]
+    // Test that the agent's own permission strings do not apply to other organizations'
+    // resources
+    fn test_has_permission_in_other_organization() {
+        let context = MockTransactionContext::default();
+        let pc = PermissionChecker::new(&context);
+
+        set_agent(&context, ORG_ID, vec![PERMISSION.to_string()]);
+
+        assert!(pc
+            .has_permission_in_organization(PUBLIC_KEY, PERMISSION, ORG_ID)
+            .unwrap());
+        assert!(!pc
+            .has_permission_in_organization(PUBLIC_KEY, PERMISSION, OTHER_ORG_ID)
+            .unwrap());
+    }
+
+    #[test]
+    // Test that a role listing the admin permission does not make its agents admins
+    fn test_has_permission_admin_not_granted_by_role() {
+        let context = MockTransactionContext::default();
+        let pc = PermissionChecker::new(&context);
+
+        set_agent(&context, ORG_ID, vec![ROLE_A.to_string()]);
+        let role = RoleBuilder::new()
+            .with_org_id(ORG_ID.to_string())
+            .with_name(ROLE_A.to_string())
+            .with_permissions(vec![ADMIN_PERMISSION.to_string()])
+            .with_active(true)
+            .build()
+            .unwrap();
+        let role_list = RoleListBuilder::new()
+            .with_roles(vec![role])
+            .build()
+            .unwrap();
+        context
+            .set_state_entry(
+                compute_role_address(ORG_ID, ROLE_A),
+                role_list.into_bytes().unwrap(),
+            )
+            .unwrap();
+
+        assert!(!pc.has_permission(PUBLIC_KEY, ADMIN_PERMISSION).unwrap());
+
+        set_agent(&context, ORG_ID, vec![ADMIN_PERMISSION.to_string()]);
+        assert!(pc.has_permission(PUBLIC_KEY, ADMIN_PERMISSION).unwrap());
+    }
+
+    #[test]
+    // Test that a role of an organization whose ID contains '.' is resolved by splitting the
+    // reference at its last '.'
+    fn test_has_permission_through_role_of_dotted_organization() {
+        let context = MockTransactionContext::default();
+        let pc = PermissionChecker::new(&context);
+        let dotted_org_id = "acme.example";
+
+        set_agent(
+            &context,
+            ORG_ID,
+            vec![format!("{}.{}", dotted_org_id, ROLE_A)],
+        );
+        set_role(&context, dotted_org_id, vec![ORG_ID.to_string()], true);
+
+        assert!(pc
+            .has_permission_in_organization(PUBLIC_KEY, PERMISSION, dotted_org_id)
+            .unwrap());
+    }
 }
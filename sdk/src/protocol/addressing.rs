@@ -0,0 +1,315 @@
+// Copyright 2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Computes and parses the state addresses of every Grid entity.
+//!
+//! Most addresses end in a truncated hash of the entity's identifier, so only the kind of entity
+//! can be recovered from them. GS1 product and location addresses embed the GTIN or GLN, and
+//! property addresses embed the page number, which can be parsed back out.
+
+use crypto::digest::Digest;
+use crypto::sha2::Sha512;
+
+use super::product::state::ProductNamespace;
+
+/// The namespace of every Pike entity
+pub const PIKE_NAMESPACE: &str = "cad11d";
+pub const PIKE_AGENT_PREFIX: &str = "cad11d00";
+pub const PIKE_ORGANIZATION_PREFIX: &str = "cad11d01";
+pub const PIKE_ROLE_PREFIX: &str = "cad11d02";
+
+/// The namespace of every Grid schema, product and location
+pub const GRID_NAMESPACE: &str = "621dee";
+pub const GRID_SCHEMA_PREFIX: &str = "621dee01";
+pub const GRID_PRODUCT_PREFIX: &str = "621dee02";
+pub const GRID_LOCATION_PREFIX: &str = "621dee04";
+
+/// The namespace of every Track and Trace entity; the first six characters of the SHA-512 hash
+/// of `grid_track_and_trace`
+pub const TRACK_AND_TRACE_NAMESPACE: &str = "a43b46";
+pub const TRACK_AND_TRACE_PROPERTY_PREFIX: &str = "a43b46ea";
+pub const TRACK_AND_TRACE_PROPOSAL_PREFIX: &str = "a43b46aa";
+pub const TRACK_AND_TRACE_RECORD_PREFIX: &str = "a43b46ec";
+
+/// Every address of a GS1 product starts with this, followed by the GTIN and `00`
+const GS1_PRODUCT_ADDRESS_PREFIX: &str = "621dee02010000000000000000000000000000000000000000000000";
+/// Every address of a GS1 location starts with this, followed by the GLN and `00`
+const GS1_LOCATION_ADDRESS_PREFIX: &str = "621dee0401000000000000000000000000000000000000000000000";
+const GS1_ADDRESS_SUFFIX: &str = "00";
+/// Every address of a product outside of the GS1 namespace starts with this, followed by a hash
+/// of the product's schema name and a hash of its identifier
+const CUSTOM_PRODUCT_ADDRESS_PREFIX: &str = "621dee0202";
+
+/// The offset of the page number within a property address
+const PROPERTY_PAGE_OFFSET: usize = 66;
+
+/// The kinds of entity stored in Grid state
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressKind {
+    Agent,
+    Organization,
+    Role,
+    Schema,
+    Product,
+    Location,
+    Record,
+    /// A property of a record, stored on page zero
+    Property,
+    /// A page of reported values of a record's property
+    PropertyPage,
+    Proposal,
+}
+
+/// Computes the address a Pike Agent is stored at based on its public_key
+pub fn compute_agent_address(public_key: &str) -> String {
+    String::from(PIKE_AGENT_PREFIX) + &hash(public_key, 62)
+}
+
+/// Computes the address a Pike Organization is stored at based on its identifier
+pub fn compute_organization_address(org_id: &str) -> String {
+    String::from(PIKE_ORGANIZATION_PREFIX) + &hash(org_id, 62)
+}
+
+/// Computes the address a Pike Role is stored at based on its owning organization and name
+pub fn compute_role_address(org_id: &str, name: &str) -> String {
+    String::from(PIKE_ROLE_PREFIX) + &hash(org_id, 20) + &hash(name, 42)
+}
+
+/// Computes the address a Grid Schema is stored at based on its name
+pub fn compute_schema_address(name: &str) -> String {
+    String::from(GRID_SCHEMA_PREFIX) + &hash(name, 62)
+}
+
+/// Computes the address a GS1 product is stored at based on its GTIN
+pub fn compute_gs1_product_address(gtin: &str) -> String {
+    String::from(GS1_PRODUCT_ADDRESS_PREFIX) + gtin + GS1_ADDRESS_SUFFIX
+}
+
+/// Computes the address a product that follows one of the schemas of the custom namespace is
+/// stored at. Products of different schemas may share an identifier without sharing an address.
+pub fn compute_custom_product_address(schema_name: &str, product_id: &str) -> String {
+    String::from(CUSTOM_PRODUCT_ADDRESS_PREFIX) + &hash(schema_name, 20) + &hash(product_id, 40)
+}
+
+/// Computes the address a product is stored at based on its namespace. The schema name is only
+/// used by custom products.
+pub fn compute_product_address(
+    namespace: &ProductNamespace,
+    schema_name: &str,
+    product_id: &str,
+) -> String {
+    match namespace {
+        ProductNamespace::GS1 => compute_gs1_product_address(product_id),
+        ProductNamespace::Custom => compute_custom_product_address(schema_name, product_id),
+    }
+}
+
+/// Computes the address a GS1 location is stored at based on its GLN
+pub fn compute_gs1_location_address(gln: &str) -> String {
+    String::from(GS1_LOCATION_ADDRESS_PREFIX) + gln + GS1_ADDRESS_SUFFIX
+}
+
+/// Computes the address a Track and Trace record is stored at based on its identifier
+pub fn compute_record_address(record_id: &str) -> String {
+    String::from(TRACK_AND_TRACE_RECORD_PREFIX) + &hash(record_id, 62)
+}
+
+/// Computes the address of a page of a record's property. Page zero holds the property itself;
+/// the following pages hold its reported values.
+pub fn compute_property_address(record_id: &str, property_name: &str, page: u32) -> String {
+    compute_property_address_range(record_id) + &hash(property_name, 22) + &format!("{:04x}", page)
+}
+
+/// Computes the prefix shared by the addresses of every property of a record
+pub fn compute_property_address_range(record_id: &str) -> String {
+    String::from(TRACK_AND_TRACE_PROPERTY_PREFIX) + &hash(record_id, 36)
+}
+
+/// Computes the address of the proposals made to an agent for a record
+pub fn compute_proposal_address(record_id: &str, agent_id: &str) -> String {
+    String::from(TRACK_AND_TRACE_PROPOSAL_PREFIX) + &hash(record_id, 36) + &hash(agent_id, 26)
+}
+
+/// Returns the kind of entity stored at the address, or `None` if it is not a Grid address
+pub fn classify_address(address: &str) -> Option<AddressKind> {
+    match address.get(..8)? {
+        PIKE_AGENT_PREFIX => Some(AddressKind::Agent),
+        PIKE_ORGANIZATION_PREFIX => Some(AddressKind::Organization),
+        PIKE_ROLE_PREFIX => Some(AddressKind::Role),
+        GRID_SCHEMA_PREFIX => Some(AddressKind::Schema),
+        GRID_PRODUCT_PREFIX => Some(AddressKind::Product),
+        GRID_LOCATION_PREFIX => Some(AddressKind::Location),
+        TRACK_AND_TRACE_RECORD_PREFIX => Some(AddressKind::Record),
+        TRACK_AND_TRACE_PROPOSAL_PREFIX => Some(AddressKind::Proposal),
+        TRACK_AND_TRACE_PROPERTY_PREFIX => match address.get(PROPERTY_PAGE_OFFSET..) {
+            Some("0000") => Some(AddressKind::Property),
+            _ => Some(AddressKind::PropertyPage),
+        },
+        _ => None,
+    }
+}
+
+/// Returns the GTIN embedded in a GS1 product address
+pub fn parse_gs1_product_address(address: &str) -> Option<&str> {
+    parse_gs1_address(address, GS1_PRODUCT_ADDRESS_PREFIX)
+}
+
+/// Returns the GLN embedded in a GS1 location address
+pub fn parse_gs1_location_address(address: &str) -> Option<&str> {
+    parse_gs1_address(address, GS1_LOCATION_ADDRESS_PREFIX)
+}
+
+/// Returns the page number embedded in a property address
+pub fn parse_property_page(address: &str) -> Option<u32> {
+    if !address.starts_with(TRACK_AND_TRACE_PROPERTY_PREFIX) {
+        return None;
+    }
+
+    address
+        .get(PROPERTY_PAGE_OFFSET..)
+        .filter(|page| page.len() == 4)
+        .and_then(|page| u32::from_str_radix(page, 16).ok())
+}
+
+fn parse_gs1_address<'a>(address: &'a str, prefix: &str) -> Option<&'a str> {
+    if !address.starts_with(prefix)
+        || !address.ends_with(GS1_ADDRESS_SUFFIX)
+        || address.len() <= prefix.len() + GS1_ADDRESS_SUFFIX.len()
+    {
+        return None;
+    }
+
+    address.get(prefix.len()..address.len() - GS1_ADDRESS_SUFFIX.len())
+}
+
+fn hash(to_hash: &str, num: usize) -> String {
+    let mut sha = Sha512::new();
+    sha.input_str(to_hash);
+    sha.result_str()[..num].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the Track and Trace namespace is derived from the family name
+    #[test]
+    fn track_and_trace_namespace() {
+        assert_eq!(hash("grid_track_and_trace", 6), TRACK_AND_TRACE_NAMESPACE);
+    }
+
+    /// Verify that hashed addresses are 70 characters long and are classified as the kind of
+    /// entity they were computed for.
+    #[test]
+    fn compute_and_classify_addresses() {
+        let agent_address = compute_agent_address("agent_public_key");
+        assert_eq!(
+            agent_address,
+            "cad11d00292d5baa6b4002893890c62b4257074a462b5961721526a9cca1769ac9aeb8"
+        );
+
+        let addresses = vec![
+            (agent_address, AddressKind::Agent),
+            (
+                compute_organization_address("org_01"),
+                AddressKind::Organization,
+            ),
+            (compute_role_address("org_01", "shipper"), AddressKind::Role),
+            (compute_schema_address("lightbulb"), AddressKind::Schema),
+            (compute_record_address("record_01"), AddressKind::Record),
+            (
+                compute_property_address("record_01", "temperature", 0),
+                AddressKind::Property,
+            ),
+            (
+                compute_property_address("record_01", "temperature", 3),
+                AddressKind::PropertyPage,
+            ),
+            (
+                compute_proposal_address("record_01", "agent_public_key"),
+                AddressKind::Proposal,
+            ),
+        ];
+
+        for (address, kind) in addresses {
+            assert_eq!(address.len(), 70);
+            assert_eq!(classify_address(&address), Some(kind));
+        }
+    }
+
+    /// Verify that GS1 addresses are classified and that the GTIN or GLN can be parsed back out
+    #[test]
+    fn parse_gs1_addresses() {
+        let product_address = compute_gs1_product_address("00012345600012");
+        assert_eq!(
+            classify_address(&product_address),
+            Some(AddressKind::Product)
+        );
+        assert_eq!(
+            parse_gs1_product_address(&product_address),
+            Some("00012345600012")
+        );
+        assert_eq!(parse_gs1_location_address(&product_address), None);
+
+        let location_address = compute_gs1_location_address("0123456789012");
+        assert_eq!(location_address.len(), 70);
+        assert_eq!(
+            classify_address(&location_address),
+            Some(AddressKind::Location)
+        );
+        assert_eq!(
+            parse_gs1_location_address(&location_address),
+            Some("0123456789012")
+        );
+    }
+
+    /// Verify that custom products are partitioned by schema and are not mistaken for GS1 products
+    #[test]
+    fn custom_product_addresses() {
+        let address = compute_product_address(&ProductNamespace::Custom, "lightbulb", "LB-001");
+        assert_eq!(
+            address,
+            compute_custom_product_address("lightbulb", "LB-001")
+        );
+        assert_eq!(address.len(), 70);
+        assert_eq!(classify_address(&address), Some(AddressKind::Product));
+        assert_eq!(parse_gs1_product_address(&address), None);
+        assert_ne!(address, compute_custom_product_address("lamp", "LB-001"));
+        assert_eq!(
+            compute_product_address(&ProductNamespace::GS1, "", "00012345600012"),
+            compute_gs1_product_address("00012345600012")
+        );
+    }
+
+    /// Verify that the page number is parsed from property addresses only
+    #[test]
+    fn parse_property_pages() {
+        assert_eq!(
+            parse_property_page(&compute_property_address("record_01", "temperature", 258)),
+            Some(258)
+        );
+        assert_eq!(
+            parse_property_page(&compute_record_address("record_01")),
+            None
+        );
+    }
+
+    /// Verify that addresses outside of Grid's namespaces are not classified
+    #[test]
+    fn classify_unknown_addresses() {
+        assert_eq!(classify_address("00ec01"), None);
+        assert_eq!(classify_address("00ec0100abcdef"), None);
+        assert_eq!(classify_address(""), None);
+    }
+}
@@ -21,7 +21,10 @@ use super::errors::BuilderError;
 
 use crate::protocol::schema::state::PropertyValue;
 use crate::protos;
-use crate::protos::{location_payload, location_payload::LocationPayload_Action};
+use crate::protos::{
+    location_payload,
+    location_payload::{LocationPayload_Action, LocationTransferAction_Phase},
+};
 use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
 };
@@ -68,6 +71,7 @@ pub enum Action {
     LocationCreate(LocationCreateAction),
     LocationUpdate(LocationUpdateAction),
     LocationDelete(LocationDeleteAction),
+    LocationTransfer(LocationTransferAction),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -100,6 +104,9 @@ impl FromProto<protos::location_payload::LocationPayload> for LocationPayload {
             LocationPayload_Action::LOCATION_DELETE => Action::LocationDelete(
                 LocationDeleteAction::from_proto(payload.get_location_delete().clone())?,
             ),
+            LocationPayload_Action::LOCATION_TRANSFER => Action::LocationTransfer(
+                LocationTransferAction::from_proto(payload.get_location_transfer().clone())?,
+            ),
             LocationPayload_Action::UNSET_ACTION => {
                 return Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert LocationPayload_Action with type unset".to_string(),
@@ -132,6 +139,10 @@ impl FromNative<LocationPayload> for protos::location_payload::LocationPayload {
                 proto.set_action(LocationPayload_Action::LOCATION_DELETE);
                 proto.set_location_delete(payload.clone().into_proto()?);
             }
+            Action::LocationTransfer(payload) => {
+                proto.set_action(LocationPayload_Action::LOCATION_TRANSFER);
+                proto.set_location_transfer(payload.clone().into_proto()?);
+            }
         }
 
         Ok(proto)
@@ -227,6 +238,7 @@ pub struct LocationCreateAction {
     location_id: String,
     owner: String,
     properties: Vec<PropertyValue>,
+    parent_location_id: String,
 }
 
 impl LocationCreateAction {
@@ -245,6 +257,12 @@ impl LocationCreateAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+
+    /// The location the new location is contained in, or an empty string for a top-level
+    /// location
+    pub fn parent_location_id(&self) -> &str {
+        &self.parent_location_id
+    }
 }
 
 impl FromProto<location_payload::LocationCreateAction> for LocationCreateAction {
@@ -261,6 +279,7 @@ impl FromProto<location_payload::LocationCreateAction> for LocationCreateAction
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            parent_location_id: proto.get_parent_location_id().to_string(),
         })
     }
 }
@@ -280,6 +299,7 @@ impl FromNative<LocationCreateAction> for location_payload::LocationCreateAction
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_parent_location_id(native.parent_location_id().to_string());
         Ok(proto)
     }
 }
@@ -317,6 +337,7 @@ pub struct LocationCreateActionBuilder {
     location_id: Option<String>,
     owner: Option<String>,
     properties: Option<Vec<PropertyValue>>,
+    parent_location_id: Option<String>,
 }
 
 impl LocationCreateActionBuilder {
@@ -339,6 +360,10 @@ impl LocationCreateActionBuilder {
         self.properties = Some(value);
         self
     }
+    pub fn with_parent_location_id(mut self, value: String) -> Self {
+        self.parent_location_id = Some(value);
+        self
+    }
     pub fn build(self) -> Result<LocationCreateAction, BuilderError> {
         let namespace = self.namespace.ok_or_else(|| {
             BuilderError::MissingField("'namespace' field is required".to_string())
@@ -352,11 +377,13 @@ impl LocationCreateActionBuilder {
         let properties = self
             .properties
             .ok_or_else(|| BuilderError::MissingField("'properties' field is required".into()))?;
+        let parent_location_id = self.parent_location_id.unwrap_or_default();
         Ok(LocationCreateAction {
             namespace,
             location_id,
             owner,
             properties,
+            parent_location_id,
         })
     }
 }
@@ -366,6 +393,8 @@ pub struct LocationUpdateAction {
     namespace: LocationNamespace,
     location_id: String,
     properties: Vec<PropertyValue>,
+    parent_location_id: String,
+    detach_from_parent: bool,
 }
 
 impl LocationUpdateAction {
@@ -380,6 +409,16 @@ impl LocationUpdateAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+
+    /// The location the location is moved into, or an empty string to keep its current parent
+    pub fn parent_location_id(&self) -> &str {
+        &self.parent_location_id
+    }
+
+    /// Whether the location is detached from its current parent
+    pub fn detach_from_parent(&self) -> bool {
+        self.detach_from_parent
+    }
 }
 
 impl FromProto<protos::location_payload::LocationUpdateAction> for LocationUpdateAction {
@@ -395,6 +434,8 @@ impl FromProto<protos::location_payload::LocationUpdateAction> for LocationUpdat
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            parent_location_id: proto.get_parent_location_id().to_string(),
+            detach_from_parent: proto.get_detach_from_parent(),
         })
     }
 }
@@ -413,6 +454,8 @@ impl FromNative<LocationUpdateAction> for protos::location_payload::LocationUpda
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_parent_location_id(native.parent_location_id().to_string());
+        proto.set_detach_from_parent(native.detach_from_parent());
 
         Ok(proto)
     }
@@ -451,6 +494,8 @@ pub struct LocationUpdateActionBuilder {
     namespace: Option<LocationNamespace>,
     location_id: Option<String>,
     properties: Vec<PropertyValue>,
+    parent_location_id: Option<String>,
+    detach_from_parent: bool,
 }
 
 impl LocationUpdateActionBuilder {
@@ -473,6 +518,16 @@ impl LocationUpdateActionBuilder {
         self
     }
 
+    pub fn with_parent_location_id(mut self, parent_location_id: String) -> Self {
+        self.parent_location_id = Some(parent_location_id);
+        self
+    }
+
+    pub fn with_detach_from_parent(mut self, detach_from_parent: bool) -> Self {
+        self.detach_from_parent = detach_from_parent;
+        self
+    }
+
     pub fn build(self) -> Result<LocationUpdateAction, BuilderError> {
         let namespace = self.namespace.ok_or_else(|| {
             BuilderError::MissingField("'namespace' field is required".to_string())
@@ -492,10 +547,14 @@ impl LocationUpdateActionBuilder {
             }
         };
 
+        let parent_location_id = self.parent_location_id.unwrap_or_default();
+
         Ok(LocationUpdateAction {
             namespace,
             location_id,
             properties,
+            parent_location_id,
+            detach_from_parent: self.detach_from_parent,
         })
     }
 }
@@ -601,3 +660,190 @@ impl LocationDeleteActionBuilder {
         })
     }
 }
+
+/// Native implementation for LocationTransferAction_Phase
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransferPhase {
+    /// An admin of the owner offers the location to another organization
+    Offer,
+    /// An admin of the organization the location was offered to takes ownership of it
+    Accept,
+    /// An admin of either organization withdraws the pending offer
+    Cancel,
+}
+
+impl Default for TransferPhase {
+    fn default() -> Self {
+        TransferPhase::Offer
+    }
+}
+
+impl FromProto<LocationTransferAction_Phase> for TransferPhase {
+    fn from_proto(phase: LocationTransferAction_Phase) -> Result<Self, ProtoConversionError> {
+        match phase {
+            LocationTransferAction_Phase::OFFER => Ok(TransferPhase::Offer),
+            LocationTransferAction_Phase::ACCEPT => Ok(TransferPhase::Accept),
+            LocationTransferAction_Phase::CANCEL => Ok(TransferPhase::Cancel),
+            LocationTransferAction_Phase::UNSET_PHASE => {
+                Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert LocationTransferAction_Phase with type UNSET_PHASE".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl FromNative<TransferPhase> for LocationTransferAction_Phase {
+    fn from_native(phase: TransferPhase) -> Result<Self, ProtoConversionError> {
+        match phase {
+            TransferPhase::Offer => Ok(LocationTransferAction_Phase::OFFER),
+            TransferPhase::Accept => Ok(LocationTransferAction_Phase::ACCEPT),
+            TransferPhase::Cancel => Ok(LocationTransferAction_Phase::CANCEL),
+        }
+    }
+}
+
+impl IntoProto<LocationTransferAction_Phase> for TransferPhase {}
+impl IntoNative<TransferPhase> for LocationTransferAction_Phase {}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LocationTransferAction {
+    namespace: LocationNamespace,
+    location_id: String,
+    phase: TransferPhase,
+    new_owner: String,
+}
+
+/// Native implementation for LocationTransferAction
+impl LocationTransferAction {
+    pub fn namespace(&self) -> &LocationNamespace {
+        &self.namespace
+    }
+
+    pub fn location_id(&self) -> &str {
+        &self.location_id
+    }
+
+    pub fn phase(&self) -> &TransferPhase {
+        &self.phase
+    }
+
+    /// The organization the location is offered to; empty unless the phase is `Offer`
+    pub fn new_owner(&self) -> &str {
+        &self.new_owner
+    }
+}
+
+impl FromProto<protos::location_payload::LocationTransferAction> for LocationTransferAction {
+    fn from_proto(
+        proto: protos::location_payload::LocationTransferAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(LocationTransferAction {
+            namespace: LocationNamespace::from_proto(proto.get_namespace())?,
+            location_id: proto.get_location_id().to_string(),
+            phase: TransferPhase::from_proto(proto.get_phase())?,
+            new_owner: proto.get_new_owner().to_string(),
+        })
+    }
+}
+
+impl FromNative<LocationTransferAction> for protos::location_payload::LocationTransferAction {
+    fn from_native(native: LocationTransferAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::location_payload::LocationTransferAction::new();
+        proto.set_namespace(native.namespace().clone().into_proto()?);
+        proto.set_location_id(native.location_id().to_string());
+        proto.set_phase(native.phase().into_proto()?);
+        proto.set_new_owner(native.new_owner().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<LocationTransferAction> for LocationTransferAction {
+    fn from_bytes(bytes: &[u8]) -> Result<LocationTransferAction, ProtoConversionError> {
+        let proto: protos::location_payload::LocationTransferAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get LocationTransferAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for LocationTransferAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from LocationTransferAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::location_payload::LocationTransferAction> for LocationTransferAction {}
+impl IntoNative<LocationTransferAction> for protos::location_payload::LocationTransferAction {}
+
+/// Builder used to create a LocationTransferAction
+#[derive(Default, Clone)]
+pub struct LocationTransferActionBuilder {
+    namespace: Option<LocationNamespace>,
+    location_id: Option<String>,
+    phase: Option<TransferPhase>,
+    new_owner: Option<String>,
+}
+
+impl LocationTransferActionBuilder {
+    pub fn new() -> Self {
+        LocationTransferActionBuilder::default()
+    }
+
+    pub fn with_namespace(mut self, namespace: LocationNamespace) -> Self {
+        self.namespace = Some(namespace);
+        self
+    }
+
+    pub fn with_location_id(mut self, location_id: String) -> Self {
+        self.location_id = Some(location_id);
+        self
+    }
+
+    pub fn with_phase(mut self, phase: TransferPhase) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
+    pub fn with_new_owner(mut self, new_owner: String) -> Self {
+        self.new_owner = Some(new_owner);
+        self
+    }
+
+    pub fn build(self) -> Result<LocationTransferAction, BuilderError> {
+        let namespace = self.namespace.ok_or_else(|| {
+            BuilderError::MissingField("'namespace' field is required".to_string())
+        })?;
+
+        let location_id = self.location_id.ok_or_else(|| {
+            BuilderError::MissingField("'location_id' field is required".to_string())
+        })?;
+
+        let phase = self
+            .phase
+            .ok_or_else(|| BuilderError::MissingField("'phase' field is required".to_string()))?;
+
+        let new_owner = self.new_owner.unwrap_or_default();
+        if phase == TransferPhase::Offer && new_owner.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'new_owner' field is required to offer a location".to_string(),
+            ));
+        }
+
+        Ok(LocationTransferAction {
+            namespace,
+            location_id,
+            phase,
+            new_owner,
+        })
+    }
+}
@@ -68,6 +68,10 @@ pub struct Location {
     namespace: LocationNamespace,
     owner: String,
     properties: Vec<PropertyValue>,
+    pending_owner: String,
+    schema_version: u32,
+    parent_location_id: String,
+    child_location_ids: Vec<String>,
 }
 
 impl Location {
@@ -87,12 +91,37 @@ impl Location {
         &self.properties
     }
 
+    /// The organization a transfer of this location has been offered to, or an empty string if
+    /// no transfer is pending
+    pub fn pending_owner(&self) -> &str {
+        &self.pending_owner
+    }
+
+    /// The version of the schema the location's properties were last validated against
+    pub fn schema_version(&self) -> &u32 {
+        &self.schema_version
+    }
+
+    /// The location this location is contained in, or an empty string for a top-level location
+    pub fn parent_location_id(&self) -> &str {
+        &self.parent_location_id
+    }
+
+    /// The locations directly contained in this location
+    pub fn child_location_ids(&self) -> &[String] {
+        &self.child_location_ids
+    }
+
     pub fn into_builder(self) -> LocationBuilder {
         LocationBuilder::new()
             .with_location_id(self.location_id)
             .with_namespace(self.namespace)
             .with_owner(self.owner)
             .with_properties(self.properties)
+            .with_pending_owner(self.pending_owner)
+            .with_schema_version(self.schema_version)
+            .with_parent_location_id(self.parent_location_id)
+            .with_child_location_ids(self.child_location_ids)
     }
 }
 
@@ -110,6 +139,10 @@ impl FromProto<protos::location_state::Location> for Location {
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            pending_owner: location.get_pending_owner().to_string(),
+            schema_version: location.get_schema_version(),
+            parent_location_id: location.get_parent_location_id().to_string(),
+            child_location_ids: location.get_child_location_ids().to_vec(),
         })
     }
 }
@@ -128,6 +161,12 @@ impl FromNative<Location> for protos::location_state::Location {
                 .map(PropertyValue::into_proto)
                 .collect::<Result<Vec<schema_state::PropertyValue>, ProtoConversionError>>()?,
         ));
+        proto.set_pending_owner(location.pending_owner().to_string());
+        proto.set_schema_version(*location.schema_version());
+        proto.set_parent_location_id(location.parent_location_id().to_string());
+        proto.set_child_location_ids(RepeatedField::from_vec(
+            location.child_location_ids().to_vec(),
+        ));
         Ok(proto)
     }
 }
@@ -185,6 +224,10 @@ pub struct LocationBuilder {
     pub namespace: Option<LocationNamespace>,
     pub owner: Option<String>,
     pub properties: Option<Vec<PropertyValue>>,
+    pub pending_owner: Option<String>,
+    pub schema_version: Option<u32>,
+    pub parent_location_id: Option<String>,
+    pub child_location_ids: Option<Vec<String>>,
 }
 
 impl LocationBuilder {
@@ -212,6 +255,26 @@ impl LocationBuilder {
         self
     }
 
+    pub fn with_pending_owner(mut self, pending_owner: String) -> Self {
+        self.pending_owner = Some(pending_owner);
+        self
+    }
+
+    pub fn with_schema_version(mut self, schema_version: u32) -> Self {
+        self.schema_version = Some(schema_version);
+        self
+    }
+
+    pub fn with_parent_location_id(mut self, parent_location_id: String) -> Self {
+        self.parent_location_id = Some(parent_location_id);
+        self
+    }
+
+    pub fn with_child_location_ids(mut self, child_location_ids: Vec<String>) -> Self {
+        self.child_location_ids = Some(child_location_ids);
+        self
+    }
+
     pub fn build(self) -> Result<Location, LocationBuildError> {
         let location_id = self.location_id.ok_or_else(|| {
             LocationBuildError::MissingField("'location_id' field is required".to_string())
@@ -229,11 +292,20 @@ impl LocationBuilder {
             LocationBuildError::MissingField("'properties' field is required".to_string())
         })?;
 
+        let pending_owner = self.pending_owner.unwrap_or_default();
+        let schema_version = self.schema_version.unwrap_or_default();
+        let parent_location_id = self.parent_location_id.unwrap_or_default();
+        let child_location_ids = self.child_location_ids.unwrap_or_default();
+
         Ok(Location {
             location_id,
             namespace,
             owner,
             properties,
+            pending_owner,
+            schema_version,
+            parent_location_id,
+            child_location_ids,
         })
     }
 }
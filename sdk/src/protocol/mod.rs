@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod addressing;
 pub mod errors;
 pub mod location;
 pub mod pike;
@@ -28,8 +28,14 @@ use crate::protos::{
 pub enum Action {
     CreateAgent,
     UpdateAgent,
+    DeleteAgent,
+    RotateAgentKey,
     CreateOrganization,
     UpdateOrganization,
+    DeleteOrganization,
+    CreateRole,
+    UpdateRole,
+    DeleteRole,
 }
 
 impl FromProto<protos::pike_payload::PikePayload_Action> for Action {
@@ -39,12 +45,22 @@ impl FromProto<protos::pike_payload::PikePayload_Action> for Action {
         match actions {
             protos::pike_payload::PikePayload_Action::CREATE_AGENT => Ok(Action::CreateAgent),
             protos::pike_payload::PikePayload_Action::UPDATE_AGENT => Ok(Action::UpdateAgent),
+            protos::pike_payload::PikePayload_Action::DELETE_AGENT => Ok(Action::DeleteAgent),
+            protos::pike_payload::PikePayload_Action::ROTATE_AGENT_KEY => {
+                Ok(Action::RotateAgentKey)
+            }
             protos::pike_payload::PikePayload_Action::CREATE_ORGANIZATION => {
                 Ok(Action::CreateOrganization)
             }
             protos::pike_payload::PikePayload_Action::UPDATE_ORGANIZATION => {
                 Ok(Action::UpdateOrganization)
             }
+            protos::pike_payload::PikePayload_Action::DELETE_ORGANIZATION => {
+                Ok(Action::DeleteOrganization)
+            }
+            protos::pike_payload::PikePayload_Action::CREATE_ROLE => Ok(Action::CreateRole),
+            protos::pike_payload::PikePayload_Action::UPDATE_ROLE => Ok(Action::UpdateRole),
+            protos::pike_payload::PikePayload_Action::DELETE_ROLE => Ok(Action::DeleteRole),
             protos::pike_payload::PikePayload_Action::ACTION_UNSET => {
                 Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert PikePayload_Action with type unset.".to_string(),
@@ -59,12 +75,22 @@ impl FromNative<Action> for protos::pike_payload::PikePayload_Action {
         match action {
             Action::CreateAgent => Ok(protos::pike_payload::PikePayload_Action::CREATE_AGENT),
             Action::UpdateAgent => Ok(protos::pike_payload::PikePayload_Action::UPDATE_AGENT),
+            Action::DeleteAgent => Ok(protos::pike_payload::PikePayload_Action::DELETE_AGENT),
+            Action::RotateAgentKey => {
+                Ok(protos::pike_payload::PikePayload_Action::ROTATE_AGENT_KEY)
+            }
             Action::CreateOrganization => {
                 Ok(protos::pike_payload::PikePayload_Action::CREATE_ORGANIZATION)
             }
             Action::UpdateOrganization => {
                 Ok(protos::pike_payload::PikePayload_Action::UPDATE_ORGANIZATION)
             }
+            Action::DeleteOrganization => {
+                Ok(protos::pike_payload::PikePayload_Action::DELETE_ORGANIZATION)
+            }
+            Action::CreateRole => Ok(protos::pike_payload::PikePayload_Action::CREATE_ROLE),
+            Action::UpdateRole => Ok(protos::pike_payload::PikePayload_Action::UPDATE_ROLE),
+            Action::DeleteRole => Ok(protos::pike_payload::PikePayload_Action::DELETE_ROLE),
         }
     }
 }
@@ -454,6 +480,287 @@ impl UpdateAgentActionBuilder {
     }
 }
 
+/// Native implementation for DeleteAgentAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DeleteAgentAction {
+    org_id: String,
+    public_key: String,
+}
+
+impl DeleteAgentAction {
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+}
+
+impl FromProto<protos::pike_payload::DeleteAgentAction> for DeleteAgentAction {
+    fn from_proto(
+        delete_agent: protos::pike_payload::DeleteAgentAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(DeleteAgentAction {
+            org_id: delete_agent.get_org_id().to_string(),
+            public_key: delete_agent.get_public_key().to_string(),
+        })
+    }
+}
+
+impl FromNative<DeleteAgentAction> for protos::pike_payload::DeleteAgentAction {
+    fn from_native(delete_agent: DeleteAgentAction) -> Result<Self, ProtoConversionError> {
+        let mut proto_delete_agent = protos::pike_payload::DeleteAgentAction::new();
+
+        proto_delete_agent.set_org_id(delete_agent.org_id().to_string());
+        proto_delete_agent.set_public_key(delete_agent.public_key().to_string());
+
+        Ok(proto_delete_agent)
+    }
+}
+
+impl FromBytes<DeleteAgentAction> for DeleteAgentAction {
+    fn from_bytes(bytes: &[u8]) -> Result<DeleteAgentAction, ProtoConversionError> {
+        let proto: protos::pike_payload::DeleteAgentAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get DeleteAgentAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for DeleteAgentAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from DeleteAgentAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::pike_payload::DeleteAgentAction> for DeleteAgentAction {}
+impl IntoNative<DeleteAgentAction> for protos::pike_payload::DeleteAgentAction {}
+
+#[derive(Debug)]
+pub enum DeleteAgentActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for DeleteAgentActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            DeleteAgentActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            DeleteAgentActionBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for DeleteAgentActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            DeleteAgentActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a DeleteAgentAction
+#[derive(Default, Clone)]
+pub struct DeleteAgentActionBuilder {
+    pub org_id: Option<String>,
+    pub public_key: Option<String>,
+}
+
+impl DeleteAgentActionBuilder {
+    pub fn new() -> Self {
+        DeleteAgentActionBuilder::default()
+    }
+
+    pub fn with_org_id(mut self, org_id: String) -> DeleteAgentActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn with_public_key(mut self, public_key: String) -> DeleteAgentActionBuilder {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    pub fn build(self) -> Result<DeleteAgentAction, DeleteAgentActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            DeleteAgentActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let public_key = self.public_key.ok_or_else(|| {
+            DeleteAgentActionBuildError::MissingField("'public_key' field is required".to_string())
+        })?;
+
+        Ok(DeleteAgentAction { org_id, public_key })
+    }
+}
+
+/// Native implementation for RotateAgentKeyAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RotateAgentKeyAction {
+    org_id: String,
+    public_key: String,
+    new_public_key: String,
+}
+
+impl RotateAgentKeyAction {
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    pub fn new_public_key(&self) -> &str {
+        &self.new_public_key
+    }
+}
+
+impl FromProto<protos::pike_payload::RotateAgentKeyAction> for RotateAgentKeyAction {
+    fn from_proto(
+        rotate_agent_key: protos::pike_payload::RotateAgentKeyAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(RotateAgentKeyAction {
+            org_id: rotate_agent_key.get_org_id().to_string(),
+            public_key: rotate_agent_key.get_public_key().to_string(),
+            new_public_key: rotate_agent_key.get_new_public_key().to_string(),
+        })
+    }
+}
+
+impl FromNative<RotateAgentKeyAction> for protos::pike_payload::RotateAgentKeyAction {
+    fn from_native(rotate_agent_key: RotateAgentKeyAction) -> Result<Self, ProtoConversionError> {
+        let mut proto_rotate_agent_key = protos::pike_payload::RotateAgentKeyAction::new();
+
+        proto_rotate_agent_key.set_org_id(rotate_agent_key.org_id().to_string());
+        proto_rotate_agent_key.set_public_key(rotate_agent_key.public_key().to_string());
+        proto_rotate_agent_key.set_new_public_key(rotate_agent_key.new_public_key().to_string());
+
+        Ok(proto_rotate_agent_key)
+    }
+}
+
+impl FromBytes<RotateAgentKeyAction> for RotateAgentKeyAction {
+    fn from_bytes(bytes: &[u8]) -> Result<RotateAgentKeyAction, ProtoConversionError> {
+        let proto: protos::pike_payload::RotateAgentKeyAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get RotateAgentKeyAction from bytes".to_string(),
+            )
+        })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for RotateAgentKeyAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from RotateAgentKeyAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::pike_payload::RotateAgentKeyAction> for RotateAgentKeyAction {}
+impl IntoNative<RotateAgentKeyAction> for protos::pike_payload::RotateAgentKeyAction {}
+
+#[derive(Debug)]
+pub enum RotateAgentKeyActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for RotateAgentKeyActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            RotateAgentKeyActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            RotateAgentKeyActionBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RotateAgentKeyActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RotateAgentKeyActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a RotateAgentKeyAction
+#[derive(Default, Clone)]
+pub struct RotateAgentKeyActionBuilder {
+    pub org_id: Option<String>,
+    pub public_key: Option<String>,
+    pub new_public_key: Option<String>,
+}
+
+impl RotateAgentKeyActionBuilder {
+    pub fn new() -> Self {
+        RotateAgentKeyActionBuilder::default()
+    }
+
+    pub fn with_org_id(mut self, org_id: String) -> RotateAgentKeyActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn with_public_key(mut self, public_key: String) -> RotateAgentKeyActionBuilder {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    pub fn with_new_public_key(mut self, new_public_key: String) -> RotateAgentKeyActionBuilder {
+        self.new_public_key = Some(new_public_key);
+        self
+    }
+
+    pub fn build(self) -> Result<RotateAgentKeyAction, RotateAgentKeyActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            RotateAgentKeyActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let public_key = self.public_key.ok_or_else(|| {
+            RotateAgentKeyActionBuildError::MissingField(
+                "'public_key' field is required".to_string(),
+            )
+        })?;
+
+        let new_public_key = self.new_public_key.ok_or_else(|| {
+            RotateAgentKeyActionBuildError::MissingField(
+                "'new_public_key' field is required".to_string(),
+            )
+        })?;
+
+        Ok(RotateAgentKeyAction {
+            org_id,
+            public_key,
+            new_public_key,
+        })
+    }
+}
+
 /// Native implementation for CreageOrganizationAction
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct CreateOrganizationAction {
@@ -822,96 +1129,781 @@ impl UpdateOrganizationActionBuilder {
     }
 }
 
-/// Native implementation for PikePayload
-#[derive(Debug, Clone, PartialEq)]
-pub struct PikePayload {
-    action: Action,
-    create_agent: CreateAgentAction,
-    update_agent: UpdateAgentAction,
-    create_organization: CreateOrganizationAction,
-    update_organization: UpdateOrganizationAction,
+/// Native implementation for DeleteOrganizationAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DeleteOrganizationAction {
+    org_id: String,
 }
 
-impl PikePayload {
-    pub fn action(&self) -> &Action {
-        &self.action
-    }
-
-    pub fn create_agent(&self) -> &CreateAgentAction {
-        &self.create_agent
-    }
-
-    pub fn update_agent(&self) -> &UpdateAgentAction {
-        &self.update_agent
-    }
-
-    pub fn create_organization(&self) -> &CreateOrganizationAction {
-        &self.create_organization
-    }
-
-    pub fn update_organization(&self) -> &UpdateOrganizationAction {
-        &self.update_organization
+impl DeleteOrganizationAction {
+    pub fn org_id(&self) -> &str {
+        &self.org_id
     }
 }
 
-impl FromProto<protos::pike_payload::PikePayload> for PikePayload {
+impl FromProto<protos::pike_payload::DeleteOrganizationAction> for DeleteOrganizationAction {
     fn from_proto(
-        payload: protos::pike_payload::PikePayload,
+        delete_org: protos::pike_payload::DeleteOrganizationAction,
     ) -> Result<Self, ProtoConversionError> {
-        Ok(PikePayload {
-            action: Action::from_proto(payload.get_action())?,
-            create_agent: CreateAgentAction::from_proto(payload.get_create_agent().clone())?,
-            update_agent: UpdateAgentAction::from_proto(payload.get_update_agent().clone())?,
-            create_organization: CreateOrganizationAction::from_proto(
-                payload.get_create_organization().clone(),
-            )?,
-            update_organization: UpdateOrganizationAction::from_proto(
-                payload.get_update_organization().clone(),
-            )?,
+        Ok(DeleteOrganizationAction {
+            org_id: delete_org.get_id().to_string(),
         })
     }
 }
 
-impl FromNative<PikePayload> for protos::pike_payload::PikePayload {
-    fn from_native(payload: PikePayload) -> Result<Self, ProtoConversionError> {
-        let mut proto_payload = protos::pike_payload::PikePayload::new();
+impl FromNative<DeleteOrganizationAction> for protos::pike_payload::DeleteOrganizationAction {
+    fn from_native(delete_org: DeleteOrganizationAction) -> Result<Self, ProtoConversionError> {
+        let mut proto_delete_org = protos::pike_payload::DeleteOrganizationAction::new();
 
-        proto_payload.set_action(payload.action().clone().into_proto()?);
-        proto_payload.set_create_agent(payload.create_agent().clone().into_proto()?);
-        proto_payload.set_update_agent(payload.update_agent().clone().into_proto()?);
-        proto_payload.set_create_organization(payload.create_organization().clone().into_proto()?);
-        proto_payload.set_update_organization(payload.update_organization().clone().into_proto()?);
+        proto_delete_org.set_id(delete_org.org_id().to_string());
 
-        Ok(proto_payload)
+        Ok(proto_delete_org)
     }
 }
 
-impl FromBytes<PikePayload> for PikePayload {
-    fn from_bytes(bytes: &[u8]) -> Result<PikePayload, ProtoConversionError> {
-        let proto: protos::pike_payload::PikePayload =
+impl FromBytes<DeleteOrganizationAction> for DeleteOrganizationAction {
+    fn from_bytes(bytes: &[u8]) -> Result<DeleteOrganizationAction, ProtoConversionError> {
+        let proto: protos::pike_payload::DeleteOrganizationAction =
             protobuf::parse_from_bytes(bytes).map_err(|_| {
                 ProtoConversionError::SerializationError(
-                    "Unable to get PikePayload from bytes".to_string(),
+                    "Unable to get DeleteOrganizationAction from bytes".to_string(),
                 )
             })?;
         proto.into_native()
     }
 }
 
-impl IntoBytes for PikePayload {
+impl IntoBytes for DeleteOrganizationAction {
     fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
         let proto = self.into_proto()?;
         let bytes = proto.write_to_bytes().map_err(|_| {
             ProtoConversionError::SerializationError(
-                "Unable to get bytes from PikePayload".to_string(),
+                "Unable to get bytes from DeleteOrganizationAction".to_string(),
             )
         })?;
         Ok(bytes)
     }
 }
 
-impl IntoProto<protos::pike_payload::PikePayload> for PikePayload {}
-impl IntoNative<PikePayload> for protos::pike_payload::PikePayload {}
+impl IntoProto<protos::pike_payload::DeleteOrganizationAction> for DeleteOrganizationAction {}
+impl IntoNative<DeleteOrganizationAction> for protos::pike_payload::DeleteOrganizationAction {}
+
+#[derive(Debug)]
+pub enum DeleteOrganizationActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for DeleteOrganizationActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            DeleteOrganizationActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            DeleteOrganizationActionBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for DeleteOrganizationActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            DeleteOrganizationActionBuildError::MissingField(ref s) => {
+                write!(f, "MissingField: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create a DeleteOrganizationAction
+#[derive(Default, Clone)]
+pub struct DeleteOrganizationActionBuilder {
+    pub org_id: Option<String>,
+}
+
+impl DeleteOrganizationActionBuilder {
+    pub fn new() -> Self {
+        DeleteOrganizationActionBuilder::default()
+    }
+
+    pub fn with_org_id(mut self, org_id: String) -> DeleteOrganizationActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn build(self) -> Result<DeleteOrganizationAction, DeleteOrganizationActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            DeleteOrganizationActionBuildError::MissingField(
+                "'org_id' field is required".to_string(),
+            )
+        })?;
+
+        Ok(DeleteOrganizationAction { org_id })
+    }
+}
+
+/// Native implementation for CreateRoleAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CreateRoleAction {
+    org_id: String,
+    name: String,
+    description: String,
+    permissions: Vec<String>,
+    allowed_organizations: Vec<String>,
+    active: bool,
+}
+
+impl CreateRoleAction {
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    pub fn allowed_organizations(&self) -> &[String] {
+        &self.allowed_organizations
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+}
+
+impl FromProto<protos::pike_payload::CreateRoleAction> for CreateRoleAction {
+    fn from_proto(
+        create_role: protos::pike_payload::CreateRoleAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(CreateRoleAction {
+            org_id: create_role.get_org_id().to_string(),
+            name: create_role.get_name().to_string(),
+            description: create_role.get_description().to_string(),
+            permissions: create_role.get_permissions().to_vec(),
+            allowed_organizations: create_role.get_allowed_organizations().to_vec(),
+            active: create_role.get_active(),
+        })
+    }
+}
+
+impl FromNative<CreateRoleAction> for protos::pike_payload::CreateRoleAction {
+    fn from_native(create_role: CreateRoleAction) -> Result<Self, ProtoConversionError> {
+        let mut proto_create_role = protos::pike_payload::CreateRoleAction::new();
+
+        proto_create_role.set_org_id(create_role.org_id().to_string());
+        proto_create_role.set_name(create_role.name().to_string());
+        proto_create_role.set_description(create_role.description().to_string());
+        proto_create_role
+            .set_permissions(RepeatedField::from_vec(create_role.permissions().to_vec()));
+        proto_create_role.set_allowed_organizations(RepeatedField::from_vec(
+            create_role.allowed_organizations().to_vec(),
+        ));
+        proto_create_role.set_active(create_role.active());
+
+        Ok(proto_create_role)
+    }
+}
+
+impl FromBytes<CreateRoleAction> for CreateRoleAction {
+    fn from_bytes(bytes: &[u8]) -> Result<CreateRoleAction, ProtoConversionError> {
+        let proto: protos::pike_payload::CreateRoleAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get CreateRoleAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for CreateRoleAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from CreateRoleAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::pike_payload::CreateRoleAction> for CreateRoleAction {}
+impl IntoNative<CreateRoleAction> for protos::pike_payload::CreateRoleAction {}
+
+#[derive(Debug)]
+pub enum CreateRoleActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for CreateRoleActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            CreateRoleActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            CreateRoleActionBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for CreateRoleActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            CreateRoleActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a CreateRoleAction
+#[derive(Default, Clone)]
+pub struct CreateRoleActionBuilder {
+    pub org_id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+    pub allowed_organizations: Vec<String>,
+    pub active: Option<bool>,
+}
+
+impl CreateRoleActionBuilder {
+    pub fn new() -> Self {
+        CreateRoleActionBuilder::default()
+    }
+
+    pub fn with_org_id(mut self, org_id: String) -> CreateRoleActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn with_name(mut self, name: String) -> CreateRoleActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn with_description(mut self, description: String) -> CreateRoleActionBuilder {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn with_permissions(mut self, permissions: Vec<String>) -> CreateRoleActionBuilder {
+        self.permissions = permissions;
+        self
+    }
+
+    pub fn with_allowed_organizations(
+        mut self,
+        allowed_organizations: Vec<String>,
+    ) -> CreateRoleActionBuilder {
+        self.allowed_organizations = allowed_organizations;
+        self
+    }
+
+    pub fn with_active(mut self, active: bool) -> CreateRoleActionBuilder {
+        self.active = Some(active);
+        self
+    }
+
+    pub fn build(self) -> Result<CreateRoleAction, CreateRoleActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            CreateRoleActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            CreateRoleActionBuildError::MissingField("'name' field is required".to_string())
+        })?;
+
+        let description = self.description.unwrap_or_default();
+        let permissions = self.permissions;
+        let allowed_organizations = self.allowed_organizations;
+        let active = self.active.unwrap_or(true);
+
+        Ok(CreateRoleAction {
+            org_id,
+            name,
+            description,
+            permissions,
+            allowed_organizations,
+            active,
+        })
+    }
+}
+
+/// Native implementation for UpdateRoleAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UpdateRoleAction {
+    org_id: String,
+    name: String,
+    description: String,
+    permissions: Vec<String>,
+    allowed_organizations: Vec<String>,
+    active: bool,
+}
+
+impl UpdateRoleAction {
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    pub fn allowed_organizations(&self) -> &[String] {
+        &self.allowed_organizations
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+}
+
+impl FromProto<protos::pike_payload::UpdateRoleAction> for UpdateRoleAction {
+    fn from_proto(
+        update_role: protos::pike_payload::UpdateRoleAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(UpdateRoleAction {
+            org_id: update_role.get_org_id().to_string(),
+            name: update_role.get_name().to_string(),
+            description: update_role.get_description().to_string(),
+            permissions: update_role.get_permissions().to_vec(),
+            allowed_organizations: update_role.get_allowed_organizations().to_vec(),
+            active: update_role.get_active(),
+        })
+    }
+}
+
+impl FromNative<UpdateRoleAction> for protos::pike_payload::UpdateRoleAction {
+    fn from_native(update_role: UpdateRoleAction) -> Result<Self, ProtoConversionError> {
+        let mut proto_update_role = protos::pike_payload::UpdateRoleAction::new();
+
+        proto_update_role.set_org_id(update_role.org_id().to_string());
+        proto_update_role.set_name(update_role.name().to_string());
+        proto_update_role.set_description(update_role.description().to_string());
+        proto_update_role
+            .set_permissions(RepeatedField::from_vec(update_role.permissions().to_vec()));
+        proto_update_role.set_allowed_organizations(RepeatedField::from_vec(
+            update_role.allowed_organizations().to_vec(),
+        ));
+        proto_update_role.set_active(update_role.active());
+
+        Ok(proto_update_role)
+    }
+}
+
+impl FromBytes<UpdateRoleAction> for UpdateRoleAction {
+    fn from_bytes(bytes: &[u8]) -> Result<UpdateRoleAction, ProtoConversionError> {
+        let proto: protos::pike_payload::UpdateRoleAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get UpdateRoleAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for UpdateRoleAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from UpdateRoleAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::pike_payload::UpdateRoleAction> for UpdateRoleAction {}
+impl IntoNative<UpdateRoleAction> for protos::pike_payload::UpdateRoleAction {}
+
+#[derive(Debug)]
+pub enum UpdateRoleActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for UpdateRoleActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            UpdateRoleActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            UpdateRoleActionBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for UpdateRoleActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            UpdateRoleActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a UpdateRoleAction
+#[derive(Default, Clone)]
+pub struct UpdateRoleActionBuilder {
+    pub org_id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+    pub allowed_organizations: Vec<String>,
+    pub active: Option<bool>,
+}
+
+impl UpdateRoleActionBuilder {
+    pub fn new() -> Self {
+        UpdateRoleActionBuilder::default()
+    }
+
+    pub fn with_org_id(mut self, org_id: String) -> UpdateRoleActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn with_name(mut self, name: String) -> UpdateRoleActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn with_description(mut self, description: String) -> UpdateRoleActionBuilder {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn with_permissions(mut self, permissions: Vec<String>) -> UpdateRoleActionBuilder {
+        self.permissions = permissions;
+        self
+    }
+
+    pub fn with_allowed_organizations(
+        mut self,
+        allowed_organizations: Vec<String>,
+    ) -> UpdateRoleActionBuilder {
+        self.allowed_organizations = allowed_organizations;
+        self
+    }
+
+    pub fn with_active(mut self, active: bool) -> UpdateRoleActionBuilder {
+        self.active = Some(active);
+        self
+    }
+
+    pub fn build(self) -> Result<UpdateRoleAction, UpdateRoleActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            UpdateRoleActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            UpdateRoleActionBuildError::MissingField("'name' field is required".to_string())
+        })?;
+
+        let description = self.description.unwrap_or_default();
+        let permissions = self.permissions;
+        let allowed_organizations = self.allowed_organizations;
+        let active = self.active.unwrap_or(true);
+
+        Ok(UpdateRoleAction {
+            org_id,
+            name,
+            description,
+            permissions,
+            allowed_organizations,
+            active,
+        })
+    }
+}
+
+/// Native implementation for DeleteRoleAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DeleteRoleAction {
+    org_id: String,
+    name: String,
+}
+
+impl DeleteRoleAction {
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl FromProto<protos::pike_payload::DeleteRoleAction> for DeleteRoleAction {
+    fn from_proto(
+        delete_role: protos::pike_payload::DeleteRoleAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(DeleteRoleAction {
+            org_id: delete_role.get_org_id().to_string(),
+            name: delete_role.get_name().to_string(),
+        })
+    }
+}
+
+impl FromNative<DeleteRoleAction> for protos::pike_payload::DeleteRoleAction {
+    fn from_native(delete_role: DeleteRoleAction) -> Result<Self, ProtoConversionError> {
+        let mut proto_delete_role = protos::pike_payload::DeleteRoleAction::new();
+
+        proto_delete_role.set_org_id(delete_role.org_id().to_string());
+        proto_delete_role.set_name(delete_role.name().to_string());
+
+        Ok(proto_delete_role)
+    }
+}
+
+impl FromBytes<DeleteRoleAction> for DeleteRoleAction {
+    fn from_bytes(bytes: &[u8]) -> Result<DeleteRoleAction, ProtoConversionError> {
+        let proto: protos::pike_payload::DeleteRoleAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get DeleteRoleAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for DeleteRoleAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from DeleteRoleAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::pike_payload::DeleteRoleAction> for DeleteRoleAction {}
+impl IntoNative<DeleteRoleAction> for protos::pike_payload::DeleteRoleAction {}
+
+#[derive(Debug)]
+pub enum DeleteRoleActionBuildError {
+    MissingField(String),
+}
+
+impl StdError for DeleteRoleActionBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            DeleteRoleActionBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            DeleteRoleActionBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for DeleteRoleActionBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            DeleteRoleActionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a DeleteRoleAction
+#[derive(Default, Clone)]
+pub struct DeleteRoleActionBuilder {
+    pub org_id: Option<String>,
+    pub name: Option<String>,
+}
+
+impl DeleteRoleActionBuilder {
+    pub fn new() -> Self {
+        DeleteRoleActionBuilder::default()
+    }
+
+    pub fn with_org_id(mut self, org_id: String) -> DeleteRoleActionBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn with_name(mut self, name: String) -> DeleteRoleActionBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn build(self) -> Result<DeleteRoleAction, DeleteRoleActionBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            DeleteRoleActionBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let name = self.name.ok_or_else(|| {
+            DeleteRoleActionBuildError::MissingField("'name' field is required".to_string())
+        })?;
+
+        Ok(DeleteRoleAction { org_id, name })
+    }
+}
+
+/// Native implementation for PikePayload
+#[derive(Debug, Clone, PartialEq)]
+pub struct PikePayload {
+    action: Action,
+    create_agent: CreateAgentAction,
+    update_agent: UpdateAgentAction,
+    create_organization: CreateOrganizationAction,
+    update_organization: UpdateOrganizationAction,
+    delete_agent: DeleteAgentAction,
+    rotate_agent_key: RotateAgentKeyAction,
+    delete_organization: DeleteOrganizationAction,
+    create_role: CreateRoleAction,
+    update_role: UpdateRoleAction,
+    delete_role: DeleteRoleAction,
+}
+
+impl PikePayload {
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+
+    pub fn create_agent(&self) -> &CreateAgentAction {
+        &self.create_agent
+    }
+
+    pub fn update_agent(&self) -> &UpdateAgentAction {
+        &self.update_agent
+    }
+
+    pub fn create_organization(&self) -> &CreateOrganizationAction {
+        &self.create_organization
+    }
+
+    pub fn update_organization(&self) -> &UpdateOrganizationAction {
+        &self.update_organization
+    }
+
+    pub fn delete_agent(&self) -> &DeleteAgentAction {
+        &self.delete_agent
+    }
+
+    pub fn rotate_agent_key(&self) -> &RotateAgentKeyAction {
+        &self.rotate_agent_key
+    }
+
+    pub fn delete_organization(&self) -> &DeleteOrganizationAction {
+        &self.delete_organization
+    }
+
+    pub fn create_role(&self) -> &CreateRoleAction {
+        &self.create_role
+    }
+
+    pub fn update_role(&self) -> &UpdateRoleAction {
+        &self.update_role
+    }
+
+    pub fn delete_role(&self) -> &DeleteRoleAction {
+        &self.delete_role
+    }
+}
+
+impl FromProto<protos::pike_payload::PikePayload> for PikePayload {
+    fn from_proto(
+        payload: protos::pike_payload::PikePayload,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PikePayload {
+            action: Action::from_proto(payload.get_action())?,
+            create_agent: CreateAgentAction::from_proto(payload.get_create_agent().clone())?,
+            update_agent: UpdateAgentAction::from_proto(payload.get_update_agent().clone())?,
+            create_organization: CreateOrganizationAction::from_proto(
+                payload.get_create_organization().clone(),
+            )?,
+            update_organization: UpdateOrganizationAction::from_proto(
+                payload.get_update_organization().clone(),
+            )?,
+            delete_agent: DeleteAgentAction::from_proto(payload.get_delete_agent().clone())?,
+            rotate_agent_key: RotateAgentKeyAction::from_proto(
+                payload.get_rotate_agent_key().clone(),
+            )?,
+            delete_organization: DeleteOrganizationAction::from_proto(
+                payload.get_delete_organization().clone(),
+            )?,
+            create_role: CreateRoleAction::from_proto(payload.get_create_role().clone())?,
+            update_role: UpdateRoleAction::from_proto(payload.get_update_role().clone())?,
+            delete_role: DeleteRoleAction::from_proto(payload.get_delete_role().clone())?,
+        })
+    }
+}
+
+impl FromNative<PikePayload> for protos::pike_payload::PikePayload {
+    fn from_native(payload: PikePayload) -> Result<Self, ProtoConversionError> {
+        let mut proto_payload = protos::pike_payload::PikePayload::new();
+
+        proto_payload.set_action(payload.action().clone().into_proto()?);
+        proto_payload.set_create_agent(payload.create_agent().clone().into_proto()?);
+        proto_payload.set_update_agent(payload.update_agent().clone().into_proto()?);
+        proto_payload.set_create_organization(payload.create_organization().clone().into_proto()?);
+        proto_payload.set_update_organization(payload.update_organization().clone().into_proto()?);
+        proto_payload.set_delete_agent(payload.delete_agent().clone().into_proto()?);
+        proto_payload.set_rotate_agent_key(payload.rotate_agent_key().clone().into_proto()?);
+        proto_payload.set_delete_organization(payload.delete_organization().clone().into_proto()?);
+        proto_payload.set_create_role(payload.create_role().clone().into_proto()?);
+        proto_payload.set_update_role(payload.update_role().clone().into_proto()?);
+        proto_payload.set_delete_role(payload.delete_role().clone().into_proto()?);
+
+        Ok(proto_payload)
+    }
+}
+
+impl FromBytes<PikePayload> for PikePayload {
+    fn from_bytes(bytes: &[u8]) -> Result<PikePayload, ProtoConversionError> {
+        let proto: protos::pike_payload::PikePayload =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get PikePayload from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for PikePayload {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from PikePayload".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::pike_payload::PikePayload> for PikePayload {}
+impl IntoNative<PikePayload> for protos::pike_payload::PikePayload {}
 
 #[derive(Debug)]
 pub enum PikePayloadBuildError {
@@ -948,6 +1940,12 @@ pub struct PikePayloadBuilder {
     pub update_agent: Option<UpdateAgentAction>,
     pub create_organization: Option<CreateOrganizationAction>,
     pub update_organization: Option<UpdateOrganizationAction>,
+    pub delete_agent: Option<DeleteAgentAction>,
+    pub rotate_agent_key: Option<RotateAgentKeyAction>,
+    pub delete_organization: Option<DeleteOrganizationAction>,
+    pub create_role: Option<CreateRoleAction>,
+    pub update_role: Option<UpdateRoleAction>,
+    pub delete_role: Option<DeleteRoleAction>,
 }
 
 impl PikePayloadBuilder {
@@ -986,6 +1984,42 @@ impl PikePayloadBuilder {
         self
     }
 
+    pub fn with_delete_agent(mut self, delete_agent: DeleteAgentAction) -> PikePayloadBuilder {
+        self.delete_agent = Some(delete_agent);
+        self
+    }
+
+    pub fn with_rotate_agent_key(
+        mut self,
+        rotate_agent_key: RotateAgentKeyAction,
+    ) -> PikePayloadBuilder {
+        self.rotate_agent_key = Some(rotate_agent_key);
+        self
+    }
+
+    pub fn with_delete_organization(
+        mut self,
+        delete_organization: DeleteOrganizationAction,
+    ) -> PikePayloadBuilder {
+        self.delete_organization = Some(delete_organization);
+        self
+    }
+
+    pub fn with_create_role(mut self, create_role: CreateRoleAction) -> PikePayloadBuilder {
+        self.create_role = Some(create_role);
+        self
+    }
+
+    pub fn with_update_role(mut self, update_role: UpdateRoleAction) -> PikePayloadBuilder {
+        self.update_role = Some(update_role);
+        self
+    }
+
+    pub fn with_delete_role(mut self, delete_role: DeleteRoleAction) -> PikePayloadBuilder {
+        self.delete_role = Some(delete_role);
+        self
+    }
+
     pub fn build(self) -> Result<PikePayload, PikePayloadBuildError> {
         let action = self.action.ok_or_else(|| {
             PikePayloadBuildError::MissingField("'action' field is required".to_string())
@@ -1039,12 +2073,90 @@ impl PikePayloadBuilder {
             }
         };
 
+        let delete_agent = {
+            if action == Action::DeleteAgent {
+                self.delete_agent.ok_or_else(|| {
+                    PikePayloadBuildError::MissingField(
+                        "'delete_agent' field is required".to_string(),
+                    )
+                })?
+            } else {
+                DeleteAgentAction::default()
+            }
+        };
+
+        let rotate_agent_key = {
+            if action == Action::RotateAgentKey {
+                self.rotate_agent_key.ok_or_else(|| {
+                    PikePayloadBuildError::MissingField(
+                        "'rotate_agent_key' field is required".to_string(),
+                    )
+                })?
+            } else {
+                RotateAgentKeyAction::default()
+            }
+        };
+
+        let delete_organization = {
+            if action == Action::DeleteOrganization {
+                self.delete_organization.ok_or_else(|| {
+                    PikePayloadBuildError::MissingField(
+                        "'delete_organization' field is required".to_string(),
+                    )
+                })?
+            } else {
+                DeleteOrganizationAction::default()
+            }
+        };
+
+        let create_role = {
+            if action == Action::CreateRole {
+                self.create_role.ok_or_else(|| {
+                    PikePayloadBuildError::MissingField(
+                        "'create_role' field is required".to_string(),
+                    )
+                })?
+            } else {
+                CreateRoleAction::default()
+            }
+        };
+
+        let update_role = {
+            if action == Action::UpdateRole {
+                self.update_role.ok_or_else(|| {
+                    PikePayloadBuildError::MissingField(
+                        "'update_role' field is required".to_string(),
+                    )
+                })?
+            } else {
+                UpdateRoleAction::default()
+            }
+        };
+
+        let delete_role = {
+            if action == Action::DeleteRole {
+                self.delete_role.ok_or_else(|| {
+                    PikePayloadBuildError::MissingField(
+                        "'delete_role' field is required".to_string(),
+                    )
+                })?
+            } else {
+                DeleteRoleAction::default()
+            }
+        };
+
         Ok(PikePayload {
             action,
             create_agent,
             update_agent,
             create_organization,
             update_organization,
+            delete_agent,
+            rotate_agent_key,
+            delete_organization,
+            create_role,
+            update_role,
+            delete_role,
         })
     }
 }
@@ -1376,6 +2488,108 @@ mod tests {
         assert_eq!(payload.update_organization, action);
     }
 
+    #[test]
+    // check that a rotate_agent_key action is built correctly and round trips as bytes
+    fn check_rotate_agent_key_action() {
+        let original = RotateAgentKeyActionBuilder::new()
+            .with_org_id("organization".to_string())
+            .with_public_key("public_key".to_string())
+            .with_new_public_key("new_public_key".to_string())
+            .build()
+            .unwrap();
+
+        assert_eq!(original.org_id(), "organization");
+        assert_eq!(original.public_key(), "public_key");
+        assert_eq!(original.new_public_key(), "new_public_key");
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let rotate = RotateAgentKeyAction::from_bytes(&bytes).unwrap();
+        assert_eq!(rotate, original);
+
+        assert!(RotateAgentKeyActionBuilder::new()
+            .with_org_id("organization".to_string())
+            .with_public_key("public_key".to_string())
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // check that a pike payload with create_role is built correctly and round trips as bytes
+    fn check_pike_create_role_payload() {
+        let action = CreateRoleActionBuilder::new()
+            .with_org_id("organization".to_string())
+            .with_name("shipper".to_string())
+            .with_description("Ships products".to_string())
+            .with_permissions(vec!["can_update_location".to_string()])
+            .with_allowed_organizations(vec!["partner".to_string()])
+            .build()
+            .unwrap();
+
+        assert_eq!(action.org_id(), "organization");
+        assert_eq!(action.name(), "shipper");
+        assert_eq!(action.permissions(), ["can_update_location".to_string()]);
+        assert_eq!(action.allowed_organizations(), ["partner".to_string()]);
+        assert!(action.active());
+
+        let original = PikePayloadBuilder::new()
+            .with_action(Action::CreateRole)
+            .with_create_role(action.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(original.create_role, action);
+        assert_eq!(original.update_role, UpdateRoleAction::default());
+        assert_eq!(original.delete_role, DeleteRoleAction::default());
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let payload = PikePayload::from_bytes(&bytes).unwrap();
+        assert_eq!(payload, original);
+    }
+
+    #[test]
+    // check that delete actions require their action-specific payload
+    fn check_pike_delete_payloads() {
+        let delete_agent = DeleteAgentActionBuilder::new()
+            .with_org_id("organization".to_string())
+            .with_public_key("public_key".to_string())
+            .build()
+            .unwrap();
+
+        let payload = PikePayloadBuilder::new()
+            .with_action(Action::DeleteAgent)
+            .with_delete_agent(delete_agent.clone())
+            .build()
+            .unwrap();
+
+        assert_eq!(payload.action, Action::DeleteAgent);
+        assert_eq!(payload.delete_agent, delete_agent);
+        assert_eq!(
+            payload.delete_organization,
+            DeleteOrganizationAction::default()
+        );
+
+        let delete_organization = DeleteOrganizationActionBuilder::new()
+            .with_org_id("organization".to_string())
+            .build()
+            .unwrap();
+
+        let original = PikePayloadBuilder::new()
+            .with_action(Action::DeleteOrganization)
+            .with_delete_organization(delete_organization.clone())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let payload = PikePayload::from_bytes(&bytes).unwrap();
+        assert_eq!(payload, original);
+        assert_eq!(payload.delete_organization().org_id(), "organization");
+
+        assert!(PikePayloadBuilder::new()
+            .with_action(Action::DeleteAgent)
+            .build()
+            .is_err());
+    }
+
     #[test]
     // check that a pike payload can be converted to bytes and back
     fn check_pike_payload_bytes() {
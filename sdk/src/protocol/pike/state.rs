@@ -468,6 +468,9 @@ pub struct Organization {
     name: String,
     address: String,
     metadata: Vec<KeyValueEntry>,
+    agent_count: u32,
+    agents_counted: bool,
+    roles: Vec<String>,
 }
 
 impl Organization {
@@ -486,6 +489,21 @@ impl Organization {
     pub fn metadata(&self) -> &[KeyValueEntry] {
         &self.metadata
     }
+
+    pub fn agent_count(&self) -> u32 {
+        self.agent_count
+    }
+
+    /// Whether `agent_count` is maintained; false for organizations created before agents were
+    /// counted, which cannot be deleted
+    pub fn agents_counted(&self) -> bool {
+        self.agents_counted
+    }
+
+    /// Names of the roles the organization has defined
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
 }
 
 impl FromProto<protos::pike_state::Organization> for Organization {
@@ -500,6 +518,9 @@ impl FromProto<protos::pike_state::Organization> for Organization {
                 .into_iter()
                 .map(KeyValueEntry::from_proto)
                 .collect::<Result<Vec<KeyValueEntry>, ProtoConversionError>>()?,
+            agent_count: org.get_agent_count(),
+            agents_counted: org.get_agents_counted(),
+            roles: org.get_roles().to_vec(),
         })
     }
 }
@@ -519,6 +540,9 @@ impl FromNative<Organization> for protos::pike_state::Organization {
                 .collect::<Result<Vec<protos::pike_state::KeyValueEntry>, ProtoConversionError>>(
                 )?,
         ));
+        org_proto.set_agent_count(org.agent_count());
+        org_proto.set_agents_counted(org.agents_counted());
+        org_proto.set_roles(RepeatedField::from_vec(org.roles().to_vec()));
 
         Ok(org_proto)
     }
@@ -585,6 +609,9 @@ pub struct OrganizationBuilder {
     pub name: Option<String>,
     pub address: Option<String>,
     pub metadata: Vec<KeyValueEntry>,
+    pub agent_count: u32,
+    pub agents_counted: bool,
+    pub roles: Vec<String>,
 }
 
 impl OrganizationBuilder {
@@ -612,6 +639,21 @@ impl OrganizationBuilder {
         self
     }
 
+    pub fn with_agent_count(mut self, agent_count: u32) -> OrganizationBuilder {
+        self.agent_count = agent_count;
+        self
+    }
+
+    pub fn with_agents_counted(mut self, agents_counted: bool) -> OrganizationBuilder {
+        self.agents_counted = agents_counted;
+        self
+    }
+
+    pub fn with_roles(mut self, roles: Vec<String>) -> OrganizationBuilder {
+        self.roles = roles;
+        self
+    }
+
     pub fn build(self) -> Result<Organization, OrganizationBuildError> {
         let org_id = self.org_id.ok_or_else(|| {
             OrganizationBuildError::MissingField("'org_id' field is required".to_string())
@@ -627,11 +669,20 @@ impl OrganizationBuilder {
 
         let metadata = self.metadata;
 
+        let agent_count = self.agent_count;
+
+        let agents_counted = self.agents_counted;
+
+        let roles = self.roles;
+
         Ok(Organization {
             org_id,
             name,
             address,
             metadata,
+            agent_count,
+            agents_counted,
+            roles,
         })
     }
 }
@@ -768,10 +819,356 @@ impl OrganizationListBuilder {
     }
 }
 
+/// Native implementation of Role
+#[derive(Debug, Clone, PartialEq)]
+pub struct Role {
+    org_id: String,
+    name: String,
+    description: String,
+    permissions: Vec<String>,
+    allowed_organizations: Vec<String>,
+    active: bool,
+}
+
+impl Role {
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn permissions(&self) -> &[String] {
+        &self.permissions
+    }
+
+    pub fn allowed_organizations(&self) -> &[String] {
+        &self.allowed_organizations
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+}
+
+impl FromProto<protos::pike_state::Role> for Role {
+    fn from_proto(role: protos::pike_state::Role) -> Result<Self, ProtoConversionError> {
+        Ok(Role {
+            org_id: role.get_org_id().to_string(),
+            name: role.get_name().to_string(),
+            description: role.get_description().to_string(),
+            permissions: role.get_permissions().to_vec(),
+            allowed_organizations: role.get_allowed_organizations().to_vec(),
+            active: role.get_active(),
+        })
+    }
+}
+
+impl FromNative<Role> for protos::pike_state::Role {
+    fn from_native(role: Role) -> Result<Self, ProtoConversionError> {
+        let mut role_proto = protos::pike_state::Role::new();
+
+        role_proto.set_org_id(role.org_id().to_string());
+        role_proto.set_name(role.name().to_string());
+        role_proto.set_description(role.description().to_string());
+        role_proto.set_permissions(RepeatedField::from_vec(role.permissions().to_vec()));
+        role_proto.set_allowed_organizations(RepeatedField::from_vec(
+            role.allowed_organizations().to_vec(),
+        ));
+        role_proto.set_active(role.active());
+
+        Ok(role_proto)
+    }
+}
+
+impl FromBytes<Role> for Role {
+    fn from_bytes(bytes: &[u8]) -> Result<Role, ProtoConversionError> {
+        let proto: protos::pike_state::Role = protobuf::parse_from_bytes(bytes).map_err(|_| {
+            ProtoConversionError::SerializationError("Unable to get Role from bytes".to_string())
+        })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for Role {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError("Unable to get bytes from Role".to_string())
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::pike_state::Role> for Role {}
+impl IntoNative<Role> for protos::pike_state::Role {}
+
+#[derive(Debug)]
+pub enum RoleBuildError {
+    MissingField(String),
+}
+
+impl StdError for RoleBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            RoleBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            RoleBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RoleBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RoleBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a Role
+#[derive(Default, Clone)]
+pub struct RoleBuilder {
+    pub org_id: Option<String>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub permissions: Vec<String>,
+    pub allowed_organizations: Vec<String>,
+    pub active: Option<bool>,
+}
+
+impl RoleBuilder {
+    pub fn new() -> Self {
+        RoleBuilder::default()
+    }
+
+    pub fn with_org_id(mut self, org_id: String) -> RoleBuilder {
+        self.org_id = Some(org_id);
+        self
+    }
+
+    pub fn with_name(mut self, name: String) -> RoleBuilder {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn with_description(mut self, description: String) -> RoleBuilder {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn with_permissions(mut self, permissions: Vec<String>) -> RoleBuilder {
+        self.permissions = permissions;
+        self
+    }
+
+    pub fn with_allowed_organizations(mut self, allowed_organizations: Vec<String>) -> RoleBuilder {
+        self.allowed_organizations = allowed_organizations;
+        self
+    }
+
+    pub fn with_active(mut self, active: bool) -> RoleBuilder {
+        self.active = Some(active);
+        self
+    }
+
+    pub fn build(self) -> Result<Role, RoleBuildError> {
+        let org_id = self.org_id.ok_or_else(|| {
+            RoleBuildError::MissingField("'org_id' field is required".to_string())
+        })?;
+
+        let name = self
+            .name
+            .ok_or_else(|| RoleBuildError::MissingField("'name' field is required".to_string()))?;
+
+        let description = self.description.unwrap_or_default();
+        let permissions = self.permissions;
+        let allowed_organizations = self.allowed_organizations;
+        let active = self.active.unwrap_or(true);
+
+        Ok(Role {
+            org_id,
+            name,
+            description,
+            permissions,
+            allowed_organizations,
+            active,
+        })
+    }
+}
+
+/// Native implementation of RoleList
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleList {
+    roles: Vec<Role>,
+}
+
+impl RoleList {
+    pub fn roles(&self) -> &[Role] {
+        &self.roles
+    }
+}
+
+impl FromProto<protos::pike_state::RoleList> for RoleList {
+    fn from_proto(role_list: protos::pike_state::RoleList) -> Result<Self, ProtoConversionError> {
+        Ok(RoleList {
+            roles: role_list
+                .get_roles()
+                .to_vec()
+                .into_iter()
+                .map(Role::from_proto)
+                .collect::<Result<Vec<Role>, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl FromNative<RoleList> for protos::pike_state::RoleList {
+    fn from_native(role_list: RoleList) -> Result<Self, ProtoConversionError> {
+        let mut role_list_proto = protos::pike_state::RoleList::new();
+
+        role_list_proto.set_roles(RepeatedField::from_vec(
+            role_list
+                .roles()
+                .to_vec()
+                .into_iter()
+                .map(Role::into_proto)
+                .collect::<Result<Vec<protos::pike_state::Role>, ProtoConversionError>>()?,
+        ));
+
+        Ok(role_list_proto)
+    }
+}
+
+impl FromBytes<RoleList> for RoleList {
+    fn from_bytes(bytes: &[u8]) -> Result<RoleList, ProtoConversionError> {
+        let proto: protos::pike_state::RoleList =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get RoleList from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for RoleList {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from RoleList".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::pike_state::RoleList> for RoleList {}
+impl IntoNative<RoleList> for protos::pike_state::RoleList {}
+
+#[derive(Debug)]
+pub enum RoleListBuildError {
+    MissingField(String),
+}
+
+impl StdError for RoleListBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            RoleListBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            RoleListBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for RoleListBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RoleListBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a RoleList
+#[derive(Default, Clone)]
+pub struct RoleListBuilder {
+    pub roles: Vec<Role>,
+}
+
+impl RoleListBuilder {
+    pub fn new() -> Self {
+        RoleListBuilder::default()
+    }
+
+    pub fn with_roles(mut self, roles: Vec<Role>) -> RoleListBuilder {
+        self.roles = roles;
+        self
+    }
+
+    pub fn build(self) -> Result<RoleList, RoleListBuildError> {
+        let roles = {
+            if self.roles.is_empty() {
+                return Err(RoleListBuildError::MissingField(
+                    "'roles' cannot be empty".to_string(),
+                ));
+            } else {
+                self.roles
+            }
+        };
+
+        Ok(RoleList { roles })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    // check that a Role is built correctly and can be converted to bytes and back
+    fn check_role_builder_and_bytes() {
+        let original = RoleBuilder::new()
+            .with_org_id("organization".to_string())
+            .with_name("shipper".to_string())
+            .with_description("Ships products".to_string())
+            .with_permissions(vec!["can_update_location".to_string()])
+            .with_allowed_organizations(vec!["partner".to_string()])
+            .build()
+            .unwrap();
+
+        assert_eq!(original.org_id(), "organization");
+        assert_eq!(original.name(), "shipper");
+        assert_eq!(original.description(), "Ships products");
+        assert_eq!(original.permissions(), ["can_update_location".to_string()]);
+        assert_eq!(original.allowed_organizations(), ["partner".to_string()]);
+        assert!(original.active());
+
+        let role_list = RoleListBuilder::new()
+            .with_roles(vec![original.clone()])
+            .build()
+            .unwrap();
+        let bytes = role_list.clone().into_bytes().unwrap();
+        assert_eq!(RoleList::from_bytes(&bytes).unwrap(), role_list);
+
+        assert!(RoleBuilder::new()
+            .with_org_id("organization".to_string())
+            .build()
+            .is_err());
+    }
+
     #[test]
     // check that a KeyValueEntry is built correctly
     fn check_key_value_entry_builder() {
@@ -923,6 +1320,9 @@ mod tests {
             .with_name("name".to_string())
             .with_address("address".to_string())
             .with_metadata(vec![key_value.clone()])
+            .with_agent_count(2)
+            .with_agents_counted(true)
+            .with_roles(vec!["auditor".to_string()])
             .build()
             .unwrap();
 
@@ -930,6 +1330,9 @@ mod tests {
         assert_eq!(organization.name(), "name");
         assert_eq!(organization.address(), "address");
         assert_eq!(organization.metadata(), [key_value]);
+        assert_eq!(organization.agent_count(), 2);
+        assert!(organization.agents_counted());
+        assert_eq!(organization.roles(), ["auditor"]);
     }
 
     #[test]
@@ -19,9 +19,15 @@ use std::error::Error as StdError;
 
 use super::errors::BuilderError;
 
-use crate::protocol::{product::state::ProductNamespace, schema::state::PropertyValue};
+use crate::protocol::{
+    product::state::{ProductNamespace, ProductStatus},
+    schema::state::PropertyValue,
+};
 use crate::protos;
-use crate::protos::{product_payload, product_payload::ProductPayload_Action};
+use crate::protos::{
+    product_payload,
+    product_payload::{ProductPayload_Action, ProductTransferAction_Phase},
+};
 use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
 };
@@ -32,6 +38,8 @@ pub enum Action {
     ProductCreate(ProductCreateAction),
     ProductUpdate(ProductUpdateAction),
     ProductDelete(ProductDeleteAction),
+    ProductTransfer(ProductTransferAction),
+    ProductSetStatus(ProductSetStatusAction),
 }
 
 // Rust native implementation for ProductPayload
@@ -64,6 +72,12 @@ impl FromProto<protos::product_payload::ProductPayload> for ProductPayload {
             ProductPayload_Action::PRODUCT_DELETE => Action::ProductDelete(
                 ProductDeleteAction::from_proto(payload.get_product_delete().clone())?,
             ),
+            ProductPayload_Action::PRODUCT_TRANSFER => Action::ProductTransfer(
+                ProductTransferAction::from_proto(payload.get_product_transfer().clone())?,
+            ),
+            ProductPayload_Action::PRODUCT_SET_STATUS => Action::ProductSetStatus(
+                ProductSetStatusAction::from_proto(payload.get_product_set_status().clone())?,
+            ),
             ProductPayload_Action::UNSET_ACTION => {
                 return Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert ProductPayload_Action with type unset".to_string(),
@@ -96,6 +110,14 @@ impl FromNative<ProductPayload> for protos::product_payload::ProductPayload {
                 proto.set_action(ProductPayload_Action::PRODUCT_DELETE);
                 proto.set_product_delete(payload.clone().into_proto()?);
             }
+            Action::ProductTransfer(payload) => {
+                proto.set_action(ProductPayload_Action::PRODUCT_TRANSFER);
+                proto.set_product_transfer(payload.clone().into_proto()?);
+            }
+            Action::ProductSetStatus(payload) => {
+                proto.set_action(ProductPayload_Action::PRODUCT_SET_STATUS);
+                proto.set_product_set_status(payload.clone().into_proto()?);
+            }
         }
 
         Ok(proto)
@@ -193,6 +215,8 @@ pub struct ProductCreateAction {
     product_id: String,
     owner: String,
     properties: Vec<PropertyValue>,
+    schema_name: String,
+    status: ProductStatus,
 }
 
 impl ProductCreateAction {
@@ -211,6 +235,16 @@ impl ProductCreateAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+
+    /// The schema of a custom product; empty for GS1 products
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    /// The status the product starts in
+    pub fn status(&self) -> &ProductStatus {
+        &self.status
+    }
 }
 
 impl FromProto<product_payload::ProductCreateAction> for ProductCreateAction {
@@ -227,6 +261,8 @@ impl FromProto<product_payload::ProductCreateAction> for ProductCreateAction {
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            schema_name: proto.get_schema_name().to_string(),
+            status: ProductStatus::from_proto(proto.get_status())?,
         })
     }
 }
@@ -246,6 +282,8 @@ impl FromNative<ProductCreateAction> for product_payload::ProductCreateAction {
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_schema_name(native.schema_name().to_string());
+        proto.set_status(native.status().clone().into_proto()?);
         Ok(proto)
     }
 }
@@ -283,6 +321,8 @@ pub struct ProductCreateActionBuilder {
     product_id: Option<String>,
     owner: Option<String>,
     properties: Option<Vec<PropertyValue>>,
+    schema_name: Option<String>,
+    status: Option<ProductStatus>,
 }
 
 impl ProductCreateActionBuilder {
@@ -305,6 +345,14 @@ impl ProductCreateActionBuilder {
         self.properties = Some(value);
         self
     }
+    pub fn with_schema_name(mut self, value: String) -> Self {
+        self.schema_name = Some(value);
+        self
+    }
+    pub fn with_status(mut self, value: ProductStatus) -> Self {
+        self.status = Some(value);
+        self
+    }
     pub fn build(self) -> Result<ProductCreateAction, BuilderError> {
         let product_namespace = self.product_namespace.ok_or_else(|| {
             BuilderError::MissingField("'product_namespace' field is required".to_string())
@@ -318,11 +366,21 @@ impl ProductCreateActionBuilder {
         let properties = self
             .properties
             .ok_or_else(|| BuilderError::MissingField("'properties' field is required".into()))?;
+
+        let schema_name = self.schema_name.unwrap_or_default();
+        if product_namespace == ProductNamespace::Custom && schema_name.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'schema_name' field is required for custom products".to_string(),
+            ));
+        }
+        let status = self.status.unwrap_or_default();
         Ok(ProductCreateAction {
             product_namespace,
             product_id,
             owner,
             properties,
+            schema_name,
+            status,
         })
     }
 }
@@ -332,6 +390,7 @@ pub struct ProductUpdateAction {
     product_namespace: ProductNamespace,
     product_id: String,
     properties: Vec<PropertyValue>,
+    schema_name: String,
 }
 
 /// Native implementation for ProductUpdateAction
@@ -347,6 +406,11 @@ impl ProductUpdateAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+
+    /// The schema of a custom product; empty for GS1 products
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
 }
 
 impl FromProto<protos::product_payload::ProductUpdateAction> for ProductUpdateAction {
@@ -362,6 +426,7 @@ impl FromProto<protos::product_payload::ProductUpdateAction> for ProductUpdateAc
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            schema_name: proto.get_schema_name().to_string(),
         })
     }
 }
@@ -380,6 +445,7 @@ impl FromNative<ProductUpdateAction> for protos::product_payload::ProductUpdateA
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_schema_name(native.schema_name().to_string());
 
         Ok(proto)
     }
@@ -418,6 +484,7 @@ pub struct ProductUpdateActionBuilder {
     product_namespace: Option<ProductNamespace>,
     product_id: Option<String>,
     properties: Vec<PropertyValue>,
+    schema_name: Option<String>,
 }
 
 impl ProductUpdateActionBuilder {
@@ -440,6 +507,11 @@ impl ProductUpdateActionBuilder {
         self
     }
 
+    pub fn with_schema_name(mut self, schema_name: String) -> Self {
+        self.schema_name = Some(schema_name);
+        self
+    }
+
     pub fn build(self) -> Result<ProductUpdateAction, BuilderError> {
         let product_namespace = self.product_namespace.ok_or_else(|| {
             BuilderError::MissingField("'product_namespace' field is required".to_string())
@@ -459,10 +531,18 @@ impl ProductUpdateActionBuilder {
             }
         };
 
+        let schema_name = self.schema_name.unwrap_or_default();
+        if product_namespace == ProductNamespace::Custom && schema_name.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'schema_name' field is required for custom products".to_string(),
+            ));
+        }
+
         Ok(ProductUpdateAction {
             product_namespace,
             product_id,
             properties,
+            schema_name,
         })
     }
 }
@@ -471,6 +551,7 @@ impl ProductUpdateActionBuilder {
 pub struct ProductDeleteAction {
     product_namespace: ProductNamespace,
     product_id: String,
+    schema_name: String,
 }
 
 /// Native implementation for ProductDeleteAction
@@ -482,6 +563,11 @@ impl ProductDeleteAction {
     pub fn product_id(&self) -> &str {
         &self.product_id
     }
+
+    /// The schema of a custom product; empty for GS1 products
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
 }
 
 impl FromProto<protos::product_payload::ProductDeleteAction> for ProductDeleteAction {
@@ -491,6 +577,7 @@ impl FromProto<protos::product_payload::ProductDeleteAction> for ProductDeleteAc
         Ok(ProductDeleteAction {
             product_namespace: ProductNamespace::from_proto(proto.get_product_namespace())?,
             product_id: proto.get_product_id().to_string(),
+            schema_name: proto.get_schema_name().to_string(),
         })
     }
 }
@@ -500,6 +587,7 @@ impl FromNative<ProductDeleteAction> for protos::product_payload::ProductDeleteA
         let mut proto = protos::product_payload::ProductDeleteAction::new();
         proto.set_product_namespace(native.product_namespace().clone().into_proto()?);
         proto.set_product_id(native.product_id().to_string());
+        proto.set_schema_name(native.schema_name().to_string());
         Ok(proto)
     }
 }
@@ -536,6 +624,7 @@ impl IntoNative<ProductDeleteAction> for protos::product_payload::ProductDeleteA
 pub struct ProductDeleteActionBuilder {
     product_namespace: Option<ProductNamespace>,
     product_id: Option<String>,
+    schema_name: Option<String>,
 }
 
 impl ProductDeleteActionBuilder {
@@ -553,6 +642,11 @@ impl ProductDeleteActionBuilder {
         self
     }
 
+    pub fn with_schema_name(mut self, schema_name: String) -> Self {
+        self.schema_name = Some(schema_name);
+        self
+    }
+
     pub fn build(self) -> Result<ProductDeleteAction, BuilderError> {
         let product_namespace = self.product_namespace.ok_or_else(|| {
             BuilderError::MissingField("'product_namespace' field is required".to_string())
@@ -562,9 +656,369 @@ impl ProductDeleteActionBuilder {
             BuilderError::MissingField("'product_id' field is required".to_string())
         })?;
 
+        let schema_name = self.schema_name.unwrap_or_default();
+        if product_namespace == ProductNamespace::Custom && schema_name.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'schema_name' field is required for custom products".to_string(),
+            ));
+        }
+
         Ok(ProductDeleteAction {
             product_namespace,
             product_id,
+            schema_name,
+        })
+    }
+}
+
+/// Native implementation for ProductTransferAction_Phase
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TransferPhase {
+    /// An admin of the owner offers the product to another organization
+    Offer,
+    /// An admin of the organization the product was offered to takes ownership of it
+    Accept,
+    /// An admin of either organization withdraws the pending offer
+    Cancel,
+}
+
+impl Default for TransferPhase {
+    fn default() -> Self {
+        TransferPhase::Offer
+    }
+}
+
+impl FromProto<ProductTransferAction_Phase> for TransferPhase {
+    fn from_proto(phase: ProductTransferAction_Phase) -> Result<Self, ProtoConversionError> {
+        match phase {
+            ProductTransferAction_Phase::OFFER => Ok(TransferPhase::Offer),
+            ProductTransferAction_Phase::ACCEPT => Ok(TransferPhase::Accept),
+            ProductTransferAction_Phase::CANCEL => Ok(TransferPhase::Cancel),
+            ProductTransferAction_Phase::UNSET_PHASE => {
+                Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert ProductTransferAction_Phase with type UNSET_PHASE".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl FromNative<TransferPhase> for ProductTransferAction_Phase {
+    fn from_native(phase: TransferPhase) -> Result<Self, ProtoConversionError> {
+        match phase {
+            TransferPhase::Offer => Ok(ProductTransferAction_Phase::OFFER),
+            TransferPhase::Accept => Ok(ProductTransferAction_Phase::ACCEPT),
+            TransferPhase::Cancel => Ok(ProductTransferAction_Phase::CANCEL),
+        }
+    }
+}
+
+impl IntoProto<ProductTransferAction_Phase> for TransferPhase {}
+impl IntoNative<TransferPhase> for ProductTransferAction_Phase {}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProductTransferAction {
+    product_namespace: ProductNamespace,
+    product_id: String,
+    schema_name: String,
+    phase: TransferPhase,
+    new_owner: String,
+}
+
+/// Native implementation for ProductTransferAction
+impl ProductTransferAction {
+    pub fn product_namespace(&self) -> &ProductNamespace {
+        &self.product_namespace
+    }
+
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+
+    /// The schema of a custom product; empty for GS1 products
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    pub fn phase(&self) -> &TransferPhase {
+        &self.phase
+    }
+
+    /// The organization the product is offered to; empty unless the phase is `Offer`
+    pub fn new_owner(&self) -> &str {
+        &self.new_owner
+    }
+}
+
+impl FromProto<protos::product_payload::ProductTransferAction> for ProductTransferAction {
+    fn from_proto(
+        proto: protos::product_payload::ProductTransferAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProductTransferAction {
+            product_namespace: ProductNamespace::from_proto(proto.get_product_namespace())?,
+            product_id: proto.get_product_id().to_string(),
+            schema_name: proto.get_schema_name().to_string(),
+            phase: TransferPhase::from_proto(proto.get_phase())?,
+            new_owner: proto.get_new_owner().to_string(),
+        })
+    }
+}
+
+impl FromNative<ProductTransferAction> for protos::product_payload::ProductTransferAction {
+    fn from_native(native: ProductTransferAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::product_payload::ProductTransferAction::new();
+        proto.set_product_namespace(native.product_namespace().clone().into_proto()?);
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_schema_name(native.schema_name().to_string());
+        proto.set_phase(native.phase().into_proto()?);
+        proto.set_new_owner(native.new_owner().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ProductTransferAction> for ProductTransferAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ProductTransferAction, ProtoConversionError> {
+        let proto: protos::product_payload::ProductTransferAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ProductTransferAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ProductTransferAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ProductTransferAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::product_payload::ProductTransferAction> for ProductTransferAction {}
+impl IntoNative<ProductTransferAction> for protos::product_payload::ProductTransferAction {}
+
+/// Builder used to create a ProductTransferAction
+#[derive(Default, Clone)]
+pub struct ProductTransferActionBuilder {
+    product_namespace: Option<ProductNamespace>,
+    product_id: Option<String>,
+    schema_name: Option<String>,
+    phase: Option<TransferPhase>,
+    new_owner: Option<String>,
+}
+
+impl ProductTransferActionBuilder {
+    pub fn new() -> Self {
+        ProductTransferActionBuilder::default()
+    }
+
+    pub fn with_product_namespace(mut self, product_namespace: ProductNamespace) -> Self {
+        self.product_namespace = Some(product_namespace);
+        self
+    }
+
+    pub fn with_product_id(mut self, product_id: String) -> Self {
+        self.product_id = Some(product_id);
+        self
+    }
+
+    pub fn with_schema_name(mut self, schema_name: String) -> Self {
+        self.schema_name = Some(schema_name);
+        self
+    }
+
+    pub fn with_phase(mut self, phase: TransferPhase) -> Self {
+        self.phase = Some(phase);
+        self
+    }
+
+    pub fn with_new_owner(mut self, new_owner: String) -> Self {
+        self.new_owner = Some(new_owner);
+        self
+    }
+
+    pub fn build(self) -> Result<ProductTransferAction, BuilderError> {
+        let product_namespace = self.product_namespace.ok_or_else(|| {
+            BuilderError::MissingField("'product_namespace' field is required".to_string())
+        })?;
+
+        let product_id = self.product_id.ok_or_else(|| {
+            BuilderError::MissingField("'product_id' field is required".to_string())
+        })?;
+
+        let schema_name = self.schema_name.unwrap_or_default();
+        if product_namespace == ProductNamespace::Custom && schema_name.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'schema_name' field is required for custom products".to_string(),
+            ));
+        }
+
+        let phase = self
+            .phase
+            .ok_or_else(|| BuilderError::MissingField("'phase' field is required".to_string()))?;
+
+        let new_owner = self.new_owner.unwrap_or_default();
+        if phase == TransferPhase::Offer && new_owner.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'new_owner' field is required to offer a product".to_string(),
+            ));
+        }
+
+        Ok(ProductTransferAction {
+            product_namespace,
+            product_id,
+            schema_name,
+            phase,
+            new_owner,
+        })
+    }
+}
+
+/// Native implementation for ProductSetStatusAction
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProductSetStatusAction {
+    product_namespace: ProductNamespace,
+    product_id: String,
+    schema_name: String,
+    status: ProductStatus,
+}
+
+impl ProductSetStatusAction {
+    pub fn product_namespace(&self) -> &ProductNamespace {
+        &self.product_namespace
+    }
+
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+
+    /// The schema of a custom product; empty for GS1 products
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    /// The status the product moves to
+    pub fn status(&self) -> &ProductStatus {
+        &self.status
+    }
+}
+
+impl FromProto<protos::product_payload::ProductSetStatusAction> for ProductSetStatusAction {
+    fn from_proto(
+        proto: protos::product_payload::ProductSetStatusAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ProductSetStatusAction {
+            product_namespace: ProductNamespace::from_proto(proto.get_product_namespace())?,
+            product_id: proto.get_product_id().to_string(),
+            schema_name: proto.get_schema_name().to_string(),
+            status: ProductStatus::from_proto(proto.get_status())?,
+        })
+    }
+}
+
+impl FromNative<ProductSetStatusAction> for protos::product_payload::ProductSetStatusAction {
+    fn from_native(native: ProductSetStatusAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = protos::product_payload::ProductSetStatusAction::new();
+        proto.set_product_namespace(native.product_namespace().clone().into_proto()?);
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_schema_name(native.schema_name().to_string());
+        proto.set_status(native.status().clone().into_proto()?);
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ProductSetStatusAction> for ProductSetStatusAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ProductSetStatusAction, ProtoConversionError> {
+        let proto: protos::product_payload::ProductSetStatusAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ProductSetStatusAction from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for ProductSetStatusAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from ProductSetStatusAction".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::product_payload::ProductSetStatusAction> for ProductSetStatusAction {}
+impl IntoNative<ProductSetStatusAction> for protos::product_payload::ProductSetStatusAction {}
+
+/// Builder used to create a ProductSetStatusAction
+#[derive(Default, Clone)]
+pub struct ProductSetStatusActionBuilder {
+    product_namespace: Option<ProductNamespace>,
+    product_id: Option<String>,
+    schema_name: Option<String>,
+    status: Option<ProductStatus>,
+}
+
+impl ProductSetStatusActionBuilder {
+    pub fn new() -> Self {
+        ProductSetStatusActionBuilder::default()
+    }
+
+    pub fn with_product_namespace(mut self, product_namespace: ProductNamespace) -> Self {
+        self.product_namespace = Some(product_namespace);
+        self
+    }
+
+    pub fn with_product_id(mut self, product_id: String) -> Self {
+        self.product_id = Some(product_id);
+        self
+    }
+
+    pub fn with_schema_name(mut self, schema_name: String) -> Self {
+        self.schema_name = Some(schema_name);
+        self
+    }
+
+    pub fn with_status(mut self, status: ProductStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn build(self) -> Result<ProductSetStatusAction, BuilderError> {
+        let product_namespace = self.product_namespace.ok_or_else(|| {
+            BuilderError::MissingField("'product_namespace' field is required".to_string())
+        })?;
+
+        let product_id = self.product_id.ok_or_else(|| {
+            BuilderError::MissingField("'product_id' field is required".to_string())
+        })?;
+
+        let schema_name = self.schema_name.unwrap_or_default();
+        if product_namespace == ProductNamespace::Custom && schema_name.is_empty() {
+            return Err(BuilderError::MissingField(
+                "'schema_name' field is required for custom products".to_string(),
+            ));
+        }
+
+        let status = self
+            .status
+            .ok_or_else(|| BuilderError::MissingField("'status' field is required".to_string()))?;
+
+        Ok(ProductSetStatusAction {
+            product_namespace,
+            product_id,
+            schema_name,
+            status,
         })
     }
 }
@@ -675,6 +1129,78 @@ mod tests {
         test_from_bytes(action, ProductDeleteAction::from_bytes);
     }
 
+    #[test]
+    // Test that a product transfer action can be built correctly and that an offer requires the
+    // organization the product is offered to
+    fn test_product_transfer_builder() {
+        let action = ProductTransferActionBuilder::new()
+            .with_product_id("688955434684".into()) // GTIN-12
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_phase(TransferPhase::Offer)
+            .with_new_owner("Walmart".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.product_id(), "688955434684");
+        assert_eq!(*action.product_namespace(), ProductNamespace::GS1);
+        assert_eq!(*action.phase(), TransferPhase::Offer);
+        assert_eq!(action.new_owner(), "Walmart");
+
+        assert!(ProductTransferActionBuilder::new()
+            .with_product_id("688955434684".into())
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_phase(TransferPhase::Offer)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // Test that a product transfer action can be converted to bytes and back
+    fn test_product_transfer_into_bytes() {
+        let action = ProductTransferActionBuilder::new()
+            .with_product_id("688955434684".into()) // GTIN-12
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_phase(TransferPhase::Accept)
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, ProductTransferAction::from_bytes);
+    }
+
+    #[test]
+    // Test that a product set status action can be built correctly
+    fn test_product_set_status_builder() {
+        let action = ProductSetStatusActionBuilder::new()
+            .with_product_id("688955434684".into()) // GTIN-12
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_status(ProductStatus::Recalled)
+            .build()
+            .unwrap();
+
+        assert_eq!(action.product_id(), "688955434684");
+        assert_eq!(*action.product_namespace(), ProductNamespace::GS1);
+        assert_eq!(*action.status(), ProductStatus::Recalled);
+
+        assert!(ProductSetStatusActionBuilder::new()
+            .with_product_id("688955434684".into())
+            .with_product_namespace(ProductNamespace::GS1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    // Test that a product set status action can be converted to bytes and back
+    fn test_product_set_status_into_bytes() {
+        let action = ProductSetStatusActionBuilder::new()
+            .with_product_id("688955434684".into()) // GTIN-12
+            .with_product_namespace(ProductNamespace::GS1)
+            .with_status(ProductStatus::Discontinued)
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, ProductSetStatusAction::from_bytes);
+    }
+
     #[test]
     // Test that a product payload can be built correctly
     fn test_product_payload_builder() {
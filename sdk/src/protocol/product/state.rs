@@ -29,6 +29,9 @@ use crate::protocol::schema::state::PropertyValue;
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProductNamespace {
     GS1,
+    /// Products described by a schema of the owner's choosing, whose identifier format
+    /// validates the product_id
+    Custom,
 }
 
 impl Default for ProductNamespace {
@@ -43,6 +46,7 @@ impl FromProto<protos::product_state::Product_ProductNamespace> for ProductNames
     ) -> Result<Self, ProtoConversionError> {
         match product_namespace {
             protos::product_state::Product_ProductNamespace::GS1 => Ok(ProductNamespace::GS1),
+            protos::product_state::Product_ProductNamespace::CUSTOM => Ok(ProductNamespace::Custom),
             protos::product_state::Product_ProductNamespace::UNSET_TYPE => {
                 Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert Product_ProductNamespace with type UNSET_TYPE".to_string(),
@@ -56,6 +60,7 @@ impl FromNative<ProductNamespace> for protos::product_state::Product_ProductName
     fn from_native(product_namespace: ProductNamespace) -> Result<Self, ProtoConversionError> {
         match product_namespace {
             ProductNamespace::GS1 => Ok(protos::product_state::Product_ProductNamespace::GS1),
+            ProductNamespace::Custom => Ok(protos::product_state::Product_ProductNamespace::CUSTOM),
         }
     }
 }
@@ -63,6 +68,78 @@ impl FromNative<ProductNamespace> for protos::product_state::Product_ProductName
 impl IntoProto<protos::product_state::Product_ProductNamespace> for ProductNamespace {}
 impl IntoNative<ProductNamespace> for protos::product_state::Product_ProductNamespace {}
 
+/// Native implementation of the Product Status enum, the stage of a product's lifecycle
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProductStatus {
+    Draft,
+    Active,
+    Discontinued,
+    Recalled,
+}
+
+impl ProductStatus {
+    /// Whether a product in this status may be moved to `status`. Drafts are activated once,
+    /// active products are discontinued or recalled, discontinued products may be reactivated or
+    /// recalled, and recalled products can only be discontinued.
+    pub fn can_transition_to(&self, status: &ProductStatus) -> bool {
+        match (self, status) {
+            (ProductStatus::Draft, ProductStatus::Active)
+            | (ProductStatus::Active, ProductStatus::Discontinued)
+            | (ProductStatus::Active, ProductStatus::Recalled)
+            | (ProductStatus::Discontinued, ProductStatus::Active)
+            | (ProductStatus::Discontinued, ProductStatus::Recalled)
+            | (ProductStatus::Recalled, ProductStatus::Discontinued) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Default for ProductStatus {
+    fn default() -> Self {
+        ProductStatus::Active
+    }
+}
+
+impl std::fmt::Display for ProductStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ProductStatus::Draft => write!(f, "DRAFT"),
+            ProductStatus::Active => write!(f, "ACTIVE"),
+            ProductStatus::Discontinued => write!(f, "DISCONTINUED"),
+            ProductStatus::Recalled => write!(f, "RECALLED"),
+        }
+    }
+}
+
+impl FromProto<protos::product_state::Product_Status> for ProductStatus {
+    fn from_proto(
+        status: protos::product_state::Product_Status,
+    ) -> Result<Self, ProtoConversionError> {
+        match status {
+            // Products stored before lifecycle states existed are active
+            protos::product_state::Product_Status::UNSET_STATUS => Ok(ProductStatus::Active),
+            protos::product_state::Product_Status::DRAFT => Ok(ProductStatus::Draft),
+            protos::product_state::Product_Status::ACTIVE => Ok(ProductStatus::Active),
+            protos::product_state::Product_Status::DISCONTINUED => Ok(ProductStatus::Discontinued),
+            protos::product_state::Product_Status::RECALLED => Ok(ProductStatus::Recalled),
+        }
+    }
+}
+
+impl FromNative<ProductStatus> for protos::product_state::Product_Status {
+    fn from_native(status: ProductStatus) -> Result<Self, ProtoConversionError> {
+        match status {
+            ProductStatus::Draft => Ok(protos::product_state::Product_Status::DRAFT),
+            ProductStatus::Active => Ok(protos::product_state::Product_Status::ACTIVE),
+            ProductStatus::Discontinued => Ok(protos::product_state::Product_Status::DISCONTINUED),
+            ProductStatus::Recalled => Ok(protos::product_state::Product_Status::RECALLED),
+        }
+    }
+}
+
+impl IntoProto<protos::product_state::Product_Status> for ProductStatus {}
+impl IntoNative<ProductStatus> for protos::product_state::Product_Status {}
+
 /// Native implementation of Product
 #[derive(Debug, Clone, PartialEq)]
 pub struct Product {
@@ -70,6 +147,10 @@ pub struct Product {
     product_namespace: ProductNamespace,
     owner: String,
     properties: Vec<PropertyValue>,
+    schema_name: String,
+    pending_owner: String,
+    schema_version: u32,
+    status: ProductStatus,
 }
 
 impl Product {
@@ -89,12 +170,37 @@ impl Product {
         &self.properties
     }
 
+    /// The schema of a custom product; empty for GS1 products
+    pub fn schema_name(&self) -> &str {
+        &self.schema_name
+    }
+
+    /// The organization a transfer of this product has been offered to, or an empty string if no
+    /// transfer is pending
+    pub fn pending_owner(&self) -> &str {
+        &self.pending_owner
+    }
+
+    /// The version of the schema the product's properties were last validated against
+    pub fn schema_version(&self) -> &u32 {
+        &self.schema_version
+    }
+
+    /// Where the product is in its lifecycle
+    pub fn status(&self) -> &ProductStatus {
+        &self.status
+    }
+
     pub fn into_builder(self) -> ProductBuilder {
         ProductBuilder::new()
             .with_product_id(self.product_id)
             .with_product_namespace(self.product_namespace)
             .with_owner(self.owner)
             .with_properties(self.properties)
+            .with_schema_name(self.schema_name)
+            .with_pending_owner(self.pending_owner)
+            .with_schema_version(self.schema_version)
+            .with_status(self.status)
     }
 }
 
@@ -110,6 +216,10 @@ impl FromProto<protos::product_state::Product> for Product {
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            schema_name: product.get_schema_name().to_string(),
+            pending_owner: product.get_pending_owner().to_string(),
+            schema_version: product.get_schema_version(),
+            status: ProductStatus::from_proto(product.get_status())?,
         })
     }
 }
@@ -128,6 +238,10 @@ impl FromNative<Product> for protos::product_state::Product {
                 .map(PropertyValue::into_proto)
                 .collect::<Result<Vec<schema_state::PropertyValue>, ProtoConversionError>>()?,
         ));
+        proto.set_schema_name(product.schema_name().to_string());
+        proto.set_pending_owner(product.pending_owner().to_string());
+        proto.set_schema_version(*product.schema_version());
+        proto.set_status(product.status().clone().into_proto()?);
         Ok(proto)
     }
 }
@@ -195,6 +309,10 @@ pub struct ProductBuilder {
     pub product_namespace: Option<ProductNamespace>,
     pub owner: Option<String>,
     pub properties: Option<Vec<PropertyValue>>,
+    pub schema_name: Option<String>,
+    pub pending_owner: Option<String>,
+    pub schema_version: Option<u32>,
+    pub status: Option<ProductStatus>,
 }
 
 impl ProductBuilder {
@@ -222,6 +340,26 @@ impl ProductBuilder {
         self
     }
 
+    pub fn with_schema_name(mut self, schema_name: String) -> Self {
+        self.schema_name = Some(schema_name);
+        self
+    }
+
+    pub fn with_pending_owner(mut self, pending_owner: String) -> Self {
+        self.pending_owner = Some(pending_owner);
+        self
+    }
+
+    pub fn with_schema_version(mut self, schema_version: u32) -> Self {
+        self.schema_version = Some(schema_version);
+        self
+    }
+
+    pub fn with_status(mut self, status: ProductStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
     pub fn build(self) -> Result<Product, ProductBuildError> {
         let product_id = self.product_id.ok_or_else(|| {
             ProductBuildError::MissingField("'product_id' field is required".to_string())
@@ -240,11 +378,26 @@ impl ProductBuilder {
             ProductBuildError::MissingField("'properties' field is required".to_string())
         })?;
 
+        let schema_name = self.schema_name.unwrap_or_default();
+        if product_namespace == ProductNamespace::Custom && schema_name.is_empty() {
+            return Err(ProductBuildError::MissingField(
+                "'schema_name' field is required for custom products".to_string(),
+            ));
+        }
+
+        let pending_owner = self.pending_owner.unwrap_or_default();
+        let schema_version = self.schema_version.unwrap_or_default();
+        let status = self.status.unwrap_or_default();
+
         Ok(Product {
             product_id,
             product_namespace,
             owner,
             properties,
+            schema_name,
+            pending_owner,
+            schema_version,
+            status,
         })
     }
 }
@@ -440,6 +593,61 @@ mod tests {
         test_from_bytes(original, Product::from_bytes);
     }
 
+    #[test]
+    // Test that the pending owner of a product offered to another organization survives the
+    // round trip through bytes
+    fn test_product_with_pending_owner_into_bytes() {
+        let original = build_product()
+            .into_builder()
+            .with_pending_owner("Walmart".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(original.pending_owner(), "Walmart");
+        test_from_bytes(original, Product::from_bytes);
+    }
+
+    #[test]
+    // Test that a product's status survives the round trip through bytes and that products
+    // without one are active
+    fn test_product_with_status_into_bytes() {
+        assert_eq!(*build_product().status(), ProductStatus::Active);
+
+        let original = build_product()
+            .into_builder()
+            .with_status(ProductStatus::Recalled)
+            .build()
+            .unwrap();
+
+        assert_eq!(*original.status(), ProductStatus::Recalled);
+        test_from_bytes(original, Product::from_bytes);
+    }
+
+    #[test]
+    // Test the transitions allowed between lifecycle states
+    fn test_product_status_transitions() {
+        assert!(ProductStatus::Draft.can_transition_to(&ProductStatus::Active));
+        assert!(ProductStatus::Active.can_transition_to(&ProductStatus::Recalled));
+        assert!(ProductStatus::Recalled.can_transition_to(&ProductStatus::Discontinued));
+        assert!(!ProductStatus::Recalled.can_transition_to(&ProductStatus::Active));
+        assert!(!ProductStatus::Active.can_transition_to(&ProductStatus::Draft));
+        assert!(!ProductStatus::Active.can_transition_to(&ProductStatus::Active));
+    }
+
+    #[test]
+    // Test that the version of the schema a product was validated against survives the round
+    // trip through bytes
+    fn test_product_with_schema_version_into_bytes() {
+        let original = build_product()
+            .into_builder()
+            .with_schema_version(2)
+            .build()
+            .unwrap();
+
+        assert_eq!(original.schema_version(), &2);
+        test_from_bytes(original, Product::from_bytes);
+    }
+
     #[test]
     // Test that a product list can be built correctly
     fn test_product_list_builder() {
@@ -17,8 +17,9 @@ use protobuf::RepeatedField;
 
 use std::error::Error as StdError;
 
-use crate::protocol::schema::state::PropertyDefinition;
+use crate::protocol::schema::state::{IdentifierFormat, PropertyDefinition};
 use crate::protos;
+use crate::protos::schema_payload::PropertyUpdate_UpdateType;
 use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
 };
@@ -166,6 +167,7 @@ pub struct SchemaCreateAction {
     schema_name: String,
     description: String,
     properties: Vec<PropertyDefinition>,
+    identifier_format: Option<IdentifierFormat>,
 }
 
 impl SchemaCreateAction {
@@ -180,6 +182,10 @@ impl SchemaCreateAction {
     pub fn properties(&self) -> &[PropertyDefinition] {
         &self.properties
     }
+
+    pub fn identifier_format(&self) -> Option<&IdentifierFormat> {
+        self.identifier_format.as_ref()
+    }
 }
 
 impl FromProto<protos::schema_payload::SchemaCreateAction> for SchemaCreateAction {
@@ -195,6 +201,13 @@ impl FromProto<protos::schema_payload::SchemaCreateAction> for SchemaCreateActio
                 .into_iter()
                 .map(PropertyDefinition::from_proto)
                 .collect::<Result<Vec<PropertyDefinition>, ProtoConversionError>>()?,
+            identifier_format: if schema_create.has_identifier_format() {
+                Some(IdentifierFormat::from_proto(
+                    schema_create.get_identifier_format().clone(),
+                )?)
+            } else {
+                None
+            },
         })
     }
 }
@@ -210,6 +223,9 @@ impl FromNative<SchemaCreateAction> for protos::schema_payload::SchemaCreateActi
             schema_create.properties().to_vec().into_iter()
             .map(PropertyDefinition::into_proto)
             .collect::<Result<Vec<protos::schema_state::PropertyDefinition>, ProtoConversionError>>()?,));
+        if let Some(identifier_format) = schema_create.identifier_format().cloned() {
+            proto_schema_create.set_identifier_format(identifier_format.into_proto()?);
+        }
 
         Ok(proto_schema_create)
     }
@@ -275,6 +291,7 @@ pub struct SchemaCreateBuilder {
     schema_name: Option<String>,
     description: Option<String>,
     properties: Vec<PropertyDefinition>,
+    identifier_format: Option<IdentifierFormat>,
 }
 
 impl SchemaCreateBuilder {
@@ -297,6 +314,14 @@ impl SchemaCreateBuilder {
         self
     }
 
+    pub fn with_identifier_format(
+        mut self,
+        identifier_format: IdentifierFormat,
+    ) -> SchemaCreateBuilder {
+        self.identifier_format = Some(identifier_format);
+        self
+    }
+
     pub fn build(self) -> Result<SchemaCreateAction, SchemaCreateBuildError> {
         let schema_name = self.schema_name.ok_or_else(|| {
             SchemaCreateBuildError::MissingField("'schema_name' field is required".to_string())
@@ -318,6 +343,7 @@ impl SchemaCreateBuilder {
             schema_name,
             description,
             properties,
+            identifier_format: self.identifier_format,
         })
     }
 }
@@ -326,6 +352,7 @@ impl SchemaCreateBuilder {
 pub struct SchemaUpdateAction {
     schema_name: String,
     properties: Vec<PropertyDefinition>,
+    property_updates: Vec<PropertyUpdate>,
 }
 
 /// Native implementation for SchemaUpdateAction
@@ -337,6 +364,10 @@ impl SchemaUpdateAction {
     pub fn properties(&self) -> &[PropertyDefinition] {
         &self.properties
     }
+
+    pub fn property_updates(&self) -> &[PropertyUpdate] {
+        &self.property_updates
+    }
 }
 
 impl FromProto<protos::schema_payload::SchemaUpdateAction> for SchemaUpdateAction {
@@ -351,6 +382,12 @@ impl FromProto<protos::schema_payload::SchemaUpdateAction> for SchemaUpdateActio
                 .into_iter()
                 .map(PropertyDefinition::from_proto)
                 .collect::<Result<Vec<PropertyDefinition>, ProtoConversionError>>()?,
+            property_updates: schema_update
+                .get_property_updates()
+                .to_vec()
+                .into_iter()
+                .map(PropertyUpdate::from_proto)
+                .collect::<Result<Vec<PropertyUpdate>, ProtoConversionError>>()?,
         })
     }
 }
@@ -365,6 +402,14 @@ impl FromNative<SchemaUpdateAction> for protos::schema_payload::SchemaUpdateActi
             schema_update.properties().to_vec().into_iter()
             .map(PropertyDefinition::into_proto)
             .collect::<Result<Vec<protos::schema_state::PropertyDefinition>, ProtoConversionError>>()?,));
+        proto_schema_update.set_property_updates(RepeatedField::from_vec(
+            schema_update
+                .property_updates()
+                .to_vec()
+                .into_iter()
+                .map(PropertyUpdate::into_proto)
+                .collect::<Result<Vec<protos::schema_payload::PropertyUpdate>, ProtoConversionError>>()?,
+        ));
 
         Ok(proto_schema_update)
     }
@@ -430,6 +475,7 @@ pub struct SchemaUpdateBuilder {
     schema_name: Option<String>,
     description: Option<String>,
     properties: Vec<PropertyDefinition>,
+    property_updates: Vec<PropertyUpdate>,
 }
 
 impl SchemaUpdateBuilder {
@@ -447,24 +493,248 @@ impl SchemaUpdateBuilder {
         self
     }
 
+    pub fn with_property_updates(
+        mut self,
+        property_updates: Vec<PropertyUpdate>,
+    ) -> SchemaUpdateBuilder {
+        self.property_updates = property_updates;
+        self
+    }
+
     pub fn build(self) -> Result<SchemaUpdateAction, SchemaUpdateBuildError> {
         let schema_name = self.schema_name.ok_or_else(|| {
             SchemaUpdateBuildError::MissingField("'schema field is required".to_string())
         })?;
 
-        let properties = {
-            if !self.properties.is_empty() {
-                self.properties
-            } else {
-                return Err(SchemaUpdateBuildError::MissingField(
-                    "'properties' field is required".to_string(),
-                ));
-            }
-        };
+        if self.properties.is_empty() && self.property_updates.is_empty() {
+            return Err(SchemaUpdateBuildError::MissingField(
+                "'properties' or 'property_updates' field is required".to_string(),
+            ));
+        }
 
         Ok(SchemaUpdateAction {
             schema_name,
-            properties,
+            properties: self.properties,
+            property_updates: self.property_updates,
+        })
+    }
+}
+
+/// Native implementation for PropertyUpdate_UpdateType
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UpdateType {
+    /// Append new options to an enum property
+    AddEnumOptions,
+    /// Mark the property as deprecated, which also makes it optional
+    Deprecate,
+    /// Replace the description of the property
+    SetDescription,
+    /// Make a required property optional
+    RelaxRequired,
+}
+
+impl Default for UpdateType {
+    fn default() -> Self {
+        UpdateType::SetDescription
+    }
+}
+
+impl FromProto<PropertyUpdate_UpdateType> for UpdateType {
+    fn from_proto(update_type: PropertyUpdate_UpdateType) -> Result<Self, ProtoConversionError> {
+        match update_type {
+            PropertyUpdate_UpdateType::ADD_ENUM_OPTIONS => Ok(UpdateType::AddEnumOptions),
+            PropertyUpdate_UpdateType::DEPRECATE => Ok(UpdateType::Deprecate),
+            PropertyUpdate_UpdateType::SET_DESCRIPTION => Ok(UpdateType::SetDescription),
+            PropertyUpdate_UpdateType::RELAX_REQUIRED => Ok(UpdateType::RelaxRequired),
+            PropertyUpdate_UpdateType::UNSET_UPDATE_TYPE => {
+                Err(ProtoConversionError::InvalidTypeError(
+                    "Cannot convert PropertyUpdate_UpdateType with type UNSET_UPDATE_TYPE"
+                        .to_string(),
+                ))
+            }
+        }
+    }
+}
+
+impl FromNative<UpdateType> for PropertyUpdate_UpdateType {
+    fn from_native(update_type: UpdateType) -> Result<Self, ProtoConversionError> {
+        match update_type {
+            UpdateType::AddEnumOptions => Ok(PropertyUpdate_UpdateType::ADD_ENUM_OPTIONS),
+            UpdateType::Deprecate => Ok(PropertyUpdate_UpdateType::DEPRECATE),
+            UpdateType::SetDescription => Ok(PropertyUpdate_UpdateType::SET_DESCRIPTION),
+            UpdateType::RelaxRequired => Ok(PropertyUpdate_UpdateType::RELAX_REQUIRED),
+        }
+    }
+}
+
+impl IntoProto<PropertyUpdate_UpdateType> for UpdateType {}
+impl IntoNative<UpdateType> for PropertyUpdate_UpdateType {}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PropertyUpdate {
+    property_name: String,
+    update_type: UpdateType,
+    enum_options: Vec<String>,
+    description: String,
+}
+
+/// Native implementation for PropertyUpdate
+impl PropertyUpdate {
+    pub fn property_name(&self) -> &str {
+        &self.property_name
+    }
+
+    pub fn update_type(&self) -> &UpdateType {
+        &self.update_type
+    }
+
+    /// The options to append; empty unless the update type is `AddEnumOptions`
+    pub fn enum_options(&self) -> &[String] {
+        &self.enum_options
+    }
+
+    /// The new description; empty unless the update type is `SetDescription`
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl FromProto<protos::schema_payload::PropertyUpdate> for PropertyUpdate {
+    fn from_proto(
+        property_update: protos::schema_payload::PropertyUpdate,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PropertyUpdate {
+            property_name: property_update.get_property_name().to_string(),
+            update_type: UpdateType::from_proto(property_update.get_update_type())?,
+            enum_options: property_update.get_enum_options().to_vec(),
+            description: property_update.get_description().to_string(),
+        })
+    }
+}
+
+impl FromNative<PropertyUpdate> for protos::schema_payload::PropertyUpdate {
+    fn from_native(property_update: PropertyUpdate) -> Result<Self, ProtoConversionError> {
+        let mut proto_property_update = protos::schema_payload::PropertyUpdate::new();
+
+        proto_property_update.set_property_name(property_update.property_name().to_string());
+        proto_property_update.set_update_type(property_update.update_type().into_proto()?);
+        proto_property_update.set_enum_options(RepeatedField::from_vec(
+            property_update.enum_options().to_vec(),
+        ));
+        proto_property_update.set_description(property_update.description().to_string());
+
+        Ok(proto_property_update)
+    }
+}
+
+impl FromBytes<PropertyUpdate> for PropertyUpdate {
+    fn from_bytes(bytes: &[u8]) -> Result<PropertyUpdate, ProtoConversionError> {
+        let proto: protos::schema_payload::PropertyUpdate = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get PropertyUpdate from bytes".to_string(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+
+impl IntoBytes for PropertyUpdate {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get bytes from PropertyUpdate".to_string(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+
+impl IntoProto<protos::schema_payload::PropertyUpdate> for PropertyUpdate {}
+impl IntoNative<PropertyUpdate> for protos::schema_payload::PropertyUpdate {}
+
+#[derive(Debug)]
+pub enum PropertyUpdateBuildError {
+    MissingField(String),
+}
+
+impl StdError for PropertyUpdateBuildError {
+    fn description(&self) -> &str {
+        match *self {
+            PropertyUpdateBuildError::MissingField(ref msg) => msg,
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn StdError> {
+        match *self {
+            PropertyUpdateBuildError::MissingField(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PropertyUpdateBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            PropertyUpdateBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a PropertyUpdate
+#[derive(Default, Clone)]
+pub struct PropertyUpdateBuilder {
+    property_name: Option<String>,
+    update_type: Option<UpdateType>,
+    enum_options: Vec<String>,
+    description: Option<String>,
+}
+
+impl PropertyUpdateBuilder {
+    pub fn new() -> Self {
+        PropertyUpdateBuilder::default()
+    }
+
+    pub fn with_property_name(mut self, property_name: String) -> PropertyUpdateBuilder {
+        self.property_name = Some(property_name);
+        self
+    }
+
+    pub fn with_update_type(mut self, update_type: UpdateType) -> PropertyUpdateBuilder {
+        self.update_type = Some(update_type);
+        self
+    }
+
+    pub fn with_enum_options(mut self, enum_options: Vec<String>) -> PropertyUpdateBuilder {
+        self.enum_options = enum_options;
+        self
+    }
+
+    pub fn with_description(mut self, description: String) -> PropertyUpdateBuilder {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn build(self) -> Result<PropertyUpdate, PropertyUpdateBuildError> {
+        let property_name = self.property_name.ok_or_else(|| {
+            PropertyUpdateBuildError::MissingField("'property_name' field is required".to_string())
+        })?;
+
+        let update_type = self.update_type.ok_or_else(|| {
+            PropertyUpdateBuildError::MissingField("'update_type' field is required".to_string())
+        })?;
+
+        if update_type == UpdateType::AddEnumOptions && self.enum_options.is_empty() {
+            return Err(PropertyUpdateBuildError::MissingField(
+                "'enum_options' field is required to add enum options".to_string(),
+            ));
+        }
+
+        Ok(PropertyUpdate {
+            property_name,
+            update_type,
+            enum_options: self.enum_options,
+            description: self.description.unwrap_or_default(),
         })
     }
 }
@@ -569,6 +839,51 @@ mod tests {
         assert_eq!(update, original);
     }
 
+    #[test]
+    // check that a schema update with only property updates can be converted to bytes and back
+    fn check_schema_update_property_updates_bytes() {
+        let property_update = PropertyUpdateBuilder::new()
+            .with_property_name("TEST".to_string())
+            .with_update_type(UpdateType::AddEnumOptions)
+            .with_enum_options(vec!["Four".to_string()])
+            .build()
+            .unwrap();
+
+        let original = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .with_property_updates(vec![property_update.clone()])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let update = SchemaUpdateAction::from_bytes(&bytes).unwrap();
+        assert!(update.properties().is_empty());
+        assert_eq!(update.property_updates(), &[property_update]);
+        assert_eq!(update, original);
+    }
+
+    #[test]
+    // check that a schema update without properties or property updates is rejected
+    fn check_schema_update_action_empty() {
+        let result = SchemaUpdateBuilder::new()
+            .with_schema_name("TestSchema".to_string())
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    // check that adding enum options requires the options to add
+    fn check_property_update_add_enum_options_missing() {
+        let result = PropertyUpdateBuilder::new()
+            .with_property_name("TEST".to_string())
+            .with_update_type(UpdateType::AddEnumOptions)
+            .build();
+
+        assert!(result.is_err());
+    }
+
     #[test]
     // check that a schema payload with create action is built correctly
     fn check_schema_create_action_payload() {
@@ -17,6 +17,8 @@ use protobuf::RepeatedField;
 
 use std::error::Error as StdError;
 
+use regex::Regex;
+
 use crate::protos;
 use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
@@ -32,6 +34,9 @@ pub enum DataType {
     Enum,
     Struct,
     LatLong,
+    Timestamp,
+    Decimal,
+    List,
 }
 
 impl FromProto<protos::schema_state::PropertyDefinition_DataType> for DataType {
@@ -46,6 +51,9 @@ impl FromProto<protos::schema_state::PropertyDefinition_DataType> for DataType {
             protos::schema_state::PropertyDefinition_DataType::ENUM => Ok(DataType::Enum),
             protos::schema_state::PropertyDefinition_DataType::STRUCT => Ok(DataType::Struct),
             protos::schema_state::PropertyDefinition_DataType::LAT_LONG => Ok(DataType::LatLong),
+            protos::schema_state::PropertyDefinition_DataType::TIMESTAMP => Ok(DataType::Timestamp),
+            protos::schema_state::PropertyDefinition_DataType::DECIMAL => Ok(DataType::Decimal),
+            protos::schema_state::PropertyDefinition_DataType::LIST => Ok(DataType::List),
             protos::schema_state::PropertyDefinition_DataType::UNSET_DATA_TYPE => {
                 Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert PropertyDefinition_DataType with type unset.".to_string(),
@@ -65,6 +73,9 @@ impl FromNative<DataType> for protos::schema_state::PropertyDefinition_DataType
             DataType::Enum => Ok(protos::schema_state::PropertyDefinition_DataType::ENUM),
             DataType::Struct => Ok(protos::schema_state::PropertyDefinition_DataType::STRUCT),
             DataType::LatLong => Ok(protos::schema_state::PropertyDefinition_DataType::LAT_LONG),
+            DataType::Timestamp => Ok(protos::schema_state::PropertyDefinition_DataType::TIMESTAMP),
+            DataType::Decimal => Ok(protos::schema_state::PropertyDefinition_DataType::DECIMAL),
+            DataType::List => Ok(protos::schema_state::PropertyDefinition_DataType::LIST),
         }
     }
 }
@@ -171,6 +182,158 @@ impl LatLongBuilder {
     }
 }
 
+/// Native implementation of Decimal, an exact value of `digits * 10^-scale`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decimal {
+    digits: i64,
+    scale: u32,
+}
+
+impl Decimal {
+    pub fn digits(&self) -> &i64 {
+        &self.digits
+    }
+
+    pub fn scale(&self) -> &u32 {
+        &self.scale
+    }
+}
+
+impl std::fmt::Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let sign = if self.digits < 0 { "-" } else { "" };
+        let digits = format!(
+            "{:0>width$}",
+            i128::from(self.digits).abs(),
+            width = self.scale as usize + 1
+        );
+        let (integer, fraction) = digits.split_at(digits.len() - self.scale as usize);
+        if fraction.is_empty() {
+            write!(f, "{}{}", sign, integer)
+        } else {
+            write!(f, "{}{}.{}", sign, integer, fraction)
+        }
+    }
+}
+
+impl std::str::FromStr for Decimal {
+    type Err = DecimalBuildError;
+
+    fn from_str(decimal: &str) -> Result<Self, Self::Err> {
+        let invalid = || DecimalBuildError::InvalidDecimal(decimal.to_string());
+
+        let trimmed = decimal.trim();
+        let negative = trimmed.starts_with('-');
+        let unsigned = if negative || trimmed.starts_with('+') {
+            &trimmed[1..]
+        } else {
+            trimmed
+        };
+
+        let mut parts = unsigned.splitn(2, '.');
+        let integer = parts.next().unwrap_or("");
+        let fraction = parts.next().unwrap_or("");
+        if (integer.is_empty() && fraction.is_empty())
+            || !integer
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return Err(invalid());
+        }
+
+        let digits = format!("{}{}", integer, fraction)
+            .parse::<i64>()
+            .map_err(|_| invalid())?;
+
+        DecimalBuilder::new()
+            .with_digits(if negative { -digits } else { digits })
+            .with_scale(fraction.len() as u32)
+            .build()
+    }
+}
+
+impl FromProto<protos::schema_state::Decimal> for Decimal {
+    fn from_proto(decimal: protos::schema_state::Decimal) -> Result<Self, ProtoConversionError> {
+        Ok(Decimal {
+            digits: decimal.get_digits(),
+            scale: decimal.get_scale(),
+        })
+    }
+}
+
+impl FromNative<Decimal> for protos::schema_state::Decimal {
+    fn from_native(decimal: Decimal) -> Result<Self, ProtoConversionError> {
+        let mut proto_decimal = protos::schema_state::Decimal::new();
+        proto_decimal.set_digits(*decimal.digits());
+        proto_decimal.set_scale(*decimal.scale());
+        Ok(proto_decimal)
+    }
+}
+
+impl IntoProto<protos::schema_state::Decimal> for Decimal {}
+impl IntoNative<Decimal> for protos::schema_state::Decimal {}
+
+/// The largest scale for which every fraction digit fits in the i64 digits
+const MAX_DECIMAL_SCALE: u32 = 18;
+
+#[derive(Debug, PartialEq)]
+pub enum DecimalBuildError {
+    /// Returned when the scale is larger than the number of digits an i64 can hold.
+    InvalidScale(u32),
+    /// Returned when a string cannot be parsed as a decimal.
+    InvalidDecimal(String),
+}
+
+impl StdError for DecimalBuildError {}
+
+impl std::fmt::Display for DecimalBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            DecimalBuildError::InvalidScale(ref s) => write!(
+                f,
+                "Invalid scale - must be at most {}, but received: {}",
+                MAX_DECIMAL_SCALE, s
+            ),
+            DecimalBuildError::InvalidDecimal(ref s) => write!(f, "Invalid decimal: {}", s),
+        }
+    }
+}
+
+/// Builder used to create a Decimal
+#[derive(Default, Clone, PartialEq)]
+pub struct DecimalBuilder {
+    pub digits: i64,
+    pub scale: u32,
+}
+
+impl DecimalBuilder {
+    pub fn new() -> Self {
+        DecimalBuilder::default()
+    }
+
+    pub fn with_digits(mut self, digits: i64) -> DecimalBuilder {
+        self.digits = digits;
+        self
+    }
+
+    pub fn with_scale(mut self, scale: u32) -> DecimalBuilder {
+        self.scale = scale;
+        self
+    }
+
+    pub fn build(self) -> Result<Decimal, DecimalBuildError> {
+        if self.scale > MAX_DECIMAL_SCALE {
+            return Err(DecimalBuildError::InvalidScale(self.scale));
+        }
+
+        Ok(Decimal {
+            digits: self.digits,
+            scale: self.scale,
+        })
+    }
+}
+
 /// Native implementation of PropertyDefinition
 #[derive(Debug, Clone, PartialEq)]
 pub struct PropertyDefinition {
@@ -181,6 +344,9 @@ pub struct PropertyDefinition {
     number_exponent: i32,
     enum_options: Vec<String>,
     struct_properties: Vec<PropertyDefinition>,
+    deprecated: bool,
+    constraints: Option<PropertyConstraints>,
+    list_data_type: Option<DataType>,
 }
 
 impl PropertyDefinition {
@@ -211,6 +377,46 @@ impl PropertyDefinition {
     pub fn struct_properties(&self) -> &[PropertyDefinition] {
         &self.struct_properties
     }
+
+    pub fn deprecated(&self) -> &bool {
+        &self.deprecated
+    }
+
+    pub fn constraints(&self) -> Option<&PropertyConstraints> {
+        self.constraints.as_ref()
+    }
+
+    pub fn list_data_type(&self) -> Option<&DataType> {
+        self.list_data_type.as_ref()
+    }
+
+    /// Returns the definition each element of a LIST property must follow, or None if the
+    /// property is not a list
+    pub fn list_element_definition(&self) -> Option<PropertyDefinition> {
+        self.list_data_type
+            .as_ref()
+            .map(|list_data_type| PropertyDefinition {
+                data_type: list_data_type.clone(),
+                required: false,
+                list_data_type: None,
+                ..self.clone()
+            })
+    }
+
+    pub fn into_builder(self) -> PropertyDefinitionBuilder {
+        let mut builder = PropertyDefinitionBuilder::new()
+            .with_name(self.name)
+            .with_data_type(self.data_type)
+            .with_required(self.required)
+            .with_description(self.description)
+            .with_number_exponent(self.number_exponent)
+            .with_enum_options(self.enum_options)
+            .with_struct_properties(self.struct_properties)
+            .with_deprecated(self.deprecated);
+        builder.constraints = self.constraints;
+        builder.list_data_type = self.list_data_type;
+        builder
+    }
 }
 
 impl FromProto<protos::schema_state::PropertyDefinition> for PropertyDefinition {
@@ -230,6 +436,18 @@ impl FromProto<protos::schema_state::PropertyDefinition> for PropertyDefinition
                 .into_iter()
                 .map(PropertyDefinition::from_proto)
                 .collect::<Result<Vec<PropertyDefinition>, ProtoConversionError>>()?,
+            deprecated: property_definition.get_deprecated(),
+            constraints: if property_definition.has_constraints() {
+                Some(PropertyConstraints::from_proto(
+                    property_definition.get_constraints().clone(),
+                )?)
+            } else {
+                None
+            },
+            list_data_type: match property_definition.get_list_data_type() {
+                protos::schema_state::PropertyDefinition_DataType::UNSET_DATA_TYPE => None,
+                list_data_type => Some(DataType::from_proto(list_data_type)?),
+            },
         })
     }
 }
@@ -251,6 +469,13 @@ impl FromNative<PropertyDefinition> for protos::schema_state::PropertyDefinition
             property_definition.struct_properties().to_vec().into_iter()
             .map(PropertyDefinition::into_proto)
             .collect::<Result<Vec<protos::schema_state::PropertyDefinition>, ProtoConversionError>>()?,));
+        proto_property_definition.set_deprecated(*property_definition.deprecated());
+        if let Some(constraints) = property_definition.constraints() {
+            proto_property_definition.set_constraints(constraints.clone().into_proto()?);
+        }
+        if let Some(list_data_type) = property_definition.list_data_type() {
+            proto_property_definition.set_list_data_type(list_data_type.clone().into_proto()?);
+        }
         Ok(proto_property_definition)
     }
 }
@@ -286,6 +511,7 @@ impl IntoNative<PropertyDefinition> for protos::schema_state::PropertyDefinition
 pub enum PropertyDefinitionBuildError {
     MissingField(String),
     EmptyVec(String),
+    InvalidField(String),
 }
 
 impl StdError for PropertyDefinitionBuildError {
@@ -293,6 +519,7 @@ impl StdError for PropertyDefinitionBuildError {
         match *self {
             PropertyDefinitionBuildError::MissingField(ref msg) => msg,
             PropertyDefinitionBuildError::EmptyVec(ref msg) => msg,
+            PropertyDefinitionBuildError::InvalidField(ref msg) => msg,
         }
     }
 
@@ -300,6 +527,7 @@ impl StdError for PropertyDefinitionBuildError {
         match *self {
             PropertyDefinitionBuildError::MissingField(_) => None,
             PropertyDefinitionBuildError::EmptyVec(_) => None,
+            PropertyDefinitionBuildError::InvalidField(_) => None,
         }
     }
 }
@@ -309,6 +537,7 @@ impl std::fmt::Display for PropertyDefinitionBuildError {
         match *self {
             PropertyDefinitionBuildError::MissingField(ref s) => write!(f, "MissingField: {}", s),
             PropertyDefinitionBuildError::EmptyVec(ref s) => write!(f, "EmptyVec: {}", s),
+            PropertyDefinitionBuildError::InvalidField(ref s) => write!(f, "InvalidField: {}", s),
         }
     }
 }
@@ -323,6 +552,9 @@ pub struct PropertyDefinitionBuilder {
     pub number_exponent: Option<i32>,
     pub enum_options: Vec<String>,
     pub struct_properties: Vec<PropertyDefinition>,
+    pub deprecated: Option<bool>,
+    pub constraints: Option<PropertyConstraints>,
+    pub list_data_type: Option<DataType>,
 }
 
 impl PropertyDefinitionBuilder {
@@ -368,6 +600,24 @@ impl PropertyDefinitionBuilder {
         self
     }
 
+    pub fn with_deprecated(mut self, deprecated: bool) -> PropertyDefinitionBuilder {
+        self.deprecated = Some(deprecated);
+        self
+    }
+
+    pub fn with_constraints(
+        mut self,
+        constraints: PropertyConstraints,
+    ) -> PropertyDefinitionBuilder {
+        self.constraints = Some(constraints);
+        self
+    }
+
+    pub fn with_list_data_type(mut self, list_data_type: DataType) -> PropertyDefinitionBuilder {
+        self.list_data_type = Some(list_data_type);
+        self
+    }
+
     pub fn build(self) -> Result<PropertyDefinition, PropertyDefinitionBuildError> {
         let name = self.name.ok_or_else(|| {
             PropertyDefinitionBuildError::MissingField("'name' field is required".to_string())
@@ -380,8 +630,31 @@ impl PropertyDefinitionBuilder {
         let required = self.required.unwrap_or_else(|| false);
         let description = self.description.unwrap_or_default();
 
+        let list_data_type = {
+            if data_type == DataType::List {
+                match self.list_data_type {
+                    Some(DataType::List) => {
+                        return Err(PropertyDefinitionBuildError::InvalidField(
+                            "'list_data_type' cannot be List".to_string(),
+                        ))
+                    }
+                    Some(list_data_type) => Some(list_data_type),
+                    None => {
+                        return Err(PropertyDefinitionBuildError::MissingField(
+                            "'list_data_type' field is required".to_string(),
+                        ))
+                    }
+                }
+            } else {
+                None
+            }
+        };
+
+        // The number_exponent, enum_options and struct_properties of a list apply to its elements
+        let element_data_type = list_data_type.as_ref().unwrap_or(&data_type);
+
         let number_exponent = {
-            if data_type == DataType::Number {
+            if element_data_type == &DataType::Number {
                 self.number_exponent.ok_or_else(|| {
                     PropertyDefinitionBuildError::MissingField(
                         "'number_exponent' field is required".to_string(),
@@ -393,7 +666,7 @@ impl PropertyDefinitionBuilder {
         };
 
         let enum_options = {
-            if data_type == DataType::Enum {
+            if element_data_type == &DataType::Enum {
                 if !self.enum_options.is_empty() {
                     self.enum_options
                 } else {
@@ -407,7 +680,7 @@ impl PropertyDefinitionBuilder {
         };
 
         let struct_properties = {
-            if data_type == DataType::Struct {
+            if element_data_type == &DataType::Struct {
                 if !self.struct_properties.is_empty() {
                     self.struct_properties
                 } else {
@@ -428,6 +701,494 @@ impl PropertyDefinitionBuilder {
             number_exponent,
             enum_options,
             struct_properties,
+            deprecated: self.deprecated.unwrap_or_default(),
+            constraints: self.constraints,
+            list_data_type,
+        })
+    }
+}
+
+/// Native implementation of PropertyConstraints
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyConstraints {
+    min_number: Option<i64>,
+    max_number: Option<i64>,
+    min_length: u32,
+    max_length: u32,
+    pattern: String,
+    allowed_values: Vec<String>,
+    max_bytes: u32,
+    min_lat_long: Option<LatLong>,
+    max_lat_long: Option<LatLong>,
+}
+
+impl PropertyConstraints {
+    pub fn min_number(&self) -> Option<&i64> {
+        self.min_number.as_ref()
+    }
+
+    pub fn max_number(&self) -> Option<&i64> {
+        self.max_number.as_ref()
+    }
+
+    pub fn min_length(&self) -> &u32 {
+        &self.min_length
+    }
+
+    pub fn max_length(&self) -> &u32 {
+        &self.max_length
+    }
+
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn allowed_values(&self) -> &[String] {
+        &self.allowed_values
+    }
+
+    pub fn max_bytes(&self) -> &u32 {
+        &self.max_bytes
+    }
+
+    pub fn min_lat_long(&self) -> Option<&LatLong> {
+        self.min_lat_long.as_ref()
+    }
+
+    pub fn max_lat_long(&self) -> Option<&LatLong> {
+        self.max_lat_long.as_ref()
+    }
+
+    pub fn into_builder(self) -> PropertyConstraintsBuilder {
+        PropertyConstraintsBuilder {
+            min_number: self.min_number,
+            max_number: self.max_number,
+            min_length: Some(self.min_length),
+            max_length: Some(self.max_length),
+            pattern: Some(self.pattern),
+            allowed_values: self.allowed_values,
+            max_bytes: Some(self.max_bytes),
+            min_lat_long: self.min_lat_long,
+            max_lat_long: self.max_lat_long,
+        }
+    }
+
+    /// Returns true if the pattern, if any, matches the whole string
+    pub fn matches_pattern(&self, value: &str) -> Result<bool, PropertyConstraintsBuildError> {
+        if self.pattern.is_empty() {
+            return Ok(true);
+        }
+
+        Ok(compile_value_pattern(&self.pattern)?.is_match(value))
+    }
+}
+
+/// Compiles the pattern so that it must match the whole value
+fn compile_value_pattern(pattern: &str) -> Result<Regex, PropertyConstraintsBuildError> {
+    Regex::new(&format!("^(?:{})$", pattern))
+        .map_err(|err| PropertyConstraintsBuildError::InvalidPattern(err.to_string()))
+}
+
+impl FromProto<protos::schema_state::PropertyConstraints> for PropertyConstraints {
+    fn from_proto(
+        constraints: protos::schema_state::PropertyConstraints,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(PropertyConstraints {
+            min_number: if constraints.get_has_min_number() {
+                Some(constraints.get_min_number())
+            } else {
+                None
+            },
+            max_number: if constraints.get_has_max_number() {
+                Some(constraints.get_max_number())
+            } else {
+                None
+            },
+            min_length: constraints.get_min_length(),
+            max_length: constraints.get_max_length(),
+            pattern: constraints.get_pattern().to_string(),
+            allowed_values: constraints.get_allowed_values().to_vec(),
+            max_bytes: constraints.get_max_bytes(),
+            min_lat_long: if constraints.has_min_lat_long() {
+                Some(LatLong::from_proto(constraints.get_min_lat_long().clone())?)
+            } else {
+                None
+            },
+            max_lat_long: if constraints.has_max_lat_long() {
+                Some(LatLong::from_proto(constraints.get_max_lat_long().clone())?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl FromNative<PropertyConstraints> for protos::schema_state::PropertyConstraints {
+    fn from_native(constraints: PropertyConstraints) -> Result<Self, ProtoConversionError> {
+        let mut proto_constraints = protos::schema_state::PropertyConstraints::new();
+        if let Some(min_number) = constraints.min_number() {
+            proto_constraints.set_has_min_number(true);
+            proto_constraints.set_min_number(*min_number);
+        }
+        if let Some(max_number) = constraints.max_number() {
+            proto_constraints.set_has_max_number(true);
+            proto_constraints.set_max_number(*max_number);
+        }
+        proto_constraints.set_min_length(*constraints.min_length());
+        proto_constraints.set_max_length(*constraints.max_length());
+        proto_constraints.set_pattern(constraints.pattern().to_string());
+        proto_constraints.set_allowed_values(RepeatedField::from_vec(
+            constraints.allowed_values().to_vec(),
+        ));
+        proto_constraints.set_max_bytes(*constraints.max_bytes());
+        if let Some(min_lat_long) = constraints.min_lat_long() {
+            proto_constraints.set_min_lat_long(min_lat_long.clone().into_proto()?);
+        }
+        if let Some(max_lat_long) = constraints.max_lat_long() {
+            proto_constraints.set_max_lat_long(max_lat_long.clone().into_proto()?);
+        }
+        Ok(proto_constraints)
+    }
+}
+
+impl IntoProto<protos::schema_state::PropertyConstraints> for PropertyConstraints {}
+impl IntoNative<PropertyConstraints> for protos::schema_state::PropertyConstraints {}
+
+#[derive(Debug, PartialEq)]
+pub enum PropertyConstraintsBuildError {
+    /// Returned when the pattern is not a valid regular expression.
+    InvalidPattern(String),
+    /// Returned when a lower bound is greater than its upper bound.
+    InvalidRange(String),
+}
+
+impl StdError for PropertyConstraintsBuildError {}
+
+impl std::fmt::Display for PropertyConstraintsBuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            PropertyConstraintsBuildError::InvalidPattern(ref s) => {
+                write!(f, "Invalid property pattern: {}", s)
+            }
+            PropertyConstraintsBuildError::InvalidRange(ref s) => {
+                write!(f, "Invalid property range: {}", s)
+            }
+        }
+    }
+}
+
+/// Builder used to create PropertyConstraints
+#[derive(Default, Clone, PartialEq)]
+pub struct PropertyConstraintsBuilder {
+    pub min_number: Option<i64>,
+    pub max_number: Option<i64>,
+    pub min_length: Option<u32>,
+    pub max_length: Option<u32>,
+    pub pattern: Option<String>,
+    pub allowed_values: Vec<String>,
+    pub max_bytes: Option<u32>,
+    pub min_lat_long: Option<LatLong>,
+    pub max_lat_long: Option<LatLong>,
+}
+
+impl PropertyConstraintsBuilder {
+    pub fn new() -> Self {
+        PropertyConstraintsBuilder::default()
+    }
+
+    pub fn with_min_number(mut self, min_number: i64) -> PropertyConstraintsBuilder {
+        self.min_number = Some(min_number);
+        self
+    }
+
+    pub fn with_max_number(mut self, max_number: i64) -> PropertyConstraintsBuilder {
+        self.max_number = Some(max_number);
+        self
+    }
+
+    pub fn with_min_length(mut self, min_length: u32) -> PropertyConstraintsBuilder {
+        self.min_length = Some(min_length);
+        self
+    }
+
+    pub fn with_max_length(mut self, max_length: u32) -> PropertyConstraintsBuilder {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn with_pattern(mut self, pattern: String) -> PropertyConstraintsBuilder {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    pub fn with_allowed_values(
+        mut self,
+        allowed_values: Vec<String>,
+    ) -> PropertyConstraintsBuilder {
+        self.allowed_values = allowed_values;
+        self
+    }
+
+    pub fn with_max_bytes(mut self, max_bytes: u32) -> PropertyConstraintsBuilder {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn with_min_lat_long(mut self, min_lat_long: LatLong) -> PropertyConstraintsBuilder {
+        self.min_lat_long = Some(min_lat_long);
+        self
+    }
+
+    pub fn with_max_lat_long(mut self, max_lat_long: LatLong) -> PropertyConstraintsBuilder {
+        self.max_lat_long = Some(max_lat_long);
+        self
+    }
+
+    pub fn build(self) -> Result<PropertyConstraints, PropertyConstraintsBuildError> {
+        let min_length = self.min_length.unwrap_or_default();
+        let max_length = self.max_length.unwrap_or_default();
+        let pattern = self.pattern.unwrap_or_default();
+
+        if let (Some(min_number), Some(max_number)) = (self.min_number, self.max_number) {
+            if max_number < min_number {
+                return Err(PropertyConstraintsBuildError::InvalidRange(format!(
+                    "max_number {} is less than min_number {}",
+                    max_number, min_number
+                )));
+            }
+        }
+
+        if max_length != 0 && max_length < min_length {
+            return Err(PropertyConstraintsBuildError::InvalidRange(format!(
+                "max_length {} is less than min_length {}",
+                max_length, min_length
+            )));
+        }
+
+        if let (Some(min_lat_long), Some(max_lat_long)) = (&self.min_lat_long, &self.max_lat_long) {
+            if max_lat_long.latitude() < min_lat_long.latitude()
+                || max_lat_long.longitude() < min_lat_long.longitude()
+            {
+                return Err(PropertyConstraintsBuildError::InvalidRange(
+                    "max_lat_long must be north-east of min_lat_long".to_string(),
+                ));
+            }
+        }
+
+        if !pattern.is_empty() {
+            compile_value_pattern(&pattern)?;
+        }
+
+        Ok(PropertyConstraints {
+            min_number: self.min_number,
+            max_number: self.max_number,
+            min_length,
+            max_length,
+            pattern,
+            allowed_values: self.allowed_values,
+            max_bytes: self.max_bytes.unwrap_or_default(),
+            min_lat_long: self.min_lat_long,
+            max_lat_long: self.max_lat_long,
+        })
+    }
+}
+
+/// Native implementation of IdentifierFormat
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentifierFormat {
+    pattern: String,
+    min_length: u32,
+    max_length: u32,
+}
+
+impl IdentifierFormat {
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn min_length(&self) -> &u32 {
+        &self.min_length
+    }
+
+    pub fn max_length(&self) -> &u32 {
+        &self.max_length
+    }
+
+    /// Checks that the identifier has an allowed length and that the pattern, if any, matches
+    /// the whole identifier
+    pub fn validate(&self, identifier: &str) -> Result<(), IdentifierFormatError> {
+        let length = identifier.chars().count() as u32;
+        if length < self.min_length {
+            return Err(IdentifierFormatError::TooShort {
+                identifier: identifier.to_string(),
+                min_length: self.min_length,
+            });
+        }
+
+        if self.max_length != 0 && length > self.max_length {
+            return Err(IdentifierFormatError::TooLong {
+                identifier: identifier.to_string(),
+                max_length: self.max_length,
+            });
+        }
+
+        if !self.pattern.is_empty() && !compile_pattern(&self.pattern)?.is_match(identifier) {
+            return Err(IdentifierFormatError::PatternMismatch {
+                identifier: identifier.to_string(),
+                pattern: self.pattern.clone(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Compiles the pattern so that it must match the whole identifier
+fn compile_pattern(pattern: &str) -> Result<Regex, IdentifierFormatError> {
+    Regex::new(&format!("^(?:{})$", pattern))
+        .map_err(|err| IdentifierFormatError::InvalidPattern(err.to_string()))
+}
+
+impl FromProto<protos::schema_state::IdentifierFormat> for IdentifierFormat {
+    fn from_proto(
+        identifier_format: protos::schema_state::IdentifierFormat,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(IdentifierFormat {
+            pattern: identifier_format.get_pattern().to_string(),
+            min_length: identifier_format.get_min_length(),
+            max_length: identifier_format.get_max_length(),
+        })
+    }
+}
+
+impl FromNative<IdentifierFormat> for protos::schema_state::IdentifierFormat {
+    fn from_native(identifier_format: IdentifierFormat) -> Result<Self, ProtoConversionError> {
+        let mut proto_identifier_format = protos::schema_state::IdentifierFormat::new();
+        proto_identifier_format.set_pattern(identifier_format.pattern().to_string());
+        proto_identifier_format.set_min_length(*identifier_format.min_length());
+        proto_identifier_format.set_max_length(*identifier_format.max_length());
+        Ok(proto_identifier_format)
+    }
+}
+
+impl IntoProto<protos::schema_state::IdentifierFormat> for IdentifierFormat {}
+impl IntoNative<IdentifierFormat> for protos::schema_state::IdentifierFormat {}
+
+#[derive(Debug, PartialEq)]
+pub enum IdentifierFormatError {
+    /// Returned when the pattern is not a valid regular expression.
+    InvalidPattern(String),
+    /// Returned when the maximum length is less than the minimum length.
+    InvalidLengths {
+        min_length: u32,
+        max_length: u32,
+    },
+    TooShort {
+        identifier: String,
+        min_length: u32,
+    },
+    TooLong {
+        identifier: String,
+        max_length: u32,
+    },
+    PatternMismatch {
+        identifier: String,
+        pattern: String,
+    },
+}
+
+impl StdError for IdentifierFormatError {}
+
+impl std::fmt::Display for IdentifierFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            IdentifierFormatError::InvalidPattern(ref s) => {
+                write!(f, "Invalid identifier pattern: {}", s)
+            }
+            IdentifierFormatError::InvalidLengths {
+                min_length,
+                max_length,
+            } => write!(
+                f,
+                "Invalid identifier lengths: max_length {} is less than min_length {}",
+                max_length, min_length
+            ),
+            IdentifierFormatError::TooShort {
+                ref identifier,
+                min_length,
+            } => write!(
+                f,
+                "Identifier {} is shorter than {} characters",
+                identifier, min_length
+            ),
+            IdentifierFormatError::TooLong {
+                ref identifier,
+                max_length,
+            } => write!(
+                f,
+                "Identifier {} is longer than {} characters",
+                identifier, max_length
+            ),
+            IdentifierFormatError::PatternMismatch {
+                ref identifier,
+                ref pattern,
+            } => write!(
+                f,
+                "Identifier {} does not match the pattern {}",
+                identifier, pattern
+            ),
+        }
+    }
+}
+
+/// Builder used to create an IdentifierFormat
+#[derive(Default, Clone)]
+pub struct IdentifierFormatBuilder {
+    pub pattern: Option<String>,
+    pub min_length: Option<u32>,
+    pub max_length: Option<u32>,
+}
+
+impl IdentifierFormatBuilder {
+    pub fn new() -> Self {
+        IdentifierFormatBuilder::default()
+    }
+
+    pub fn with_pattern(mut self, pattern: String) -> IdentifierFormatBuilder {
+        self.pattern = Some(pattern);
+        self
+    }
+
+    pub fn with_min_length(mut self, min_length: u32) -> IdentifierFormatBuilder {
+        self.min_length = Some(min_length);
+        self
+    }
+
+    pub fn with_max_length(mut self, max_length: u32) -> IdentifierFormatBuilder {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn build(self) -> Result<IdentifierFormat, IdentifierFormatError> {
+        let pattern = self.pattern.unwrap_or_default();
+        let min_length = self.min_length.unwrap_or_default();
+        let max_length = self.max_length.unwrap_or_default();
+
+        if !pattern.is_empty() {
+            compile_pattern(&pattern)?;
+        }
+
+        if max_length != 0 && max_length < min_length {
+            return Err(IdentifierFormatError::InvalidLengths {
+                min_length,
+                max_length,
+            });
+        }
+
+        Ok(IdentifierFormat {
+            pattern,
+            min_length,
+            max_length,
         })
     }
 }
@@ -439,6 +1200,8 @@ pub struct Schema {
     description: String,
     owner: String,
     properties: Vec<PropertyDefinition>,
+    identifier_format: Option<IdentifierFormat>,
+    version: u32,
 }
 
 impl Schema {
@@ -457,6 +1220,25 @@ impl Schema {
     pub fn properties(&self) -> &[PropertyDefinition] {
         &self.properties
     }
+
+    pub fn identifier_format(&self) -> Option<&IdentifierFormat> {
+        self.identifier_format.as_ref()
+    }
+
+    pub fn version(&self) -> &u32 {
+        &self.version
+    }
+
+    pub fn into_builder(self) -> SchemaBuilder {
+        let mut builder = SchemaBuilder::new()
+            .with_name(self.name)
+            .with_description(self.description)
+            .with_owner(self.owner)
+            .with_properties(self.properties)
+            .with_version(self.version);
+        builder.identifier_format = self.identifier_format;
+        builder
+    }
 }
 
 impl FromProto<protos::schema_state::Schema> for Schema {
@@ -471,6 +1253,14 @@ impl FromProto<protos::schema_state::Schema> for Schema {
                 .into_iter()
                 .map(PropertyDefinition::from_proto)
                 .collect::<Result<Vec<PropertyDefinition>, ProtoConversionError>>()?,
+            identifier_format: if schema.has_identifier_format() {
+                Some(IdentifierFormat::from_proto(
+                    schema.get_identifier_format().clone(),
+                )?)
+            } else {
+                None
+            },
+            version: schema.get_version(),
         })
     }
 }
@@ -489,6 +1279,10 @@ impl FromNative<Schema> for protos::schema_state::Schema {
                 .map(PropertyDefinition::into_proto)
                 .collect::<Result<Vec<protos::schema_state::PropertyDefinition>, ProtoConversionError>>()?,
         ));
+        if let Some(identifier_format) = schema.identifier_format().cloned() {
+            proto_schema.set_identifier_format(identifier_format.into_proto()?);
+        }
+        proto_schema.set_version(*schema.version());
         Ok(proto_schema)
     }
 }
@@ -552,6 +1346,8 @@ pub struct SchemaBuilder {
     pub description: Option<String>,
     pub owner: Option<String>,
     pub properties: Vec<PropertyDefinition>,
+    pub identifier_format: Option<IdentifierFormat>,
+    pub version: Option<u32>,
 }
 
 impl SchemaBuilder {
@@ -579,6 +1375,16 @@ impl SchemaBuilder {
         self
     }
 
+    pub fn with_identifier_format(mut self, identifier_format: IdentifierFormat) -> SchemaBuilder {
+        self.identifier_format = Some(identifier_format);
+        self
+    }
+
+    pub fn with_version(mut self, version: u32) -> SchemaBuilder {
+        self.version = Some(version);
+        self
+    }
+
     pub fn build(self) -> Result<Schema, SchemaBuildError> {
         let name = self.name.ok_or_else(|| {
             SchemaBuildError::MissingField("'name' field is required".to_string())
@@ -604,6 +1410,8 @@ impl SchemaBuilder {
             description,
             owner,
             properties,
+            identifier_format: self.identifier_format,
+            version: self.version.unwrap_or(1),
         })
     }
 }
@@ -749,6 +1557,9 @@ pub struct PropertyValue {
     enum_value: u32,
     struct_values: Vec<PropertyValue>,
     lat_long_value: LatLong,
+    timestamp_value: u64,
+    decimal_value: Decimal,
+    list_values: Vec<PropertyValue>,
 }
 
 impl PropertyValue {
@@ -787,6 +1598,18 @@ impl PropertyValue {
     pub fn lat_long_value(&self) -> &LatLong {
         &self.lat_long_value
     }
+
+    pub fn timestamp_value(&self) -> &u64 {
+        &self.timestamp_value
+    }
+
+    pub fn decimal_value(&self) -> &Decimal {
+        &self.decimal_value
+    }
+
+    pub fn list_values(&self) -> &[PropertyValue] {
+        &self.list_values
+    }
 }
 
 impl FromProto<protos::schema_state::PropertyValue> for PropertyValue {
@@ -808,6 +1631,14 @@ impl FromProto<protos::schema_state::PropertyValue> for PropertyValue {
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
             lat_long_value: property_value.get_lat_long_value().clone().into_native()?,
+            timestamp_value: property_value.get_timestamp_value(),
+            decimal_value: property_value.get_decimal_value().clone().into_native()?,
+            list_values: property_value
+                .get_list_values()
+                .to_vec()
+                .into_iter()
+                .map(PropertyValue::from_proto)
+                .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
         })
     }
 }
@@ -833,6 +1664,18 @@ impl FromNative<PropertyValue> for protos::schema_state::PropertyValue {
         ));
         proto_property_value
             .set_lat_long_value(property_value.lat_long_value().clone().into_proto()?);
+        proto_property_value.set_timestamp_value(*property_value.timestamp_value());
+        proto_property_value
+            .set_decimal_value(property_value.decimal_value().clone().into_proto()?);
+        proto_property_value.set_list_values(RepeatedField::from_vec(
+            property_value
+                .list_values()
+                .to_vec()
+                .into_iter()
+                .map(PropertyValue::into_proto)
+                .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
+                )?,
+        ));
         Ok(proto_property_value)
     }
 }
@@ -903,6 +1746,9 @@ pub struct PropertyValueBuilder {
     pub enum_value: Option<u32>,
     pub struct_values: Vec<PropertyValue>,
     pub lat_long_value: Option<LatLong>,
+    pub timestamp_value: Option<u64>,
+    pub decimal_value: Option<Decimal>,
+    pub list_values: Vec<PropertyValue>,
 }
 
 impl PropertyValueBuilder {
@@ -955,6 +1801,21 @@ impl PropertyValueBuilder {
         self
     }
 
+    pub fn with_timestamp_value(mut self, timestamp_value: u64) -> PropertyValueBuilder {
+        self.timestamp_value = Some(timestamp_value);
+        self
+    }
+
+    pub fn with_decimal_value(mut self, decimal_value: Decimal) -> PropertyValueBuilder {
+        self.decimal_value = Some(decimal_value);
+        self
+    }
+
+    pub fn with_list_values(mut self, list_values: Vec<PropertyValue>) -> PropertyValueBuilder {
+        self.list_values = list_values;
+        self
+    }
+
     pub fn build(self) -> Result<PropertyValue, PropertyValueBuildError> {
         let name = self.name.ok_or_else(|| {
             PropertyValueBuildError::MissingField("'name' field is required".to_string())
@@ -1053,6 +1914,33 @@ impl PropertyValueBuilder {
             }
         };
 
+        let timestamp_value = {
+            if data_type == DataType::Timestamp {
+                self.timestamp_value.ok_or_else(|| {
+                    PropertyValueBuildError::MissingField(
+                        "'timestamp_value' field is required".to_string(),
+                    )
+                })?
+            } else {
+                0 as u64
+            }
+        };
+
+        let decimal_value = {
+            if data_type == DataType::Decimal {
+                self.decimal_value.ok_or_else(|| {
+                    PropertyValueBuildError::MissingField(
+                        "'decimal_value' field is required".to_string(),
+                    )
+                })?
+            } else {
+                Decimal {
+                    digits: 0,
+                    scale: 0,
+                }
+            }
+        };
+
         Ok(PropertyValue {
             name,
             data_type,
@@ -1063,6 +1951,9 @@ impl PropertyValueBuilder {
             enum_value,
             struct_values,
             lat_long_value,
+            timestamp_value,
+            decimal_value,
+            list_values: self.list_values,
         })
     }
 }
@@ -1160,6 +2051,74 @@ mod tests {
         assert_eq!(property_definition, original);
     }
 
+    #[test]
+    // check that a property definition with constraints can be converted to bytes and back
+    fn check_property_definition_with_constraints_bytes() {
+        let constraints = PropertyConstraintsBuilder::new()
+            .with_min_number(0)
+            .with_max_number(100)
+            .with_min_lat_long(LatLongBuilder::new().with_lat_long(0, 0).build().unwrap())
+            .build()
+            .unwrap();
+        let original = PropertyDefinitionBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(0)
+            .with_constraints(constraints.clone())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let property_definition = PropertyDefinition::from_bytes(&bytes).unwrap();
+        assert_eq!(property_definition.constraints(), Some(&constraints));
+        assert_eq!(property_definition.constraints().unwrap().max_length(), &0);
+        assert_eq!(
+            property_definition.constraints().unwrap().max_lat_long(),
+            None
+        );
+        assert_eq!(property_definition, original);
+    }
+
+    #[test]
+    // check that invalid patterns and ranges are rejected by the constraints builder
+    fn check_property_constraints_builder_invalid() {
+        match PropertyConstraintsBuilder::new()
+            .with_pattern("[0-9".to_string())
+            .build()
+        {
+            Err(PropertyConstraintsBuildError::InvalidPattern(_)) => (),
+            res => panic!("Expected InvalidPattern, got {:?}", res),
+        }
+
+        match PropertyConstraintsBuilder::new()
+            .with_min_number(10)
+            .with_max_number(5)
+            .build()
+        {
+            Err(PropertyConstraintsBuildError::InvalidRange(_)) => (),
+            res => panic!("Expected InvalidRange, got {:?}", res),
+        }
+
+        match PropertyConstraintsBuilder::new()
+            .with_min_length(10)
+            .with_max_length(5)
+            .build()
+        {
+            Err(PropertyConstraintsBuildError::InvalidRange(_)) => (),
+            res => panic!("Expected InvalidRange, got {:?}", res),
+        }
+
+        match PropertyConstraintsBuilder::new()
+            .with_min_lat_long(LatLongBuilder::new().with_lat_long(10, 10).build().unwrap())
+            .with_max_lat_long(LatLongBuilder::new().with_lat_long(20, 0).build().unwrap())
+            .build()
+        {
+            Err(PropertyConstraintsBuildError::InvalidRange(_)) => (),
+            res => panic!("Expected InvalidRange, got {:?}", res),
+        }
+    }
+
     #[test]
     // check that a schema with a enum property is built correctly
     fn check_schema_builder() {
@@ -1189,6 +2148,7 @@ mod tests {
         assert_eq!(schema.description, "Test Schema");
         assert_eq!(schema.owner, "owner");
         assert_eq!(schema.properties, vec![property_definition]);
+        assert_eq!(schema.version, 1);
     }
 
     #[test]
@@ -1222,6 +2182,124 @@ mod tests {
         assert_eq!(schema, original);
     }
 
+    #[test]
+    // check that a schema with an identifier format can be converted to bytes and back
+    fn check_schema_with_identifier_format_bytes() {
+        let property_definition = PropertyDefinitionBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::String)
+            .build()
+            .unwrap();
+
+        let identifier_format = IdentifierFormatBuilder::new()
+            .with_pattern("SKU-[0-9]+".to_string())
+            .with_min_length(5)
+            .with_max_length(12)
+            .build()
+            .unwrap();
+
+        let original = SchemaBuilder::new()
+            .with_name("TestSchema".to_string())
+            .with_owner("owner".to_string())
+            .with_properties(vec![property_definition])
+            .with_identifier_format(identifier_format.clone())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let schema = Schema::from_bytes(&bytes).unwrap();
+        assert_eq!(schema.identifier_format(), Some(&identifier_format));
+        assert_eq!(schema, original);
+    }
+
+    #[test]
+    // check that a schema's version and deprecated properties can be converted to bytes and back
+    fn check_schema_with_version_bytes() {
+        let property_definition = PropertyDefinitionBuilder::new()
+            .with_name("TEST".to_string())
+            .with_data_type(DataType::String)
+            .with_deprecated(true)
+            .build()
+            .unwrap();
+
+        let original = SchemaBuilder::new()
+            .with_name("TestSchema".to_string())
+            .with_owner("owner".to_string())
+            .with_properties(vec![property_definition])
+            .with_version(3)
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+
+        let schema = Schema::from_bytes(&bytes).unwrap();
+        assert_eq!(schema.version(), &3);
+        assert!(schema.properties()[0].deprecated());
+        assert_eq!(schema, original);
+    }
+
+    #[test]
+    // check that identifiers are validated against the lengths and the pattern
+    fn check_identifier_format_validate() {
+        let identifier_format = IdentifierFormatBuilder::new()
+            .with_pattern("SKU-[0-9]+".to_string())
+            .with_min_length(5)
+            .with_max_length(8)
+            .build()
+            .unwrap();
+
+        assert!(identifier_format.validate("SKU-1234").is_ok());
+        assert_eq!(
+            identifier_format.validate("SKU-"),
+            Err(IdentifierFormatError::TooShort {
+                identifier: "SKU-".to_string(),
+                min_length: 5,
+            })
+        );
+        assert_eq!(
+            identifier_format.validate("SKU-12345"),
+            Err(IdentifierFormatError::TooLong {
+                identifier: "SKU-12345".to_string(),
+                max_length: 8,
+            })
+        );
+        // The pattern must match the whole identifier
+        assert_eq!(
+            identifier_format.validate("XSKU-123"),
+            Err(IdentifierFormatError::PatternMismatch {
+                identifier: "XSKU-123".to_string(),
+                pattern: "SKU-[0-9]+".to_string(),
+            })
+        );
+
+        let unbounded = IdentifierFormatBuilder::new().build().unwrap();
+        assert!(unbounded.validate("any part number").is_ok());
+    }
+
+    #[test]
+    // check that invalid patterns and lengths are rejected by the builder
+    fn check_identifier_format_builder_invalid() {
+        match IdentifierFormatBuilder::new()
+            .with_pattern("SKU-[0-9".to_string())
+            .build()
+        {
+            Err(IdentifierFormatError::InvalidPattern(_)) => (),
+            res => panic!("Expected InvalidPattern, got {:?}", res),
+        }
+
+        assert_eq!(
+            IdentifierFormatBuilder::new()
+                .with_min_length(10)
+                .with_max_length(5)
+                .build(),
+            Err(IdentifierFormatError::InvalidLengths {
+                min_length: 10,
+                max_length: 5,
+            })
+        );
+    }
+
     #[test]
     // check that a property value with a string data type is built correctly
     fn check_property_value_builder_string() {
@@ -1282,6 +2360,122 @@ mod tests {
         assert_eq!(property_value.lat_long_value, lat_long);
     }
 
+    #[test]
+    // check that a list property definition requires the data type of its elements and that the
+    // element fields are checked against that type
+    fn check_property_definition_builder_list() {
+        let property_definition = PropertyDefinitionBuilder::new()
+            .with_name("allergens".to_string())
+            .with_data_type(DataType::List)
+            .with_list_data_type(DataType::Enum)
+            .with_enum_options(vec!["milk".to_string(), "peanuts".to_string()])
+            .build()
+            .unwrap();
+
+        assert_eq!(property_definition.list_data_type(), Some(&DataType::Enum));
+        let element_definition = property_definition.list_element_definition().unwrap();
+        assert_eq!(element_definition.data_type(), &DataType::Enum);
+        assert_eq!(
+            element_definition.enum_options(),
+            property_definition.enum_options()
+        );
+
+        match PropertyDefinitionBuilder::new()
+            .with_name("allergens".to_string())
+            .with_data_type(DataType::List)
+            .with_list_data_type(DataType::Enum)
+            .build()
+        {
+            Err(PropertyDefinitionBuildError::EmptyVec(_)) => (),
+            res => panic!("Expected EmptyVec, got {:?}", res),
+        }
+
+        match PropertyDefinitionBuilder::new()
+            .with_name("allergens".to_string())
+            .with_data_type(DataType::List)
+            .build()
+        {
+            Err(PropertyDefinitionBuildError::MissingField(_)) => (),
+            res => panic!("Expected MissingField, got {:?}", res),
+        }
+
+        match PropertyDefinitionBuilder::new()
+            .with_name("matrix".to_string())
+            .with_data_type(DataType::List)
+            .with_list_data_type(DataType::List)
+            .build()
+        {
+            Err(PropertyDefinitionBuildError::InvalidField(_)) => (),
+            res => panic!("Expected InvalidField, got {:?}", res),
+        }
+    }
+
+    #[test]
+    // check that decimals are parsed from and displayed as exact strings
+    fn check_decimal_from_str_and_display() {
+        let decimal = "12.050".parse::<Decimal>().unwrap();
+        assert_eq!(decimal.digits(), &12050);
+        assert_eq!(decimal.scale(), &3);
+        assert_eq!(decimal.to_string(), "12.050");
+
+        assert_eq!("-0.5".parse::<Decimal>().unwrap().to_string(), "-0.5");
+        assert_eq!("+7".parse::<Decimal>().unwrap().to_string(), "7");
+        assert_eq!(".25".parse::<Decimal>().unwrap().to_string(), "0.25");
+
+        assert!("".parse::<Decimal>().is_err());
+        assert!("1.2.3".parse::<Decimal>().is_err());
+        assert!("--1".parse::<Decimal>().is_err());
+        assert!("1e5".parse::<Decimal>().is_err());
+        assert_eq!(
+            DecimalBuilder::new().with_digits(1).with_scale(19).build(),
+            Err(DecimalBuildError::InvalidScale(19))
+        );
+    }
+
+    #[test]
+    // check that timestamp, decimal and list values can be converted to bytes and back
+    fn check_property_value_new_types_bytes() {
+        let original = PropertyValueBuilder::new()
+            .with_name("best_before_dates".to_string())
+            .with_data_type(DataType::List)
+            .with_list_values(vec![
+                PropertyValueBuilder::new()
+                    .with_name("best_before_dates".to_string())
+                    .with_data_type(DataType::Timestamp)
+                    .with_timestamp_value(1_604_000_000)
+                    .build()
+                    .unwrap(),
+                PropertyValueBuilder::new()
+                    .with_name("best_before_dates".to_string())
+                    .with_data_type(DataType::Timestamp)
+                    .with_timestamp_value(1_605_000_000)
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_value = PropertyValue::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            property_value.list_values()[1].timestamp_value(),
+            &1_605_000_000
+        );
+        assert_eq!(property_value, original);
+
+        let original = PropertyValueBuilder::new()
+            .with_name("net_weight".to_string())
+            .with_data_type(DataType::Decimal)
+            .with_decimal_value("0.375".parse().unwrap())
+            .build()
+            .unwrap();
+
+        let bytes = original.clone().into_bytes().unwrap();
+        let property_value = PropertyValue::from_bytes(&bytes).unwrap();
+        assert_eq!(property_value.decimal_value().to_string(), "0.375");
+        assert_eq!(property_value, original);
+    }
+
     #[test]
     // check that a property value can be converted to bytes and back
     fn check_property_value_bytes() {
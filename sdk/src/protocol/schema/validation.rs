@@ -0,0 +1,723 @@
+// Copyright 2020 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation of property values against the schema property definitions they claim to follow.
+
+use std::error::Error as StdError;
+
+use crate::protocol::schema::state::{
+    DataType, PropertyConstraints, PropertyDefinition, PropertyValue,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum PropertyValueError {
+    /// Returned when the value's data type differs from the definition's.
+    WrongDataType {
+        path: String,
+        expected: DataType,
+        actual: DataType,
+    },
+    /// Returned when a value is provided that the definitions do not declare.
+    UndefinedProperty { path: String },
+    /// Returned when a required value is not provided.
+    MissingProperty { path: String, expected: DataType },
+    /// Returned when an enum value is not an index into the enum options.
+    InvalidEnumValue { path: String, value: u32 },
+    /// Returned when the value breaks one of the definition's constraints.
+    ConstraintViolation { path: String, reason: String },
+}
+
+impl PropertyValueError {
+    /// The path of the offending value, e.g. `dimensions.height` for a value nested in a struct
+    /// or `alternate_gtins[1]` for a list element
+    pub fn path(&self) -> &str {
+        match self {
+            PropertyValueError::WrongDataType { path, .. }
+            | PropertyValueError::UndefinedProperty { path }
+            | PropertyValueError::MissingProperty { path, .. }
+            | PropertyValueError::InvalidEnumValue { path, .. }
+            | PropertyValueError::ConstraintViolation { path, .. } => path,
+        }
+    }
+}
+
+impl StdError for PropertyValueError {}
+
+impl std::fmt::Display for PropertyValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            PropertyValueError::WrongDataType {
+                ref path,
+                ref expected,
+                ref actual,
+            } => write!(f, "{}: expected {:?}, got {:?}", path, expected, actual),
+            PropertyValueError::UndefinedProperty { ref path } => {
+                write!(f, "{}: not defined by the schema", path)
+            }
+            PropertyValueError::MissingProperty {
+                ref path,
+                ref expected,
+            } => write!(f, "{}: required {:?} value is missing", path, expected),
+            PropertyValueError::InvalidEnumValue { ref path, value } => {
+                write!(f, "{}: {} is not one of the enum options", path, value)
+            }
+            PropertyValueError::ConstraintViolation {
+                ref path,
+                ref reason,
+            } => write!(f, "{}: {}", path, reason),
+        }
+    }
+}
+
+/// Checks a set of property values against the property definitions of the schema they follow.
+///
+/// Every value must be declared by a definition and satisfy it, as checked by
+/// `validate_property_value`, and every required, non-deprecated definition must have a value.
+pub fn validate_properties(
+    definitions: &[PropertyDefinition],
+    values: &[PropertyValue],
+) -> Result<(), PropertyValueError> {
+    validate_values("", definitions, values)
+}
+
+/// Checks that the value has the definition's data type and satisfies its constraints.
+///
+/// Struct values are checked recursively against the definition's `struct_properties`, and every
+/// element of a list value is checked against the definition's list element definition.
+pub fn validate_property_value(
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+) -> Result<(), PropertyValueError> {
+    validate_value(value.name(), definition, value)
+}
+
+fn validate_values(
+    parent_path: &str,
+    definitions: &[PropertyDefinition],
+    values: &[PropertyValue],
+) -> Result<(), PropertyValueError> {
+    let path_of = |name: &str| {
+        if parent_path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", parent_path, name)
+        }
+    };
+
+    for value in values {
+        let path = path_of(value.name());
+        let definition = definitions
+            .iter()
+            .find(|def| def.name() == value.name())
+            .ok_or_else(|| PropertyValueError::UndefinedProperty { path: path.clone() })?;
+
+        validate_value(&path, definition, value)?;
+    }
+
+    if let Some(missing) = definitions.iter().find(|def| {
+        *def.required()
+            && !*def.deprecated()
+            && !values.iter().any(|value| value.name() == def.name())
+    }) {
+        return Err(PropertyValueError::MissingProperty {
+            path: path_of(missing.name()),
+            expected: missing.data_type().clone(),
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_value(
+    path: &str,
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+) -> Result<(), PropertyValueError> {
+    if value.data_type() != definition.data_type() {
+        return Err(PropertyValueError::WrongDataType {
+            path: path.to_string(),
+            expected: definition.data_type().clone(),
+            actual: value.data_type().clone(),
+        });
+    }
+
+    match value.data_type() {
+        DataType::Enum => {
+            if *value.enum_value() as usize >= definition.enum_options().len() {
+                return Err(PropertyValueError::InvalidEnumValue {
+                    path: path.to_string(),
+                    value: *value.enum_value(),
+                });
+            }
+        }
+        DataType::Struct => {
+            validate_values(path, definition.struct_properties(), value.struct_values())?
+        }
+        DataType::List => validate_list_values(path, definition, value)?,
+        _ => (),
+    }
+
+    match definition.constraints() {
+        Some(constraints) => check_constraints(path, constraints, value),
+        None => Ok(()),
+    }
+}
+
+/// Checks every element of a list against the definition's element definition, which carries the
+/// definition's constraints
+fn validate_list_values(
+    path: &str,
+    definition: &PropertyDefinition,
+    value: &PropertyValue,
+) -> Result<(), PropertyValueError> {
+    let element_definition = match definition.list_element_definition() {
+        Some(element_definition) => element_definition,
+        None => return Ok(()),
+    };
+
+    value
+        .list_values()
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, element)| {
+            validate_value(
+                &format!("{}[{}]", path, index),
+                &element_definition,
+                element,
+            )
+        })
+}
+
+fn check_constraints(
+    path: &str,
+    constraints: &PropertyConstraints,
+    value: &PropertyValue,
+) -> Result<(), PropertyValueError> {
+    let violation = |reason: String| PropertyValueError::ConstraintViolation {
+        path: path.to_string(),
+        reason,
+    };
+
+    match value.data_type() {
+        // Bounds are compared to the raw number, so they share the definition's number_exponent;
+        // for timestamps they are seconds since the Unix epoch
+        DataType::Number | DataType::Timestamp => {
+            let number = if value.data_type() == &DataType::Number {
+                i128::from(*value.number_value())
+            } else {
+                i128::from(*value.timestamp_value())
+            };
+            if let Some(min_number) = constraints.min_number() {
+                if number < i128::from(*min_number) {
+                    return Err(violation(format!("{} is less than {}", number, min_number)));
+                }
+            }
+            if let Some(max_number) = constraints.max_number() {
+                if number > i128::from(*max_number) {
+                    return Err(violation(format!(
+                        "{} is greater than {}",
+                        number, max_number
+                    )));
+                }
+            }
+        }
+        DataType::String => {
+            let string = value.string_value();
+            let length = string.chars().count() as u32;
+            if length < *constraints.min_length() {
+                return Err(violation(format!(
+                    "shorter than {} characters",
+                    constraints.min_length()
+                )));
+            }
+            if *constraints.max_length() != 0 && length > *constraints.max_length() {
+                return Err(violation(format!(
+                    "longer than {} characters",
+                    constraints.max_length()
+                )));
+            }
+            if !constraints
+                .matches_pattern(string)
+                .map_err(|err| violation(err.to_string()))?
+            {
+                return Err(violation(format!(
+                    "does not match the pattern {}",
+                    constraints.pattern()
+                )));
+            }
+            if !constraints.allowed_values().is_empty()
+                && !constraints.allowed_values().iter().any(|v| v == string)
+            {
+                return Err(violation(format!("{} is not an allowed value", string)));
+            }
+        }
+        DataType::Bytes => {
+            if *constraints.max_bytes() != 0
+                && value.bytes_value().len() > *constraints.max_bytes() as usize
+            {
+                return Err(violation(format!(
+                    "larger than {} bytes",
+                    constraints.max_bytes()
+                )));
+            }
+        }
+        DataType::LatLong => {
+            let lat_long = value.lat_long_value();
+            if let Some(min_lat_long) = constraints.min_lat_long() {
+                if lat_long.latitude() < min_lat_long.latitude()
+                    || lat_long.longitude() < min_lat_long.longitude()
+                {
+                    return Err(violation("outside of the allowed area".to_string()));
+                }
+            }
+            if let Some(max_lat_long) = constraints.max_lat_long() {
+                if lat_long.latitude() > max_lat_long.latitude()
+                    || lat_long.longitude() > max_lat_long.longitude()
+                {
+                    return Err(violation("outside of the allowed area".to_string()));
+                }
+            }
+        }
+        _ => (),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::protocol::schema::state::{
+        LatLongBuilder, PropertyConstraintsBuilder, PropertyDefinitionBuilder, PropertyValueBuilder,
+    };
+
+    fn string_definition(constraints: PropertyConstraints) -> PropertyDefinition {
+        PropertyDefinitionBuilder::new()
+            .with_name("code".to_string())
+            .with_data_type(DataType::String)
+            .with_constraints(constraints)
+            .build()
+            .unwrap()
+    }
+
+    fn string_value(value: &str) -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name("code".to_string())
+            .with_data_type(DataType::String)
+            .with_string_value(value.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn number_value(name: &str, value: i64) -> PropertyValue {
+        PropertyValueBuilder::new()
+            .with_name(name.to_string())
+            .with_data_type(DataType::Number)
+            .with_number_value(value)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    // check that a value of the wrong type is rejected
+    fn test_wrong_data_type() {
+        let definition = PropertyDefinitionBuilder::new()
+            .with_name("code".to_string())
+            .with_data_type(DataType::String)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            validate_property_value(&definition, &number_value("code", 1)),
+            Err(PropertyValueError::WrongDataType {
+                path: "code".to_string(),
+                expected: DataType::String,
+                actual: DataType::Number,
+            })
+        );
+    }
+
+    #[test]
+    // check that number values are checked against the min and max bounds
+    fn test_number_range() {
+        let definition = PropertyDefinitionBuilder::new()
+            .with_name("weight".to_string())
+            .with_data_type(DataType::Number)
+            .with_number_exponent(-3)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_min_number(0)
+                    .with_max_number(1000)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+
+        assert!(validate_property_value(&definition, &number_value("weight", 0)).is_ok());
+        assert!(validate_property_value(&definition, &number_value("weight", 1000)).is_ok());
+        assert!(validate_property_value(&definition, &number_value("weight", -1)).is_err());
+        assert!(validate_property_value(&definition, &number_value("weight", 1001)).is_err());
+    }
+
+    #[test]
+    // check that string values are checked against the lengths, pattern and allowed values
+    fn test_string_constraints() {
+        let definition = string_definition(
+            PropertyConstraintsBuilder::new()
+                .with_min_length(2)
+                .with_max_length(4)
+                .with_pattern("[A-Z]+".to_string())
+                .build()
+                .unwrap(),
+        );
+
+        assert!(validate_property_value(&definition, &string_value("ABC")).is_ok());
+        assert!(validate_property_value(&definition, &string_value("A")).is_err());
+        assert!(validate_property_value(&definition, &string_value("ABCDE")).is_err());
+        // The pattern must match the whole value
+        assert!(validate_property_value(&definition, &string_value("AB1")).is_err());
+
+        let definition = string_definition(
+            PropertyConstraintsBuilder::new()
+                .with_allowed_values(vec!["red".to_string(), "green".to_string()])
+                .build()
+                .unwrap(),
+        );
+
+        assert!(validate_property_value(&definition, &string_value("green")).is_ok());
+        assert_eq!(
+            validate_property_value(&definition, &string_value("blue")),
+            Err(PropertyValueError::ConstraintViolation {
+                path: "code".to_string(),
+                reason: "blue is not an allowed value".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    // check that bytes values are checked against the size cap
+    fn test_bytes_size() {
+        let definition = PropertyDefinitionBuilder::new()
+            .with_name("blob".to_string())
+            .with_data_type(DataType::Bytes)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_max_bytes(2)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let value = |bytes: Vec<u8>| {
+            PropertyValueBuilder::new()
+                .with_name("blob".to_string())
+                .with_data_type(DataType::Bytes)
+                .with_bytes_value(bytes)
+                .build()
+                .unwrap()
+        };
+
+        assert!(validate_property_value(&definition, &value(vec![1, 2])).is_ok());
+        assert!(validate_property_value(&definition, &value(vec![1, 2, 3])).is_err());
+    }
+
+    #[test]
+    // check that lat/long values must fall within the bounding box
+    fn test_lat_long_bounds() {
+        let definition = PropertyDefinitionBuilder::new()
+            .with_name("position".to_string())
+            .with_data_type(DataType::LatLong)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_min_lat_long(LatLongBuilder::new().with_lat_long(0, 0).build().unwrap())
+                    .with_max_lat_long(
+                        LatLongBuilder::new()
+                            .with_lat_long(10_000_000, 10_000_000)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let value = |latitude: i64, longitude: i64| {
+            PropertyValueBuilder::new()
+                .with_name("position".to_string())
+                .with_data_type(DataType::LatLong)
+                .with_lat_long_value(
+                    LatLongBuilder::new()
+                        .with_lat_long(latitude, longitude)
+                        .build()
+                        .unwrap(),
+                )
+                .build()
+                .unwrap()
+        };
+
+        assert!(validate_property_value(&definition, &value(5_000_000, 5_000_000)).is_ok());
+        assert!(validate_property_value(&definition, &value(-1, 5_000_000)).is_err());
+        assert!(validate_property_value(&definition, &value(5_000_000, 10_000_001)).is_err());
+    }
+
+    #[test]
+    // check that enum values must be an index into the enum options
+    fn test_enum_value() {
+        let definition = PropertyDefinitionBuilder::new()
+            .with_name("color".to_string())
+            .with_data_type(DataType::Enum)
+            .with_enum_options(vec!["red".to_string(), "green".to_string()])
+            .build()
+            .unwrap();
+        let value = |enum_value: u32| {
+            PropertyValueBuilder::new()
+                .with_name("color".to_string())
+                .with_data_type(DataType::Enum)
+                .with_enum_value(enum_value)
+                .build()
+                .unwrap()
+        };
+
+        assert!(validate_property_value(&definition, &value(1)).is_ok());
+        assert_eq!(
+            validate_property_value(&definition, &value(2)),
+            Err(PropertyValueError::InvalidEnumValue {
+                path: "color".to_string(),
+                value: 2,
+            })
+        );
+    }
+
+    #[test]
+    // check that every element of a list is validated against the element definition
+    fn test_list_values() {
+        let definition = PropertyDefinitionBuilder::new()
+            .with_name("alternate_gtins".to_string())
+            .with_data_type(DataType::List)
+            .with_list_data_type(DataType::String)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_pattern("[0-9]{14}".to_string())
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let element = |gtin: &str| {
+            PropertyValueBuilder::new()
+                .with_name("alternate_gtins".to_string())
+                .with_data_type(DataType::String)
+                .with_string_value(gtin.to_string())
+                .build()
+                .unwrap()
+        };
+        let value = |list_values: Vec<PropertyValue>| {
+            PropertyValueBuilder::new()
+                .with_name("alternate_gtins".to_string())
+                .with_data_type(DataType::List)
+                .with_list_values(list_values)
+                .build()
+                .unwrap()
+        };
+
+        assert!(validate_property_value(&definition, &value(vec![])).is_ok());
+        assert!(validate_property_value(
+            &definition,
+            &value(vec![element("00012345600012"), element("10012345600019")])
+        )
+        .is_ok());
+        assert!(validate_property_value(
+            &definition,
+            &value(vec![element("00012345600012"), element("123")])
+        )
+        .is_err());
+        assert_eq!(
+            validate_property_value(
+                &definition,
+                &value(vec![number_value("alternate_gtins", 1)])
+            ),
+            Err(PropertyValueError::WrongDataType {
+                path: "alternate_gtins[0]".to_string(),
+                expected: DataType::String,
+                actual: DataType::Number,
+            })
+        );
+    }
+
+    #[test]
+    // check that timestamp values are checked against the min and max bounds
+    fn test_timestamp_range() {
+        let definition = PropertyDefinitionBuilder::new()
+            .with_name("best_before".to_string())
+            .with_data_type(DataType::Timestamp)
+            .with_constraints(
+                PropertyConstraintsBuilder::new()
+                    .with_min_number(1_600_000_000)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let value = |timestamp: u64| {
+            PropertyValueBuilder::new()
+                .with_name("best_before".to_string())
+                .with_data_type(DataType::Timestamp)
+                .with_timestamp_value(timestamp)
+                .build()
+                .unwrap()
+        };
+
+        assert!(validate_property_value(&definition, &value(1_600_000_000)).is_ok());
+        assert!(validate_property_value(&definition, &value(1_599_999_999)).is_err());
+    }
+
+    #[test]
+    // check that struct values are validated recursively
+    fn test_struct_values() {
+        let definition = PropertyDefinitionBuilder::new()
+            .with_name("dimensions".to_string())
+            .with_data_type(DataType::Struct)
+            .with_struct_properties(vec![
+                PropertyDefinitionBuilder::new()
+                    .with_name("height".to_string())
+                    .with_data_type(DataType::Number)
+                    .with_number_exponent(0)
+                    .with_required(true)
+                    .with_constraints(
+                        PropertyConstraintsBuilder::new()
+                            .with_min_number(1)
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+                PropertyDefinitionBuilder::new()
+                    .with_name("width".to_string())
+                    .with_data_type(DataType::Number)
+                    .with_number_exponent(0)
+                    .build()
+                    .unwrap(),
+            ])
+            .build()
+            .unwrap();
+        let value = |struct_values: Vec<PropertyValue>| {
+            PropertyValueBuilder::new()
+                .with_name("dimensions".to_string())
+                .with_data_type(DataType::Struct)
+                .with_struct_values(struct_values)
+                .build()
+                .unwrap()
+        };
+
+        assert!(
+            validate_property_value(&definition, &value(vec![number_value("height", 2)])).is_ok()
+        );
+        assert!(
+            validate_property_value(&definition, &value(vec![number_value("height", 0)])).is_err()
+        );
+        assert_eq!(
+            validate_property_value(&definition, &value(vec![number_value("width", 2)])),
+            Err(PropertyValueError::MissingProperty {
+                path: "dimensions.height".to_string(),
+                expected: DataType::Number,
+            })
+        );
+        assert_eq!(
+            validate_property_value(
+                &definition,
+                &value(vec![number_value("height", 2), number_value("depth", 2)])
+            ),
+            Err(PropertyValueError::UndefinedProperty {
+                path: "dimensions.depth".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    // check that a set of values is checked against the schema's definitions and that errors are
+    // qualified with the path of the offending value
+    fn test_validate_properties() {
+        let definitions = vec![
+            PropertyDefinitionBuilder::new()
+                .with_name("dimensions".to_string())
+                .with_data_type(DataType::Struct)
+                .with_required(true)
+                .with_struct_properties(vec![PropertyDefinitionBuilder::new()
+                    .with_name("height".to_string())
+                    .with_data_type(DataType::Number)
+                    .with_number_exponent(0)
+                    .build()
+                    .unwrap()])
+                .build()
+                .unwrap(),
+            PropertyDefinitionBuilder::new()
+                .with_name("legacy_code".to_string())
+                .with_data_type(DataType::String)
+                .with_required(true)
+                .with_deprecated(true)
+                .build()
+                .unwrap(),
+        ];
+        let dimensions = |height: PropertyValue| {
+            PropertyValueBuilder::new()
+                .with_name("dimensions".to_string())
+                .with_data_type(DataType::Struct)
+                .with_struct_values(vec![height])
+                .build()
+                .unwrap()
+        };
+
+        // Deprecated definitions are never required
+        assert!(
+            validate_properties(&definitions, &[dimensions(number_value("height", 2))]).is_ok()
+        );
+
+        let err = validate_properties(
+            &definitions,
+            &[dimensions(
+                PropertyValueBuilder::new()
+                    .with_name("height".to_string())
+                    .with_data_type(DataType::String)
+                    .with_string_value("tall".to_string())
+                    .build()
+                    .unwrap(),
+            )],
+        )
+        .unwrap_err();
+        assert_eq!(err.path(), "dimensions.height");
+        assert_eq!(
+            err.to_string(),
+            "dimensions.height: expected Number, got String"
+        );
+
+        assert_eq!(
+            validate_properties(&definitions, &[]),
+            Err(PropertyValueError::MissingProperty {
+                path: "dimensions".to_string(),
+                expected: DataType::Struct,
+            })
+        );
+        assert_eq!(
+            validate_properties(
+                &definitions,
+                &[dimensions(number_value("height", 2)), string_value("ABC")]
+            ),
+            Err(PropertyValueError::UndefinedProperty {
+                path: "code".to_string(),
+            })
+        );
+    }
+}
@@ -18,10 +18,15 @@ use protobuf::RepeatedField;
 use std::default::Default;
 
 use super::errors::BuilderError;
-use crate::protocol::{schema::state::PropertyValue, track_and_trace::state::Role};
+use crate::protocol::{
+    product::state::ProductNamespace,
+    schema::state::PropertyValue,
+    track_and_trace::state::{product_namespace_from_proto, ReporterConstraints, Role},
+};
 use crate::protos;
 use crate::protos::{
     track_and_trace_payload, track_and_trace_payload::TrackAndTracePayload_Action,
+    track_and_trace_state,
 };
 use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
@@ -32,6 +37,10 @@ pub struct CreateRecordAction {
     record_id: String,
     schema: String,
     properties: Vec<PropertyValue>,
+    product_id: String,
+    location_id: String,
+    product_namespace: ProductNamespace,
+    product_schema_name: String,
 }
 
 impl CreateRecordAction {
@@ -44,6 +53,22 @@ impl CreateRecordAction {
     pub fn properties(&self) -> &[PropertyValue] {
         &self.properties
     }
+    /// The ID of the product the record tracks, or an empty string if none
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+    /// The GLN of the location the record starts at, or an empty string if unknown
+    pub fn location_id(&self) -> &str {
+        &self.location_id
+    }
+    /// The namespace of the product the record tracks
+    pub fn product_namespace(&self) -> &ProductNamespace {
+        &self.product_namespace
+    }
+    /// The schema of the custom product the record tracks, or an empty string for a GS1 product
+    pub fn product_schema_name(&self) -> &str {
+        &self.product_schema_name
+    }
 }
 
 #[derive(Default, Debug)]
@@ -51,6 +76,10 @@ pub struct CreateRecordActionBuilder {
     record_id: Option<String>,
     schema: Option<String>,
     properties: Option<Vec<PropertyValue>>,
+    product_id: Option<String>,
+    location_id: Option<String>,
+    product_namespace: Option<ProductNamespace>,
+    product_schema_name: Option<String>,
 }
 
 impl CreateRecordActionBuilder {
@@ -69,6 +98,22 @@ impl CreateRecordActionBuilder {
         self.properties = Some(value);
         self
     }
+    pub fn with_product_id(mut self, value: String) -> Self {
+        self.product_id = Some(value);
+        self
+    }
+    pub fn with_location_id(mut self, value: String) -> Self {
+        self.location_id = Some(value);
+        self
+    }
+    pub fn with_product_namespace(mut self, value: ProductNamespace) -> Self {
+        self.product_namespace = Some(value);
+        self
+    }
+    pub fn with_product_schema_name(mut self, value: String) -> Self {
+        self.product_schema_name = Some(value);
+        self
+    }
     pub fn build(self) -> Result<CreateRecordAction, BuilderError> {
         let record_id = self
             .record_id
@@ -79,10 +124,18 @@ impl CreateRecordActionBuilder {
         let properties = self
             .properties
             .ok_or_else(|| BuilderError::MissingField("properties".into()))?;
+        let product_id = self.product_id.unwrap_or_default();
+        let location_id = self.location_id.unwrap_or_default();
+        let product_namespace = self.product_namespace.unwrap_or_default();
+        let product_schema_name = self.product_schema_name.unwrap_or_default();
         Ok(CreateRecordAction {
             record_id,
             schema,
             properties,
+            product_id,
+            location_id,
+            product_namespace,
+            product_schema_name,
         })
     }
 }
@@ -100,6 +153,10 @@ impl FromProto<track_and_trace_payload::CreateRecordAction> for CreateRecordActi
                 .into_iter()
                 .map(PropertyValue::from_proto)
                 .collect::<Result<Vec<PropertyValue>, ProtoConversionError>>()?,
+            product_id: proto.get_product_id().to_string(),
+            location_id: proto.get_location_id().to_string(),
+            product_namespace: product_namespace_from_proto(proto.get_product_namespace())?,
+            product_schema_name: proto.get_product_schema_name().to_string(),
         })
     }
 }
@@ -118,6 +175,15 @@ impl FromNative<CreateRecordAction> for track_and_trace_payload::CreateRecordAct
                 .collect::<Result<Vec<protos::schema_state::PropertyValue>, ProtoConversionError>>(
                 )?,
         ));
+        proto.set_product_id(create_record_action.product_id().to_string());
+        proto.set_location_id(create_record_action.location_id().to_string());
+        proto.set_product_namespace(
+            create_record_action
+                .product_namespace()
+                .clone()
+                .into_proto()?,
+        );
+        proto.set_product_schema_name(create_record_action.product_schema_name().to_string());
 
         Ok(proto)
     }
@@ -335,6 +401,10 @@ pub struct CreateProposalAction {
     role: Role,
     properties: Vec<String>,
     terms: String,
+    expires_at: u64,
+    reporter_valid_from: u64,
+    reporter_valid_until: u64,
+    reporter_constraints: Vec<ReporterConstraints>,
 }
 
 impl CreateProposalAction {
@@ -353,6 +423,18 @@ impl CreateProposalAction {
     pub fn terms(&self) -> &str {
         &self.terms
     }
+    pub fn expires_at(&self) -> &u64 {
+        &self.expires_at
+    }
+    pub fn reporter_valid_from(&self) -> &u64 {
+        &self.reporter_valid_from
+    }
+    pub fn reporter_valid_until(&self) -> &u64 {
+        &self.reporter_valid_until
+    }
+    pub fn reporter_constraints(&self) -> &[ReporterConstraints] {
+        &self.reporter_constraints
+    }
 }
 
 #[derive(Default, Debug)]
@@ -362,6 +444,10 @@ pub struct CreateProposalActionBuilder {
     role: Option<Role>,
     properties: Option<Vec<String>>,
     terms: Option<String>,
+    expires_at: Option<u64>,
+    reporter_valid_from: Option<u64>,
+    reporter_valid_until: Option<u64>,
+    reporter_constraints: Option<Vec<ReporterConstraints>>,
 }
 
 impl CreateProposalActionBuilder {
@@ -388,6 +474,22 @@ impl CreateProposalActionBuilder {
         self.terms = Some(value);
         self
     }
+    pub fn with_expires_at(mut self, value: u64) -> Self {
+        self.expires_at = Some(value);
+        self
+    }
+    pub fn with_reporter_valid_from(mut self, value: u64) -> Self {
+        self.reporter_valid_from = Some(value);
+        self
+    }
+    pub fn with_reporter_valid_until(mut self, value: u64) -> Self {
+        self.reporter_valid_until = Some(value);
+        self
+    }
+    pub fn with_reporter_constraints(mut self, value: Vec<ReporterConstraints>) -> Self {
+        self.reporter_constraints = Some(value);
+        self
+    }
     pub fn build(self) -> Result<CreateProposalAction, BuilderError> {
         let record_id = self
             .record_id
@@ -404,12 +506,20 @@ impl CreateProposalActionBuilder {
         let terms = self
             .terms
             .ok_or_else(|| BuilderError::MissingField("terms".into()))?;
+        let expires_at = self.expires_at.unwrap_or_default();
+        let reporter_valid_from = self.reporter_valid_from.unwrap_or_default();
+        let reporter_valid_until = self.reporter_valid_until.unwrap_or_default();
+        let reporter_constraints = self.reporter_constraints.unwrap_or_default();
         Ok(CreateProposalAction {
             record_id,
             receiving_agent,
             role,
             properties,
             terms,
+            expires_at,
+            reporter_valid_from,
+            reporter_valid_until,
+            reporter_constraints,
         })
     }
 }
@@ -429,6 +539,15 @@ impl FromProto<track_and_trace_payload::CreateProposalAction> for CreateProposal
                 .map(String::from)
                 .collect(),
             terms: proto.get_terms().to_string(),
+            expires_at: proto.get_expires_at(),
+            reporter_valid_from: proto.get_reporter_valid_from(),
+            reporter_valid_until: proto.get_reporter_valid_until(),
+            reporter_constraints: proto
+                .get_reporter_constraints()
+                .to_vec()
+                .into_iter()
+                .map(ReporterConstraints::from_proto)
+                .collect::<Result<Vec<ReporterConstraints>, ProtoConversionError>>()?,
         })
     }
 }
@@ -441,6 +560,17 @@ impl FromNative<CreateProposalAction> for track_and_trace_payload::CreateProposa
         proto.set_role(native.role().clone().into_proto()?);
         proto.set_properties(RepeatedField::from_vec(native.properties().to_vec()));
         proto.set_terms(native.terms().to_string());
+        proto.set_expires_at(*native.expires_at());
+        proto.set_reporter_valid_from(*native.reporter_valid_from());
+        proto.set_reporter_valid_until(*native.reporter_valid_until());
+        proto.set_reporter_constraints(RepeatedField::from_vec(
+            native
+                .reporter_constraints()
+                .to_vec()
+                .into_iter()
+                .map(ReporterConstraints::into_proto)
+                .collect::<Result<Vec<track_and_trace_state::ReporterConstraints>, ProtoConversionError>>()?,
+        ));
         Ok(proto)
     }
 }
@@ -744,6 +874,465 @@ impl IntoBytes for RevokeReporterAction {
 impl IntoProto<track_and_trace_payload::RevokeReporterAction> for RevokeReporterAction {}
 impl IntoNative<RevokeReporterAction> for track_and_trace_payload::RevokeReporterAction {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateRecordsAction {
+    parent_record_id: String,
+    child_record_ids: Vec<String>,
+}
+
+impl AggregateRecordsAction {
+    pub fn parent_record_id(&self) -> &str {
+        &self.parent_record_id
+    }
+    pub fn child_record_ids(&self) -> &[String] {
+        &self.child_record_ids
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AggregateRecordsActionBuilder {
+    parent_record_id: Option<String>,
+    child_record_ids: Option<Vec<String>>,
+}
+
+impl AggregateRecordsActionBuilder {
+    pub fn new() -> Self {
+        AggregateRecordsActionBuilder::default()
+    }
+    pub fn with_parent_record_id(mut self, value: String) -> Self {
+        self.parent_record_id = Some(value);
+        self
+    }
+    pub fn with_child_record_ids(mut self, value: Vec<String>) -> Self {
+        self.child_record_ids = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<AggregateRecordsAction, BuilderError> {
+        let parent_record_id = self
+            .parent_record_id
+            .ok_or_else(|| BuilderError::MissingField("parent_record_id".into()))?;
+        let child_record_ids = self
+            .child_record_ids
+            .ok_or_else(|| BuilderError::MissingField("child_record_ids".into()))?;
+        Ok(AggregateRecordsAction {
+            parent_record_id,
+            child_record_ids,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::AggregateRecordsAction> for AggregateRecordsAction {
+    fn from_proto(
+        proto: track_and_trace_payload::AggregateRecordsAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(AggregateRecordsAction {
+            parent_record_id: proto.get_parent_record_id().to_string(),
+            child_record_ids: proto
+                .get_child_record_ids()
+                .to_vec()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+impl FromNative<AggregateRecordsAction> for track_and_trace_payload::AggregateRecordsAction {
+    fn from_native(native: AggregateRecordsAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::AggregateRecordsAction::new();
+        proto.set_parent_record_id(native.parent_record_id().to_string());
+        proto.set_child_record_ids(RepeatedField::from_vec(native.child_record_ids().to_vec()));
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<AggregateRecordsAction> for AggregateRecordsAction {
+    fn from_bytes(bytes: &[u8]) -> Result<AggregateRecordsAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::AggregateRecordsAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get AggregateRecordsAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for AggregateRecordsAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get AggregateRecordsAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::AggregateRecordsAction> for AggregateRecordsAction {}
+impl IntoNative<AggregateRecordsAction> for track_and_trace_payload::AggregateRecordsAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisaggregateRecordsAction {
+    parent_record_id: String,
+    child_record_ids: Vec<String>,
+}
+
+impl DisaggregateRecordsAction {
+    pub fn parent_record_id(&self) -> &str {
+        &self.parent_record_id
+    }
+    pub fn child_record_ids(&self) -> &[String] {
+        &self.child_record_ids
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct DisaggregateRecordsActionBuilder {
+    parent_record_id: Option<String>,
+    child_record_ids: Option<Vec<String>>,
+}
+
+impl DisaggregateRecordsActionBuilder {
+    pub fn new() -> Self {
+        DisaggregateRecordsActionBuilder::default()
+    }
+    pub fn with_parent_record_id(mut self, value: String) -> Self {
+        self.parent_record_id = Some(value);
+        self
+    }
+    pub fn with_child_record_ids(mut self, value: Vec<String>) -> Self {
+        self.child_record_ids = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<DisaggregateRecordsAction, BuilderError> {
+        let parent_record_id = self
+            .parent_record_id
+            .ok_or_else(|| BuilderError::MissingField("parent_record_id".into()))?;
+        let child_record_ids = self
+            .child_record_ids
+            .ok_or_else(|| BuilderError::MissingField("child_record_ids".into()))?;
+        Ok(DisaggregateRecordsAction {
+            parent_record_id,
+            child_record_ids,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::DisaggregateRecordsAction> for DisaggregateRecordsAction {
+    fn from_proto(
+        proto: track_and_trace_payload::DisaggregateRecordsAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(DisaggregateRecordsAction {
+            parent_record_id: proto.get_parent_record_id().to_string(),
+            child_record_ids: proto
+                .get_child_record_ids()
+                .to_vec()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+impl FromNative<DisaggregateRecordsAction> for track_and_trace_payload::DisaggregateRecordsAction {
+    fn from_native(native: DisaggregateRecordsAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::DisaggregateRecordsAction::new();
+        proto.set_parent_record_id(native.parent_record_id().to_string());
+        proto.set_child_record_ids(RepeatedField::from_vec(native.child_record_ids().to_vec()));
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<DisaggregateRecordsAction> for DisaggregateRecordsAction {
+    fn from_bytes(bytes: &[u8]) -> Result<DisaggregateRecordsAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::DisaggregateRecordsAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get DisaggregateRecordsAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for DisaggregateRecordsAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get DisaggregateRecordsAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::DisaggregateRecordsAction> for DisaggregateRecordsAction {}
+impl IntoNative<DisaggregateRecordsAction> for track_and_trace_payload::DisaggregateRecordsAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReopenRecordAction {
+    record_id: String,
+}
+
+impl ReopenRecordAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct ReopenRecordActionBuilder {
+    record_id: Option<String>,
+}
+
+impl ReopenRecordActionBuilder {
+    pub fn new() -> Self {
+        ReopenRecordActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<ReopenRecordAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        Ok(ReopenRecordAction { record_id })
+    }
+}
+
+impl FromProto<track_and_trace_payload::ReopenRecordAction> for ReopenRecordAction {
+    fn from_proto(
+        proto: track_and_trace_payload::ReopenRecordAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ReopenRecordAction {
+            record_id: proto.get_record_id().to_string(),
+        })
+    }
+}
+
+impl FromNative<ReopenRecordAction> for track_and_trace_payload::ReopenRecordAction {
+    fn from_native(native: ReopenRecordAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::ReopenRecordAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ReopenRecordAction> for ReopenRecordAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ReopenRecordAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::ReopenRecordAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ReopenRecordAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for ReopenRecordAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get ReopenRecordAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::ReopenRecordAction> for ReopenRecordAction {}
+impl IntoNative<ReopenRecordAction> for track_and_trace_payload::ReopenRecordAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchiveRecordAction {
+    record_id: String,
+    archived: bool,
+}
+
+impl ArchiveRecordAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    pub fn archived(&self) -> &bool {
+        &self.archived
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct ArchiveRecordActionBuilder {
+    record_id: Option<String>,
+    archived: Option<bool>,
+}
+
+impl ArchiveRecordActionBuilder {
+    pub fn new() -> Self {
+        ArchiveRecordActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_archived(mut self, value: bool) -> Self {
+        self.archived = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<ArchiveRecordAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let archived = self
+            .archived
+            .ok_or_else(|| BuilderError::MissingField("archived".into()))?;
+        Ok(ArchiveRecordAction {
+            record_id,
+            archived,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::ArchiveRecordAction> for ArchiveRecordAction {
+    fn from_proto(
+        proto: track_and_trace_payload::ArchiveRecordAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ArchiveRecordAction {
+            record_id: proto.get_record_id().to_string(),
+            archived: proto.get_archived(),
+        })
+    }
+}
+
+impl FromNative<ArchiveRecordAction> for track_and_trace_payload::ArchiveRecordAction {
+    fn from_native(native: ArchiveRecordAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::ArchiveRecordAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_archived(*native.archived());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ArchiveRecordAction> for ArchiveRecordAction {
+    fn from_bytes(bytes: &[u8]) -> Result<ArchiveRecordAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::ArchiveRecordAction = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get ArchiveRecordAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for ArchiveRecordAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get ArchiveRecordAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::ArchiveRecordAction> for ArchiveRecordAction {}
+impl IntoNative<ArchiveRecordAction> for track_and_trace_payload::ArchiveRecordAction {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateRecordLocationAction {
+    record_id: String,
+    location_id: String,
+}
+
+impl UpdateRecordLocationAction {
+    pub fn record_id(&self) -> &str {
+        &self.record_id
+    }
+    /// The GLN of the location the record is now at, or an empty string if unknown
+    pub fn location_id(&self) -> &str {
+        &self.location_id
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct UpdateRecordLocationActionBuilder {
+    record_id: Option<String>,
+    location_id: Option<String>,
+}
+
+impl UpdateRecordLocationActionBuilder {
+    pub fn new() -> Self {
+        UpdateRecordLocationActionBuilder::default()
+    }
+    pub fn with_record_id(mut self, value: String) -> Self {
+        self.record_id = Some(value);
+        self
+    }
+    pub fn with_location_id(mut self, value: String) -> Self {
+        self.location_id = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<UpdateRecordLocationAction, BuilderError> {
+        let record_id = self
+            .record_id
+            .ok_or_else(|| BuilderError::MissingField("record_id".into()))?;
+        let location_id = self.location_id.unwrap_or_default();
+        Ok(UpdateRecordLocationAction {
+            record_id,
+            location_id,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_payload::UpdateRecordLocationAction> for UpdateRecordLocationAction {
+    fn from_proto(
+        proto: track_and_trace_payload::UpdateRecordLocationAction,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(UpdateRecordLocationAction {
+            record_id: proto.get_record_id().to_string(),
+            location_id: proto.get_location_id().to_string(),
+        })
+    }
+}
+
+impl FromNative<UpdateRecordLocationAction>
+    for track_and_trace_payload::UpdateRecordLocationAction
+{
+    fn from_native(native: UpdateRecordLocationAction) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_payload::UpdateRecordLocationAction::new();
+        proto.set_record_id(native.record_id().to_string());
+        proto.set_location_id(native.location_id().to_string());
+        Ok(proto)
+    }
+}
+
+impl FromBytes<UpdateRecordLocationAction> for UpdateRecordLocationAction {
+    fn from_bytes(bytes: &[u8]) -> Result<UpdateRecordLocationAction, ProtoConversionError> {
+        let proto: track_and_trace_payload::UpdateRecordLocationAction =
+            protobuf::parse_from_bytes(bytes).map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get UpdateRecordLocationAction from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for UpdateRecordLocationAction {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get UpdateRecordLocationAction from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_payload::UpdateRecordLocationAction> for UpdateRecordLocationAction {}
+impl IntoNative<UpdateRecordLocationAction>
+    for track_and_trace_payload::UpdateRecordLocationAction
+{
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Action {
     CreateRecord(CreateRecordAction),
@@ -752,6 +1341,11 @@ pub enum Action {
     CreateProposal(CreateProposalAction),
     AnswerProposal(AnswerProposalAction),
     RevokeReporter(RevokeReporterAction),
+    AggregateRecords(AggregateRecordsAction),
+    DisaggregateRecords(DisaggregateRecordsAction),
+    ReopenRecord(ReopenRecordAction),
+    ArchiveRecord(ArchiveRecordAction),
+    UpdateRecordLocation(UpdateRecordLocationAction),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -821,6 +1415,21 @@ impl FromProto<track_and_trace_payload::TrackAndTracePayload> for TrackAndTraceP
             TrackAndTracePayload_Action::REVOKE_REPORTER => Action::RevokeReporter(
                 RevokeReporterAction::from_proto(proto.get_revoke_reporter().clone())?,
             ),
+            TrackAndTracePayload_Action::AGGREGATE_RECORDS => Action::AggregateRecords(
+                AggregateRecordsAction::from_proto(proto.get_aggregate_records().clone())?,
+            ),
+            TrackAndTracePayload_Action::DISAGGREGATE_RECORDS => Action::DisaggregateRecords(
+                DisaggregateRecordsAction::from_proto(proto.get_disaggregate_records().clone())?,
+            ),
+            TrackAndTracePayload_Action::REOPEN_RECORD => Action::ReopenRecord(
+                ReopenRecordAction::from_proto(proto.get_reopen_record().clone())?,
+            ),
+            TrackAndTracePayload_Action::ARCHIVE_RECORD => Action::ArchiveRecord(
+                ArchiveRecordAction::from_proto(proto.get_archive_record().clone())?,
+            ),
+            TrackAndTracePayload_Action::UPDATE_RECORD_LOCATION => Action::UpdateRecordLocation(
+                UpdateRecordLocationAction::from_proto(proto.get_update_record_location().clone())?,
+            ),
             TrackAndTracePayload_Action::UNSET_ACTION => {
                 return Err(ProtoConversionError::InvalidTypeError(
                     "Cannot convert TrackAndTracePayload_Action with type unset.".to_string(),
@@ -866,6 +1475,26 @@ impl FromNative<TrackAndTracePayload> for track_and_trace_payload::TrackAndTrace
                 proto.set_action(TrackAndTracePayload_Action::REVOKE_REPORTER);
                 proto.set_revoke_reporter(payload.clone().into_proto()?);
             }
+            Action::AggregateRecords(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::AGGREGATE_RECORDS);
+                proto.set_aggregate_records(payload.clone().into_proto()?);
+            }
+            Action::DisaggregateRecords(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::DISAGGREGATE_RECORDS);
+                proto.set_disaggregate_records(payload.clone().into_proto()?);
+            }
+            Action::ReopenRecord(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::REOPEN_RECORD);
+                proto.set_reopen_record(payload.clone().into_proto()?);
+            }
+            Action::ArchiveRecord(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::ARCHIVE_RECORD);
+                proto.set_archive_record(payload.clone().into_proto()?);
+            }
+            Action::UpdateRecordLocation(payload) => {
+                proto.set_action(TrackAndTracePayload_Action::UPDATE_RECORD_LOCATION);
+                proto.set_update_record_location(payload.clone().into_proto()?);
+            }
         }
 
         Ok(proto)
@@ -901,6 +1530,7 @@ impl IntoNative<TrackAndTracePayload> for track_and_trace_payload::TrackAndTrace
 mod tests {
     use super::*;
     use crate::protocol::schema::state::{DataType, PropertyValueBuilder};
+    use crate::protocol::track_and_trace::state::ReporterConstraintsBuilder;
     use std::fmt::Debug;
 
     fn test_from_bytes<T: FromBytes<T> + Clone + PartialEq + IntoBytes + Debug, F>(
@@ -927,12 +1557,32 @@ mod tests {
             .with_record_id("32".into())
             .with_schema("schema".into())
             .with_properties(vec![property_value.clone()])
+            .with_product_id("00012345600012".into())
+            .with_location_id("9012345000004".into())
             .build()
             .unwrap();
 
         assert_eq!(action.record_id(), "32");
         assert_eq!(action.schema(), "schema");
         assert!(action.properties().iter().any(|x| *x == property_value));
+        assert_eq!(action.product_id(), "00012345600012");
+        assert_eq!(action.location_id(), "9012345000004");
+        assert_eq!(action.product_namespace(), &ProductNamespace::GS1);
+        assert_eq!(action.product_schema_name(), "");
+
+        let action = CreateRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .with_schema("schema".into())
+            .with_properties(vec![property_value])
+            .with_product_id("LB-0001".into())
+            .with_product_namespace(ProductNamespace::Custom)
+            .with_product_schema_name("lumber".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.product_id(), "LB-0001");
+        assert_eq!(action.product_namespace(), &ProductNamespace::Custom);
+        assert_eq!(action.product_schema_name(), "lumber");
     }
 
     #[test]
@@ -1027,6 +1677,7 @@ mod tests {
         assert_eq!(action.terms(), "term");
         assert_eq!(*action.role(), Role::Custodian);
         assert!(action.properties().iter().any(|x| x == "egg"));
+        assert_eq!(*action.expires_at(), 0);
     }
 
     #[test]
@@ -1037,6 +1688,13 @@ mod tests {
             .with_role(Role::Custodian)
             .with_properties(vec!["egg".into()])
             .with_terms("term".to_string())
+            .with_expires_at(3600)
+            .with_reporter_valid_until(7200)
+            .with_reporter_constraints(vec![ReporterConstraintsBuilder::new()
+                .with_property_name("egg".into())
+                .with_min_report_interval(60)
+                .build()
+                .unwrap()])
             .build()
             .unwrap();
 
@@ -1098,6 +1756,118 @@ mod tests {
         test_from_bytes(action, RevokeReporterAction::from_bytes);
     }
 
+    #[test]
+    fn test_aggregate_records_action_builder() {
+        let action = AggregateRecordsActionBuilder::new()
+            .with_parent_record_id("pallet".into())
+            .with_child_record_ids(vec!["case1".into(), "case2".into()])
+            .build()
+            .unwrap();
+
+        assert_eq!(action.parent_record_id(), "pallet");
+        assert_eq!(action.child_record_ids(), ["case1", "case2"]);
+    }
+
+    #[test]
+    fn test_aggregate_records_action_bytes() {
+        let action = AggregateRecordsActionBuilder::new()
+            .with_parent_record_id("pallet".into())
+            .with_child_record_ids(vec!["case1".into(), "case2".into()])
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, AggregateRecordsAction::from_bytes);
+    }
+
+    #[test]
+    fn test_disaggregate_records_action_builder() {
+        let action = DisaggregateRecordsActionBuilder::new()
+            .with_parent_record_id("pallet".into())
+            .with_child_record_ids(vec!["case1".into()])
+            .build()
+            .unwrap();
+
+        assert_eq!(action.parent_record_id(), "pallet");
+        assert_eq!(action.child_record_ids(), ["case1"]);
+    }
+
+    #[test]
+    fn test_disaggregate_records_action_bytes() {
+        let action = DisaggregateRecordsActionBuilder::new()
+            .with_parent_record_id("pallet".into())
+            .with_child_record_ids(vec!["case1".into()])
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, DisaggregateRecordsAction::from_bytes);
+    }
+
+    #[test]
+    fn test_reopen_record_action_builder() {
+        let action = ReopenRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "32");
+    }
+
+    #[test]
+    fn test_reopen_record_action_bytes() {
+        let action = ReopenRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, ReopenRecordAction::from_bytes);
+    }
+
+    #[test]
+    fn test_archive_record_action_builder() {
+        let action = ArchiveRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .with_archived(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "32");
+        assert_eq!(*action.archived(), true);
+    }
+
+    #[test]
+    fn test_archive_record_action_bytes() {
+        let action = ArchiveRecordActionBuilder::new()
+            .with_record_id("32".into())
+            .with_archived(false)
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, ArchiveRecordAction::from_bytes);
+    }
+
+    #[test]
+    fn test_update_record_location_action_builder() {
+        let action = UpdateRecordLocationActionBuilder::new()
+            .with_record_id("32".into())
+            .with_location_id("9012345000004".into())
+            .build()
+            .unwrap();
+
+        assert_eq!(action.record_id(), "32");
+        assert_eq!(action.location_id(), "9012345000004");
+    }
+
+    #[test]
+    fn test_update_record_location_action_bytes() {
+        let action = UpdateRecordLocationActionBuilder::new()
+            .with_record_id("32".into())
+            .with_location_id("9012345000004".into())
+            .build()
+            .unwrap();
+
+        test_from_bytes(action, UpdateRecordLocationAction::from_bytes);
+    }
+
     #[test]
     fn test_payload_builder() {
         let action = RevokeReporterActionBuilder::new()
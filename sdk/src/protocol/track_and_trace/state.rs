@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use super::errors::BuilderError;
+use crate::protocol::product::state::ProductNamespace;
 use crate::protocol::schema::state::{PropertyDefinition, PropertyValue};
+use crate::protos::product_state::Product_ProductNamespace;
 use crate::protos::track_and_trace_state;
 use crate::protos::{
     FromBytes, FromNative, FromProto, IntoBytes, IntoNative, IntoProto, ProtoConversionError,
@@ -21,11 +23,161 @@ use crate::protos::{
 use protobuf::Message;
 use protobuf::RepeatedField;
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReporterConstraints {
+    property_name: String,
+    min_report_interval: u64,
+    min_value: Option<i64>,
+    max_value: Option<i64>,
+}
+
+impl ReporterConstraints {
+    pub fn property_name(&self) -> &str {
+        &self.property_name
+    }
+    pub fn min_report_interval(&self) -> &u64 {
+        &self.min_report_interval
+    }
+    pub fn min_value(&self) -> Option<i64> {
+        self.min_value
+    }
+    pub fn max_value(&self) -> Option<i64> {
+        self.max_value
+    }
+    /// Whether a Number value falls within the constrained range
+    pub fn allows_value(&self, value: i64) -> bool {
+        self.min_value.map(|min| value >= min).unwrap_or(true)
+            && self.max_value.map(|max| value <= max).unwrap_or(true)
+    }
+    pub fn into_builder(self) -> ReporterConstraintsBuilder {
+        let mut builder = ReporterConstraintsBuilder::new()
+            .with_property_name(self.property_name)
+            .with_min_report_interval(self.min_report_interval);
+        if let Some(min_value) = self.min_value {
+            builder = builder.with_min_value(min_value);
+        }
+        if let Some(max_value) = self.max_value {
+            builder = builder.with_max_value(max_value);
+        }
+        builder
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct ReporterConstraintsBuilder {
+    property_name: Option<String>,
+    min_report_interval: Option<u64>,
+    min_value: Option<i64>,
+    max_value: Option<i64>,
+}
+
+impl ReporterConstraintsBuilder {
+    pub fn new() -> Self {
+        ReporterConstraintsBuilder::default()
+    }
+    pub fn with_property_name(mut self, value: String) -> Self {
+        self.property_name = Some(value);
+        self
+    }
+    pub fn with_min_report_interval(mut self, value: u64) -> Self {
+        self.min_report_interval = Some(value);
+        self
+    }
+    pub fn with_min_value(mut self, value: i64) -> Self {
+        self.min_value = Some(value);
+        self
+    }
+    pub fn with_max_value(mut self, value: i64) -> Self {
+        self.max_value = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<ReporterConstraints, BuilderError> {
+        let property_name = self
+            .property_name
+            .ok_or_else(|| BuilderError::MissingField("property_name".into()))?;
+        let min_report_interval = self.min_report_interval.unwrap_or_default();
+        Ok(ReporterConstraints {
+            property_name,
+            min_report_interval,
+            min_value: self.min_value,
+            max_value: self.max_value,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::ReporterConstraints> for ReporterConstraints {
+    fn from_proto(
+        proto: track_and_trace_state::ReporterConstraints,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(ReporterConstraints {
+            property_name: proto.get_property_name().to_string(),
+            min_report_interval: proto.get_min_report_interval(),
+            min_value: if proto.get_has_min_value() {
+                Some(proto.get_min_value())
+            } else {
+                None
+            },
+            max_value: if proto.get_has_max_value() {
+                Some(proto.get_max_value())
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl FromNative<ReporterConstraints> for track_and_trace_state::ReporterConstraints {
+    fn from_native(native: ReporterConstraints) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::ReporterConstraints::new();
+        proto.set_property_name(native.property_name().to_string());
+        proto.set_min_report_interval(*native.min_report_interval());
+        if let Some(min_value) = native.min_value() {
+            proto.set_has_min_value(true);
+            proto.set_min_value(min_value);
+        }
+        if let Some(max_value) = native.max_value() {
+            proto.set_has_max_value(true);
+            proto.set_max_value(max_value);
+        }
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<ReporterConstraints> for ReporterConstraints {
+    fn from_bytes(bytes: &[u8]) -> Result<ReporterConstraints, ProtoConversionError> {
+        let proto: track_and_trace_state::ReporterConstraints = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get ReporterConstraints from bytes".into(),
+            )
+        })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for ReporterConstraints {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError(
+                "Unable to get ReporterConstraints from bytes".into(),
+            )
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_state::ReporterConstraints> for ReporterConstraints {}
+impl IntoNative<ReporterConstraints> for track_and_trace_state::ReporterConstraints {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Reporter {
     public_key: String,
     authorized: bool,
     index: u32,
+    valid_from: u64,
+    valid_until: u64,
+    constraints: Option<ReporterConstraints>,
+    last_reported_at: u64,
 }
 
 impl Reporter {
@@ -38,11 +190,36 @@ impl Reporter {
     pub fn index(&self) -> &u32 {
         &self.index
     }
+    pub fn valid_from(&self) -> &u64 {
+        &self.valid_from
+    }
+    pub fn valid_until(&self) -> &u64 {
+        &self.valid_until
+    }
+    pub fn constraints(&self) -> Option<&ReporterConstraints> {
+        self.constraints.as_ref()
+    }
+    pub fn last_reported_at(&self) -> &u64 {
+        &self.last_reported_at
+    }
+    /// Whether the reporter is authorized and the timestamp falls within its validity window
+    pub fn is_active(&self, timestamp: u64) -> bool {
+        self.authorized
+            && (self.valid_from == 0 || timestamp >= self.valid_from)
+            && (self.valid_until == 0 || timestamp <= self.valid_until)
+    }
     pub fn into_builder(self) -> ReporterBuilder {
-        ReporterBuilder::new()
+        let builder = ReporterBuilder::new()
             .with_public_key(self.public_key)
             .with_authorized(self.authorized)
             .with_index(self.index)
+            .with_valid_from(self.valid_from)
+            .with_valid_until(self.valid_until)
+            .with_last_reported_at(self.last_reported_at);
+        match self.constraints {
+            Some(constraints) => builder.with_constraints(constraints),
+            None => builder,
+        }
     }
 }
 
@@ -51,6 +228,10 @@ pub struct ReporterBuilder {
     public_key: Option<String>,
     authorized: Option<bool>,
     index: Option<u32>,
+    valid_from: Option<u64>,
+    valid_until: Option<u64>,
+    constraints: Option<ReporterConstraints>,
+    last_reported_at: Option<u64>,
 }
 
 impl ReporterBuilder {
@@ -69,6 +250,22 @@ impl ReporterBuilder {
         self.index = Some(value);
         self
     }
+    pub fn with_valid_from(mut self, value: u64) -> Self {
+        self.valid_from = Some(value);
+        self
+    }
+    pub fn with_valid_until(mut self, value: u64) -> Self {
+        self.valid_until = Some(value);
+        self
+    }
+    pub fn with_constraints(mut self, value: ReporterConstraints) -> Self {
+        self.constraints = Some(value);
+        self
+    }
+    pub fn with_last_reported_at(mut self, value: u64) -> Self {
+        self.last_reported_at = Some(value);
+        self
+    }
     pub fn build(self) -> Result<Reporter, BuilderError> {
         let public_key = self
             .public_key
@@ -79,10 +276,18 @@ impl ReporterBuilder {
         let index = self
             .index
             .ok_or_else(|| BuilderError::MissingField("index".into()))?;
+        // Reporters are unrestricted unless a grant says otherwise
+        let valid_from = self.valid_from.unwrap_or_default();
+        let valid_until = self.valid_until.unwrap_or_default();
+        let last_reported_at = self.last_reported_at.unwrap_or_default();
         Ok(Reporter {
             public_key,
             authorized,
             index,
+            valid_from,
+            valid_until,
+            constraints: self.constraints,
+            last_reported_at,
         })
     }
 }
@@ -95,6 +300,16 @@ impl FromProto<track_and_trace_state::Property_Reporter> for Reporter {
             public_key: proto.get_public_key().to_string(),
             authorized: proto.get_authorized(),
             index: proto.get_index(),
+            valid_from: proto.get_valid_from(),
+            valid_until: proto.get_valid_until(),
+            constraints: if proto.has_constraints() {
+                Some(ReporterConstraints::from_proto(
+                    proto.get_constraints().clone(),
+                )?)
+            } else {
+                None
+            },
+            last_reported_at: proto.get_last_reported_at(),
         })
     }
 }
@@ -105,6 +320,12 @@ impl FromNative<Reporter> for track_and_trace_state::Property_Reporter {
         proto.set_public_key(native.public_key().to_string());
         proto.set_authorized(*native.authorized());
         proto.set_index(*native.index());
+        proto.set_valid_from(*native.valid_from());
+        proto.set_valid_until(*native.valid_until());
+        if let Some(constraints) = native.constraints() {
+            proto.set_constraints(constraints.clone().into_proto()?);
+        }
+        proto.set_last_reported_at(*native.last_reported_at());
 
         Ok(proto)
     }
@@ -771,6 +992,7 @@ pub enum Status {
     Accepted,
     Rejected,
     Canceled,
+    Expired,
 }
 
 impl Default for Status {
@@ -788,6 +1010,7 @@ impl FromProto<track_and_trace_state::Proposal_Status> for Status {
             track_and_trace_state::Proposal_Status::ACCEPTED => Ok(Status::Accepted),
             track_and_trace_state::Proposal_Status::REJECTED => Ok(Status::Rejected),
             track_and_trace_state::Proposal_Status::CANCELED => Ok(Status::Canceled),
+            track_and_trace_state::Proposal_Status::EXPIRED => Ok(Status::Expired),
         }
     }
 }
@@ -799,6 +1022,7 @@ impl FromNative<Status> for track_and_trace_state::Proposal_Status {
             Status::Accepted => Ok(track_and_trace_state::Proposal_Status::ACCEPTED),
             Status::Rejected => Ok(track_and_trace_state::Proposal_Status::REJECTED),
             Status::Canceled => Ok(track_and_trace_state::Proposal_Status::CANCELED),
+            Status::Expired => Ok(track_and_trace_state::Proposal_Status::EXPIRED),
         }
     }
 }
@@ -816,6 +1040,10 @@ pub struct Proposal {
     properties: Vec<String>,
     status: Status,
     terms: String,
+    expires_at: u64,
+    reporter_valid_from: u64,
+    reporter_valid_until: u64,
+    reporter_constraints: Vec<ReporterConstraints>,
 }
 
 impl Proposal {
@@ -843,6 +1071,22 @@ impl Proposal {
     pub fn terms(&self) -> &str {
         &self.terms
     }
+    pub fn expires_at(&self) -> &u64 {
+        &self.expires_at
+    }
+    /// Whether the proposal can no longer be accepted at the given timestamp
+    pub fn is_expired(&self, timestamp: u64) -> bool {
+        self.expires_at != 0 && timestamp > self.expires_at
+    }
+    pub fn reporter_valid_from(&self) -> &u64 {
+        &self.reporter_valid_from
+    }
+    pub fn reporter_valid_until(&self) -> &u64 {
+        &self.reporter_valid_until
+    }
+    pub fn reporter_constraints(&self) -> &[ReporterConstraints] {
+        &self.reporter_constraints
+    }
     pub fn into_builder(self) -> ProposalBuilder {
         ProposalBuilder::new()
             .with_record_id(self.record_id)
@@ -853,6 +1097,10 @@ impl Proposal {
             .with_properties(self.properties)
             .with_status(self.status)
             .with_terms(self.terms)
+            .with_expires_at(self.expires_at)
+            .with_reporter_valid_from(self.reporter_valid_from)
+            .with_reporter_valid_until(self.reporter_valid_until)
+            .with_reporter_constraints(self.reporter_constraints)
     }
 }
 
@@ -866,6 +1114,10 @@ pub struct ProposalBuilder {
     properties: Option<Vec<String>>,
     status: Option<Status>,
     terms: Option<String>,
+    expires_at: Option<u64>,
+    reporter_valid_from: Option<u64>,
+    reporter_valid_until: Option<u64>,
+    reporter_constraints: Option<Vec<ReporterConstraints>>,
 }
 
 impl ProposalBuilder {
@@ -904,6 +1156,22 @@ impl ProposalBuilder {
         self.terms = Some(value);
         self
     }
+    pub fn with_expires_at(mut self, value: u64) -> Self {
+        self.expires_at = Some(value);
+        self
+    }
+    pub fn with_reporter_valid_from(mut self, value: u64) -> Self {
+        self.reporter_valid_from = Some(value);
+        self
+    }
+    pub fn with_reporter_valid_until(mut self, value: u64) -> Self {
+        self.reporter_valid_until = Some(value);
+        self
+    }
+    pub fn with_reporter_constraints(mut self, value: Vec<ReporterConstraints>) -> Self {
+        self.reporter_constraints = Some(value);
+        self
+    }
     pub fn build(self) -> Result<Proposal, BuilderError> {
         let record_id = self
             .record_id
@@ -929,6 +1197,11 @@ impl ProposalBuilder {
         let terms = self
             .terms
             .ok_or_else(|| BuilderError::MissingField("terms".into()))?;
+        // Proposals without an expiry stay open until they are answered
+        let expires_at = self.expires_at.unwrap_or_default();
+        let reporter_valid_from = self.reporter_valid_from.unwrap_or_default();
+        let reporter_valid_until = self.reporter_valid_until.unwrap_or_default();
+        let reporter_constraints = self.reporter_constraints.unwrap_or_default();
         Ok(Proposal {
             record_id,
             timestamp,
@@ -938,6 +1211,10 @@ impl ProposalBuilder {
             properties,
             status,
             terms,
+            expires_at,
+            reporter_valid_from,
+            reporter_valid_until,
+            reporter_constraints,
         })
     }
 }
@@ -958,6 +1235,15 @@ impl FromProto<track_and_trace_state::Proposal> for Proposal {
                 .collect(),
             status: Status::from_proto(proto.get_status())?,
             terms: proto.get_terms().to_string(),
+            expires_at: proto.get_expires_at(),
+            reporter_valid_from: proto.get_reporter_valid_from(),
+            reporter_valid_until: proto.get_reporter_valid_until(),
+            reporter_constraints: proto
+                .get_reporter_constraints()
+                .to_vec()
+                .into_iter()
+                .map(ReporterConstraints::from_proto)
+                .collect::<Result<Vec<ReporterConstraints>, ProtoConversionError>>()?,
         })
     }
 }
@@ -974,6 +1260,17 @@ impl FromNative<Proposal> for track_and_trace_state::Proposal {
         proto.set_properties(RepeatedField::from_vec(native.properties().to_vec()));
         proto.set_status(native.status().clone().into_proto()?);
         proto.set_terms(native.terms().to_string());
+        proto.set_expires_at(*native.expires_at());
+        proto.set_reporter_valid_from(*native.reporter_valid_from());
+        proto.set_reporter_valid_until(*native.reporter_valid_until());
+        proto.set_reporter_constraints(RepeatedField::from_vec(
+            native
+                .reporter_constraints()
+                .to_vec()
+                .into_iter()
+                .map(ReporterConstraints::into_proto)
+                .collect::<Result<Vec<track_and_trace_state::ReporterConstraints>, ProtoConversionError>>()?,
+        ));
 
         Ok(proto)
     }
@@ -1193,6 +1490,138 @@ impl IntoBytes for AssociatedAgent {
 impl IntoProto<track_and_trace_state::Record_AssociatedAgent> for AssociatedAgent {}
 impl IntoNative<AssociatedAgent> for track_and_trace_state::Record_AssociatedAgent {}
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Aggregation {
+    parent_record_id: String,
+    child_record_id: String,
+    aggregated_at: u64,
+    disaggregated_at: u64,
+}
+
+impl Aggregation {
+    pub fn parent_record_id(&self) -> &str {
+        &self.parent_record_id
+    }
+    pub fn child_record_id(&self) -> &str {
+        &self.child_record_id
+    }
+    pub fn aggregated_at(&self) -> &u64 {
+        &self.aggregated_at
+    }
+    pub fn disaggregated_at(&self) -> &u64 {
+        &self.disaggregated_at
+    }
+    /// Whether the child is still aggregated into the parent
+    pub fn is_current(&self) -> bool {
+        self.disaggregated_at == 0
+    }
+
+    pub fn into_builder(self) -> AggregationBuilder {
+        AggregationBuilder::new()
+            .with_parent_record_id(self.parent_record_id)
+            .with_child_record_id(self.child_record_id)
+            .with_aggregated_at(self.aggregated_at)
+            .with_disaggregated_at(self.disaggregated_at)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AggregationBuilder {
+    parent_record_id: Option<String>,
+    child_record_id: Option<String>,
+    aggregated_at: Option<u64>,
+    disaggregated_at: Option<u64>,
+}
+
+impl AggregationBuilder {
+    pub fn new() -> Self {
+        AggregationBuilder::default()
+    }
+    pub fn with_parent_record_id(mut self, value: String) -> Self {
+        self.parent_record_id = Some(value);
+        self
+    }
+    pub fn with_child_record_id(mut self, value: String) -> Self {
+        self.child_record_id = Some(value);
+        self
+    }
+    pub fn with_aggregated_at(mut self, value: u64) -> Self {
+        self.aggregated_at = Some(value);
+        self
+    }
+    pub fn with_disaggregated_at(mut self, value: u64) -> Self {
+        self.disaggregated_at = Some(value);
+        self
+    }
+    pub fn build(self) -> Result<Aggregation, BuilderError> {
+        let parent_record_id = self
+            .parent_record_id
+            .ok_or_else(|| BuilderError::MissingField("parent_record_id".into()))?;
+        let child_record_id = self
+            .child_record_id
+            .ok_or_else(|| BuilderError::MissingField("child_record_id".into()))?;
+        let aggregated_at = self
+            .aggregated_at
+            .ok_or_else(|| BuilderError::MissingField("aggregated_at".into()))?;
+        let disaggregated_at = self.disaggregated_at.unwrap_or_default();
+        Ok(Aggregation {
+            parent_record_id,
+            child_record_id,
+            aggregated_at,
+            disaggregated_at,
+        })
+    }
+}
+
+impl FromProto<track_and_trace_state::Record_Aggregation> for Aggregation {
+    fn from_proto(
+        proto: track_and_trace_state::Record_Aggregation,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(Aggregation {
+            parent_record_id: proto.get_parent_record_id().to_string(),
+            child_record_id: proto.get_child_record_id().to_string(),
+            aggregated_at: proto.get_aggregated_at(),
+            disaggregated_at: proto.get_disaggregated_at(),
+        })
+    }
+}
+
+impl FromNative<Aggregation> for track_and_trace_state::Record_Aggregation {
+    fn from_native(native: Aggregation) -> Result<Self, ProtoConversionError> {
+        let mut proto = track_and_trace_state::Record_Aggregation::new();
+
+        proto.set_parent_record_id(native.parent_record_id().to_string());
+        proto.set_child_record_id(native.child_record_id().to_string());
+        proto.set_aggregated_at(*native.aggregated_at());
+        proto.set_disaggregated_at(*native.disaggregated_at());
+
+        Ok(proto)
+    }
+}
+
+impl FromBytes<Aggregation> for Aggregation {
+    fn from_bytes(bytes: &[u8]) -> Result<Aggregation, ProtoConversionError> {
+        let proto: track_and_trace_state::Record_Aggregation = protobuf::parse_from_bytes(bytes)
+            .map_err(|_| {
+                ProtoConversionError::SerializationError(
+                    "Unable to get Aggregation from bytes".into(),
+                )
+            })?;
+        proto.into_native()
+    }
+}
+impl IntoBytes for Aggregation {
+    fn into_bytes(self) -> Result<Vec<u8>, ProtoConversionError> {
+        let proto = self.into_proto()?;
+        let bytes = proto.write_to_bytes().map_err(|_| {
+            ProtoConversionError::SerializationError("Unable to get Aggregation from bytes".into())
+        })?;
+        Ok(bytes)
+    }
+}
+impl IntoProto<track_and_trace_state::Record_Aggregation> for Aggregation {}
+impl IntoNative<Aggregation> for track_and_trace_state::Record_Aggregation {}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Record {
     record_id: String,
@@ -1200,6 +1629,15 @@ pub struct Record {
     owners: Vec<AssociatedAgent>,
     custodians: Vec<AssociatedAgent>,
     field_final: bool,
+    schema_version: u32,
+    aggregations: Vec<Aggregation>,
+    last_timestamp: u64,
+    archived: bool,
+    reopen_approvals: Vec<String>,
+    product_id: String,
+    location_id: String,
+    product_namespace: ProductNamespace,
+    product_schema_name: String,
 }
 
 impl Record {
@@ -1218,6 +1656,56 @@ impl Record {
     pub fn field_final(&self) -> &bool {
         &self.field_final
     }
+    pub fn schema_version(&self) -> &u32 {
+        &self.schema_version
+    }
+    pub fn aggregations(&self) -> &[Aggregation] {
+        &self.aggregations
+    }
+    pub fn last_timestamp(&self) -> &u64 {
+        &self.last_timestamp
+    }
+    pub fn archived(&self) -> &bool {
+        &self.archived
+    }
+    pub fn reopen_approvals(&self) -> &[String] {
+        &self.reopen_approvals
+    }
+    /// The ID of the product the record tracks, or an empty string if none
+    pub fn product_id(&self) -> &str {
+        &self.product_id
+    }
+    /// The GLN of the location the record is currently at, or an empty string if unknown
+    pub fn location_id(&self) -> &str {
+        &self.location_id
+    }
+    /// The namespace of the product the record tracks
+    pub fn product_namespace(&self) -> &ProductNamespace {
+        &self.product_namespace
+    }
+    /// The schema of the custom product the record tracks, or an empty string for a GS1 product
+    pub fn product_schema_name(&self) -> &str {
+        &self.product_schema_name
+    }
+    /// The record this record is currently aggregated into, if any
+    pub fn parent_record_id(&self) -> Option<&str> {
+        self.aggregations
+            .iter()
+            .find(|aggregation| {
+                aggregation.is_current() && aggregation.child_record_id() == self.record_id
+            })
+            .map(|aggregation| aggregation.parent_record_id())
+    }
+    /// The records currently aggregated into this record
+    pub fn child_record_ids(&self) -> Vec<&str> {
+        self.aggregations
+            .iter()
+            .filter(|aggregation| {
+                aggregation.is_current() && aggregation.parent_record_id() == self.record_id
+            })
+            .map(|aggregation| aggregation.child_record_id())
+            .collect()
+    }
     pub fn into_builder(self) -> RecordBuilder {
         RecordBuilder::new()
             .with_record_id(self.record_id)
@@ -1225,6 +1713,15 @@ impl Record {
             .with_owners(self.owners)
             .with_custodians(self.custodians)
             .with_field_final(self.field_final)
+            .with_schema_version(self.schema_version)
+            .with_aggregations(self.aggregations)
+            .with_last_timestamp(self.last_timestamp)
+            .with_archived(self.archived)
+            .with_reopen_approvals(self.reopen_approvals)
+            .with_product_id(self.product_id)
+            .with_location_id(self.location_id)
+            .with_product_namespace(self.product_namespace)
+            .with_product_schema_name(self.product_schema_name)
     }
 }
 
@@ -1235,6 +1732,15 @@ pub struct RecordBuilder {
     owners: Option<Vec<AssociatedAgent>>,
     custodians: Option<Vec<AssociatedAgent>>,
     field_final: Option<bool>,
+    schema_version: Option<u32>,
+    aggregations: Option<Vec<Aggregation>>,
+    last_timestamp: Option<u64>,
+    archived: Option<bool>,
+    reopen_approvals: Option<Vec<String>>,
+    product_id: Option<String>,
+    location_id: Option<String>,
+    product_namespace: Option<ProductNamespace>,
+    product_schema_name: Option<String>,
 }
 
 impl RecordBuilder {
@@ -1261,6 +1767,42 @@ impl RecordBuilder {
         self.field_final = Some(value);
         self
     }
+    pub fn with_schema_version(mut self, value: u32) -> Self {
+        self.schema_version = Some(value);
+        self
+    }
+    pub fn with_aggregations(mut self, value: Vec<Aggregation>) -> Self {
+        self.aggregations = Some(value);
+        self
+    }
+    pub fn with_last_timestamp(mut self, value: u64) -> Self {
+        self.last_timestamp = Some(value);
+        self
+    }
+    pub fn with_archived(mut self, value: bool) -> Self {
+        self.archived = Some(value);
+        self
+    }
+    pub fn with_reopen_approvals(mut self, value: Vec<String>) -> Self {
+        self.reopen_approvals = Some(value);
+        self
+    }
+    pub fn with_product_id(mut self, value: String) -> Self {
+        self.product_id = Some(value);
+        self
+    }
+    pub fn with_location_id(mut self, value: String) -> Self {
+        self.location_id = Some(value);
+        self
+    }
+    pub fn with_product_namespace(mut self, value: ProductNamespace) -> Self {
+        self.product_namespace = Some(value);
+        self
+    }
+    pub fn with_product_schema_name(mut self, value: String) -> Self {
+        self.product_schema_name = Some(value);
+        self
+    }
     pub fn build(self) -> Result<Record, BuilderError> {
         let record_id = self
             .record_id
@@ -1277,12 +1819,31 @@ impl RecordBuilder {
         let field_final = self
             .field_final
             .ok_or_else(|| BuilderError::MissingField("field_final".into()))?;
+        // Records created before schemas were versioned have no schema version
+        let schema_version = self.schema_version.unwrap_or_default();
+        let aggregations = self.aggregations.unwrap_or_default();
+        let last_timestamp = self.last_timestamp.unwrap_or_default();
+        let archived = self.archived.unwrap_or_default();
+        let reopen_approvals = self.reopen_approvals.unwrap_or_default();
+        let product_id = self.product_id.unwrap_or_default();
+        let location_id = self.location_id.unwrap_or_default();
+        let product_namespace = self.product_namespace.unwrap_or_default();
+        let product_schema_name = self.product_schema_name.unwrap_or_default();
         Ok(Record {
             record_id,
             schema,
             owners,
             custodians,
             field_final,
+            schema_version,
+            aggregations,
+            last_timestamp,
+            archived,
+            reopen_approvals,
+            product_id,
+            location_id,
+            product_namespace,
+            product_schema_name,
         })
     }
 }
@@ -1305,10 +1866,40 @@ impl FromProto<track_and_trace_state::Record> for Record {
                 .map(AssociatedAgent::from_proto)
                 .collect::<Result<Vec<AssociatedAgent>, ProtoConversionError>>()?,
             field_final: proto.get_field_final(),
+            schema_version: proto.get_schema_version(),
+            aggregations: proto
+                .get_aggregations()
+                .to_vec()
+                .into_iter()
+                .map(Aggregation::from_proto)
+                .collect::<Result<Vec<Aggregation>, ProtoConversionError>>()?,
+            last_timestamp: proto.get_last_timestamp(),
+            archived: proto.get_archived(),
+            reopen_approvals: proto
+                .get_reopen_approvals()
+                .to_vec()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            product_id: proto.get_product_id().to_string(),
+            location_id: proto.get_location_id().to_string(),
+            product_namespace: product_namespace_from_proto(proto.get_product_namespace())?,
+            product_schema_name: proto.get_product_schema_name().to_string(),
         })
     }
 }
 
+/// Converts the namespace of a product reference. References made before products had
+/// namespaces leave it unset, and are to GS1 products.
+pub(crate) fn product_namespace_from_proto(
+    namespace: Product_ProductNamespace,
+) -> Result<ProductNamespace, ProtoConversionError> {
+    match namespace {
+        Product_ProductNamespace::UNSET_TYPE => Ok(ProductNamespace::GS1),
+        namespace => ProductNamespace::from_proto(namespace),
+    }
+}
+
 impl FromNative<Record> for track_and_trace_state::Record {
     fn from_native(native: Record) -> Result<Self, ProtoConversionError> {
         let mut proto = track_and_trace_state::Record::new();
@@ -1341,6 +1932,22 @@ impl FromNative<Record> for track_and_trace_state::Record {
             ),
         );
         proto.set_field_final(*native.field_final());
+        proto.set_schema_version(*native.schema_version());
+        proto.set_aggregations(RepeatedField::from_vec(
+            native
+                .aggregations()
+                .to_vec()
+                .into_iter()
+                .map(Aggregation::into_proto)
+                .collect::<Result<Vec<track_and_trace_state::Record_Aggregation>, ProtoConversionError>>()?,
+        ));
+        proto.set_last_timestamp(*native.last_timestamp());
+        proto.set_archived(*native.archived());
+        proto.set_reopen_approvals(RepeatedField::from_vec(native.reopen_approvals().to_vec()));
+        proto.set_product_id(native.product_id().to_string());
+        proto.set_location_id(native.location_id().to_string());
+        proto.set_product_namespace(native.product_namespace().clone().into_proto()?);
+        proto.set_product_schema_name(native.product_schema_name().to_string());
 
         Ok(proto)
     }
@@ -1502,6 +2109,59 @@ mod tests {
         assert_eq!(builder.public_key, Some("1234".to_string()));
         assert_eq!(builder.authorized, Some(true));
         assert_eq!(builder.index, Some(0));
+        assert_eq!(builder.valid_from, Some(0));
+        assert_eq!(builder.valid_until, Some(0));
+        assert_eq!(builder.constraints, None);
+    }
+
+    #[test]
+    // check that a reporter is only active while authorized and within its validity window
+    fn test_reporter_is_active() {
+        let reporter = ReporterBuilder::new()
+            .with_public_key("1234".to_string())
+            .with_authorized(true)
+            .with_index(0)
+            .with_valid_from(100)
+            .with_valid_until(200)
+            .build()
+            .unwrap();
+
+        assert!(!reporter.is_active(99));
+        assert!(reporter.is_active(100));
+        assert!(reporter.is_active(200));
+        assert!(!reporter.is_active(201));
+
+        let revoked = reporter
+            .into_builder()
+            .with_authorized(false)
+            .build()
+            .unwrap();
+        assert!(!revoked.is_active(150));
+    }
+
+    #[test]
+    // check that the value range of reporter constraints is inclusive and may be one-sided
+    fn test_reporter_constraints_allows_value() {
+        let constraints = ReporterConstraintsBuilder::new()
+            .with_property_name("temperature".to_string())
+            .with_min_value(-40)
+            .with_max_value(60)
+            .build()
+            .unwrap();
+
+        assert!(constraints.allows_value(-40));
+        assert!(constraints.allows_value(60));
+        assert!(!constraints.allows_value(-41));
+        assert!(!constraints.allows_value(61));
+
+        let upper_bound_only = ReporterConstraintsBuilder::new()
+            .with_property_name("temperature".to_string())
+            .with_max_value(60)
+            .build()
+            .unwrap();
+
+        assert!(upper_bound_only.allows_value(i64::min_value()));
+        assert!(!upper_bound_only.allows_value(61));
     }
 
     #[test]
@@ -1515,6 +2175,25 @@ mod tests {
             .unwrap();
 
         test_from_bytes(original, Reporter::from_bytes);
+
+        let constraints = ReporterConstraintsBuilder::new()
+            .with_property_name("temperature".to_string())
+            .with_min_report_interval(60)
+            .with_min_value(-40)
+            .build()
+            .unwrap();
+
+        let constrained = ReporterBuilder::new()
+            .with_public_key("1234".to_string())
+            .with_authorized(true)
+            .with_index(0)
+            .with_valid_until(200)
+            .with_constraints(constraints)
+            .with_last_reported_at(150)
+            .build()
+            .unwrap();
+
+        test_from_bytes(constrained, Reporter::from_bytes);
     }
 
     #[test]
@@ -1933,6 +2612,7 @@ mod tests {
         assert!(proposal.properties().iter().any(|x| x == "wet"));
         assert_eq!(*proposal.status(), Status::Open);
         assert_eq!(proposal.terms(), "a term");
+        assert_eq!(*proposal.expires_at(), 0);
     }
 
     #[test]
@@ -1959,6 +2639,32 @@ mod tests {
         assert_eq!(builder.properties, Some(vec!["wet".to_string()]));
         assert_eq!(builder.status, Some(Status::Open));
         assert_eq!(builder.terms, Some("a term".to_string()));
+        assert_eq!(builder.expires_at, Some(0));
+    }
+
+    #[test]
+    // check that a proposal only expires once its expiry has passed
+    fn test_proposal_expiry() {
+        let never_expires = ProposalBuilder::new()
+            .with_record_id("egg1234".into())
+            .with_timestamp(214)
+            .with_issuing_agent("james".into())
+            .with_receiving_agent("joe".into())
+            .with_role(Role::Owner)
+            .with_properties(vec![])
+            .with_status(Status::Open)
+            .with_terms("a term".into())
+            .build()
+            .unwrap();
+        assert!(!never_expires.is_expired(u64::max_value()));
+
+        let expires = never_expires
+            .into_builder()
+            .with_expires_at(300)
+            .build()
+            .unwrap();
+        assert!(!expires.is_expired(300));
+        assert!(expires.is_expired(301));
     }
 
     #[test]
@@ -2094,6 +2800,15 @@ mod tests {
         assert_eq!(builder.owners, Some(vec![associated_agent.clone()]));
         assert_eq!(builder.custodians, Some(vec![associated_agent.clone()]));
         assert_eq!(builder.field_final, Some(false));
+        assert_eq!(builder.schema_version, Some(0));
+        assert_eq!(builder.aggregations, Some(vec![]));
+        assert_eq!(builder.last_timestamp, Some(0));
+        assert_eq!(builder.archived, Some(false));
+        assert_eq!(builder.reopen_approvals, Some(vec![]));
+        assert_eq!(builder.product_id, Some(String::new()));
+        assert_eq!(builder.location_id, Some(String::new()));
+        assert_eq!(builder.product_namespace, Some(ProductNamespace::GS1));
+        assert_eq!(builder.product_schema_name, Some(String::new()));
     }
 
     #[test]
@@ -2109,10 +2824,61 @@ mod tests {
             .with_schema("egg".into())
             .with_owners(vec![associated_agent.clone()])
             .with_custodians(vec![associated_agent.clone()])
+            .with_field_final(true)
+            .with_schema_version(2)
+            .with_archived(true)
+            .with_reopen_approvals(vec!["agent1234".into()])
+            .with_product_id("LB-0001".into())
+            .with_location_id("9012345000004".into())
+            .with_product_namespace(ProductNamespace::Custom)
+            .with_product_schema_name("lumber".into())
+            .build()
+            .unwrap();
+
+        test_from_bytes(record, Record::from_bytes);
+    }
+
+    #[test]
+    fn test_record_aggregations() {
+        let associated_agent = AssociatedAgentBuilder::new()
+            .with_agent_id("agent1234".into())
+            .with_timestamp(2132)
+            .build()
+            .unwrap();
+
+        let into_pallet = AggregationBuilder::new()
+            .with_parent_record_id("pallet1".into())
+            .with_child_record_id("case1".into())
+            .with_aggregated_at(1)
+            .with_disaggregated_at(2)
+            .build()
+            .unwrap();
+        let into_other_pallet = AggregationBuilder::new()
+            .with_parent_record_id("pallet2".into())
+            .with_child_record_id("case1".into())
+            .with_aggregated_at(3)
+            .build()
+            .unwrap();
+        let holding_can = AggregationBuilder::new()
+            .with_parent_record_id("case1".into())
+            .with_child_record_id("can1".into())
+            .with_aggregated_at(1)
+            .build()
+            .unwrap();
+
+        let record = RecordBuilder::new()
+            .with_record_id("case1".into())
+            .with_schema("case".into())
+            .with_owners(vec![associated_agent.clone()])
+            .with_custodians(vec![associated_agent])
             .with_field_final(false)
+            .with_aggregations(vec![into_pallet, holding_can, into_other_pallet])
             .build()
             .unwrap();
 
+        assert_eq!(record.parent_record_id(), Some("pallet2"));
+        assert_eq!(record.child_record_ids(), vec!["can1"]);
+
         test_from_bytes(record, Record::from_bytes);
     }
 